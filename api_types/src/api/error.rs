@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Error payload returned by the backend for non-successful responses
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ApiErrorResponse {
+    /// Machine readable error code
+    pub code: ApiErrorCode,
+    /// Human readable error message
+    pub message: String,
+    /// Names of the request fields that failed validation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+    /// Seconds to wait before the request may be sent again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Machine readable error codes returned by the backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApiErrorCode {
+    /// The request contains invalid fields
+    ValidationError,
+    /// The requested resource does not exist
+    NotFound,
+    /// The request conflicts with the current state of the resource
+    Conflict,
+    /// Too many requests were sent
+    RateLimited,
+    /// The user needs to be KYC verified
+    KycRequired,
+    /// The request exceeds a limit of the user
+    InsufficientLimit,
+    /// Any error code not known to this version
+    #[serde(other)]
+    Unknown,
+}
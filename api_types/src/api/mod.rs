@@ -2,6 +2,7 @@
 //! Basically from the handlers.
 
 pub mod dlt;
+pub mod error;
 pub mod generic;
 pub mod kyc;
 pub mod networks;
@@ -3,8 +3,20 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum ApiNetworkType {
-    Evm { node_urls: Vec<String>, chain_id: u64 },
-    Stardust { node_urls: Vec<String> },
+    Evm {
+        node_urls: Vec<String>,
+        chain_id: u64,
+    },
+    EvmErc20 {
+        node_urls: Vec<String>,
+        chain_id: u64,
+        contract_address: String,
+        token_symbol: String,
+        token_decimals: u8,
+    },
+    Stardust {
+        node_urls: Vec<String>,
+    },
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -13,3 +13,60 @@ pub struct PutShareRequest {
 pub struct GetShareResponse {
     pub share: String,
 }
+
+/// A share of the user encrypted for one of their guardians
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GuardianShare {
+    /// Username of the guardian
+    pub guardian: String,
+    /// Base64 encoded public key of the guardian, the share is encrypted for
+    pub public_key: String,
+    pub share: String,
+}
+
+/// Struct to upload the guardian shares, replacing any existing ones
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PutGuardianSharesRequest {
+    pub shares: Vec<GuardianShare>,
+}
+
+/// Struct to download the guardian shares
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GetGuardianSharesResponse {
+    pub shares: Vec<GuardianShare>,
+}
+
+/// Struct to start a recovery with the help of the guardians
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct StartGuardianRecoveryRequest {
+    /// Base64 encoded public key the guardians encrypt the approved shares for
+    pub public_key: String,
+}
+
+/// Pending recovery of a user, as downloaded by one of their guardians
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GetGuardianRecoveryResponse {
+    /// The share encrypted for the guardian
+    pub share: String,
+    /// Base64 encoded public key of the recovery
+    pub public_key: String,
+}
+
+/// Struct to approve a recovery with the share encrypted for the public key of the recovery
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PutGuardianApprovalRequest {
+    pub share: String,
+}
+
+/// Struct to download the shares approved by the guardians
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GetGuardianApprovalsResponse {
+    pub shares: Vec<String>,
+}
@@ -15,14 +15,18 @@
 //! Overall, this file serves as the bridge between the Java code and the ETOPay SDK, enabling seamless integration of the SDK into Android applications.
 
 mod type_conversions;
+mod user_storage;
 
 #[cfg(feature = "viviswap-kyc")]
 use sdk::types::File;
 
 use once_cell::sync::OnceCell;
-use sdk::core::Sdk;
+use sdk::core::{watcher::TransactionStatusStream, Sdk};
 use std::sync::Arc;
-use tokio::{runtime::Runtime, sync::RwLock};
+use tokio::{
+    runtime::Runtime,
+    sync::{Mutex, RwLock},
+};
 
 #[doc = r"Sdk handle with atomic reference count and read write lock"]
 type SdkWrapper = Arc<RwLock<Sdk>>;
@@ -63,17 +67,35 @@ fn get_or_init_sdk() -> &'static SdkWrapper {
     sdk
 }
 
+/// Returns or creates the stream of the status changes of watched transactions of the sdk object.
+/// The stream is subscribed on first use, earlier changes are not received.
+async fn transaction_status_stream() -> &'static Mutex<TransactionStatusStream> {
+    static CELL: tokio::sync::OnceCell<Mutex<TransactionStatusStream>> = tokio::sync::OnceCell::const_new();
+    CELL.get_or_init(|| async { Mutex::new(get_or_init_sdk().read().await.subscribe_transaction_status()) })
+        .await
+}
+
+/// Formats an error as message of the exception thrown to the Java code: a JSON object with a stable
+/// error code to branch on, see [`sdk::Error::to_binding_string`].
+fn error_string(error: impl Into<sdk::Error>) -> String {
+    error.into().to_binding_string()
+}
+
 /// Main object that contains all the functionality for interfacing with the ETOPaySdk.
 #[jnigen_macro::generate("com.etospheres.etopay.ETOPaySdk")]
 mod ffi {
     use super::*;
     use sdk::{
         core::Config,
-        share::Share,
+        mnemonic::MnemonicOptions,
+        share::{Guardian, Share},
         types::{
             currencies::CryptoAmount,
-            newtypes::{AccessToken, EncryptionPin, PlainPassword},
+            newtypes::{AccessToken, EncryptionPin, MnemonicPassphrase, PlainPassword},
+            transactions::{PendingTransaction, UnsignedTransaction},
         },
+        user::{callback_storage::CallbackUserStorage, encryption::UserStorageEncryption, error::UserKvStorageError},
+        watch_only::WatchOnlySource,
         WalletError,
     };
     use type_conversions::PurchaseDetailsEntity;
@@ -98,7 +120,38 @@ mod ffi {
             let mut sdk = get_or_init_sdk().write().await;
             sdk.set_config(Config::from_json(&config)?)
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Store the users in a storage of the application instead of the file in the storage path. The users are
+    /// stored as encoded records under keys with the prefix etopay.local.user. Call this before setConfig and
+    /// initUser, the active user is logged out and the users of the previous storage are not moved.
+    ///
+    /// @param storage An object of the application with the methods String getItem(String key) returning null
+    ///                for a missing key, void setItem(String key, String value), void removeItem(String key) and
+    ///                String[] keys(). The methods are called from a background thread of the SDK and report
+    ///                errors by throwing an exception.
+    pub fn setUserStorage(storage: GlobalRef) -> Result<(), String> {
+        let vm = env
+            .get_java_vm()
+            .map_err(|e| error_string(UserKvStorageError::Storage(e.to_string())))?;
+        runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.set_user_storage(CallbackUserStorage::new(user_storage::JavaUserStorageCallbacks::new(
+                vm, storage,
+            )));
+        });
+        Ok(())
+    }
+
+    /// Encrypt the users stored by the SDK with a key derived from the pin. A stored user is locked after
+    /// initUser until the pin is entered, e.g. with verifyPin.
+    pub fn enableUserStorageEncryptionWithPin() -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.set_user_storage_encryption(UserStorageEncryption::Pin)
+        });
+        result.map_err(error_string)
     }
 
     /// Fetch available currencies and corresponding node urls.
@@ -111,8 +164,8 @@ mod ffi {
         });
 
         match result {
-            Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-            Err(e) => Err(format!("{e:#?}")),
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
         }
     }
 
@@ -124,7 +177,7 @@ mod ffi {
             let mut sdk = get_or_init_sdk().write().await;
             sdk.set_network(network_id).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Destructor for the SDK handle
@@ -145,7 +198,7 @@ mod ffi {
             let mut sdk = get_or_init_sdk().write().await;
             sdk.create_new_user(&username).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Initializes an existing user in the SDK
@@ -157,7 +210,87 @@ mod ffi {
             sdk.init_user(&username).await
         });
 
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Lists the users stored on this device
+    ///
+    /// @return Serialized JSON array of the users sorted by username, with the creation time, the KYC type,
+    /// if the user has a wallet and the time the user was last used.
+    pub fn listUsers() -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let sdk = get_or_init_sdk().write().await;
+            sdk.list_users().await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Logs out the active user and drops its cached wallet state and access token
+    pub fn logout() -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.logout().await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Logs out the active user and initializes another user stored on this device
+    ///
+    /// @param username The input string representing the username.
+    /// @param access_token The access token of the user. Empty to set it later with `refreshAccessToken`.
+    pub fn switchUser(username: String, access_token: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+
+            let access_token = if access_token.is_empty() {
+                None
+            } else {
+                Some(AccessToken::try_from(access_token)?)
+            };
+            sdk.switch_user(&username, access_token).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Exports the locally stored record of the active user, encrypted with the export password
+    ///
+    /// @param export_password The password to encrypt the export with.
+    /// @param pin The wallet pin for confirmation. Optional in case there is no wallet.
+    ///
+    /// @return The bytes of the exported user
+    pub fn exportUser(export_password: String, pin: Option<String>) -> Result<Vec<u8>, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let export_password = PlainPassword::try_from_string(export_password)?;
+            let encryption_pin = match pin {
+                Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                None => None,
+            };
+            sdk.export_user(encryption_pin.as_ref(), &export_password).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Imports a user exported with `exportUser`
+    ///
+    /// @param username The username of the exported user.
+    /// @param export The bytes of the exported user.
+    /// @param export_password The password the user was exported with.
+    pub fn importUser(username: String, export: Vec<u8>, export_password: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let export_password = PlainPassword::try_from_string(export_password)?;
+            sdk.import_user(&username, &export, &export_password).await
+        });
+
+        result.map_err(error_string)
     }
 
     /// Refreshes the access token for the user in the SDK.
@@ -174,7 +307,7 @@ mod ffi {
             };
             sdk.refresh_access_token(access_token).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Fetches the kyc verification status for the user
@@ -188,7 +321,7 @@ mod ffi {
             sdk.is_kyc_status_verified(&username).await
         });
 
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Verifies the mnemonic for the wallet
@@ -203,20 +336,73 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.verify_mnemonic(&pin, &mnemonic).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+    /// Verifies the mnemonic and the BIP-39 passphrase for the wallet
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param mnemonic The input string representing the mnemonic
+    /// @param passphrase The BIP-39 passphrase of the wallet
+    ///
+    /// @return a boolean indicating if the mnemonic and passphrase are correct or not
+    pub fn verifyMnemonicWithPassphrase(pin: String, mnemonic: String, passphrase: String) -> Result<bool, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.verify_mnemonic_with_passphrase(&pin, &mnemonic, &passphrase).await
+        });
+        result.map_err(error_string)
     }
     /// Creates a new wallet and sets the pin and password
     ///
     /// @param pin The input string representing the pin.
+    /// @param word_count The number of words of the mnemonic: 12, 15, 18, 21 or 24, or 0 for the default of 24
+    /// @param language The wordlist language of the mnemonic, e.g. `english`, `french` or `japanese`, or null
+    ///        for English
     ///
     /// @return The mnemonic of the created wallet as a string
-    pub fn createNewWallet(pin: String) -> Result<String, String> {
+    pub fn createNewWallet(pin: String, word_count: i64, language: Option<String>) -> Result<String, String> {
+        let word_count = match word_count {
+            0 => None,
+            count => Some(u8::try_from(count).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))?),
+        };
         let result = runtime().block_on(async move {
             let mut sdk = get_or_init_sdk().write().await;
             let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.create_wallet_from_new_mnemonic(&pin).await
+            let options = MnemonicOptions::try_from_parts(word_count, language.as_deref())?;
+            sdk.create_wallet_from_new_mnemonic(&pin, &options).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+    /// Creates a new wallet with a BIP-39 passphrase (25th word). The passphrase is stored encrypted on this
+    /// device and is never part of the wallet shares.
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param passphrase The BIP-39 passphrase of the wallet
+    /// @param word_count The number of words of the mnemonic: 12, 15, 18, 21 or 24, or 0 for the default of 24
+    /// @param language The wordlist language of the mnemonic, or null for English
+    ///
+    /// @return The mnemonic of the created wallet as a string
+    pub fn createNewWalletWithPassphrase(
+        pin: String,
+        passphrase: String,
+        word_count: i64,
+        language: Option<String>,
+    ) -> Result<String, String> {
+        let word_count = match word_count {
+            0 => None,
+            count => Some(u8::try_from(count).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))?),
+        };
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            let options = MnemonicOptions::try_from_parts(word_count, language.as_deref())?;
+            sdk.create_wallet_from_new_mnemonic_with_passphrase(&pin, &passphrase, &options)
+                .await
+        });
+        result.map_err(error_string)
     }
     /// Creates/migrates a wallet from an existing mnemonic
     ///
@@ -228,7 +414,41 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.create_wallet_from_existing_mnemonic(&pin, &mnemonic).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+    /// Creates/migrates a wallet from an existing mnemonic and BIP-39 passphrase (25th word). The passphrase is
+    /// stored encrypted on this device and is never part of the wallet shares.
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param mnemonic The input string representing the mnemonic
+    /// @param passphrase The BIP-39 passphrase used with the mnemonic
+    pub fn createWalletFromMnemonicWithPassphrase(
+        pin: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.create_wallet_from_existing_mnemonic_with_passphrase(&pin, &mnemonic, &passphrase)
+                .await
+        });
+        result.map_err(error_string)
+    }
+    /// Sets the BIP-39 passphrase of the wallet on this device, e.g. after restoring a wallet which uses a
+    /// passphrase on a new device.
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param passphrase The BIP-39 passphrase of the wallet
+    pub fn setWalletPassphrase(pin: String, passphrase: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.set_wallet_passphrase(&pin, &passphrase).await
+        });
+        result.map_err(error_string)
     }
 
     /// Creates a wallet from a previously created backup
@@ -244,7 +464,7 @@ mod ffi {
             sdk.create_wallet_from_backup(&pin, &backup, &backup_password).await
         });
 
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
     /// Creates a wallet backup file for the existing wallet and encrypts it with the password
     ///
@@ -261,7 +481,26 @@ mod ffi {
             sdk.create_wallet_backup(&pin, &backup_password).await
         });
 
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Creates a versioned JSON wallet backup for the existing wallet and encrypts it with the password.
+    /// In addition to the mnemonic, it contains the network ids, the preferred network, the derived address
+    /// indices and the labels of the additional shares.
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param backup_password The input string representing the password to be used to encrypt the backup
+    ///
+    /// @return The bytes of the backup file
+    pub fn createJsonWalletBackup(pin: String, backup_password: String) -> Result<Vec<u8>, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let backup_password = PlainPassword::try_from_string(backup_password)?;
+            sdk.create_json_wallet_backup(&pin, &backup_password).await
+        });
+
+        result.map_err(error_string)
     }
 
     /// Deletes the local wallet and associated files
@@ -273,7 +512,7 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.delete_wallet(&pin).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Generate a new receiver address based on selected currency in the config.
@@ -287,7 +526,7 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.generate_new_address(&pin).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Fetches the current balance of the base crypto currency on the wallet
@@ -300,7 +539,146 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.get_balance(&pin).await.and_then(f64::try_from)
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Derives a new address of the EVM wallet with the next unused address index.
+    ///
+    /// @param pin The input string representing the pin.
+    /// @return The newly derived address as String.
+    #[public_name = "deriveNewAddress"]
+    pub fn deriveNewAddress(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.derive_new_address(&pin).await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Fetches all addresses of the wallet in use, starting with the default address.
+    ///
+    /// @param pin The input string representing the pin.
+    /// @return Serialized JSON array of the addresses ordered by their address index.
+    #[public_name = "getWalletAddresses"]
+    pub fn getWalletAddresses(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.get_wallet_addresses(&pin).await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Fetches the current balance of a single address of the wallet
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param address One of the addresses returned by {@link #getWalletAddresses}.
+    /// @return The current balance as a double precision floating point number
+    #[public_name = "getAddressBalance"]
+    pub fn getAddressBalance(pin: String, address: String) -> Result<f64, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.get_address_balance(&pin, &address).await.and_then(f64::try_from)
+        });
+        result.map_err(error_string)
+    }
+
+    /// Sets the EVM addresses watched by the watch-only wallet
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param addresses Serialized JSON array of the addresses, the first one is used as the default address.
+    #[public_name = "setWatchOnlyAddresses"]
+    pub fn setWatchOnlyAddresses(pin: String, addresses: String) -> Result<(), String> {
+        let addresses: Vec<String> =
+            serde_json::from_str(&addresses).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))?;
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_watch_only_source(&pin, Some(WatchOnlySource::EvmAddresses(addresses)))
+                .await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Sets the extended public key of the EVM account watched by the watch-only wallet
+    ///
+    /// @param pin The input string representing the pin.
+    /// @param xpub The extended public key of the account at the derivation path m/44'/60'/0'.
+    #[public_name = "setWatchOnlyXpub"]
+    pub fn setWatchOnlyXpub(pin: String, xpub: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_watch_only_source(&pin, Some(WatchOnlySource::EvmAccountXpub(xpub)))
+                .await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Enables the watch-only wallet with the current addresses of the EVM wallet
+    ///
+    /// @param pin The input string representing the pin.
+    /// @return Serialized JSON array of the addresses watched by the watch-only wallet.
+    #[public_name = "enableWatchOnly"]
+    pub fn enableWatchOnly(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.enable_watch_only(&pin).await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Fetches the default address of the watch-only wallet
+    ///
+    /// @return The default address as String.
+    #[public_name = "getWatchOnlyAddress"]
+    pub fn getWatchOnlyAddress() -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.get_watch_only_address().await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Fetches the current balance of the watch-only wallet without the pin
+    ///
+    /// @return The current balance as a double precision floating point number
+    #[public_name = "getWatchOnlyBalance"]
+    pub fn getWatchOnlyBalance() -> Result<f64, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.get_watch_only_balance().await.and_then(f64::try_from)
+        });
+        result.map_err(error_string)
+    }
+
+    /// Gets the detailed lists of transactions of the watch-only wallet without the pin
+    ///
+    /// @param start The start page
+    /// @param limit The limit per page
+    ///
+    /// @return The details of the wallet transactions as a serialized JSON string.
+    #[public_name = "getWatchOnlyTransactionList"]
+    pub fn getWatchOnlyTxList(start: i64, limit: i64) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.get_watch_only_wallet_tx_list(start as usize, limit as usize).await
+        });
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
     }
 
     /// Initialize the KYC process for Postident by generating a case id.
@@ -319,8 +697,8 @@ mod ffi {
             });
 
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -339,8 +717,8 @@ mod ffi {
                 sdk.get_kyc_details_for_postident().await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -363,7 +741,7 @@ mod ffi {
                 sdk.update_kyc_status_for_postident(&case_id).await
             });
 
-            result.map_err(|e| format!("{e:#?}"))
+            result.map_err(error_string)
         })
     }
 
@@ -390,7 +768,7 @@ mod ffi {
             sdk.create_purchase_request(&receiver, amount, &product_hash, &app_data, &purchase_type)
                 .await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Fetches the purchase details from the given purchase ID.
@@ -408,9 +786,9 @@ mod ffi {
         match result {
             Ok(value) => {
                 let entity: PurchaseDetailsEntity = value;
-                serde_json::to_string(&entity).map_err(|e| format!("{e:#?}"))
+                serde_json::to_string(&entity).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))
             }
-            Err(e) => Err(format!("{e:#?}")),
+            Err(e) => Err(error_string(e)),
         }
     }
 
@@ -425,7 +803,7 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.confirm_purchase_request(&pin, &purchase_id).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Starts the KYC verification process for viviswap
@@ -442,8 +820,8 @@ mod ffi {
                 sdk.start_kyc_verification_for_viviswap(&mail, terms_accepted).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -459,8 +837,8 @@ mod ffi {
                 sdk.get_kyc_details_for_viviswap().await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -505,8 +883,8 @@ mod ffi {
             });
 
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -519,7 +897,7 @@ mod ffi {
                 let mut sdk = get_or_init_sdk().write().await;
                 sdk.submit_kyc_partially_status_for_viviswap().await
             });
-            result.map_err(|e| format!("{e:#?}"))
+            result.map_err(error_string)
         })
     }
 
@@ -578,7 +956,7 @@ mod ffi {
                 )
                 .await
             });
-            result.map_err(|e| format!("{e:#?}"))
+            result.map_err(error_string)
         })
     }
 
@@ -637,7 +1015,7 @@ mod ffi {
                 )
                 .await
             });
-            result.map_err(|e| format!("{e:#?}"))
+            result.map_err(error_string)
         })
     }
 
@@ -652,8 +1030,8 @@ mod ffi {
             });
 
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -676,7 +1054,7 @@ mod ffi {
                     .await
             });
 
-            result.map_err(|e| format!("{e:#?}"))
+            result.map_err(error_string)
         })
     }
 
@@ -691,8 +1069,8 @@ mod ffi {
             });
 
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -735,7 +1113,7 @@ mod ffi {
                     .await
             });
 
-            result.map_err(|e| format!("{e:#?}"))
+            result.map_err(error_string)
         })
     }
 
@@ -745,11 +1123,11 @@ mod ffi {
     #[public_name = "pinVerify"]
     pub fn verifyPin(pin: String) -> Result<(), String> {
         let result = runtime().block_on(async move {
-            let sdk = get_or_init_sdk().write().await;
+            let mut sdk = get_or_init_sdk().write().await;
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.verify_pin(&pin).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Resets the pin for the wallet using the wallet password
@@ -763,7 +1141,7 @@ mod ffi {
             let new_pin = EncryptionPin::try_from_string(new_pin)?;
             sdk.change_pin(&pin, &new_pin).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Set the password to use for wallet operations. If the password was already set, this changes it.
@@ -778,7 +1156,50 @@ mod ffi {
             let new_password = PlainPassword::try_from_string(new_password)?;
             sdk.set_wallet_password(&pin, &new_password).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Recovers the wallet with the mnemonic, for example after it was locked because of too many wrong pin attempts.
+    /// Sets a new pin and password and replaces the existing wallet shares.
+    ///
+    /// @param new_pin The new pin to be set for the wallet
+    /// @param new_password The new password to be set for the wallet
+    /// @param mnemonic The mnemonic of the wallet
+    #[public_name = "recoverWalletWithMnemonic"]
+    pub fn recoverWalletWithMnemonic(new_pin: String, new_password: String, mnemonic: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let new_pin = EncryptionPin::try_from_string(new_pin)?;
+            let new_password = PlainPassword::try_from_string(new_password)?;
+            sdk.recover_wallet_with_mnemonic(&new_pin, &new_password, &mnemonic)
+                .await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Recovers a wallet which uses a BIP-39 passphrase with the mnemonic and the passphrase, like
+    /// {@link #recoverWalletWithMnemonic}.
+    ///
+    /// @param new_pin The new pin to be set for the wallet
+    /// @param new_password The new password to be set for the wallet
+    /// @param mnemonic The mnemonic of the wallet
+    /// @param passphrase The BIP-39 passphrase of the wallet
+    #[public_name = "recoverWalletWithMnemonicAndPassphrase"]
+    pub fn recoverWalletWithMnemonicAndPassphrase(
+        new_pin: String,
+        new_password: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let new_pin = EncryptionPin::try_from_string(new_pin)?;
+            let new_password = PlainPassword::try_from_string(new_password)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.recover_wallet_with_mnemonic_and_passphrase(&new_pin, &new_password, &mnemonic, &passphrase)
+                .await
+        });
+        result.map_err(error_string)
     }
 
     /// Check if the password to use for wallet operations is set.
@@ -791,7 +1212,7 @@ mod ffi {
             let sdk = get_or_init_sdk().read().await;
             sdk.is_wallet_password_set().await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Sends the given amount to the given address
@@ -809,7 +1230,215 @@ mod ffi {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.send_amount(&pin, &address, amount, data).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Sends the given amount from a specific address of the wallet to the given address
+    ///
+    /// @param pin The pin for verification
+    /// @param sender The address of the wallet to send from, one of {@link #getWalletAddresses}
+    /// @param address The address of the receiver
+    /// @param amount The amount to send in the selected currency
+    /// @param data The data associated with the transaction. Pass NULL to not specify any data.
+    /// @return The transaction id.
+    #[public_name = "sendAmountFrom"]
+    pub fn sendAmountFrom(
+        pin: String,
+        sender: String,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let amount = CryptoAmount::try_from(amount)?;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.send_amount_from(&pin, Some(&sender), &address, amount, data).await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Speeds up a pending EVM transaction by replacing it with the same transaction with higher fees
+    ///
+    /// @param pin The pin for verification
+    /// @param transaction_hash The hash of the pending transaction
+    /// @return The hash of the replacement transaction.
+    #[public_name = "speedUpTransaction"]
+    pub fn speedUpTransaction(pin: String, transaction_hash: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.speed_up_transaction(&pin, &transaction_hash).await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Cancels a pending EVM transaction by replacing it with a transaction without value to the own address
+    ///
+    /// @param pin The pin for verification
+    /// @param transaction_hash The hash of the pending transaction
+    /// @return The hash of the cancel transaction.
+    #[public_name = "cancelTransaction"]
+    pub fn cancelTransaction(pin: String, transaction_hash: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.cancel_transaction(&pin, &transaction_hash).await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Broadcasts all pending EVM transactions of the selected network again, e.g. after the app was restarted
+    ///
+    /// @param pin The pin for verification
+    /// @return Serialized JSON array of the hashes of the transactions that were broadcast.
+    #[public_name = "rebroadcastPendingTransactions"]
+    pub fn rebroadcastPendingTransactions(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.rebroadcast_pending_transactions(&pin).await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Prepares an EVM transaction to be signed on an offline device
+    ///
+    /// @param pin The pin for verification. Pass NULL to use the watch-only wallet.
+    /// @param sender The address of the wallet to send from. Pass NULL to use the default address.
+    /// @param address The address of the receiver
+    /// @param amount The amount to send in the selected currency
+    /// @param data The data associated with the transaction. Pass NULL to not specify any data.
+    /// @return The unsigned transaction as QR string.
+    #[public_name = "prepareUnsignedTransaction"]
+    pub fn prepareUnsignedTransaction(
+        pin: Option<String>,
+        sender: Option<String>,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let amount = CryptoAmount::try_from(amount)?;
+            let pin = match pin {
+                Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                None => None,
+            };
+            sdk.prepare_unsigned_transaction(pin.as_ref(), sender.as_deref(), &address, amount, data)
+                .await
+        });
+        result.map(|unsigned| unsigned.to_qr_string()).map_err(error_string)
+    }
+
+    /// Decodes an EVM transaction prepared by {@link #prepareUnsignedTransaction} to review it before signing
+    ///
+    /// @param pin The pin for verification
+    /// @param unsigned_transaction The unsigned transaction as QR string
+    /// @return Serialized JSON object with the receiver, value, data and fees of the transaction.
+    #[public_name = "reviewUnsignedTransaction"]
+    pub fn reviewUnsignedTransaction(pin: String, unsigned_transaction: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let unsigned = UnsignedTransaction::from_qr_string(&unsigned_transaction)?;
+            sdk.review_unsigned_transaction(&pin, &unsigned).await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Signs an EVM transaction prepared by {@link #prepareUnsignedTransaction} without contacting the network
+    ///
+    /// @param pin The pin for verification
+    /// @param unsigned_transaction The unsigned transaction as QR string
+    /// @return The signed transaction as QR string.
+    #[public_name = "signUnsignedTransaction"]
+    pub fn signUnsignedTransaction(pin: String, unsigned_transaction: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let unsigned = UnsignedTransaction::from_qr_string(&unsigned_transaction)?;
+            sdk.sign_unsigned_transaction(&pin, &unsigned).await
+        });
+        result.map(|signed| signed.to_qr_string()).map_err(error_string)
+    }
+
+    /// Broadcasts an EVM transaction signed by {@link #signUnsignedTransaction} and tracks it as pending
+    ///
+    /// @param pin The pin for verification. Pass NULL to use the watch-only wallet.
+    /// @param signed_transaction The signed transaction as QR string
+    /// @return The transaction hash.
+    #[public_name = "broadcastSignedTransaction"]
+    pub fn broadcastSignedTransaction(pin: Option<String>, signed_transaction: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = match pin {
+                Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                None => None,
+            };
+            let raw_transaction = PendingTransaction::raw_transaction_from_qr_string(&signed_transaction)?;
+            sdk.broadcast_signed_transaction(pin.as_ref(), &raw_transaction).await
+        });
+        result.map_err(error_string)
+    }
+
+    /// Checks the status of the watched transactions of the selected network
+    ///
+    /// @param pin The pin for verification
+    /// @return Serialized JSON array of the watched transactions whose status or number of confirmations changed.
+    #[public_name = "checkWatchedTransactions"]
+    pub fn checkWatchedTransactions(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.check_watched_transactions(&pin).await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Gets the transactions followed by the transaction watcher
+    ///
+    /// @return Serialized JSON array of the watched transactions of all networks with their last known status.
+    #[public_name = "getWatchedTransactions"]
+    pub fn getWatchedTransactions() -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let sdk = get_or_init_sdk().write().await;
+            sdk.get_watched_transactions().await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Waits for the next status change of a watched transaction
+    ///
+    /// Blocks until checkWatchedTransactions finds a watched transaction whose status or number of confirmations
+    /// changed. Call it in a loop on a background thread, e.g. to emit the changes to a Flow. Changes found before
+    /// the first call are not received, and the oldest changes are skipped if they are not received fast enough.
+    ///
+    /// @return Serialized JSON of the updated transaction.
+    #[public_name = "nextTransactionStatus"]
+    pub fn nextTransactionStatus() -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut stream = transaction_status_stream().await.lock().await;
+            stream.recv().await
+        });
+
+        serde_json::to_string(&result).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))
     }
 
     /// Updates the IBAN of the user
@@ -827,8 +1456,8 @@ mod ffi {
                 sdk.update_iban_for_viviswap(&pin, address).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -844,8 +1473,8 @@ mod ffi {
                 sdk.get_iban_for_viviswap().await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -864,8 +1493,8 @@ mod ffi {
                 sdk.create_deposit_with_viviswap(&pin).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -884,8 +1513,8 @@ mod ffi {
                 sdk.create_detail_for_viviswap(&pin).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -913,8 +1542,8 @@ mod ffi {
                 sdk.create_withdrawal_with_viviswap(amount, pin.as_ref(), data).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -931,8 +1560,8 @@ mod ffi {
                 sdk.get_swap_details(order_id).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -950,8 +1579,8 @@ mod ffi {
                 sdk.get_swap_list(start as u32, limit as u32).await
             });
             match result {
-                Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-                Err(e) => Err(format!("{e:#?}")),
+                Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+                Err(e) => Err(error_string(e)),
             }
         })
     }
@@ -969,8 +1598,8 @@ mod ffi {
             sdk.get_tx_list(start as u32, limit as u32).await
         });
         match result {
-            Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-            Err(e) => Err(format!("{e:#?}")),
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
         }
     }
 
@@ -985,7 +1614,7 @@ mod ffi {
                 .await
                 .and_then(|amount| Ok(f64::try_from(amount)?))
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Deletes the user in ETOPay. Hazmat!
@@ -1000,7 +1629,7 @@ mod ffi {
             };
             sdk.delete_user(encryption_pin.as_ref()).await
         });
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Gets the detailed lists of wallet transactions
@@ -1018,8 +1647,8 @@ mod ffi {
             sdk.get_wallet_tx_list(&pin, start as usize, limit as usize).await
         });
         match result {
-            Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-            Err(e) => Err(format!("{e:#?}")),
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
         }
     }
 
@@ -1038,8 +1667,8 @@ mod ffi {
         });
 
         match result {
-            Ok(value) => serde_json::to_string(&value).map_err(|e| format!("{e:#?}")),
-            Err(e) => Err(format!("{e:#?}")),
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
         }
     }
 
@@ -1056,7 +1685,7 @@ mod ffi {
 
         result
             .map(|s| s.map(|s| s.to_string().expose_secret().to_string()))
-            .map_err(|e| format!("{e:#?}"))
+            .map_err(error_string)
     }
 
     /// Set/upload the recovery share.
@@ -1069,7 +1698,184 @@ mod ffi {
             sdk.set_recovery_share(share).await
         });
 
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
+    }
+
+    /// Get the additional shares created for share schemes with more than three shares.
+    ///
+    /// @return Serialized JSON array of the additional shares as strings.
+    pub fn getAdditionalShares() -> Result<String, String> {
+        use sdk::secrecy::ExposeSecret;
+        let result = runtime().block_on(async move {
+            let sdk = get_or_init_sdk().write().await;
+            sdk.get_additional_shares().await
+        });
+
+        match result {
+            Ok(shares) => {
+                let shares = shares
+                    .iter()
+                    .map(|s| s.to_string().expose_secret().to_string())
+                    .collect::<Vec<_>>();
+                serde_json::to_string(&shares).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))
+            }
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Set the additional shares needed to initialize the wallet.
+    ///
+    /// @param shares Serialized JSON array of the additional shares collected from their holders.
+    pub fn setAdditionalShares(shares: String) -> Result<(), String> {
+        let shares: Vec<String> =
+            serde_json::from_str(&shares).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))?;
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let shares = shares
+                .iter()
+                .map(|s| s.parse::<Share>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| sdk::Error::Wallet(WalletError::Share(e)))?;
+            sdk.set_additional_shares(shares).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Check the health of the shares of the wallet without reconstructing it.
+    ///
+    /// @param pin The pin for verification
+    /// @return Serialized JSON object with the health of the `local`, `backup`, `recovery`,
+    ///         `provided_recovery` and `additional` shares.
+    pub fn checkShareHealth(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.check_share_health(&pin).await
+        });
+
+        match result {
+            Ok(value) => serde_json::to_string(&value).map_err(|e| error_string(sdk::Error::Parse(e.to_string()))),
+            Err(e) => Err(error_string(e)),
+        }
+    }
+
+    /// Re-split the wallet secret and replace all shares.
+    ///
+    /// @param pin The pin for verification
+    pub fn repairShares(pin: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.repair_shares(&pin).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Enable or disable unlocking the wallet with the shares on this device only, e.g. on an offline
+    /// device signing transactions.
+    ///
+    /// @param pin The pin for verification
+    /// @param enabled Whether the recovery share is stored on the device or removed from it
+    pub fn setOfflineUnlock(pin: String, enabled: bool) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_offline_unlock(&pin, enabled).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Get the public key of the user as a guardian. Other users need it to nominate the user as
+    /// their guardian.
+    ///
+    /// @param pin The pin for verification
+    /// @return The base64 encoded public key.
+    pub fn getGuardianPublicKey(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.get_guardian_public_key(&pin).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Nominate guardians who can help to recover the wallet. All shares are replaced.
+    ///
+    /// @param pin The pin for verification
+    /// @param guardians Serialized JSON array of objects with the `username` and `public_key` of each guardian.
+    /// @param threshold The number of shares needed to reconstruct the wallet, at most 3.
+    pub fn setGuardians(pin: String, guardians: String, threshold: i64) -> Result<(), String> {
+        let guardians: Vec<Guardian> =
+            serde_json::from_str(&guardians).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))?;
+        let threshold = u8::try_from(threshold).map_err(|e| error_string(sdk::Error::Parse(e.to_string())))?;
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_guardians(&pin, guardians, threshold).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Start recovering the wallet with the help of the guardians. The key of the recovery is stored
+    /// encrypted with the pin until the recovery is completed with `completeGuardianRecovery`.
+    ///
+    /// @param pin The pin for verification
+    /// @return The fingerprint of the recovery key, which the guardians confirm before approving.
+    pub fn startGuardianRecovery(pin: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.start_guardian_recovery(&pin).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Get the fingerprint of the key of the recovery started by another user, who nominated this user as
+    /// guardian. Show it to compare it with the fingerprint of the other user before approving.
+    ///
+    /// @param username The username of the user recovering their wallet.
+    /// @return The fingerprint of the recovery key.
+    pub fn getGuardianRecoveryFingerprint(username: String) -> Result<String, String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            sdk.get_guardian_recovery_fingerprint(&username).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Approve the recovery of another user, who nominated this user as guardian.
+    ///
+    /// @param pin The pin for verification
+    /// @param username The username of the user recovering their wallet.
+    /// @param fingerprint The fingerprint of the recovery key confirmed by the other user.
+    pub fn approveGuardianRecovery(pin: String, username: String, fingerprint: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.approve_guardian_recovery(&pin, &username, &fingerprint).await
+        });
+
+        result.map_err(error_string)
+    }
+
+    /// Complete the recovery of the wallet with the shares approved by the guardians. All shares are replaced.
+    ///
+    /// @param pin The pin for verification
+    pub fn completeGuardianRecovery(pin: String) -> Result<(), String> {
+        let result = runtime().block_on(async move {
+            let mut sdk = get_or_init_sdk().write().await;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.complete_guardian_recovery(&pin).await
+        });
+
+        result.map_err(error_string)
     }
 
     /// Get the user's preferred network.
@@ -1081,7 +1887,7 @@ mod ffi {
             sdk.get_preferred_network().await
         });
 
-        result.map(|s| s.map(|c| c.to_string())).map_err(|e| format!("{e:#?}"))
+        result.map(|s| s.map(|c| c.to_string())).map_err(error_string)
     }
 
     /// Set the user's preferred network.
@@ -1093,7 +1899,7 @@ mod ffi {
             sdk.set_preferred_network(network_id).await
         });
 
-        result.map_err(|e| format!("{e:#?}"))
+        result.map_err(error_string)
     }
 
     /// Get sdk build information.
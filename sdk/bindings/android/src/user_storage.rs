@@ -0,0 +1,101 @@
+//! Connects a user storage of the Java application to the SDK.
+
+use jni::objects::{GlobalRef, JObject, JObjectArray, JString, JValue};
+use jni::{JNIEnv, JavaVM};
+use sdk::user::callback_storage::UserStorageCallbacks;
+
+/// The callbacks of a Java object implementing the methods `String getItem(String key)`,
+/// `void setItem(String key, String value)`, `void removeItem(String key)` and `String[] keys()`.
+pub struct JavaUserStorageCallbacks {
+    vm: JavaVM,
+    storage: GlobalRef,
+}
+
+impl JavaUserStorageCallbacks {
+    pub fn new(vm: JavaVM, storage: GlobalRef) -> Self {
+        Self { vm, storage }
+    }
+
+    /// Calls a method of the storage object on the current thread, which is attached to the JVM if needed.
+    fn call<T>(
+        &self,
+        f: impl for<'local> FnOnce(&mut JNIEnv<'local>, &JObject) -> jni::errors::Result<T>,
+    ) -> Result<T, String> {
+        let mut env = self.vm.attach_current_thread().map_err(|e| e.to_string())?;
+        // free the local references created by the call, the thread might stay attached
+        env.with_local_frame(16, |env| {
+            Ok::<_, jni::errors::Error>(f(env, self.storage.as_obj()).map_err(|e| java_error(env, e)))
+        })
+        .map_err(|e| e.to_string())?
+    }
+}
+
+/// Clears a pending exception thrown by the storage and describes it.
+fn java_error(env: &mut JNIEnv, error: jni::errors::Error) -> String {
+    let Ok(exception) = env.exception_occurred() else {
+        return error.to_string();
+    };
+    if exception.is_null() {
+        return error.to_string();
+    }
+    let _ = env.exception_clear();
+    env.call_method(&exception, "toString", "()Ljava/lang/String;", &[])
+        .and_then(|s| s.l())
+        .and_then(|s| env.get_string(&JString::from(s)).map(String::from))
+        .unwrap_or_else(|_| error.to_string())
+}
+
+impl UserStorageCallbacks for JavaUserStorageCallbacks {
+    fn get_item(&self, key: &str) -> Result<Option<String>, String> {
+        self.call(|env, storage| {
+            let key = env.new_string(key)?;
+            let value = env
+                .call_method(
+                    storage,
+                    "getItem",
+                    "(Ljava/lang/String;)Ljava/lang/String;",
+                    &[JValue::Object(&key)],
+                )?
+                .l()?;
+            if value.is_null() {
+                return Ok(None);
+            }
+            Ok(Some(env.get_string(&JString::from(value))?.into()))
+        })
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<(), String> {
+        self.call(|env, storage| {
+            let key = env.new_string(key)?;
+            let value = env.new_string(value)?;
+            env.call_method(
+                storage,
+                "setItem",
+                "(Ljava/lang/String;Ljava/lang/String;)V",
+                &[JValue::Object(&key), JValue::Object(&value)],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn remove_item(&self, key: &str) -> Result<(), String> {
+        self.call(|env, storage| {
+            let key = env.new_string(key)?;
+            env.call_method(storage, "removeItem", "(Ljava/lang/String;)V", &[JValue::Object(&key)])?;
+            Ok(())
+        })
+    }
+
+    fn keys(&self) -> Result<Vec<String>, String> {
+        self.call(|env, storage| {
+            let keys = JObjectArray::from(env.call_method(storage, "keys", "()[Ljava/lang/String;", &[])?.l()?);
+            let len = env.get_array_length(&keys)?;
+            let mut result = Vec::with_capacity(len as usize);
+            for i in 0..len {
+                let key = JString::from(env.get_object_array_element(&keys, i)?);
+                result.push(env.get_string(&key)?.into());
+            }
+            Ok(result)
+        })
+    }
+}
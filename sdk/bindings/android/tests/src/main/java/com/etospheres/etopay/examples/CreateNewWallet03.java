@@ -19,7 +19,7 @@ public class CreateNewWallet03 {
 
             // create new wallet
             sdk.setWalletPassword(utils.PIN, password);
-            sdk.createNewWallet(utils.PIN);
+            sdk.createNewWallet(utils.PIN, 0, null);
             System.out.println("Created new wallet.");
 
         } catch (Exception e) {
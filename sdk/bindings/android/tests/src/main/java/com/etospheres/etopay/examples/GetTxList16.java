@@ -19,7 +19,7 @@ public class GetTxList16 {
 
             // create new wallet
             sdk.setWalletPassword(utils.PIN, password);
-            sdk.createNewWallet(utils.PIN);
+            sdk.createNewWallet(utils.PIN, 0, null);
             System.out.println("Created and initialized new wallet.");
 
             // get tx list
@@ -25,7 +25,7 @@ public class GetWalletTxList19 {
 
             // create new wallet
             sdk.setWalletPassword(utils.PIN, password);
-            sdk.createNewWallet(utils.PIN);
+            sdk.createNewWallet(utils.PIN, 0, null);
             System.out.println("Created and initialized new wallet.");
 
             // fetch networks from backend
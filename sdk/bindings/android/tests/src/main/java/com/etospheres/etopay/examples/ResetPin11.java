@@ -19,7 +19,7 @@ public class ResetPin11 {
 
             // create and init new wallet
             sdk.setWalletPassword(utils.PIN, password);
-            sdk.createNewWallet(utils.PIN);
+            sdk.createNewWallet(utils.PIN, 0, null);
             System.out.println("Created and init new wallet.");
 
             // reset pin
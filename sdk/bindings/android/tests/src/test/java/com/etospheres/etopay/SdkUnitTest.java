@@ -154,7 +154,7 @@ public class SdkUnitTest {
 
         sdk.setWalletPassword(PIN, PASSWORD);
 
-        this.mnemonic = sdk.createNewWallet(PIN);
+        this.mnemonic = sdk.createNewWallet(PIN, 0, null);
         logger.debug(String.format("Wallet created. Mnemonic: %s", this.mnemonic));
     }
 
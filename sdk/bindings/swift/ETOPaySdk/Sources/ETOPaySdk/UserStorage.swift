@@ -0,0 +1,43 @@
+import ETOPaySdkBin
+
+/// A string key-value storage of the application to store the users in, e.g. the keychain or an encrypted
+/// database. The methods are called from background threads of the SDK and must be thread-safe.
+public protocol UserStorageProtocol {
+	/// Returns the value of a key, or `nil` if the key does not exist.
+	func getItem(key: String) -> String?
+	/// Associates a key with a value and returns `false` if the value could not be stored.
+	func setItem(key: String, value: String) -> Bool
+	/// Removes a key and returns `false` if it could not be removed. Removing a missing key succeeds.
+	func removeItem(key: String) -> Bool
+	/// Returns all keys of the storage.
+	func keys() -> [String]
+}
+
+/// Passes a storage of the application to `ETOPaySdk.setUserStorage`.
+public class UserStorage {
+	let storage: UserStorageProtocol
+
+	public init(_ storage: UserStorageProtocol) {
+		self.storage = storage
+	}
+
+	func getItem(key: RustString) -> String? {
+		storage.getItem(key: key.toString())
+	}
+
+	func setItem(key: RustString, value: RustString) -> Bool {
+		storage.setItem(key: key.toString(), value: value.toString())
+	}
+
+	func removeItem(key: RustString) -> Bool {
+		storage.removeItem(key: key.toString())
+	}
+
+	func keys() -> RustVec<RustString> {
+		let keys = RustVec<RustString>()
+		for key in storage.keys() {
+			keys.push(value: key.intoRustString())
+		}
+		return keys
+	}
+}
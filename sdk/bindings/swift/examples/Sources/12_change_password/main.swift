@@ -27,7 +27,7 @@ Task {
 
         // Create new wallet
         try await sdk.setWalletPassword(env.pin, env.password)
-        let _ = try await sdk.createNewWallet(env.pin)
+        let _ = try await sdk.createNewWallet(env.pin, nil, nil)
         print("created new wallet")
 
         // Change password
@@ -18,17 +18,26 @@ use crate::ffi::{
     NewViviswapUser, PurchaseDetails, TxStatus, ViviswapAddressDetail, ViviswapDeposit, ViviswapKycStatus,
     ViviswapPartiallyKycDetails, ViviswapWithdrawal,
 };
+use crate::user_storage::SwiftUserStorageCallbacks;
+use crate::utils::error_string;
+use sdk::core::watcher::TransactionStatusStream;
 use sdk::core::{Config, Sdk};
+use sdk::mnemonic::MnemonicOptions;
 use sdk::types::currencies::CryptoAmount;
-use sdk::types::newtypes::{AccessToken, EncryptionPin, PlainPassword};
+use sdk::types::newtypes::{AccessToken, EncryptionPin, MnemonicPassphrase, PlainPassword};
+use sdk::types::transactions::{PendingTransaction, UnsignedTransaction};
+use sdk::user::callback_storage::CallbackUserStorage;
+use sdk::user::encryption::UserStorageEncryption;
+use sdk::watch_only::WatchOnlySource;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 /// Struct representing the ETOPay SDK with an inner data structure wrapped in an atomic reference count and read-write lock.
 /// Utilizes atomic reference counting (`Arc`) and a read-write lock (`RwLock`) to provide thread-safe access to the inner data structure,
 /// allowing multiple threads to concurrently read from or write to the ETOPay SDK while ensuring data integrity and preventing data races.
 pub struct ETOPaySdk {
     inner: Arc<RwLock<sdk::core::Sdk>>,
+    transaction_status: Mutex<TransactionStatusStream>,
 }
 
 impl ETOPaySdk {
@@ -39,8 +48,11 @@ impl ETOPaySdk {
     /// New `ETOPaySdk` instance with atomic reference count and read write lock
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
+        let sdk = sdk::core::Sdk::default();
+        let transaction_status = Mutex::new(sdk.subscribe_transaction_status());
         Self {
-            inner: Arc::new(RwLock::new(sdk::core::Sdk::default())),
+            inner: Arc::new(RwLock::new(sdk)),
+            transaction_status,
         }
     }
     /// Sets the configuration as a JSON-encoded string.
@@ -65,7 +77,32 @@ impl ETOPaySdk {
         let mut sdk = self.inner.write().await;
         Config::from_json(&config)
             .and_then(|r| sdk.set_config(r))
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
+    }
+
+    /// Store the users in a storage of the application instead of the file in the storage path. The users are
+    /// stored as encoded records under keys with the prefix `etopay.local.user.`. Call this before `set_config`
+    /// and `init_user`, the active user is logged out and the users of the previous storage are not moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The storage of the application, a `UserStorage` wrapping a `UserStorageProtocol`.
+    pub async fn set_user_storage(&self, storage: crate::ffi::UserStorage) {
+        let mut sdk = self.inner.write().await;
+        sdk.set_user_storage(CallbackUserStorage::new(SwiftUserStorageCallbacks::new(storage)));
+    }
+
+    /// Encrypt the users stored by the SDK with a key derived from the pin. A stored user is locked after
+    /// `init_user` until the pin is entered, e.g. with `verify_pin`.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the encryption is enabled successfully.
+    /// * Err - if the user storage could not be opened again.
+    pub async fn enable_user_storage_encryption_with_pin(&self) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        sdk.set_user_storage_encryption(UserStorageEncryption::Pin)
+            .map_err(error_string)
     }
 
     /// Fetch available networks.
@@ -82,7 +119,7 @@ impl ETOPaySdk {
                 .map(|n| n.into_iter().map(|network| network.into()).collect())
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Selects the network for the ETOPay SDK.
@@ -97,7 +134,7 @@ impl ETOPaySdk {
     /// * Err - if something went wrong.`
     pub async fn set_network(&self, network_id: String) -> Result<(), String> {
         let mut sdk = self.inner.write().await;
-        sdk.set_network(network_id).await.map_err(|e| format!("{e:#?}"))
+        sdk.set_network(network_id).await.map_err(error_string)
     }
 
     /// Destructor for the SDK handle
@@ -129,9 +166,7 @@ impl ETOPaySdk {
     /// * Err - if there is an issue validating the configuration, initializing the repository, or creating the user.
     pub async fn create_new_user(&self, username: String) -> Result<(), String> {
         let mut sdk = self.inner.write().await;
-        sdk.create_new_user(&username)
-            .await
-            .map_err(|err| format!("{:#?}", err))
+        sdk.create_new_user(&username).await.map_err(error_string)
     }
 
     /// Initializes the user
@@ -146,7 +181,103 @@ impl ETOPaySdk {
     /// * Err - if there is an issue validating the configuration, initializing the repository, or checking the KYC status.
     pub async fn init_user(&self, username: String) -> Result<(), String> {
         let mut sdk = self.inner.write().await;
-        sdk.init_user(&username).await.map_err(|err| format!("{:#?}", err))
+        sdk.init_user(&username).await.map_err(error_string)
+    }
+
+    /// Lists the users stored on this device
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the users sorted by username.
+    /// * Err - if the repository is not initialized or the users could not be loaded.
+    pub async fn list_users(&self) -> Result<Vec<UserSummary>, String> {
+        let sdk = self.inner.write().await;
+        sdk.list_users()
+            .await
+            .map(|users| users.into_iter().map(Into::into).collect())
+            .map_err(error_string)
+    }
+
+    /// Logs out the active user and drops its cached wallet state and access token
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty after the user is logged out.
+    pub async fn logout(&self) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        sdk.logout().await.map_err(error_string)
+    }
+
+    /// Logs out the active user and initializes another user stored on this device
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user to switch to.
+    /// * `access_token` - The access token of the user. Empty to set it later with `refresh_access_token`.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty if the user is initialized successfully.
+    /// * Err - if there is an issue initializing the user. No user is active then.
+    pub async fn switch_user(&self, username: String, access_token: String) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let access_token = if access_token.is_empty() {
+                None
+            } else {
+                Some(AccessToken::try_from(access_token)?)
+            };
+            sdk.switch_user(&username, access_token).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Exports the locally stored record of the active user, encrypted with the export password
+    ///
+    /// # Arguments
+    ///
+    /// * `export_password` - The password to encrypt the export with.
+    /// * `pin` - The wallet pin for confirmation. Optional in case there is no wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the bytes of the exported user.
+    /// * Err - if the user is not initialized or the PIN is wrong.
+    pub async fn export_user(&self, export_password: String, pin: Option<String>) -> Result<Vec<u8>, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let export_password = PlainPassword::try_from_string(export_password)?;
+            let encryption_pin = match pin {
+                Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                None => None,
+            };
+            sdk.export_user(encryption_pin.as_ref(), &export_password).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Imports a user exported with `export_user`
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the exported user.
+    /// * `export` - The bytes of the exported user.
+    /// * `export_password` - The password the user was exported with.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty if the user is imported.
+    /// * Err - if the export password or the username is wrong, the export is invalid or the user already exists.
+    pub async fn import_user(&self, username: String, export: Vec<u8>, export_password: String) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let export_password = PlainPassword::try_from_string(export_password)?;
+            sdk.import_user(&username, &export, &export_password).await
+        }
+        .await
+        .map_err(error_string)
     }
 
     /// Refreshes the access token
@@ -170,7 +301,7 @@ impl ETOPaySdk {
             sdk.refresh_access_token(access_token).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Gets the kyc verification status
@@ -185,9 +316,7 @@ impl ETOPaySdk {
     /// * Err - if there is an issue validating the configuration, initializing the repository, or checking the KYC status.
     pub async fn is_kyc_verified(&self, username: String) -> Result<bool, String> {
         let mut sdk = self.inner.write().await;
-        sdk.is_kyc_status_verified(&username)
-            .await
-            .map_err(|err| format!("{:#?}", err))
+        sdk.is_kyc_status_verified(&username).await.map_err(error_string)
     }
 
     /// Verifies the given mnemonic
@@ -208,7 +337,35 @@ impl ETOPaySdk {
             sdk.verify_mnemonic(&pin, &mnemonic).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
+    }
+
+    /// Verifies the given mnemonic and BIP-39 passphrase
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `mnemonic` - The mnemonic to verify.
+    /// * `passphrase` - The BIP-39 passphrase to verify.
+    ///
+    /// # Returns
+    ///
+    /// * Ok(boolean) - if the mnemonic and passphrase are successfully verified or not.
+    /// * Err - if there is an error initializing the wallet.
+    pub async fn verify_mnemonic_with_passphrase(
+        &self,
+        pin: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<bool, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.verify_mnemonic_with_passphrase(&pin, &mnemonic, &passphrase).await
+        }
+        .await
+        .map_err(error_string)
     }
 
     /// Creates the new wallet
@@ -216,19 +373,60 @@ impl ETOPaySdk {
     /// # Arguments
     ///
     /// * `pin` - The PIN for the wallet.
+    /// * `word_count` - The optional number of words of the mnemonic: 12, 15, 18, 21 or 24 (default).
+    /// * `language` - The optional wordlist language of the mnemonic, e.g. `english` (default), `french` or
+    ///   `japanese`.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - returns the mnemonic phrase of the newly created wallet if successful.
+    /// * Err - if there is an error initializing the wallet, initializing the repository, initializing the user.
+    pub async fn create_new_wallet(
+        &self,
+        pin: String,
+        word_count: Option<u8>,
+        language: Option<String>,
+    ) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let options = MnemonicOptions::try_from_parts(word_count, language.as_deref())?;
+            sdk.create_wallet_from_new_mnemonic(&pin, &options).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Creates the new wallet with a BIP-39 passphrase (25th word), which is stored encrypted on this device
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `passphrase` - The BIP-39 passphrase of the wallet.
+    /// * `word_count` - The optional number of words of the mnemonic: 12, 15, 18, 21 or 24 (default).
+    /// * `language` - The optional wordlist language of the mnemonic, `english` by default.
     ///
     /// # Returns
     ///
     /// * Ok - returns the mnemonic phrase of the newly created wallet if successful.
     /// * Err - if there is an error initializing the wallet, initializing the repository, initializing the user.
-    pub async fn create_new_wallet(&self, pin: String) -> Result<String, String> {
+    pub async fn create_new_wallet_with_passphrase(
+        &self,
+        pin: String,
+        passphrase: String,
+        word_count: Option<u8>,
+        language: Option<String>,
+    ) -> Result<String, String> {
         let mut sdk = self.inner.write().await;
         async move {
             let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.create_wallet_from_new_mnemonic(&pin).await
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            let options = MnemonicOptions::try_from_parts(word_count, language.as_deref())?;
+            sdk.create_wallet_from_new_mnemonic_with_passphrase(&pin, &passphrase, &options)
+                .await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Creates wallet from mnemonic
@@ -249,7 +447,58 @@ impl ETOPaySdk {
             sdk.create_wallet_from_existing_mnemonic(&pin, &mnemonic).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
+    }
+
+    /// Creates wallet from mnemonic and BIP-39 passphrase (25th word), which is stored encrypted on this device
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `mnemonic` - The mnemonic to migrate from.
+    /// * `passphrase` - The BIP-39 passphrase used with the mnemonic.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty if the wallet is successfully created.
+    /// * Err - if there is an error initializing the wallet, initializing the repository, initializing the user.
+    pub async fn create_wallet_from_mnemonic_with_passphrase(
+        &self,
+        pin: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.create_wallet_from_existing_mnemonic_with_passphrase(&pin, &mnemonic, &passphrase)
+                .await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Sets the BIP-39 passphrase of the wallet on this device
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `passphrase` - The BIP-39 passphrase of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty if the passphrase is stored.
+    /// * Err - if the pin is wrong or there is an error initializing the repository or the user.
+    pub async fn set_wallet_passphrase(&self, pin: String, passphrase: String) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.set_wallet_passphrase(&pin, &passphrase).await
+        }
+        .await
+        .map_err(error_string)
     }
 
     /// Restores a wallet from backup
@@ -277,7 +526,7 @@ impl ETOPaySdk {
             sdk.create_wallet_from_backup(&pin, &backup, &backup_password).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Creates a wallet backup
@@ -299,7 +548,30 @@ impl ETOPaySdk {
             sdk.create_wallet_backup(&pin, &password).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
+    }
+
+    /// Creates a versioned JSON wallet backup, which also contains the network ids, the preferred network,
+    /// the derived address indices and the labels of the additional shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `backup_password` - The password to use for the backup.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the bytes representing the backup file if successful.
+    /// * Err - if there is an error initializing the wallet.
+    pub async fn create_json_wallet_backup(&self, pin: String, backup_password: String) -> Result<Vec<u8>, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let password = PlainPassword::try_from_string(backup_password)?;
+            sdk.create_json_wallet_backup(&pin, &password).await
+        }
+        .await
+        .map_err(error_string)
     }
 
     /// Deletes an existing wallet
@@ -319,7 +591,7 @@ impl ETOPaySdk {
             sdk.delete_wallet(&pin).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Generates a new receiver address.
@@ -339,7 +611,7 @@ impl ETOPaySdk {
             sdk.generate_new_address(&pin).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Fetches the current balance
@@ -359,7 +631,180 @@ impl ETOPaySdk {
             sdk.get_balance(&pin).await.and_then(f64::try_from)
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
+    }
+
+    /// Derives a new address of the EVM wallet with the next unused address index
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the newly derived address.
+    /// * Err - if the selected network is not an EVM network or there is an error initializing the wallet.
+    pub async fn derive_new_address(&self, pin: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.derive_new_address(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Fetches all addresses of the wallet in use
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the addresses ordered by their address index, starting with the default address.
+    /// * Err - if there is an error initializing the wallet.
+    pub async fn get_wallet_addresses(&self, pin: String) -> Result<Vec<String>, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.get_wallet_addresses(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Fetches the current balance of a single address of the wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `address` - One of the addresses of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the balance as a f64 if successful.
+    /// * Err - if the address does not belong to the wallet or there is an error initializing the wallet.
+    pub async fn get_address_balance(&self, pin: String, address: String) -> Result<f64, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.get_address_balance(&pin, &address).await.and_then(f64::try_from)
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Sets the EVM addresses watched by the watch-only wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `addresses` - The addresses of the wallet, the first one is used as the default address.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the addresses were stored.
+    /// * Err - if the pin is wrong, an address is invalid or there is an error initializing the user.
+    pub async fn set_watch_only_addresses(&self, pin: String, addresses: Vec<String>) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_watch_only_source(&pin, Some(WatchOnlySource::EvmAddresses(addresses)))
+                .await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Sets the extended public key of the EVM account watched by the watch-only wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `xpub` - The extended public key of the account at the derivation path `m/44'/60'/0'`.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the extended public key was stored.
+    /// * Err - if the pin is wrong, the extended public key is invalid or there is an error initializing the user.
+    pub async fn set_watch_only_xpub(&self, pin: String, xpub: String) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_watch_only_source(&pin, Some(WatchOnlySource::EvmAccountXpub(xpub)))
+                .await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Enables the watch-only wallet with the current addresses of the EVM wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the addresses watched by the watch-only wallet.
+    /// * Err - if the selected network is not an EVM network or there is an error initializing the wallet.
+    pub async fn enable_watch_only(&self, pin: String) -> Result<Vec<String>, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.enable_watch_only(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Fetches the default address of the watch-only wallet
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the default address.
+    /// * Err - if no watch-only addresses are set or the selected network is not an EVM network.
+    pub async fn get_watch_only_address(&self) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        sdk.get_watch_only_address().await.map_err(error_string)
+    }
+
+    /// Fetches the current balance of the watch-only wallet without the pin
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the balance as a f64 if successful.
+    /// * Err - if no watch-only addresses are set or the selected network is not an EVM network.
+    pub async fn get_watch_only_balance(&self) -> Result<f64, String> {
+        let mut sdk = self.inner.write().await;
+        sdk.get_watch_only_balance()
+            .await
+            .and_then(f64::try_from)
+            .map_err(error_string)
+    }
+
+    /// Fetches the list of transactions of the watch-only wallet without the pin
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The start page.
+    /// * `limit` - The limit per page.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - wallet transaction list if successful.
+    /// * Err - if no watch-only addresses are set or the selected network is not an EVM network.
+    pub async fn get_watch_only_transaction_list(
+        &self,
+        start: usize,
+        limit: usize,
+    ) -> Result<Vec<WalletTxInfo>, String> {
+        let mut sdk = self.inner.write().await;
+        sdk.get_watch_only_wallet_tx_list(start, limit)
+            .await
+            .map(|l| l.transactions.into_iter().map(|tx| tx.into()).collect())
+            .map_err(error_string)
     }
 
     /// Starts KYC verification with Postident
@@ -376,7 +821,7 @@ impl ETOPaySdk {
             sdk.start_kyc_verification_for_postident()
                 .await
                 .map(Into::into)
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -394,7 +839,7 @@ impl ETOPaySdk {
             sdk.get_kyc_details_for_postident()
                 .await
                 .map(Into::into)
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -415,7 +860,7 @@ impl ETOPaySdk {
             let sdk = self.inner.write().await;
             sdk.update_kyc_status_for_postident(&case_id)
                 .await
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -448,7 +893,7 @@ impl ETOPaySdk {
                 .await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Fetches the purchase details
@@ -466,7 +911,7 @@ impl ETOPaySdk {
         sdk.get_purchase_details(&purchase_id)
             .await
             .and_then(TryInto::try_into)
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
     }
 
     /// Confirms the purchase request
@@ -488,7 +933,7 @@ impl ETOPaySdk {
             sdk.confirm_purchase_request(&pin, &purchase_id).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Starts KYC verification with viviswap
@@ -513,7 +958,7 @@ impl ETOPaySdk {
             sdk.start_kyc_verification_for_viviswap(&mail, terms_accepted)
                 .await
                 .map(Into::into)
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -533,7 +978,7 @@ impl ETOPaySdk {
             sdk.get_kyc_details_for_viviswap()
                 .await
                 .map(Into::into)
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -581,7 +1026,7 @@ impl ETOPaySdk {
             )
             .await
             .map(Into::into)
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -601,7 +1046,7 @@ impl ETOPaySdk {
             let mut sdk = self.inner.write().await;
             sdk.submit_kyc_partially_status_for_viviswap()
                 .await
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -629,7 +1074,7 @@ impl ETOPaySdk {
                 identity_personal_document_data.into(),
             )
             .await
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -678,7 +1123,7 @@ impl ETOPaySdk {
                 official_document,
             )
             .await
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -700,7 +1145,7 @@ impl ETOPaySdk {
                     let questions = kyc_amla_questions.into_iter().map(|q| q.into()).collect();
                     Ok(questions)
                 }
-                Err(err) => Err(format!("{:#?}", err)),
+                Err(err) => Err(error_string(err)),
             }
         })
     }
@@ -723,7 +1168,7 @@ impl ETOPaySdk {
                     let documents = kyc_open_documents.into_iter().map(|d| d.into()).collect();
                     Ok(documents)
                 }
-                Err(err) => Err(format!("{:#?}", err)),
+                Err(err) => Err(error_string(err)),
             }
         })
     }
@@ -759,7 +1204,7 @@ impl ETOPaySdk {
 
             sdk.set_viviswap_kyc_document(document_id, expiration_date, document_number, front_image, back_image)
                 .await
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -786,7 +1231,7 @@ impl ETOPaySdk {
             let sdk = self.inner.write().await;
             sdk.set_viviswap_kyc_amla_answer(question_id, answers, freetext_answer)
                 .await
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -802,13 +1247,13 @@ impl ETOPaySdk {
     /// * Err - if there is an error for initializing the repository, initializing the user, initializing the wallet, password is missing,
     ///   pin or password is incorrect.
     pub async fn verify_pin(&self, pin: String) -> Result<(), String> {
-        let sdk = self.inner.write().await;
+        let mut sdk = self.inner.write().await;
         async move {
             let pin = EncryptionPin::try_from_string(pin)?;
             sdk.verify_pin(&pin).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Resets the wallet pin
@@ -831,7 +1276,7 @@ impl ETOPaySdk {
             sdk.change_pin(&pin, &new_pin).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Set the password to use for wallet operations. If the password was already set, this changes it.
@@ -853,7 +1298,70 @@ impl ETOPaySdk {
             sdk.set_wallet_password(&pin, &new_password).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
+    }
+
+    /// Recovers the wallet with the mnemonic, for example after it was locked because of too many wrong pin attempts.
+    /// Sets a new pin and password and replaces the existing wallet shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_pin` - The new pin to set for the wallet.
+    /// * `new_password` - The new password to set for the wallet.
+    /// * `mnemonic` - The mnemonic of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty if the wallet is recovered successfully.
+    /// * Err - if there is an error for initializing the repository, initializing the user or recreating the wallet.
+    pub async fn recover_wallet_with_mnemonic(
+        &self,
+        new_pin: String,
+        new_password: String,
+        mnemonic: String,
+    ) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let new_pin = EncryptionPin::try_from_string(new_pin)?;
+            let new_password = PlainPassword::try_from_string(new_password)?;
+            sdk.recover_wallet_with_mnemonic(&new_pin, &new_password, &mnemonic)
+                .await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Recovers a wallet which uses a BIP-39 passphrase with the mnemonic and the passphrase, like
+    /// [`recover_wallet_with_mnemonic`].
+    ///
+    /// # Arguments
+    ///
+    /// * `new_pin` - The new pin to set for the wallet.
+    /// * `new_password` - The new password to set for the wallet.
+    /// * `mnemonic` - The mnemonic of the wallet.
+    /// * `passphrase` - The BIP-39 passphrase of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - empty if the wallet is recovered successfully.
+    /// * Err - if there is an error for initializing the repository, initializing the user or recreating the wallet.
+    pub async fn recover_wallet_with_mnemonic_and_passphrase(
+        &self,
+        new_pin: String,
+        new_password: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let new_pin = EncryptionPin::try_from_string(new_pin)?;
+            let new_password = PlainPassword::try_from_string(new_password)?;
+            let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+            sdk.recover_wallet_with_mnemonic_and_passphrase(&new_pin, &new_password, &mnemonic, &passphrase)
+                .await
+        }
+        .await
+        .map_err(error_string)
     }
 
     /// Check if the password to use for wallet operations is set.
@@ -864,37 +1372,298 @@ impl ETOPaySdk {
     /// Whether the password is already set or not.
     pub async fn is_wallet_password_set(&self) -> Result<bool, String> {
         let sdk = self.inner.read().await;
-        sdk.is_wallet_password_set().await.map_err(|err| format!("{:#?}", err))
+        sdk.is_wallet_password_set().await.map_err(error_string)
+    }
+
+    /// Sends amount from wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `address` - The receiver's address.
+    /// * `amount` - The amount to send.
+    /// * `data` - The associated data with the transaction.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - transaction id if the amount is sent successfully.
+    /// * Err - if the user or wallet is not initialized, there is an error verifying the PIN, or there is an error sending the amount.
+    pub async fn send_amount(
+        &self,
+        pin: String,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let amount = CryptoAmount::try_from(amount)?;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.send_amount(&pin, &address, amount, data).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Sends amount from a specific address of the wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `sender` - The address of the wallet to send from.
+    /// * `address` - The receiver's address.
+    /// * `amount` - The amount to send.
+    /// * `data` - The associated data with the transaction.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - transaction id if the amount is sent successfully.
+    /// * Err - if the sender address does not belong to the wallet, or any of the errors of [`Self::send_amount`].
+    pub async fn send_amount_from(
+        &self,
+        pin: String,
+        sender: String,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let amount = CryptoAmount::try_from(amount)?;
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.send_amount_from(&pin, Some(&sender), &address, amount, data).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Speeds up a pending EVM transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `transaction_hash` - The hash of the pending transaction.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the hash of the replacement transaction.
+    /// * Err - if the transaction is not pending or the network rejects the replacement.
+    pub async fn speed_up_transaction(&self, pin: String, transaction_hash: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.speed_up_transaction(&pin, &transaction_hash).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Cancels a pending EVM transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `transaction_hash` - The hash of the pending transaction.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the hash of the cancel transaction.
+    /// * Err - if the transaction is not pending or the network rejects the replacement.
+    pub async fn cancel_transaction(&self, pin: String, transaction_hash: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.cancel_transaction(&pin, &transaction_hash).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Broadcasts all pending EVM transactions of the selected network again
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the hashes of the transactions that were broadcast.
+    /// * Err - if there is an error initializing the wallet or retrieving the nonces.
+    pub async fn rebroadcast_pending_transactions(&self, pin: String) -> Result<Vec<String>, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.rebroadcast_pending_transactions(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Prepares an EVM transaction to be signed on an offline device
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user. If `None`, the watch-only wallet is used.
+    /// * `sender` - The address of the wallet to send from. If `None`, the default address is used.
+    /// * `address` - The receiver's address.
+    /// * `amount` - The amount to send.
+    /// * `data` - The associated data with the tag. Optional.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the unsigned transaction as QR string.
+    /// * Err - if the network is not an EVM network or the nonce or fees cannot be retrieved.
+    pub async fn prepare_unsigned_transaction(
+        &self,
+        pin: Option<String>,
+        sender: Option<String>,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let amount = CryptoAmount::try_from(amount)?;
+            let pin = match pin {
+                Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                None => None,
+            };
+            sdk.prepare_unsigned_transaction(pin.as_ref(), sender.as_deref(), &address, amount, data)
+                .await
+                .map(|unsigned| unsigned.to_qr_string())
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Decodes an EVM transaction prepared by `prepare_unsigned_transaction` to review it before signing
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `unsigned_transaction` - The unsigned transaction as QR string.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the receiver, value, data and fees of the transaction.
+    /// * Err - if the transaction is for another chain or its sender does not belong to the wallet.
+    pub async fn review_unsigned_transaction(
+        &self,
+        pin: String,
+        unsigned_transaction: String,
+    ) -> Result<UnsignedTransactionDetails, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let unsigned = UnsignedTransaction::from_qr_string(&unsigned_transaction)?;
+            sdk.review_unsigned_transaction(&pin, &unsigned).await
+        }
+        .await
+        .map(Into::into)
+        .map_err(error_string)
+    }
+
+    /// Signs an EVM transaction prepared by `prepare_unsigned_transaction` without contacting the network
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `unsigned_transaction` - The unsigned transaction as QR string.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the signed transaction as QR string.
+    /// * Err - if the transaction is for another chain or its sender does not belong to the wallet.
+    pub async fn sign_unsigned_transaction(&self, pin: String, unsigned_transaction: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            let unsigned = UnsignedTransaction::from_qr_string(&unsigned_transaction)?;
+            sdk.sign_unsigned_transaction(&pin, &unsigned)
+                .await
+                .map(|signed| signed.to_qr_string())
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Broadcasts an EVM transaction that was signed on an offline device
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user. If `None`, the watch-only wallet is used.
+    /// * `signed_transaction` - The signed transaction as QR string.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the transaction hash.
+    /// * Err - if the transaction was not signed by the wallet or the network rejects it.
+    pub async fn broadcast_signed_transaction(
+        &self,
+        pin: Option<String>,
+        signed_transaction: String,
+    ) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = match pin {
+                Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                None => None,
+            };
+            let raw_transaction = PendingTransaction::raw_transaction_from_qr_string(&signed_transaction)?;
+            sdk.broadcast_signed_transaction(pin.as_ref(), &raw_transaction).await
+        }
+        .await
+        .map_err(error_string)
     }
 
-    /// Sends amount from wallet
+    /// Checks the status of the watched transactions of the selected network
     ///
     /// # Arguments
     ///
-    /// * `pin` - The PIN of the user.
-    /// * `address` - The receiver's address.
-    /// * `amount` - The amount to send.
-    /// * `data` - The associated data with the transaction.
+    /// * `pin` - The user's PIN.
     ///
     /// # Returns
     ///
-    /// * Ok - transaction id if the amount is sent successfully.
-    /// * Err - if the user or wallet is not initialized, there is an error verifying the PIN, or there is an error sending the amount.
-    pub async fn send_amount(
-        &self,
-        pin: String,
-        address: String,
-        amount: f64,
-        data: Option<Vec<u8>>,
-    ) -> Result<String, String> {
+    /// * Ok - the watched transactions whose status or number of confirmations changed.
+    /// * Err - if there is an error initializing the wallet or verifying the PIN.
+    pub async fn check_watched_transactions(&self, pin: String) -> Result<Vec<WatchedTransaction>, String> {
         let mut sdk = self.inner.write().await;
         async move {
-            let amount = CryptoAmount::try_from(amount)?;
             let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.send_amount(&pin, &address, amount, data).await
+            sdk.check_watched_transactions(&pin).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map(|txs| txs.into_iter().map(Into::into).collect())
+        .map_err(error_string)
+    }
+
+    /// Gets the transactions followed by the transaction watcher
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the watched transactions of all networks with their last known status.
+    /// * Err - if the user is not initialized.
+    pub async fn get_watched_transactions(&self) -> Result<Vec<WatchedTransaction>, String> {
+        let sdk = self.inner.write().await;
+        sdk.get_watched_transactions()
+            .await
+            .map(|txs| txs.into_iter().map(Into::into).collect())
+            .map_err(error_string)
+    }
+
+    /// Waits for the next status change of a watched transaction
+    ///
+    /// Returns once `check_watched_transactions` finds a watched transaction whose status or number of
+    /// confirmations changed, so the app can await it in a loop, e.g. to feed an `AsyncStream`. If the changes
+    /// are not awaited fast enough, the oldest ones are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * Some - the updated transaction.
+    /// * None - if the SDK was destroyed.
+    pub async fn next_transaction_status(&self) -> Option<WatchedTransaction> {
+        let mut stream = self.transaction_status.lock().await;
+        stream.recv().await.map(Into::into)
     }
 
     /// Updates IBAN in SDK
@@ -919,7 +1688,7 @@ impl ETOPaySdk {
             }
             .await
             .map(Into::into)
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -936,10 +1705,7 @@ impl ETOPaySdk {
     pub async fn get_iban_viviswap(&self) -> Result<ViviswapAddressDetail, String> {
         sdk::require_feature!("viviswap-swap", {
             let mut sdk = self.inner.write().await;
-            sdk.get_iban_for_viviswap()
-                .await
-                .map(Into::into)
-                .map_err(|err| format!("{:#?}", err))
+            sdk.get_iban_for_viviswap().await.map(Into::into).map_err(error_string)
         })
     }
 
@@ -962,7 +1728,7 @@ impl ETOPaySdk {
             }
             .await
             .map(Into::into)
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -985,7 +1751,7 @@ impl ETOPaySdk {
             }
             .await
             .map(Into::into)
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -1020,7 +1786,7 @@ impl ETOPaySdk {
             }
             .await
             .map(Into::into)
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
         })
     }
 
@@ -1041,7 +1807,7 @@ impl ETOPaySdk {
             sdk.get_swap_details(order_id)
                 .await
                 .map(Into::into)
-                .map_err(|err| format!("{:#?}", err))
+                .map_err(error_string)
         })
     }
 
@@ -1065,7 +1831,7 @@ impl ETOPaySdk {
                     let orders = order_list.orders.into_iter().map(|o| o.into()).collect();
                     Ok(orders)
                 }
-                Err(err) => Err(format!("{:#?}", err)),
+                Err(err) => Err(error_string(err)),
             }
         })
     }
@@ -1085,7 +1851,7 @@ impl ETOPaySdk {
         sdk.get_exchange_rate()
             .await
             .and_then(|v| Ok(f64::try_from(v)?))
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
     }
 
     /// Creates withdraw with viviswap
@@ -1106,7 +1872,7 @@ impl ETOPaySdk {
                 let txs = tx_list.txs.into_iter().map(|tx| tx.into()).collect();
                 Ok(txs)
             }
-            Err(err) => Err(format!("{:#?}", err)),
+            Err(err) => Err(error_string(err)),
         }
     }
 
@@ -1130,7 +1896,7 @@ impl ETOPaySdk {
             sdk.delete_user(encryption_pin.as_ref()).await
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Returns wallet transaction list
@@ -1159,7 +1925,7 @@ impl ETOPaySdk {
                 .map(|l| l.transactions.into_iter().map(|tx| tx.into()).collect())
         }
         .await
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Fetches a wallet transaction.
@@ -1181,7 +1947,7 @@ impl ETOPaySdk {
         }
         .await
         .map(Into::into)
-        .map_err(|err| format!("{:#?}", err))
+        .map_err(error_string)
     }
 
     /// Get the recovery share.
@@ -1197,7 +1963,7 @@ impl ETOPaySdk {
         sdk.get_recovery_share()
             .await
             .map(|s| s.map(|s| s.to_string().expose_secret().to_string()).unwrap_or_default())
-            .map_err(|err| format!("{:#?}", err))
+            .map_err(error_string)
     }
 
     /// Set the recovery share.
@@ -1213,8 +1979,257 @@ impl ETOPaySdk {
     pub async fn set_recovery_share(&self, share: String) -> Result<(), String> {
         let mut sdk = self.inner.write().await;
 
-        let share: sdk::share::Share = share.parse().map_err(|e| format!("{e:#?}"))?;
-        sdk.set_recovery_share(share).await.map_err(|err| format!("{:#?}", err))
+        let share: sdk::share::Share = share.parse().map_err(|e| error_string(sdk::WalletError::Share(e)))?;
+        sdk.set_recovery_share(share).await.map_err(error_string)
+    }
+
+    /// Get the additional shares created for share schemes with more than three shares.
+    ///
+    /// # Returns
+    ///
+    /// * The additional shares as strings, to be handed out according to their label.
+    /// * Err - if the user is not initialized.
+    pub async fn get_additional_shares(&self) -> Result<Vec<String>, String> {
+        use sdk::secrecy::ExposeSecret;
+
+        let sdk = self.inner.write().await;
+        sdk.get_additional_shares()
+            .await
+            .map(|shares| {
+                shares
+                    .iter()
+                    .map(|s| s.to_string().expose_secret().to_string())
+                    .collect()
+            })
+            .map_err(error_string)
+    }
+
+    /// Set the additional shares needed to initialize the wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `shares` - The additional shares collected from their holders.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if conversion went well.
+    /// * Err - if a share has the wrong format or the user is not initialized.
+    pub async fn set_additional_shares(&self, shares: Vec<String>) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+
+        let shares = shares
+            .iter()
+            .map(|s| s.parse::<sdk::share::Share>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| error_string(sdk::WalletError::Share(e)))?;
+        sdk.set_additional_shares(shares).await.map_err(error_string)
+    }
+
+    /// Check the health of the shares of the wallet without reconstructing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the health of each share.
+    /// * Err - if the user is not initialized or the shares could not be downloaded.
+    pub async fn check_share_health(&self, pin: String) -> Result<ShareHealthReport, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.check_share_health(&pin).await
+        }
+        .await
+        .map(Into::into)
+        .map_err(error_string)
+    }
+
+    /// Re-split the wallet secret and replace all shares.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the shares were replaced.
+    /// * Err - if not enough usable shares are available to reconstruct the secret.
+    pub async fn repair_shares(&self, pin: String) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.repair_shares(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Enable or disable unlocking the wallet with the shares on this device only, e.g. on an offline device
+    /// signing transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    /// * `enabled` - Whether the recovery share is stored on the device or removed from it.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the recovery share was stored or removed.
+    /// * Err - if the wallet could not be reconstructed.
+    pub async fn set_offline_unlock(&self, pin: String, enabled: bool) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_offline_unlock(&pin, enabled).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Get the public key of the user as a guardian. Other users need it to nominate the user as their guardian.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the base64 encoded public key.
+    /// * Err - if the wallet could not be reconstructed.
+    pub async fn get_guardian_public_key(&self, pin: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.get_guardian_public_key(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Nominate guardians who can help to recover the wallet. All shares are replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    /// * `usernames` - The usernames of the guardians.
+    /// * `public_keys` - The public keys of the guardians, in the same order as the usernames.
+    /// * `threshold` - The number of shares needed to reconstruct the wallet, at most 3.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the shares were created and uploaded.
+    /// * Err - if the guardians or the threshold are invalid, or the shares could not be uploaded.
+    pub async fn set_guardians(
+        &self,
+        pin: String,
+        usernames: Vec<String>,
+        public_keys: Vec<String>,
+        threshold: u8,
+    ) -> Result<(), String> {
+        if usernames.len() != public_keys.len() {
+            return Err(String::from("Each guardian needs a username and a public key"));
+        }
+        let guardians = usernames
+            .into_iter()
+            .zip(public_keys)
+            .map(|(username, public_key)| sdk::share::Guardian { username, public_key })
+            .collect();
+
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.set_guardians(&pin, guardians, threshold).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Start recovering the wallet with the help of the guardians. The key of the recovery is stored
+    /// encrypted with the PIN until the recovery is completed with `complete_guardian_recovery`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the fingerprint of the recovery key, which the guardians confirm before approving.
+    /// * Err - if the user is not logged in.
+    pub async fn start_guardian_recovery(&self, pin: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.start_guardian_recovery(&pin).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Get the fingerprint of the key of the recovery started by another user, who nominated this user as
+    /// guardian. Show it to compare it with the fingerprint of the other user before approving.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user recovering their wallet.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - the fingerprint of the recovery key.
+    /// * Err - if the other user did not start a recovery.
+    pub async fn get_guardian_recovery_fingerprint(&self, username: String) -> Result<String, String> {
+        let mut sdk = self.inner.write().await;
+        sdk.get_guardian_recovery_fingerprint(&username)
+            .await
+            .map_err(error_string)
+    }
+
+    /// Approve the recovery of another user, who nominated this user as guardian.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    /// * `username` - The username of the user recovering their wallet.
+    /// * `fingerprint` - The fingerprint of the recovery key confirmed by the other user.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the recovery was approved.
+    /// * Err - if the other user did not start a recovery or the fingerprint does not match.
+    pub async fn approve_guardian_recovery(
+        &self,
+        pin: String,
+        username: String,
+        fingerprint: String,
+    ) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.approve_guardian_recovery(&pin, &username, &fingerprint).await
+        }
+        .await
+        .map_err(error_string)
+    }
+
+    /// Complete the recovery of the wallet with the shares approved by the guardians. All shares are replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The user's PIN.
+    ///
+    /// # Returns
+    ///
+    /// * Ok - if the wallet was recovered.
+    /// * Err - if no recovery was started or not enough shares are available.
+    pub async fn complete_guardian_recovery(&self, pin: String) -> Result<(), String> {
+        let mut sdk = self.inner.write().await;
+        async move {
+            let pin = EncryptionPin::try_from_string(pin)?;
+            sdk.complete_guardian_recovery(&pin).await
+        }
+        .await
+        .map_err(error_string)
     }
 
     /// Get the user's preferred network.
@@ -1228,7 +2243,7 @@ impl ETOPaySdk {
         let result = sdk.get_preferred_network().await;
         match result {
             Ok(network) => Ok(network.unwrap_or_default()),
-            Err(err) => Err(format!("{:#?}", err)),
+            Err(err) => Err(error_string(err)),
         }
     }
 
@@ -1244,9 +2259,7 @@ impl ETOPaySdk {
     /// * Err - if there was an error contacting the backend.
     pub async fn set_preferred_network(&self, network_id: Option<String>) -> Result<(), String> {
         let mut sdk = self.inner.write().await;
-        sdk.set_preferred_network(network_id)
-            .await
-            .map_err(|err| format!("{:#?}", err))
+        sdk.set_preferred_network(network_id).await.map_err(error_string)
     }
 
     /// Get sdk build information.
@@ -1541,6 +2554,156 @@ impl WalletTxInfo {
     }
 }
 
+pub struct WatchedTransaction {
+    pub transaction_hash: String,
+    pub network_id: String,
+    pub status: String,
+    pub confirmations: u64,
+}
+
+impl WatchedTransaction {
+    pub fn transaction_hash(&self) -> String {
+        self.transaction_hash.clone()
+    }
+
+    pub fn network_id(&self) -> String {
+        self.network_id.clone()
+    }
+
+    pub fn status(&self) -> String {
+        self.status.clone()
+    }
+
+    pub fn confirmations(&self) -> u64 {
+        self.confirmations
+    }
+}
+
+pub struct UnsignedTransactionDetails {
+    pub from: String,
+    pub to: String,
+    pub value: String,
+    pub data: Vec<u8>,
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: String,
+    pub max_priority_fee_per_gas: String,
+    pub max_fee: String,
+}
+
+impl UnsignedTransactionDetails {
+    pub fn from(&self) -> String {
+        self.from.clone()
+    }
+
+    pub fn to(&self) -> String {
+        self.to.clone()
+    }
+
+    pub fn value(&self) -> String {
+        self.value.clone()
+    }
+
+    pub fn data(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    pub fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    pub fn max_fee_per_gas(&self) -> String {
+        self.max_fee_per_gas.clone()
+    }
+
+    pub fn max_priority_fee_per_gas(&self) -> String {
+        self.max_priority_fee_per_gas.clone()
+    }
+
+    pub fn max_fee(&self) -> String {
+        self.max_fee.clone()
+    }
+}
+
+pub struct ShareHealthReport {
+    pub local: String,
+    pub backup: String,
+    pub recovery: String,
+    pub provided_recovery: String,
+    pub additional: Vec<String>,
+    pub needs_repair: bool,
+}
+
+impl ShareHealthReport {
+    pub fn local(&self) -> String {
+        self.local.clone()
+    }
+
+    pub fn backup(&self) -> String {
+        self.backup.clone()
+    }
+
+    pub fn recovery(&self) -> String {
+        self.recovery.clone()
+    }
+
+    pub fn provided_recovery(&self) -> String {
+        self.provided_recovery.clone()
+    }
+
+    pub fn additional(&self) -> Vec<String> {
+        self.additional.clone()
+    }
+
+    pub fn needs_repair(&self) -> bool {
+        self.needs_repair
+    }
+}
+
+pub struct UserSummary {
+    pub username: String,
+    pub created_at: i64,
+    pub kyc_type: String,
+    pub has_wallet: bool,
+    pub last_used_at: i64,
+    pub locked: bool,
+}
+
+impl UserSummary {
+    pub fn username(&self) -> String {
+        self.username.clone()
+    }
+
+    pub fn created_at(&self) -> i64 {
+        self.created_at
+    }
+
+    pub fn kyc_type(&self) -> String {
+        self.kyc_type.clone()
+    }
+
+    pub fn has_wallet(&self) -> bool {
+        self.has_wallet
+    }
+
+    pub fn last_used_at(&self) -> i64 {
+        self.last_used_at
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+}
+
 pub struct Network {
     pub id: String,
     pub name: String,
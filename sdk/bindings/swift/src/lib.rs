@@ -12,6 +12,7 @@ mod utils;
 use ffi_functions::*;
 mod ffi_functions;
 mod type_conversion;
+mod user_storage;
 
 #[swift_bridge::bridge]
 pub mod ffi {
@@ -243,6 +244,52 @@ pub mod ffi {
         fn explorer_url(&self) -> String;
     }
 
+    extern "Rust" {
+        type WatchedTransaction;
+
+        fn transaction_hash(&self) -> String;
+        fn network_id(&self) -> String;
+        fn status(&self) -> String;
+        fn confirmations(&self) -> u64;
+    }
+
+    extern "Rust" {
+        type UnsignedTransactionDetails;
+
+        fn from(&self) -> String;
+        fn to(&self) -> String;
+        fn value(&self) -> String;
+        fn data(&self) -> Vec<u8>;
+        fn chain_id(&self) -> u64;
+        fn nonce(&self) -> u64;
+        fn gas_limit(&self) -> u64;
+        fn max_fee_per_gas(&self) -> String;
+        fn max_priority_fee_per_gas(&self) -> String;
+        fn max_fee(&self) -> String;
+    }
+
+    extern "Rust" {
+        type UserSummary;
+
+        fn username(&self) -> String;
+        fn created_at(&self) -> i64;
+        fn kyc_type(&self) -> String;
+        fn has_wallet(&self) -> bool;
+        fn last_used_at(&self) -> i64;
+        fn locked(&self) -> bool;
+    }
+
+    extern "Rust" {
+        type ShareHealthReport;
+
+        fn local(&self) -> String;
+        fn backup(&self) -> String;
+        fn recovery(&self) -> String;
+        fn provided_recovery(&self) -> String;
+        fn additional(&self) -> Vec<String>;
+        fn needs_repair(&self) -> bool;
+    }
+
     extern "Rust" {
         type Network;
 
@@ -250,6 +297,19 @@ pub mod ffi {
         fn name(&self) -> String;
     }
 
+    // Storage of the users implemented by the Swift application, see `UserStorage.swift`.
+    extern "Swift" {
+        type UserStorage;
+
+        #[swift_bridge(swift_name = "getItem")]
+        fn get_item(&self, key: String) -> Option<String>;
+        #[swift_bridge(swift_name = "setItem")]
+        fn set_item(&self, key: String, value: String) -> bool;
+        #[swift_bridge(swift_name = "removeItem")]
+        fn remove_item(&self, key: String) -> bool;
+        fn keys(&self) -> Vec<String>;
+    }
+
     // Export Rust functions with the above shared types for Swift.
     extern "Rust" {
         type ETOPaySdk;
@@ -259,6 +319,10 @@ pub mod ffi {
 
         #[swift_bridge(swift_name = "setConfig")]
         async fn set_config(&self, config: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "setUserStorage")]
+        async fn set_user_storage(&self, storage: UserStorage);
+        #[swift_bridge(swift_name = "enableUserStorageEncryptionWithPin")]
+        async fn enable_user_storage_encryption_with_pin(&self) -> Result<(), String>;
         #[swift_bridge(swift_name = "getNetworks")]
         async fn get_networks(&self) -> Result<Vec<Network>, String>;
         #[swift_bridge(swift_name = "setNetwork")]
@@ -270,14 +334,52 @@ pub mod ffi {
         async fn init_user(&self, username: String) -> Result<(), String>;
         #[swift_bridge(swift_name = "refreshAccessToken")]
         async fn refresh_access_token(&self, access_token: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "listUsers")]
+        async fn list_users(&self) -> Result<Vec<UserSummary>, String>;
+        async fn logout(&self) -> Result<(), String>;
+        #[swift_bridge(swift_name = "switchUser")]
+        async fn switch_user(&self, username: String, access_token: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "exportUser")]
+        async fn export_user(&self, export_password: String, pin: Option<String>) -> Result<Vec<u8>, String>;
+        #[swift_bridge(swift_name = "importUser")]
+        async fn import_user(&self, username: String, export: Vec<u8>, export_password: String) -> Result<(), String>;
         #[swift_bridge(swift_name = "isKycVerified")]
         async fn is_kyc_verified(&self, username: String) -> Result<bool, String>;
         #[swift_bridge(swift_name = "verifyMnemonic")]
         async fn verify_mnemonic(&self, pin: String, mnemonic: String) -> Result<bool, String>;
+        #[swift_bridge(swift_name = "verifyMnemonicWithPassphrase")]
+        async fn verify_mnemonic_with_passphrase(
+            &self,
+            pin: String,
+            mnemonic: String,
+            passphrase: String,
+        ) -> Result<bool, String>;
         #[swift_bridge(swift_name = "createNewWallet")]
-        async fn create_new_wallet(&self, pin: String) -> Result<String, String>;
+        async fn create_new_wallet(
+            &self,
+            pin: String,
+            word_count: Option<u8>,
+            language: Option<String>,
+        ) -> Result<String, String>;
+        #[swift_bridge(swift_name = "createNewWalletWithPassphrase")]
+        async fn create_new_wallet_with_passphrase(
+            &self,
+            pin: String,
+            passphrase: String,
+            word_count: Option<u8>,
+            language: Option<String>,
+        ) -> Result<String, String>;
         #[swift_bridge(swift_name = "createWalletFromMnemonic")]
         async fn create_wallet_from_mnemonic(&self, pin: String, mnemonic: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "createWalletFromMnemonicWithPassphrase")]
+        async fn create_wallet_from_mnemonic_with_passphrase(
+            &self,
+            pin: String,
+            mnemonic: String,
+            passphrase: String,
+        ) -> Result<(), String>;
+        #[swift_bridge(swift_name = "setWalletPassphrase")]
+        async fn set_wallet_passphrase(&self, pin: String, passphrase: String) -> Result<(), String>;
         #[swift_bridge(swift_name = "restoreWalletFromBackup")]
         async fn restore_wallet_from_backup(
             &self,
@@ -287,12 +389,36 @@ pub mod ffi {
         ) -> Result<(), String>;
         #[swift_bridge(swift_name = "createWalletBackup")]
         async fn create_wallet_backup(&self, pin: String, password: String) -> Result<Vec<u8>, String>;
+        #[swift_bridge(swift_name = "createJsonWalletBackup")]
+        async fn create_json_wallet_backup(&self, pin: String, password: String) -> Result<Vec<u8>, String>;
         #[swift_bridge(swift_name = "deleteWallet")]
         async fn delete_wallet(&self, pin: String) -> Result<(), String>;
         #[swift_bridge(swift_name = "generateNewAddress")]
         async fn generate_new_address(&self, pin: String) -> Result<String, String>;
         #[swift_bridge(swift_name = "getWalletBalance")]
         async fn get_balance(&self, pin: String) -> Result<f64, String>;
+        #[swift_bridge(swift_name = "deriveNewAddress")]
+        async fn derive_new_address(&self, pin: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "getWalletAddresses")]
+        async fn get_wallet_addresses(&self, pin: String) -> Result<Vec<String>, String>;
+        #[swift_bridge(swift_name = "getAddressBalance")]
+        async fn get_address_balance(&self, pin: String, address: String) -> Result<f64, String>;
+        #[swift_bridge(swift_name = "setWatchOnlyAddresses")]
+        async fn set_watch_only_addresses(&self, pin: String, addresses: Vec<String>) -> Result<(), String>;
+        #[swift_bridge(swift_name = "setWatchOnlyXpub")]
+        async fn set_watch_only_xpub(&self, pin: String, xpub: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "enableWatchOnly")]
+        async fn enable_watch_only(&self, pin: String) -> Result<Vec<String>, String>;
+        #[swift_bridge(swift_name = "getWatchOnlyAddress")]
+        async fn get_watch_only_address(&self) -> Result<String, String>;
+        #[swift_bridge(swift_name = "getWatchOnlyBalance")]
+        async fn get_watch_only_balance(&self) -> Result<f64, String>;
+        #[swift_bridge(swift_name = "getWatchOnlyTransactionList")]
+        async fn get_watch_only_transaction_list(
+            &self,
+            start: usize,
+            limit: usize,
+        ) -> Result<Vec<WalletTxInfo>, String>;
 
         // functions for postident, actual implementation is hidden behind feature flag
         #[swift_bridge(swift_name = "initKycVerificationForPostident")]
@@ -383,6 +509,21 @@ pub mod ffi {
         async fn reset_pin(&self, pin: String, new_pin: String) -> Result<(), String>;
         #[swift_bridge(swift_name = "setWalletPassword")]
         async fn set_wallet_password(&self, pin: String, new_password: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "recoverWalletWithMnemonic")]
+        async fn recover_wallet_with_mnemonic(
+            &self,
+            new_pin: String,
+            new_password: String,
+            mnemonic: String,
+        ) -> Result<(), String>;
+        #[swift_bridge(swift_name = "recoverWalletWithMnemonicAndPassphrase")]
+        async fn recover_wallet_with_mnemonic_and_passphrase(
+            &self,
+            new_pin: String,
+            new_password: String,
+            mnemonic: String,
+            passphrase: String,
+        ) -> Result<(), String>;
         #[swift_bridge(swift_name = "isWalletPasswordSet")]
         pub async fn is_wallet_password_set(&self) -> Result<bool, String>;
         #[swift_bridge(swift_name = "sendAmount")]
@@ -393,6 +534,50 @@ pub mod ffi {
             amount: f64,
             data: Option<Vec<u8>>,
         ) -> Result<String, String>;
+        #[swift_bridge(swift_name = "sendAmountFrom")]
+        async fn send_amount_from(
+            &self,
+            pin: String,
+            sender: String,
+            address: String,
+            amount: f64,
+            data: Option<Vec<u8>>,
+        ) -> Result<String, String>;
+        #[swift_bridge(swift_name = "speedUpTransaction")]
+        async fn speed_up_transaction(&self, pin: String, transaction_hash: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "cancelTransaction")]
+        async fn cancel_transaction(&self, pin: String, transaction_hash: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "rebroadcastPendingTransactions")]
+        async fn rebroadcast_pending_transactions(&self, pin: String) -> Result<Vec<String>, String>;
+        #[swift_bridge(swift_name = "prepareUnsignedTransaction")]
+        async fn prepare_unsigned_transaction(
+            &self,
+            pin: Option<String>,
+            sender: Option<String>,
+            address: String,
+            amount: f64,
+            data: Option<Vec<u8>>,
+        ) -> Result<String, String>;
+        #[swift_bridge(swift_name = "reviewUnsignedTransaction")]
+        async fn review_unsigned_transaction(
+            &self,
+            pin: String,
+            unsigned_transaction: String,
+        ) -> Result<UnsignedTransactionDetails, String>;
+        #[swift_bridge(swift_name = "signUnsignedTransaction")]
+        async fn sign_unsigned_transaction(&self, pin: String, unsigned_transaction: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "broadcastSignedTransaction")]
+        async fn broadcast_signed_transaction(
+            &self,
+            pin: Option<String>,
+            signed_transaction: String,
+        ) -> Result<String, String>;
+        #[swift_bridge(swift_name = "checkWatchedTransactions")]
+        async fn check_watched_transactions(&self, pin: String) -> Result<Vec<WatchedTransaction>, String>;
+        #[swift_bridge(swift_name = "getWatchedTransactions")]
+        async fn get_watched_transactions(&self) -> Result<Vec<WatchedTransaction>, String>;
+        #[swift_bridge(swift_name = "nextTransactionStatus")]
+        async fn next_transaction_status(&self) -> Option<WatchedTransaction>;
         #[swift_bridge(swift_name = "updateIbanViviswap")]
         async fn update_iban_viviswap(&self, pin: String, address: String) -> Result<ViviswapAddressDetail, String>;
         #[swift_bridge(swift_name = "getIbanViviswap")]
@@ -432,6 +617,39 @@ pub mod ffi {
         async fn get_recovery_share(&self) -> Result<String, String>;
         #[swift_bridge(swift_name = "setRecoveryShare")]
         async fn set_recovery_share(&self, share: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "getAdditionalShares")]
+        async fn get_additional_shares(&self) -> Result<Vec<String>, String>;
+        #[swift_bridge(swift_name = "setAdditionalShares")]
+        async fn set_additional_shares(&self, shares: Vec<String>) -> Result<(), String>;
+        #[swift_bridge(swift_name = "checkShareHealth")]
+        async fn check_share_health(&self, pin: String) -> Result<ShareHealthReport, String>;
+        #[swift_bridge(swift_name = "repairShares")]
+        async fn repair_shares(&self, pin: String) -> Result<(), String>;
+        #[swift_bridge(swift_name = "setOfflineUnlock")]
+        async fn set_offline_unlock(&self, pin: String, enabled: bool) -> Result<(), String>;
+        #[swift_bridge(swift_name = "getGuardianPublicKey")]
+        async fn get_guardian_public_key(&self, pin: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "setGuardians")]
+        async fn set_guardians(
+            &self,
+            pin: String,
+            usernames: Vec<String>,
+            public_keys: Vec<String>,
+            threshold: u8,
+        ) -> Result<(), String>;
+        #[swift_bridge(swift_name = "startGuardianRecovery")]
+        async fn start_guardian_recovery(&self, pin: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "getGuardianRecoveryFingerprint")]
+        async fn get_guardian_recovery_fingerprint(&self, username: String) -> Result<String, String>;
+        #[swift_bridge(swift_name = "approveGuardianRecovery")]
+        async fn approve_guardian_recovery(
+            &self,
+            pin: String,
+            username: String,
+            fingerprint: String,
+        ) -> Result<(), String>;
+        #[swift_bridge(swift_name = "completeGuardianRecovery")]
+        async fn complete_guardian_recovery(&self, pin: String) -> Result<(), String>;
         #[swift_bridge(swift_name = "getPreferredNetwork")]
         async fn get_preferred_network(&self) -> Result<String, String>;
         #[swift_bridge(swift_name = "setPreferredNetwork")]
@@ -238,6 +238,60 @@ impl From<sdk::types::transactions::WalletTxInfo> for crate::ffi_functions::Wall
     }
 }
 
+impl From<sdk::types::transactions::WatchedTransaction> for crate::ffi_functions::WatchedTransaction {
+    fn from(value: sdk::types::transactions::WatchedTransaction) -> Self {
+        crate::ffi_functions::WatchedTransaction {
+            transaction_hash: value.transaction_hash,
+            network_id: value.network_id,
+            status: format!("{:?}", value.status),
+            confirmations: value.confirmations,
+        }
+    }
+}
+
+impl From<sdk::types::transactions::UnsignedTransactionDetails> for crate::ffi_functions::UnsignedTransactionDetails {
+    fn from(value: sdk::types::transactions::UnsignedTransactionDetails) -> Self {
+        crate::ffi_functions::UnsignedTransactionDetails {
+            from: value.from,
+            to: value.to,
+            value: value.value.to_string(),
+            data: value.data,
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas.to_string(),
+            max_fee: value.max_fee.to_string(),
+        }
+    }
+}
+
+impl From<sdk::types::users::UserSummary> for crate::ffi_functions::UserSummary {
+    fn from(value: sdk::types::users::UserSummary) -> Self {
+        crate::ffi_functions::UserSummary {
+            username: value.username,
+            created_at: value.created_at.unwrap_or(0),
+            kyc_type: format!("{:?}", value.kyc_type),
+            has_wallet: value.has_wallet,
+            last_used_at: value.last_used_at.unwrap_or(0),
+            locked: value.locked,
+        }
+    }
+}
+
+impl From<sdk::share::ShareHealthReport> for crate::ffi_functions::ShareHealthReport {
+    fn from(value: sdk::share::ShareHealthReport) -> Self {
+        crate::ffi_functions::ShareHealthReport {
+            needs_repair: value.needs_repair(),
+            local: format!("{:?}", value.local),
+            backup: format!("{:?}", value.backup),
+            recovery: format!("{:?}", value.recovery),
+            provided_recovery: format!("{:?}", value.provided_recovery),
+            additional: value.additional.iter().map(|health| format!("{health:?}")).collect(),
+        }
+    }
+}
+
 impl From<sdk::types::networks::Network> for crate::ffi_functions::Network {
     fn from(value: sdk::types::networks::Network) -> Self {
         crate::ffi_functions::Network {
@@ -0,0 +1,44 @@
+//! Connects a user storage of the Swift application to the SDK.
+
+use crate::ffi::UserStorage;
+use sdk::user::callback_storage::UserStorageCallbacks;
+
+/// The callbacks of a [`UserStorage`] of the Swift application.
+pub struct SwiftUserStorageCallbacks(UserStorage);
+
+impl SwiftUserStorageCallbacks {
+    pub fn new(storage: UserStorage) -> Self {
+        Self(storage)
+    }
+}
+
+// SAFETY: the SDK requires a `Send + Sync` storage. The `UserStorageProtocol` of the Swift application is
+// documented to be thread-safe, and the `UserStorage` class only holds a reference to it.
+unsafe impl Send for SwiftUserStorageCallbacks {}
+unsafe impl Sync for SwiftUserStorageCallbacks {}
+
+impl UserStorageCallbacks for SwiftUserStorageCallbacks {
+    fn get_item(&self, key: &str) -> Result<Option<String>, String> {
+        Ok(self.0.get_item(key.to_string()))
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<(), String> {
+        if self.0.set_item(key.to_string(), value.to_string()) {
+            Ok(())
+        } else {
+            Err(format!("the value of {key} could not be stored"))
+        }
+    }
+
+    fn remove_item(&self, key: &str) -> Result<(), String> {
+        if self.0.remove_item(key.to_string()) {
+            Ok(())
+        } else {
+            Err(format!("{key} could not be removed"))
+        }
+    }
+
+    fn keys(&self) -> Result<Vec<String>, String> {
+        Ok(self.0.keys())
+    }
+}
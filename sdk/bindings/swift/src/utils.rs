@@ -1,4 +1,10 @@
-//! helper macros for generating From implementations for enums and structs
+//! helper for formatting errors and macros for generating From implementations for enums and structs
+
+/// Formats an error for the client app as JSON object with a stable error code to branch on, see
+/// [`sdk::Error::to_binding_string`].
+pub fn error_string(error: impl Into<sdk::Error>) -> String {
+    error.into().to_binding_string()
+}
 
 /// Macro to convert between two enums with the same variants (e.g. sdk and FFI types)
 #[macro_export]
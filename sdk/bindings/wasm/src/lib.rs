@@ -1,29 +1,50 @@
 //! Contains the bindings for WASM, needs to be compiled to the `wasm32-unknown-unknown` target.
 
 mod types;
+mod user_storage;
 mod utils;
 
 use crate::types::*;
-use crate::utils::set_panic_hook;
+use crate::user_storage::{JsUserStorage, JsUserStorageCallbacks};
+use crate::utils::{error_string, set_panic_hook};
 
 #[cfg(feature = "viviswap-kyc")]
 use sdk::types::File;
 
 use sdk::{
+    core::watcher::TransactionStatusStream,
     core::{Config, Sdk},
+    mnemonic::MnemonicOptions,
     types::{
         currencies::CryptoAmount,
-        newtypes::{AccessToken, EncryptionPin, PlainPassword},
+        newtypes::{AccessToken, EncryptionPin, MnemonicPassphrase, PlainPassword},
+        transactions::{PendingTransaction, UnsignedTransaction},
     },
+    user::{callback_storage::CallbackUserStorage, encryption::UserStorageEncryption},
+    watch_only::WatchOnlySource,
 };
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use wasm_bindgen::prelude::*;
 
 /// Main object that contains all the functionality for interfacing with the ETOPaySdk.
 #[wasm_bindgen]
 pub struct ETOPaySdk {
     inner: Arc<RwLock<Sdk>>,
+    transaction_status: Mutex<TransactionStatusStream>,
+}
+
+impl ETOPaySdk {
+    /// Runs a function of the SDK. The users stored in the IndexedDB are read again before, so the changes of
+    /// other tabs and workers are not overwritten, and the changes of the function are written before it returns.
+    async fn call<T>(&self, f: impl AsyncFnOnce(&mut Sdk) -> Result<T, String>) -> Result<T, String> {
+        let mut sdk = self.inner.write().await;
+        sdk.reload_user_storage().await.map_err(error_string)?;
+        let result = f(&mut sdk).await;
+        let flushed = sdk.flush_user_storage().await.map_err(error_string);
+        let value = result?;
+        flushed.map(|()| value)
+    }
 }
 
 #[wasm_bindgen]
@@ -37,9 +58,11 @@ impl ETOPaySdk {
         set_panic_hook();
 
         let sdk = Sdk::default();
+        let transaction_status = Mutex::new(sdk.subscribe_transaction_status());
 
         Self {
             inner: Arc::new(RwLock::new(sdk)),
+            transaction_status,
         }
     }
 
@@ -60,10 +83,62 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "setConfig")]
     pub async fn set_config(&self, config: String) -> Result<(), String> {
+        self.call(async move |sdk| {
+            Config::from_json(&config)
+                .and_then(|r| sdk.set_config(r))
+                .map_err(error_string)?;
+
+            // prefer the IndexedDB, which is also available in workers, and keep the local storage otherwise
+            if sdk.uses_custom_user_storage() {
+                return Ok(());
+            }
+            if let Err(e) = sdk.use_indexed_db_user_storage().await {
+                log::warn!("Could not open the IndexedDB user storage: {e:#?}");
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Stores the users in a storage of the application instead of the browser storage. The storage implements
+    /// the `UserStorage` interface, i.e. the synchronous methods `getItem`, `setItem`, `removeItem` and `keys`
+    /// of string keys and values, like `window.localStorage`. The users are stored as encoded records under keys
+    /// with the prefix `etopay.local.user.`. Call this before `setConfig` and `initUser`, the active user is
+    /// logged out and the users of the previous storage are not moved.
+    ///
+    /// @param {UserStorage} storage The storage of the application.
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setUserStorage")]
+    pub async fn set_user_storage(&self, storage: JsUserStorage) {
         let mut sdk = self.inner.write().await;
-        Config::from_json(&config)
-            .and_then(|r| sdk.set_config(r))
-            .map_err(|err| format!("{:#?}", err))
+        sdk.set_user_storage(CallbackUserStorage::new(JsUserStorageCallbacks::new(storage)));
+    }
+
+    /// Encrypts the users stored in the browser with a key derived from the pin. A stored user
+    /// is locked after `initUser` until the pin is entered, e.g. with `verifyPin`.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "enableUserStorageEncryptionWithPin")]
+    pub async fn enable_user_storage_encryption_with_pin(&self) -> Result<(), String> {
+        self.call(async move |sdk| {
+            sdk.set_user_storage_encryption(UserStorageEncryption::Pin)
+                .map_err(error_string)?;
+            if sdk.uses_indexed_db_user_storage() {
+                sdk.use_indexed_db_user_storage().await.map_err(error_string)?;
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    /// Waits until all changes of the users are written to the IndexedDB. Fails if a write failed, e.g. because
+    /// the storage quota of the browser is exceeded.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "flushUserStorage")]
+    pub async fn flush_user_storage(&self) -> Result<(), String> {
+        let sdk = self.inner.read().await;
+        sdk.flush_user_storage().await.map_err(error_string)
     }
 
     /// Selects the network for the ETOPay SDK.
@@ -72,8 +147,8 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "setNetwork")]
     pub async fn set_network(&self, network_id: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        sdk.set_network(network_id).await.map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| sdk.set_network(network_id).await.map_err(error_string))
+            .await
     }
 
     /// Fetch available networks.
@@ -81,16 +156,18 @@ impl ETOPaySdk {
     /// @returns {Option<Vec<Network>>} Sdk networks
     #[wasm_bindgen(skip_jsdoc, js_name = "getNetworks")]
     pub async fn get_networks(&self) -> Result<Vec<Network>, String> {
-        let mut sdk = self.inner.write().await;
-        let networks = sdk
-            .get_networks()
-            .await
-            .map_err(|e| format!("{e:#?}"))?
-            .iter()
-            .map(|n| Network::from(n.clone()))
-            .collect::<Vec<Network>>();
+        self.call(async move |sdk| {
+            let networks = sdk
+                .get_networks()
+                .await
+                .map_err(error_string)?
+                .iter()
+                .map(|n| Network::from(n.clone()))
+                .collect::<Vec<Network>>();
 
-        Ok(networks)
+            Ok(networks)
+        })
+        .await
     }
 
     /// Initializes the etopay logger
@@ -108,8 +185,8 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "createNewUser")]
     pub async fn create_new_user(&self, username: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        sdk.create_new_user(&username).await.map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| sdk.create_new_user(&username).await.map_err(error_string))
+            .await
     }
 
     /// Initializes an existing user in the SDK
@@ -118,8 +195,99 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "initializeUser")]
     pub async fn initialize_user(&self, username: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        sdk.init_user(&username).await.map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| sdk.init_user(&username).await.map_err(error_string))
+            .await
+    }
+
+    /// Lists the users stored on this device.
+    ///
+    /// @returns {Promise<UserSummary[]>} The users sorted by username
+    #[wasm_bindgen(skip_jsdoc, js_name = "listUsers")]
+    pub async fn list_users(&self) -> Result<Vec<UserSummary>, String> {
+        self.call(async move |sdk| {
+            sdk.list_users()
+                .await
+                .map(|users| users.into_iter().map(Into::into).collect())
+                .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Logs out the active user and drops its cached wallet state and access token.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "logout")]
+    pub async fn logout(&self) -> Result<(), String> {
+        self.call(async move |sdk| sdk.logout().await.map_err(error_string))
+            .await
+    }
+
+    /// Logs out the active user and initializes another user stored on this device.
+    ///
+    /// @param {string} username - The username of the user to switch to.
+    /// @param {string} access_token - The access token of the user. Empty to set it later with `refreshAccessToken`.
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "switchUser")]
+    pub async fn switch_user(&self, username: String, access_token: String) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let access_token = if access_token.is_empty() {
+                    None
+                } else {
+                    Some(AccessToken::try_from(access_token)?)
+                };
+                sdk.switch_user(&username, access_token).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Exports the locally stored record of the active user, encrypted with the export password.
+    ///
+    /// @param {string} export_password - The password to encrypt the export with.
+    /// @param {string} pin - The wallet pin for confirmation. Optional in case there is no wallet.
+    /// @returns {Promise<Uint8Array>} The bytes of the exported user
+    #[wasm_bindgen(skip_jsdoc, js_name = "exportUser")]
+    pub async fn export_user(&self, export_password: String, pin: Option<String>) -> Result<Vec<u8>, String> {
+        self.call(async move |sdk| {
+            async move {
+                let export_password = PlainPassword::try_from_string(export_password)?;
+                let encryption_pin = match pin {
+                    Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                    None => None,
+                };
+                sdk.export_user(encryption_pin.as_ref(), &export_password).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Imports a user exported with `exportUser`.
+    ///
+    /// @param {string} username - The username of the exported user.
+    /// @param {Uint8Array} export_bytes - The bytes of the exported user.
+    /// @param {string} export_password - The password the user was exported with.
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "importUser")]
+    pub async fn import_user(
+        &self,
+        username: String,
+        export_bytes: Vec<u8>,
+        export_password: String,
+    ) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let export_password = PlainPassword::try_from_string(export_password)?;
+                sdk.import_user(&username, &export_bytes, &export_password).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
     }
 
     /// Refreshes the access token for the user in the SDK.
@@ -128,17 +296,19 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "refreshAccessToken")]
     pub async fn refresh_access_token(&self, access_token: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let access_token = if access_token.is_empty() {
-                None
-            } else {
-                Some(AccessToken::try_from(access_token)?)
-            };
-            sdk.refresh_access_token(access_token).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let access_token = if access_token.is_empty() {
+                    None
+                } else {
+                    Some(AccessToken::try_from(access_token)?)
+                };
+                sdk.refresh_access_token(access_token).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Fetches the kyc verification status for the user
@@ -148,25 +318,65 @@ impl ETOPaySdk {
     /// @returns {Promise<bool>} The kyc verification status as a boolean value
     #[wasm_bindgen(skip_jsdoc, js_name = "isKycVerified")]
     pub async fn is_kyc_verified(&self, username: String) -> Result<bool, String> {
-        let mut sdk = self.inner.write().await;
-        sdk.is_kyc_status_verified(&username)
+        self.call(async move |sdk| sdk.is_kyc_status_verified(&username).await.map_err(error_string))
             .await
-            .map_err(|e| format!("{e:#?}"))
     }
 
     /// Creates a new random wallet and returns the mnemonic.
     ///
     /// @param {string} pin - The input string representing the pin.
+    /// @param {number | undefined} word_count - The number of words of the mnemonic: 12, 15, 18, 21 or 24 (default).
+    /// @param {string | undefined} language - The wordlist language of the mnemonic, e.g. `english` (default),
+    /// `chinese_simplified`, `chinese_traditional`, `czech`, `french`, `italian`, `japanese`, `korean`,
+    /// `portuguese` or `spanish`.
     /// @returns {Promise<String>}
     #[wasm_bindgen(skip_jsdoc, js_name = "createNewWallet")]
-    pub async fn create_new_wallet(&self, pin: String) -> Result<String, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.create_wallet_from_new_mnemonic(&pin).await
-        }
+    pub async fn create_new_wallet(
+        &self,
+        pin: String,
+        word_count: Option<u8>,
+        language: Option<String>,
+    ) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let options = MnemonicOptions::try_from_parts(word_count, language.as_deref())?;
+                sdk.create_wallet_from_new_mnemonic(&pin, &options).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Creates a new random wallet with a BIP-39 passphrase (25th word) and returns the mnemonic. The
+    /// passphrase is not part of the wallet shares and is stored encrypted on this device.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} passphrase - The BIP-39 passphrase of the wallet.
+    /// @param {number | undefined} word_count - The number of words of the mnemonic: 12, 15, 18, 21 or 24 (default).
+    /// @param {string | undefined} language - The wordlist language of the mnemonic, `english` by default.
+    /// @returns {Promise<String>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "createNewWalletWithPassphrase")]
+    pub async fn create_new_wallet_with_passphrase(
+        &self,
+        pin: String,
+        passphrase: String,
+        word_count: Option<u8>,
+        language: Option<String>,
+    ) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+                let options = MnemonicOptions::try_from_parts(word_count, language.as_deref())?;
+                sdk.create_wallet_from_new_mnemonic_with_passphrase(&pin, &passphrase, &options)
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Initializes a wallet from an existing mnemonic.
@@ -176,13 +386,62 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "createWalletFromMnemonic")]
     pub async fn create_wallet_from_mnemonic(&self, pin: String, mnemonic: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.create_wallet_from_existing_mnemonic(&pin, &mnemonic).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.create_wallet_from_existing_mnemonic(&pin, &mnemonic).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Initializes a wallet from an existing mnemonic and BIP-39 passphrase (25th word), e.g. to migrate a
+    /// wallet from another application. The passphrase is stored encrypted on this device.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} mnemonic - The input string representing the mnemonic
+    /// @param {string} passphrase - The BIP-39 passphrase used with the mnemonic.
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "createWalletFromMnemonicWithPassphrase")]
+    pub async fn create_wallet_from_mnemonic_with_passphrase(
+        &self,
+        pin: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+                sdk.create_wallet_from_existing_mnemonic_with_passphrase(&pin, &mnemonic, &passphrase)
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Sets the BIP-39 passphrase of the wallet on this device. It is needed on every device which restores a
+    /// wallet with a passphrase from its shares.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} passphrase - The BIP-39 passphrase of the wallet.
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setWalletPassphrase")]
+    pub async fn set_wallet_passphrase(&self, pin: String, passphrase: String) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+                sdk.set_wallet_passphrase(&pin, &passphrase).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Creates a wallet from a backup.
@@ -199,14 +458,16 @@ impl ETOPaySdk {
         backup: Vec<u8>,
         backup_password: String,
     ) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            let backup_password = PlainPassword::try_from_string(backup_password)?;
-            sdk.create_wallet_from_backup(&pin, &backup, &backup_password).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let backup_password = PlainPassword::try_from_string(backup_password)?;
+                sdk.create_wallet_from_backup(&pin, &backup, &backup_password).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Creates a wallet backup.
@@ -217,14 +478,37 @@ impl ETOPaySdk {
     /// @returns {Promise<Uint8Array>}
     #[wasm_bindgen(skip_jsdoc, js_name = "createWalletBackup")]
     pub async fn create_wallet_backup(&self, pin: String, backup_password: String) -> Result<Vec<u8>, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            let backup_password = PlainPassword::try_from_string(backup_password)?;
-            sdk.create_wallet_backup(&pin, &backup_password).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let backup_password = PlainPassword::try_from_string(backup_password)?;
+                sdk.create_wallet_backup(&pin, &backup_password).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Creates a versioned JSON wallet backup, which also contains the network ids, the preferred network,
+    /// the derived address indices and the labels of the additional shares.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} backup_password - Password used to create the backup.
+    ///
+    /// @returns {Promise<Uint8Array>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "createJsonWalletBackup")]
+    pub async fn create_json_wallet_backup(&self, pin: String, backup_password: String) -> Result<Vec<u8>, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let backup_password = PlainPassword::try_from_string(backup_password)?;
+                sdk.create_json_wallet_backup(&pin, &backup_password).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Deletes and existing wallet.
@@ -234,13 +518,15 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "deleteWallet")]
     pub async fn delete_wallet(&self, pin: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.delete_wallet(&pin).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.delete_wallet(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Verify if the provided mnemonic is the one stored in the wallet.
@@ -251,13 +537,41 @@ impl ETOPaySdk {
     /// @returns {Promise<boolean>} - whether the mnemonics are the same or not.
     #[wasm_bindgen(skip_jsdoc, js_name = "verifyMnemonic")]
     pub async fn verify_mnemonic(&self, pin: String, mnemonic: String) -> Result<bool, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.verify_mnemonic(&pin, &mnemonic).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.verify_mnemonic(&pin, &mnemonic).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Verify if the provided mnemonic and BIP-39 passphrase are the ones of the wallet.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} mnemonic - The input string representing the mnemonic.
+    /// @param {string} passphrase - The BIP-39 passphrase of the wallet.
+    ///
+    /// @returns {Promise<boolean>} - whether the mnemonics and passphrases are the same or not.
+    #[wasm_bindgen(skip_jsdoc, js_name = "verifyMnemonicWithPassphrase")]
+    pub async fn verify_mnemonic_with_passphrase(
+        &self,
+        pin: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<bool, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+                sdk.verify_mnemonic_with_passphrase(&pin, &mnemonic, &passphrase).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Generate a new receiver address based on selected network in the config.
@@ -267,13 +581,15 @@ impl ETOPaySdk {
     /// @returns {Promise<string>} The receiver wallet address as string
     #[wasm_bindgen(skip_jsdoc, js_name = "generateNewAddress")]
     pub async fn generate_new_address(&self, pin: String) -> Result<String, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.generate_new_address(&pin).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.generate_new_address(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Fetches the current balance of the base crypto network on the wallet
@@ -283,13 +599,174 @@ impl ETOPaySdk {
     /// @returns {Promise<number>} The current balance as a double precision floating point number
     #[wasm_bindgen(skip_jsdoc, js_name = "getWalletBalance")]
     pub async fn get_wallet_balance(&self, pin: String) -> Result<f64, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.get_balance(&pin).await.and_then(f64::try_from)
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.get_balance(&pin).await.and_then(f64::try_from)
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Derives a new address of the EVM wallet with the next unused address index.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<string>} The newly derived address
+    #[wasm_bindgen(skip_jsdoc, js_name = "deriveNewAddress")]
+    pub async fn derive_new_address(&self, pin: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.derive_new_address(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Fetches all addresses of the wallet in use, starting with the default address
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<string[]>} The addresses ordered by their address index
+    #[wasm_bindgen(skip_jsdoc, js_name = "getWalletAddresses")]
+    pub async fn get_wallet_addresses(&self, pin: String) -> Result<Vec<String>, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.get_wallet_addresses(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Fetches the current balance of a single address of the wallet
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} address - One of the addresses returned by {@link getWalletAddresses}.
+    ///
+    /// @returns {Promise<number>} The current balance as a double precision floating point number
+    #[wasm_bindgen(skip_jsdoc, js_name = "getAddressBalance")]
+    pub async fn get_address_balance(&self, pin: String, address: String) -> Result<f64, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.get_address_balance(&pin, &address).await.and_then(f64::try_from)
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Sets the EVM addresses watched by the watch-only wallet
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string[]} addresses - The addresses of the wallet, the first one is used as the default address.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setWatchOnlyAddresses")]
+    pub async fn set_watch_only_addresses(&self, pin: String, addresses: Vec<String>) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.set_watch_only_source(&pin, Some(WatchOnlySource::EvmAddresses(addresses)))
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Sets the extended public key of the EVM account watched by the watch-only wallet
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} xpub - The extended public key of the account at the derivation path `m/44'/60'/0'`.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setWatchOnlyXpub")]
+    pub async fn set_watch_only_xpub(&self, pin: String, xpub: String) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.set_watch_only_source(&pin, Some(WatchOnlySource::EvmAccountXpub(xpub)))
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Enables the watch-only wallet with the current addresses of the EVM wallet
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<string[]>} The addresses watched by the watch-only wallet
+    #[wasm_bindgen(skip_jsdoc, js_name = "enableWatchOnly")]
+    pub async fn enable_watch_only(&self, pin: String) -> Result<Vec<String>, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.enable_watch_only(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Fetches the default address of the watch-only wallet
+    ///
+    /// @returns {Promise<string>} The default address
+    #[wasm_bindgen(skip_jsdoc, js_name = "getWatchOnlyAddress")]
+    pub async fn get_watch_only_address(&self) -> Result<String, String> {
+        self.call(async move |sdk| sdk.get_watch_only_address().await.map_err(error_string))
+            .await
+    }
+
+    /// Fetches the current balance of the watch-only wallet without the pin
+    ///
+    /// @returns {Promise<number>} The current balance as a double precision floating point number
+    #[wasm_bindgen(skip_jsdoc, js_name = "getWatchOnlyBalance")]
+    pub async fn get_watch_only_balance(&self) -> Result<f64, String> {
+        self.call(async move |sdk| {
+            sdk.get_watch_only_balance()
+                .await
+                .and_then(f64::try_from)
+                .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Gets the detailed lists of transactions of the watch-only wallet without the pin
+    ///
+    /// @param {string} start - The start page
+    /// @param {string} limit - The limit per page
+    ///
+    /// @returns {Promise<WalletTxInfoList>} The list of wallet transactions
+    #[wasm_bindgen(skip_jsdoc, js_name = "getWatchOnlyTransactionList")]
+    pub async fn get_watch_only_transaction_list(
+        &self,
+        start: usize,
+        limit: usize,
+    ) -> Result<WalletTxInfoList, String> {
+        self.call(async move |sdk| {
+            sdk.get_watch_only_wallet_tx_list(start, limit)
+                .await
+                .map(|l| WalletTxInfoList {
+                    transactions: l.transactions.into_iter().map(Into::into).collect(),
+                })
+                .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Initialize the KYC process for Postident
@@ -301,14 +778,16 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "startKycVerificationForPostident")]
     pub async fn start_kyc_verification_for_postident(&self) -> Result<NewCaseIdResponse, String> {
         sdk::require_feature!("postident", {
-            let mut sdk = self.inner.write().await;
-            sdk.start_kyc_verification_for_postident()
-                .await
-                .map(|v| NewCaseIdResponse {
-                    case_id: v.case_id,
-                    case_url: v.case_url,
-                })
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.start_kyc_verification_for_postident()
+                    .await
+                    .map(|v| NewCaseIdResponse {
+                        case_id: v.case_id,
+                        case_url: v.case_url,
+                    })
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -321,15 +800,17 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getKycDetailsForPostident")]
     pub async fn get_kyc_details_for_postident(&self) -> Result<CaseDetailsResponse, String> {
         sdk::require_feature!("postident", {
-            let sdk = self.inner.write().await;
-            sdk.get_kyc_details_for_postident()
-                .await
-                .map(|v| CaseDetailsResponse {
-                    archived: v.archived,
-                    case_id: v.case_id,
-                    status: v.status,
-                })
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.get_kyc_details_for_postident()
+                    .await
+                    .map(|v| CaseDetailsResponse {
+                        archived: v.archived,
+                        case_id: v.case_id,
+                        status: v.status,
+                    })
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -343,10 +824,12 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "updateKycStatusForPostident")]
     pub async fn update_kyc_status_for_postident(&self, case_id: String) -> Result<(), String> {
         sdk::require_feature!("postident", {
-            let sdk = self.inner.write().await;
-            sdk.update_kyc_status_for_postident(&case_id)
-                .await
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.update_kyc_status_for_postident(&case_id)
+                    .await
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -368,14 +851,16 @@ impl ETOPaySdk {
         app_data: String,
         purchase_type: String,
     ) -> Result<String, String> {
-        let sdk = self.inner.write().await;
-        async move {
-            let amount = CryptoAmount::try_from(amount)?;
-            sdk.create_purchase_request(&receiver, amount, &product_hash, &app_data, &purchase_type)
-                .await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let amount = CryptoAmount::try_from(amount)?;
+                sdk.create_purchase_request(&receiver, amount, &product_hash, &app_data, &purchase_type)
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Fetches the purchase details from the given purchase ID.
@@ -385,24 +870,26 @@ impl ETOPaySdk {
     /// @returns {Promise<PurchseDetails>} The purchase details
     #[wasm_bindgen(skip_jsdoc, js_name = "getPurchaseDetails")]
     pub async fn get_purchase_details(&self, purchase_id: String) -> Result<PurchaseDetails, String> {
-        let sdk = self.inner.write().await;
-        sdk.get_purchase_details(&purchase_id)
-            .await
-            .and_then(|v| {
-                let invalid_reasons = match v.clone().status {
-                    sdk::types::ApiTxStatus::WaitingForVerification(r) => r,
-                    sdk::types::ApiTxStatus::Invalid(r) => r,
-                    _ => Vec::new(),
-                };
-
-                Ok(PurchaseDetails {
-                    main_address: v.system_address,
-                    amount: f64::try_from(v.amount)?,
-                    status: v.status.into(),
-                    invalid_reasons,
+        self.call(async move |sdk| {
+            sdk.get_purchase_details(&purchase_id)
+                .await
+                .and_then(|v| {
+                    let invalid_reasons = match v.clone().status {
+                        sdk::types::ApiTxStatus::WaitingForVerification(r) => r,
+                        sdk::types::ApiTxStatus::Invalid(r) => r,
+                        _ => Vec::new(),
+                    };
+
+                    Ok(PurchaseDetails {
+                        main_address: v.system_address,
+                        amount: f64::try_from(v.amount)?,
+                        status: v.status.into(),
+                        invalid_reasons,
+                    })
                 })
-            })
-            .map_err(|e| format!("{e:#?}"))
+                .map_err(error_string)
+        })
+        .await
     }
 
     /// Confirm the purchase for the given purchase ID.
@@ -412,13 +899,15 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "confirmPurchaseRequest")]
     pub async fn confirm_purchase_request(&self, pin: String, purchase_id: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.confirm_purchase_request(&pin, &purchase_id).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.confirm_purchase_request(&pin, &purchase_id).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
     /// Set the password to use for wallet operations. If the password was already set, this changes it.
     ///
@@ -427,14 +916,73 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "setWalletPassword")]
     pub async fn set_wallet_password(&self, pin: String, new_password: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            let new_password = PlainPassword::try_from_string(new_password)?;
-            sdk.set_wallet_password(&pin, &new_password).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let new_password = PlainPassword::try_from_string(new_password)?;
+                sdk.set_wallet_password(&pin, &new_password).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Recovers the wallet with the mnemonic, for example after it was locked because of too many wrong pin attempts.
+    /// Sets a new pin and password and replaces the existing wallet shares.
+    ///
+    /// @param {string} new_pin - The new pin to set for the wallet
+    /// @param {string} new_password - The new password to set for the wallet
+    /// @param {string} mnemonic - The mnemonic of the wallet
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "recoverWalletWithMnemonic")]
+    pub async fn recover_wallet_with_mnemonic(
+        &self,
+        new_pin: String,
+        new_password: String,
+        mnemonic: String,
+    ) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let new_pin = EncryptionPin::try_from_string(new_pin)?;
+                let new_password = PlainPassword::try_from_string(new_password)?;
+                sdk.recover_wallet_with_mnemonic(&new_pin, &new_password, &mnemonic)
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Recovers a wallet which uses a BIP-39 passphrase with the mnemonic and the passphrase, like
+    /// {@link recoverWalletWithMnemonic}.
+    ///
+    /// @param {string} new_pin - The new pin to set for the wallet
+    /// @param {string} new_password - The new password to set for the wallet
+    /// @param {string} mnemonic - The mnemonic of the wallet
+    /// @param {string} passphrase - The BIP-39 passphrase of the wallet
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "recoverWalletWithMnemonicAndPassphrase")]
+    pub async fn recover_wallet_with_mnemonic_and_passphrase(
+        &self,
+        new_pin: String,
+        new_password: String,
+        mnemonic: String,
+        passphrase: String,
+    ) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let new_pin = EncryptionPin::try_from_string(new_pin)?;
+                let new_password = PlainPassword::try_from_string(new_password)?;
+                let passphrase = MnemonicPassphrase::try_from_string(passphrase)?;
+                sdk.recover_wallet_with_mnemonic_and_passphrase(&new_pin, &new_password, &mnemonic, &passphrase)
+                    .await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Check if the password to use for wallet operations is set.
@@ -443,8 +991,8 @@ impl ETOPaySdk {
     /// @returns {Promise<bool>}
     #[wasm_bindgen(skip_jsdoc, js_name = "isWalletPasswordSet")]
     pub async fn is_wallet_password_set(&self) -> Result<bool, String> {
-        let sdk = self.inner.write().await;
-        sdk.is_wallet_password_set().await.map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| sdk.is_wallet_password_set().await.map_err(error_string))
+            .await
     }
 
     /// Verifies the pin for the wallet
@@ -453,13 +1001,15 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "verifyPin")]
     pub async fn verify_pin(&self, pin: String) -> Result<(), String> {
-        let sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.verify_pin(&pin).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.verify_pin(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Change the pin used to encrypt the wallet password
@@ -469,14 +1019,16 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "resetPin")]
     pub async fn reset_pin(&self, pin: String, new_pin: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            let new_pin = EncryptionPin::try_from_string(new_pin)?;
-            sdk.change_pin(&pin, &new_pin).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let new_pin = EncryptionPin::try_from_string(new_pin)?;
+                sdk.change_pin(&pin, &new_pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Sends the given amount to the given address
@@ -494,14 +1046,251 @@ impl ETOPaySdk {
         amount: f64,
         data: Option<Vec<u8>>,
     ) -> Result<String, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let amount = CryptoAmount::try_from(amount)?;
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.send_amount(&pin, &address, amount, data).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let amount = CryptoAmount::try_from(amount)?;
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.send_amount(&pin, &address, amount, data).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Sends the given amount from a specific address of the wallet to the given address
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @param {string} sender - The address of the wallet to send from, one of {@link getWalletAddresses}
+    /// @param {string} address - The address of the receiver
+    /// @param {number} amount - The amount to send in the selected currency
+    /// @param {Uint8Array | undefined} data - The data associated with the transaction. Optional.
+    /// @returns {Promise<string>} the transaction id.
+    #[wasm_bindgen(skip_jsdoc, js_name = "sendAmountFrom")]
+    pub async fn send_amount_from(
+        &self,
+        pin: String,
+        sender: String,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let amount = CryptoAmount::try_from(amount)?;
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.send_amount_from(&pin, Some(&sender), &address, amount, data).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Speeds up a pending EVM transaction by replacing it with the same transaction with higher fees
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @param {string} transaction_hash - The hash of the pending transaction
+    /// @returns {Promise<string>} the hash of the replacement transaction.
+    #[wasm_bindgen(skip_jsdoc, js_name = "speedUpTransaction")]
+    pub async fn speed_up_transaction(&self, pin: String, transaction_hash: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.speed_up_transaction(&pin, &transaction_hash).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Cancels a pending EVM transaction by replacing it with a transaction without value to the own address
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @param {string} transaction_hash - The hash of the pending transaction
+    /// @returns {Promise<string>} the hash of the cancel transaction.
+    #[wasm_bindgen(skip_jsdoc, js_name = "cancelTransaction")]
+    pub async fn cancel_transaction(&self, pin: String, transaction_hash: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.cancel_transaction(&pin, &transaction_hash).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Broadcasts all pending EVM transactions of the selected network again, e.g. after the app was restarted
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @returns {Promise<string[]>} the hashes of the transactions that were broadcast.
+    #[wasm_bindgen(skip_jsdoc, js_name = "rebroadcastPendingTransactions")]
+    pub async fn rebroadcast_pending_transactions(&self, pin: String) -> Result<Vec<String>, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.rebroadcast_pending_transactions(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Prepares an EVM transaction to be signed on an offline device
+    ///
+    /// @param {string | undefined} pin - The pin for verification. If undefined, the watch-only wallet is used.
+    /// @param {string | undefined} sender - The address of the wallet to send from. If undefined, the default address is used.
+    /// @param {string} address - The address of the receiver
+    /// @param {number} amount - The amount to send in the selected currency
+    /// @param {Uint8Array | undefined} data - The data associated with the transaction. Optional.
+    /// @returns {Promise<string>} the unsigned transaction as QR string.
+    #[wasm_bindgen(skip_jsdoc, js_name = "prepareUnsignedTransaction")]
+    pub async fn prepare_unsigned_transaction(
+        &self,
+        pin: Option<String>,
+        sender: Option<String>,
+        address: String,
+        amount: f64,
+        data: Option<Vec<u8>>,
+    ) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let amount = CryptoAmount::try_from(amount)?;
+                let pin = match pin {
+                    Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                    None => None,
+                };
+                sdk.prepare_unsigned_transaction(pin.as_ref(), sender.as_deref(), &address, amount, data)
+                    .await
+                    .map(|unsigned| unsigned.to_qr_string())
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Decodes an EVM transaction prepared by {@link prepareUnsignedTransaction} to review it before signing
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @param {string} unsigned_transaction - The unsigned transaction as QR string
+    /// @returns {Promise<UnsignedTransactionDetails>} the receiver, value, data and fees of the transaction.
+    #[wasm_bindgen(skip_jsdoc, js_name = "reviewUnsignedTransaction")]
+    pub async fn review_unsigned_transaction(
+        &self,
+        pin: String,
+        unsigned_transaction: String,
+    ) -> Result<UnsignedTransactionDetails, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let unsigned = UnsignedTransaction::from_qr_string(&unsigned_transaction)?;
+                sdk.review_unsigned_transaction(&pin, &unsigned).await
+            }
+            .await
+            .map(Into::into)
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Signs an EVM transaction prepared by {@link prepareUnsignedTransaction} without contacting the network
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @param {string} unsigned_transaction - The unsigned transaction as QR string
+    /// @returns {Promise<string>} the signed transaction as QR string.
+    #[wasm_bindgen(skip_jsdoc, js_name = "signUnsignedTransaction")]
+    pub async fn sign_unsigned_transaction(&self, pin: String, unsigned_transaction: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                let unsigned = UnsignedTransaction::from_qr_string(&unsigned_transaction)?;
+                sdk.sign_unsigned_transaction(&pin, &unsigned)
+                    .await
+                    .map(|signed| signed.to_qr_string())
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Broadcasts an EVM transaction signed by {@link signUnsignedTransaction} and tracks it as pending
+    ///
+    /// @param {string | undefined} pin - The pin for verification. If undefined, the watch-only wallet is used.
+    /// @param {string} signed_transaction - The signed transaction as QR string
+    /// @returns {Promise<string>} the transaction hash.
+    #[wasm_bindgen(skip_jsdoc, js_name = "broadcastSignedTransaction")]
+    pub async fn broadcast_signed_transaction(
+        &self,
+        pin: Option<String>,
+        signed_transaction: String,
+    ) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = match pin {
+                    Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                    None => None,
+                };
+                let raw_transaction = PendingTransaction::raw_transaction_from_qr_string(&signed_transaction)?;
+                sdk.broadcast_signed_transaction(pin.as_ref(), &raw_transaction).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Checks the status of the watched transactions of the selected network
+    ///
+    /// Finalized, failed and replaced transactions are no longer watched afterwards. Call this function periodically
+    /// while there are watched transactions.
+    ///
+    /// @param {string} pin - The pin for verification
+    /// @returns {Promise<WatchedTransaction[]>} the watched transactions whose status or number of confirmations changed.
+    #[wasm_bindgen(skip_jsdoc, js_name = "checkWatchedTransactions")]
+    pub async fn check_watched_transactions(&self, pin: String) -> Result<Vec<WatchedTransaction>, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.check_watched_transactions(&pin).await
+            }
+            .await
+            .map(|txs| txs.into_iter().map(Into::into).collect())
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Gets the transactions followed by the transaction watcher
+    ///
+    /// @returns {Promise<WatchedTransaction[]>} the watched transactions of all networks with their last known status.
+    #[wasm_bindgen(skip_jsdoc, js_name = "getWatchedTransactions")]
+    pub async fn get_watched_transactions(&self) -> Result<Vec<WatchedTransaction>, String> {
+        self.call(async move |sdk| {
+            sdk.get_watched_transactions()
+                .await
+                .map(|txs| txs.into_iter().map(Into::into).collect())
+                .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
+    }
+
+    /// Waits for the next status change of a watched transaction
+    ///
+    /// Resolves once `checkWatchedTransactions` finds a watched transaction whose status or number of confirmations
+    /// changed, so the UI can await it in a loop while polling `checkWatchedTransactions` elsewhere. If the changes
+    /// are not awaited fast enough, the oldest ones are skipped.
+    ///
+    /// @returns {Promise<WatchedTransaction | undefined>} the updated transaction.
+    #[wasm_bindgen(skip_jsdoc, js_name = "nextTransactionStatus")]
+    pub async fn next_transaction_status(&self) -> Option<WatchedTransaction> {
+        let mut stream = self.transaction_status.lock().await;
+        stream.recv().await.map(Into::into)
     }
 
     /// Gets the detailed lists of purchases (COMPLIMENTS and PURCHASES)
@@ -512,13 +1301,15 @@ impl ETOPaySdk {
     /// @returns {Promise<TxList>} The details of the created purchases
     #[wasm_bindgen(skip_jsdoc, js_name = "getTransactionList")]
     pub async fn get_transaction_list(&self, start: u32, limit: u32) -> Result<TxList, String> {
-        let sdk = self.inner.write().await;
-        sdk.get_tx_list(start, limit)
-            .await
-            .map(|t| TxList {
-                txs: t.txs.into_iter().map(Into::into).collect(),
-            })
-            .map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| {
+            sdk.get_tx_list(start, limit)
+                .await
+                .map(|t| TxList {
+                    txs: t.txs.into_iter().map(Into::into).collect(),
+                })
+                .map_err(error_string)
+        })
+        .await
     }
 
     /// Gets the current exchange rate for the cryptocurrency to EURO
@@ -526,11 +1317,13 @@ impl ETOPaySdk {
     /// @returns {Promise<number>} The exchange rate as a floating point number
     #[wasm_bindgen(skip_jsdoc, js_name = "getExchangeRate")]
     pub async fn get_exchange_rate(&self) -> Result<f64, String> {
-        let sdk = self.inner.write().await;
-        sdk.get_exchange_rate()
-            .await
-            .and_then(|amount| Ok(f64::try_from(amount)?))
-            .map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| {
+            sdk.get_exchange_rate()
+                .await
+                .and_then(|amount| Ok(f64::try_from(amount)?))
+                .map_err(error_string)
+        })
+        .await
     }
 
     /// Deletes the user in etopay. Hazmat!
@@ -539,16 +1332,18 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "deleteUser")]
     pub async fn delete_user(&self, pin: Option<String>) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let encryption_pin = match pin {
-                Some(p) => Some(EncryptionPin::try_from_string(p)?),
-                None => None,
-            };
-            sdk.delete_user(encryption_pin.as_ref()).await
-        }
+        self.call(async move |sdk| {
+            async move {
+                let encryption_pin = match pin {
+                    Some(p) => Some(EncryptionPin::try_from_string(p)?),
+                    None => None,
+                };
+                sdk.delete_user(encryption_pin.as_ref()).await
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Gets the detailed lists of wallet transactions
@@ -565,17 +1360,19 @@ impl ETOPaySdk {
         start: usize,
         limit: usize,
     ) -> Result<WalletTxInfoList, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.get_wallet_tx_list(&pin, start, limit)
-                .await
-                .map(|l| WalletTxInfoList {
-                    transactions: l.transactions.into_iter().map(Into::into).collect(),
-                })
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.get_wallet_tx_list(&pin, start, limit)
+                    .await
+                    .map(|l| WalletTxInfoList {
+                        transactions: l.transactions.into_iter().map(Into::into).collect(),
+                    })
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Gets the details of a specific wallet transaction
@@ -586,13 +1383,15 @@ impl ETOPaySdk {
     /// @returns {Promise<WalletTxInfo} The details of the wallet transaction as a serialized JSON string.
     #[wasm_bindgen(skip_jsdoc, js_name = "getWalletTransaction")]
     pub async fn get_wallet_tx(&self, pin: String, tx_id: String) -> Result<WalletTxInfo, String> {
-        let mut sdk = self.inner.write().await;
-        async move {
-            let pin = EncryptionPin::try_from_string(pin)?;
-            sdk.get_wallet_tx(&pin, &tx_id).await.map(Into::into)
-        }
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.get_wallet_tx(&pin, &tx_id).await.map(Into::into)
+            }
+            .await
+            .map_err(error_string)
+        })
         .await
-        .map_err(|e| format!("{e:#?}"))
     }
 
     /// Updates the IBAN of the user
@@ -605,14 +1404,16 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "updateIbanViviswap")]
     pub async fn update_iban_viviswap(&self, pin: String, address: String) -> Result<ViviswapAddressDetail, String> {
         sdk::require_feature!("viviswap-swap", {
-            let mut sdk = self.inner.write().await;
-            async move {
-                let pin = EncryptionPin::try_from_string(pin)?;
-                sdk.update_iban_for_viviswap(&pin, address).await
-            }
+            self.call(async move |sdk| {
+                async move {
+                    let pin = EncryptionPin::try_from_string(pin)?;
+                    sdk.update_iban_for_viviswap(&pin, address).await
+                }
+                .await
+                .map(Into::into)
+                .map_err(error_string)
+            })
             .await
-            .map(Into::into)
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -622,11 +1423,8 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getIbanViviswap")]
     pub async fn get_iban_viviswap(&self) -> Result<ViviswapAddressDetail, String> {
         sdk::require_feature!("viviswap-swap", {
-            let mut sdk = self.inner.write().await;
-            sdk.get_iban_for_viviswap()
+            self.call(async move |sdk| sdk.get_iban_for_viviswap().await.map(Into::into).map_err(error_string))
                 .await
-                .map(Into::into)
-                .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -637,13 +1435,15 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "createDepositWithViviswap")]
     pub async fn create_deposit_with_viviswap(&self, pin: String) -> Result<ViviswapDeposit, String> {
         sdk::require_feature!("viviswap-swap", {
-            let mut sdk = self.inner.write().await;
-            async move {
-                let pin = EncryptionPin::try_from_string(pin)?;
-                sdk.create_deposit_with_viviswap(&pin).await.map(Into::into)
-            }
+            self.call(async move |sdk| {
+                async move {
+                    let pin = EncryptionPin::try_from_string(pin)?;
+                    sdk.create_deposit_with_viviswap(&pin).await.map(Into::into)
+                }
+                .await
+                .map_err(error_string)
+            })
             .await
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -654,13 +1454,15 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "createDetailForViviswap")]
     pub async fn create_detail_for_viviswap(&self, pin: String) -> Result<ViviswapAddressDetail, String> {
         sdk::require_feature!("viviswap-swap", {
-            let mut sdk = self.inner.write().await;
-            async move {
-                let pin = EncryptionPin::try_from_string(pin)?;
-                sdk.create_detail_for_viviswap(&pin).await.map(Into::into)
-            }
+            self.call(async move |sdk| {
+                async move {
+                    let pin = EncryptionPin::try_from_string(pin)?;
+                    sdk.create_detail_for_viviswap(&pin).await.map(Into::into)
+                }
+                .await
+                .map_err(error_string)
+            })
             .await
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -680,18 +1482,20 @@ impl ETOPaySdk {
         data: Option<Vec<u8>>,
     ) -> Result<ViviswapWithdrawal, String> {
         sdk::require_feature!("viviswap-swap", {
-            let mut sdk = self.inner.write().await;
-            async move {
-                let amount = CryptoAmount::try_from(amount)?;
-                let pin = match pin {
-                    Some(pin) => Some(EncryptionPin::try_from_string(pin)?),
-                    None => None,
-                };
-                sdk.create_withdrawal_with_viviswap(amount, pin.as_ref(), data).await
-            }
+            self.call(async move |sdk| {
+                async move {
+                    let amount = CryptoAmount::try_from(amount)?;
+                    let pin = match pin {
+                        Some(pin) => Some(EncryptionPin::try_from_string(pin)?),
+                        None => None,
+                    };
+                    sdk.create_withdrawal_with_viviswap(amount, pin.as_ref(), data).await
+                }
+                .await
+                .map(Into::into)
+                .map_err(error_string)
+            })
             .await
-            .map(Into::into)
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -704,11 +1508,13 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getSwapDetails")]
     pub async fn get_swap_details(&self, order_id: String) -> Result<Order, String> {
         sdk::require_feature!("viviswap-swap", {
-            let sdk = self.inner.write().await;
-            sdk.get_swap_details(order_id)
-                .await
-                .map(Into::into)
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.get_swap_details(order_id)
+                    .await
+                    .map(Into::into)
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -722,13 +1528,15 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getSwapList")]
     pub async fn get_swap_list(&self, start: u32, limit: u32) -> Result<OrderList, String> {
         sdk::require_feature!("viviswap-swap", {
-            let sdk = self.inner.write().await;
-            sdk.get_swap_list(start, limit)
-                .await
-                .map(|l| OrderList {
-                    orders: l.orders.into_iter().map(Into::into).collect(),
-                })
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.get_swap_list(start, limit)
+                    .await
+                    .map(|l| OrderList {
+                        orders: l.orders.into_iter().map(Into::into).collect(),
+                    })
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -746,11 +1554,13 @@ impl ETOPaySdk {
         terms_accepted: bool,
     ) -> Result<NewViviswapUser, String> {
         sdk::require_feature!("viviswap-kyc", {
-            let mut sdk = self.inner.write().await;
-            sdk.start_kyc_verification_for_viviswap(&mail, terms_accepted)
-                .await
-                .map(Into::into)
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.start_kyc_verification_for_viviswap(&mail, terms_accepted)
+                    .await
+                    .map(Into::into)
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -760,11 +1570,13 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getKycDetailsForViviswap")]
     pub async fn get_kyc_details_for_viviswap(&self) -> Result<ViviswapKycStatus, String> {
         sdk::require_feature!("viviswap-kyc", {
-            let mut sdk = self.inner.write().await;
-            sdk.get_kyc_details_for_viviswap()
-                .await
-                .map(Into::into)
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.get_kyc_details_for_viviswap()
+                    .await
+                    .map(Into::into)
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -795,20 +1607,22 @@ impl ETOPaySdk {
         date_of_birth: String,
     ) -> Result<ViviswapPartiallyKycDetails, String> {
         sdk::require_feature!("viviswap-kyc", {
-            let mut sdk = self.inner.write().await;
-            sdk.update_kyc_partially_status_for_viviswap(
-                Some(is_individual),
-                Some(is_pep),
-                Some(is_us_citizen),
-                Some(is_regulatory_disclosure),
-                Some(country_of_residence),
-                Some(nationality),
-                Some(full_name),
-                Some(date_of_birth),
-            )
+            self.call(async move |sdk| {
+                sdk.update_kyc_partially_status_for_viviswap(
+                    Some(is_individual),
+                    Some(is_pep),
+                    Some(is_us_citizen),
+                    Some(is_regulatory_disclosure),
+                    Some(country_of_residence),
+                    Some(nationality),
+                    Some(full_name),
+                    Some(date_of_birth),
+                )
+                .await
+                .map(Into::into)
+                .map_err(error_string)
+            })
             .await
-            .map(Into::into)
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -817,10 +1631,12 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "submitKycPartiallyStatusForViviswap")]
     pub async fn submit_kyc_partially_status_for_viviswap(&self) -> Result<(), String> {
         sdk::require_feature!("viviswap-kyc", {
-            let mut sdk = self.inner.write().await;
-            sdk.submit_kyc_partially_status_for_viviswap()
-                .await
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.submit_kyc_partially_status_for_viviswap()
+                    .await
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -852,37 +1668,38 @@ impl ETOPaySdk {
         personal_video_filename: String,
     ) -> Result<(), String> {
         sdk::require_feature!("viviswap-kyc", {
-            let sdk = self.inner.write().await;
-
-            let front_image = File::from_bytes(
-                &official_document_front_image_data,
-                &official_document_front_image_filename,
-            );
-
-            let back_image = if let (Some(filename), Some(data)) =
-                (official_document_back_image_filename, official_document_back_image_data)
-            {
-                Some(File::from_bytes(&data, &filename))
-            } else {
-                None
-            };
-
-            let official_document_video = File::from_bytes(&personal_video_data, &personal_video_filename);
+            self.call(async move |sdk| {
+                let front_image = File::from_bytes(
+                    &official_document_front_image_data,
+                    &official_document_front_image_filename,
+                );
+
+                let back_image = if let (Some(filename), Some(data)) =
+                    (official_document_back_image_filename, official_document_back_image_data)
+                {
+                    Some(File::from_bytes(&data, &filename))
+                } else {
+                    None
+                };
 
-            sdk.set_viviswap_kyc_identity_details(
-                sdk::types::IdentityOfficialDocumentData {
-                    r#type: official_document_type.into(),
-                    expiration_date,
-                    document_number,
-                    front_image,
-                    back_image,
-                },
-                sdk::types::IdentityPersonalDocumentData {
-                    video: official_document_video,
-                },
-            )
+                let official_document_video = File::from_bytes(&personal_video_data, &personal_video_filename);
+
+                sdk.set_viviswap_kyc_identity_details(
+                    sdk::types::IdentityOfficialDocumentData {
+                        r#type: official_document_type.into(),
+                        expiration_date,
+                        document_number,
+                        front_image,
+                        back_image,
+                    },
+                    sdk::types::IdentityPersonalDocumentData {
+                        video: official_document_video,
+                    },
+                )
+                .await
+                .map_err(error_string)
+            })
             .await
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -919,30 +1736,31 @@ impl ETOPaySdk {
         official_document_image_filename: Option<String>,
     ) -> Result<(), String> {
         sdk::require_feature!("viviswap-kyc", {
-            let sdk = self.inner.write().await;
-
-            let official_document = if let (Some(filename), Some(data)) =
-                (official_document_image_filename, official_document_image_data)
-            {
-                Some(File::from_bytes(&data, &filename))
-            } else {
-                None
-            };
+            self.call(async move |sdk| {
+                let official_document = if let (Some(filename), Some(data)) =
+                    (official_document_image_filename, official_document_image_data)
+                {
+                    Some(File::from_bytes(&data, &filename))
+                } else {
+                    None
+                };
 
-            sdk.set_viviswap_kyc_residence_details(
-                country_code,
-                region,
-                zip_code,
-                city,
-                address_line_1,
-                address_line_2,
-                is_public_entry,
-                public_entry_reference,
-                has_no_official_document,
-                official_document,
-            )
+                sdk.set_viviswap_kyc_residence_details(
+                    country_code,
+                    region,
+                    zip_code,
+                    city,
+                    address_line_1,
+                    address_line_2,
+                    is_public_entry,
+                    public_entry_reference,
+                    has_no_official_document,
+                    official_document,
+                )
+                .await
+                .map_err(error_string)
+            })
             .await
-            .map_err(|e| format!("{e:#?}"))
         })
     }
 
@@ -952,13 +1770,15 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getViviswapKycAmlaOpenQuestions")]
     pub async fn get_viviswap_kyc_amla_open_questions(&self) -> Result<OpenAmlaQuestions, String> {
         sdk::require_feature!("viviswap-kyc", {
-            let sdk = self.inner.write().await;
-            sdk.get_viviswap_kyc_amla_open_questions()
-                .await
-                .map(|q| OpenAmlaQuestions {
-                    questions: q.into_iter().map(Into::into).collect(),
-                })
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.get_viviswap_kyc_amla_open_questions()
+                    .await
+                    .map(|q| OpenAmlaQuestions {
+                        questions: q.into_iter().map(Into::into).collect(),
+                    })
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -977,10 +1797,12 @@ impl ETOPaySdk {
         freetext_answer: Option<String>,
     ) -> Result<(), String> {
         sdk::require_feature!("viviswap-kyc", {
-            let sdk = self.inner.write().await;
-            sdk.set_viviswap_kyc_amla_answer(question_id, answers, freetext_answer)
-                .await
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.set_viviswap_kyc_amla_answer(question_id, answers, freetext_answer)
+                    .await
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -990,13 +1812,15 @@ impl ETOPaySdk {
     #[wasm_bindgen(skip_jsdoc, js_name = "getViviswapKycOpenDocuments")]
     pub async fn get_viviswap_kyc_open_documents(&self) -> Result<OpenDocuments, String> {
         sdk::require_feature!("viviswap-kyc", {
-            let sdk = self.inner.write().await;
-            sdk.get_viviswap_kyc_open_documents()
-                .await
-                .map(|d| OpenDocuments {
-                    documents: d.into_iter().map(Into::into).collect(),
-                })
-                .map_err(|e| format!("{e:#?}"))
+            self.call(async move |sdk| {
+                sdk.get_viviswap_kyc_open_documents()
+                    .await
+                    .map(|d| OpenDocuments {
+                        documents: d.into_iter().map(Into::into).collect(),
+                    })
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -1024,23 +1848,24 @@ impl ETOPaySdk {
         back_image_filename: Option<String>,
     ) -> Result<(), String> {
         sdk::require_feature!("viviswap-kyc", {
-            let sdk = self.inner.write().await;
-
-            let front_image = if let (Some(filename), Some(data)) = (front_image_filename, front_image_data) {
-                Some(File::from_bytes(&data, &filename))
-            } else {
-                None
-            };
+            self.call(async move |sdk| {
+                let front_image = if let (Some(filename), Some(data)) = (front_image_filename, front_image_data) {
+                    Some(File::from_bytes(&data, &filename))
+                } else {
+                    None
+                };
 
-            let back_image = if let (Some(filename), Some(data)) = (back_image_filename, back_image_data) {
-                Some(File::from_bytes(&data, &filename))
-            } else {
-                None
-            };
+                let back_image = if let (Some(filename), Some(data)) = (back_image_filename, back_image_data) {
+                    Some(File::from_bytes(&data, &filename))
+                } else {
+                    None
+                };
 
-            sdk.set_viviswap_kyc_document(document_id, expiration_date, document_number, front_image, back_image)
-                .await
-                .map_err(|e| format!("{e:#?}"))
+                sdk.set_viviswap_kyc_document(document_id, expiration_date, document_number, front_image, back_image)
+                    .await
+                    .map_err(error_string)
+            })
+            .await
         })
     }
 
@@ -1051,11 +1876,13 @@ impl ETOPaySdk {
     pub async fn get_recovery_share(&self) -> Result<Option<String>, String> {
         use sdk::secrecy::ExposeSecret;
 
-        let sdk = self.inner.write().await;
-        sdk.get_recovery_share()
-            .await
-            .map(|s| s.map(|s| s.to_string().expose_secret().to_string()))
-            .map_err(|err| format!("{:#?}", err))
+        self.call(async move |sdk| {
+            sdk.get_recovery_share()
+                .await
+                .map(|s| s.map(|s| s.to_string().expose_secret().to_string()))
+                .map_err(error_string)
+        })
+        .await
     }
 
     /// Set the recovery share.
@@ -1064,10 +1891,239 @@ impl ETOPaySdk {
     /// @returns {Promise<void>}
     #[wasm_bindgen(skip_jsdoc, js_name = "setRecoveryShare")]
     pub async fn set_recovery_share(&self, share: String) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
+        self.call(async move |sdk| {
+            let share: sdk::share::Share = share.parse().map_err(|e| error_string(sdk::WalletError::Share(e)))?;
+            sdk.set_recovery_share(share).await.map_err(error_string)
+        })
+        .await
+    }
+
+    /// Get the additional shares created for share schemes with more than three shares.
+    ///
+    /// @returns {Promise<string[]>} The additional shares as strings, to be handed out according to their label.
+    #[wasm_bindgen(skip_jsdoc, js_name = "getAdditionalShares")]
+    pub async fn get_additional_shares(&self) -> Result<Vec<String>, String> {
+        use sdk::secrecy::ExposeSecret;
+
+        self.call(async move |sdk| {
+            sdk.get_additional_shares()
+                .await
+                .map(|shares| {
+                    shares
+                        .iter()
+                        .map(|s| s.to_string().expose_secret().to_string())
+                        .collect()
+                })
+                .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Set the additional shares needed to initialize the wallet.
+    ///
+    /// @param {string[]} shares The additional shares collected from their holders.
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setAdditionalShares")]
+    pub async fn set_additional_shares(&self, shares: Vec<String>) -> Result<(), String> {
+        self.call(async move |sdk| {
+            let shares = shares
+                .iter()
+                .map(|s| s.parse::<sdk::share::Share>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| error_string(sdk::WalletError::Share(e)))?;
+            sdk.set_additional_shares(shares).await.map_err(error_string)
+        })
+        .await
+    }
+
+    /// Check the health of the shares of the wallet without reconstructing it.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<ShareHealthReport>} The health of each share.
+    #[wasm_bindgen(skip_jsdoc, js_name = "checkShareHealth")]
+    pub async fn check_share_health(&self, pin: String) -> Result<ShareHealthReport, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.check_share_health(&pin).await
+            }
+            .await
+            .map(Into::into)
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Re-split the wallet secret and replace all shares.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "repairShares")]
+    pub async fn repair_shares(&self, pin: String) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.repair_shares(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Enable or disable unlocking the wallet with the shares on this device only, e.g. on an offline
+    /// device signing transactions.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {boolean} enabled - Whether the recovery share is stored on the device or removed from it.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setOfflineUnlock")]
+    pub async fn set_offline_unlock(&self, pin: String, enabled: bool) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.set_offline_unlock(&pin, enabled).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Get the public key of the user as a guardian. Other users need it to nominate the user as
+    /// their guardian.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<string>} The base64 encoded public key.
+    #[wasm_bindgen(skip_jsdoc, js_name = "getGuardianPublicKey")]
+    pub async fn get_guardian_public_key(&self, pin: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.get_guardian_public_key(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Nominate guardians who can help to recover the wallet. All shares are replaced.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string[]} usernames - The usernames of the guardians.
+    /// @param {string[]} public_keys - The public keys of the guardians, in the same order as the usernames.
+    /// @param {number} threshold - The number of shares needed to reconstruct the wallet, at most 3.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "setGuardians")]
+    pub async fn set_guardians(
+        &self,
+        pin: String,
+        usernames: Vec<String>,
+        public_keys: Vec<String>,
+        threshold: u8,
+    ) -> Result<(), String> {
+        if usernames.len() != public_keys.len() {
+            return Err(String::from("Each guardian needs a username and a public key"));
+        }
+        let guardians = usernames
+            .into_iter()
+            .zip(public_keys)
+            .map(|(username, public_key)| sdk::share::Guardian { username, public_key })
+            .collect();
+
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.set_guardians(&pin, guardians, threshold).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Start recovering the wallet with the help of the guardians. The key of the recovery is stored
+    /// encrypted with the pin until the recovery is completed with `completeGuardianRecovery`.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<string>} The fingerprint of the recovery key, which the guardians confirm before approving.
+    #[wasm_bindgen(skip_jsdoc, js_name = "startGuardianRecovery")]
+    pub async fn start_guardian_recovery(&self, pin: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.start_guardian_recovery(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Get the fingerprint of the key of the recovery started by another user, who nominated this user
+    /// as guardian. Show it to compare it with the fingerprint of the other user before approving.
+    ///
+    /// @param {string} username - The username of the user recovering their wallet.
+    ///
+    /// @returns {Promise<string>} The fingerprint of the recovery key.
+    #[wasm_bindgen(skip_jsdoc, js_name = "getGuardianRecoveryFingerprint")]
+    pub async fn get_guardian_recovery_fingerprint(&self, username: String) -> Result<String, String> {
+        self.call(async move |sdk| {
+            sdk.get_guardian_recovery_fingerprint(&username)
+                .await
+                .map_err(error_string)
+        })
+        .await
+    }
+
+    /// Approve the recovery of another user, who nominated this user as guardian.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    /// @param {string} username - The username of the user recovering their wallet.
+    /// @param {string} fingerprint - The fingerprint of the recovery key confirmed by the other user.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "approveGuardianRecovery")]
+    pub async fn approve_guardian_recovery(
+        &self,
+        pin: String,
+        username: String,
+        fingerprint: String,
+    ) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.approve_guardian_recovery(&pin, &username, &fingerprint).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
+    }
 
-        let share: sdk::share::Share = share.parse().map_err(|e| format!("{e:#?}"))?;
-        sdk.set_recovery_share(share).await.map_err(|err| format!("{:#?}", err))
+    /// Complete the recovery of the wallet with the shares approved by the guardians. All shares are replaced.
+    ///
+    /// @param {string} pin - The input string representing the pin.
+    ///
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(skip_jsdoc, js_name = "completeGuardianRecovery")]
+    pub async fn complete_guardian_recovery(&self, pin: String) -> Result<(), String> {
+        self.call(async move |sdk| {
+            async move {
+                let pin = EncryptionPin::try_from_string(pin)?;
+                sdk.complete_guardian_recovery(&pin).await
+            }
+            .await
+            .map_err(error_string)
+        })
+        .await
     }
 
     /// Get the preferred network.
@@ -1075,8 +2131,8 @@ impl ETOPaySdk {
     /// @returns {Promise<String?>} The id of preferred network id, or `undefined` if none exists.
     #[wasm_bindgen(skip_jsdoc, js_name = "getPreferredNetwork")]
     pub async fn get_preferred_network(&self) -> Result<Option<String>, String> {
-        let sdk = self.inner.write().await;
-        sdk.get_preferred_network().await.map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| sdk.get_preferred_network().await.map_err(error_string))
+            .await
     }
 
     /// Set the preferred network.
@@ -1086,8 +2142,8 @@ impl ETOPaySdk {
     /// @returns {Promise<()>}
     #[wasm_bindgen(skip_jsdoc, js_name = "setPreferredNetwork")]
     pub async fn set_preferred_network(&self, network: Option<String>) -> Result<(), String> {
-        let mut sdk = self.inner.write().await;
-        sdk.set_preferred_network(network).await.map_err(|e| format!("{e:#?}"))
+        self.call(async move |sdk| sdk.set_preferred_network(network).await.map_err(error_string))
+            .await
     }
 
     /// Get sdk build information.
@@ -213,6 +213,173 @@ impl From<sdk::types::transactions::WalletTxInfo> for WalletTxInfo {
     }
 }
 
+#[wasm_bindgen]
+#[derive(Clone)]
+pub enum WatchedTransactionStatus {
+    Pending,
+    Included,
+    Finalized,
+    Failed,
+    Replaced,
+}
+
+convert_enum!(
+    sdk::types::transactions::WatchedTransactionStatus,
+    WatchedTransactionStatus,
+    Pending,
+    Included,
+    Finalized,
+    Failed,
+    Replaced,
+);
+
+#[wasm_bindgen(getter_with_clone, inspectable)]
+#[derive(Clone)]
+pub struct WatchedTransaction {
+    /// The transaction hash on the network
+    pub transaction_hash: String,
+    /// The id of the network of the transaction
+    pub network_id: String,
+    /// Status of the transaction
+    pub status: WatchedTransactionStatus,
+    /// Number of blocks confirming the transaction
+    pub confirmations: u64,
+}
+
+impl From<sdk::types::transactions::WatchedTransaction> for WatchedTransaction {
+    fn from(value: sdk::types::transactions::WatchedTransaction) -> Self {
+        Self {
+            transaction_hash: value.transaction_hash,
+            network_id: value.network_id,
+            status: value.status.into(),
+            confirmations: value.confirmations,
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone, inspectable)]
+#[derive(Clone)]
+pub struct UnsignedTransactionDetails {
+    /// The sender address
+    pub from: String,
+    /// The receiver address, for token transfers the receiver of the tokens
+    pub to: String,
+    /// The amount sent to the receiver as decimal string, in the currency of the network
+    pub value: String,
+    /// The data of the transaction, for token transfers the encoded call of the token contract
+    pub data: Vec<u8>,
+    /// The id of the chain the transaction can be included in
+    pub chain_id: u64,
+    /// The nonce of the transaction
+    pub nonce: u64,
+    /// The maximum amount of gas that the transaction can consume
+    pub gas_limit: u64,
+    /// The maximum fee per unit of gas in wei as decimal string
+    pub max_fee_per_gas: String,
+    /// The maximum tip per unit of gas in wei as decimal string
+    pub max_priority_fee_per_gas: String,
+    /// The maximum fee of the transaction as decimal string, in the native currency of the chain
+    pub max_fee: String,
+}
+
+impl From<sdk::types::transactions::UnsignedTransactionDetails> for UnsignedTransactionDetails {
+    fn from(value: sdk::types::transactions::UnsignedTransactionDetails) -> Self {
+        Self {
+            from: value.from,
+            to: value.to,
+            value: value.value.to_string(),
+            data: value.data,
+            chain_id: value.chain_id,
+            nonce: value.nonce,
+            gas_limit: value.gas_limit,
+            max_fee_per_gas: value.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas: value.max_priority_fee_per_gas.to_string(),
+            max_fee: value.max_fee.to_string(),
+        }
+    }
+}
+
+#[wasm_bindgen(getter_with_clone, inspectable)]
+#[derive(Clone)]
+pub struct UserSummary {
+    /// The username
+    pub username: String,
+    /// Unix timestamp in seconds of the creation of the user, if known
+    pub created_at: Option<i64>,
+    /// The KYC type of the user
+    pub kyc_type: String,
+    /// Whether the user has a wallet
+    pub has_wallet: bool,
+    /// Unix timestamp in seconds of the last time the user was initialized, if known
+    pub last_used_at: Option<i64>,
+    /// Whether the user is locked until the pin is entered, only the username is known then
+    pub locked: bool,
+}
+
+impl From<sdk::types::users::UserSummary> for UserSummary {
+    fn from(value: sdk::types::users::UserSummary) -> Self {
+        Self {
+            username: value.username,
+            created_at: value.created_at,
+            kyc_type: format!("{:?}", value.kyc_type),
+            has_wallet: value.has_wallet,
+            last_used_at: value.last_used_at,
+            locked: value.locked,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub enum ShareHealth {
+    Healthy,
+    Missing,
+    Stale,
+    Unknown,
+    Inconsistent,
+    Corrupt,
+}
+
+convert_enum!(
+    sdk::share::ShareHealth,
+    ShareHealth,
+    Healthy,
+    Missing,
+    Stale,
+    Unknown,
+    Inconsistent,
+    Corrupt,
+);
+
+#[wasm_bindgen(getter_with_clone, inspectable)]
+pub struct ShareHealthReport {
+    /// The local share stored on the device
+    pub local: ShareHealth,
+    /// The encrypted backup share stored in the backend
+    pub backup: ShareHealth,
+    /// The recovery share stored in the backend
+    pub recovery: ShareHealth,
+    /// The recovery share provided by the user
+    pub provided_recovery: ShareHealth,
+    /// The additional shares stored on the device, provided by the user or created with the wallet
+    pub additional: Vec<ShareHealth>,
+    /// If any of the shares should be repaired
+    pub needs_repair: bool,
+}
+
+impl From<sdk::share::ShareHealthReport> for ShareHealthReport {
+    fn from(value: sdk::share::ShareHealthReport) -> Self {
+        Self {
+            needs_repair: value.needs_repair(),
+            local: value.local.into(),
+            backup: value.backup.into(),
+            recovery: value.recovery.into(),
+            provided_recovery: value.provided_recovery.into(),
+            additional: value.additional.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
 #[wasm_bindgen(getter_with_clone, inspectable)]
 pub struct ViviswapAddressDetail {
     /// the unique id of the address detail
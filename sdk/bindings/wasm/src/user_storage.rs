@@ -0,0 +1,73 @@
+//! Connects a user storage of the JavaScript application to the SDK.
+
+use sdk::user::callback_storage::UserStorageCallbacks;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(typescript_custom_section)]
+const USER_STORAGE_INTERFACE: &str = r#"
+/**
+ * A string key-value storage of the application to store the users in, e.g. `window.localStorage`.
+ * The methods are called synchronously and report errors by throwing.
+ */
+export interface UserStorage {
+    getItem(key: string): string | null | undefined;
+    setItem(key: string, value: string): void;
+    removeItem(key: string): void;
+    keys(): string[];
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A storage object of the application implementing the `UserStorage` interface.
+    #[wasm_bindgen(typescript_type = "UserStorage")]
+    pub type JsUserStorage;
+
+    #[wasm_bindgen(method, catch, js_name = "getItem")]
+    fn get_item(this: &JsUserStorage, key: &str) -> Result<Option<String>, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = "setItem")]
+    fn set_item(this: &JsUserStorage, key: &str, value: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = "removeItem")]
+    fn remove_item(this: &JsUserStorage, key: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn keys(this: &JsUserStorage) -> Result<Vec<String>, JsValue>;
+}
+
+/// The callbacks of a [`JsUserStorage`].
+pub struct JsUserStorageCallbacks(JsUserStorage);
+
+impl JsUserStorageCallbacks {
+    pub fn new(storage: JsUserStorage) -> Self {
+        Self(storage)
+    }
+}
+
+// SAFETY: the SDK requires a `Send + Sync` storage. The wasm module is single-threaded, so the JavaScript
+// object is never accessed from another thread.
+unsafe impl Send for JsUserStorageCallbacks {}
+unsafe impl Sync for JsUserStorageCallbacks {}
+
+fn js_error_string(error: JsValue) -> String {
+    error.as_string().unwrap_or_else(|| format!("{error:?}"))
+}
+
+impl UserStorageCallbacks for JsUserStorageCallbacks {
+    fn get_item(&self, key: &str) -> Result<Option<String>, String> {
+        self.0.get_item(key).map_err(js_error_string)
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<(), String> {
+        self.0.set_item(key, value).map_err(js_error_string)
+    }
+
+    fn remove_item(&self, key: &str) -> Result<(), String> {
+        self.0.remove_item(key).map_err(js_error_string)
+    }
+
+    fn keys(&self) -> Result<Vec<String>, String> {
+        self.0.keys().map_err(js_error_string)
+    }
+}
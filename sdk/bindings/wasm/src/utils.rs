@@ -9,6 +9,12 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// Formats an error for the client app as JSON object with a stable error code to branch on, see
+/// [`sdk::Error::to_binding_string`].
+pub fn error_string(error: impl Into<sdk::Error>) -> String {
+    error.into().to_binding_string()
+}
+
 /// helper for generating From implementations for enums
 /// to convert between two enums with the same variants (eg. api_types and gRPC types)
 macro_rules! convert_enum{
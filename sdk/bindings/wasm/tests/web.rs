@@ -1,6 +1,7 @@
 //! Test suite for the Web and headless browsers.
 
 #![cfg(target_arch = "wasm32")]
+#![allow(clippy::unwrap_used)]
 
 extern crate wasm_bindgen_test;
 use wasm_bindgen_test::*;
@@ -11,3 +12,119 @@ wasm_bindgen_test_configure!(run_in_browser);
 fn pass() {
     assert_eq!(1 + 1, 2);
 }
+
+mod indexed_db_user_storage {
+    use super::*;
+    use sdk::user::conformance;
+    use sdk::user::indexed_db_storage::IndexedDbUserStorage;
+    use sdk::user::UserKvStorage;
+    use web_sys::js_sys;
+
+    /// A record of the user `alice` as written by the local storage of earlier SDK versions, base64 encoded.
+    const LOCAL_STORAGE_RECORD: &str = "mcClYWxpY2XAnAECAwQFBgcICQoLDMKpVW5kZWZpbmVkwMCQ";
+
+    /// Returns a database name that is not used by another test or an earlier run.
+    fn unique_db_name(test: &str) -> String {
+        format!("etopay-test-{test}-{}", js_sys::Math::random())
+    }
+
+    fn local_storage() -> web_sys::Storage {
+        web_sys::window().unwrap().local_storage().unwrap().unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn conforms_to_the_user_storage_checks() {
+        let checks: [fn(&mut dyn UserKvStorage); 11] = [
+            conformance::check_set_get,
+            conformance::check_set_overwrites,
+            conformance::check_set_get_all_fields,
+            conformance::check_get_nonexistent,
+            conformance::check_exists,
+            conformance::check_not_exists,
+            conformance::check_delete_existing,
+            conformance::check_delete_nonexistent,
+            conformance::check_users_are_independent,
+            conformance::check_list_empty,
+            conformance::check_list,
+        ];
+        for check in checks {
+            let mut storage = IndexedDbUserStorage::open_unencrypted(&unique_db_name("conformance"))
+                .await
+                .unwrap();
+            check(&mut storage);
+            storage.flush().await.unwrap();
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn users_persist_when_the_database_is_opened_again() {
+        // Arrange
+        let db_name = unique_db_name("persist");
+        let mut storage = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+        conformance::check_set_get(&mut storage);
+        let user = storage.get("username").unwrap();
+        storage.flush().await.unwrap();
+
+        // Act
+        let reopened = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+
+        // Assert
+        assert_eq!(reopened.get("username").unwrap(), user);
+    }
+
+    #[wasm_bindgen_test]
+    async fn deleted_users_stay_deleted_when_the_database_is_opened_again() {
+        // Arrange
+        let db_name = unique_db_name("delete");
+        let mut storage = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+        conformance::check_set_get(&mut storage);
+        storage.delete("username").unwrap();
+        storage.flush().await.unwrap();
+
+        // Act
+        let reopened = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+
+        // Assert
+        assert!(!reopened.exists("username").unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    async fn changes_of_another_tab_are_seen_after_reloading() {
+        // Arrange: two storages on the same database, like two tabs
+        let db_name = unique_db_name("tabs");
+        let mut tab = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+        let mut other_tab = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+        conformance::check_set_get(&mut other_tab);
+        let user = other_tab.get("username").unwrap();
+        other_tab.flush().await.unwrap();
+
+        // Act & Assert: the user stored by the other tab is read
+        assert!(!tab.exists("username").unwrap());
+        tab.reload().await.unwrap();
+        assert_eq!(tab.get("username").unwrap(), user);
+
+        // Act & Assert: the user deleted by this tab is gone in the other tab
+        tab.delete("username").unwrap();
+        tab.flush().await.unwrap();
+        other_tab.reload().await.unwrap();
+        assert!(!other_tab.exists("username").unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    async fn users_of_the_local_storage_are_moved_to_the_database() {
+        // Arrange
+        let key = "etopay.local.user.alice";
+        local_storage().set_item(key, LOCAL_STORAGE_RECORD).unwrap();
+
+        // Act
+        let db_name = unique_db_name("migrate");
+        let storage = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+
+        // Assert
+        assert_eq!(storage.get("alice").unwrap().username, "alice");
+        assert_eq!(local_storage().get_item(key).unwrap(), None);
+
+        let reopened = IndexedDbUserStorage::open_unencrypted(&db_name).await.unwrap();
+        assert!(reopened.exists("alice").unwrap());
+    }
+}
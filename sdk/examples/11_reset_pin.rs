@@ -1,3 +1,4 @@
+use etopay_sdk::mnemonic::MnemonicOptions;
 use etopay_sdk::types::newtypes::EncryptionPin;
 mod utils;
 use testing::USER_SATOSHI;
@@ -20,7 +21,9 @@ async fn main() {
 
     // Create new wallet
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Reset pin
     let new_pin = EncryptionPin::try_from_string("123456").unwrap();
@@ -1,3 +1,4 @@
+use etopay_sdk::mnemonic::MnemonicOptions;
 use etopay_sdk::types::newtypes::PlainPassword;
 mod utils;
 use testing::USER_SATOSHI;
@@ -16,7 +17,9 @@ async fn main() {
 
     // Create new wallet
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Change password
     let new_password = PlainPassword::try_from_string("StrongP@ssw0rd").unwrap();
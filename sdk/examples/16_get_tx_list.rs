@@ -1,3 +1,4 @@
+use etopay_sdk::mnemonic::MnemonicOptions;
 mod utils;
 use testing::USER_SATOSHI;
 use utils::init_sdk;
@@ -15,7 +16,9 @@ async fn main() {
 
     // Create new wallet
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Get tx list
     let tx_list = sdk.get_tx_list(0, 10).await.unwrap();
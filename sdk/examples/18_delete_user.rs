@@ -1,3 +1,4 @@
+use etopay_sdk::mnemonic::MnemonicOptions;
 use etopay_sdk::types::newtypes::AccessToken;
 mod utils;
 use testing::USER_ARCHIVEME;
@@ -28,7 +29,9 @@ async fn main() {
 
     // Create new wallet
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Delete user
     sdk.delete_user(Some(&user.pin)).await.unwrap();
@@ -0,0 +1,290 @@
+//! Shared HTTP client used for all backend requests.
+//!
+//! The client is created once per [`crate::core::Config`] and reused for all requests, such that
+//! connections are pooled. Failed requests are retried with an exponential back-off with jitter:
+//! - Requests answered with `429 Too Many Requests` are retried for all methods, since the backend
+//!   rejected them before processing.
+//! - Idempotent requests (`GET`, `PUT`, `DELETE`) are additionally retried on timeouts, connection
+//!   errors and `502 Bad Gateway` / `503 Service Unavailable` / `504 Gateway Timeout` responses. These
+//!   can come from a proxy after the backend already processed the request, so other requests are not
+//!   retried to avoid submitting e.g. a transaction twice. Other errors, e.g. building or redirecting
+//!   the request, are returned right away.
+//!
+//! A `Retry-After` header of a `429` or `503` response is respected up to [`HttpConfig::max_retry_delay`].
+//! If the backend asks to wait longer, the response is returned without retrying.
+
+use super::error::Result;
+use log::warn;
+use rand::Rng;
+use reqwest::{header::RETRY_AFTER, Method, RequestBuilder, Response, StatusCode};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Settings of the HTTP client used for backend requests.
+#[derive(Debug)]
+pub struct HttpConfig {
+    /// Timeout of a single request attempt. Not supported on `wasm32`, where the browser
+    /// timeouts apply.
+    pub timeout: Duration,
+
+    /// Timeout for establishing a connection. Not supported on `wasm32`.
+    pub connect_timeout: Duration,
+
+    /// Maximum number of retries of a failed request. `0` disables retries.
+    pub max_retries: u32,
+
+    /// Delay before the first retry, doubled for every further retry.
+    pub retry_base_delay: Duration,
+
+    /// Upper limit of the delay between two attempts. Responses with a longer `Retry-After` are
+    /// returned without retrying.
+    pub max_retry_delay: Duration,
+
+    /// The client, created on first use with the settings above.
+    client: OnceLock<reqwest::Client>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            max_retries: 3,
+            retry_base_delay: Duration::from_millis(500),
+            max_retry_delay: Duration::from_secs(30),
+            client: OnceLock::new(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Get the shared client, creating it on first use.
+    pub(crate) fn client(&self) -> &reqwest::Client {
+        self.client.get_or_init(|| {
+            let builder = reqwest::Client::builder();
+
+            #[cfg(not(target_arch = "wasm32"))]
+            let builder = builder.timeout(self.timeout).connect_timeout(self.connect_timeout);
+
+            builder.build().unwrap_or_else(|e| {
+                warn!("Could not create configured HTTP client, falling back to the default: {e}");
+                reqwest::Client::new()
+            })
+        })
+    }
+
+    /// Send the request with the shared client, retrying it according to the retry policy.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let mut request = request.build()?;
+        let idempotent = matches!(*request.method(), Method::GET | Method::PUT | Method::DELETE);
+
+        let mut attempt = 0;
+        loop {
+            // keep a copy of the request around in case we need to retry it
+            let retry_request = if attempt < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let result = self.client().execute(request).await;
+
+            let Some(next_request) = retry_request else {
+                return Ok(result?);
+            };
+
+            let delay = match &result {
+                Ok(response)
+                    if response.status() == StatusCode::TOO_MANY_REQUESTS
+                        || (idempotent && response.status() == StatusCode::SERVICE_UNAVAILABLE) =>
+                {
+                    match retry_after(response) {
+                        Some(delay) if delay > self.max_retry_delay => {
+                            warn!(
+                                "Request to {} asks to retry after {delay:?}, which is longer than {:?}, not retrying",
+                                next_request.url(),
+                                self.max_retry_delay
+                            );
+                            None
+                        }
+                        Some(delay) => Some(delay),
+                        None => Some(self.backoff(attempt)),
+                    }
+                }
+                Ok(response)
+                    if idempotent
+                        && matches!(response.status(), StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT) =>
+                {
+                    Some(self.backoff(attempt))
+                }
+                Err(e) if idempotent && is_transient(e) => Some(self.backoff(attempt)),
+                _ => None,
+            };
+            let Some(delay) = delay else {
+                return Ok(result?);
+            };
+
+            match &result {
+                Ok(response) => warn!(
+                    "Request to {} failed with status {}, retrying in {delay:?}",
+                    next_request.url(),
+                    response.status()
+                ),
+                Err(e) => warn!("Request to {} failed: {e}, retrying in {delay:?}", next_request.url()),
+            }
+
+            sleep(delay).await;
+            request = next_request;
+            attempt += 1;
+        }
+    }
+
+    /// Exponential back-off for the given retry attempt, with a random jitter of up to half the delay.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .retry_base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_retry_delay);
+        let half = delay / 2;
+        half + half.mul_f64(rand::rng().random::<f64>())
+    }
+}
+
+/// Whether the error is a timeout or a failed connection, which is likely to go away when retrying
+/// the request.
+fn is_transient(error: &reqwest::Error) -> bool {
+    #[cfg(not(target_arch = "wasm32"))]
+    if error.is_connect() {
+        return true;
+    }
+    error.is_timeout()
+}
+
+/// Parse the `Retry-After` header, given either in seconds or as HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_utils::set_config;
+
+    fn fast_retries() -> HttpConfig {
+        HttpConfig {
+            retry_base_delay: Duration::from_millis(1),
+            max_retry_delay: Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    #[rstest::rstest]
+    #[case::get_bad_gateway(Method::GET, 502, 4)]
+    #[case::post_bad_gateway(Method::POST, 502, 1)]
+    #[case::get_service_unavailable(Method::GET, 503, 4)]
+    #[case::post_service_unavailable(Method::POST, 503, 1)]
+    #[case::get_too_many_requests(Method::GET, 429, 4)]
+    #[case::post_too_many_requests(Method::POST, 429, 4)]
+    #[case::get_internal_server_error(Method::GET, 500, 1)]
+    #[case::get_not_found(Method::GET, 404, 1)]
+    #[tokio::test]
+    async fn test_send_retries(#[case] method: Method, #[case] status: usize, #[case] expected_attempts: usize) {
+        // Arrange
+        let (mut srv, _config, _cleanup) = set_config().await;
+        let mock = srv
+            .mock(method.as_str(), "/api/retry")
+            .with_status(status)
+            .expect(expected_attempts)
+            .create();
+
+        let http = fast_retries();
+        let request = http.client().request(method, format!("{}/api/retry", srv.url()));
+
+        // Act
+        let response = http.send(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status().as_u16() as usize, status);
+        mock.assert();
+    }
+
+    #[rstest::rstest]
+    #[case::within_limit("0", 2)]
+    #[case::above_limit("60", 1)]
+    #[tokio::test]
+    async fn test_send_respects_retry_after_limit(#[case] header: &str, #[case] expected_attempts: usize) {
+        // Arrange
+        let (mut srv, _config, _cleanup) = set_config().await;
+        let mock = srv
+            .mock("GET", "/api/retry")
+            .with_status(429)
+            .with_header("Retry-After", header)
+            .expect(expected_attempts)
+            .create();
+
+        let http = HttpConfig {
+            max_retries: 1,
+            ..fast_retries()
+        };
+        let request = http.client().get(format!("{}/api/retry", srv.url()));
+
+        // Act
+        let response = http.send(request).await.unwrap();
+
+        // Assert
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        mock.assert();
+    }
+
+    #[rstest::rstest]
+    #[case::seconds("3", Some(Duration::from_secs(3)))]
+    #[case::past_date("Wed, 21 Oct 2015 07:28:00 GMT", None)]
+    #[case::invalid("soon", None)]
+    #[tokio::test]
+    async fn test_retry_after(#[case] header: &str, #[case] expected: Option<Duration>) {
+        // Arrange
+        let (mut srv, _config, _cleanup) = set_config().await;
+        let _mock = srv
+            .mock("GET", "/api/retry")
+            .with_status(429)
+            .with_header("Retry-After", header)
+            .create();
+
+        let response = reqwest::get(format!("{}/api/retry", srv.url())).await.unwrap();
+
+        // Act
+        let delay = retry_after(&response);
+
+        // Assert
+        assert_eq!(delay, expected);
+    }
+
+    #[test]
+    fn test_backoff_is_bounded() {
+        let http = HttpConfig::default();
+
+        for attempt in 0..40 {
+            let delay = http.backoff(attempt);
+            let max = http.retry_base_delay.saturating_mul(2u32.saturating_pow(attempt));
+            assert!(delay <= max.min(http.max_retry_delay));
+            assert!(delay >= max.min(http.max_retry_delay) / 2);
+        }
+    }
+}
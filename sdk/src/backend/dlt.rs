@@ -40,15 +40,15 @@ pub async fn put_user_address(
     };
     info!("Putting user address {address}");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .put(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
         .query(&query)
-        .json(&body)
-        .send()
-        .await?;
+        .json(&body);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     match response.status() {
         StatusCode::CREATED => (),
@@ -60,10 +60,7 @@ pub async fn put_user_address(
                 "Failed to put user address: Response status: {}, Response text: {}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
@@ -94,13 +91,13 @@ pub async fn get_networks(config: &Config, access_token: &AccessToken) -> Result
     info!("Used url: {url:#?}");
     info!("Getting networks ..");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
 
     match response.status() {
@@ -116,10 +113,7 @@ pub async fn get_networks(config: &Config, access_token: &AccessToken) -> Result
                 "Failed to get node urls from backend: Response status: {}, Response text: {:?}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -1,13 +1,17 @@
+use api_types::api::error::{ApiErrorCode, ApiErrorResponse};
 use reqwest::StatusCode;
+use serde::{ser::Serializer, Serialize};
 
 /// A [`core::result::Result`] with [`ApiError`] as its error variant.
 pub type Result<T> = core::result::Result<T, ApiError>;
 
 /// Errors related to sdk backend
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Debug, Serialize)]
+#[serde(tag = "type", content = "error", rename_all = "camelCase")]
 pub enum ApiError {
     /// API request error
     #[error("API request error: {0}")]
+    #[serde(serialize_with = "crate::error::display_string")]
     Reqwest(#[from] reqwest::Error),
 
     /// Error raises if authentication token is outdated or invalid
@@ -18,11 +22,216 @@ pub enum ApiError {
     #[error("Parse error: {0}")]
     Parse(String),
 
+    /// The backend rejected the request because some fields are invalid
+    #[error("Validation error: {message} (fields: {})", fields.join(", "))]
+    Validation {
+        /// Error message of the backend
+        message: String,
+        /// Names of the invalid fields
+        fields: Vec<String>,
+    },
+
+    /// The requested resource does not exist
+    #[error("Not found: {message}")]
+    NotFound {
+        /// Error message of the backend
+        message: String,
+    },
+
+    /// The request conflicts with the current state of the resource
+    #[error("Conflict: {message}")]
+    Conflict {
+        /// Error message of the backend
+        message: String,
+    },
+
+    /// Too many requests were sent to the backend
+    #[error("Rate limited: {message}")]
+    #[serde(rename_all = "camelCase")]
+    RateLimited {
+        /// Error message of the backend
+        message: String,
+        /// Seconds to wait before sending the request again, if known
+        retry_after_secs: Option<u64>,
+    },
+
+    /// The user needs to complete the KYC process first
+    #[error("KYC required: {message}")]
+    KycRequired {
+        /// Error message of the backend
+        message: String,
+    },
+
+    /// The request exceeds a limit of the user
+    #[error("Insufficient limit: {message}")]
+    InsufficientLimit {
+        /// Error message of the backend
+        message: String,
+    },
+
     /// Unexpected response error
     #[error("Unexpected response: code: {code}, body: {body}")]
-    UnexpectedResponse { code: StatusCode, body: String },
+    UnexpectedResponse {
+        #[serde(serialize_with = "status_code")]
+        code: StatusCode,
+        body: String,
+    },
 
     /// Error raises if something is wrong with the shares (e.g. not encrypted)
     #[error("Share error: {0}")]
     Share(String),
 }
+
+impl ApiError {
+    /// Create the error for a non-successful response from its status code and body.
+    ///
+    /// The body is parsed as [`ApiErrorResponse`]. If this fails or the error code is unknown,
+    /// [`ApiError::UnexpectedResponse`] is returned.
+    pub(crate) fn from_response(code: StatusCode, body: String) -> Self {
+        let Ok(response) = serde_json::from_str::<ApiErrorResponse>(&body) else {
+            return Self::UnexpectedResponse { code, body };
+        };
+
+        let message = response.message;
+        match response.code {
+            ApiErrorCode::ValidationError => Self::Validation {
+                message,
+                fields: response.fields,
+            },
+            ApiErrorCode::NotFound => Self::NotFound { message },
+            ApiErrorCode::Conflict => Self::Conflict { message },
+            ApiErrorCode::RateLimited => Self::RateLimited {
+                message,
+                retry_after_secs: response.retry_after_secs,
+            },
+            ApiErrorCode::KycRequired => Self::KycRequired { message },
+            ApiErrorCode::InsufficientLimit => Self::InsufficientLimit { message },
+            ApiErrorCode::Unknown => Self::UnexpectedResponse { code, body },
+        }
+    }
+
+    /// Stable code of the error, see [`crate::Error::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Reqwest(_) => "BACKEND_REQUEST",
+            Self::MissingAccessToken => "MISSING_ACCESS_TOKEN",
+            Self::Parse(_) => "BACKEND_PARSE",
+            Self::Validation { .. } => "BACKEND_VALIDATION",
+            Self::NotFound { .. } => "BACKEND_NOT_FOUND",
+            Self::Conflict { .. } => "BACKEND_CONFLICT",
+            Self::RateLimited { .. } => "BACKEND_RATE_LIMITED",
+            Self::KycRequired { .. } => "BACKEND_KYC_REQUIRED",
+            Self::InsufficientLimit { .. } => "BACKEND_INSUFFICIENT_LIMIT",
+            Self::UnexpectedResponse { .. } => "BACKEND_UNEXPECTED_RESPONSE",
+            Self::Share(_) => "BACKEND_SHARE",
+        }
+    }
+}
+
+fn status_code<S>(value: &StatusCode, serializer: S) -> core::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    value.as_u16().serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[rstest::rstest]
+    #[case::validation(
+        r#"{"code":"VALIDATION_ERROR","message":"invalid input","fields":["amount","network_id"]}"#,
+        "Validation error: invalid input (fields: amount, network_id)"
+    )]
+    #[case::not_found(
+        r#"{"code":"NOT_FOUND","message":"no such purchase"}"#,
+        "Not found: no such purchase"
+    )]
+    #[case::conflict(r#"{"code":"CONFLICT","message":"user exists"}"#, "Conflict: user exists")]
+    #[case::rate_limited(
+        r#"{"code":"RATE_LIMITED","message":"slow down","retry_after_secs":5}"#,
+        "Rate limited: slow down"
+    )]
+    #[case::kyc_required(r#"{"code":"KYC_REQUIRED","message":"verify first"}"#, "KYC required: verify first")]
+    #[case::insufficient_limit(
+        r#"{"code":"INSUFFICIENT_LIMIT","message":"limit exceeded"}"#,
+        "Insufficient limit: limit exceeded"
+    )]
+    #[case::unknown_code(
+        r#"{"code":"SOMETHING_NEW","message":"?"}"#,
+        r#"Unexpected response: code: 400 Bad Request, body: {"code":"SOMETHING_NEW","message":"?"}"#
+    )]
+    #[case::plain_text("oops", "Unexpected response: code: 400 Bad Request, body: oops")]
+    #[case::empty("", "Unexpected response: code: 400 Bad Request, body: ")]
+    fn test_from_response(#[case] body: &str, #[case] expected: &str) {
+        // Act
+        let error = ApiError::from_response(StatusCode::BAD_REQUEST, body.to_string());
+
+        // Assert
+        assert_eq!(error.to_string(), expected);
+    }
+
+    #[test]
+    fn test_rate_limited_keeps_retry_after() {
+        // Act
+        let error = ApiError::from_response(
+            StatusCode::TOO_MANY_REQUESTS,
+            r#"{"code":"RATE_LIMITED","message":"slow down","retry_after_secs":5}"#.to_string(),
+        );
+
+        // Assert
+        assert!(matches!(
+            error,
+            ApiError::RateLimited {
+                retry_after_secs: Some(5),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_serialize_typed_error() {
+        // Arrange
+        let error = ApiError::Validation {
+            message: "invalid input".to_string(),
+            fields: vec!["amount".to_string()],
+        };
+
+        // Act
+        let json = serde_json::to_value(&error).unwrap();
+
+        // Assert
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "validation",
+                "error": { "message": "invalid input", "fields": ["amount"] }
+            })
+        );
+    }
+
+    #[test]
+    fn test_binding_string_contains_code() {
+        // Arrange
+        let error = crate::Error::BackendApi(ApiError::NotFound {
+            message: "no such purchase".to_string(),
+        });
+
+        // Act
+        let json: serde_json::Value = serde_json::from_str(&error.to_binding_string()).unwrap();
+
+        // Assert
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "BACKEND_NOT_FOUND",
+                "message": "BackendApi error: Not found: no such purchase",
+                "error": {
+                    "type": "backendApi",
+                    "error": { "type": "notFound", "error": { "message": "no such purchase" } }
+                }
+            })
+        );
+    }
+}
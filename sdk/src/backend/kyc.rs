@@ -36,13 +36,13 @@ pub async fn check_kyc_status(
     info!("Used url: {url:#?}");
     info!("Used username: {username}");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
 
     let kyc_status = match response.status() {
@@ -55,10 +55,7 @@ pub async fn check_kyc_status(
                 "Failed to check kyc status: Response status: {}, Response text: {}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
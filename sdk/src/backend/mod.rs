@@ -2,6 +2,7 @@
 //!
 //!
 
+pub mod client;
 pub mod dlt;
 pub mod kyc;
 
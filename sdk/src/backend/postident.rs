@@ -26,13 +26,13 @@ pub async fn get_new_case_id(config: &Config, access_token: &AccessToken) -> Res
     info!("Used url: {url:#?}");
     info!("Get new case id");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
 
     let kyc_status = match response.status() {
@@ -45,10 +45,7 @@ pub async fn get_new_case_id(config: &Config, access_token: &AccessToken) -> Res
                 "Failed to get new case id: Response status: {}, Response text: {:?}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
@@ -72,13 +69,13 @@ pub async fn get_case_details(config: &Config, access_token: &AccessToken) -> Re
     info!("Used url: {url:#?}");
     info!("Get new case id");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     let case_details = match response.status() {
         StatusCode::OK => response.json::<CaseDetailsResponse>().await?,
@@ -90,10 +87,7 @@ pub async fn get_case_details(config: &Config, access_token: &AccessToken) -> Re
                 "Failed to get case details: Response status: {}, Response text: {}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
@@ -122,14 +116,14 @@ pub async fn update_case_status(config: &Config, access_token: &AccessToken, cas
         case_id: case_id.into(),
     };
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .post(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .json(&request)
-        .send()
-        .await?;
+        .json(&request);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     match response.status() {
         StatusCode::ACCEPTED => (),
@@ -141,10 +135,7 @@ pub async fn update_case_status(config: &Config, access_token: &AccessToken, cas
                 "Failed to update case status: Response status: {}, Response text: {:?}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
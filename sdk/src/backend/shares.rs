@@ -1,8 +1,13 @@
 use super::error::{ApiError, Result};
 use crate::types::newtypes::AccessToken;
-use crate::{core::Config, share::Share};
-use api_types::api::user::GetShareResponse;
-use api_types::api::user::PutShareRequest;
+use crate::{
+    core::Config,
+    share::{Guardian, Share},
+};
+use api_types::api::user::{
+    GetGuardianApprovalsResponse, GetGuardianRecoveryResponse, GetGuardianSharesResponse, GetShareResponse,
+    GuardianShare, PutGuardianApprovalRequest, PutGuardianSharesRequest, PutShareRequest, StartGuardianRecoveryRequest,
+};
 use log::{debug, error, info};
 use reqwest::StatusCode;
 use secrecy::ExposeSecret;
@@ -45,14 +50,14 @@ pub async fn upload_backup_share(
 
     info!("Uploading backup share for user {}", username);
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .put(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .json(&body)
-        .send()
-        .await?;
+        .json(&body);
+    let response = config.http.send(request).await?;
     debug!("Upload backup share response: {response:#?}");
 
     match response.status() {
@@ -65,10 +70,7 @@ pub async fn upload_backup_share(
                 "Failed to upload the backup share: Response status: {}, Response text: {:?}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -104,14 +106,14 @@ pub async fn upload_recovery_share(
     };
     info!("Uploading recovery share for user {}", username);
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .put(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .json(&body)
-        .send()
-        .await?;
+        .json(&body);
+    let response = config.http.send(request).await?;
     debug!("Upload recovery share response: {response:#?}");
 
     match response.status() {
@@ -124,10 +126,7 @@ pub async fn upload_recovery_share(
                 "Failed to upload the recovery share: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -160,13 +159,13 @@ pub async fn download_backup_share(
 
     info!("Downloading backup share for user {}", username);
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Download backup share response: {response:#?}");
 
     match response.status() {
@@ -184,10 +183,7 @@ pub async fn download_backup_share(
                 "Failed to download the backup share: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -220,13 +216,13 @@ pub async fn download_recovery_share(
 
     info!("Downloading recovery share for user {}", username);
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Download recovery share response: {response:#?}");
 
     match response.status() {
@@ -244,10 +240,7 @@ pub async fn download_recovery_share(
                 "Failed to download the recovery share: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -274,13 +267,13 @@ pub async fn delete_shares(config: &Config, access_token: &AccessToken, username
 
     info!("Deleting shares for user {}", username);
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .delete(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Delete shares response: {response:#?}");
 
     match response.status() {
@@ -293,10 +286,381 @@ pub async fn delete_shares(config: &Config, access_token: &AccessToken, username
                 "Failed to delete user shares: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
+            Err(ApiError::from_response(status, text))
+        }
+    }
+}
+
+/// Uploads the shares of the guardians, replacing any existing guardian shares
+///
+/// # Arguments
+///
+/// * `config` - The configuration object.
+/// * `access_token` - The access token for authentication.
+/// * `shares` - The guardians with the share encrypted for each of them.
+/// * `username` - The corresponding user for the shares.
+///
+/// # Returns
+///
+/// Returns an empty `Result` if successful, or an `Error` if an error occurs.
+///
+/// # Errors
+///
+/// Returns an `ApiError::MissingAccessToken` if the request is unauthorized, `ApiError::ShareError` if a share is not encrypted
+/// or an `ApiError::UnexpectedResponse` if an unexpected error occurs.
+pub async fn upload_guardian_shares(
+    config: &Config,
+    access_token: &AccessToken,
+    shares: &[(Guardian, Share)],
+    username: &str,
+) -> Result<()> {
+    let base_url = &config.backend_url;
+    let url = format!("{base_url}/user/shares/guardians");
+    info!("Used url: {url:#?}");
+
+    // Double check if the shares are encrypted
+    if shares.iter().any(|(_, share)| !share.is_encrypted()) {
+        return Err(ApiError::Share("Guardian share is not encrypted".to_string()));
+    }
+
+    let body = PutGuardianSharesRequest {
+        shares: shares
+            .iter()
+            .map(|(guardian, share)| GuardianShare {
+                guardian: guardian.username.clone(),
+                public_key: guardian.public_key.clone(),
+                share: share.to_string().expose_secret().to_owned(),
+            })
+            .collect(),
+    };
+
+    info!("Uploading {} guardian shares for user {}", shares.len(), username);
+
+    let request = config
+        .http
+        .client()
+        .put(&url)
+        .bearer_auth(access_token.as_str())
+        .header("X-APP-NAME", &config.auth_provider)
+        .json(&body);
+    let response = config.http.send(request).await?;
+    debug!("Upload guardian shares response: {response:#?}");
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(ApiError::MissingAccessToken),
+        _ => {
+            let status = response.status();
+            let text = response.text().await?;
+            error!(
+                "Failed to upload the guardian shares: Response status: {}, Response text: {}",
+                status, text
+            );
+            Err(ApiError::from_response(status, text))
+        }
+    }
+}
+
+/// Download the shares of the guardians
+///
+/// # Arguments
+///
+/// * `config` - The configuration object.
+/// * `access_token` - The access token for authentication.
+/// * `username` - The corresponding user for the shares.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the guardians with their encrypted share, or an empty list if the user has no guardians.
+///
+/// # Errors
+///
+/// * `ApiError::MissingAccessToken` if the request is unauthorized.
+/// * `ApiError::ParseError` if it's not possible to parse the string share.
+pub async fn download_guardian_shares(
+    config: &Config,
+    access_token: &AccessToken,
+    username: &str,
+) -> Result<Vec<(Guardian, Share)>> {
+    let base_url = &config.backend_url;
+    let url = format!("{base_url}/user/shares/guardians");
+    info!("Used url: {url:#?}");
+
+    info!("Downloading guardian shares for user {}", username);
+
+    let request = config
+        .http
+        .client()
+        .get(&url)
+        .bearer_auth(access_token.as_str())
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
+    debug!("Download guardian shares response: {response:#?}");
+
+    match response.status() {
+        StatusCode::OK => response
+            .json::<GetGuardianSharesResponse>()
+            .await?
+            .shares
+            .into_iter()
+            .map(|share| {
+                let guardian = Guardian {
+                    username: share.guardian,
+                    public_key: share.public_key,
+                };
+                let share = share
+                    .share
+                    .parse::<Share>()
+                    .map_err(|e| ApiError::Parse(e.to_string()))?;
+                Ok((guardian, share))
             })
+            .collect(),
+        StatusCode::NOT_FOUND => Ok(Vec::new()),
+        StatusCode::UNAUTHORIZED => Err(ApiError::MissingAccessToken),
+        _ => {
+            let status = response.status();
+            let text = response.text().await?;
+            error!(
+                "Failed to download the guardian shares: Response status: {}, Response text: {}",
+                status, text
+            );
+            Err(ApiError::from_response(status, text))
+        }
+    }
+}
+
+/// Starts a recovery with the help of the guardians
+///
+/// # Arguments
+///
+/// * `config` - The configuration object.
+/// * `access_token` - The access token for authentication.
+/// * `public_key` - The base64 encoded public key the guardians encrypt the approved shares for.
+/// * `username` - The user to recover.
+///
+/// # Returns
+///
+/// Returns an empty `Result` if successful, or an `Error` if an error occurs.
+///
+/// # Errors
+///
+/// Returns an `ApiError::MissingAccessToken` if the request is unauthorized, or an `ApiError::UnexpectedResponse` if an unhandled error occurs.
+pub async fn start_guardian_recovery(
+    config: &Config,
+    access_token: &AccessToken,
+    public_key: &str,
+    username: &str,
+) -> Result<()> {
+    let base_url = &config.backend_url;
+    let url = format!("{base_url}/user/shares/guardians/recovery");
+    info!("Used url: {url:#?}");
+
+    let body = StartGuardianRecoveryRequest {
+        public_key: public_key.to_owned(),
+    };
+    info!("Starting guardian recovery for user {}", username);
+
+    let request = config
+        .http
+        .client()
+        .post(&url)
+        .bearer_auth(access_token.as_str())
+        .header("X-APP-NAME", &config.auth_provider)
+        .json(&body);
+    let response = config.http.send(request).await?;
+    debug!("Start guardian recovery response: {response:#?}");
+
+    match response.status() {
+        StatusCode::OK | StatusCode::CREATED => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(ApiError::MissingAccessToken),
+        _ => {
+            let status = response.status();
+            let text = response.text().await?;
+            error!(
+                "Failed to start the guardian recovery: Response status: {}, Response text: {}",
+                status, text
+            );
+            Err(ApiError::from_response(status, text))
+        }
+    }
+}
+
+/// Download the shares approved by the guardians for the current recovery
+///
+/// # Arguments
+///
+/// * `config` - The configuration object.
+/// * `access_token` - The access token for authentication.
+/// * `username` - The user to recover.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the approved shares, encrypted for the public key of the recovery.
+///
+/// # Errors
+///
+/// * `ApiError::MissingAccessToken` if the request is unauthorized.
+/// * `ApiError::ParseError` if it's not possible to parse the string share.
+pub async fn download_guardian_approvals(
+    config: &Config,
+    access_token: &AccessToken,
+    username: &str,
+) -> Result<Vec<Share>> {
+    let base_url = &config.backend_url;
+    let url = format!("{base_url}/user/shares/guardians/recovery");
+    info!("Used url: {url:#?}");
+
+    info!("Downloading guardian approvals for user {}", username);
+
+    let request = config
+        .http
+        .client()
+        .get(&url)
+        .bearer_auth(access_token.as_str())
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
+    debug!("Download guardian approvals response: {response:#?}");
+
+    match response.status() {
+        StatusCode::OK => response
+            .json::<GetGuardianApprovalsResponse>()
+            .await?
+            .shares
+            .iter()
+            .map(|share| share.parse::<Share>().map_err(|e| ApiError::Parse(e.to_string())))
+            .collect(),
+        StatusCode::NOT_FOUND => Ok(Vec::new()),
+        StatusCode::UNAUTHORIZED => Err(ApiError::MissingAccessToken),
+        _ => {
+            let status = response.status();
+            let text = response.text().await?;
+            error!(
+                "Failed to download the guardian approvals: Response status: {}, Response text: {}",
+                status, text
+            );
+            Err(ApiError::from_response(status, text))
+        }
+    }
+}
+
+/// Download the pending recovery of a user the current user is a guardian of
+///
+/// # Arguments
+///
+/// * `config` - The configuration object.
+/// * `access_token` - The access token of the guardian.
+/// * `username` - The user to recover.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the share encrypted for the guardian and the base64 encoded public key of the
+/// recovery, or `None` if there is no pending recovery.
+///
+/// # Errors
+///
+/// * `ApiError::MissingAccessToken` if the request is unauthorized.
+/// * `ApiError::ParseError` if it's not possible to parse the string share.
+pub async fn download_guardian_recovery(
+    config: &Config,
+    access_token: &AccessToken,
+    username: &str,
+) -> Result<Option<(Share, String)>> {
+    let base_url = &config.backend_url;
+    let url = format!("{base_url}/user/guardians/{username}/recovery");
+    info!("Used url: {url:#?}");
+
+    info!("Downloading guardian recovery of user {}", username);
+
+    let request = config
+        .http
+        .client()
+        .get(&url)
+        .bearer_auth(access_token.as_str())
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
+    debug!("Download guardian recovery response: {response:#?}");
+
+    match response.status() {
+        StatusCode::OK => {
+            let recovery = response.json::<GetGuardianRecoveryResponse>().await?;
+            let share = recovery
+                .share
+                .parse::<Share>()
+                .map_err(|e| ApiError::Parse(e.to_string()))?;
+            Ok(Some((share, recovery.public_key)))
+        }
+        StatusCode::NOT_FOUND => Ok(None),
+        StatusCode::UNAUTHORIZED => Err(ApiError::MissingAccessToken),
+        _ => {
+            let status = response.status();
+            let text = response.text().await?;
+            error!(
+                "Failed to download the guardian recovery: Response status: {}, Response text: {}",
+                status, text
+            );
+            Err(ApiError::from_response(status, text))
+        }
+    }
+}
+
+/// Uploads the approval of a guardian for the recovery of a user
+///
+/// # Arguments
+///
+/// * `config` - The configuration object.
+/// * `access_token` - The access token of the guardian.
+/// * `share` - The share encrypted for the public key of the recovery.
+/// * `username` - The user to recover.
+///
+/// # Returns
+///
+/// Returns an empty `Result` if successful, or an `Error` if an error occurs.
+///
+/// # Errors
+///
+/// Returns an `ApiError::MissingAccessToken` if the request is unauthorized, `ApiError::ShareError` if the share is not encrypted
+/// or an `ApiError::UnexpectedResponse` if an unexpected error occurs.
+pub async fn upload_guardian_approval(
+    config: &Config,
+    access_token: &AccessToken,
+    share: &Share,
+    username: &str,
+) -> Result<()> {
+    let base_url = &config.backend_url;
+    let url = format!("{base_url}/user/guardians/{username}/recovery");
+    info!("Used url: {url:#?}");
+
+    // Double check if the share is encrypted
+    if !share.is_encrypted() {
+        return Err(ApiError::Share("Guardian share is not encrypted".to_string()));
+    }
+
+    let body = PutGuardianApprovalRequest {
+        share: share.to_string().expose_secret().to_owned(),
+    };
+    info!("Approving guardian recovery of user {}", username);
+
+    let request = config
+        .http
+        .client()
+        .put(&url)
+        .bearer_auth(access_token.as_str())
+        .header("X-APP-NAME", &config.auth_provider)
+        .json(&body);
+    let response = config.http.send(request).await?;
+    debug!("Upload guardian approval response: {response:#?}");
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        StatusCode::UNAUTHORIZED => Err(ApiError::MissingAccessToken),
+        _ => {
+            let status = response.status();
+            let text = response.text().await?;
+            error!(
+                "Failed to approve the guardian recovery: Response status: {}, Response text: {}",
+                status, text
+            );
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -549,4 +913,272 @@ mod tests {
         }
         mock_server.assert();
     }
+
+    fn example_guardian() -> Guardian {
+        Guardian {
+            username: "guardian".to_string(),
+            public_key: "cHVibGljIGtleQ==".to_string(),
+        }
+    }
+
+    #[rstest::rstest]
+    #[case(200, ENCRYPTED_SHARE, Ok(()))]
+    #[case(401, ENCRYPTED_SHARE, Err(ApiError::MissingAccessToken))]
+    #[case(500, NOT_ENCRYPTED_SHARE, Err(ApiError::Share("Guardian share is not encrypted".to_string())))]
+    #[case(501, ENCRYPTED_SHARE, Err(ApiError::UnexpectedResponse {
+        code: StatusCode::NOT_IMPLEMENTED,
+        body: "".to_string()
+    }))]
+    #[tokio::test]
+    async fn test_upload_guardian_shares(
+        #[case] status_code: usize,
+        #[case] str_share: &str,
+        #[case] expected: Result<()>,
+    ) {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+
+        let mock_request = PutGuardianSharesRequest {
+            shares: vec![GuardianShare {
+                guardian: example_guardian().username,
+                public_key: example_guardian().public_key,
+                share: ENCRYPTED_SHARE.into(),
+            }],
+        };
+        let body = serde_json::to_string(&mock_request).unwrap();
+
+        let mock_server = srv
+            .mock("PUT", "/api/user/shares/guardians")
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .match_body(Matcher::Exact(body))
+            .with_status(status_code)
+            // Skip call if share is not encrypted
+            .expect(if str_share == NOT_ENCRYPTED_SHARE { 0 } else { 1 })
+            .create();
+
+        // Act
+        let shares = vec![(example_guardian(), str_share.parse::<Share>().unwrap())];
+        let response = upload_guardian_shares(&config, &TOKEN, &shares, USERNAME).await;
+
+        // Assert
+        match expected {
+            Ok(_) => response.unwrap(),
+            Err(ref err) => {
+                assert_eq!(response.unwrap_err().to_string(), err.to_string());
+            }
+        }
+        mock_server.assert();
+    }
+
+    #[rstest::rstest]
+    #[case(200, Ok(1))]
+    #[case(404, Ok(0))]
+    #[case(401, Err(ApiError::MissingAccessToken))]
+    #[tokio::test]
+    async fn test_download_guardian_shares(#[case] status_code: usize, #[case] expected: Result<usize>) {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+
+        let resp_body = GetGuardianSharesResponse {
+            shares: vec![GuardianShare {
+                guardian: example_guardian().username,
+                public_key: example_guardian().public_key,
+                share: ENCRYPTED_SHARE.into(),
+            }],
+        };
+
+        let mut mock_server = srv
+            .mock("GET", "/api/user/shares/guardians")
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .with_status(status_code)
+            .with_header("content-type", "application/json");
+        if status_code == 200 {
+            mock_server = mock_server.with_body(serde_json::to_string(&resp_body).unwrap());
+        }
+        let mock_server = mock_server.expect(1).create();
+
+        // Act
+        let response = download_guardian_shares(&config, &TOKEN, USERNAME).await;
+
+        // Assert
+        match expected {
+            Ok(count) => {
+                let shares = response.unwrap();
+                assert_eq!(shares.len(), count);
+                if let Some((guardian, share)) = shares.first() {
+                    assert_eq!(guardian, &example_guardian());
+                    assert_eq!(share.to_string().expose_secret(), ENCRYPTED_SHARE);
+                }
+            }
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+        mock_server.assert();
+    }
+
+    #[rstest::rstest]
+    #[case(201, Ok(()))]
+    #[case(401, Err(ApiError::MissingAccessToken))]
+    #[case(500, Err(ApiError::UnexpectedResponse {
+        code: StatusCode::INTERNAL_SERVER_ERROR,
+        body: "".to_string()
+    }))]
+    #[tokio::test]
+    async fn test_start_guardian_recovery(#[case] status_code: usize, #[case] expected: Result<()>) {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+
+        let body = serde_json::to_string(&StartGuardianRecoveryRequest {
+            public_key: example_guardian().public_key,
+        })
+        .unwrap();
+
+        let mock_server = srv
+            .mock("POST", "/api/user/shares/guardians/recovery")
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .match_body(Matcher::Exact(body))
+            .with_status(status_code)
+            .expect(1)
+            .create();
+
+        // Act
+        let response = start_guardian_recovery(&config, &TOKEN, &example_guardian().public_key, USERNAME).await;
+
+        // Assert
+        match expected {
+            Ok(_) => response.unwrap(),
+            Err(ref err) => {
+                assert_eq!(response.unwrap_err().to_string(), err.to_string());
+            }
+        }
+        mock_server.assert();
+    }
+
+    #[rstest::rstest]
+    #[case(200, Ok(1))]
+    #[case(404, Ok(0))]
+    #[case(401, Err(ApiError::MissingAccessToken))]
+    #[tokio::test]
+    async fn test_download_guardian_approvals(#[case] status_code: usize, #[case] expected: Result<usize>) {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+
+        let resp_body = GetGuardianApprovalsResponse {
+            shares: vec![ENCRYPTED_SHARE.into()],
+        };
+
+        let mut mock_server = srv
+            .mock("GET", "/api/user/shares/guardians/recovery")
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .with_status(status_code)
+            .with_header("content-type", "application/json");
+        if status_code == 200 {
+            mock_server = mock_server.with_body(serde_json::to_string(&resp_body).unwrap());
+        }
+        let mock_server = mock_server.expect(1).create();
+
+        // Act
+        let response = download_guardian_approvals(&config, &TOKEN, USERNAME).await;
+
+        // Assert
+        match expected {
+            Ok(count) => assert_eq!(response.unwrap().len(), count),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+        mock_server.assert();
+    }
+
+    #[rstest::rstest]
+    #[case(200, Ok(true))]
+    #[case(404, Ok(false))]
+    #[case(401, Err(ApiError::MissingAccessToken))]
+    #[tokio::test]
+    async fn test_download_guardian_recovery(#[case] status_code: usize, #[case] expected: Result<bool>) {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+
+        let resp_body = GetGuardianRecoveryResponse {
+            share: ENCRYPTED_SHARE.into(),
+            public_key: example_guardian().public_key,
+        };
+
+        let mut mock_server = srv
+            .mock("GET", format!("/api/user/guardians/{USERNAME}/recovery").as_str())
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .with_status(status_code)
+            .with_header("content-type", "application/json");
+        if status_code == 200 {
+            mock_server = mock_server.with_body(serde_json::to_string(&resp_body).unwrap());
+        }
+        let mock_server = mock_server.expect(1).create();
+
+        // Act
+        let response = download_guardian_recovery(&config, &TOKEN, USERNAME).await;
+
+        // Assert
+        match expected {
+            Ok(found) => {
+                let recovery = response.unwrap();
+                assert_eq!(recovery.is_some(), found);
+                if let Some((share, public_key)) = recovery {
+                    assert_eq!(share.to_string().expose_secret(), ENCRYPTED_SHARE);
+                    assert_eq!(public_key, example_guardian().public_key);
+                }
+            }
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+        mock_server.assert();
+    }
+
+    #[rstest::rstest]
+    #[case(200, ENCRYPTED_SHARE, Ok(()))]
+    #[case(401, ENCRYPTED_SHARE, Err(ApiError::MissingAccessToken))]
+    #[case(200, NOT_ENCRYPTED_SHARE, Err(ApiError::Share("Guardian share is not encrypted".to_string())))]
+    #[tokio::test]
+    async fn test_upload_guardian_approval(
+        #[case] status_code: usize,
+        #[case] str_share: &str,
+        #[case] expected: Result<()>,
+    ) {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+
+        let body = serde_json::to_string(&PutGuardianApprovalRequest {
+            share: ENCRYPTED_SHARE.into(),
+        })
+        .unwrap();
+
+        let mock_server = srv
+            .mock("PUT", format!("/api/user/guardians/{USERNAME}/recovery").as_str())
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .match_body(Matcher::Exact(body))
+            .with_status(status_code)
+            // Skip call if share is not encrypted
+            .expect(if str_share == NOT_ENCRYPTED_SHARE { 0 } else { 1 })
+            .create();
+
+        // Act
+        let share = str_share.parse::<Share>().unwrap();
+        let response = upload_guardian_approval(&config, &TOKEN, &share, USERNAME).await;
+
+        // Assert
+        match expected {
+            Ok(_) => response.unwrap(),
+            Err(ref err) => {
+                assert_eq!(response.unwrap_err().to_string(), err.to_string());
+            }
+        }
+        mock_server.assert();
+    }
 }
@@ -77,14 +77,14 @@ pub async fn create_new_transaction(
     info!("Used url: {url:#?}");
     info!("Create new transaction to {receiver} with the amount of {amount:?}");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .post(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .json(&body)
-        .send()
-        .await?;
+        .json(&body);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     let tx_response = match response.status() {
         StatusCode::CREATED => response.json::<CreateTransactionResponse>().await?,
@@ -96,10 +96,7 @@ pub async fn create_new_transaction(
                 "Failed to create a new transaction: Response status: {}, Response text: {}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
@@ -133,14 +130,14 @@ pub async fn commit_transaction(config: &Config, access_token: &AccessToken, ind
     info!("Used url: {url:#?}");
     info!("Commit transaction for {index}");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .post(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .json(&body)
-        .send()
-        .await?;
+        .json(&body);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     match response.status() {
         StatusCode::ACCEPTED => Ok(()),
@@ -152,10 +149,7 @@ pub async fn commit_transaction(config: &Config, access_token: &AccessToken, ind
                 "Failed to commit transaction `{index}`: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -186,14 +180,14 @@ pub async fn get_transaction_details(
     let query = GetTransactionStatusRequest { index: index.into() };
     info!("Get transaction status for {index}");
     info!("Used url: {url:#?}");
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .query(&query)
-        .send()
-        .await?;
+        .query(&query);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     match response.status() {
         StatusCode::OK => Ok(response.json::<GetTransactionDetailsResponse>().await?),
@@ -205,10 +199,7 @@ pub async fn get_transaction_details(
                 "Failed to get transaction `{index}` details: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -248,14 +239,14 @@ pub async fn get_transactions_list(
 
     info!("Get transaction list");
     info!("Used url: {url:#?}");
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .query(&query)
-        .send()
-        .await?;
+        .query(&query);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
     let tx_response = match response.status() {
         StatusCode::OK => response.json::<GetTxsDetailsResponse>().await?,
@@ -267,10 +258,7 @@ pub async fn get_transactions_list(
                 "Failed to get a list of transactions: Response status: {}, Response text: {}",
                 status, text
             );
-            return Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            });
+            return Err(ApiError::from_response(status, text));
         }
     };
 
@@ -30,13 +30,13 @@ pub async fn delete_user_account(config: &Config, access_token: &AccessToken) ->
     info!("Used url: {url:#?}");
     info!("Deleting user account");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .delete(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
 
     match response.status() {
@@ -49,10 +49,7 @@ pub async fn delete_user_account(config: &Config, access_token: &AccessToken) ->
                 "Failed to delete user account: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -85,14 +82,14 @@ pub async fn set_preferred_network(
 
     let body = SetPreferredNetworkRequest { network_id };
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .put(&url)
         .bearer_auth(access_token.as_str())
         .header("X-APP-NAME", &config.auth_provider)
-        .json(&body)
-        .send()
-        .await?;
+        .json(&body);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
 
     match response.status() {
@@ -105,10 +102,7 @@ pub async fn set_preferred_network(
                 "Failed to set preferred currency: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
@@ -134,13 +128,13 @@ pub async fn get_preferred_network(config: &Config, access_token: &AccessToken)
     info!("Used url: {url:#?}");
     info!("Getting preferred network");
 
-    let client = reqwest::Client::new();
-    let response = client
+    let request = config
+        .http
+        .client()
         .get(&url)
         .bearer_auth(access_token.as_str())
-        .header("X-APP-NAME", &config.auth_provider)
-        .send()
-        .await?;
+        .header("X-APP-NAME", &config.auth_provider);
+    let response = config.http.send(request).await?;
     debug!("Response: {response:#?}");
 
     match response.status() {
@@ -153,10 +147,7 @@ pub async fn get_preferred_network(config: &Config, access_token: &AccessToken)
                 "Failed to get preferred currency: Response status: {}, Response text: {}",
                 status, text
             );
-            Err(ApiError::UnexpectedResponse {
-                code: status,
-                body: text,
-            })
+            Err(ApiError::from_response(status, text))
         }
     }
 }
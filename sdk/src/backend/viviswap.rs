@@ -7,6 +7,7 @@
 //!
 
 use super::error::{ApiError, Result};
+use crate::core::config::HttpConfig;
 use crate::core::Config;
 use crate::types::currencies::{CryptoAmount, Currency};
 use crate::types::newtypes::AccessToken;
@@ -30,24 +31,27 @@ use rust_decimal::Decimal;
 use std::future::Future;
 
 /// helper object for calling the backend (reduces boiler-plate code)
-struct ViviswapBackendCall {
+struct ViviswapBackendCall<'a> {
+    http: &'a HttpConfig,
     request_builder: reqwest::RequestBuilder,
     method: reqwest::Method,
     url: String,
 }
 
-impl ViviswapBackendCall {
-    pub fn new(config: &Config, access_token: &AccessToken, method: reqwest::Method, url_path: &str) -> Self {
+impl<'a> ViviswapBackendCall<'a> {
+    pub fn new(config: &'a Config, access_token: &AccessToken, method: reqwest::Method, url_path: &str) -> Self {
         let url = format!("{}{url_path}", config.backend_url);
         info!("Used url: {url:#?}");
 
-        let client = reqwest::Client::new();
-        let request_builder = client
+        let request_builder = config
+            .http
+            .client()
             .request(method.clone(), &url)
             .bearer_auth(access_token.as_str())
             .header("X-APP-NAME", &config.auth_provider);
 
         Self {
+            http: &config.http,
             request_builder,
             method,
             url: url_path.to_string(),
@@ -57,16 +61,14 @@ impl ViviswapBackendCall {
     pub fn with_query<T: serde::Serialize>(self, query: &T) -> Self {
         Self {
             request_builder: self.request_builder.query(query),
-            method: self.method,
-            url: self.url,
+            ..self
         }
     }
 
     pub fn with_body<T: serde::Serialize>(self, body: &T) -> Self {
         Self {
             request_builder: self.request_builder.json(body),
-            method: self.method,
-            url: self.url,
+            ..self
         }
     }
 
@@ -75,7 +77,7 @@ impl ViviswapBackendCall {
         F: FnOnce(reqwest::Response) -> Fut,
         Fut: Future<Output = Result<R>>,
     {
-        let response = self.request_builder.send().await?;
+        let response = self.http.send(self.request_builder).await?;
         debug!("Response: {response:#?}");
 
         match response.status() {
@@ -90,10 +92,7 @@ impl ViviswapBackendCall {
                     "Failed to {method} {}: Response status: {}, Response text: {}",
                     url, status, text
                 );
-                Err(ApiError::UnexpectedResponse {
-                    code: status,
-                    body: text,
-                })
+                Err(ApiError::from_response(status, text))
             }
         }
     }
@@ -4,11 +4,17 @@
 
 use super::Sdk;
 use crate::error::{Error, Result};
+use crate::user::encryption::{UserStorageCipher, UserStorageEncryption};
 use crate::user::repository::UserRepoImpl;
-use crate::user::UserRepo;
-use log::info;
+use crate::user::{UserKvStorage, UserRepo};
+use log::{info, warn};
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use crate::backend::client::HttpConfig;
+pub use crate::share::ShareScheme;
 
 /// The default log file used by the sdk to write logs
 const ETOPAY_LOGFILE: &str = "etopay_sdk.log";
@@ -28,6 +34,20 @@ pub struct Config {
 
     /// Log level for filtering which log messages that end up in the log file.
     pub log_level: log::LevelFilter,
+
+    /// Number of consecutive wrong pin attempts after which the wallet is locked and has to be
+    /// recovered with the mnemonic.
+    pub max_pin_attempts: u32,
+
+    /// Number of blocks including and following the block of an EVM transaction after which the transaction
+    /// watcher considers it final.
+    pub evm_confirmations: u64,
+
+    /// Settings of the HTTP client used for all backend requests.
+    pub http: HttpConfig,
+
+    /// The scheme used to split the wallet mnemonic into shares when a wallet is created.
+    pub shares: ShareScheme,
 }
 
 /// Struct representing the  deserialized version of the config in JSON format.
@@ -43,6 +63,83 @@ struct DeserializedConfig {
     storage_path: String,
 
     auth_provider: String,
+
+    #[serde(default = "default_max_pin_attempts")]
+    max_pin_attempts: u32,
+
+    #[serde(default = "default_evm_confirmations")]
+    evm_confirmations: u64,
+
+    #[serde(default)]
+    http: DeserializedHttpConfig,
+
+    #[serde(default)]
+    shares: DeserializedShareScheme,
+}
+
+/// Struct representing the deserialized version of the [`HttpConfig`] in JSON format.
+#[derive(Debug, serde::Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(default)]
+struct DeserializedHttpConfig {
+    timeout_secs: u64,
+    connect_timeout_secs: u64,
+    max_retries: u32,
+    retry_base_delay_ms: u64,
+    max_retry_delay_secs: u64,
+}
+
+impl Default for DeserializedHttpConfig {
+    fn default() -> Self {
+        let http = HttpConfig::default();
+        Self {
+            timeout_secs: http.timeout.as_secs(),
+            connect_timeout_secs: http.connect_timeout.as_secs(),
+            max_retries: http.max_retries,
+            retry_base_delay_ms: http.retry_base_delay.as_millis() as u64,
+            max_retry_delay_secs: http.max_retry_delay.as_secs(),
+        }
+    }
+}
+
+/// Struct representing the deserialized version of the [`ShareScheme`] in JSON format.
+#[derive(Debug, serde::Deserialize)]
+#[cfg_attr(test, derive(PartialEq))]
+#[serde(default)]
+struct DeserializedShareScheme {
+    threshold: u8,
+    additional_labels: Vec<String>,
+}
+
+impl Default for DeserializedShareScheme {
+    fn default() -> Self {
+        let scheme = ShareScheme::default();
+        Self {
+            threshold: scheme.threshold,
+            additional_labels: scheme.additional_labels,
+        }
+    }
+}
+
+impl From<DeserializedShareScheme> for ShareScheme {
+    fn from(value: DeserializedShareScheme) -> Self {
+        Self {
+            threshold: value.threshold,
+            additional_labels: value.additional_labels,
+        }
+    }
+}
+
+impl From<DeserializedHttpConfig> for HttpConfig {
+    fn from(value: DeserializedHttpConfig) -> Self {
+        let mut http = HttpConfig::default();
+        http.timeout = Duration::from_secs(value.timeout_secs);
+        http.connect_timeout = Duration::from_secs(value.connect_timeout_secs);
+        http.max_retries = value.max_retries;
+        http.retry_base_delay = Duration::from_millis(value.retry_base_delay_ms);
+        http.max_retry_delay = Duration::from_secs(value.max_retry_delay_secs);
+        http
+    }
 }
 
 #[cfg(test)]
@@ -54,6 +151,10 @@ impl Default for DeserializedConfig {
             auth_provider: "standalone".to_string(),
             log_level: default_log_level(),
             storage_path: default_storage_path(),
+            max_pin_attempts: default_max_pin_attempts(),
+            evm_confirmations: default_evm_confirmations(),
+            http: DeserializedHttpConfig::default(),
+            shares: DeserializedShareScheme::default(),
         }
     }
 }
@@ -64,6 +165,12 @@ fn default_log_level() -> String {
 fn default_storage_path() -> String {
     ".".to_string()
 }
+fn default_max_pin_attempts() -> u32 {
+    10
+}
+fn default_evm_confirmations() -> u64 {
+    12
+}
 
 /// To be used by bindings to deserialize JSON to the [`DeserializedConfig`] struct.
 impl FromStr for DeserializedConfig {
@@ -91,12 +198,39 @@ impl TryFrom<DeserializedConfig> for Config {
             return Err(crate::Error::SetConfig("auth_provider must not be empty".to_string()));
         }
 
+        if value.max_pin_attempts == 0 {
+            return Err(crate::Error::SetConfig(
+                "max_pin_attempts must be greater than zero".to_string(),
+            ));
+        }
+
+        if value.evm_confirmations == 0 {
+            return Err(crate::Error::SetConfig(
+                "evm_confirmations must be greater than zero".to_string(),
+            ));
+        }
+
+        if value.http.timeout_secs == 0 || value.http.connect_timeout_secs == 0 {
+            return Err(crate::Error::SetConfig(
+                "http timeouts must be greater than zero".to_string(),
+            ));
+        }
+
+        let shares = ShareScheme::from(value.shares);
+        shares
+            .validate()
+            .map_err(|e| crate::Error::SetConfig(format!("Invalid shares config: {e}")))?;
+
         Ok(Self {
             backend_url: reqwest::Url::parse(&value.backend_url).map_err(|e| crate::Error::SetConfig(e.to_string()))?,
             log_level: log::LevelFilter::from_str(&value.log_level)
                 .map_err(|e| crate::Error::SetConfig(format!("Could not parse log level: {e:#?}")))?,
             auth_provider: value.auth_provider,
             path_prefix: path_prefix.into(),
+            max_pin_attempts: value.max_pin_attempts,
+            evm_confirmations: value.evm_confirmations,
+            http: value.http.into(),
+            shares,
         })
     }
 }
@@ -134,14 +268,155 @@ impl Sdk {
         Ok(())
     }
 
+    /// Set a custom storage backend for the users
+    ///
+    /// By default, the users are stored in a jammdb file in the path prefix of the [`Config`], in the browser
+    /// local storage on wasm or in memory, depending on the enabled features. The custom storage replaces the
+    /// default storage and is kept when the config is set again. Call this before initializing a user, since
+    /// the users of the previous storage are not migrated and the active user is logged out.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The storage backend, which should pass the checks in [`crate::user::conformance`].
+    pub fn set_user_storage(&mut self, storage: impl UserKvStorage + Send + Sync + 'static) {
+        info!("Setting custom user storage");
+        if self.active_user.take().is_some() {
+            warn!("The active user is logged out, since the user storage changed");
+        }
+        self.repo = Some(Box::new(UserRepoImpl::new(storage)));
+        self.custom_user_storage = true;
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.indexed_db = None;
+        }
+    }
+
+    /// Whether the users are stored in the custom storage set with [`Sdk::set_user_storage`].
+    pub fn uses_custom_user_storage(&self) -> bool {
+        self.custom_user_storage
+    }
+
+    /// Store the users in the IndexedDB of the browser
+    ///
+    /// In contrast to the browser local storage used by default, the IndexedDB is available in Web Workers and
+    /// service workers and is not limited to a few megabytes. The users are read again with
+    /// [`Sdk::reload_user_storage`] before and written with [`Sdk::flush_user_storage`] after each call, which the
+    /// wasm bindings do for every function. Users of the local storage are moved to the IndexedDB.
+    /// The IndexedDB is kept when the config is set again, call this again after
+    /// [`Sdk::set_user_storage_encryption`] to encrypt the users. The active user is logged out.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepository`] - If the IndexedDB is not available or could not be opened.
+    #[cfg(target_arch = "wasm32")]
+    pub async fn use_indexed_db_user_storage(&mut self) -> Result<()> {
+        use crate::user::indexed_db_storage::{IndexedDbUserStorage, DEFAULT_DB_NAME};
+
+        info!("Opening the IndexedDB user storage");
+        let storage = IndexedDbUserStorage::open(DEFAULT_DB_NAME, self.user_storage_cipher.clone()).await?;
+        if self.active_user.take().is_some() {
+            warn!("The active user is logged out, since the user storage changed");
+        }
+        self.indexed_db = Some(storage.connection());
+        self.repo = Some(Box::new(UserRepoImpl::new(storage)));
+        self.custom_user_storage = false;
+        Ok(())
+    }
+
+    /// Whether the users are stored in the IndexedDB opened with [`Sdk::use_indexed_db_user_storage`].
+    #[cfg(target_arch = "wasm32")]
+    pub fn uses_indexed_db_user_storage(&self) -> bool {
+        self.indexed_db.is_some()
+    }
+
+    /// Waits until all changes of the users are written to the IndexedDB. Does nothing if another user storage
+    /// is used.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepository`] - If a write failed, e.g. with
+    ///   [`crate::user::error::UserKvStorageError::QuotaExceeded`].
+    #[cfg(target_arch = "wasm32")]
+    pub async fn flush_user_storage(&self) -> Result<()> {
+        if let Some(connection) = &self.indexed_db {
+            connection.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Reads the users of the IndexedDB again after the pending changes are written, so the changes of other tabs
+    /// and workers are seen instead of being overwritten. Does nothing if another user storage is used.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepository`] - If the users could not be read or a write failed, e.g. with
+    ///   [`crate::user::error::UserKvStorageError::QuotaExceeded`].
+    #[cfg(target_arch = "wasm32")]
+    pub async fn reload_user_storage(&self) -> Result<()> {
+        if let Some(connection) = &self.indexed_db {
+            connection.reload().await?;
+        }
+        Ok(())
+    }
+
+    /// Encrypt the users stored in the default user storage
+    ///
+    /// The jammdb file and the browser local storage then store every user record encrypted with AES-256-GCM.
+    /// Records written before are encrypted the next time they are stored. Has no effect on the in-memory
+    /// storage and on a storage set with [`Sdk::set_user_storage`]. With [`UserStorageEncryption::Pin`], the
+    /// record of a user can only be read after the pin was entered in any function taking the pin, e.g.
+    /// [`Sdk::verify_pin`]. The key is rotated when the pin is changed with [`Sdk::change_pin`]. See
+    /// [`crate::user::encryption`] for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `encryption` - The source of the encryption key.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepository`] - If the user storage could not be opened again.
+    pub fn set_user_storage_encryption(&mut self, encryption: UserStorageEncryption) -> Result<()> {
+        info!("Setting user storage encryption: {encryption:?}");
+        self.user_storage_cipher = Some(Arc::new(UserStorageCipher::new(encryption)));
+        if self.custom_user_storage {
+            warn!("The custom user storage is not encrypted by the SDK");
+            return Ok(());
+        }
+        #[cfg(target_arch = "wasm32")]
+        if self.indexed_db.is_some() {
+            info!("The IndexedDB user storage is encrypted once it is opened again");
+            return Ok(());
+        }
+        if self.config.is_some() {
+            if self.active_user.take().is_some() {
+                warn!("The active user is logged out, since the user storage changed");
+            }
+            // close the storage first, since the file can only be opened once
+            self.repo = None;
+            self.initialize_user_repository()?;
+        }
+        Ok(())
+    }
+
     /// Set path prefix
     fn initialize_user_repository(&mut self) -> Result<()> {
+        if self.custom_user_storage {
+            info!("Keeping the custom user storage");
+            return Ok(());
+        }
+        #[cfg(target_arch = "wasm32")]
+        if self.indexed_db.is_some() {
+            info!("Keeping the IndexedDB user storage");
+            return Ok(());
+        }
+
         // initialize jammdb
         #[cfg(feature = "jammdb_repo")]
         let repo: Box<dyn UserRepo + Send + Sync> = {
             let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
             Box::new(UserRepoImpl::new(crate::user::file_storage::FileUserStorage::new(
                 &config.path_prefix,
+                self.user_storage_cipher.clone(),
             )?))
         };
 
@@ -149,7 +424,7 @@ impl Sdk {
         #[cfg(target_arch = "wasm32")]
         let repo: Box<dyn UserRepo + Send + Sync> = {
             // try to access to browser local storage
-            let browser_storage = crate::user::web_storage::BrowserLocalStorage::new();
+            let browser_storage = crate::user::web_storage::BrowserLocalStorage::new(self.user_storage_cipher.clone());
             if browser_storage.is_available() {
                 Box::new(UserRepoImpl::new(browser_storage))
             } else {
@@ -181,6 +456,10 @@ impl Config {
                 path_prefix: Path::new(&cleanup.path_prefix).into(),
                 auth_provider: "standalone".to_string(),
                 log_level: log::LevelFilter::Debug,
+                max_pin_attempts: default_max_pin_attempts(),
+                evm_confirmations: default_evm_confirmations(),
+                http: HttpConfig::default(),
+                shares: ShareScheme::default(),
             },
             cleanup,
         )
@@ -195,6 +474,10 @@ impl Config {
                 path_prefix: Path::new(&cleanup.path_prefix).into(),
                 auth_provider: "standalone".to_string(),
                 log_level: log::LevelFilter::Debug,
+                max_pin_attempts: default_max_pin_attempts(),
+                evm_confirmations: default_evm_confirmations(),
+                http: HttpConfig::default(),
+                shares: ShareScheme::default(),
             },
             cleanup,
         )
@@ -204,6 +487,7 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::user::MockUserKvStorage;
     use rstest::rstest;
 
     fn valid_deserialized_config() -> DeserializedConfig {
@@ -212,9 +496,35 @@ mod tests {
             log_level: "INFO".to_string(),
             storage_path: ".".to_string(),
             auth_provider: "nonempty".to_string(),
+            max_pin_attempts: 10,
+            evm_confirmations: 12,
+            http: DeserializedHttpConfig::default(),
+            shares: DeserializedShareScheme::default(),
         }
     }
 
+    #[test]
+    fn test_custom_user_storage_is_kept_when_config_changes() {
+        // Arrange
+        let mut storage = MockUserKvStorage::new();
+        storage
+            .expect_delete()
+            .once()
+            .withf(|username| username == "custom_user")
+            .returning(|_| Ok(()));
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut sdk = Sdk::default();
+        sdk.set_user_storage(storage);
+
+        // Act
+        sdk.set_config(config).unwrap();
+
+        // Assert
+        assert!(sdk.uses_custom_user_storage());
+        let repo = sdk.repo.as_mut().unwrap();
+        repo.delete("custom_user").unwrap();
+    }
+
     #[test]
     fn test_valid_config() {
         Config::try_from(valid_deserialized_config()).unwrap();
@@ -236,6 +546,75 @@ mod tests {
         Config::try_from(config).unwrap_err();
     }
 
+    #[test]
+    fn test_zero_max_pin_attempts_error() {
+        let mut config = valid_deserialized_config();
+        config.max_pin_attempts = 0;
+
+        Config::try_from(config).unwrap_err();
+    }
+
+    #[test]
+    fn test_zero_evm_confirmations_error() {
+        let mut config = valid_deserialized_config();
+        config.evm_confirmations = 0;
+
+        Config::try_from(config).unwrap_err();
+    }
+
+    #[test]
+    fn test_zero_http_timeout_error() {
+        let mut config = valid_deserialized_config();
+        config.http.timeout_secs = 0;
+
+        Config::try_from(config).unwrap_err();
+    }
+
+    #[test]
+    fn test_http_config_from_json() {
+        let config = Config::from_json(
+            r#"{
+                "backend_url": "http://example.com",
+                "auth_provider": "standalone",
+                "http": {
+                    "timeout_secs": 5,
+                    "max_retries": 0
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.http.timeout, Duration::from_secs(5));
+        assert_eq!(config.http.max_retries, 0);
+        assert_eq!(config.http.connect_timeout, HttpConfig::default().connect_timeout);
+    }
+
+    #[test]
+    fn test_shares_config_from_json() {
+        let config = Config::from_json(
+            r#"{
+                "backend_url": "http://example.com",
+                "auth_provider": "standalone",
+                "shares": {
+                    "threshold": 3,
+                    "additional_labels": ["trusted contact", "hardware backup"]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.shares.threshold, 3);
+        assert_eq!(config.shares.count(), 5);
+    }
+
+    #[test]
+    fn test_invalid_shares_error() {
+        let mut config = valid_deserialized_config();
+        config.shares.threshold = 4;
+
+        Config::try_from(config).unwrap_err();
+    }
+
     #[test]
     fn test_invalid_backend_url_error() {
         let mut config = valid_deserialized_config();
@@ -1,6 +1,6 @@
 use super::Sdk;
-use crate::testing_utils::{example_network_id, example_networks, SALT, TOKEN};
-use crate::types::users::UserEntity;
+use crate::testing_utils::{example_network_id, example_networks, ENCRYPTED_PASSWORD, SALT, TOKEN};
+use crate::types::users::{PinAttempts, UserEntity};
 use crate::wallet_manager::MockWalletManager;
 use crate::{
     testing_utils::{example_get_user, USERNAME},
@@ -89,6 +89,18 @@ pub async fn handle_error_test_cases(
                     viviswap_state: None,
                     local_share: None,
                     wallet_transactions: Vec::new(),
+                    wallet_tx_history: Default::default(),
+                    pin_attempts: Default::default(),
+                    evm_address_indices: Vec::new(),
+                    pending_transactions: Default::default(),
+                    watched_transactions: Vec::new(),
+                    watch_only: None,
+                    created_at: None,
+                    last_used_at: None,
+                    guardian_secret_key: None,
+                    guardian_recovery_key: None,
+                    encrypted_passphrase: None,
+                    offline_recovery_share: None,
                 })
             });
             sdk.repo = Some(Box::new(mock_user_repo));
@@ -111,6 +123,46 @@ pub async fn handle_error_test_cases(
                 wallet_manager: Box::new(MockWalletManager::new()),
             });
         }
+        crate::Error::PinAttemptsLockedOut => {
+            let mut mock_user_repo = MockUserRepo::new();
+            mock_user_repo.expect_get().times(1).returning(move |r1| {
+                assert_eq!(r1, USERNAME);
+                Ok(UserEntity {
+                    user_id: None,
+                    username: USERNAME.to_string(),
+                    encrypted_password: Some(ENCRYPTED_PASSWORD.clone()),
+                    salt: SALT.into(),
+                    is_kyc_verified: true,
+                    kyc_type: KycType::Undefined,
+                    viviswap_state: None,
+                    local_share: None,
+                    wallet_transactions: Vec::new(),
+                    wallet_tx_history: Default::default(),
+                    pin_attempts: PinAttempts {
+                        failed: u32::MAX,
+                        last_failed_at: Some(0),
+                    },
+                    evm_address_indices: Vec::new(),
+                    pending_transactions: Default::default(),
+                    watched_transactions: Vec::new(),
+                    watch_only: None,
+                    created_at: None,
+                    last_used_at: None,
+                    guardian_secret_key: None,
+                    guardian_recovery_key: None,
+                    encrypted_passphrase: None,
+                    offline_recovery_share: None,
+                })
+            });
+            mock_user_repo.expect_set_pin_attempts().never();
+            mock_user_repo.expect_update().never();
+            sdk.repo = Some(Box::new(mock_user_repo));
+            sdk.active_user = Some(crate::types::users::ActiveUser {
+                username: USERNAME.into(),
+                wallet_manager: Box::new(MockWalletManager::new()),
+            });
+            sdk.access_token = Some(TOKEN.clone());
+        }
         other => panic!("Got unexpected or unhandled result: {:?}", other),
     }
 }
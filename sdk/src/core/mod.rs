@@ -16,6 +16,8 @@ pub mod user;
 pub mod viviswap;
 /// Wallet module.
 pub mod wallet;
+/// Transaction watcher module.
+pub mod watcher;
 
 /// Exchange module.
 pub mod exchange;
@@ -23,6 +25,9 @@ pub mod exchange;
 /// Share module.
 pub mod share;
 
+/// Watch-only wallet module.
+pub mod watch_only;
+
 /// Testing utils in sdk core
 #[cfg(test)]
 pub(crate) mod core_testing_utils;
@@ -32,11 +37,15 @@ use crate::build;
 use crate::error::Result;
 use crate::types::networks::Network;
 use crate::types::newtypes::{AccessToken, EncryptionPin};
+use crate::types::transactions::WatchedTransaction;
 use crate::types::users::ActiveUser;
+use crate::user::encryption::UserStorageCipher;
 use crate::user::UserRepo;
+use crate::wallet::wallet_user_eth::TokenMetadataCache;
 use crate::wallet_manager::WalletBorrow;
 pub use config::Config;
 use log::debug;
+use watcher::{TransactionStatusCallback, TRANSACTION_STATUS_CAPACITY};
 
 pub(crate) type UserRepoT = Box<dyn UserRepo + Send + Sync + 'static>;
 
@@ -50,10 +59,25 @@ pub struct Sdk {
     access_token: Option<AccessToken>,
     /// Contains the user repository for storing and loading different users.
     repo: Option<UserRepoT>,
+    /// Whether the user repository uses a storage set with [`Sdk::set_user_storage`], which is kept when the
+    /// config changes.
+    custom_user_storage: bool,
+    /// The connection to the IndexedDB opened with [`Sdk::use_indexed_db_user_storage`], if the user repository
+    /// uses it. The IndexedDB is kept when the config changes.
+    #[cfg(target_arch = "wasm32")]
+    indexed_db: Option<crate::user::indexed_db_storage::IndexedDbConnection>,
+    /// Encrypts the records of the default user storage, set with [`Sdk::set_user_storage_encryption`]
+    user_storage_cipher: Option<std::sync::Arc<UserStorageCipher>>,
     /// The currently active network
     network: Option<Network>,
     /// Available networks
     networks: Vec<Network>,
+    /// Receives the status changes of watched transactions
+    transaction_status_callback: Option<TransactionStatusCallback>,
+    /// Sends the status changes of watched transactions to the streams of [`Sdk::subscribe_transaction_status`]
+    transaction_status_sender: tokio::sync::broadcast::Sender<WatchedTransaction>,
+    /// Caches the token metadata queried from ERC-20 contracts for all wallets of this instance
+    token_metadata_cache: TokenMetadataCache,
 }
 
 impl Drop for Sdk {
@@ -71,8 +95,15 @@ impl Default for Sdk {
             active_user: None,
             access_token: None,
             repo: None,
+            custom_user_storage: false,
+            #[cfg(target_arch = "wasm32")]
+            indexed_db: None,
+            user_storage_cipher: None,
             network: None,
             networks: vec![],
+            transaction_status_callback: None,
+            transaction_status_sender: tokio::sync::broadcast::channel(TRANSACTION_STATUS_CAPACITY).0,
+            token_metadata_cache: TokenMetadataCache::default(),
         }
     }
 }
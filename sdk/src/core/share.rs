@@ -2,7 +2,8 @@
 
 use super::Sdk;
 use crate::error::Result;
-use crate::share::Share;
+use crate::share::{Guardian, Share, ShareHealthReport};
+use crate::types::newtypes::EncryptionPin;
 use log::info;
 
 impl Sdk {
@@ -40,6 +41,349 @@ impl Sdk {
         active_user.wallet_manager.set_recovery_share(Some(share));
         Ok(())
     }
+
+    /// Get the additional shares created for share schemes with more than three shares.
+    ///
+    /// The additional shares are only kept in memory after the wallet is created, and should be handed out
+    /// to their holders, e.g. trusted contacts, according to their label.
+    ///
+    /// # Returns
+    ///
+    /// The additional shares, or an empty list if there are none.
+    ///
+    /// # Error
+    ///
+    /// Returns error if the user is not initialized.
+    pub async fn get_additional_shares(&self) -> Result<Vec<Share>> {
+        info!("Getting additional shares");
+        let Some(active_user) = &self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        Ok(active_user.wallet_manager.get_additional_shares())
+    }
+
+    /// Set the additional shares needed to initialize the wallet if the share scheme requires more than
+    /// the local, recovery and backup share.
+    ///
+    /// # Arguments
+    ///
+    /// * `shares` - The additional shares collected from their holders.
+    ///
+    /// # Error
+    ///
+    /// Returns error if the user is not initialized.
+    pub async fn set_additional_shares(&mut self, shares: Vec<Share>) -> Result<()> {
+        info!("Setting {} additional shares", shares.len());
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        active_user.wallet_manager.set_additional_shares(shares);
+        Ok(())
+    }
+
+    /// Check the health of the shares of the wallet without reconstructing it.
+    ///
+    /// Verifies the local share, the backup and recovery shares stored in the backend, as well as the
+    /// recovery share and additional shares provided by the user. Shares are compared using the
+    /// fingerprint of the secret embedded in them.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to decrypt the wallet password.
+    ///
+    /// # Returns
+    ///
+    /// A report with the health of each share. Use [`ShareHealthReport::needs_repair`] to check if
+    /// the shares should be repaired with [`Sdk::repair_shares`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, the user is not logged in, or the
+    /// shares could not be downloaded.
+    pub async fn check_share_health(&mut self, pin: &EncryptionPin) -> Result<ShareHealthReport> {
+        info!("Checking share health");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        let report = active_user
+            .wallet_manager
+            .check_shares(config, &self.access_token, repo, pin)
+            .await?;
+        Ok(report)
+    }
+
+    /// Re-split the wallet secret and replace all shares.
+    ///
+    /// The secret is reconstructed from the shares that are still usable, then new shares are
+    /// created with the configured share scheme. The backup and recovery shares are uploaded and the
+    /// local share is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to decrypt the wallet password.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, the user is not logged in, or not
+    /// enough usable shares are available to reconstruct the secret.
+    pub async fn repair_shares(&mut self, pin: &EncryptionPin) -> Result<()> {
+        info!("Repairing shares");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        active_user
+            .wallet_manager
+            .repair_shares(config, &self.access_token, repo, pin)
+            .await?;
+        Ok(())
+    }
+
+    /// Enable or disable unlocking the wallet with the shares on this device only.
+    ///
+    /// When enabled, the recovery share is stored on the device encrypted with the PIN, so the wallet can
+    /// be initialized without the access token and the backup share of the backend, e.g. on an offline
+    /// device that signs the transactions prepared with [`Sdk::prepare_unsigned_transaction`]. Anyone who
+    /// knows the PIN can then use the wallet on this device. The stored share is replaced when the shares
+    /// are created again and is removed when the wallet is deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to encrypt the recovery share.
+    /// * `enabled` - Whether the recovery share is stored on the device or removed from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, or the wallet could not be reconstructed.
+    pub async fn set_offline_unlock(&mut self, pin: &EncryptionPin, enabled: bool) -> Result<()> {
+        info!("Setting offline unlock to {enabled}");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        active_user
+            .wallet_manager
+            .set_offline_unlock(config, &self.access_token, repo, pin, enabled)
+            .await?;
+        Ok(())
+    }
+
+    /// Get the public key of the user as a guardian.
+    ///
+    /// Other users need this key to nominate the user as their guardian with [`Sdk::set_guardians`].
+    /// A random key pair is created on the first call, and its secret key is stored on the device
+    /// encrypted with the PIN, so the same public key is returned afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to encrypt and decrypt the secret key.
+    ///
+    /// # Returns
+    ///
+    /// The base64 encoded public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, or the wallet could not be reconstructed.
+    pub async fn get_guardian_public_key(&mut self, pin: &EncryptionPin) -> Result<String> {
+        info!("Getting guardian public key");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        let public_key = active_user
+            .wallet_manager
+            .get_guardian_public_key(config, &self.access_token, repo, pin)
+            .await?;
+        Ok(public_key)
+    }
+
+    /// Nominate guardians who can help to recover the wallet.
+    ///
+    /// The wallet secret is split again with one additional share for each guardian. These shares are
+    /// encrypted with the public key of their guardian and stored in the backend. The local, backup and
+    /// recovery shares are replaced, so the new recovery share should be downloaded again.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to decrypt the wallet password.
+    /// * `guardians` - The guardians with their public key, see [`Sdk::get_guardian_public_key`].
+    /// * `threshold` - The number of shares needed to reconstruct the wallet, at most 3.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, the user is not logged in, or the
+    /// shares could not be created or uploaded.
+    pub async fn set_guardians(&mut self, pin: &EncryptionPin, guardians: Vec<Guardian>, threshold: u8) -> Result<()> {
+        info!("Setting {} guardians", guardians.len());
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        active_user
+            .wallet_manager
+            .set_guardians(config, &self.access_token, repo, pin, guardians, threshold)
+            .await?;
+        Ok(())
+    }
+
+    /// Start recovering the wallet with the help of the guardians.
+    ///
+    /// The guardians are asked to approve the recovery with [`Sdk::approve_guardian_recovery`]. The key
+    /// used to receive the approved shares is stored on the device encrypted with the PIN until the
+    /// recovery is completed with [`Sdk::complete_guardian_recovery`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to encrypt the key of the recovery.
+    ///
+    /// # Returns
+    ///
+    /// The fingerprint of the public key of the recovery, e.g. `1a2b-3c4d-5e6f-7a8b`. It should be shown to
+    /// the user, who tells it the guardians on another channel to confirm it before approving.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, or the user is not logged in.
+    pub async fn start_guardian_recovery(&mut self, pin: &EncryptionPin) -> Result<String> {
+        info!("Starting guardian recovery");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        let fingerprint = active_user
+            .wallet_manager
+            .start_guardian_recovery(config, &self.access_token, repo, pin)
+            .await?;
+        Ok(fingerprint)
+    }
+
+    /// Get the fingerprint of the key of the recovery started by another user, who nominated this user as
+    /// guardian.
+    ///
+    /// The fingerprint should be shown to the guardian, to compare it with the fingerprint the other user
+    /// got from [`Sdk::start_guardian_recovery`] before approving.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user recovering their wallet.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the config is not initialized, the user is not logged in, or the other user did
+    /// not start a recovery.
+    pub async fn get_guardian_recovery_fingerprint(&mut self, username: &str) -> Result<String> {
+        info!("Getting guardian recovery fingerprint of {username}");
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        let fingerprint = active_user
+            .wallet_manager
+            .get_guardian_recovery_fingerprint(config, &self.access_token, username)
+            .await?;
+        Ok(fingerprint)
+    }
+
+    /// Approve the recovery of another user, who nominated this user as guardian.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to decrypt the guardian key.
+    /// * `username` - The username of the user recovering their wallet.
+    /// * `fingerprint` - The fingerprint of the recovery key confirmed by the other user, see
+    ///   [`Sdk::get_guardian_recovery_fingerprint`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, the user is not logged in, the other
+    /// user did not start a recovery, or the fingerprint of the recovery key does not match.
+    pub async fn approve_guardian_recovery(
+        &mut self,
+        pin: &EncryptionPin,
+        username: &str,
+        fingerprint: &str,
+    ) -> Result<()> {
+        info!("Approving guardian recovery of {username}");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        active_user
+            .wallet_manager
+            .approve_guardian_recovery(config, &self.access_token, repo, pin, username, fingerprint)
+            .await?;
+        Ok(())
+    }
+
+    /// Complete the recovery of the wallet with the shares approved by the guardians.
+    ///
+    /// If not enough guardians approved, the backup share is used as well, which requires the wallet
+    /// password to be set to the previous password. All shares are replaced, so the new recovery share
+    /// should be downloaded again.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, used to decrypt the wallet password.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the user or config is not initialized, the user is not logged in, no recovery
+    /// was started or not enough shares are available to reconstruct the wallet.
+    pub async fn complete_guardian_recovery(&mut self, pin: &EncryptionPin) -> Result<()> {
+        info!("Completing guardian recovery");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        active_user
+            .wallet_manager
+            .complete_guardian_recovery(config, &self.access_token, repo, pin)
+            .await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -48,10 +392,12 @@ mod tests {
     use crate::{
         core::Sdk,
         error::Result,
-        share::Share,
-        testing_utils::{set_config, USERNAME},
+        share::{Guardian, Share, ShareHealth, ShareHealthReport},
+        testing_utils::{example_get_user, set_config, PIN, USERNAME},
+        types::users::KycType,
         wallet_manager::MockWalletManager,
     };
+    use api_types::api::viviswap::detail::SwapPaymentDetailKey;
     use mockall::predicate::eq;
     use rstest::rstest;
     use secrecy::ExposeSecret;
@@ -138,4 +484,489 @@ mod tests {
             }
         }
     }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[tokio::test]
+    async fn test_set_additional_shares(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_set_additional_shares()
+                    .once()
+                    .with(eq(vec![Share::mock_share()]))
+                    .returning(|_shares| ());
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.set_additional_shares(vec![Share::mock_share()]).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    fn example_report() -> ShareHealthReport {
+        ShareHealthReport {
+            local: ShareHealth::Healthy,
+            backup: ShareHealth::Stale,
+            recovery: ShareHealth::Healthy,
+            provided_recovery: ShareHealth::Missing,
+            additional: Vec::new(),
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(example_report()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_check_share_health(#[case] expected: Result<ShareHealthReport>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_check_shares()
+                    .once()
+                    .returning(|_, _, _, _| Ok(example_report()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.check_share_health(&PIN).await;
+
+        // Assert
+        match expected {
+            Ok(report) => {
+                let response = response.unwrap();
+                assert_eq!(response, report);
+                assert!(response.needs_repair());
+            }
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_repair_shares(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_repair_shares()
+                    .once()
+                    .returning(|_, _, _, _| Ok(()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.repair_shares(&PIN).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_set_offline_unlock(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_set_offline_unlock()
+                    .once()
+                    .returning(|_, _, _, _, enabled| {
+                        assert!(enabled);
+                        Ok(())
+                    });
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.set_offline_unlock(&PIN, true).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(String::from("public key")))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_get_guardian_public_key(#[case] expected: Result<String>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_get_guardian_public_key()
+                    .once()
+                    .returning(|_, _, _, _| Ok(String::from("public key")));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.get_guardian_public_key(&PIN).await;
+
+        // Assert
+        match expected {
+            Ok(public_key) => assert_eq!(response.unwrap(), public_key),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    fn example_guardian() -> Guardian {
+        Guardian {
+            username: String::from("guardian"),
+            public_key: String::from("public key"),
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_set_guardians(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_set_guardians()
+                    .once()
+                    .withf(|_, _, _, _, guardians, threshold| guardians == &[example_guardian()] && *threshold == 2)
+                    .returning(|_, _, _, _, _, _| Ok(()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.set_guardians(&PIN, vec![example_guardian()], 2).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_start_guardian_recovery(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_start_guardian_recovery()
+                    .once()
+                    .returning(|_, _, _, _| Ok(String::from("1a2b-3c4d-5e6f-7a8b")));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.start_guardian_recovery(&PIN).await;
+
+        // Assert
+        match expected {
+            Ok(()) => assert_eq!(response.unwrap(), "1a2b-3c4d-5e6f-7a8b"),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[tokio::test]
+    async fn test_get_guardian_recovery_fingerprint(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_get_guardian_recovery_fingerprint()
+                    .once()
+                    .withf(|_, _, username| username == "friend")
+                    .returning(|_, _, _| Ok(String::from("1a2b-3c4d-5e6f-7a8b")));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.get_guardian_recovery_fingerprint("friend").await;
+
+        // Assert
+        match expected {
+            Ok(()) => assert_eq!(response.unwrap(), "1a2b-3c4d-5e6f-7a8b"),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_approve_guardian_recovery(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_approve_guardian_recovery()
+                    .once()
+                    .withf(|_, _, _, _, username, fingerprint| {
+                        username == "friend" && fingerprint == "1a2b-3c4d-5e6f-7a8b"
+                    })
+                    .returning(|_, _, _, _, _, _| Ok(()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk
+            .approve_guardian_recovery(&PIN, "friend", "1a2b-3c4d-5e6f-7a8b")
+            .await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_complete_guardian_recovery(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_complete_guardian_recovery()
+                    .once()
+                    .returning(|_, _, _, _| Ok(()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.complete_guardian_recovery(&PIN).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
 }
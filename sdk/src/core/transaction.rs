@@ -1,20 +1,26 @@
-use super::Sdk;
+use super::watcher::watch_transaction;
+use super::{Config, Sdk, UserRepoT};
 use crate::backend::transactions::{
     commit_transaction, create_new_transaction, get_transaction_details, get_transactions_list,
 };
 use crate::error::Result;
 use crate::types::currencies::CryptoAmount;
 use crate::types::networks::{Network, NetworkType};
-use crate::types::transactions::{GasCostEstimation, PurchaseDetails};
+use crate::types::transactions::{
+    GasCostEstimation, PendingTransaction, PurchaseDetails, TransactionReplacement, UnsignedTransaction,
+    UnsignedTransactionDetails,
+};
 use crate::types::{
-    newtypes::EncryptionPin,
+    newtypes::{AccessToken, EncryptionPin},
     transactions::{TxInfo, TxList},
 };
 use crate::wallet::error::WalletError;
-use crate::wallet_user::TransactionIntent;
+use crate::wallet_manager::{WalletBorrow, WalletManager};
+use crate::wallet_user::{TransactionIntent, WalletUser};
 use api_types::api::networks::ApiNetworkType;
 use api_types::api::transactions::{ApiApplicationMetadata, ApiTxStatus, PurchaseModel, Reason};
-use log::{debug, info};
+use log::{debug, info, warn};
+use std::collections::HashMap;
 
 impl Sdk {
     /// Create purchase request
@@ -126,7 +132,7 @@ impl Sdk {
     /// if there is an error getting the transaction details, or if there is an error committing the transaction.
     pub async fn confirm_purchase_request(&mut self, pin: &EncryptionPin, purchase_id: &str) -> Result<()> {
         info!("Confirming purchase request with id {purchase_id}");
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -174,23 +180,41 @@ impl Sdk {
             address_to: tx_details.system_address.clone(),
             amount,
             data: Some(purchase_id.to_string().into_bytes()),
+            address_from: None,
         };
 
         let tx_id = wallet.send_amount(&intent).await?;
 
         // Store tx details for all networks other than Stardust (which stores transactions internally)
         // TODO: rework this to always store the transactions
-        if let ApiNetworkType::Evm { .. } = tx_details.network.network_type {
-            let tx_id = wallet.send_amount(&intent).await?;
-
-            let newly_created_transaction = wallet.get_wallet_tx(&tx_id).await?;
-            let mut user = repo.get(&active_user.username)?;
-            user.wallet_transactions.push(newly_created_transaction);
-            let _ = repo.set_wallet_transactions(&active_user.username, user.wallet_transactions);
+        match tx_details.network.network_type {
+            ApiNetworkType::Evm { .. } => {
+                let newly_created_transaction = wallet.get_wallet_tx(&tx_id).await?;
+                let mut user = repo.get(&active_user.username)?;
+                user.wallet_transactions.push(newly_created_transaction);
+                let _ = repo.set_wallet_transactions(&active_user.username, user.wallet_transactions);
+            }
+            ApiNetworkType::EvmErc20 { .. } => {
+                // token transfers are kept in the scanned history of the network, the next scan
+                // will pick up the confirmed transfer event and replace this entry
+                let newly_created_transaction = wallet.get_wallet_tx(&tx_id).await?;
+                let mut user = repo.get(&active_user.username)?;
+                let mut history = user
+                    .wallet_tx_history
+                    .remove(&tx_details.network.id)
+                    .unwrap_or_default();
+                history.merge([newly_created_transaction]);
+                let _ = repo.set_wallet_tx_history(&active_user.username, &tx_details.network.id, history);
+            }
+            ApiNetworkType::Stardust { .. } => {}
         }
 
         debug!("Transaction id on network: {tx_id}");
 
+        if let Err(e) = watch_transaction(repo, &active_user.username, &tx_details.network.id, &tx_id) {
+            warn!("Could not watch transaction {tx_id}: {e}");
+        }
+
         commit_transaction(config, access_token, purchase_id, &tx_id).await?;
 
         Ok(())
@@ -220,8 +244,38 @@ impl Sdk {
         amount: CryptoAmount,
         data: Option<Vec<u8>>,
     ) -> Result<String> {
-        info!("Sending amount {amount:?} to receiver {address}");
-        self.verify_pin(pin).await?;
+        self.send_amount_from(pin, None, address, amount, data).await
+    }
+
+    /// Send amount from a specific address of the wallet to receiver address
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `sender` - The address of the wallet to send from, one of [`Sdk::get_wallet_addresses`]. If `None`, the
+    ///   default address is used. Only supported for EVM networks.
+    /// * `address` - The receiver's address.
+    /// * `amount` - The amount to send.
+    /// * `data` - The associated data with the tag. Optional.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(String)` containing the transaction hash if the amount is sent successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the sender address does not belong to the wallet, or if there is an error sending the amount.
+    pub async fn send_amount_from(
+        &mut self,
+        pin: &EncryptionPin,
+        sender: Option<&str>,
+        address: &str,
+        amount: CryptoAmount,
+        data: Option<Vec<u8>>,
+    ) -> Result<String> {
+        info!("Sending amount {amount:?} from {sender:?} to receiver {address}");
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -243,34 +297,458 @@ impl Sdk {
             address_to: address.to_string(),
             amount,
             data,
+            address_from: sender.map(String::from),
         };
 
         let tx_id = match network.network_type {
-            NetworkType::EvmErc20 {
-                node_urls: _,
-                chain_id: _,
-                contract_address: _,
-            } => wallet.send_amount(&intent).await?,
+            NetworkType::EvmErc20 { .. } => {
+                send_tracked_evm_transaction(repo, &active_user.username, &network.id, &**wallet, &intent).await?
+            }
             NetworkType::Evm {
                 node_urls: _,
                 chain_id: _,
             } => {
-                let tx_id = wallet.send_amount(&intent).await?;
+                let tx_id =
+                    send_tracked_evm_transaction(repo, &active_user.username, &network.id, &**wallet, &intent).await?;
 
                 // store the created transaction in the repo
-                let newly_created_transaction = wallet.get_wallet_tx(&tx_id).await?;
-                let user = repo.get(&active_user.username)?;
-                let mut wallet_transactions = user.wallet_transactions;
-                wallet_transactions.push(newly_created_transaction);
-                let _ = repo.set_wallet_transactions(&active_user.username, wallet_transactions);
+                match wallet.get_wallet_tx(&tx_id).await {
+                    Ok(newly_created_transaction) => {
+                        let user = repo.get(&active_user.username)?;
+                        let mut wallet_transactions = user.wallet_transactions;
+                        wallet_transactions.push(newly_created_transaction);
+                        let _ = repo.set_wallet_transactions(&active_user.username, wallet_transactions);
+                    }
+                    Err(e) => warn!("Could not get the details of the sent transaction {tx_id}: {e}"),
+                }
                 tx_id
             }
             NetworkType::Stardust { node_urls: _ } => wallet.send_amount(&intent).await?,
         };
 
+        if let Err(e) = watch_transaction(repo, &active_user.username, &network.id, &tx_id) {
+            warn!("Could not watch transaction {tx_id}: {e}");
+        }
+
         Ok(tx_id)
     }
 
+    /// Get the EVM transactions of the current network that were sent but are not yet included in a block
+    ///
+    /// Transactions whose nonce is already used by an included transaction (either the transaction itself or one
+    /// of its replacements) are removed from the tracked transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    ///
+    /// # Returns
+    ///
+    /// Returns the pending transactions, ordered by the time they were signed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// or if the nonces cannot be retrieved from the network.
+    pub async fn get_pending_transactions(&mut self, pin: &EncryptionPin) -> Result<Vec<PendingTransaction>> {
+        info!("Getting pending transactions");
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+
+        let wallet = active_user
+            .wallet_manager
+            .try_get(config, &self.access_token, repo, network.clone(), pin)
+            .await?;
+
+        get_unconfirmed_transactions(repo, &active_user.username, &network.id, &**wallet).await
+    }
+
+    /// Speed up a pending EVM transaction
+    ///
+    /// Signs and broadcasts the same transaction again with the same nonce and increased fees, so that it replaces
+    /// the pending transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `transaction_hash` - The hash of the pending transaction, one of [`Sdk::get_pending_transactions`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the hash of the replacement transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the transaction is not pending, or if the network rejects the replacement.
+    pub async fn speed_up_transaction(&mut self, pin: &EncryptionPin, transaction_hash: &str) -> Result<String> {
+        info!("Speeding up transaction {transaction_hash}");
+        self.replace_transaction(pin, transaction_hash, TransactionReplacement::SpeedUp)
+            .await
+    }
+
+    /// Cancel a pending EVM transaction
+    ///
+    /// Signs and broadcasts a transaction without value to the own address with the same nonce and increased
+    /// fees, so that it replaces the pending transaction. The cancellation only succeeds if the replacement is
+    /// included before the original transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `transaction_hash` - The hash of the pending transaction, one of [`Sdk::get_pending_transactions`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the hash of the cancel transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the transaction is not pending, or if the network rejects the replacement.
+    pub async fn cancel_transaction(&mut self, pin: &EncryptionPin, transaction_hash: &str) -> Result<String> {
+        info!("Cancelling transaction {transaction_hash}");
+        self.replace_transaction(pin, transaction_hash, TransactionReplacement::Cancel)
+            .await
+    }
+
+    /// Broadcast all pending EVM transactions of the current network again
+    ///
+    /// Nodes drop transactions from their pool, e.g. when they restart or the fees are too low. Since later
+    /// transactions of the same sender cannot be included before, the pending transactions should be
+    /// broadcast again, e.g. after the app is restarted.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    ///
+    /// # Returns
+    ///
+    /// Returns the hashes of the transactions that were broadcast successfully.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// or if the nonces cannot be retrieved from the network. Failing to broadcast a single transaction is
+    /// only logged, since the node might already know it.
+    pub async fn rebroadcast_pending_transactions(&mut self, pin: &EncryptionPin) -> Result<Vec<String>> {
+        info!("Re-broadcasting pending transactions");
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+
+        let wallet = active_user
+            .wallet_manager
+            .try_get(config, &self.access_token, repo, network.clone(), pin)
+            .await?;
+
+        let remaining = get_unconfirmed_transactions(repo, &active_user.username, &network.id, &**wallet).await?;
+
+        let mut broadcast = Vec::new();
+        for tx in remaining {
+            match wallet.broadcast_transaction(&tx).await {
+                Ok(()) => broadcast.push(tx.transaction_hash),
+                Err(e) => warn!("Could not re-broadcast transaction {}: {e}", tx.transaction_hash),
+            }
+        }
+
+        Ok(broadcast)
+    }
+
+    /// Prepare an EVM transaction to be signed on an offline device
+    ///
+    /// The nonce, fees and gas limit are retrieved from the network, the nonces of tracked pending transactions of
+    /// the sender are skipped. Without a pin, the watch-only wallet is used, so the online device does not need
+    /// the keys of the wallet. The transaction is signed with [`Sdk::sign_unsigned_transaction`] on the offline
+    /// device and broadcast with [`Sdk::broadcast_signed_transaction`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, or `None` to use the watch-only wallet.
+    /// * `sender` - The address of the wallet to send from. If `None`, the default address is used.
+    /// * `address` - The receiver's address.
+    /// * `amount` - The amount to send.
+    /// * `data` - The associated data with the tag. Optional.
+    ///
+    /// # Returns
+    ///
+    /// Returns the unsigned transaction, see [`UnsignedTransaction::to_qr_string`] to transfer it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the network is not an EVM network, or if the nonce or fees cannot be retrieved from the network.
+    pub async fn prepare_unsigned_transaction(
+        &mut self,
+        pin: Option<&EncryptionPin>,
+        sender: Option<&str>,
+        address: &str,
+        amount: CryptoAmount,
+        data: Option<Vec<u8>>,
+    ) -> Result<UnsignedTransaction> {
+        info!("Preparing unsigned transaction of amount {amount:?} from {sender:?} to receiver {address}");
+        if let Some(pin) = pin {
+            self.check_pin(pin).await?;
+        }
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        let wallet = try_get_online_wallet(
+            &mut *active_user.wallet_manager,
+            self.config.as_mut(),
+            &self.access_token,
+            repo,
+            network.clone(),
+            pin,
+        )
+        .await?;
+
+        let intent = TransactionIntent {
+            address_to: address.to_string(),
+            amount,
+            data,
+            address_from: sender.map(String::from),
+        };
+
+        let sender = match sender {
+            Some(sender) => sender.to_string(),
+            None => wallet.get_address().await?,
+        };
+        let pending = get_unconfirmed_transactions(repo, &active_user.username, &network.id, &**wallet).await?;
+        let min_nonce = next_tracked_nonce(&pending, &sender);
+
+        Ok(wallet.prepare_unsigned_transaction(&intent, min_nonce).await?)
+    }
+
+    /// Decode an EVM transaction prepared by [`Sdk::prepare_unsigned_transaction`] to review it before signing
+    ///
+    /// The transaction is decoded without contacting the network, so this can be called on the offline device to
+    /// show the receiver, value, data and fees to the user before calling [`Sdk::sign_unsigned_transaction`]. For
+    /// token transfers without configured token decimals, the decimals are queried from the network.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `unsigned` - The unsigned transaction, see [`UnsignedTransaction::from_qr_string`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the decoded content of the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the transaction cannot be decoded, is for another chain, or if its sender does not belong to the wallet.
+    pub async fn review_unsigned_transaction(
+        &mut self,
+        pin: &EncryptionPin,
+        unsigned: &UnsignedTransaction,
+    ) -> Result<UnsignedTransactionDetails> {
+        info!("Reviewing unsigned transaction from {}", unsigned.from);
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+
+        let wallet = active_user
+            .wallet_manager
+            .try_get(config, &self.access_token, repo, network, pin)
+            .await?;
+
+        Ok(wallet.review_unsigned_transaction(unsigned).await?)
+    }
+
+    /// Sign an EVM transaction prepared by [`Sdk::prepare_unsigned_transaction`]
+    ///
+    /// The transaction is signed without contacting the network, so this can be called on an offline device.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `unsigned` - The unsigned transaction, see [`UnsignedTransaction::from_qr_string`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the signed transaction, see [`PendingTransaction::to_qr_string`] to transfer it back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the transaction is for another chain, or if its sender does not belong to the wallet.
+    pub async fn sign_unsigned_transaction(
+        &mut self,
+        pin: &EncryptionPin,
+        unsigned: &UnsignedTransaction,
+    ) -> Result<PendingTransaction> {
+        info!("Signing unsigned transaction from {}", unsigned.from);
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+
+        let wallet = active_user
+            .wallet_manager
+            .try_get(config, &self.access_token, repo, network, pin)
+            .await?;
+
+        Ok(wallet.sign_unsigned_transaction(unsigned).await?)
+    }
+
+    /// Broadcast an EVM transaction that was signed on an offline device
+    ///
+    /// The transaction is tracked as pending and watched like the transactions sent with [`Sdk::send_amount`].
+    /// Without a pin, the watch-only wallet is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, or `None` to use the watch-only wallet.
+    /// * `raw_transaction` - The signed transaction, see [`PendingTransaction::raw_transaction_from_qr_string`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the hash of the broadcast transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, if there is an error verifying the PIN,
+    /// if the transaction was not signed by the wallet, or if the network rejects the transaction.
+    pub async fn broadcast_signed_transaction(
+        &mut self,
+        pin: Option<&EncryptionPin>,
+        raw_transaction: &[u8],
+    ) -> Result<String> {
+        info!("Broadcasting signed transaction");
+        if let Some(pin) = pin {
+            self.check_pin(pin).await?;
+        }
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        let wallet = try_get_online_wallet(
+            &mut *active_user.wallet_manager,
+            self.config.as_mut(),
+            &self.access_token,
+            repo,
+            network.clone(),
+            pin,
+        )
+        .await?;
+
+        let signed = wallet.broadcast_signed_transaction(raw_transaction).await?;
+        let tx_id = signed.transaction_hash.clone();
+        info!("Broadcast transaction {tx_id} with nonce {}", signed.nonce);
+
+        // the transaction is already broadcast, so failing to track it is only logged
+        let mut pending = get_tracked_transactions(repo, &active_user.username, &network.id)?;
+        if !pending.iter().any(|tx| tx.transaction_hash == tx_id) {
+            pending.push(signed);
+            if let Err(e) = repo.set_pending_transactions(&active_user.username, &network.id, pending) {
+                warn!("Could not track transaction {tx_id}: {e}");
+            }
+        }
+
+        if let Err(e) = watch_transaction(repo, &active_user.username, &network.id, &tx_id) {
+            warn!("Could not watch transaction {tx_id}: {e}");
+        }
+
+        Ok(tx_id)
+    }
+
+    /// Replace a pending EVM transaction of the current network and track the replacement instead.
+    async fn replace_transaction(
+        &mut self,
+        pin: &EncryptionPin,
+        transaction_hash: &str,
+        replacement: TransactionReplacement,
+    ) -> Result<String> {
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+
+        let wallet = active_user
+            .wallet_manager
+            .try_get(config, &self.access_token, repo, network.clone(), pin)
+            .await?;
+
+        // a transaction whose nonce is already confirmed cannot be replaced anymore
+        let mut pending = get_unconfirmed_transactions(repo, &active_user.username, &network.id, &**wallet).await?;
+        let Some(position) = pending
+            .iter()
+            .position(|tx| tx.transaction_hash.eq_ignore_ascii_case(transaction_hash))
+        else {
+            return Err(WalletError::TransactionNotFound.into());
+        };
+
+        let original = &pending[position];
+        let mut signed = wallet.sign_replacement(original, replacement).await?;
+        signed.replaces = original.replaces.clone();
+        signed.replaces.push(original.transaction_hash.clone());
+        wallet.broadcast_transaction(&signed).await?;
+
+        let replacement_hash = signed.transaction_hash.clone();
+        pending[position] = signed;
+        repo.set_pending_transactions(&active_user.username, &network.id, pending)?;
+
+        // keep watching the original transaction, since it might still be included instead of the replacement. It
+        // is reported as replaced by the watcher otherwise.
+        if let Err(e) = watch_transaction(repo, &active_user.username, &network.id, &replacement_hash) {
+            warn!("Could not watch transaction {replacement_hash}: {e}");
+        }
+
+        Ok(replacement_hash)
+    }
+
     /// Estimate gas for sending amount to receiver
     ///
     /// # Arguments
@@ -296,7 +774,7 @@ impl Sdk {
         data: Option<Vec<u8>>,
     ) -> Result<GasCostEstimation> {
         info!("Estimating gas for sending amount {amount:?} to receiver {address}");
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -318,6 +796,7 @@ impl Sdk {
             address_to: address.to_string(),
             amount,
             data,
+            address_from: None,
         };
 
         let estimate = wallet.estimate_gas_cost(&intent).await?;
@@ -375,18 +854,135 @@ impl Sdk {
     }
 }
 
+/// Get the tracked pending transactions of the user on the network.
+pub(super) fn get_tracked_transactions(
+    repo: &mut UserRepoT,
+    username: &str,
+    network_id: &str,
+) -> Result<Vec<PendingTransaction>> {
+    let mut user = repo.get(username)?;
+    Ok(user.pending_transactions.remove(network_id).unwrap_or_default())
+}
+
+/// Get the tracked pending transactions of the user on the network without the ones whose nonce is already
+/// confirmed on the chain. The pruned transactions are removed from the repository.
+async fn get_unconfirmed_transactions(
+    repo: &mut UserRepoT,
+    username: &str,
+    network_id: &str,
+    wallet: &(dyn WalletUser + Send + Sync),
+) -> Result<Vec<PendingTransaction>> {
+    let pending = get_tracked_transactions(repo, username, network_id)?;
+    let remaining = prune_included_transactions(wallet, pending.clone()).await?;
+    if remaining != pending {
+        repo.set_pending_transactions(username, network_id, remaining.clone())?;
+    }
+    Ok(remaining)
+}
+
+/// Sign the transaction locally, track it as pending and broadcast it. The nonce is chosen after the nonces of
+/// the unconfirmed tracked transactions of the sender, so a transaction dropped by the node does not get replaced
+/// by accident, while transactions that are already included do not leave a gap.
+async fn send_tracked_evm_transaction(
+    repo: &mut UserRepoT,
+    username: &str,
+    network_id: &str,
+    wallet: &(dyn WalletUser + Send + Sync),
+    intent: &TransactionIntent,
+) -> Result<String> {
+    let mut pending = get_unconfirmed_transactions(repo, username, network_id, wallet).await?;
+
+    let sender = match &intent.address_from {
+        Some(address) => address.clone(),
+        None => wallet.get_address().await?,
+    };
+    let min_nonce = next_tracked_nonce(&pending, &sender);
+
+    let signed = wallet.sign_transaction(intent, min_nonce).await?;
+    let tx_id = signed.transaction_hash.clone();
+
+    // track the transaction before broadcasting it, so it is not lost if the app is closed in between
+    pending.push(signed.clone());
+    repo.set_pending_transactions(username, network_id, pending.clone())?;
+
+    if let Err(e) = wallet.broadcast_transaction(&signed).await {
+        pending.pop();
+        let _ = repo.set_pending_transactions(username, network_id, pending);
+        return Err(e.into());
+    }
+
+    info!("Broadcast transaction {tx_id} with nonce {}", signed.nonce);
+    Ok(tx_id)
+}
+
+/// The nonce following the tracked pending transactions of the sender, if there are any.
+fn next_tracked_nonce(pending: &[PendingTransaction], sender: &str) -> Option<u64> {
+    pending
+        .iter()
+        .filter(|tx| tx.from.eq_ignore_ascii_case(sender))
+        .map(|tx| tx.nonce + 1)
+        .max()
+}
+
+/// Get the wallet of the user if the pin is given, otherwise the watch-only wallet. Both can prepare and
+/// broadcast transactions, only the former can sign them.
+async fn try_get_online_wallet<'a>(
+    wallet_manager: &'a mut (dyn WalletManager + Send + Sync + 'static),
+    config: Option<&mut Config>,
+    access_token: &Option<AccessToken>,
+    repo: &mut UserRepoT,
+    network: Network,
+    pin: Option<&EncryptionPin>,
+) -> Result<WalletBorrow<'a>> {
+    match pin {
+        Some(pin) => {
+            let config = config.ok_or(crate::Error::MissingConfig)?;
+            Ok(wallet_manager.try_get(config, access_token, repo, network, pin).await?)
+        }
+        None => Ok(wallet_manager.try_get_watch_only(repo, network)?),
+    }
+}
+
+/// Remove the transactions whose nonce is already used by a transaction included in a block.
+pub(super) async fn prune_included_transactions(
+    wallet: &(dyn WalletUser + Send + Sync),
+    pending: Vec<PendingTransaction>,
+) -> Result<Vec<PendingTransaction>> {
+    let mut confirmed_nonces = HashMap::new();
+    let mut remaining = Vec::new();
+    for tx in pending {
+        let confirmed_nonce = match confirmed_nonces.get(&tx.from) {
+            Some(nonce) => *nonce,
+            None => {
+                let nonce = wallet.get_confirmed_nonce(&tx.from).await?;
+                confirmed_nonces.insert(tx.from.clone(), nonce);
+                nonce
+            }
+        };
+        if tx.nonce >= confirmed_nonce {
+            remaining.push(tx);
+        } else {
+            debug!(
+                "Transaction {} with nonce {} is included",
+                tx.transaction_hash, tx.nonce
+            );
+        }
+    }
+    Ok(remaining)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::core_testing_utils::handle_error_test_cases;
     use crate::testing_utils::{
-        example_api_network, example_get_user, example_network_id, example_networks, example_tx_details,
-        example_tx_metadata, example_wallet_borrow, set_config, AUTH_PROVIDER, HEADER_X_APP_NAME, PURCHASE_ID, TOKEN,
-        TX_INDEX, USERNAME,
+        example_api_network, example_erc20_api_network, example_get_user, example_network_id, example_networks,
+        example_tx_details, example_tx_metadata, example_wallet_borrow, set_config, AUTH_PROVIDER, ENCRYPTED_PASSWORD,
+        HEADER_X_APP_NAME, PIN, PURCHASE_ID, SALT, TOKEN, TX_INDEX, USERNAME,
     };
     use crate::types::currencies::Currency;
     use crate::types::transactions::WalletTxInfo;
-    use crate::types::users::KycType;
+    use crate::types::users::{KycType, UserEntity};
     use crate::{
         core::Sdk,
         user::MockUserRepo,
@@ -403,6 +999,84 @@ mod tests {
     use rstest::rstest;
     use rust_decimal_macros::dec;
 
+    const SENDER: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+    fn pending_transaction(transaction_hash: &str, nonce: u64) -> PendingTransaction {
+        PendingTransaction {
+            transaction_hash: transaction_hash.to_string(),
+            from: SENDER.to_string(),
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            raw_transaction: Vec::new(),
+            created_at: 0,
+            replaces: Vec::new(),
+        }
+    }
+
+    fn user_with_pending_transactions(pending: Vec<PendingTransaction>) -> UserEntity {
+        UserEntity {
+            user_id: None,
+            username: USERNAME.to_string(),
+            encrypted_password: Some(ENCRYPTED_PASSWORD.clone()),
+            salt: SALT.into(),
+            is_kyc_verified: false,
+            kyc_type: KycType::Undefined,
+            viviswap_state: None,
+            local_share: None,
+            wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: HashMap::from([(example_network_id(Currency::Eth), pending)]),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
+        }
+    }
+
+    /// Creates an [`Sdk`] on the ETH network with a user that has the given pending transactions and is read
+    /// `user_reads` times from the repository. Both the wallet and the watch-only wallet are created by `mock_wallet`.
+    async fn sdk_with_pending_transactions(
+        pending: Vec<PendingTransaction>,
+        user_reads: usize,
+        mut mock_user_repo: MockUserRepo,
+        mock_wallet: impl Fn() -> MockWalletUser + Send + Sync + 'static,
+    ) -> (Sdk, mockito::Server, testing::CleanUp) {
+        let (srv, config, cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+
+        mock_user_repo
+            .expect_get()
+            .times(user_reads)
+            .returning(move |_| Ok(user_with_pending_transactions(pending.clone())));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mock_wallet = std::sync::Arc::new(mock_wallet);
+        let mock_watch_only_wallet = mock_wallet.clone();
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_try_get()
+            .returning(move |_, _, _, _, _| Ok(WalletBorrow::from(mock_wallet())));
+        mock_wallet_manager
+            .expect_try_get_watch_only()
+            .returning(move |_, _| Ok(WalletBorrow::from(mock_watch_only_wallet())));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        (sdk, srv, cleanup)
+    }
+
     fn examples_wallet_tx_list() -> GetTxsDetailsResponse {
         let main_address = "atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r".to_string();
         let aux_address = "atoi1qpnrumvaex24dy0duulp4q07lpa00w20ze6jfd0xly422kdcjxzakzsz5kf".to_string();
@@ -524,7 +1198,11 @@ mod tests {
 
         match &expected {
             Ok(_) => {
-                let mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 1, KycType::Undefined);
+                let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 2, KycType::Undefined);
+                mock_user_repo
+                    .expect_set_watched_transactions()
+                    .once()
+                    .returning(|_, _| Ok(()));
                 sdk.repo = Some(Box::new(mock_user_repo));
 
                 let mut mock_wallet_manager = MockWalletManager::new();
@@ -624,6 +1302,105 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_commit_transaction_erc20_should_store_transaction_in_network_history() {
+        // Arrange
+        let (mut srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        let erc20_network = example_erc20_api_network();
+        let mut networks = example_networks();
+        networks.push(erc20_network.clone().into());
+        sdk.set_networks(networks);
+        sdk.set_network(erc20_network.id.clone()).await.unwrap();
+
+        let wallet_transaction = WalletTxInfo {
+            date: String::new(),
+            block_id: None,
+            transaction_id: String::from("tx_id"),
+            log_index: None,
+            receiver: String::new(),
+            incoming: false,
+            amount: 5.0,
+            network: String::from("ETH"),
+            status: format!("{:?}", InclusionState::Pending),
+            explorer_url: None,
+        };
+
+        let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Eth, false, 3, KycType::Undefined);
+        mock_user_repo
+            .expect_set_watched_transactions()
+            .once()
+            .returning(|_, _| Ok(()));
+        let expected_transaction = wallet_transaction.clone();
+        let expected_network_id = erc20_network.id.clone();
+        mock_user_repo
+            .expect_set_wallet_tx_history()
+            .once()
+            .returning(move |username, network_id, history| {
+                assert_eq!(username, USERNAME);
+                assert_eq!(network_id, expected_network_id);
+                assert_eq!(history.last_scanned_block, None);
+                assert_eq!(history.transactions, vec![expected_transaction.clone()]);
+                Ok(())
+            });
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager.expect_try_get().returning(move |_, _, _, _, _| {
+            let mut mock_wallet_user = MockWalletUser::new();
+            mock_wallet_user
+                .expect_send_amount()
+                .once()
+                .returning(|_| Ok("tx_id".to_string()));
+            let value = wallet_transaction.clone();
+            mock_wallet_user
+                .expect_get_wallet_tx()
+                .once()
+                .returning(move |_| Ok(value.clone()));
+            Ok(WalletBorrow::from(mock_wallet_user))
+        });
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+        sdk.access_token = Some(TOKEN.clone());
+
+        let mock_tx_response = GetTransactionDetailsResponse {
+            system_address: "0xb0b".to_string(),
+            amount: dec!(5.0),
+            status: ApiTxStatus::Valid,
+            network: erc20_network,
+        };
+        let body = serde_json::to_string(&mock_tx_response).unwrap();
+
+        let mock_server_details = srv
+            .mock("GET", "/api/transactions/details?index=123")
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .with_status(200)
+            .with_body(&body)
+            .with_header("content-type", "application/json")
+            .create();
+
+        let mock_server_commit = srv
+            .mock("POST", "/api/transactions/commit")
+            .match_header(HEADER_X_APP_NAME, AUTH_PROVIDER)
+            .match_header("authorization", format!("Bearer {}", TOKEN.as_str()).as_str())
+            .with_status(202)
+            .expect(1)
+            .with_header("content-type", "application/json")
+            .create();
+
+        // Act
+        let pin = EncryptionPin::try_from_string("1234").unwrap();
+        let response = sdk.confirm_purchase_request(&pin, PURCHASE_ID).await;
+
+        // Assert
+        response.unwrap();
+        mock_server_details.assert();
+        mock_server_commit.assert();
+    }
+
     #[rstest]
     #[case::success(Ok(example_tx_details()))]
     #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
@@ -704,7 +1481,11 @@ mod tests {
 
         match &expected {
             Ok(_) => {
-                let mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 1, KycType::Undefined);
+                let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 2, KycType::Undefined);
+                mock_user_repo
+                    .expect_set_watched_transactions()
+                    .once()
+                    .returning(|_, _| Ok(()));
                 sdk.repo = Some(Box::new(mock_user_repo));
 
                 let mut mock_wallet_manager = MockWalletManager::new();
@@ -749,6 +1530,52 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_send_amount_from_passes_sender_to_wallet() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Iota)).await.unwrap();
+
+        let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 2, KycType::Undefined);
+        mock_user_repo
+            .expect_set_watched_transactions()
+            .once()
+            .returning(|_, _| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager.expect_try_get().returning(move |_, _, _, _, _| {
+            let mut mock_wallet = MockWalletUser::new();
+            mock_wallet
+                .expect_send_amount()
+                .times(1)
+                .withf(|intent| intent.address_from.as_deref() == Some("0xa11ce"))
+                .returning(move |_| Ok(String::from("transaction id")));
+            Ok(WalletBorrow::from(mock_wallet))
+        });
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        // Act
+        let amount = CryptoAmount::try_from(dec!(25.0)).unwrap();
+        let response = sdk
+            .send_amount_from(
+                &EncryptionPin::try_from_string("1234").unwrap(),
+                Some("0xa11ce"),
+                "0xb0b",
+                amount,
+                None,
+            )
+            .await;
+
+        // Assert
+        assert_eq!(response.unwrap(), "transaction id");
+    }
+
     #[tokio::test]
     async fn test_send_amount_with_eth_should_trigger_a_call_to_set_wallet_transaction() {
         // Arrange
@@ -761,6 +1588,7 @@ mod tests {
             date: String::new(),
             block_id: Some(String::new()),
             transaction_id: String::from("tx_id"),
+            log_index: None,
             receiver: String::new(),
             incoming: false,
             amount: 5.0,
@@ -771,7 +1599,16 @@ mod tests {
 
         let wallet_transactions = vec![wallet_transaction.clone()].to_owned();
 
-        let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Eth, false, 2, KycType::Undefined);
+        let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Eth, false, 4, KycType::Undefined);
+        mock_user_repo
+            .expect_set_watched_transactions()
+            .once()
+            .returning(|_, _| Ok(()));
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .times(1)
+            .withf(|_, _, pending| pending.len() == 1 && pending[0].transaction_hash == "tx_id")
+            .returning(|_, _, _| Ok(()));
         mock_user_repo
             .expect_set_wallet_transactions()
             .times(1)
@@ -785,9 +1622,18 @@ mod tests {
         mock_wallet_manager.expect_try_get().returning(move |_, _, _, _, _| {
             let mut mock_wallet = MockWalletUser::new();
             mock_wallet
-                .expect_send_amount()
+                .expect_get_address()
+                .times(1)
+                .returning(|| Ok(String::from(SENDER)));
+            mock_wallet
+                .expect_sign_transaction()
+                .times(1)
+                .withf(|_, min_nonce| min_nonce.is_none())
+                .returning(|_, _| Ok(pending_transaction("tx_id", 0)));
+            mock_wallet
+                .expect_broadcast_transaction()
                 .times(1)
-                .returning(move |_| Ok(String::from("tx_id")));
+                .returning(|_| Ok(()));
 
             let value = wallet_transaction.clone();
             mock_wallet
@@ -877,4 +1723,377 @@ mod tests {
             m.assert();
         }
     }
+
+    #[tokio::test]
+    async fn test_send_amount_with_eth_untracks_transaction_if_broadcast_fails() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        let mut seq = mockall::Sequence::new();
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(|_, _, pending| pending.iter().map(|tx| tx.nonce).eq([4, 5]))
+            .returning(|_, _, _| Ok(()));
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(|_, _, pending| pending.iter().map(|tx| tx.nonce).eq([4]))
+            .returning(|_, _, _| Ok(()));
+
+        let (mut sdk, _srv, _cleanup) =
+            sdk_with_pending_transactions(vec![pending_transaction("0x04", 4)], 2, mock_user_repo, || {
+                let mut mock_wallet = MockWalletUser::new();
+                mock_wallet.expect_get_confirmed_nonce().returning(|_| Ok(4));
+                mock_wallet.expect_get_address().returning(|| Ok(String::from(SENDER)));
+                mock_wallet
+                    .expect_sign_transaction()
+                    .once()
+                    .withf(|_, min_nonce| *min_nonce == Some(5))
+                    .returning(|_, _| Ok(pending_transaction("0x05", 5)));
+                mock_wallet
+                    .expect_broadcast_transaction()
+                    .once()
+                    .returning(|_| Err(WalletError::InvalidTransaction(String::from("underpriced"))));
+                mock_wallet
+            })
+            .await;
+
+        // Act
+        let amount = CryptoAmount::try_from(dec!(5.0)).unwrap();
+        let response = sdk.send_amount(&PIN, "0xb0b...", amount, None).await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(WalletError::InvalidTransaction(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_transaction_replaces_tracked_transaction() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .withf(|_, _, pending| {
+                *pending
+                    == [
+                        pending_transaction("0x04", 3),
+                        PendingTransaction {
+                            replaces: vec![String::from("0x05")],
+                            ..pending_transaction("0x44", 4)
+                        },
+                    ]
+            })
+            .returning(|_, _, _| Ok(()));
+        mock_user_repo
+            .expect_set_watched_transactions()
+            .once()
+            .withf(|_, watched| watched.len() == 1 && watched[0].transaction_hash == "0x44")
+            .returning(|_, _| Ok(()));
+
+        let pending = vec![pending_transaction("0x04", 3), pending_transaction("0x05", 4)];
+        let (mut sdk, _srv, _cleanup) = sdk_with_pending_transactions(pending, 3, mock_user_repo, || {
+            let mut mock_wallet = MockWalletUser::new();
+            mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(3));
+            mock_wallet
+                .expect_sign_replacement()
+                .once()
+                .withf(|pending, replacement| {
+                    pending.transaction_hash == "0x05" && *replacement == TransactionReplacement::SpeedUp
+                })
+                .returning(|_, _| Ok(pending_transaction("0x44", 4)));
+            mock_wallet.expect_broadcast_transaction().once().returning(|_| Ok(()));
+            mock_wallet
+        })
+        .await;
+
+        // Act
+        let response = sdk.speed_up_transaction(&PIN, "0x05").await;
+
+        // Assert
+        assert_eq!(response.unwrap(), "0x44");
+    }
+
+    #[tokio::test]
+    async fn test_cancel_transaction_fails_for_unknown_transaction() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_set_pending_transactions().never();
+
+        let (mut sdk, _srv, _cleanup) =
+            sdk_with_pending_transactions(vec![pending_transaction("0x04", 4)], 2, mock_user_repo, || {
+                let mut mock_wallet = MockWalletUser::new();
+                mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(4));
+                mock_wallet.expect_sign_replacement().never();
+                mock_wallet
+            })
+            .await;
+
+        // Act
+        let response = sdk.cancel_transaction(&PIN, "0x05").await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(WalletError::TransactionNotFound))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_speed_up_transaction_fails_for_included_transaction() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .withf(|_, _, pending| *pending == [pending_transaction("0x05", 5)])
+            .returning(|_, _, _| Ok(()));
+
+        let pending = vec![pending_transaction("0x04", 4), pending_transaction("0x05", 5)];
+        let (mut sdk, _srv, _cleanup) = sdk_with_pending_transactions(pending, 2, mock_user_repo, || {
+            let mut mock_wallet = MockWalletUser::new();
+            mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(5));
+            mock_wallet.expect_sign_replacement().never();
+            mock_wallet.expect_broadcast_transaction().never();
+            mock_wallet
+        })
+        .await;
+
+        // Act
+        let response = sdk.speed_up_transaction(&PIN, "0x04").await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(WalletError::TransactionNotFound))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_amount_with_eth_ignores_nonces_of_included_transactions() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        let mut seq = mockall::Sequence::new();
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(|_, _, pending| pending.is_empty())
+            .returning(|_, _, _| Ok(()));
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .in_sequence(&mut seq)
+            .withf(|_, _, pending| *pending == [pending_transaction("0x05", 5)])
+            .returning(|_, _, _| Ok(()));
+        mock_user_repo
+            .expect_set_watched_transactions()
+            .once()
+            .returning(|_, _| Ok(()));
+
+        let (mut sdk, _srv, _cleanup) =
+            sdk_with_pending_transactions(vec![pending_transaction("0x04", 4)], 3, mock_user_repo, || {
+                let mut mock_wallet = MockWalletUser::new();
+                mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(5));
+                mock_wallet.expect_get_address().returning(|| Ok(String::from(SENDER)));
+                mock_wallet
+                    .expect_sign_transaction()
+                    .once()
+                    .withf(|_, min_nonce| min_nonce.is_none())
+                    .returning(|_, _| Ok(pending_transaction("0x05", 5)));
+                mock_wallet.expect_broadcast_transaction().once().returning(|_| Ok(()));
+                mock_wallet
+                    .expect_get_wallet_tx()
+                    .once()
+                    .returning(|_| Err(WalletError::TransactionNotFound));
+                mock_wallet
+            })
+            .await;
+
+        // Act
+        let amount = CryptoAmount::try_from(dec!(5.0)).unwrap();
+        let response = sdk.send_amount(&PIN, "0xb0b...", amount, None).await;
+
+        // Assert
+        assert_eq!(response.unwrap(), "0x05");
+    }
+
+    #[tokio::test]
+    async fn test_rebroadcast_pending_transactions_skips_included_transactions() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .withf(|_, _, pending| *pending == [pending_transaction("0x05", 5)])
+            .returning(|_, _, _| Ok(()));
+
+        let pending = vec![pending_transaction("0x04", 4), pending_transaction("0x05", 5)];
+        let (mut sdk, _srv, _cleanup) = sdk_with_pending_transactions(pending, 2, mock_user_repo, || {
+            let mut mock_wallet = MockWalletUser::new();
+            mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(5));
+            mock_wallet
+                .expect_broadcast_transaction()
+                .once()
+                .withf(|tx| tx.transaction_hash == "0x05")
+                .returning(|_| Ok(()));
+            mock_wallet
+        })
+        .await;
+
+        // Act
+        let response = sdk.rebroadcast_pending_transactions(&PIN).await;
+
+        // Assert
+        assert_eq!(response.unwrap(), vec![String::from("0x05")]);
+    }
+
+    #[tokio::test]
+    async fn test_prepare_unsigned_transaction_skips_tracked_nonces() {
+        // Arrange
+        let unsigned = UnsignedTransaction {
+            from: SENDER.to_string(),
+            raw_transaction: vec![0x02, 0xc0],
+        };
+        let expected = unsigned.clone();
+        let (mut sdk, _srv, _cleanup) = sdk_with_pending_transactions(
+            vec![pending_transaction("0x04", 4)],
+            1,
+            MockUserRepo::new(),
+            move || {
+                let unsigned = unsigned.clone();
+                let mut mock_wallet = MockWalletUser::new();
+                mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(4));
+                mock_wallet.expect_get_address().returning(|| Ok(String::from(SENDER)));
+                mock_wallet
+                    .expect_prepare_unsigned_transaction()
+                    .once()
+                    .withf(|intent, min_nonce| intent.address_to == "0xb0b..." && *min_nonce == Some(5))
+                    .returning(move |_, _| Ok(unsigned.clone()));
+                mock_wallet
+            },
+        )
+        .await;
+
+        // Act
+        let amount = CryptoAmount::try_from(dec!(5.0)).unwrap();
+        let response = sdk
+            .prepare_unsigned_transaction(None, None, "0xb0b...", amount, None)
+            .await;
+
+        // Assert
+        assert_eq!(response.unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_review_unsigned_transaction(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Iota)).await.unwrap();
+
+        let unsigned = UnsignedTransaction {
+            from: SENDER.to_string(),
+            raw_transaction: vec![0x02, 0xc0],
+        };
+        let details = UnsignedTransactionDetails {
+            from: SENDER.to_string(),
+            to: String::from("0xb0b..."),
+            value: dec!(5.0),
+            data: Vec::new(),
+            chain_id: 1,
+            nonce: 4,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            max_fee: dec!(0.000042),
+        };
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+
+                let details = details.clone();
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager.expect_try_get().returning(move |_, _, _, _, _| {
+                    let details = details.clone();
+                    let mut mock_wallet = MockWalletUser::new();
+                    mock_wallet
+                        .expect_review_unsigned_transaction()
+                        .once()
+                        .withf(|unsigned| unsigned.raw_transaction == [0x02, 0xc0])
+                        .returning(move |_| Ok(details.clone()));
+                    Ok(WalletBorrow::from(mock_wallet))
+                });
+
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.review_unsigned_transaction(&PIN, &unsigned).await;
+
+        // Assert
+        match expected {
+            Ok(()) => assert_eq!(response.unwrap(), details),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed_transaction_tracks_and_watches_transaction() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .withf(|_, _, pending| *pending == [pending_transaction("0x04", 4), pending_transaction("0x05", 5)])
+            .returning(|_, _, _| Ok(()));
+        mock_user_repo
+            .expect_set_watched_transactions()
+            .once()
+            .withf(|_, watched| watched.len() == 1 && watched[0].transaction_hash == "0x05")
+            .returning(|_, _| Ok(()));
+
+        let (mut sdk, _srv, _cleanup) =
+            sdk_with_pending_transactions(vec![pending_transaction("0x04", 4)], 2, mock_user_repo, || {
+                let mut mock_wallet = MockWalletUser::new();
+                mock_wallet
+                    .expect_broadcast_signed_transaction()
+                    .once()
+                    .withf(|raw_transaction| raw_transaction.to_vec() == vec![0x02, 0xab])
+                    .returning(|_| Ok(pending_transaction("0x05", 5)));
+                mock_wallet
+            })
+            .await;
+
+        // Act
+        let response = sdk.broadcast_signed_transaction(None, &[0x02, 0xab]).await;
+
+        // Assert
+        assert_eq!(response.unwrap(), "0x05");
+    }
 }
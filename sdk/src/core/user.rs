@@ -8,7 +8,10 @@ use crate::error::Result;
 use crate::types::newtypes::AccessToken;
 use crate::types::newtypes::EncryptionPin;
 use crate::types::newtypes::EncryptionSalt;
-use crate::types::users::{ActiveUser, KycType, UserEntity};
+use crate::types::newtypes::PlainPassword;
+use crate::types::users::{ActiveUser, KycType, UserEntity, UserSummary};
+use crate::user::error::UserKvStorageError;
+use crate::wallet_manager::WalletManagerImpl;
 use log::{debug, info, warn};
 
 impl Sdk {
@@ -66,6 +69,18 @@ impl Sdk {
             viviswap_state: Option::None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: Some(chrono::Utc::now().timestamp()),
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         };
 
         repo.create(&user)?;
@@ -94,7 +109,7 @@ impl Sdk {
         // make sure the pin is correct before continuing, only if the wallet exists
         if user_entity.encrypted_password.is_some() {
             let pin = pin.ok_or(crate::Error::Wallet(crate::WalletError::WrongPinOrPassword))?;
-            self.verify_pin(pin).await?;
+            self.check_pin(pin).await?;
             info!("Pin verified");
         }
 
@@ -145,18 +160,49 @@ impl Sdk {
     /// # Errors
     ///
     /// Returns an `Error` if there is an issue validating the configuration, initializing the repository, or checking the KYC status.
+    ///
+    /// If the user storage is encrypted with the pin (see [`Sdk::set_user_storage_encryption`]), the user is
+    /// locked until the pin is entered, e.g. with [`Sdk::verify_pin`]. The KYC state is not updated then.
     pub async fn init_user(&mut self, username: &str) -> Result<()> {
         info!("Initializing user {username}");
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
         };
-        let user = repo.get(username)?;
-        let active_user = ActiveUser::from(user);
+
+        // forget the keys of the previous session, an encrypted user is unlocked again with the pin
+        if let Some(cipher) = &self.user_storage_cipher {
+            cipher.lock();
+        }
+
+        let locked = match repo.get(username) {
+            Ok(_) => false,
+            Err(UserKvStorageError::Locked { .. }) => {
+                info!("The stored user is locked until the pin is entered");
+                true
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let active_user = ActiveUser {
+            username: username.to_string(),
+            wallet_manager: Box::new(
+                WalletManagerImpl::new(username).with_token_metadata_cache(self.token_metadata_cache.clone()),
+            ),
+        };
 
         if let Some(access_token) = &self.access_token {
-            let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
-            let status = check_kyc_status(config, access_token, username).await?;
-            repo.set_kyc_state(username, status.is_verified)?;
+            if locked {
+                warn!("Not updating the KYC state of the locked user");
+            } else {
+                let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+                let status = check_kyc_status(config, access_token, username).await?;
+                repo.set_kyc_state(username, status.is_verified)?;
+            }
+        }
+
+        if !locked {
+            if let Err(e) = repo.set_last_used_at(username, chrono::Utc::now().timestamp()) {
+                warn!("Could not store the time the user was last used: {e}");
+            }
         }
 
         self.active_user = Some(active_user);
@@ -164,6 +210,152 @@ impl Sdk {
         Ok(())
     }
 
+    /// List the users stored on this device
+    ///
+    /// # Returns
+    ///
+    /// Returns the summaries of all stored users sorted by username, e.g. to show an account switcher. Users
+    /// that are encrypted with the pin and locked are listed with their username only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if the repository is not initialized or the users could not be loaded.
+    pub async fn list_users(&self) -> Result<Vec<UserSummary>> {
+        debug!("Listing the users");
+        let Some(repo) = &self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+
+        Ok(repo.list()?)
+    }
+
+    /// Log out the active user
+    ///
+    /// Drops the active user together with the wallet state cached for it, e.g. the recovery share or a started
+    /// guardian recovery, forgets the access token and locks the users encrypted with the pin. The stored user
+    /// is kept. Does nothing if no user is initialized. In the browser, waits until the changes of the users are
+    /// written to the IndexedDB, see [`Sdk::flush_user_storage`].
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` after the user is logged out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if a pending write to the IndexedDB failed. The user is logged out nevertheless.
+    pub async fn logout(&mut self) -> Result<()> {
+        if let Some(active_user) = self.active_user.take() {
+            info!("Logging out user {}", active_user.username);
+        }
+        self.access_token = None;
+
+        if let Some(cipher) = &self.user_storage_cipher {
+            cipher.lock();
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        self.flush_user_storage().await?;
+
+        Ok(())
+    }
+
+    /// Switch to another user stored on this device
+    ///
+    /// Logs out the active user with [`Sdk::logout`] and initializes the other user with [`Sdk::init_user`].
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user to switch to.
+    /// * `access_token` - The access token of the user, or `None` to set it later with
+    ///   [`Sdk::refresh_access_token`]. The KYC state is only updated with an access token.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the user is initialized successfully, or an `Error` if an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if there is an issue initializing the user, e.g. if it does not exist. No user is
+    /// active then.
+    pub async fn switch_user(&mut self, username: &str, access_token: Option<AccessToken>) -> Result<()> {
+        info!("Switching to user {username}");
+        self.logout().await?;
+        self.access_token = access_token;
+        self.init_user(username).await
+    }
+
+    /// Export the active user
+    ///
+    /// Exports the locally stored record of the active user, e.g. to move it to another device with
+    /// [`Sdk::import_user`]. The record is encrypted with a key derived from the export password and can only be
+    /// imported with the same password and the username of the user.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user, required if the user has a wallet.
+    /// * `export_password` - The password to encrypt the export with.
+    ///
+    /// # Returns
+    ///
+    /// Returns the encrypted export.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if no user is initialized, the user is locked until the pin is entered, or the PIN
+    /// is wrong.
+    pub async fn export_user(
+        &mut self,
+        pin: Option<&EncryptionPin>,
+        export_password: &PlainPassword,
+    ) -> Result<Vec<u8>> {
+        let user_entity = self.get_user().await?;
+
+        // make sure the pin is correct before continuing, only if the wallet exists
+        if user_entity.encrypted_password.is_some() {
+            let pin = pin.ok_or(crate::Error::Wallet(crate::WalletError::WrongPinOrPassword))?;
+            self.check_pin(pin).await?;
+            info!("Pin verified");
+        }
+
+        info!("Exporting user {}", user_entity.username);
+
+        // load the user again, since verifying the pin resets the failed pin attempts
+        let user_entity = self.get_user().await?;
+        Ok(crate::user::record::export(&user_entity, export_password)?)
+    }
+
+    /// Import a user
+    ///
+    /// Stores a user exported with [`Sdk::export_user`] on this device. Initialize it with [`Sdk::init_user`]
+    /// afterwards. The failed pin attempts of the exported user are not imported.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the exported user. The export only decrypts for the user it was created
+    ///   for.
+    /// * `export` - The encrypted export of the user.
+    /// * `export_password` - The password the user was exported with.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the user is imported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error` if the repository is not initialized, the export password or the username is wrong,
+    /// the export is invalid or a user with the same username already exists.
+    pub async fn import_user(&mut self, username: &str, export: &[u8], export_password: &PlainPassword) -> Result<()> {
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+
+        let user = crate::user::record::import(username, export, export_password)?;
+        info!("Importing user {username}");
+        repo.create(&user)?;
+
+        Ok(())
+    }
+
     /// Refresh access token
     ///
     /// # Arguments
@@ -252,6 +444,7 @@ mod tests {
     use super::*;
     use crate::core::core_testing_utils::handle_error_test_cases;
     use crate::testing_utils::{example_get_user, set_config, AUTH_PROVIDER, HEADER_X_APP_NAME, TOKEN, USERNAME};
+    use crate::user::{memory_storage::MemoryUserStorage, repository::UserRepoImpl};
     use crate::{core::Sdk, user::MockUserRepo, wallet_manager::MockWalletManager};
     use api_types::api::kyc::KycStatusResponse;
     use api_types::api::viviswap::detail::SwapPaymentDetailKey;
@@ -420,6 +613,10 @@ mod tests {
             Ok(_) => {
                 let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 1, KycType::Undefined);
                 mock_user_repo.expect_set_kyc_state().times(1).returning(|_, _| Ok(()));
+                mock_user_repo
+                    .expect_set_last_used_at()
+                    .times(1)
+                    .returning(|_, _| Ok(()));
                 sdk.repo = Some(Box::new(mock_user_repo));
 
                 sdk.active_user = Some(crate::types::users::ActiveUser {
@@ -464,6 +661,168 @@ mod tests {
         }
     }
 
+    /// An SDK with an in-memory user repository and the users `alice` and `bob` without wallets
+    async fn sdk_with_users() -> (Sdk, testing::CleanUp) {
+        let (_srv, config, cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.repo = Some(Box::new(UserRepoImpl::new(MemoryUserStorage::new())));
+        sdk.create_new_user("bob").await.unwrap();
+        sdk.create_new_user("alice").await.unwrap();
+        (sdk, cleanup)
+    }
+
+    #[tokio::test]
+    async fn test_list_users() {
+        // Arrange
+        let (mut sdk, _cleanup) = sdk_with_users().await;
+        sdk.init_user("bob").await.unwrap();
+
+        // Act
+        let users = sdk.list_users().await.unwrap();
+
+        // Assert
+        let usernames: Vec<_> = users.iter().map(|user| user.username.as_str()).collect();
+        assert_eq!(usernames, ["alice", "bob"]);
+        assert!(users
+            .iter()
+            .all(|user| user.created_at.is_some() && !user.has_wallet && !user.locked));
+        assert_eq!(users[0].last_used_at, None);
+        assert!(users[1].last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_list_users_repo_not_initialized() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.repo = None;
+
+        // Act
+        let response = sdk.list_users().await;
+
+        // Assert
+        assert!(matches!(response, Err(crate::Error::UserRepoNotInitialized)));
+    }
+
+    #[tokio::test]
+    async fn test_logout_drops_the_active_user_and_access_token() {
+        // Arrange
+        let (mut sdk, _cleanup) = sdk_with_users().await;
+        sdk.init_user("alice").await.unwrap();
+        sdk.access_token = Some(TOKEN.clone());
+
+        // Act
+        sdk.logout().await.unwrap();
+
+        // Assert
+        assert!(sdk.active_user.is_none());
+        assert!(sdk.access_token.is_none());
+        assert!(matches!(sdk.get_user().await, Err(crate::Error::UserNotInitialized)));
+        assert_eq!(sdk.list_users().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_switch_user() {
+        // Arrange
+        let (mut sdk, _cleanup) = sdk_with_users().await;
+        sdk.init_user("alice").await.unwrap();
+
+        // Act
+        sdk.switch_user("bob", None).await.unwrap();
+
+        // Assert
+        assert_eq!(sdk.get_user().await.unwrap().username, "bob");
+    }
+
+    #[tokio::test]
+    async fn test_switch_to_unknown_user_logs_out() {
+        // Arrange
+        let (mut sdk, _cleanup) = sdk_with_users().await;
+        sdk.init_user("alice").await.unwrap();
+
+        // Act
+        let response = sdk.switch_user("carol", None).await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::UserRepository(UserKvStorageError::UserNotFound { .. }))
+        ));
+        assert!(sdk.active_user.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_export_and_import_user() {
+        // Arrange
+        let (mut sdk, _cleanup) = sdk_with_users().await;
+        sdk.init_user("alice").await.unwrap();
+        let alice = sdk.get_user().await.unwrap();
+
+        let (_srv, config, _cleanup_other) = set_config().await;
+        let mut other_sdk = Sdk::new(config).unwrap();
+        other_sdk.repo = Some(Box::new(UserRepoImpl::new(MemoryUserStorage::new())));
+
+        let export_password = PlainPassword::try_from_string("export password").unwrap();
+
+        // Act
+        let export = sdk.export_user(None, &export_password).await.unwrap();
+        other_sdk.import_user("alice", &export, &export_password).await.unwrap();
+
+        // Assert
+        other_sdk.init_user("alice").await.unwrap();
+        let imported = other_sdk.get_user().await.unwrap();
+        assert_eq!(imported.salt, alice.salt);
+        assert_eq!(imported.created_at, alice.created_at);
+    }
+
+    #[tokio::test]
+    async fn test_import_existing_user_fails() {
+        // Arrange
+        let (mut sdk, _cleanup) = sdk_with_users().await;
+        sdk.init_user("alice").await.unwrap();
+        let export_password = PlainPassword::try_from_string("export password").unwrap();
+        let export = sdk.export_user(None, &export_password).await.unwrap();
+
+        // Act
+        let response = sdk.import_user("alice", &export, &export_password).await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::UserRepository(
+                UserKvStorageError::UserAlreadyExists { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_export_user_with_wallet_requires_pin() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.repo = Some(Box::new(example_get_user(
+            SwapPaymentDetailKey::Iota,
+            false,
+            1,
+            KycType::Undefined,
+        )));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk
+            .export_user(None, &PlainPassword::try_from_string("export password").unwrap())
+            .await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(crate::WalletError::WrongPinOrPassword))
+        ));
+    }
+
     #[rstest]
     #[case::success(Ok(true))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
@@ -178,7 +178,7 @@ impl Sdk {
     ) -> Result<ViviswapAddressDetail> {
         info!("Updating user IBAN");
         // verify pin
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
 
         // ensure that the repository exist (cannot borrow as mutable here since we also borrow self as mutable in between)
         if self.repo.is_none() {
@@ -432,7 +432,7 @@ impl Sdk {
         info!("Creating withdrawal with viviswap");
         // load user entity
         if let Some(pin) = pin {
-            self.verify_pin(pin).await?;
+            self.check_pin(pin).await?;
         }
         let user = self.get_user().await?;
 
@@ -3,17 +3,28 @@
 //!
 //! It also includes various helper functions and imports required for the wallet functionality.
 
-use super::Sdk;
+use super::{Sdk, UserRepoT};
 use crate::{
-    backend::dlt::put_user_address,
+    backend::{dlt::put_user_address, shares::download_backup_share},
+    backup::WalletMetadata,
     error::Result,
+    mnemonic::MnemonicOptions,
+    share::{
+        encrypt_passphrase, is_secret_of_share, reencrypt_local_share, reencrypt_passphrase, reencrypt_secret_key,
+        Share,
+    },
     types::{
         currencies::CryptoAmount,
-        newtypes::{EncryptionPin, EncryptionSalt, PlainPassword},
-        transactions::{WalletTxInfo, WalletTxInfoList},
+        networks::NetworkType,
+        newtypes::{EncryptedPassword, EncryptionPin, EncryptionSalt, MnemonicPassphrase, PlainPassword},
+        transactions::{WalletTxHistory, WalletTxInfo, WalletTxInfoList},
+        users::{KycType, PinAttempts, UserEntity},
     },
+    user::{encryption::UserStorageCipher, error::UserKvStorageError},
     wallet::error::{ErrorKind, WalletError},
+    wallet_user::WalletUser,
 };
+use iota_sdk::wallet::account::types::InclusionState;
 use log::{debug, info, warn};
 
 impl Sdk {
@@ -22,6 +33,8 @@ impl Sdk {
     /// # Arguments
     ///
     /// * `pin` - The PIN for the wallet.
+    /// * `options` - The number of words and the wordlist language of the new mnemonic. Use
+    ///   [`MnemonicOptions::default`] for 24 English words.
     ///
     /// # Returns
     ///
@@ -32,8 +45,53 @@ impl Sdk {
     /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
     /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
     /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
-    pub async fn create_wallet_from_new_mnemonic(&mut self, pin: &EncryptionPin) -> Result<String> {
+    pub async fn create_wallet_from_new_mnemonic(
+        &mut self,
+        pin: &EncryptionPin,
+        options: &MnemonicOptions,
+    ) -> Result<String> {
+        self.create_wallet_with_new_mnemonic(pin, None, options).await
+    }
+
+    /// Create and store a wallet from a new random mnemonic and a BIP-39 passphrase (25th word)
+    ///
+    /// The passphrase is not part of the wallet shares, it is stored encrypted with the pin on this device. On
+    /// other devices it has to be set with [`Sdk::set_wallet_passphrase`] before the wallet can be used.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `passphrase` - The BIP-39 passphrase of the wallet.
+    /// * `options` - The number of words and the wordlist language of the new mnemonic.
+    ///
+    /// # Returns
+    ///
+    /// The new random mnemonic.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    pub async fn create_wallet_from_new_mnemonic_with_passphrase(
+        &mut self,
+        pin: &EncryptionPin,
+        passphrase: &MnemonicPassphrase,
+        options: &MnemonicOptions,
+    ) -> Result<String> {
+        self.create_wallet_with_new_mnemonic(pin, Some(passphrase), options)
+            .await
+    }
+
+    /// Create and store a wallet from a new random mnemonic and the optional BIP-39 passphrase.
+    async fn create_wallet_with_new_mnemonic(
+        &mut self,
+        pin: &EncryptionPin,
+        passphrase: Option<&MnemonicPassphrase>,
+        options: &MnemonicOptions,
+    ) -> Result<String> {
         info!("Creating a new wallet from random mnemonic");
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -47,7 +105,7 @@ impl Sdk {
 
         let mnemonic = active_user
             .wallet_manager
-            .create_wallet_from_new_mnemonic(config, &self.access_token, repo, pin)
+            .create_wallet_from_new_mnemonic(config, &self.access_token, repo, pin, &passphrase.cloned(), options)
             .await?;
         Ok(mnemonic)
     }
@@ -64,8 +122,50 @@ impl Sdk {
     /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
     /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
     /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::Mnemonic`] - If the mnemonic has a wrong number of words, an unknown word or a wrong
+    ///   checksum. The language of the wordlist is detected automatically.
     pub async fn create_wallet_from_existing_mnemonic(&mut self, pin: &EncryptionPin, mnemonic: &str) -> Result<()> {
+        self.create_wallet_with_existing_mnemonic(pin, mnemonic, None).await
+    }
+
+    /// Create and store a wallet from an existing mnemonic and BIP-39 passphrase (25th word), for example
+    /// to migrate a wallet which uses a passphrase in another wallet application.
+    ///
+    /// The passphrase is not part of the wallet shares, it is stored encrypted with the pin on this device. On
+    /// other devices it has to be set with [`Sdk::set_wallet_passphrase`] before the wallet can be used.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `mnemonic` - The mnemonic to use for the wallet.
+    /// * `passphrase` - The BIP-39 passphrase used with the mnemonic.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::Mnemonic`] - If the mnemonic has a wrong number of words, an unknown word or a wrong
+    ///   checksum.
+    pub async fn create_wallet_from_existing_mnemonic_with_passphrase(
+        &mut self,
+        pin: &EncryptionPin,
+        mnemonic: &str,
+        passphrase: &MnemonicPassphrase,
+    ) -> Result<()> {
+        self.create_wallet_with_existing_mnemonic(pin, mnemonic, Some(passphrase))
+            .await
+    }
+
+    /// Create and store a wallet from an existing mnemonic and the optional BIP-39 passphrase.
+    async fn create_wallet_with_existing_mnemonic(
+        &mut self,
+        pin: &EncryptionPin,
+        mnemonic: &str,
+        passphrase: Option<&MnemonicPassphrase>,
+    ) -> Result<()> {
         info!("Creating a new wallet from existing mnemonic");
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -79,12 +179,17 @@ impl Sdk {
 
         active_user
             .wallet_manager
-            .create_wallet_from_existing_mnemonic(config, &self.access_token, repo, pin, mnemonic)
+            .create_wallet_from_existing_mnemonic(config, &self.access_token, repo, pin, mnemonic, &passphrase.cloned())
             .await?;
         Ok(())
     }
 
-    /// Create and store a wallet from an existing kdbx backup file
+    /// Create and store a wallet from an existing kdbx or JSON backup file
+    ///
+    /// The format of the backup is detected automatically. The metadata of a JSON backup is restored:
+    /// the derived address indices are stored and the preferred network is set if the user is logged in.
+    /// The backup only contains the ids of the networks, which are resolved against the networks of the
+    /// backend; the preferred network is selected if no network is selected yet.
     ///
     /// # Arguments
     ///
@@ -104,6 +209,7 @@ impl Sdk {
         backup_password: &PlainPassword,
     ) -> Result<()> {
         info!("Creating a new wallet from backup");
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -115,10 +221,50 @@ impl Sdk {
 
         let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
 
-        active_user
+        let metadata = active_user
             .wallet_manager
             .create_wallet_from_backup(config, &self.access_token, repo, pin, backup, backup_password)
             .await?;
+        let Some(metadata) = metadata else {
+            return Ok(());
+        };
+
+        info!("Restoring wallet metadata from backup");
+        let mut user = repo.get(&active_user.username)?;
+        user.evm_address_indices.extend(metadata.evm_address_indices);
+        user.evm_address_indices.sort_unstable();
+        user.evm_address_indices.dedup();
+        repo.update(&user)?;
+
+        if let (Some(access_token), Some(network_id)) = (&self.access_token, &metadata.preferred_network) {
+            if let Err(e) =
+                crate::backend::user::set_preferred_network(config, access_token, Some(network_id.clone())).await
+            {
+                warn!("Could not restore the preferred network: {e}");
+            }
+        }
+
+        // the backup only contains the ids of the networks, resolve them against the available networks
+        let networks = match self.get_networks().await {
+            Ok(networks) => networks,
+            Err(e) => {
+                warn!("Could not resolve the networks of the backup: {e}");
+                return Ok(());
+            }
+        };
+        for network_id in &metadata.network_ids {
+            if !networks.iter().any(|network| &network.id == network_id) {
+                warn!("Network {network_id} of the backup is not available anymore");
+            }
+        }
+        if self.network.is_none() {
+            if let Some(network) = metadata
+                .preferred_network
+                .and_then(|network_id| networks.into_iter().find(|network| network.id == network_id))
+            {
+                self.network = Some(network);
+            }
+        }
         Ok(())
     }
 
@@ -144,6 +290,7 @@ impl Sdk {
         backup_password: &PlainPassword,
     ) -> Result<Vec<u8>> {
         info!("Creating wallet backup");
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -162,6 +309,68 @@ impl Sdk {
         Ok(backup)
     }
 
+    /// Create a JSON wallet backup from an existing wallet.
+    ///
+    /// In addition to the mnemonic, the backup contains the ids of the networks, the preferred network, the
+    /// derived address indices and the labels of the additional shares. The backup is encrypted with
+    /// AES-256-GCM using a key derived from the backup password with Argon2id.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `backup_password` - The password to use when creating the backup file.
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the JSON backup file.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    pub async fn create_json_wallet_backup(
+        &mut self,
+        pin: &EncryptionPin,
+        backup_password: &PlainPassword,
+    ) -> Result<Vec<u8>> {
+        info!("Creating JSON wallet backup");
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        let preferred_network = match &self.access_token {
+            Some(access_token) => crate::backend::user::get_preferred_network(config, access_token)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Could not get the preferred network for the backup: {e}");
+                    None
+                }),
+            None => None,
+        };
+        // the share labels are taken from the shares by the wallet manager
+        let metadata = WalletMetadata {
+            network_ids: self.networks.iter().map(|network| network.id.clone()).collect(),
+            preferred_network,
+            evm_address_indices: repo.get(&active_user.username)?.evm_address_indices,
+            share_labels: Vec::new(),
+        };
+
+        let backup = active_user
+            .wallet_manager
+            .create_json_wallet_backup(config, &self.access_token, repo, pin, backup_password, metadata)
+            .await?;
+        Ok(backup)
+    }
+
     /// Verify the mnemonic by checking if the mnemonic is the same as the one in the shares
     ///
     /// # Arguments
@@ -172,7 +381,8 @@ impl Sdk {
     /// # Returns
     ///
     /// Returns `Ok(true)` if the mnemonic is successfully verified, otherwise returns `Ok(false)`,
-    /// or an `Error`.
+    /// or an `Error`. Returns `Ok(false)` for wallets with a BIP-39 passphrase, use
+    /// [`Sdk::verify_mnemonic_with_passphrase`] for them.
     ///
     /// # Errors
     ///
@@ -180,7 +390,47 @@ impl Sdk {
     /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
     /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
     pub async fn verify_mnemonic(&mut self, pin: &EncryptionPin, mnemonic: &str) -> Result<bool> {
+        self.verify_mnemonic_and_passphrase(pin, mnemonic, None).await
+    }
+
+    /// Verify the mnemonic and the BIP-39 passphrase by checking if the mnemonic is the same as the one in the
+    /// shares and the passphrase is the one stored on this device
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet.
+    /// * `mnemonic` - The mnemonic to verify.
+    /// * `passphrase` - The BIP-39 passphrase to verify.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(true)` if the mnemonic and passphrase are successfully verified, otherwise returns
+    /// `Ok(false)`, or an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    pub async fn verify_mnemonic_with_passphrase(
+        &mut self,
+        pin: &EncryptionPin,
+        mnemonic: &str,
+        passphrase: &MnemonicPassphrase,
+    ) -> Result<bool> {
+        self.verify_mnemonic_and_passphrase(pin, mnemonic, Some(passphrase))
+            .await
+    }
+
+    /// Verify the mnemonic and the optional BIP-39 passphrase of the wallet.
+    async fn verify_mnemonic_and_passphrase(
+        &mut self,
+        pin: &EncryptionPin,
+        mnemonic: &str,
+        passphrase: Option<&MnemonicPassphrase>,
+    ) -> Result<bool> {
         info!("Verifying mnemonic");
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -194,7 +444,7 @@ impl Sdk {
 
         let is_verified = active_user
             .wallet_manager
-            .check_mnemonic(config, &self.access_token, repo, pin, mnemonic)
+            .check_mnemonic(config, &self.access_token, repo, pin, mnemonic, &passphrase.cloned())
             .await?;
         Ok(is_verified)
     }
@@ -215,7 +465,7 @@ impl Sdk {
     pub async fn delete_wallet(&mut self, pin: &EncryptionPin) -> Result<()> {
         warn!("Deleting wallet for user. Potential loss of funds if mnemonic/wallet is not backed up!");
 
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
 
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
@@ -236,7 +486,8 @@ impl Sdk {
 
     /// Verify pin
     ///
-    /// Verifies the pin for the wallet.
+    /// Verifies the pin for the wallet. Wrong attempts are counted. A password stored by older versions with
+    /// the legacy key derivation is migrated to the current format once the pin is verified.
     ///
     /// # Arguments
     ///
@@ -250,35 +501,59 @@ impl Sdk {
     ///
     /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
     /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
     /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
     /// * [`WalletError::WrongPinOrPassword`] - If the pin or password is incorrect.
-    pub async fn verify_pin(&self, pin: &EncryptionPin) -> Result<()> {
+    /// * [`crate::Error::PinAttemptsBackoff`] - If the pin was wrong too many times and the user has to wait.
+    /// * [`crate::Error::PinAttemptsLockedOut`] - If the maximum number of wrong pin attempts is reached.
+    pub async fn verify_pin(&mut self, pin: &EncryptionPin) -> Result<()> {
         info!("Verifying wallet pin");
-        let Some(repo) = &self.repo else {
+        self.check_pin(pin).await
+    }
+
+    /// Verifies the pin for [`Sdk::verify_pin`] and the operations that need it, and upgrades what was stored
+    /// by older versions with the verified pin: a password encrypted with the legacy key derivation is
+    /// re-encrypted with the current one, and the user is stored encrypted with the key of the pin.
+    pub(crate) async fn check_pin(&mut self, pin: &EncryptionPin) -> Result<()> {
+        let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
         };
         let Some(active_user) = &self.active_user else {
             return Err(crate::Error::UserNotInitialized);
         };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
 
         let username = &active_user.username;
-        let user = repo.get(username)?;
-
-        // Ensure encrypted password exists in user
-        let Some(encrypted_password) = user.encrypted_password else {
-            return Err(WalletError::WalletNotInitialized(ErrorKind::MissingPassword))?;
-        };
-
-        // Decrypt the password using the provided PIN
-        if encrypted_password.decrypt(pin, &user.salt).is_err() {
-            return Err(WalletError::WrongPinOrPassword)?;
+        let verified = get_user_and_decrypt_password(
+            repo,
+            self.user_storage_cipher.as_deref(),
+            username,
+            pin,
+            config.max_pin_attempts,
+        )?;
+
+        if verified
+            .user
+            .encrypted_password
+            .as_ref()
+            .is_some_and(EncryptedPassword::is_legacy)
+        {
+            info!("Migrating encrypted password to the current key derivation");
+            let encrypted_password = verified.password.encrypt(pin, &verified.user.salt)?;
+            repo.set_wallet_password(username, encrypted_password)?;
+        } else if verified.unlocked {
+            info!("Storing the user encrypted with the key of the pin");
+            let user = repo.get(username)?;
+            repo.update(&user)?;
         }
+
         Ok(())
     }
 
     /// Reset pin
     ///
-    /// Resets the pin for the wallet using the provided password and new pin.
+    /// Resets the pin for the wallet using the provided password and new pin. The password and the
+    /// local share are re-encrypted with the new pin.
     ///
     /// # Arguments
     ///
@@ -294,7 +569,11 @@ impl Sdk {
     /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
     /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
     /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
     /// * [`WalletError::WrongPinOrPassword`] - If the pin or password is incorrect.
+    /// * [`crate::Error::PinAttemptsBackoff`] - If the pin was wrong too many times and the user has to wait.
+    /// * [`crate::Error::PinAttemptsLockedOut`] - If the maximum number of wrong pin attempts is reached.
     pub async fn change_pin(&mut self, old_pin: &EncryptionPin, new_pin: &EncryptionPin) -> Result<()> {
         info!("Resetting pin with password");
         let Some(repo) = &mut self.repo else {
@@ -303,24 +582,65 @@ impl Sdk {
         let Some(active_user) = &mut self.active_user else {
             return Err(crate::Error::UserNotInitialized);
         };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
 
         let username = &active_user.username;
-        let mut user = repo.get(username)?;
-
-        let Some(encrypted_password) = user.encrypted_password else {
-            return Err(WalletError::WalletNotInitialized(ErrorKind::MissingPassword))?;
-        };
+        let cipher = self.user_storage_cipher.as_deref();
 
         // decrypt the password
-        let password = encrypted_password.decrypt(old_pin, &user.salt)?;
+        let VerifiedUser { mut user, password, .. } =
+            get_user_and_decrypt_password(repo, cipher, username, old_pin, config.max_pin_attempts)?;
 
         // Set new pin and encrypted password
         let salt = EncryptionSalt::generate();
         let encrypted_password = password.encrypt(new_pin, &salt)?;
 
+        // Re-encrypt the local shares and secret keys with the new pin
+        let local_share = user
+            .local_share
+            .as_deref()
+            .map(|share| reencrypt_local_share(share, (old_pin, &user.salt), (new_pin, &salt)))
+            .transpose()
+            .map_err(WalletError::Share)?;
+        let guardian_secret_key = user
+            .guardian_secret_key
+            .as_deref()
+            .map(|key| reencrypt_secret_key(key, (old_pin, &user.salt), (new_pin, &salt)))
+            .transpose()
+            .map_err(WalletError::Share)?;
+        let guardian_recovery_key = user
+            .guardian_recovery_key
+            .as_deref()
+            .map(|key| reencrypt_secret_key(key, (old_pin, &user.salt), (new_pin, &salt)))
+            .transpose()
+            .map_err(WalletError::Share)?;
+        let encrypted_passphrase = user
+            .encrypted_passphrase
+            .as_deref()
+            .map(|passphrase| reencrypt_passphrase(passphrase, (old_pin, &user.salt), (new_pin, &salt)))
+            .transpose()
+            .map_err(WalletError::Share)?;
+        let offline_recovery_share = user
+            .offline_recovery_share
+            .as_deref()
+            .map(|share| reencrypt_local_share(share, (old_pin, &user.salt), (new_pin, &salt)))
+            .transpose()
+            .map_err(WalletError::Share)?;
+
         // Update user
         user.salt = salt;
         user.encrypted_password = Some(encrypted_password);
+        user.local_share = local_share;
+        user.guardian_secret_key = guardian_secret_key;
+        user.guardian_recovery_key = guardian_recovery_key;
+        user.encrypted_passphrase = encrypted_passphrase;
+        user.offline_recovery_share = offline_recovery_share;
+        user.pin_attempts = PinAttempts::default();
+
+        // Rotate the key of the user storage, so the user is stored with the key of the new pin
+        if let Some(cipher) = cipher {
+            cipher.rotate_key(username, new_pin)?;
+        }
         repo.update(&user)?;
 
         Ok(())
@@ -351,19 +671,37 @@ impl Sdk {
             return Err(crate::Error::UserNotInitialized);
         };
 
-        let mut user = repo.get(&active_user.username)?;
+        let cipher = self.user_storage_cipher.as_deref();
+        if let Some(cipher) = cipher {
+            let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+            check_unlock_attempts(repo, &active_user.username, config.max_pin_attempts)?;
+            cipher.offer_pin(&active_user.username, pin);
+        }
+        let mut user = match repo.get(&active_user.username) {
+            Ok(user) => user,
+            Err(e) => {
+                if let Some(cipher) = cipher {
+                    cipher.discard_pin(&active_user.username);
+                }
+                return Err(match e {
+                    UserKvStorageError::Locked { .. } => {
+                        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+                        count_failed_unlock(repo, &active_user.username, config.max_pin_attempts)
+                    }
+                    e => e.into(),
+                });
+            }
+        };
 
         // if password already exists, return an error!
-        if let Some(encrypted_password) = user.encrypted_password {
+        if user.encrypted_password.is_some() {
             info!("Password exists, changing password");
 
-            // verify that the pin is correct by decrypting the password using the provided PIN
-            if encrypted_password.decrypt(pin, &user.salt).is_err() {
-                return Err(WalletError::WrongPinOrPassword)?;
-            }
-
             let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
 
+            // verify that the pin is correct by decrypting the password using the provided PIN
+            get_user_and_decrypt_password(repo, cipher, &active_user.username, pin, config.max_pin_attempts)?;
+
             active_user
                 .wallet_manager
                 .change_wallet_password(config, &self.access_token, repo, pin, new_password)
@@ -373,15 +711,266 @@ impl Sdk {
             let salt = EncryptionSalt::generate();
             let encrypted_password = new_password.encrypt(pin, &salt)?;
 
-            // Update user
+            // Update user, encrypted with the key of the new pin
             user.salt = salt;
             user.encrypted_password = Some(encrypted_password);
+            if let Some(cipher) = cipher {
+                cipher.confirm_pin(&active_user.username)?;
+            }
+            repo.update(&user)?;
+        }
+
+        Ok(())
+    }
+
+    /// Recover the wallet with the mnemonic, for example after it was locked because of too many
+    /// wrong pin attempts.
+    ///
+    /// Sets a new pin and password, resets the failed pin attempts and recreates the wallet shares
+    /// from the mnemonic. The existing wallet shares are replaced, so the mnemonic is first checked
+    /// against the fingerprint of the local share, or of the backup share if there is no local share. If the user
+    /// has a wallet and neither share is available, e.g. without an access token, the wallet is not replaced.
+    /// The guardian key is encrypted with the forgotten pin, so it is removed and a new guardian public key
+    /// has to be handed out to the users who nominated this user as guardian.
+    /// If the user storage is encrypted with the pin and the user is locked out, the stored user cannot be
+    /// read anymore. It is then rebuilt with the new pin, so the mnemonic is checked against the backup share
+    /// and the data only stored on this device, like the transaction history, is lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_pin` - The new pin to set for the wallet.
+    /// * `new_password` - The new password to set for the wallet.
+    /// * `mnemonic` - The mnemonic of the wallet.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the wallet is recovered successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::MnemonicDoesNotMatchWallet`] - If the mnemonic is not the one of the existing wallet,
+    ///   or the shares of the wallet have no fingerprint to check it against.
+    /// * [`WalletError::MnemonicNotVerifiable`] - If the user has a wallet, but neither the local nor the backup
+    ///   share is available to check the mnemonic against.
+    pub async fn recover_wallet_with_mnemonic(
+        &mut self,
+        new_pin: &EncryptionPin,
+        new_password: &PlainPassword,
+        mnemonic: &str,
+    ) -> Result<()> {
+        self.recover_wallet(new_pin, new_password, mnemonic, None).await
+    }
+
+    /// Recover the wallet with the mnemonic and the BIP-39 passphrase, like
+    /// [`Sdk::recover_wallet_with_mnemonic`] for wallets which use a passphrase. The passphrase is stored
+    /// encrypted with the new pin on this device.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_pin` - The new pin to set for the wallet.
+    /// * `new_password` - The new password to set for the wallet.
+    /// * `mnemonic` - The mnemonic of the wallet.
+    /// * `passphrase` - The BIP-39 passphrase of the wallet.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::MnemonicDoesNotMatchWallet`] - If the mnemonic is not the one of the existing wallet,
+    ///   or the shares of the wallet have no fingerprint to check it against.
+    /// * [`WalletError::MnemonicNotVerifiable`] - If the user has a wallet, but neither the local nor the backup
+    ///   share is available to check the mnemonic against.
+    pub async fn recover_wallet_with_mnemonic_and_passphrase(
+        &mut self,
+        new_pin: &EncryptionPin,
+        new_password: &PlainPassword,
+        mnemonic: &str,
+        passphrase: &MnemonicPassphrase,
+    ) -> Result<()> {
+        self.recover_wallet(new_pin, new_password, mnemonic, Some(passphrase))
+            .await
+    }
+
+    /// Recover the wallet with the mnemonic and the optional BIP-39 passphrase.
+    async fn recover_wallet(
+        &mut self,
+        new_pin: &EncryptionPin,
+        new_password: &PlainPassword,
+        mnemonic: &str,
+        passphrase: Option<&MnemonicPassphrase>,
+    ) -> Result<()> {
+        info!("Recovering wallet with mnemonic");
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let config = self.config.as_ref().ok_or(crate::Error::MissingConfig)?;
+
+        let username = &active_user.username;
+        let cipher = self.user_storage_cipher.as_deref();
+
+        // A user stored encrypted with the key of the forgotten pin cannot be read anymore, so it is rebuilt
+        // from scratch with the wallet recreated from the mnemonic
+        let previous_user = match repo.get(username) {
+            Ok(user) => Some(user),
+            Err(UserKvStorageError::Locked { .. }) if cipher.is_some_and(UserStorageCipher::uses_pin) => {
+                warn!("The stored user is locked with the forgotten pin, rebuilding it");
+                None
+            }
+            Err(e) => return Err(e)?,
+        };
+
+        // Only replace the shares of the wallet the mnemonic belongs to
+        let local_share = previous_user.as_ref().and_then(|user| user.local_share.as_ref());
+        let share = match local_share {
+            Some(share) => Some(share.parse::<Share>().map_err(WalletError::Share)?),
+            None => match &self.access_token {
+                Some(token) => download_backup_share(config, token, username).await?,
+                None => None,
+            },
+        };
+        let has_wallet = previous_user
+            .as_ref()
+            .map_or(true, |user| user.encrypted_password.is_some());
+        match share {
+            Some(share) => {
+                if is_secret_of_share(&share, mnemonic)? != Some(true) {
+                    return Err(WalletError::MnemonicDoesNotMatchWallet)?;
+                }
+            }
+            // an existing wallet is never replaced without checking the mnemonic
+            None if has_wallet => {
+                return Err(WalletError::MnemonicNotVerifiable)?;
+            }
+            None => {}
+        }
+
+        // Set new pin and encrypted password, and unlock the pin
+        let mut user = match &previous_user {
+            Some(user) => user.clone(),
+            None => UserEntity {
+                user_id: None,
+                username: username.clone(),
+                encrypted_password: None,
+                salt: EncryptionSalt::generate(),
+                is_kyc_verified: false,
+                kyc_type: KycType::Undefined,
+                viviswap_state: None,
+                local_share: None,
+                wallet_transactions: Vec::new(),
+                wallet_tx_history: Default::default(),
+                pin_attempts: Default::default(),
+                evm_address_indices: Vec::new(),
+                pending_transactions: Default::default(),
+                watched_transactions: Vec::new(),
+                watch_only: None,
+                created_at: None,
+                last_used_at: None,
+                guardian_secret_key: None,
+                guardian_recovery_key: None,
+                encrypted_passphrase: None,
+                offline_recovery_share: None,
+            },
+        };
+        let salt = EncryptionSalt::generate();
+        user.encrypted_password = Some(new_password.encrypt(new_pin, &salt)?);
+        user.salt = salt;
+        user.pin_attempts = PinAttempts::default();
+        // the secret keys are encrypted with the forgotten pin, so a new guardian key has to be handed out
+        user.guardian_secret_key = None;
+        user.guardian_recovery_key = None;
+        // the passphrase is encrypted with the forgotten pin as well, the given one is stored with the shares
+        user.encrypted_passphrase = None;
+        // the offline unlock has to be enabled again with the new pin
+        user.offline_recovery_share = None;
+
+        // the rebuilt user is stored with the key of the new pin
+        let locked_attempts = match (&previous_user, cipher) {
+            (None, Some(cipher)) => {
+                let attempts = repo.unlock_attempts(username)?;
+                cipher.offer_pin(username, new_pin);
+                cipher.confirm_pin(username)?;
+                Some(attempts)
+            }
+            _ => None,
+        };
+        repo.update(&user)?;
+
+        if let Err(e) = active_user
+            .wallet_manager
+            .create_wallet_from_existing_mnemonic(
+                config,
+                &self.access_token,
+                repo,
+                new_pin,
+                mnemonic,
+                &passphrase.cloned(),
+            )
+            .await
+        {
+            // only unlock the wallet if the mnemonic could be used to recreate the shares
+            warn!("Could not recover wallet with mnemonic, restoring previous pin and password: {e}");
+            match (&previous_user, locked_attempts) {
+                (Some(previous_user), _) => repo.update(previous_user)?,
+                // the locked record is already replaced, so keep the rebuilt one locked out
+                (None, attempts) => {
+                    user.pin_attempts = attempts.unwrap_or_default();
+                    repo.update(&user)?;
+                }
+            }
+            return Err(e)?;
+        }
+
+        // Rotate the key of the user storage, so the user is stored with the key of the new pin
+        if let Some(cipher) = cipher {
+            cipher.rotate_key(username, new_pin)?;
+            let user = repo.get(username)?;
             repo.update(&user)?;
         }
 
         Ok(())
     }
 
+    /// Set the BIP-39 passphrase of the wallet on this device
+    ///
+    /// The passphrase is not part of the wallet shares. It has to be set on every device that restores a
+    /// wallet with a passphrase from its shares, otherwise initializing the wallet fails with
+    /// [`ErrorKind::MissingPassphrase`]. Any passphrase leads to a valid wallet, so a wrong passphrase shows up
+    /// as a wallet with different addresses.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN for the wallet, used to encrypt the passphrase.
+    /// * `passphrase` - The BIP-39 passphrase of the wallet.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    pub async fn set_wallet_passphrase(&mut self, pin: &EncryptionPin, passphrase: &MnemonicPassphrase) -> Result<()> {
+        info!("Setting wallet passphrase");
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let mut user = repo.get(&active_user.username)?;
+        user.encrypted_passphrase = Some(encrypt_passphrase(passphrase, pin, &user.salt).map_err(WalletError::Share)?);
+        repo.update(&user)?;
+        Ok(())
+    }
+
     /// Check if the password to use for wallet operations is set. If this returns `false`,
     /// the password should be set with [`set_wallet_password`], otherwise you need to use
     /// [`change_password`] to change it.
@@ -422,7 +1011,7 @@ impl Sdk {
     /// * [`crate::Error::MissingConfig`] - If the sdk config is missing.
     pub async fn generate_new_address(&mut self, pin: &EncryptionPin) -> Result<String> {
         info!("Generating new wallet address");
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
         let Some(repo) = &mut self.repo else {
             return Err(crate::Error::UserRepoNotInitialized);
         };
@@ -446,6 +1035,91 @@ impl Sdk {
         Ok(address)
     }
 
+    /// Derives a new address of the EVM wallet.
+    ///
+    /// Unlike [`Sdk::generate_new_address`], which always returns the default address, every call derives the
+    /// address with the next unused address index, e.g. to use a separate receiving address per counterparty. The
+    /// address indices in use are stored for the user and are shared by all EVM networks.
+    ///
+    /// # Returns
+    ///
+    /// Returns the newly derived address.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`crate::Error::MissingNetwork`] - If the network is not set.
+    /// * [`WalletError::WalletFeatureNotImplemented`] - If the selected network is not an EVM network.
+    pub async fn derive_new_address(&mut self, pin: &EncryptionPin) -> Result<String> {
+        info!("Deriving new wallet address");
+        self.check_pin(pin).await?;
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        if !matches!(
+            network.network_type,
+            NetworkType::Evm { .. } | NetworkType::EvmErc20 { .. }
+        ) {
+            return Err(WalletError::WalletFeatureNotImplemented.into());
+        }
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+
+        let mut user = repo.get(&active_user.username)?;
+        let next_index = user.evm_address_indices.iter().max().copied().unwrap_or(0) + 1;
+        user.evm_address_indices.push(next_index);
+        repo.update(&user)?;
+
+        // the addresses are ordered by their index, so the new address is the last one
+        let access_token = &self.access_token;
+        let result = async {
+            let wallet = active_user
+                .wallet_manager
+                .try_get(config, access_token, repo, network, pin)
+                .await?;
+            let addresses = wallet.get_addresses().await?;
+            addresses.last().cloned().ok_or(WalletError::EmptyWalletAddress)
+        }
+        .await;
+
+        match result {
+            Ok(address) => {
+                debug!("Derived address {address} with index {next_index}");
+                Ok(address)
+            }
+            Err(e) => {
+                // do not keep the index of an address that could not be derived
+                user.evm_address_indices.pop();
+                repo.update(&user)?;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Get all addresses of the wallet in use
+    ///
+    /// # Returns
+    ///
+    /// Returns the addresses ordered by their address index, starting with the default address. For EVM networks,
+    /// this includes the addresses derived with [`Sdk::derive_new_address`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
+    pub async fn get_wallet_addresses(&mut self, pin: &EncryptionPin) -> Result<Vec<String>> {
+        info!("Fetching wallet addresses");
+        self.check_pin(pin).await?;
+        let wallet = self.try_get_active_user_wallet(pin).await?;
+        let addresses = wallet.get_addresses().await?;
+        debug!("Addresses: {addresses:?}");
+        Ok(addresses)
+    }
+
     /// Get the balance of the user
     ///
     /// Fetches the balance of the user from the wallet.
@@ -460,22 +1134,46 @@ impl Sdk {
     /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
     pub async fn get_balance(&mut self, pin: &EncryptionPin) -> Result<CryptoAmount> {
         info!("Fetching balance");
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
         let wallet = self.try_get_active_user_wallet(pin).await?;
         let balance = wallet.get_balance().await?;
         debug!("Balance: {balance:?}");
         Ok(balance)
     }
 
+    /// Get the balance of a single address of the wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - One of the addresses returned by [`Sdk::get_wallet_addresses`].
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
+    /// * [`WalletError::UnknownAddress`] - If the address does not belong to the wallet.
+    /// * [`WalletError::WalletFeatureNotImplemented`] - If the selected network does not support it.
+    pub async fn get_address_balance(&mut self, pin: &EncryptionPin, address: &str) -> Result<CryptoAmount> {
+        info!("Fetching balance of address {address}");
+        self.check_pin(pin).await?;
+        let wallet = self.try_get_active_user_wallet(pin).await?;
+        let balance = wallet.get_address_balance(address).await?;
+        debug!("Balance: {balance:?}");
+        Ok(balance)
+    }
+
     /// wallet transaction list
     ///
     /// Returns paginated list of wallet transaction list.
     ///
     /// # Arguments
     ///
-    /// * `start` - The starting index of transactions to fetch.
+    /// * `start` - The page of transactions to fetch.
     /// * `limit` - The number of transactions per page.
     ///
+    /// For EVM networks the network is scanned for new incoming and outgoing transfers (native or
+    /// ERC-20 `Transfer` events), continuing where the last scan stopped. The scanned history is persisted per network in the user repository.
+    ///
     /// # Returns
     ///
     /// Returns a `WalletTxInfoList` containing paginated history of wallet transactions if the outputs are claimed successfully, otherwise returns an `Error`.
@@ -491,47 +1189,31 @@ impl Sdk {
         limit: usize,
     ) -> Result<WalletTxInfoList> {
         info!("Wallet getting list of transactions");
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
 
         let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
         let user = self.get_user().await?;
         let wallet = self.try_get_active_user_wallet(pin).await?;
 
         let tx_list = match network.network_type {
-            crate::types::networks::NetworkType::EvmErc20 {
-                node_urls: _,
-                chain_id: _,
-                contract_address: _,
-            } => wallet.get_wallet_tx_list(start, limit).await?,
-            crate::types::networks::NetworkType::Evm {
-                node_urls: _,
-                chain_id: _,
-            } => {
-                // We retrieve the transaction list from the wallet,
-                // then synchronize selected transactions (by fetching their current status from the network),
-                // and finally, save the refreshed list back to the wallet
-                let mut wallet_transactions = user.wallet_transactions;
-
-                for transaction in wallet_transactions.iter_mut().skip(start).take(limit) {
-                    let synchronized_transaction = wallet.get_wallet_tx(&transaction.transaction_id).await;
-                    match synchronized_transaction {
-                        Ok(stx) => *transaction = stx,
-                        Err(e) => {
-                            // On error, return historical (cached) transaction data
-                            log::debug!("[sync_transactions] could not retrieve data about transaction from the network, transaction: {:?}, error: {:?}", transaction.clone(), e);
-                        }
-                    }
-                }
+            crate::types::networks::NetworkType::Evm { .. } | crate::types::networks::NetworkType::EvmErc20 { .. } => {
+                let username = user.username.clone();
+                let history = user.wallet_tx_history.get(&network.id).cloned().unwrap_or_default();
+                // native transactions sent by this SDK which are not yet included in a scanned block
+                let sent_transactions = match network.network_type {
+                    NetworkType::Evm { .. } => user.wallet_transactions,
+                    _ => Vec::new(),
+                };
+                let (transactions, history) =
+                    evm_wallet_tx_page(&**wallet, history, sent_transactions, start, limit).await;
 
                 let Some(repo) = &mut self.repo else {
                     return Err(crate::Error::UserRepoNotInitialized);
                 };
 
-                let _ = repo.set_wallet_transactions(&user.username, wallet_transactions.clone());
+                let _ = repo.set_wallet_tx_history(&username, &network.id, history);
 
-                WalletTxInfoList {
-                    transactions: wallet_transactions,
-                }
+                WalletTxInfoList { transactions }
             }
             crate::types::networks::NetworkType::Stardust { node_urls: _ } => {
                 wallet.get_wallet_tx_list(start, limit).await?
@@ -559,27 +1241,262 @@ impl Sdk {
     /// * [`WalletError::WalletNotInitialized`] - If there is an error initializing the wallet.
     pub async fn get_wallet_tx(&mut self, pin: &EncryptionPin, tx_id: &str) -> Result<WalletTxInfo> {
         info!("Wallet getting details of particular transactions");
-        self.verify_pin(pin).await?;
+        self.check_pin(pin).await?;
         let wallet = self.try_get_active_user_wallet(pin).await?;
         let wallet_tx = wallet.get_wallet_tx(tx_id).await?;
         Ok(wallet_tx)
     }
 }
 
+/// Scans an EVM network for new transfers of the wallet, continuing where the last scan of the persisted `history`
+/// stopped, and returns the requested page of transactions together with the updated history to be persisted. The
+/// `sent_transactions` which are not part of the scanned history are merged into the page.
+pub(super) async fn evm_wallet_tx_page(
+    wallet: &(dyn WalletUser + Send + Sync),
+    mut history: WalletTxHistory,
+    sent_transactions: Vec<WalletTxInfo>,
+    start: usize,
+    limit: usize,
+) -> (Vec<WalletTxInfo>, WalletTxHistory) {
+    // We continue scanning the network where the last scan stopped and merge the newly
+    // found transactions into the persisted history.
+    // once the history is full, backfilled transactions would be dropped again right away
+    let backfill_from = history.first_scanned_block.filter(|_| !history.is_full());
+    match wallet.scan_wallet_tx(history.last_scanned_block, backfill_from).await {
+        Ok(scan) => {
+            history.merge(scan.transactions);
+            history.last_scanned_block = scan.last_scanned_block.or(history.last_scanned_block);
+            history.first_scanned_block = scan.first_scanned_block.or(history.first_scanned_block);
+        }
+        Err(e) => {
+            // On error, continue with the historical (cached) transaction data
+            warn!("[get_wallet_tx_list] could not scan the network for new transactions: {e:?}");
+        }
+    }
+
+    let mut merged = history.clone();
+    merged.merge(sent_transactions.into_iter().filter(|tx| {
+        !history
+            .transactions
+            .iter()
+            .any(|t| t.transaction_id == tx.transaction_id)
+    }));
+
+    // Extract transactions for the current page based on the start index and page size,
+    // then synchronize them (by fetching their current status from the network)
+    let mut transactions = merged
+        .transactions
+        .into_iter()
+        .skip(start * limit)
+        .take(limit)
+        .collect::<Vec<_>>();
+
+    for transaction in transactions.iter_mut() {
+        if transaction.status == format!("{:?}", InclusionState::Confirmed) {
+            continue;
+        }
+        let synchronized_transaction = wallet.get_wallet_tx(&transaction.transaction_id).await;
+        match synchronized_transaction {
+            Ok(stx) => *transaction = stx,
+            Err(e) => {
+                // On error, return historical (cached) transaction data
+                log::debug!("[sync_transactions] could not retrieve data about transaction from the network, transaction: {:?}, error: {:?}", transaction.clone(), e);
+            }
+        }
+    }
+
+    // only update transactions that are part of the scanned history, the others are
+    // picked up once the scan reaches their block
+    history.merge(
+        transactions
+            .iter()
+            .filter(|tx| {
+                history
+                    .transactions
+                    .iter()
+                    .any(|t| t.transaction_id == tx.transaction_id)
+            })
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+
+    (transactions, history)
+}
+
+/// Number of wrong pin attempts that are allowed before the back-off starts.
+const PIN_ATTEMPTS_WITHOUT_BACKOFF: u32 = 3;
+/// Back-off after the first wrong pin attempt exceeding [`PIN_ATTEMPTS_WITHOUT_BACKOFF`], doubled
+/// with every further wrong attempt.
+const PIN_BACKOFF_SECS: u64 = 30;
+/// Upper limit of the back-off between two pin attempts.
+const MAX_PIN_BACKOFF_SECS: u64 = 24 * 60 * 60;
+
+/// Checks if another pin attempt is allowed at `now` (unix timestamp in seconds).
+fn check_pin_attempts(attempts: &PinAttempts, max_attempts: u32, now: i64) -> Result<()> {
+    if attempts.failed >= max_attempts {
+        return Err(crate::Error::PinAttemptsLockedOut);
+    }
+
+    let Some(last_failed_at) = attempts.last_failed_at else {
+        return Ok(());
+    };
+    let Some(exponent) = attempts.failed.checked_sub(PIN_ATTEMPTS_WITHOUT_BACKOFF) else {
+        return Ok(());
+    };
+
+    let backoff = PIN_BACKOFF_SECS
+        .saturating_mul(2u64.saturating_pow(exponent))
+        .min(MAX_PIN_BACKOFF_SECS);
+    let elapsed = u64::try_from(now.saturating_sub(last_failed_at)).unwrap_or_default();
+    if elapsed < backoff {
+        return Err(crate::Error::PinAttemptsBackoff {
+            retry_after_secs: backoff - elapsed,
+        });
+    }
+
+    Ok(())
+}
+
+/// The user and the decrypted password after the pin was verified.
+struct VerifiedUser {
+    user: UserEntity,
+    password: PlainPassword,
+    /// Whether the pin unlocked the user storage for the first time, so the user can now be stored encrypted
+    /// with the key of the pin
+    unlocked: bool,
+}
+
+/// Loads the user and decrypts the password with the pin, see [`decrypt_password_with_attempts`].
+///
+/// If the user storage is encrypted with a key derived from the pin, the pin is offered to unlock the stored
+/// user first. A locked user that cannot be decrypted with the pin is counted as wrong pin, see
+/// [`count_failed_unlock`].
+fn get_user_and_decrypt_password(
+    repo: &UserRepoT,
+    cipher: Option<&UserStorageCipher>,
+    username: &str,
+    pin: &EncryptionPin,
+    max_attempts: u32,
+) -> Result<VerifiedUser> {
+    if let Some(cipher) = cipher {
+        check_unlock_attempts(repo, username, max_attempts)?;
+        cipher.offer_pin(username, pin);
+    }
+
+    let result = match repo.get(username) {
+        Ok(user) => decrypt_password_with_attempts(repo, &user, pin, max_attempts).map(|password| (user, password)),
+        Err(UserKvStorageError::Locked { .. }) => Err(count_failed_unlock(repo, username, max_attempts)),
+        Err(e) => Err(e.into()),
+    };
+
+    let unlocked = match (cipher, &result) {
+        (Some(cipher), Ok(_)) => cipher.confirm_pin(username)?,
+        (Some(cipher), Err(_)) => {
+            cipher.discard_pin(username);
+            false
+        }
+        (None, _) => false,
+    };
+    result.map(|(user, password)| VerifiedUser {
+        user,
+        password,
+        unlocked,
+    })
+}
+
+/// Checks the failed pin attempts stored in clear with the encrypted record of the user before the pin is offered
+/// to unlock it, so a locked out user cannot be unlocked with the correct pin.
+fn check_unlock_attempts(repo: &UserRepoT, username: &str, max_attempts: u32) -> Result<()> {
+    let attempts = repo.unlock_attempts(username)?;
+    check_pin_attempts(&attempts, max_attempts, chrono::Utc::now().timestamp())
+}
+
+/// Counts a pin that could not unlock the encrypted record of the user and returns the error to report.
+///
+/// The record cannot be read without the key, so the attempts are stored in clear next to it and limited
+/// like the wrong pins counted by [`decrypt_password_with_attempts`]. Once the record is unlocked, they are
+/// the pin attempts of the user and reset after the correct pin.
+fn count_failed_unlock(repo: &UserRepoT, username: &str, max_attempts: u32) -> crate::Error {
+    let previous = match repo.unlock_attempts(username) {
+        Ok(previous) => previous,
+        Err(e) => return e.into(),
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    if let Err(e) = check_pin_attempts(&previous, max_attempts, now) {
+        return e;
+    }
+
+    let attempts = PinAttempts {
+        failed: previous.failed.saturating_add(1),
+        last_failed_at: Some(now),
+    };
+    warn!(
+        "Wrong pin entered to unlock the user, {} consecutive failed attempts",
+        attempts.failed
+    );
+    if let Err(e) = repo.set_unlock_attempts(username, attempts) {
+        return e.into();
+    }
+
+    if attempts.failed >= max_attempts {
+        return crate::Error::PinAttemptsLockedOut;
+    }
+    WalletError::WrongPinOrPassword.into()
+}
+
+/// Decrypts the password of the user with the pin while limiting the number of wrong attempts.
+/// Wrong attempts are counted in the user repository and reset again after a correct attempt.
+fn decrypt_password_with_attempts(
+    repo: &UserRepoT,
+    user: &UserEntity,
+    pin: &EncryptionPin,
+    max_attempts: u32,
+) -> Result<PlainPassword> {
+    // Ensure encrypted password exists in user
+    let Some(encrypted_password) = &user.encrypted_password else {
+        return Err(WalletError::WalletNotInitialized(ErrorKind::MissingPassword))?;
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    check_pin_attempts(&user.pin_attempts, max_attempts, now)?;
+
+    let Ok(password) = encrypted_password.decrypt(pin, &user.salt) else {
+        let attempts = PinAttempts {
+            failed: user.pin_attempts.failed.saturating_add(1),
+            last_failed_at: Some(now),
+        };
+        warn!("Wrong pin entered, {} consecutive failed attempts", attempts.failed);
+        repo.set_pin_attempts(&user.username, attempts)?;
+
+        if attempts.failed >= max_attempts {
+            return Err(crate::Error::PinAttemptsLockedOut);
+        }
+        return Err(WalletError::WrongPinOrPassword)?;
+    };
+
+    if user.pin_attempts.failed > 0 {
+        repo.set_pin_attempts(&user.username, PinAttempts::default())?;
+    }
+
+    Ok(password)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::core_testing_utils::handle_error_test_cases;
     use crate::testing_utils::{
-        example_get_user, example_network_id, example_networks, example_wallet_tx_info, set_config, ADDRESS,
-        AUTH_PROVIDER, BACKUP_PASSWORD, HEADER_X_APP_NAME, MNEMONIC, PIN, SALT, TOKEN, TX_INDEX, USERNAME,
+        example_get_user, example_network, example_network_id, example_networks, example_wallet_tx_info, set_config,
+        ADDRESS, AUTH_PROVIDER, BACKUP_PASSWORD, ENCRYPTED_PASSWORD, HEADER_X_APP_NAME, LEGACY_ENCRYPTED_PASSWORD,
+        MNEMONIC, PIN, SALT, TOKEN, TX_INDEX, USERNAME,
     };
     use crate::types::currencies::Currency;
-    use crate::types::users::UserEntity;
     use crate::{
         core::Sdk,
+        share::decrypt_passphrase,
         types::users::KycType,
-        user::MockUserRepo,
+        user::{memory_storage::MemoryUserStorage, repository::UserRepoImpl, MockUserRepo, UserRepo},
         wallet_manager::{MockWalletManager, WalletBorrow},
         wallet_user::MockWalletUser,
     };
@@ -593,27 +1510,168 @@ mod tests {
     const BACKUP: &[u8] = &[42, 77, 15, 203, 89, 123, 34, 56, 178, 90, 210, 33, 47, 192, 1, 17];
 
     #[rstest]
-    #[case::success(Ok(MNEMONIC.to_string()))]
-    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::success(Ok(MNEMONIC.to_string()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_create_wallet_from_new_mnemonic(#[case] expected: Result<String>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_create_wallet_from_new_mnemonic()
+                    .once()
+                    .returning(|_, _, _, _, _, _| Ok(MNEMONIC.to_string()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk
+            .create_wallet_from_new_mnemonic(&PIN, &MnemonicOptions::default())
+            .await;
+
+        // Assert
+        match expected {
+            Ok(resp) => {
+                assert_eq!(response.unwrap(), resp);
+            }
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::missing_config(Err(crate::Error::MissingConfig))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_create_wallet_from_existing_mnemonic(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_create_wallet_from_existing_mnemonic()
+                    .once()
+                    .returning(|_, _, _, _, _, _| Ok(()));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.create_wallet_from_existing_mnemonic(&PIN, MNEMONIC).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_wallet_from_existing_mnemonic_with_passphrase() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        let passphrase = MnemonicPassphrase::try_from_string("25th word").unwrap();
+
+        sdk.repo = Some(Box::new(example_get_user(
+            SwapPaymentDetailKey::Iota,
+            false,
+            1,
+            KycType::Undefined,
+        )));
+        let expected_passphrase = passphrase.clone();
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_create_wallet_from_existing_mnemonic()
+            .once()
+            .withf(move |_, _, _, _, mnemonic, passphrase| {
+                mnemonic == MNEMONIC && passphrase.as_ref() == Some(&expected_passphrase)
+            })
+            .returning(|_, _, _, _, _, _| Ok(()));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        // Act
+        let response = sdk
+            .create_wallet_from_existing_mnemonic_with_passphrase(&PIN, MNEMONIC, &passphrase)
+            .await;
+
+        // Assert
+        response.unwrap();
+    }
+
+    #[rstest]
+    #[case::success(Ok(()))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
     #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
     #[tokio::test]
-    async fn test_create_wallet_from_new_mnemonic(#[case] expected: Result<String>) {
+    async fn test_set_wallet_passphrase(#[case] expected: Result<()>) {
         // Arrange
         let (_srv, config, _cleanup) = set_config().await;
         let mut sdk = Sdk::new(config).unwrap();
+        let passphrase = MnemonicPassphrase::try_from_string("25th word").unwrap();
 
         match &expected {
             Ok(_) => {
-                sdk.repo = Some(Box::new(MockUserRepo::new()));
-                let mut mock_wallet_manager = MockWalletManager::new();
-                mock_wallet_manager
-                    .expect_create_wallet_from_new_mnemonic()
+                let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 2, KycType::Undefined);
+                let expected_passphrase = passphrase.clone();
+                mock_user_repo
+                    .expect_update()
                     .once()
-                    .returning(|_, _, _, _| Ok(MNEMONIC.to_string()));
+                    .withf(move |user| {
+                        user.encrypted_passphrase.as_deref().is_some_and(|stored| {
+                            decrypt_passphrase(stored, &PIN, &user.salt).is_ok_and(|p| p == expected_passphrase)
+                        })
+                    })
+                    .returning(|_| Ok(()));
+                sdk.repo = Some(Box::new(mock_user_repo));
                 sdk.active_user = Some(crate::types::users::ActiveUser {
                     username: USERNAME.into(),
-                    wallet_manager: Box::new(mock_wallet_manager),
+                    wallet_manager: Box::new(MockWalletManager::new()),
                 });
             }
             Err(error) => {
@@ -622,13 +1680,11 @@ mod tests {
         }
 
         // Act
-        let response = sdk.create_wallet_from_new_mnemonic(&PIN).await;
+        let response = sdk.set_wallet_passphrase(&PIN, &passphrase).await;
 
         // Assert
         match expected {
-            Ok(resp) => {
-                assert_eq!(response.unwrap(), resp);
-            }
+            Ok(()) => response.unwrap(),
             Err(ref expected_err) => {
                 assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
             }
@@ -636,24 +1692,30 @@ mod tests {
     }
 
     #[rstest]
-    #[case::success(Ok(()))]
+    #[case::success(Ok(BACKUP.to_vec()))]
     #[case::missing_config(Err(crate::Error::MissingConfig))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
     #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
     #[tokio::test]
-    async fn test_create_wallet_from_existing_mnemonic(#[case] expected: Result<()>) {
+    async fn test_create_wallet_backup(#[case] expected: Result<Vec<u8>>) {
         // Arrange
         let (_srv, config, _cleanup) = set_config().await;
         let mut sdk = Sdk::new(config).unwrap();
 
         match &expected {
             Ok(_) => {
-                sdk.repo = Some(Box::new(MockUserRepo::new()));
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
                 let mut mock_wallet_manager = MockWalletManager::new();
                 mock_wallet_manager
-                    .expect_create_wallet_from_existing_mnemonic()
+                    .expect_create_wallet_backup()
                     .once()
-                    .returning(|_, _, _, _, _| Ok(()));
+                    .returning(|_, _, _, _, _| Ok(BACKUP.to_vec()));
                 sdk.active_user = Some(crate::types::users::ActiveUser {
                     username: USERNAME.into(),
                     wallet_manager: Box::new(mock_wallet_manager),
@@ -665,11 +1727,13 @@ mod tests {
         }
 
         // Act
-        let response = sdk.create_wallet_from_existing_mnemonic(&PIN, MNEMONIC).await;
+        let response = sdk.create_wallet_backup(&PIN, &BACKUP_PASSWORD).await;
 
         // Assert
         match expected {
-            Ok(()) => response.unwrap(),
+            Ok(resp) => {
+                assert_eq!(response.unwrap(), resp);
+            }
             Err(ref expected_err) => {
                 assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
             }
@@ -677,24 +1741,30 @@ mod tests {
     }
 
     #[rstest]
-    #[case::success(Ok(BACKUP.to_vec()))]
+    #[case::success(Ok(()))]
     #[case::missing_config(Err(crate::Error::MissingConfig))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
     #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
     #[tokio::test]
-    async fn test_create_wallet_backup(#[case] expected: Result<Vec<u8>>) {
+    async fn test_create_wallet_from_backup(#[case] expected: Result<()>) {
         // Arrange
         let (_srv, config, _cleanup) = set_config().await;
         let mut sdk = Sdk::new(config).unwrap();
 
         match &expected {
             Ok(_) => {
-                sdk.repo = Some(Box::new(MockUserRepo::new()));
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
                 let mut mock_wallet_manager = MockWalletManager::new();
                 mock_wallet_manager
-                    .expect_create_wallet_backup()
+                    .expect_create_wallet_from_backup()
                     .once()
-                    .returning(|_, _, _, _, _| Ok(BACKUP.to_vec()));
+                    .returning(|_, _, _, _, _, _| Ok(None));
                 sdk.active_user = Some(crate::types::users::ActiveUser {
                     username: USERNAME.into(),
                     wallet_manager: Box::new(mock_wallet_manager),
@@ -706,38 +1776,94 @@ mod tests {
         }
 
         // Act
-        let response = sdk.create_wallet_backup(&PIN, &BACKUP_PASSWORD).await;
+        let response = sdk.create_wallet_from_backup(&PIN, BACKUP, &BACKUP_PASSWORD).await;
 
         // Assert
         match expected {
-            Ok(resp) => {
-                assert_eq!(response.unwrap(), resp);
-            }
+            Ok(()) => response.unwrap(),
             Err(ref expected_err) => {
                 assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
             }
         }
     }
 
+    #[tokio::test]
+    async fn test_create_wallet_from_backup_restores_metadata() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(2).returning(|_| {
+            Ok(UserEntity {
+                evm_address_indices: vec![1],
+                ..user_with_pin_attempts(PinAttempts::default())
+            })
+        });
+        mock_user_repo
+            .expect_update()
+            .once()
+            .withf(|user| user.evm_address_indices == [1, 2])
+            .returning(|_| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_create_wallet_from_backup()
+            .once()
+            .returning(|_, _, _, _, _, _| {
+                Ok(Some(WalletMetadata {
+                    network_ids: vec![example_network_id(Currency::Iota), String::from("removed network")],
+                    preferred_network: Some(example_network_id(Currency::Iota)),
+                    evm_address_indices: vec![2],
+                    share_labels: Vec::new(),
+                }))
+            });
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+        sdk.set_networks(example_networks());
+
+        // Act
+        let response = sdk.create_wallet_from_backup(&PIN, BACKUP, &BACKUP_PASSWORD).await;
+
+        // Assert
+        response.unwrap();
+        assert_eq!(sdk.networks, example_networks());
+        assert_eq!(sdk.network, Some(example_network(Currency::Iota)));
+    }
+
     #[rstest]
-    #[case::success(Ok(()))]
+    #[case::success(Ok(BACKUP.to_vec()))]
     #[case::missing_config(Err(crate::Error::MissingConfig))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
     #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
     #[tokio::test]
-    async fn test_create_wallet_from_backup(#[case] expected: Result<()>) {
+    async fn test_create_json_wallet_backup(#[case] expected: Result<Vec<u8>>) {
         // Arrange
         let (_srv, config, _cleanup) = set_config().await;
         let mut sdk = Sdk::new(config).unwrap();
 
         match &expected {
             Ok(_) => {
-                sdk.repo = Some(Box::new(MockUserRepo::new()));
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    2,
+                    KycType::Undefined,
+                )));
+                sdk.set_networks(example_networks());
                 let mut mock_wallet_manager = MockWalletManager::new();
                 mock_wallet_manager
-                    .expect_create_wallet_from_backup()
+                    .expect_create_json_wallet_backup()
                     .once()
-                    .returning(|_, _, _, _, _, _| Ok(()));
+                    .withf(|_, _, _, _, _, metadata| {
+                        metadata.network_ids == [example_network_id(Currency::Iota), example_network_id(Currency::Eth)]
+                            && metadata.preferred_network.is_none()
+                    })
+                    .returning(|_, _, _, _, _, _| Ok(BACKUP.to_vec()));
                 sdk.active_user = Some(crate::types::users::ActiveUser {
                     username: USERNAME.into(),
                     wallet_manager: Box::new(mock_wallet_manager),
@@ -749,11 +1875,13 @@ mod tests {
         }
 
         // Act
-        let response = sdk.create_wallet_from_backup(&PIN, BACKUP, &BACKUP_PASSWORD).await;
+        let response = sdk.create_json_wallet_backup(&PIN, &BACKUP_PASSWORD).await;
 
         // Assert
         match expected {
-            Ok(()) => response.unwrap(),
+            Ok(resp) => {
+                assert_eq!(response.unwrap(), resp);
+            }
             Err(ref expected_err) => {
                 assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
             }
@@ -765,6 +1893,7 @@ mod tests {
     #[case::missing_config(Err(crate::Error::MissingConfig))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
     #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
     #[tokio::test]
     async fn test_verify_mnemonic(#[case] expected: Result<bool>) {
         // Arrange
@@ -773,12 +1902,17 @@ mod tests {
 
         match &expected {
             Ok(_) => {
-                sdk.repo = Some(Box::new(MockUserRepo::new()));
+                sdk.repo = Some(Box::new(example_get_user(
+                    SwapPaymentDetailKey::Iota,
+                    false,
+                    1,
+                    KycType::Undefined,
+                )));
                 let mut mock_wallet_manager = MockWalletManager::new();
                 mock_wallet_manager
                     .expect_check_mnemonic()
                     .once()
-                    .returning(|_, _, _, _, _| Ok(true));
+                    .returning(|_, _, _, _, _, _| Ok(true));
                 sdk.active_user = Some(crate::types::users::ActiveUser {
                     username: USERNAME.into(),
                     wallet_manager: Box::new(mock_wallet_manager),
@@ -893,6 +2027,392 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_check_pin_migrates_legacy_encrypted_password() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(UserEntity {
+                user_id: None,
+                username: USERNAME.to_string(),
+                encrypted_password: Some(LEGACY_ENCRYPTED_PASSWORD.clone()),
+                salt: SALT.into(),
+                is_kyc_verified: false,
+                kyc_type: KycType::Undefined,
+                viviswap_state: None,
+                local_share: None,
+                wallet_transactions: Vec::new(),
+                wallet_tx_history: Default::default(),
+                pin_attempts: Default::default(),
+                evm_address_indices: Vec::new(),
+                pending_transactions: Default::default(),
+                watched_transactions: Vec::new(),
+                watch_only: None,
+                created_at: None,
+                last_used_at: None,
+                guardian_secret_key: None,
+                guardian_recovery_key: None,
+                encrypted_passphrase: None,
+                offline_recovery_share: None,
+            })
+        });
+        mock_user_repo
+            .expect_set_wallet_password()
+            .once()
+            .withf(|username, password| {
+                username == USERNAME
+                    && !password.is_legacy()
+                    && password.decrypt(&PIN, &SALT.into()).unwrap().as_str() == "StrongP@55w0rd"
+            })
+            .returning(|_, _| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk.check_pin(&PIN).await;
+
+        // Assert
+        response.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_pin_stores_legacy_password_in_current_format() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        let mut repo = UserRepoImpl::new(MemoryUserStorage::new());
+        repo.create(&UserEntity {
+            user_id: None,
+            username: USERNAME.to_string(),
+            encrypted_password: Some(LEGACY_ENCRYPTED_PASSWORD.clone()),
+            salt: SALT.into(),
+            is_kyc_verified: false,
+            kyc_type: KycType::Undefined,
+            viviswap_state: None,
+            local_share: None,
+            wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
+        })
+        .unwrap();
+        sdk.repo = Some(Box::new(repo));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk.verify_pin(&PIN).await;
+
+        // Assert
+        response.unwrap();
+        let stored = sdk.get_user().await.unwrap().encrypted_password.unwrap();
+        assert!(!stored.is_legacy());
+        assert_eq!(stored.decrypt(&PIN, &SALT.into()).unwrap().as_str(), "StrongP@55w0rd");
+    }
+
+    /// The local share of a wallet created from the mnemonic, as stored in the user.
+    fn local_share_of(mnemonic: &str) -> String {
+        let password = secrecy::SecretBox::new("password".to_string().into_bytes().into());
+        let shares =
+            crate::share::create_shares_from_mnemonic(mnemonic, false, &password, &Default::default()).unwrap();
+        secrecy::ExposeSecret::expose_secret(&shares.local.to_string()).to_string()
+    }
+
+    fn user_with_pin_attempts(pin_attempts: PinAttempts) -> UserEntity {
+        UserEntity {
+            user_id: None,
+            username: USERNAME.to_string(),
+            encrypted_password: Some(ENCRYPTED_PASSWORD.clone()),
+            salt: SALT.into(),
+            is_kyc_verified: false,
+            kyc_type: KycType::Undefined,
+            viviswap_state: None,
+            local_share: None,
+            wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts,
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
+        }
+    }
+
+    #[rstest]
+    #[case::no_failed_attempts(0, None, 100, Ok(()))]
+    #[case::below_backoff(2, Some(100), 100, Ok(()))]
+    #[case::backoff_running(3, Some(100), 110, Err(crate::Error::PinAttemptsBackoff { retry_after_secs: 20 }))]
+    #[case::backoff_elapsed(3, Some(100), 130, Ok(()))]
+    #[case::backoff_doubled(5, Some(100), 130, Err(crate::Error::PinAttemptsBackoff { retry_after_secs: 90 }))]
+    #[case::backoff_grows(9, Some(100), 100, Err(crate::Error::PinAttemptsBackoff { retry_after_secs: 1920 }))]
+    #[case::locked_out(10, Some(100), 1_000_000, Err(crate::Error::PinAttemptsLockedOut))]
+    fn test_check_pin_attempts(
+        #[case] failed: u32,
+        #[case] last_failed_at: Option<i64>,
+        #[case] now: i64,
+        #[case] expected: Result<()>,
+    ) {
+        // Arrange
+        let attempts = PinAttempts { failed, last_failed_at };
+
+        // Act
+        let response = check_pin_attempts(&attempts, 10, now);
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::wrong_pin(2, Err(crate::Error::Wallet(WalletError::WrongPinOrPassword)))]
+    #[case::last_attempt(9, Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_verify_pin_counts_wrong_attempts(#[case] failed: u32, #[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(user_with_pin_attempts(PinAttempts {
+                failed,
+                last_failed_at: None,
+            }))
+        });
+        mock_user_repo
+            .expect_set_pin_attempts()
+            .once()
+            .withf(move |username, attempts| {
+                username == USERNAME && attempts.failed == failed + 1 && attempts.last_failed_at.is_some()
+            })
+            .returning(|_, _| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        let wrong_pin = EncryptionPin::try_from_string("4321").unwrap();
+
+        // Act
+        let response = sdk.verify_pin(&wrong_pin).await;
+
+        // Assert
+        assert_eq!(response.unwrap_err().to_string(), expected.unwrap_err().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_verify_pin_resets_attempts_on_success() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(user_with_pin_attempts(PinAttempts {
+                failed: 3,
+                last_failed_at: Some(0),
+            }))
+        });
+        mock_user_repo
+            .expect_set_pin_attempts()
+            .once()
+            .withf(|_, attempts| *attempts == PinAttempts::default())
+            .returning(|_, _| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk.verify_pin(&PIN).await;
+
+        // Assert
+        response.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_verify_pin_locked_out_rejects_correct_pin() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(user_with_pin_attempts(PinAttempts {
+                failed: 10,
+                last_failed_at: Some(0),
+            }))
+        });
+        mock_user_repo.expect_set_pin_attempts().never();
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk.verify_pin(&PIN).await;
+
+        // Assert
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            crate::Error::PinAttemptsLockedOut.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_wallet_with_mnemonic_unlocks_pin() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(UserEntity {
+                local_share: Some(local_share_of(MNEMONIC)),
+                ..user_with_pin_attempts(PinAttempts {
+                    failed: 10,
+                    last_failed_at: Some(0),
+                })
+            })
+        });
+        mock_user_repo
+            .expect_update()
+            .once()
+            .withf(|user| {
+                user.pin_attempts == PinAttempts::default()
+                    && user
+                        .encrypted_password
+                        .as_ref()
+                        .is_some_and(|p| p.decrypt(&PIN, &user.salt).is_ok())
+            })
+            .returning(|_| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_create_wallet_from_existing_mnemonic()
+            .once()
+            .returning(|_, _, _, _, _, _| Ok(()));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        // Act
+        let response = sdk.recover_wallet_with_mnemonic(&PIN, &BACKUP_PASSWORD, MNEMONIC).await;
+
+        // Assert
+        response.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recover_wallet_with_other_mnemonic_keeps_wallet() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let other_mnemonic = iota_sdk::client::Client::generate_mnemonic().unwrap();
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(UserEntity {
+                local_share: Some(local_share_of(other_mnemonic.as_ref())),
+                ..user_with_pin_attempts(PinAttempts {
+                    failed: 10,
+                    last_failed_at: Some(0),
+                })
+            })
+        });
+        mock_user_repo.expect_update().never();
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_create_wallet_from_existing_mnemonic()
+            .never();
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        // Act
+        let response = sdk.recover_wallet_with_mnemonic(&PIN, &BACKUP_PASSWORD, MNEMONIC).await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(WalletError::MnemonicDoesNotMatchWallet))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recover_wallet_without_share_to_check_keeps_wallet() {
+        // Arrange: a user with a wallet, but no local share and no access token to download the backup share
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(1).returning(move |_| {
+            Ok(user_with_pin_attempts(PinAttempts {
+                failed: 10,
+                last_failed_at: Some(0),
+            }))
+        });
+        mock_user_repo.expect_update().never();
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_create_wallet_from_existing_mnemonic()
+            .never();
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        // Act
+        let response = sdk.recover_wallet_with_mnemonic(&PIN, &BACKUP_PASSWORD, MNEMONIC).await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(WalletError::MnemonicNotVerifiable))
+        ));
+    }
+
     #[rstest]
     #[case::success(Ok(()))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
@@ -935,6 +2455,209 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "jammdb_repo")]
+    #[tokio::test]
+    async fn test_pin_encrypted_user_storage_is_unlocked_and_rotated_with_the_pin() {
+        use crate::user::encryption::UserStorageEncryption;
+
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_user_storage_encryption(UserStorageEncryption::Pin).unwrap();
+        sdk.create_new_user(USERNAME).await.unwrap();
+        sdk.init_user(USERNAME).await.unwrap();
+        let password = PlainPassword::try_from_string("StrongP@55w0rd").unwrap();
+        sdk.set_wallet_password(&PIN, &password).await.unwrap();
+        let new_pin = EncryptionPin::try_from_string("4321").unwrap();
+
+        // Act & Assert: a new session is locked until the pin is entered
+        sdk.init_user(USERNAME).await.unwrap();
+        assert!(matches!(
+            sdk.get_user().await,
+            Err(crate::Error::UserRepository(UserKvStorageError::Locked { .. }))
+        ));
+        assert!(matches!(
+            sdk.verify_pin(&new_pin).await,
+            Err(crate::Error::Wallet(WalletError::WrongPinOrPassword))
+        ));
+        sdk.verify_pin(&PIN).await.unwrap();
+        assert!(sdk.get_user().await.unwrap().encrypted_password.is_some());
+
+        // Act & Assert: after changing the pin, only the new pin unlocks the user
+        sdk.change_pin(&PIN, &new_pin).await.unwrap();
+        sdk.init_user(USERNAME).await.unwrap();
+        assert!(matches!(
+            sdk.verify_pin(&PIN).await,
+            Err(crate::Error::Wallet(WalletError::WrongPinOrPassword))
+        ));
+        sdk.verify_pin(&new_pin).await.unwrap();
+        sdk.get_user().await.unwrap();
+    }
+
+    #[cfg(feature = "jammdb_repo")]
+    #[tokio::test]
+    async fn test_wrong_pins_are_counted_while_the_user_storage_is_locked() {
+        use crate::user::encryption::UserStorageEncryption;
+
+        // Arrange
+        let (_srv, mut config, _cleanup) = set_config().await;
+        config.max_pin_attempts = 2;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_user_storage_encryption(UserStorageEncryption::Pin).unwrap();
+        sdk.create_new_user(USERNAME).await.unwrap();
+        sdk.init_user(USERNAME).await.unwrap();
+        let password = PlainPassword::try_from_string("StrongP@55w0rd").unwrap();
+        sdk.set_wallet_password(&PIN, &password).await.unwrap();
+        let wrong_pin = EncryptionPin::try_from_string("4321").unwrap();
+        sdk.init_user(USERNAME).await.unwrap();
+
+        // Act & Assert: the wrong pins are counted although the user cannot be read
+        assert!(matches!(
+            sdk.verify_pin(&wrong_pin).await,
+            Err(crate::Error::Wallet(WalletError::WrongPinOrPassword))
+        ));
+        assert!(matches!(
+            sdk.set_wallet_password(&wrong_pin, &password).await,
+            Err(crate::Error::PinAttemptsLockedOut)
+        ));
+
+        // Act & Assert: the correct pin does not unlock the locked out user
+        assert!(matches!(
+            sdk.verify_pin(&PIN).await,
+            Err(crate::Error::PinAttemptsLockedOut)
+        ));
+        assert!(matches!(
+            sdk.get_user().await,
+            Err(crate::Error::UserRepository(UserKvStorageError::Locked { .. }))
+        ));
+    }
+
+    #[cfg(feature = "jammdb_repo")]
+    #[tokio::test]
+    async fn test_locked_out_user_with_pin_encrypted_user_storage_recovers_with_mnemonic() {
+        use crate::user::encryption::UserStorageEncryption;
+
+        // Arrange
+        let (mut srv, mut config, _cleanup) = set_config().await;
+        config.max_pin_attempts = 2;
+        let password = secrecy::SecretBox::new("password".to_string().into_bytes().into());
+        let shares =
+            crate::share::create_shares_from_mnemonic(MNEMONIC, false, &password, &Default::default()).unwrap();
+        let backup_share = secrecy::ExposeSecret::expose_secret(&shares.backup.to_string()).to_string();
+        let download_backup_mock = srv
+            .mock("GET", "/api/user/shares/backup")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!("{{ \"share\":\"{backup_share}\" }}"))
+            .expect(1)
+            .create();
+        let guardians_mock = srv.mock("GET", "/api/user/shares/guardians").with_status(404).create();
+        let upload_backup_mock = srv.mock("PUT", "/api/user/shares/backup").with_status(200).create();
+        let upload_recovery_mock = srv.mock("PUT", "/api/user/shares/recovery").with_status(200).create();
+
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_user_storage_encryption(UserStorageEncryption::Pin).unwrap();
+        sdk.create_new_user(USERNAME).await.unwrap();
+        sdk.init_user(USERNAME).await.unwrap();
+        let password = PlainPassword::try_from_string("StrongP@55w0rd").unwrap();
+        sdk.set_wallet_password(&PIN, &password).await.unwrap();
+        let new_pin = EncryptionPin::try_from_string("4321").unwrap();
+        sdk.init_user(USERNAME).await.unwrap();
+        sdk.access_token = Some(TOKEN.clone());
+
+        assert!(matches!(
+            sdk.verify_pin(&new_pin).await,
+            Err(crate::Error::Wallet(WalletError::WrongPinOrPassword))
+        ));
+        assert!(matches!(
+            sdk.verify_pin(&new_pin).await,
+            Err(crate::Error::PinAttemptsLockedOut)
+        ));
+        assert!(matches!(
+            sdk.verify_pin(&PIN).await,
+            Err(crate::Error::PinAttemptsLockedOut)
+        ));
+
+        // Act
+        sdk.recover_wallet_with_mnemonic(&new_pin, &BACKUP_PASSWORD, MNEMONIC)
+            .await
+            .unwrap();
+
+        // Assert: the user is stored with the key of the new pin and unlocked by it
+        sdk.init_user(USERNAME).await.unwrap();
+        sdk.verify_pin(&new_pin).await.unwrap();
+        let user = sdk.get_user().await.unwrap();
+        assert_eq!(user.pin_attempts, PinAttempts::default());
+        assert!(user.encrypted_password.is_some());
+        assert!(user.local_share.is_some());
+        download_backup_mock.assert();
+        guardians_mock.assert();
+        upload_backup_mock.assert();
+        upload_recovery_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_change_pin_reencrypts_local_share() {
+        use secrecy::ExposeSecret;
+
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let share = crate::share::Share::mock_share();
+        let local_share = crate::share::encrypt_local_share(&share, &PIN, &SALT.into()).unwrap();
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().once().returning(move |_| {
+            Ok(UserEntity {
+                user_id: None,
+                username: USERNAME.to_string(),
+                encrypted_password: Some(ENCRYPTED_PASSWORD.clone()),
+                salt: SALT.into(),
+                is_kyc_verified: false,
+                kyc_type: KycType::Undefined,
+                viviswap_state: None,
+                local_share: Some(local_share.to_string().expose_secret().to_string()),
+                wallet_transactions: Vec::new(),
+                wallet_tx_history: Default::default(),
+                pin_attempts: Default::default(),
+                evm_address_indices: Vec::new(),
+                pending_transactions: Default::default(),
+                watched_transactions: Vec::new(),
+                watch_only: None,
+                created_at: None,
+                last_used_at: None,
+                guardian_secret_key: None,
+                guardian_recovery_key: None,
+                encrypted_passphrase: None,
+                offline_recovery_share: Some(local_share.to_string().expose_secret().to_string()),
+            })
+        });
+        let new_pin = EncryptionPin::try_from_string("4321").unwrap();
+        let new_pin_check = EncryptionPin::try_from_string("4321").unwrap();
+        mock_user_repo.expect_update().once().returning(move |user| {
+            for stored in [&user.local_share, &user.offline_recovery_share] {
+                let stored = stored.as_ref().unwrap().parse().unwrap();
+                assert_eq!(
+                    crate::share::decrypt_local_share(&stored, &new_pin_check, &user.salt).unwrap(),
+                    crate::share::Share::mock_share()
+                );
+                crate::share::decrypt_local_share(&stored, &PIN, &SALT.into()).unwrap_err();
+            }
+            Ok(())
+        });
+        sdk.repo = Some(Box::new(mock_user_repo));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk.change_pin(&PIN, &new_pin).await;
+
+        // Assert
+        response.unwrap();
+    }
+
     #[rstest]
     #[case::success(Ok(()))]
     #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
@@ -952,14 +2675,26 @@ mod tests {
                     assert_eq!(r1, USERNAME);
                     Ok(UserEntity {
                         user_id: None,
-                        username: USERNAME.into(),
+                        username: USERNAME.to_string(),
                         encrypted_password: None,
                         salt: SALT.into(),
                         is_kyc_verified: false,
                         kyc_type: KycType::Undefined,
-                        viviswap_state: Option::None,
+                        viviswap_state: None,
                         local_share: None,
                         wallet_transactions: Vec::new(),
+                        wallet_tx_history: Default::default(),
+                        pin_attempts: Default::default(),
+                        evm_address_indices: Vec::new(),
+                        pending_transactions: Default::default(),
+                        watched_transactions: Vec::new(),
+                        watch_only: None,
+                        created_at: None,
+                        last_used_at: None,
+                        guardian_secret_key: None,
+                        guardian_recovery_key: None,
+                        encrypted_passphrase: None,
+                        offline_recovery_share: None,
                     })
                 });
                 mock_user_repo.expect_update().once().returning(|_| Ok(()));
@@ -1061,6 +2796,81 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_derive_new_address_stores_next_index() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(2).returning(|_| {
+            Ok(UserEntity {
+                evm_address_indices: vec![1],
+                ..user_with_pin_attempts(PinAttempts::default())
+            })
+        });
+        mock_user_repo
+            .expect_update()
+            .once()
+            .withf(|user| user.evm_address_indices == [1, 2])
+            .returning(|_| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager.expect_try_get().once().returning(|_, _, _, _, _| {
+            let mut mock_wallet_user = MockWalletUser::new();
+            mock_wallet_user.expect_get_addresses().once().returning(|| {
+                Ok(vec![
+                    String::from("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+                    String::from("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"),
+                    String::from("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC"),
+                ])
+            });
+            Ok(WalletBorrow::from(mock_wallet_user))
+        });
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+
+        // Act
+        let response = sdk.derive_new_address(&PIN).await;
+
+        // Assert
+        assert_eq!(response.unwrap(), "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC");
+    }
+
+    #[tokio::test]
+    async fn test_derive_new_address_requires_evm_network() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo
+            .expect_get()
+            .once()
+            .returning(|_| Ok(user_with_pin_attempts(PinAttempts::default())));
+        sdk.repo = Some(Box::new(mock_user_repo));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Iota)).await.unwrap();
+
+        // Act
+        let response = sdk.derive_new_address(&PIN).await;
+
+        // Assert
+        assert!(matches!(
+            response,
+            Err(crate::Error::Wallet(WalletError::WalletFeatureNotImplemented))
+        ));
+    }
+
     #[rstest]
     // SAFETY: we know that this value is not negative
     #[case::success(Ok(unsafe { CryptoAmount::new_unchecked(dec!(25.0)) }))]
@@ -1216,4 +3026,80 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_get_wallet_tx_list_evm_scans_and_persists_history() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        let tx = |id: &str, block_id: Option<&str>, status: InclusionState| WalletTxInfo {
+            block_id: block_id.map(String::from),
+            transaction_id: id.to_string(),
+            status: format!("{status:?}"),
+            ..example_wallet_tx_info()
+        };
+
+        let confirmed = tx("0x1", Some("10"), InclusionState::Confirmed);
+        let pending = tx("0x2", None, InclusionState::Pending);
+        let synchronized = tx("0x2", Some("11"), InclusionState::Confirmed);
+
+        let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 2, KycType::Undefined);
+        let expected_history = crate::types::transactions::WalletTxHistory {
+            last_scanned_block: Some(12),
+            first_scanned_block: Some(8),
+            transactions: vec![synchronized.clone(), confirmed.clone()],
+        };
+        mock_user_repo
+            .expect_set_wallet_tx_history()
+            .once()
+            .withf(move |username, network_id, history| {
+                username == USERNAME && network_id == example_network_id(Currency::Eth) && *history == expected_history
+            })
+            .returning(|_, _, _| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let scan_confirmed = confirmed.clone();
+        let synchronized_clone = synchronized.clone();
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager.expect_try_get().returning(move |_, _, _, _, _| {
+            let mut mock_wallet_user = MockWalletUser::new();
+            let transactions = vec![scan_confirmed.clone(), pending.clone()];
+            mock_wallet_user
+                .expect_scan_wallet_tx()
+                .once()
+                .with(mockall::predicate::eq(None), mockall::predicate::eq(None))
+                .returning(move |_, _| {
+                    Ok(crate::wallet_user::WalletTxScan {
+                        transactions: transactions.clone(),
+                        last_scanned_block: Some(12),
+                        first_scanned_block: Some(8),
+                    })
+                });
+            let synchronized = synchronized_clone.clone();
+            mock_wallet_user
+                .expect_get_wallet_tx()
+                .once()
+                .withf(|tx_id| tx_id == "0x2")
+                .returning(move |_| Ok(synchronized.clone()));
+            Ok(WalletBorrow::from(mock_wallet_user))
+        });
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+
+        // Act
+        let response = sdk.get_wallet_tx_list(&PIN, 0, 10).await;
+
+        // Assert
+        assert_eq!(
+            response.unwrap(),
+            WalletTxInfoList {
+                transactions: vec![synchronized, confirmed]
+            }
+        );
+    }
 }
@@ -0,0 +1,472 @@
+//! The watch-only module provides read-only access to the wallet of the user without the pin.
+//!
+//! The watch-only wallet is created from the addresses or the extended public key stored for the user, so it can
+//! show the balance and the transaction history, e.g. on a device that does not hold the shares. It cannot sign
+//! transactions. Only EVM and ERC-20 networks are supported.
+
+use super::wallet::evm_wallet_tx_page;
+use super::Sdk;
+use crate::error::Result;
+use crate::types::currencies::CryptoAmount;
+use crate::types::networks::NetworkType;
+use crate::types::newtypes::EncryptionPin;
+use crate::types::transactions::WalletTxInfoList;
+use crate::wallet::error::WalletError;
+use crate::wallet::watch_only::WatchOnlySource;
+use crate::wallet_manager::WalletBorrow;
+use log::{debug, info};
+
+impl Sdk {
+    /// Set the source of the watch-only wallet of the user.
+    ///
+    /// The scanned transaction history of the previous source is removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    /// * `source` - The EVM addresses or the extended public key of the EVM account at `m/44'/60'/0'`. `None`
+    ///   removes the watch-only wallet.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`crate::Error::WrongPinOrPassword`] - If the pin is wrong.
+    /// * [`WalletError::EmptyWalletAddress`] - If the list of addresses is empty.
+    /// * [`WalletError::FromHexError`] - If an address is invalid.
+    /// * [`WalletError::Parse`] - If the extended public key is invalid.
+    pub async fn set_watch_only_source(&mut self, pin: &EncryptionPin, source: Option<WatchOnlySource>) -> Result<()> {
+        info!("Setting the watch-only source");
+        self.check_pin(pin).await?;
+        self.store_watch_only_source(source)
+    }
+
+    /// Validates and stores the source of the watch-only wallet, and removes the histories scanned for the previous
+    /// source.
+    fn store_watch_only_source(&mut self, source: Option<WatchOnlySource>) -> Result<()> {
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        if let Some(source) = &source {
+            source.evm_addresses(&[])?;
+        }
+
+        let mut user = repo.get(&active_user.username)?;
+        user.watch_only = source;
+        user.wallet_tx_history.retain(|key, _| !is_watch_only_history_key(key));
+        repo.update(&user)?;
+        Ok(())
+    }
+
+    /// Enable the watch-only wallet with the current EVM addresses of the wallet.
+    ///
+    /// The default address and the addresses derived with [`Sdk::derive_new_address`] are stored as the source of
+    /// the watch-only wallet. Addresses derived later have to be added by calling this function again.
+    ///
+    /// # Returns
+    ///
+    /// Returns the stored addresses.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserRepoNotInitialized`] - If there is an error initializing the repository.
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`crate::Error::MissingNetwork`] - If the network is not set.
+    /// * [`WalletError::WalletFeatureNotImplemented`] - If the selected network is not an EVM network.
+    pub async fn enable_watch_only(&mut self, pin: &EncryptionPin) -> Result<Vec<String>> {
+        info!("Enabling the watch-only wallet");
+        self.check_pin(pin).await?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        if !matches!(
+            network.network_type,
+            NetworkType::Evm { .. } | NetworkType::EvmErc20 { .. }
+        ) {
+            return Err(WalletError::WalletFeatureNotImplemented.into());
+        }
+
+        let addresses = {
+            let wallet = self.try_get_active_user_wallet(pin).await?;
+            wallet.get_addresses().await?
+        };
+
+        self.store_watch_only_source(Some(WatchOnlySource::EvmAddresses(addresses.clone())))?;
+        Ok(addresses)
+    }
+
+    /// Get the default address of the watch-only wallet.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::MissingWatchOnlySource`] - If no watch-only source is set.
+    /// * [`WalletError::WalletFeatureNotImplemented`] - If the selected network is not an EVM network.
+    pub async fn get_watch_only_address(&mut self) -> Result<String> {
+        info!("Fetching watch-only address");
+        let wallet = self.try_get_active_user_watch_only_wallet()?;
+        let address = wallet.get_address().await?;
+        debug!("Watch-only address: {address}");
+        Ok(address)
+    }
+
+    /// Get the balance of the watch-only wallet.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::MissingWatchOnlySource`] - If no watch-only source is set.
+    /// * [`WalletError::WalletFeatureNotImplemented`] - If the selected network is not an EVM network.
+    pub async fn get_watch_only_balance(&mut self) -> Result<CryptoAmount> {
+        info!("Fetching watch-only balance");
+        let wallet = self.try_get_active_user_watch_only_wallet()?;
+        let balance = wallet.get_balance().await?;
+        debug!("Watch-only balance: {balance:?}");
+        Ok(balance)
+    }
+
+    /// Get a page of the transactions of the watch-only wallet.
+    ///
+    /// The network is scanned for new transfers the same way as in [`Sdk::get_wallet_tx_list`]. The scanned history
+    /// is kept apart from the history of the wallet and stored per network and watched addresses, so a changed
+    /// source is scanned from the start.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The page of transactions to fetch.
+    /// * `limit` - The number of transactions per page.
+    ///
+    /// # Errors
+    ///
+    /// * [`crate::Error::UserNotInitialized`] - If there is an error initializing the user.
+    /// * [`WalletError::MissingWatchOnlySource`] - If no watch-only source is set.
+    /// * [`WalletError::WalletFeatureNotImplemented`] - If the selected network is not an EVM network.
+    pub async fn get_watch_only_wallet_tx_list(&mut self, start: usize, limit: usize) -> Result<WalletTxInfoList> {
+        info!("Watch-only wallet getting list of transactions");
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        let user = self.get_user().await?;
+        let username = user.username.clone();
+
+        let (transactions, history_key, history) = {
+            let wallet = self.try_get_active_user_watch_only_wallet()?;
+            let history_key = watch_only_history_key(&network.id, &wallet.get_addresses().await?);
+            let history = user.wallet_tx_history.get(&history_key).cloned().unwrap_or_default();
+            let (transactions, history) = evm_wallet_tx_page(&**wallet, history, Vec::new(), start, limit).await;
+            (transactions, history_key, history)
+        };
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let _ = repo.set_wallet_tx_history(&username, &history_key, history);
+
+        Ok(WalletTxInfoList { transactions })
+    }
+
+    /// Tries to get the watch-only wallet of the currently active user.
+    fn try_get_active_user_watch_only_wallet(&mut self) -> Result<WalletBorrow<'_>> {
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        Ok(active_user.wallet_manager.try_get_watch_only(repo, network)?)
+    }
+}
+
+/// Prefix of the keys of the scanned transaction histories of watch-only wallets.
+const WATCH_ONLY_HISTORY_PREFIX: &str = "watch-only";
+
+/// The key of the scanned transaction history of the watch-only wallet with the given addresses on a network. It
+/// never matches a network id, so the scan cursor of the watch-only wallet is not shared with the wallet.
+fn watch_only_history_key(network_id: &str, addresses: &[String]) -> String {
+    format!("{WATCH_ONLY_HISTORY_PREFIX}:{network_id}:{}", addresses.join(","))
+}
+
+fn is_watch_only_history_key(key: &str) -> bool {
+    key.starts_with(&format!("{WATCH_ONLY_HISTORY_PREFIX}:"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::core_testing_utils::handle_error_test_cases;
+    use crate::testing_utils::{
+        example_get_user, example_network_id, example_networks, example_wallet_tx_info, set_config, ENCRYPTED_PASSWORD,
+        PIN, SALT, USERNAME,
+    };
+    use crate::types::currencies::Currency;
+    use crate::types::transactions::{WalletTxHistory, WalletTxInfo};
+    use crate::types::users::{KycType, UserEntity};
+    use crate::{
+        core::Sdk,
+        user::MockUserRepo,
+        wallet_manager::{MockWalletManager, WalletBorrow},
+        wallet_user::{MockWalletUser, WalletTxScan},
+    };
+    use api_types::api::viviswap::detail::SwapPaymentDetailKey;
+    use iota_sdk::wallet::account::types::InclusionState;
+    use rstest::rstest;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    const ADDRESS: &str = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+
+    #[rstest]
+    #[case::success(Ok(()))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::pin_locked_out(Err(crate::Error::PinAttemptsLockedOut))]
+    #[tokio::test]
+    async fn test_set_watch_only_source(#[case] expected: Result<()>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        let source = WatchOnlySource::EvmAddresses(vec![ADDRESS.to_string()]);
+
+        match &expected {
+            Ok(_) => {
+                let network_id = example_network_id(Currency::Eth);
+                let old_history_key = watch_only_history_key(&network_id, &["0x123".to_string()]);
+                let user = UserEntity {
+                    user_id: None,
+                    username: USERNAME.to_string(),
+                    encrypted_password: Some(ENCRYPTED_PASSWORD.clone()),
+                    salt: SALT.into(),
+                    is_kyc_verified: false,
+                    kyc_type: KycType::Undefined,
+                    viviswap_state: None,
+                    local_share: None,
+                    wallet_transactions: Vec::new(),
+                    wallet_tx_history: HashMap::from([
+                        (network_id.clone(), WalletTxHistory::default()),
+                        (old_history_key, WalletTxHistory::default()),
+                    ]),
+                    pin_attempts: Default::default(),
+                    evm_address_indices: Vec::new(),
+                    pending_transactions: Default::default(),
+                    watched_transactions: Vec::new(),
+                    watch_only: None,
+                    created_at: None,
+                    last_used_at: None,
+                    guardian_secret_key: None,
+                    guardian_recovery_key: None,
+                    encrypted_passphrase: None,
+                    offline_recovery_share: None,
+                };
+
+                let mut mock_user_repo = MockUserRepo::new();
+                mock_user_repo
+                    .expect_get()
+                    .times(2)
+                    .returning(move |_| Ok(user.clone()));
+                let expected_source = Some(source.clone());
+                mock_user_repo
+                    .expect_update()
+                    .once()
+                    .withf(move |user| {
+                        // the history of the wallet is kept, the one of the previous source is removed
+                        user.watch_only == expected_source
+                            && user.wallet_tx_history.keys().collect::<Vec<_>>() == vec![&network_id]
+                    })
+                    .returning(|_| Ok(()));
+                sdk.repo = Some(Box::new(mock_user_repo));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(MockWalletManager::new()),
+                });
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.set_watch_only_source(&PIN, Some(source)).await;
+
+        // Assert
+        match expected {
+            Ok(()) => response.unwrap(),
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_watch_only_source_rejects_invalid_source() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        // the user is only read to check the pin, so no update is expected
+        sdk.repo = Some(Box::new(example_get_user(
+            SwapPaymentDetailKey::Iota,
+            false,
+            1,
+            KycType::Undefined,
+        )));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(MockWalletManager::new()),
+        });
+
+        // Act
+        let response = sdk
+            .set_watch_only_source(&PIN, Some(WatchOnlySource::EvmAccountXpub("xpub123".to_string())))
+            .await;
+
+        // Assert
+        assert!(matches!(response, Err(crate::Error::Wallet(WalletError::Parse(_)))));
+    }
+
+    #[rstest]
+    // SAFETY: we know that this value is not negative
+    #[case::success(Ok(unsafe { CryptoAmount::new_unchecked(dec!(25.0)) }))]
+    #[case::repo_init_error(Err(crate::Error::UserRepoNotInitialized))]
+    #[case::user_init_error(Err(crate::Error::UserNotInitialized))]
+    #[case::missing_network(Err(crate::Error::MissingNetwork))]
+    #[case::missing_source(Err(crate::Error::Wallet(WalletError::MissingWatchOnlySource)))]
+    #[tokio::test]
+    async fn test_get_watch_only_balance(#[case] expected: Result<CryptoAmount>) {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+
+        match &expected {
+            Ok(_) => {
+                sdk.repo = Some(Box::new(MockUserRepo::new()));
+
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_try_get_watch_only()
+                    .once()
+                    .returning(|_, _| {
+                        let mut mock_wallet_user = MockWalletUser::new();
+                        mock_wallet_user
+                            .expect_get_balance()
+                            .once()
+                            // SAFETY: we know that this value is not negative
+                            .returning(|| Ok(unsafe { CryptoAmount::new_unchecked(dec!(25.0)) }));
+                        Ok(WalletBorrow::from(mock_wallet_user))
+                    });
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+                sdk.set_networks(example_networks());
+                sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+            }
+            Err(crate::Error::Wallet(WalletError::MissingWatchOnlySource)) => {
+                sdk.repo = Some(Box::new(MockUserRepo::new()));
+
+                let mut mock_wallet_manager = MockWalletManager::new();
+                mock_wallet_manager
+                    .expect_try_get_watch_only()
+                    .once()
+                    .returning(|_, _| Err(WalletError::MissingWatchOnlySource));
+                sdk.active_user = Some(crate::types::users::ActiveUser {
+                    username: USERNAME.into(),
+                    wallet_manager: Box::new(mock_wallet_manager),
+                });
+                sdk.set_networks(example_networks());
+                sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+            }
+            Err(error) => {
+                handle_error_test_cases(error, &mut sdk, 0, 0).await;
+            }
+        }
+
+        // Act
+        let response = sdk.get_watch_only_balance().await;
+
+        // Assert
+        match expected {
+            Ok(resp) => {
+                assert_eq!(response.unwrap(), resp);
+            }
+            Err(ref expected_err) => {
+                assert_eq!(response.err().unwrap().to_string(), expected_err.to_string());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_watch_only_wallet_tx_list_scans_and_persists_history() {
+        // Arrange
+        let (_srv, config, _cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        let history_key = watch_only_history_key(&example_network_id(Currency::Eth), &[ADDRESS.to_string()]);
+
+        // confirmed transactions are not synchronized again
+        let transaction = WalletTxInfo {
+            status: format!("{:?}", InclusionState::Confirmed),
+            ..example_wallet_tx_info()
+        };
+        let mut mock_user_repo = example_get_user(SwapPaymentDetailKey::Iota, false, 1, KycType::Undefined);
+        let expected_history = WalletTxHistory {
+            last_scanned_block: Some(12),
+            first_scanned_block: Some(8),
+            transactions: vec![transaction.clone()],
+        };
+        mock_user_repo
+            .expect_set_wallet_tx_history()
+            .once()
+            .withf(move |username, key, history| {
+                // the history is not stored under the network id, which holds the history of the wallet
+                username == USERNAME && key == history_key && *history == expected_history
+            })
+            .returning(|_, _, _| Ok(()));
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let scanned = transaction.clone();
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_try_get_watch_only()
+            .once()
+            .returning(move |_, _| {
+                let mut mock_wallet_user = MockWalletUser::new();
+                mock_wallet_user
+                    .expect_get_addresses()
+                    .once()
+                    .returning(|| Ok(vec![ADDRESS.to_string()]));
+                let transactions = vec![scanned.clone()];
+                mock_wallet_user.expect_scan_wallet_tx().once().returning(move |_, _| {
+                    Ok(WalletTxScan {
+                        transactions: transactions.clone(),
+                        last_scanned_block: Some(12),
+                        first_scanned_block: Some(8),
+                    })
+                });
+                Ok(WalletBorrow::from(mock_wallet_user))
+            });
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+
+        // Act
+        let response = sdk.get_watch_only_wallet_tx_list(0, 10).await;
+
+        // Assert
+        assert_eq!(response.unwrap().transactions, vec![transaction]);
+    }
+
+    #[test]
+    fn test_watch_only_history_key_is_separate_per_source() {
+        let network_id = example_network_id(Currency::Eth);
+        let key = watch_only_history_key(&network_id, &[ADDRESS.to_string()]);
+        let other_key = watch_only_history_key(&network_id, &[ADDRESS.to_string(), "0x123".to_string()]);
+
+        assert_ne!(key, network_id);
+        assert_ne!(key, other_key);
+        assert!(is_watch_only_history_key(&key));
+        assert!(!is_watch_only_history_key(&network_id));
+    }
+}
@@ -0,0 +1,516 @@
+use super::{Sdk, UserRepoT};
+use crate::core::transaction::{get_tracked_transactions, prune_included_transactions};
+use crate::error::Result;
+use crate::types::networks::{Network, NetworkType};
+use crate::types::newtypes::EncryptionPin;
+use crate::types::transactions::{WalletTxInfo, WatchedTransaction, WatchedTransactionStatus};
+use crate::types::users::UserEntity;
+use crate::wallet_user::TransactionConfirmation;
+use iota_sdk::wallet::account::types::InclusionState;
+use log::{debug, info, warn};
+use std::collections::HashSet;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+/// Callback receiving the status changes of watched transactions
+pub type TransactionStatusCallback = Box<dyn Fn(&WatchedTransaction) + Send + Sync>;
+
+/// The number of status changes kept for a [`TransactionStatusStream`] that is not read. Older ones are skipped.
+pub(crate) const TRANSACTION_STATUS_CAPACITY: usize = 64;
+
+/// Stream of the status changes of watched transactions, see [`Sdk::subscribe_transaction_status`]
+pub struct TransactionStatusStream {
+    receiver: Receiver<WatchedTransaction>,
+}
+
+impl TransactionStatusStream {
+    /// Wait for the next status change of a watched transaction
+    ///
+    /// If the stream is not read fast enough, the oldest status changes are skipped. The last known status of all
+    /// watched transactions is available with [`Sdk::get_watched_transactions`].
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated transaction, or `None` if the [`Sdk`] was dropped.
+    pub async fn recv(&mut self) -> Option<WatchedTransaction> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(transaction) => return Some(transaction),
+                Err(RecvError::Lagged(skipped)) => warn!("Skipped {skipped} transaction status changes"),
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+impl Sdk {
+    /// Set the callback receiving the status changes of watched transactions
+    ///
+    /// The callback is called by [`Sdk::check_watched_transactions`] for every watched transaction whose status
+    /// or number of confirmations changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - The callback to call with the updated transaction.
+    pub fn set_transaction_status_callback(&mut self, callback: impl Fn(&WatchedTransaction) + Send + Sync + 'static) {
+        self.transaction_status_callback = Some(Box::new(callback));
+    }
+
+    /// Subscribe to the status changes of watched transactions
+    ///
+    /// The stream receives every watched transaction whose status or number of confirmations changed in
+    /// [`Sdk::check_watched_transactions`] after subscribing. Unlike the callback, any number of streams can be
+    /// subscribed and they can be read from another task, which is how the bindings deliver the changes to the UI.
+    ///
+    /// # Returns
+    ///
+    /// Returns the stream of status changes.
+    pub fn subscribe_transaction_status(&self) -> TransactionStatusStream {
+        TransactionStatusStream {
+            receiver: self.transaction_status_sender.subscribe(),
+        }
+    }
+
+    /// Get the transactions followed by the transaction watcher
+    ///
+    /// Transactions sent with [`Sdk::send_amount`] or [`Sdk::confirm_purchase_request`] are watched until they
+    /// are finalized or failed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the watched transactions of all networks with their last known status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user is not initialized.
+    pub async fn get_watched_transactions(&self) -> Result<Vec<WatchedTransaction>> {
+        let user = self.get_user().await?;
+        Ok(user.watched_transactions)
+    }
+
+    /// Check the status of the watched transactions of the current network
+    ///
+    /// EVM transactions are finalized once they have the number of confirmations set in
+    /// [`crate::core::Config::evm_confirmations`], Stardust transactions as soon as they are confirmed. Finalized,
+    /// failed and replaced transactions are no longer watched. A transaction is replaced once another transaction
+    /// with its nonce is included, e.g. after [`Sdk::speed_up_transaction`] or [`Sdk::cancel_transaction`]. The
+    /// wallet transactions of the user are updated for every status change, the callback set with
+    /// [`Sdk::set_transaction_status_callback`] is called and the change is sent to the streams of
+    /// [`Sdk::subscribe_transaction_status`]. Transactions of other networks are checked once their network is
+    /// selected.
+    ///
+    /// Call this function periodically, e.g. every few seconds while there are watched transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - The PIN of the user.
+    ///
+    /// # Returns
+    ///
+    /// Returns the watched transactions whose status or number of confirmations changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user or wallet is not initialized, or if there is an error verifying the PIN.
+    /// Failing to check a single transaction is only logged.
+    pub async fn check_watched_transactions(&mut self, pin: &EncryptionPin) -> Result<Vec<WatchedTransaction>> {
+        info!("Checking watched transactions");
+        self.check_pin(pin).await?;
+
+        let Some(repo) = &mut self.repo else {
+            return Err(crate::Error::UserRepoNotInitialized);
+        };
+        let Some(active_user) = &mut self.active_user else {
+            return Err(crate::Error::UserNotInitialized);
+        };
+
+        let config = self.config.as_mut().ok_or(crate::Error::MissingConfig)?;
+        let network = self.network.clone().ok_or(crate::Error::MissingNetwork)?;
+        let required_confirmations = config.evm_confirmations;
+
+        let wallet = active_user
+            .wallet_manager
+            .try_get(config, &self.access_token, repo, network.clone(), pin)
+            .await?;
+
+        // Transactions that lost against a replacement with the same nonce never get included. They are detected
+        // by the nonce of the sender being used while they are still pending. The replaced originals of a speed-up
+        // or cancel transaction are no longer tracked, but listed by their replacement.
+        let mut replaced = HashSet::new();
+        if matches!(
+            network.network_type,
+            NetworkType::Evm { .. } | NetworkType::EvmErc20 { .. }
+        ) {
+            let tracked = get_tracked_transactions(repo, &active_user.username, &network.id)?;
+            if !tracked.is_empty() {
+                let remaining = prune_included_transactions(&**wallet, tracked.clone()).await?;
+                replaced.extend(
+                    tracked
+                        .into_iter()
+                        .filter(|tx| !remaining.contains(tx))
+                        .flat_map(|tx| tx.replaces.into_iter().chain([tx.transaction_hash])),
+                );
+                repo.set_pending_transactions(&active_user.username, &network.id, remaining)?;
+            }
+        }
+
+        let mut user = repo.get(&active_user.username)?;
+        let mut updates = Vec::new();
+        for watched in user
+            .watched_transactions
+            .iter_mut()
+            .filter(|tx| tx.network_id == network.id)
+        {
+            let confirmation = match wallet.get_transaction_confirmation(&watched.transaction_hash).await {
+                Ok(confirmation) => confirmation,
+                Err(e) => {
+                    warn!("Could not check watched transaction {}: {e}", watched.transaction_hash);
+                    continue;
+                }
+            };
+
+            let mut status = watched_status(&confirmation, required_confirmations);
+            if status == WatchedTransactionStatus::Pending && replaced.contains(&watched.transaction_hash) {
+                status = WatchedTransactionStatus::Replaced;
+            }
+            let confirmations = confirmation
+                .confirmations
+                .unwrap_or(u64::from(confirmation.inclusion_state == InclusionState::Confirmed));
+
+            if status == watched.status && confirmations == watched.confirmations {
+                continue;
+            }
+
+            debug!(
+                "Watched transaction {} changed to {status:?} with {confirmations} confirmations",
+                watched.transaction_hash
+            );
+            watched.status = status;
+            watched.confirmations = confirmations;
+            updates.push(watched.clone());
+        }
+
+        if updates.is_empty() {
+            return Ok(updates);
+        }
+
+        // the account of the stardust wallet keeps its transactions itself
+        if !matches!(network.network_type, NetworkType::Stardust { .. }) {
+            for update in updates.iter().filter(|tx| {
+                !matches!(
+                    tx.status,
+                    WatchedTransactionStatus::Failed | WatchedTransactionStatus::Replaced
+                )
+            }) {
+                match wallet.get_wallet_tx(&update.transaction_hash).await {
+                    Ok(transaction) => store_wallet_transaction(&mut user, &network, transaction),
+                    Err(e) => warn!("Could not get watched transaction {}: {e}", update.transaction_hash),
+                }
+            }
+        }
+
+        user.watched_transactions.retain(|tx| {
+            !matches!(
+                tx.status,
+                WatchedTransactionStatus::Finalized
+                    | WatchedTransactionStatus::Failed
+                    | WatchedTransactionStatus::Replaced
+            )
+        });
+        repo.update(&user)?;
+
+        for update in &updates {
+            if let Some(callback) = &self.transaction_status_callback {
+                callback(update);
+            }
+            // there might be no subscribed stream
+            let _ = self.transaction_status_sender.send(update.clone());
+        }
+
+        Ok(updates)
+    }
+}
+
+/// Add a submitted transaction to the transactions followed by the transaction watcher.
+pub(crate) fn watch_transaction(
+    repo: &mut UserRepoT,
+    username: &str,
+    network_id: &str,
+    transaction_hash: &str,
+) -> Result<()> {
+    let mut user = repo.get(username)?;
+    user.watched_transactions.push(WatchedTransaction {
+        transaction_hash: transaction_hash.to_string(),
+        network_id: network_id.to_string(),
+        status: WatchedTransactionStatus::Pending,
+        confirmations: 0,
+    });
+    repo.set_watched_transactions(username, user.watched_transactions)?;
+    Ok(())
+}
+
+/// Map the inclusion of a transaction to the status of the watcher.
+fn watched_status(confirmation: &TransactionConfirmation, required_confirmations: u64) -> WatchedTransactionStatus {
+    match confirmation.inclusion_state {
+        InclusionState::Pending => WatchedTransactionStatus::Pending,
+        InclusionState::Confirmed => match confirmation.confirmations {
+            Some(confirmations) if confirmations < required_confirmations => WatchedTransactionStatus::Included,
+            _ => WatchedTransactionStatus::Finalized,
+        },
+        InclusionState::Conflicting | InclusionState::UnknownPruned => WatchedTransactionStatus::Failed,
+    }
+}
+
+/// Store the latest version of a wallet transaction where the transactions of the network are kept.
+fn store_wallet_transaction(user: &mut UserEntity, network: &Network, transaction: WalletTxInfo) {
+    match network.network_type {
+        NetworkType::EvmErc20 { .. } => {
+            user.wallet_tx_history
+                .entry(network.id.clone())
+                .or_default()
+                .merge([transaction]);
+        }
+        _ => {
+            match user
+                .wallet_transactions
+                .iter_mut()
+                .find(|t| t.transaction_id == transaction.transaction_id)
+            {
+                Some(existing) => *existing = transaction,
+                None => user.wallet_transactions.push(transaction),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_utils::{
+        example_network_id, example_networks, example_wallet_tx_info, set_config, ENCRYPTED_PASSWORD, PIN, SALT,
+        USERNAME,
+    };
+    use crate::types::currencies::Currency;
+    use crate::types::transactions::PendingTransaction;
+    use crate::types::users::KycType;
+    use crate::{
+        user::MockUserRepo,
+        wallet_manager::{MockWalletManager, WalletBorrow},
+        wallet_user::MockWalletUser,
+    };
+    use rstest::rstest;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn watched(transaction_hash: &str, status: WatchedTransactionStatus, confirmations: u64) -> WatchedTransaction {
+        WatchedTransaction {
+            transaction_hash: transaction_hash.to_string(),
+            network_id: example_network_id(Currency::Eth),
+            status,
+            confirmations,
+        }
+    }
+
+    fn pending(transaction_hash: &str, nonce: u64) -> PendingTransaction {
+        PendingTransaction {
+            transaction_hash: transaction_hash.to_string(),
+            from: String::from("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            raw_transaction: Vec::new(),
+            created_at: 0,
+            replaces: Vec::new(),
+        }
+    }
+
+    fn user(
+        watched_transactions: Vec<WatchedTransaction>,
+        pending_transactions: Vec<PendingTransaction>,
+    ) -> UserEntity {
+        UserEntity {
+            user_id: None,
+            username: USERNAME.to_string(),
+            encrypted_password: Some(ENCRYPTED_PASSWORD.clone()),
+            salt: SALT.into(),
+            is_kyc_verified: false,
+            kyc_type: KycType::Undefined,
+            viviswap_state: None,
+            local_share: None,
+            wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: HashMap::from([(example_network_id(Currency::Eth), pending_transactions)]),
+            watched_transactions,
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
+        }
+    }
+
+    async fn sdk_on_eth(mock_user_repo: MockUserRepo, mock_wallet: MockWalletUser) -> (Sdk, testing::CleanUp) {
+        let (_srv, config, cleanup) = set_config().await;
+        let mut sdk = Sdk::new(config).unwrap();
+        sdk.set_networks(example_networks());
+        sdk.set_network(example_network_id(Currency::Eth)).await.unwrap();
+        sdk.repo = Some(Box::new(mock_user_repo));
+
+        let mut mock_wallet = Some(mock_wallet);
+        let mut mock_wallet_manager = MockWalletManager::new();
+        mock_wallet_manager
+            .expect_try_get()
+            .once()
+            .returning(move |_, _, _, _, _| Ok(WalletBorrow::from(mock_wallet.take().unwrap())));
+        sdk.active_user = Some(crate::types::users::ActiveUser {
+            username: USERNAME.into(),
+            wallet_manager: Box::new(mock_wallet_manager),
+        });
+
+        (sdk, cleanup)
+    }
+
+    #[rstest]
+    #[case::pending(InclusionState::Pending, Some(0), WatchedTransactionStatus::Pending)]
+    #[case::included(InclusionState::Confirmed, Some(11), WatchedTransactionStatus::Included)]
+    #[case::enough_confirmations(InclusionState::Confirmed, Some(12), WatchedTransactionStatus::Finalized)]
+    #[case::immediate_finality(InclusionState::Confirmed, None, WatchedTransactionStatus::Finalized)]
+    #[case::conflicting(InclusionState::Conflicting, Some(3), WatchedTransactionStatus::Failed)]
+    #[case::pruned(InclusionState::UnknownPruned, None, WatchedTransactionStatus::Failed)]
+    fn test_watched_status(
+        #[case] inclusion_state: InclusionState,
+        #[case] confirmations: Option<u64>,
+        #[case] expected: WatchedTransactionStatus,
+    ) {
+        // Arrange
+        let confirmation = TransactionConfirmation {
+            inclusion_state,
+            confirmations,
+        };
+
+        // Act
+        let status = watched_status(&confirmation, 12);
+
+        // Assert
+        assert_eq!(status, expected);
+    }
+
+    #[tokio::test]
+    async fn test_check_watched_transactions_finalizes_and_stores_transaction() {
+        // Arrange
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(3).returning(|_| {
+            Ok(user(
+                vec![watched("0x01", WatchedTransactionStatus::Pending, 0)],
+                Vec::new(),
+            ))
+        });
+        mock_user_repo
+            .expect_update()
+            .once()
+            .withf(|user| {
+                user.watched_transactions.is_empty()
+                    && user.wallet_transactions.len() == 1
+                    && user.wallet_transactions[0].transaction_id == "0x01"
+            })
+            .returning(|_| Ok(()));
+
+        let mut mock_wallet = MockWalletUser::new();
+        mock_wallet.expect_get_transaction_confirmation().once().returning(|_| {
+            Ok(TransactionConfirmation {
+                inclusion_state: InclusionState::Confirmed,
+                confirmations: Some(12),
+            })
+        });
+        mock_wallet.expect_get_wallet_tx().once().returning(|tx_id| {
+            Ok(WalletTxInfo {
+                transaction_id: tx_id.to_string(),
+                ..example_wallet_tx_info()
+            })
+        });
+
+        let (mut sdk, _cleanup) = sdk_on_eth(mock_user_repo, mock_wallet).await;
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        sdk.set_transaction_status_callback(move |tx| received_clone.lock().unwrap().push(tx.clone()));
+
+        // Act
+        let updates = sdk.check_watched_transactions(&PIN).await.unwrap();
+
+        // Assert
+        let expected = vec![watched("0x01", WatchedTransactionStatus::Finalized, 12)];
+        assert_eq!(updates, expected);
+        assert_eq!(*received.lock().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_check_watched_transactions_marks_replaced_transaction() {
+        // Arrange: 0x01 was sped up by 0x02, which is included
+        let mut mock_user_repo = MockUserRepo::new();
+        mock_user_repo.expect_get().times(3).returning(|_| {
+            Ok(user(
+                vec![
+                    watched("0x01", WatchedTransactionStatus::Pending, 0),
+                    watched("0x02", WatchedTransactionStatus::Pending, 0),
+                ],
+                vec![PendingTransaction {
+                    replaces: vec![String::from("0x01")],
+                    ..pending("0x02", 4)
+                }],
+            ))
+        });
+        mock_user_repo
+            .expect_set_pending_transactions()
+            .once()
+            .withf(|_, _, pending| pending.is_empty())
+            .returning(|_, _, _| Ok(()));
+        mock_user_repo
+            .expect_update()
+            .once()
+            .withf(|user| user.watched_transactions == [watched("0x02", WatchedTransactionStatus::Included, 1)])
+            .returning(|_| Ok(()));
+
+        let mut mock_wallet = MockWalletUser::new();
+        mock_wallet.expect_get_confirmed_nonce().once().returning(|_| Ok(5));
+        mock_wallet
+            .expect_get_transaction_confirmation()
+            .times(2)
+            .returning(|tx_id| {
+                Ok(match tx_id {
+                    "0x01" => TransactionConfirmation {
+                        inclusion_state: InclusionState::Pending,
+                        confirmations: Some(0),
+                    },
+                    _ => TransactionConfirmation {
+                        inclusion_state: InclusionState::Confirmed,
+                        confirmations: Some(1),
+                    },
+                })
+            });
+        mock_wallet
+            .expect_get_wallet_tx()
+            .once()
+            .withf(|tx_id| tx_id == "0x02")
+            .returning(|_| Ok(example_wallet_tx_info()));
+
+        let (mut sdk, _cleanup) = sdk_on_eth(mock_user_repo, mock_wallet).await;
+        let mut stream = sdk.subscribe_transaction_status();
+
+        // Act
+        let updates = sdk.check_watched_transactions(&PIN).await.unwrap();
+
+        // Assert
+        let expected = vec![
+            watched("0x01", WatchedTransactionStatus::Replaced, 0),
+            watched("0x02", WatchedTransactionStatus::Included, 1),
+        ];
+        assert_eq!(updates, expected);
+        assert_eq!(stream.recv().await, Some(expected[0].clone()));
+        assert_eq!(stream.recv().await, Some(expected[1].clone()));
+    }
+}
@@ -47,6 +47,18 @@ pub enum Error {
     #[error("User repositry is not initialized")]
     UserRepoNotInitialized,
 
+    /// Error occurs if the pin was entered wrong too many times and the user has to wait before trying again
+    #[error("Too many wrong pin attempts. Try again in {retry_after_secs} seconds.")]
+    #[serde(rename_all = "camelCase")]
+    PinAttemptsBackoff {
+        /// Remaining time in seconds until the pin can be entered again
+        retry_after_secs: u64,
+    },
+
+    /// Error occurs if the maximum number of wrong pin attempts is reached and the wallet is locked
+    #[error("Too many wrong pin attempts. The wallet is locked and needs to be recovered with the mnemonic.")]
+    PinAttemptsLockedOut,
+
     /// Error caused by conversions to/from Decimal and f64
     #[error("Decimal error: {0}")]
     #[serde(serialize_with = "display_string")]
@@ -89,7 +101,6 @@ pub enum Error {
 
     /// Error occurs in sdk backend (api)
     #[error("BackendApi error: {0}")]
-    #[serde(serialize_with = "debug_string")]
     BackendApi(#[from] ApiError),
 
     /// Error occurs in sdk types
@@ -108,12 +119,76 @@ pub enum Error {
     Viviswap(ViviswapError),
 }
 
+impl Error {
+    /// Stable code of the error, which client apps can branch on instead of the error message.
+    ///
+    /// Errors of the backend have their own codes, see [`ApiError::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingConfig => "MISSING_CONFIG",
+            Self::MissingNetwork => "MISSING_NETWORK",
+            Self::NetworkUnavailable(_) => "NETWORK_UNAVAILABLE",
+            Self::EmptyChainIdForEvm => "EMPTY_CHAIN_ID_FOR_EVM",
+            Self::SetConfig(_) => "SET_CONFIG",
+            Self::UserNotInitialized => "USER_NOT_INITIALIZED",
+            Self::UserRepoNotInitialized => "USER_REPO_NOT_INITIALIZED",
+            Self::PinAttemptsBackoff { .. } => "PIN_ATTEMPTS_BACKOFF",
+            Self::PinAttemptsLockedOut => "PIN_ATTEMPTS_LOCKED_OUT",
+            Self::Decimal(_) => "DECIMAL",
+            Self::UserRepository(_) => "USER_REPOSITORY",
+            Self::UserAlreadyKycVerified => "USER_ALREADY_KYC_VERIFIED",
+            Self::MissingAccessToken => "MISSING_ACCESS_TOKEN",
+            Self::ChainIdNotDefined => "CHAIN_ID_NOT_DEFINED",
+            Self::ParseChainIdError(_) => "PARSE_CHAIN_ID",
+            Self::Parse(_) => "PARSE",
+            Self::NegativeAmount => "NEGATIVE_AMOUNT",
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::LoggerInit(_) => "LOGGER_INIT",
+            Self::BackendApi(e) => e.code(),
+            Self::Type(_) => "TYPE",
+            Self::Wallet(WalletError::WrongPinOrPassword) => "WRONG_PIN_OR_PASSWORD",
+            Self::Wallet(WalletError::WalletNotInitialized(_)) => "WALLET_NOT_INITIALIZED",
+            Self::Wallet(WalletError::InsufficientBalance(_)) => "INSUFFICIENT_BALANCE",
+            Self::Wallet(WalletError::Mnemonic(_)) => "INVALID_MNEMONIC",
+            Self::Wallet(WalletError::MnemonicDoesNotMatchWallet) => "MNEMONIC_DOES_NOT_MATCH_WALLET",
+            Self::Wallet(WalletError::MnemonicNotVerifiable) => "MNEMONIC_NOT_VERIFIABLE",
+            Self::Wallet(WalletError::GuardianRecoveryFingerprintMismatch) => "GUARDIAN_RECOVERY_FINGERPRINT_MISMATCH",
+            Self::Wallet(_) => "WALLET",
+            Self::Viviswap(_) => "VIVISWAP",
+        }
+    }
+
+    /// Formats the error for the bindings as JSON object with the [`Error::code`], the error message and
+    /// the serialized error, e.g. `{"code":"MISSING_CONFIG","message":"No config is set. ...","error":{...}}`.
+    pub fn to_binding_string(&self) -> String {
+        #[derive(Serialize)]
+        struct BindingError<'a> {
+            code: &'static str,
+            message: String,
+            error: &'a Error,
+        }
+
+        let error = BindingError {
+            code: self.code(),
+            message: self.to_string(),
+            error: self,
+        };
+        serde_json::to_string(&error).unwrap_or_else(|_| format!("{self:#?}"))
+    }
+}
+
 impl From<rust_decimal::Error> for Error {
     fn from(value: rust_decimal::Error) -> Self {
         Self::Decimal(value)
     }
 }
 
+impl From<crate::mnemonic::MnemonicError> for Error {
+    fn from(value: crate::mnemonic::MnemonicError) -> Self {
+        Self::Wallet(WalletError::Mnemonic(value))
+    }
+}
+
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         error_chain_fmt(self, f)
@@ -131,8 +206,8 @@ fn error_chain_fmt(e: &impl std::error::Error, f: &mut std::fmt::Formatter<'_>)
     Ok(())
 }
 
-/// Use this to serialize Error variants that implements Debug but not Serialize
-fn display_string<T, S>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
+/// Use this to serialize Error variants that implements Display but not Serialize
+pub(crate) fn display_string<T, S>(value: &T, serializer: S) -> std::result::Result<S::Ok, S::Error>
 where
     T: Display,
     S: Serializer,
@@ -5,13 +5,15 @@
 pub mod core;
 pub mod types;
 
+// pub module to plug in a custom user storage backend
+pub mod user;
+
 // expose error and result types in the crate root
 pub mod error;
 pub use error::{Error, Result};
 
 // internal modules
 mod backend;
-mod user;
 mod wallet;
 pub use wallet::error::{ErrorKind, WalletError};
 
@@ -62,10 +62,19 @@ pub const PAYMENT_METHOD_KEY_SERIALIZED: &str = "IOTA";
 pub const PAYMENT_METHOD_ID: &str = "payment-method-id";
 pub const PAYMENT_DETAIL_ID: &str = "payment-detail-id";
 pub const CASE_ID: &str = "123";
+/// `StrongP@55w0rd` encrypted with [`PIN`] and [`SALT`] using the Argon2id format.
 pub static ENCRYPTED_PASSWORD: LazyLock<EncryptedPassword> = LazyLock::new(|| unsafe {
     EncryptedPassword::new_unchecked([
-        129, 21, 50, 74, 5, 69, 194, 252, 21, 186, 201, 175, 4, 80, 139, 188, 109, 5, 7, 17, 51, 134, 232, 183, 130,
-        121, 61, 192, 12, 13,
+        69, 80, 87, 1, 0, 76, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 7, 19, 88, 201, 33, 150, 4, 61, 240, 12, 99, 173, 126, 32,
+        254, 191, 209, 188, 233, 113, 166, 105, 71, 43, 117, 95, 210, 239, 33, 130, 215, 95, 96, 15, 79, 15, 63, 92,
+        36, 27, 251, 253,
+    ])
+});
+/// `StrongP@55w0rd` encrypted with [`PIN`] and [`SALT`] using the legacy Blake2b256 scheme.
+pub static LEGACY_ENCRYPTED_PASSWORD: LazyLock<EncryptedPassword> = LazyLock::new(|| unsafe {
+    EncryptedPassword::new_unchecked([
+        69, 80, 87, 0, 129, 21, 50, 74, 5, 69, 194, 252, 21, 186, 201, 175, 4, 80, 139, 188, 109, 5, 7, 17, 51, 134,
+        232, 183, 130, 121, 61, 192, 12, 13,
     ])
 });
 pub static BACKUP_PASSWORD: LazyLock<PlainPassword> =
@@ -141,6 +150,18 @@ pub fn example_get_user(key: SwapPaymentDetailKey, verified: bool, times: usize,
             }),
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         })
     });
     mock_user_repo
@@ -237,6 +258,24 @@ pub fn example_network(currency: Currency) -> Network {
     }
 }
 
+pub fn example_erc20_api_network() -> ApiNetwork {
+    ApiNetwork {
+        id: String::from("67a2080ddf55756bae21e7f6"),
+        name: String::from("USDC Sepolia"),
+        currency: String::from("USDC"),
+        block_explorer_url: String::from("https://sepolia.explorer.mode.network"),
+        enabled: true,
+        network_identifier: Some(String::from("usdc_sepolia")),
+        network_type: ApiNetworkType::EvmErc20 {
+            node_urls: vec![String::from("https://sepolia.mode.network")],
+            chain_id: 31337,
+            contract_address: String::from("0x5fbdb2315678afecb367f032d93f642f64180aa3"),
+            token_symbol: String::from("USDC"),
+            token_decimals: 6,
+        },
+    }
+}
+
 pub fn example_networks() -> Vec<Network> {
     vec![example_network(Currency::Iota), example_network(Currency::Eth)]
 }
@@ -378,6 +417,7 @@ pub fn example_wallet_tx_info() -> WalletTxInfo {
         date: "some date".to_string(),
         block_id: None,
         transaction_id: "some tx id".to_string(),
+        log_index: None,
         receiver: String::new(),
         incoming: true,
         amount: 20.0,
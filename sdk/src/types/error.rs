@@ -20,6 +20,10 @@ pub enum TypeError {
     #[error("Access token should not be empty")]
     EmptyAccessToken,
 
+    /// Error raises if the mnemonic passphrase is empty
+    #[error("Mnemonic passphrase should not be empty")]
+    EmptyPassphrase,
+
     /// Error raises if the password fails to be encrypted
     #[error("Unable to encrypt password.")]
     PasswordEncryption,
@@ -27,4 +31,12 @@ pub enum TypeError {
     /// Error raises if the pin or password is incorrect
     #[error("Pin or password is invalid.")]
     InvalidPinOrPassword,
+
+    /// Error raises if the encrypted password has an unknown format version or parameters above the limits
+    #[error("The encrypted password has an unsupported format.")]
+    UnsupportedPasswordFormat,
+
+    /// Error raises if an unsigned or signed transaction cannot be parsed from its QR string
+    #[error("Invalid transaction QR string: {0}")]
+    InvalidTransactionQr(String),
 }
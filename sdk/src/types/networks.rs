@@ -20,6 +20,12 @@ pub enum NetworkType {
         chain_id: u64,
         ///contract address
         contract_address: String,
+        /// symbol of the token, queried from the contract if not set
+        #[serde(default)]
+        token_symbol: Option<String>,
+        /// number of decimals of the token, queried from the contract if not set
+        #[serde(default)]
+        token_decimals: Option<u8>,
     },
     /// Represents a Stardust network.
     /// Contains the URL for the node.
@@ -52,6 +58,19 @@ impl From<ApiNetworkType> for NetworkType {
     fn from(value: ApiNetworkType) -> Self {
         match value {
             ApiNetworkType::Evm { node_urls, chain_id } => NetworkType::Evm { node_urls, chain_id },
+            ApiNetworkType::EvmErc20 {
+                node_urls,
+                chain_id,
+                contract_address,
+                token_symbol,
+                token_decimals,
+            } => NetworkType::EvmErc20 {
+                node_urls,
+                chain_id,
+                contract_address,
+                token_symbol: Some(token_symbol),
+                token_decimals: Some(token_decimals),
+            },
             ApiNetworkType::Stardust { node_urls } => NetworkType::Stardust { node_urls },
         }
     }
@@ -70,3 +89,54 @@ impl From<ApiNetwork> for Network {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_network_type_erc20_keeps_token_metadata() {
+        // Arrange
+        let api_network_type = ApiNetworkType::EvmErc20 {
+            node_urls: vec![String::from("https://sepolia.mode.network")],
+            chain_id: 31337,
+            contract_address: String::from("0x5fbdb2315678afecb367f032d93f642f64180aa3"),
+            token_symbol: String::from("USDC"),
+            token_decimals: 6,
+        };
+
+        // Act
+        let network_type = NetworkType::from(api_network_type);
+
+        // Assert
+        assert_eq!(
+            network_type,
+            NetworkType::EvmErc20 {
+                node_urls: vec![String::from("https://sepolia.mode.network")],
+                chain_id: 31337,
+                contract_address: String::from("0x5fbdb2315678afecb367f032d93f642f64180aa3"),
+                token_symbol: Some(String::from("USDC")),
+                token_decimals: Some(6),
+            }
+        );
+    }
+
+    #[test]
+    fn test_network_type_erc20_deserializes_without_token_metadata() {
+        // Arrange
+        let json = r#"{"EvmErc20":{"node_urls":["https://sepolia.mode.network"],"chain_id":31337,"contract_address":"0x5fbdb2315678afecb367f032d93f642f64180aa3"}}"#;
+
+        // Act
+        let network_type: NetworkType = serde_json::from_str(json).unwrap();
+
+        // Assert
+        assert!(matches!(
+            network_type,
+            NetworkType::EvmErc20 {
+                token_symbol: None,
+                token_decimals: None,
+                ..
+            }
+        ));
+    }
+}
@@ -28,6 +28,8 @@ use iota_sdk::crypto::hashes::blake2b::Blake2b256;
 use iota_sdk::crypto::hashes::Digest;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
 
 macro_rules! impl_redacted_debug {
     ($type:ty) => {
@@ -39,6 +41,74 @@ macro_rules! impl_redacted_debug {
     };
 }
 
+/// Magic bytes identifying the versioned [`EncryptedPassword`] format.
+const MAGIC: &[u8; 3] = b"EPW";
+/// Version of the legacy format, which derives the key with a single Blake2b256 hash and reuses the salt
+/// as nonce.
+const VERSION_LEGACY: u8 = 0;
+/// Version of the format using Argon2id for key derivation.
+const VERSION_ARGON2ID: u8 = 1;
+/// Header of passwords in the legacy format. Releases before the versioned format stored the ciphertext
+/// without any header, see [`crate::user::record`] for how their records are upgraded.
+pub(crate) const LEGACY_PASSWORD_HEADER: [u8; 4] = [MAGIC[0], MAGIC[1], MAGIC[2], VERSION_LEGACY];
+/// Length of the AES-256-GCM nonce.
+const NONCE_LEN: usize = 12;
+/// Length of the header: magic, version, three `u32` KDF parameters and the nonce.
+const HEADER_LEN: usize = 3 + 1 + 3 * 4 + NONCE_LEN;
+
+/// Argon2id parameters used to derive the password encryption key from the pin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct KdfParams {
+    /// Memory size in KiB
+    m_cost: u32,
+    /// Number of iterations
+    t_cost: u32,
+    /// Degree of parallelism
+    p_cost: u32,
+}
+
+/// The minimum Argon2id configuration recommended by OWASP. The keys of the local share and of the user storage
+/// are derived from the pin with these parameters without storing them, so they must never change. Passwords
+/// store their parameters and could move to stronger ones by changing [`KdfParams::default`].
+const PIN_KDF_PARAMS: KdfParams = KdfParams {
+    m_cost: 19 * 1024,
+    t_cost: 2,
+    p_cost: 1,
+};
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        PIN_KDF_PARAMS
+    }
+}
+
+/// Upper limits of the Argon2id parameters read from a stored password. The parameters are stored in clear,
+/// so a modified record must not make the key derivation allocate gigabytes of memory or run for minutes.
+const MAX_KDF_PARAMS: KdfParams = KdfParams {
+    m_cost: 256 * 1024,
+    t_cost: 16,
+    p_cost: 8,
+};
+
+impl KdfParams {
+    /// Returns `true` if none of the parameters exceeds the one of the limits.
+    fn is_within(&self, limits: &KdfParams) -> bool {
+        self.m_cost <= limits.m_cost && self.t_cost <= limits.t_cost && self.p_cost <= limits.p_cost
+    }
+
+    /// Derive a 256-bit key from the pin and salt. Returns `None` if the parameters are invalid.
+    fn derive_key(&self, pin: &EncryptionPin, salt: &EncryptionSalt) -> Option<zeroize::Zeroizing<[u8; 32]>> {
+        let params = argon2::Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32)).ok()?;
+        let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        let mut key = zeroize::Zeroizing::new([0u8; 32]);
+        argon2
+            .hash_password_into(pin.0.as_ref(), salt.0.as_ref(), key.as_mut())
+            .ok()?;
+        Some(key)
+    }
+}
+
 /// A password that is not encrypted and stored as plain text.
 #[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Clone)]
 pub struct PlainPassword(String);
@@ -56,22 +126,36 @@ impl PlainPassword {
     }
 
     /// Encrypt this password with the provided pin and salt.
+    ///
+    /// The encryption key is derived from the pin and salt with Argon2id, and a fresh random
+    /// nonce is generated for every encryption. The KDF parameters and the nonce are stored
+    /// alongside the ciphertext, see [`EncryptedPassword`] for the exact format.
     pub fn encrypt(&self, pin: &EncryptionPin, salt: &EncryptionSalt) -> Result<EncryptedPassword> {
-        let key = Blake2b256::new()
-            .chain_update(pin.0.as_ref())
-            .chain_update(salt.0.as_ref())
-            .finalize();
+        let params = KdfParams::default();
+        let Some(key) = params.derive_key(pin, salt) else {
+            return Err(TypeError::PasswordEncryption);
+        };
 
-        let Ok(cipher) = Aes256Gcm::new_from_slice(&key) else {
+        let Ok(cipher) = Aes256Gcm::new_from_slice(key.as_ref()) else {
             return Err(TypeError::PasswordEncryption);
         };
 
-        let nonce = Nonce::from_slice(salt.0.as_ref()); // 96-bits; unique per message
-        let Ok(cipher) = cipher.encrypt(nonce, self.0.as_ref()) else {
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill_bytes(&mut nonce);
+        let Ok(ciphertext) = cipher.encrypt(Nonce::from_slice(&nonce), self.0.as_ref()) else {
             return Err(TypeError::PasswordEncryption);
         };
 
-        Ok(EncryptedPassword(cipher.into()))
+        let mut bytes = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION_ARGON2ID);
+        bytes.extend_from_slice(&params.m_cost.to_le_bytes());
+        bytes.extend_from_slice(&params.t_cost.to_le_bytes());
+        bytes.extend_from_slice(&params.p_cost.to_le_bytes());
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+
+        Ok(EncryptedPassword(bytes.into()))
     }
 
     /// Helper function to convert into [`secrecy::Secret`] using cloning.
@@ -103,32 +187,84 @@ impl TryFrom<&str> for PlainPassword {
 }
 
 /// An encrypted password.
-#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Deserialize, Serialize, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+///
+/// The bytes are stored in a versioned format: the magic bytes `EPW` and a version byte. The current
+/// version continues with the Argon2id memory, iteration and parallelism costs (each as little-endian
+/// `u32`), a random 96-bit nonce and finally the AES-256-GCM ciphertext. The legacy version is directly
+/// followed by the ciphertext and can still be decrypted.
+#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EncryptedPassword(Box<[u8]>);
 impl_redacted_debug!(EncryptedPassword);
 
 impl EncryptedPassword {
     /// Decrypt this password with the provided pin and salt.
-    /// Returns an error if the pin or salt is incorrect.
+    /// Returns an error if the pin or salt is incorrect, or if the format is not supported.
+    ///
+    /// Passwords encrypted with the legacy scheme (see [`EncryptedPassword::is_legacy`]) are still
+    /// supported, but should be re-encrypted with [`PlainPassword::encrypt`] as soon as possible.
     pub fn decrypt(&self, pin: &EncryptionPin, salt: &EncryptionSalt) -> Result<PlainPassword> {
-        let key = Blake2b256::new()
-            .chain_update(pin.0.as_ref())
-            .chain_update(salt.0.as_ref())
-            .finalize();
-
-        let Ok(cipher) = Aes256Gcm::new_from_slice(&key) else {
-            return Err(TypeError::PasswordEncryption);
+        let plaintext = match self.format()? {
+            PasswordFormat::Legacy { ciphertext } => decrypt_legacy(ciphertext, pin, salt),
+            PasswordFormat::Argon2id {
+                params,
+                nonce,
+                ciphertext,
+            } => params
+                .derive_key(pin, salt)
+                .and_then(|key| Aes256Gcm::new_from_slice(key.as_ref()).ok())
+                .and_then(|cipher| cipher.decrypt(Nonce::from_slice(nonce), ciphertext).ok()),
         };
 
-        let nonce = Nonce::from_slice(salt.0.as_ref()); // 96-bits; unique per message
-        let Ok(plaintext) = cipher.decrypt(nonce, self.0.as_ref()) else {
+        let Some(plaintext) = plaintext else {
             return Err(TypeError::InvalidPinOrPassword);
         };
-
         Ok(PlainPassword(String::from_utf8_lossy(&plaintext).to_string()))
     }
 
+    /// Returns `true` if this password was encrypted with the legacy scheme, which derives the key
+    /// with a single Blake2b256 hash and reuses the salt as nonce.
+    pub fn is_legacy(&self) -> bool {
+        matches!(self.format(), Ok(PasswordFormat::Legacy { .. }))
+    }
+
+    /// Splits the bytes into the parts of the format given by the version byte. Returns an error for
+    /// unknown versions and for Argon2id parameters above [`MAX_KDF_PARAMS`].
+    fn format(&self) -> Result<PasswordFormat<'_>> {
+        let bytes = self.0.as_ref();
+        if bytes.len() <= MAGIC.len() || !bytes.starts_with(MAGIC) {
+            return Err(TypeError::UnsupportedPasswordFormat);
+        }
+
+        match bytes[MAGIC.len()] {
+            VERSION_LEGACY => Ok(PasswordFormat::Legacy {
+                ciphertext: &bytes[MAGIC.len() + 1..],
+            }),
+            VERSION_ARGON2ID if bytes.len() > HEADER_LEN => {
+                let read_u32 = |offset: usize| {
+                    bytes[offset..offset + 4]
+                        .try_into()
+                        .map(u32::from_le_bytes)
+                        .map_err(|_| TypeError::UnsupportedPasswordFormat)
+                };
+                let params = KdfParams {
+                    m_cost: read_u32(4)?,
+                    t_cost: read_u32(8)?,
+                    p_cost: read_u32(12)?,
+                };
+                if !params.is_within(&MAX_KDF_PARAMS) {
+                    return Err(TypeError::UnsupportedPasswordFormat);
+                }
+
+                Ok(PasswordFormat::Argon2id {
+                    params,
+                    nonce: &bytes[16..HEADER_LEN],
+                    ciphertext: &bytes[HEADER_LEN..],
+                })
+            }
+            _ => Err(TypeError::UnsupportedPasswordFormat),
+        }
+    }
+
     /// Create a new `EncryptedPassword` from raw bytes.
     ///
     /// # Safety
@@ -140,8 +276,32 @@ impl EncryptedPassword {
     }
 }
 
+/// The parts of an [`EncryptedPassword`], depending on the version of its format.
+enum PasswordFormat<'a> {
+    /// See [`VERSION_LEGACY`]
+    Legacy { ciphertext: &'a [u8] },
+    /// See [`VERSION_ARGON2ID`]
+    Argon2id {
+        params: KdfParams,
+        nonce: &'a [u8],
+        ciphertext: &'a [u8],
+    },
+}
+
+/// Decrypt a password encrypted with the legacy Blake2b256 scheme.
+fn decrypt_legacy(ciphertext: &[u8], pin: &EncryptionPin, salt: &EncryptionSalt) -> Option<Vec<u8>> {
+    let key = Blake2b256::new()
+        .chain_update(pin.0.as_ref())
+        .chain_update(salt.0.as_ref())
+        .finalize();
+
+    let cipher = Aes256Gcm::new_from_slice(&key).ok()?;
+    let nonce = Nonce::from_slice(salt.0.as_ref()); // 96-bits; unique per message
+    cipher.decrypt(nonce, ciphertext).ok()
+}
+
 /// A non-empty pin used to encrypt the password.
-#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop)]
+#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Clone)]
 pub struct EncryptionPin(Box<[u8]>);
 impl_redacted_debug!(EncryptionPin);
 
@@ -154,6 +314,40 @@ impl EncryptionPin {
         }
         Ok(Self(pin.as_bytes().into()))
     }
+
+    /// Derive the key used to encrypt the local share from this pin and the salt.
+    ///
+    /// The Argon2id output is hashed together with a context string, so the key differs from the one
+    /// used to encrypt the password with the same pin and salt.
+    pub(crate) fn derive_local_share_key(&self, salt: &EncryptionSalt) -> Result<secrecy::SecretBox<[u8]>> {
+        let Some(key) = PIN_KDF_PARAMS.derive_key(self, salt) else {
+            return Err(TypeError::PasswordEncryption);
+        };
+
+        let key = Blake2b256::new()
+            .chain_update(b"etopay local share")
+            .chain_update(key.as_ref())
+            .finalize();
+        Ok(secrecy::SecretBox::new(key.as_slice().into()))
+    }
+
+    /// Derive the key used to encrypt the stored user records from this pin and the salt.
+    ///
+    /// The salt is stored in clear with each record, since the key is needed to read the record which
+    /// contains the salt of the password.
+    pub(crate) fn derive_user_storage_key(&self, salt: &EncryptionSalt) -> Result<UserStorageKey> {
+        let Some(key) = PIN_KDF_PARAMS.derive_key(self, salt) else {
+            return Err(TypeError::PasswordEncryption);
+        };
+
+        let key = Blake2b256::new()
+            .chain_update(b"etopay user storage")
+            .chain_update(key.as_ref())
+            .finalize();
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&key);
+        Ok(UserStorageKey::from(bytes))
+    }
 }
 impl TryFrom<String> for EncryptionPin {
     type Error = TypeError;
@@ -170,8 +364,7 @@ impl TryFrom<&str> for EncryptionPin {
 
 /// A salt used in the encryption process.
 /// Should be unique for each encryption but is not a secret.
-#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Deserialize, Serialize, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EncryptionSalt(Box<[u8; 12]>);
 impl_redacted_debug!(EncryptionSalt);
 
@@ -190,6 +383,32 @@ impl From<[u8; 12]> for EncryptionSalt {
     }
 }
 
+/// A 256-bit key used to encrypt the user records in the local user storage.
+#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct UserStorageKey(Box<[u8; 32]>);
+impl_redacted_debug!(UserStorageKey);
+
+impl UserStorageKey {
+    /// Generate a new random [`UserStorageKey`].
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        rand::rng().fill_bytes(&mut key);
+        Self(Box::new(key))
+    }
+
+    /// Helper function to get access to the key bytes. Use with caution!
+    pub(crate) fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for UserStorageKey {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value.into())
+    }
+}
+
 /// Simple wrapper around a non-empty access token that cannot be printed or logged, and is
 /// automatically zeroized when dropped.
 #[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Deserialize, Serialize, Clone)]
@@ -226,9 +445,47 @@ impl TryFrom<&str> for AccessToken {
     }
 }
 
+/// A non-empty BIP-39 passphrase (also known as the "25th word") that is combined with the mnemonic to
+/// derive the seed of the wallet. The passphrase is stored NFKD normalized, as required by BIP-39.
+#[derive(zeroize::Zeroize, zeroize::ZeroizeOnDrop, Clone, PartialEq)]
+pub struct MnemonicPassphrase(String);
+impl_redacted_debug!(MnemonicPassphrase);
+
+impl MnemonicPassphrase {
+    /// Try to construct a new [`MnemonicPassphrase`] from a [`String`]-like value.
+    pub fn try_from_string(passphrase: impl Into<String>) -> Result<Self> {
+        let mut passphrase: String = passphrase.into();
+        if passphrase.is_empty() {
+            return Err(TypeError::EmptyPassphrase);
+        }
+
+        let normalized = passphrase.nfkd().collect();
+        passphrase.zeroize();
+        Ok(Self(normalized))
+    }
+
+    /// Helper function to get the underlying string, use with caution!
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl TryFrom<String> for MnemonicPassphrase {
+    type Error = TypeError;
+    fn try_from(value: String) -> Result<Self> {
+        Self::try_from_string(value)
+    }
+}
+impl TryFrom<&str> for MnemonicPassphrase {
+    type Error = TypeError;
+    fn try_from(value: &str) -> Result<Self> {
+        Self::try_from_string(value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::testing_utils::{ENCRYPTED_PASSWORD, LEGACY_ENCRYPTED_PASSWORD, PIN, SALT};
     use crate::types::newtypes::PlainPassword;
 
     #[test]
@@ -297,6 +554,146 @@ mod test {
         decrypted_password.unwrap_err();
     }
 
+    #[test]
+    fn test_encrypt_password_uses_fresh_nonce() {
+        let password = PlainPassword::try_from_string("strong_password").unwrap();
+        let pin = EncryptionPin::try_from_string("12345").unwrap();
+        let salt = EncryptionSalt::generate();
+
+        let first = password.encrypt(&pin, &salt).unwrap();
+        let second = password.encrypt(&pin, &salt).unwrap();
+
+        assert!(!first.is_legacy());
+        assert_ne!(first.0, second.0);
+        assert!(matches!(
+            first.format(),
+            Ok(PasswordFormat::Argon2id { params, .. }) if params == KdfParams::default()
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_legacy_password() {
+        let salt = EncryptionSalt::from(SALT);
+
+        assert!(LEGACY_ENCRYPTED_PASSWORD.is_legacy());
+        let decrypted_password = LEGACY_ENCRYPTED_PASSWORD.decrypt(&PIN, &salt).unwrap();
+
+        assert_eq!(decrypted_password.as_str(), "StrongP@55w0rd");
+    }
+
+    #[test]
+    fn test_decrypt_password_with_stored_params() {
+        let salt = EncryptionSalt::from(SALT);
+
+        assert!(!ENCRYPTED_PASSWORD.is_legacy());
+        let decrypted_password = ENCRYPTED_PASSWORD.decrypt(&PIN, &salt).unwrap();
+
+        assert_eq!(decrypted_password.as_str(), "StrongP@55w0rd");
+    }
+
+    #[test]
+    fn test_decrypt_password_failure_tampered_params() {
+        let salt = EncryptionSalt::from(SALT);
+        let mut bytes = ENCRYPTED_PASSWORD.0.to_vec();
+        bytes[8] = 3; // t_cost
+
+        // SAFETY: this is only for testing purposes to make sure tampered parameters give an error
+        let tampered = unsafe { EncryptedPassword::new_unchecked(bytes) };
+
+        tampered.decrypt(&PIN, &salt).unwrap_err();
+    }
+
+    #[test]
+    fn test_decrypt_password_failure_params_above_limits() {
+        let salt = EncryptionSalt::from(SALT);
+        let mut bytes = ENCRYPTED_PASSWORD.0.to_vec();
+        bytes[4..8].copy_from_slice(&u32::MAX.to_le_bytes()); // m_cost
+
+        // SAFETY: this is only for testing purposes to make sure the stored parameters are limited
+        let tampered = unsafe { EncryptedPassword::new_unchecked(bytes) };
+
+        assert!(matches!(
+            tampered.decrypt(&PIN, &salt),
+            Err(TypeError::UnsupportedPasswordFormat)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_password_failure_unknown_version() {
+        let salt = EncryptionSalt::from(SALT);
+        let mut bytes = ENCRYPTED_PASSWORD.0.to_vec();
+        bytes[3] = 2;
+
+        // SAFETY: this is only for testing purposes to make sure unknown versions give an error
+        let unknown = unsafe { EncryptedPassword::new_unchecked(bytes) };
+
+        assert!(matches!(
+            unknown.decrypt(&PIN, &salt),
+            Err(TypeError::UnsupportedPasswordFormat)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_password_failure_legacy_without_header() {
+        let salt = EncryptionSalt::from(SALT);
+
+        // SAFETY: this is only for testing purposes to make sure the legacy format is not guessed
+        let without_header =
+            unsafe { EncryptedPassword::new_unchecked(&LEGACY_ENCRYPTED_PASSWORD.0[LEGACY_PASSWORD_HEADER.len()..]) };
+
+        assert!(matches!(
+            without_header.decrypt(&PIN, &salt),
+            Err(TypeError::UnsupportedPasswordFormat)
+        ));
+    }
+
+    #[test]
+    fn test_derive_local_share_key() {
+        use secrecy::ExposeSecret;
+
+        let salt = EncryptionSalt::from(SALT);
+        let wrong_pin = EncryptionPin::try_from_string("54321").unwrap();
+
+        let key = PIN.derive_local_share_key(&salt).unwrap();
+
+        assert_eq!(key.expose_secret().len(), 32);
+        assert_eq!(
+            key.expose_secret(),
+            PIN.derive_local_share_key(&salt).unwrap().expose_secret()
+        );
+        assert_ne!(
+            key.expose_secret(),
+            wrong_pin.derive_local_share_key(&salt).unwrap().expose_secret()
+        );
+        assert_ne!(
+            key.expose_secret(),
+            PIN.derive_local_share_key(&EncryptionSalt::generate())
+                .unwrap()
+                .expose_secret()
+        );
+    }
+
+    #[test]
+    fn test_derive_user_storage_key() {
+        let wrong_pin = EncryptionPin::try_from_string("54321").unwrap();
+
+        let salt = EncryptionSalt::generate();
+
+        let key = PIN.derive_user_storage_key(&salt).unwrap();
+
+        assert!(key == PIN.derive_user_storage_key(&salt).unwrap());
+        assert!(key != wrong_pin.derive_user_storage_key(&salt).unwrap());
+        assert!(key != PIN.derive_user_storage_key(&EncryptionSalt::generate()).unwrap());
+    }
+
+    #[test]
+    fn test_passphrase_is_nfkd_normalized() {
+        let precomposed = MnemonicPassphrase::try_from_string("pa\u{00df}word \u{00e1}baco").unwrap();
+        let decomposed = MnemonicPassphrase::try_from_string("pa\u{00df}word a\u{0301}baco").unwrap();
+        assert_eq!(precomposed.as_str(), "pa\u{00df}word a\u{0301}baco");
+        assert!(precomposed == decomposed);
+    }
+
     #[test]
     fn test_generate_salt() {
         let salt = EncryptionSalt::generate();
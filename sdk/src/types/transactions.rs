@@ -1,3 +1,5 @@
+use super::error::{Result, TypeError};
+use alloy_primitives::hex;
 use api_types::api::{
     networks::ApiNetwork,
     transactions::{ApiApplicationMetadata, ApiTxStatus},
@@ -50,6 +52,10 @@ pub struct WalletTxInfo {
     pub block_id: Option<String>,
     /// transaction id for particular transaction
     pub transaction_id: String,
+    /// The index of the log of a token transfer within its block. A transaction can contain several token
+    /// transfers, which are told apart by their log index.
+    #[serde(default)]
+    pub log_index: Option<u64>,
     /// Describes type of transaction
     pub incoming: bool,
     /// The receiver of the transaction
@@ -74,6 +80,235 @@ pub struct WalletTxInfoList {
     pub transactions: Vec<WalletTxInfo>,
 }
 
+/// The maximum number of transactions kept in a [`WalletTxHistory`]. Older transactions are dropped.
+pub const MAX_WALLET_TX_HISTORY: usize = 1_000;
+
+/// Locally cached wallet transaction history of a single network, built by scanning the network.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct WalletTxHistory {
+    /// The last block that has been scanned for wallet transactions, if any
+    pub last_scanned_block: Option<u64>,
+    /// The oldest block that has been scanned for wallet transactions, if any. The blocks before it
+    /// are backfilled by subsequent scans.
+    #[serde(default)]
+    pub first_scanned_block: Option<u64>,
+    /// The transactions found so far, ordered from newest to oldest, at most [`MAX_WALLET_TX_HISTORY`]
+    pub transactions: Vec<WalletTxInfo>,
+}
+
+/// A signed EVM transaction that has been broadcast but is not yet known to be included in a block.
+///
+/// The raw transaction is kept so it can be re-broadcast (e.g. after an app restart) and the
+/// nonce and fees are kept so it can be replaced by a speed-up or cancel transaction.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PendingTransaction {
+    /// The hash of the signed transaction
+    pub transaction_hash: String,
+    /// The sender address
+    pub from: String,
+    /// The nonce of the transaction
+    pub nonce: u64,
+    /// The gas limit of the transaction
+    pub gas_limit: u64,
+    /// The maximum fee per gas in wei
+    pub max_fee_per_gas: u128,
+    /// The maximum priority fee (tip) per gas in wei
+    pub max_priority_fee_per_gas: u128,
+    /// The EIP-2718 encoded signed transaction
+    pub raw_transaction: Vec<u8>,
+    /// Unix timestamp in seconds of when the transaction was signed
+    pub created_at: i64,
+    /// The hashes of the transactions replaced by this one, oldest first
+    #[serde(default)]
+    pub replaces: Vec<String>,
+}
+
+impl PendingTransaction {
+    /// Encodes the signed transaction for the transfer from an offline device, see [`UnsignedTransaction`].
+    pub fn to_qr_string(&self) -> String {
+        format!("{SIGNED_TX_QR_PREFIX}{}", hex::encode_upper(&self.raw_transaction))
+    }
+
+    /// Decodes the signed transaction created by [`PendingTransaction::to_qr_string`].
+    ///
+    /// # Returns
+    ///
+    /// The EIP-2718 encoded signed transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a signed transaction.
+    pub fn raw_transaction_from_qr_string(qr: &str) -> Result<Vec<u8>> {
+        let Some(raw_transaction) = qr.trim().strip_prefix(SIGNED_TX_QR_PREFIX) else {
+            return Err(TypeError::InvalidTransactionQr(String::from(
+                "not a signed transaction",
+            )));
+        };
+        decode_qr_hex(raw_transaction)
+    }
+}
+
+/// The prefix of the QR string of an [`UnsignedTransaction`]
+const UNSIGNED_TX_QR_PREFIX: &str = "ETOPAY:UNSIGNED-TX:";
+
+/// The prefix of the QR string of a signed transaction
+const SIGNED_TX_QR_PREFIX: &str = "ETOPAY:SIGNED-TX:";
+
+/// An EVM transaction that was prepared on an online device and is signed on an offline device.
+///
+/// The QR string only consists of upper-case letters, digits and the characters `:` and `-`, so that it fits
+/// into a QR code in alphanumeric mode.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnsignedTransaction {
+    /// The sender address, whose key signs the transaction
+    pub from: String,
+    /// The EIP-1559 transaction as it is signed: the transaction type followed by the RLP encoded fields
+    pub raw_transaction: Vec<u8>,
+}
+
+impl UnsignedTransaction {
+    /// Encodes the transaction for the transfer to an offline device.
+    pub fn to_qr_string(&self) -> String {
+        format!(
+            "{UNSIGNED_TX_QR_PREFIX}{}:{}",
+            self.from.trim_start_matches("0x").to_uppercase(),
+            hex::encode_upper(&self.raw_transaction)
+        )
+    }
+
+    /// Decodes the transaction created by [`UnsignedTransaction::to_qr_string`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not an unsigned transaction.
+    pub fn from_qr_string(qr: &str) -> Result<Self> {
+        let Some((from, raw_transaction)) = qr
+            .trim()
+            .strip_prefix(UNSIGNED_TX_QR_PREFIX)
+            .and_then(|payload| payload.split_once(':'))
+        else {
+            return Err(TypeError::InvalidTransactionQr(String::from(
+                "not an unsigned transaction",
+            )));
+        };
+
+        let from = decode_qr_hex(from)?;
+        if from.len() != 20 {
+            return Err(TypeError::InvalidTransactionQr(format!(
+                "sender address has {} bytes",
+                from.len()
+            )));
+        }
+
+        Ok(UnsignedTransaction {
+            from: format!("0x{}", hex::encode(from)),
+            raw_transaction: decode_qr_hex(raw_transaction)?,
+        })
+    }
+}
+
+/// The content of an [`UnsignedTransaction`], shown to the user on the offline device before signing it
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct UnsignedTransactionDetails {
+    /// The sender address
+    pub from: String,
+    /// The receiver address. For token transfers this is the receiver of the tokens, not the token contract.
+    pub to: String,
+    /// The amount sent to the receiver, in the currency of the network
+    pub value: Decimal,
+    /// The data of the transaction. For token transfers this is the encoded call of the token contract.
+    pub data: Vec<u8>,
+    /// The id of the chain the transaction can be included in
+    pub chain_id: u64,
+    /// The nonce of the transaction
+    pub nonce: u64,
+    /// The maximum amount of gas that the transaction can consume
+    pub gas_limit: u64,
+    /// The maximum fee per unit of gas in wei
+    pub max_fee_per_gas: u128,
+    /// The maximum tip per unit of gas in wei
+    pub max_priority_fee_per_gas: u128,
+    /// The maximum fee of the transaction in the native currency of the chain, the gas limit times the maximum
+    /// fee per gas
+    pub max_fee: Decimal,
+}
+
+/// Decodes the hex encoded part of a transaction QR string.
+fn decode_qr_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value).map_err(|e| TypeError::InvalidTransactionQr(format!("invalid hex value: {e}")))
+}
+
+/// The kind of replacement for a pending transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionReplacement {
+    /// Re-send the same transaction with higher fees
+    SpeedUp,
+    /// Send a transaction without value to the own address with the same nonce and higher fees
+    Cancel,
+}
+
+/// Status of a transaction followed by the transaction watcher
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchedTransactionStatus {
+    /// The transaction is not yet included in a block
+    #[default]
+    Pending,
+    /// The transaction is included in a block, but does not have the required number of confirmations yet
+    Included,
+    /// The transaction is included and final, it is no longer watched
+    Finalized,
+    /// The transaction failed, it is no longer watched
+    Failed,
+    /// Another transaction with the same nonce, e.g. a speed-up or cancel transaction, was included instead, it
+    /// is no longer watched
+    Replaced,
+}
+
+/// A submitted transaction followed by the transaction watcher until it is finalized or failed. Status changes
+/// are reported as the updated [`WatchedTransaction`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WatchedTransaction {
+    /// The hash of the transaction
+    pub transaction_hash: String,
+    /// The id of the network the transaction was sent on
+    pub network_id: String,
+    /// The last known status of the transaction
+    pub status: WatchedTransactionStatus,
+    /// The number of blocks including and following the block of the transaction, 0 if not included yet
+    pub confirmations: u64,
+}
+
+impl WalletTxHistory {
+    /// Merges the given transactions into the history. Transactions that are already known (same
+    /// transaction id and log index) are replaced with the new version, as are entries without a log
+    /// index that were added before the token transfers of their transaction were scanned. The history
+    /// is kept ordered from newest to oldest, with transactions not yet included in a block first, and
+    /// only the newest [`MAX_WALLET_TX_HISTORY`] transactions are kept.
+    pub fn merge(&mut self, transactions: impl IntoIterator<Item = WalletTxInfo>) {
+        for tx in transactions {
+            match self.transactions.iter_mut().find(|t| {
+                t.transaction_id == tx.transaction_id && (t.log_index == tx.log_index || t.log_index.is_none())
+            }) {
+                Some(existing) => *existing = tx,
+                None => self.transactions.push(tx),
+            }
+        }
+
+        self.transactions.sort_by_key(|t| {
+            let block = t.block_id.as_ref().map(|b| b.parse::<u64>().unwrap_or_default());
+            (block.is_some(), std::cmp::Reverse(block))
+        });
+        self.transactions.truncate(MAX_WALLET_TX_HISTORY);
+    }
+
+    /// Whether the history holds [`MAX_WALLET_TX_HISTORY`] transactions, so backfilling older blocks
+    /// would not add anything to it.
+    pub fn is_full(&self) -> bool {
+        self.transactions.len() >= MAX_WALLET_TX_HISTORY
+    }
+}
+
 impl From<Transaction> for WalletTxInfo {
     fn from(transaction: Transaction) -> Self {
         let essence = transaction.payload.essence();
@@ -112,6 +347,7 @@ impl From<Transaction> for WalletTxInfo {
         WalletTxInfo {
             block_id: transaction.block_id.map(|id| id.to_string()),
             transaction_id: transaction.transaction_id.to_string(),
+            log_index: None,
             incoming: transaction.incoming,
             receiver: String::new(), // TODO: iota is out anyways
             amount: sum,
@@ -146,3 +382,140 @@ pub struct GasCostEstimation {
     /// The maximum amount of gas that the transaction can consume.
     pub gas_limit: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(id: &str, block_id: Option<&str>) -> WalletTxInfo {
+        WalletTxInfo {
+            date: String::new(),
+            block_id: block_id.map(String::from),
+            transaction_id: id.to_string(),
+            log_index: None,
+            incoming: true,
+            receiver: String::new(),
+            amount: 1.0,
+            network: "ETH".to_string(),
+            status: "Pending".to_string(),
+            explorer_url: None,
+        }
+    }
+
+    #[test]
+    fn test_wallet_tx_history_merge_orders_and_deduplicates() {
+        // Arrange
+        let mut history = WalletTxHistory {
+            last_scanned_block: Some(20),
+            first_scanned_block: Some(5),
+            transactions: vec![tx("a", Some("10")), tx("b", None)],
+        };
+
+        // Act
+        history.merge(vec![tx("c", Some("12")), tx("b", Some("11")), tx("d", None)]);
+
+        // Assert
+        let ids = history
+            .transactions
+            .iter()
+            .map(|t| t.transaction_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["d", "c", "b", "a"]);
+        assert_eq!(history.transactions[2].block_id.as_deref(), Some("11"));
+    }
+
+    #[test]
+    fn test_wallet_tx_history_merge_keeps_transfers_of_the_same_transaction() {
+        // Arrange
+        let transfer = |log_index: Option<u64>| WalletTxInfo {
+            log_index,
+            ..tx("a", Some("10"))
+        };
+        let mut history = WalletTxHistory {
+            transactions: vec![transfer(None)],
+            ..Default::default()
+        };
+
+        // Act
+        history.merge(vec![transfer(Some(0)), transfer(Some(1)), transfer(Some(1))]);
+
+        // Assert
+        let log_indices = history.transactions.iter().map(|t| t.log_index).collect::<Vec<_>>();
+        assert_eq!(log_indices, vec![Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn test_wallet_tx_history_merge_keeps_newest_transactions() {
+        // Arrange
+        let mut history = WalletTxHistory::default();
+        let transactions = (0..MAX_WALLET_TX_HISTORY + 10)
+            .map(|i| tx(&i.to_string(), Some(&i.to_string())))
+            .collect::<Vec<_>>();
+
+        // Act
+        history.merge(transactions);
+
+        // Assert
+        assert!(history.is_full());
+        assert_eq!(history.transactions.len(), MAX_WALLET_TX_HISTORY);
+        assert_eq!(
+            history.transactions.first().map(|t| t.transaction_id.clone()),
+            Some((MAX_WALLET_TX_HISTORY + 9).to_string())
+        );
+        assert_eq!(
+            history.transactions.last().map(|t| t.transaction_id.clone()),
+            Some(10.to_string())
+        );
+    }
+
+    #[test]
+    fn test_unsigned_transaction_qr_string_round_trip() {
+        // Arrange
+        let unsigned = UnsignedTransaction {
+            from: String::from("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+            raw_transaction: vec![0x02, 0xc0, 0xff],
+        };
+
+        // Act
+        let qr = unsigned.to_qr_string();
+        let decoded = UnsignedTransaction::from_qr_string(&qr).unwrap();
+
+        // Assert
+        assert_eq!(qr, "ETOPAY:UNSIGNED-TX:F39FD6E51AAD88F6F4CE6AB8827279CFFFB92266:02C0FF");
+        assert_eq!(decoded, unsigned);
+    }
+
+    #[test]
+    fn test_signed_transaction_qr_string_round_trip() {
+        // Arrange
+        let pending = PendingTransaction {
+            transaction_hash: String::from("0x01"),
+            from: String::from("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1,
+            max_priority_fee_per_gas: 1,
+            raw_transaction: vec![0x02, 0xab],
+            created_at: 0,
+        };
+
+        // Act
+        let qr = pending.to_qr_string();
+        let raw_transaction = PendingTransaction::raw_transaction_from_qr_string(&qr).unwrap();
+
+        // Assert
+        assert_eq!(qr, "ETOPAY:SIGNED-TX:02AB");
+        assert_eq!(raw_transaction, pending.raw_transaction);
+    }
+
+    #[rstest::rstest]
+    #[case::signed_transaction("ETOPAY:SIGNED-TX:02AB")]
+    #[case::missing_sender("ETOPAY:UNSIGNED-TX:02AB")]
+    #[case::short_sender("ETOPAY:UNSIGNED-TX:F39FD6:02AB")]
+    #[case::invalid_hex("ETOPAY:UNSIGNED-TX:F39FD6E51AAD88F6F4CE6AB8827279CFFFB92266:0XYZ")]
+    fn test_unsigned_transaction_from_invalid_qr_string(#[case] qr: &str) {
+        let result = UnsignedTransaction::from_qr_string(qr);
+
+        assert!(matches!(result, Err(TypeError::InvalidTransactionQr(_))));
+    }
+}
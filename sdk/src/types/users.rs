@@ -1,16 +1,17 @@
 use super::{
     newtypes::{EncryptedPassword, EncryptionSalt},
-    transactions::WalletTxInfo,
+    transactions::{PendingTransaction, WalletTxHistory, WalletTxInfo, WatchedTransaction},
 };
 use crate::{
     types::viviswap::ViviswapState,
+    wallet::watch_only::WatchOnlySource,
     wallet_manager::{WalletManager, WalletManagerImpl},
 };
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Struct for storing a user in the database
-#[derive(Debug, Deserialize, Serialize, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct UserEntity {
     /// User ID for backend (remove or use for telemetry?)
     pub user_id: Option<String>,
@@ -32,6 +33,109 @@ pub struct UserEntity {
 
     /// User wallet transactions
     pub wallet_transactions: Vec<WalletTxInfo>,
+
+    /// Scanned wallet transaction history per network, keyed by the network id
+    #[serde(default)]
+    pub wallet_tx_history: HashMap<String, WalletTxHistory>,
+
+    /// Failed pin attempts, used to rate limit the pin verification
+    #[serde(default)]
+    pub pin_attempts: PinAttempts,
+
+    /// Address indices of the EVM wallets derived in addition to the default address with index 0
+    #[serde(default)]
+    pub evm_address_indices: Vec<u32>,
+
+    /// Signed EVM transactions that are not yet known to be included, per network, keyed by the network id
+    #[serde(default)]
+    pub pending_transactions: HashMap<String, Vec<PendingTransaction>>,
+
+    /// Submitted transactions followed by the transaction watcher until they are finalized or failed
+    #[serde(default)]
+    pub watched_transactions: Vec<WatchedTransaction>,
+
+    /// Addresses or extended public key of the wallet used by the watch-only wallet
+    #[serde(default)]
+    pub watch_only: Option<WatchOnlySource>,
+
+    /// Unix timestamp in seconds of the creation of the user, unknown for users created by earlier SDK versions
+    #[serde(default)]
+    pub created_at: Option<i64>,
+
+    /// Unix timestamp in seconds of the last time the user was initialized with `Sdk::init_user`
+    #[serde(default)]
+    pub last_used_at: Option<i64>,
+
+    /// The x25519 secret key of the user as a guardian of other users, encrypted like the local share
+    #[serde(default)]
+    pub guardian_secret_key: Option<String>,
+
+    /// The x25519 secret key of a started guardian recovery of the user, encrypted like the local share
+    #[serde(default)]
+    pub guardian_recovery_key: Option<String>,
+
+    /// The BIP-39 passphrase of the wallet, encrypted like the local share. It is not part of the shares.
+    #[serde(default)]
+    pub encrypted_passphrase: Option<String>,
+
+    /// The recovery share encrypted like the local share, stored to unlock the wallet on the device without the
+    /// backup share of the backend, e.g. on an offline device signing transactions
+    #[serde(default)]
+    pub offline_recovery_share: Option<String>,
+}
+
+/// Metadata of a user stored on the device, e.g. to show an account switcher
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UserSummary {
+    /// Username
+    pub username: String,
+    /// Unix timestamp in seconds of the creation of the user, unknown for users created by earlier SDK versions
+    pub created_at: Option<i64>,
+    /// User KYC Type
+    pub kyc_type: KycType,
+    /// Whether the user has a wallet protected by a password
+    pub has_wallet: bool,
+    /// Unix timestamp in seconds of the last time the user was initialized, if known
+    pub last_used_at: Option<i64>,
+    /// Whether the stored user is encrypted with the pin and locked. Only the username is known then, the other
+    /// fields have their default values.
+    pub locked: bool,
+}
+
+impl UserSummary {
+    /// The summary of a user that is locked until the pin is entered
+    pub(crate) fn locked(username: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            created_at: None,
+            kyc_type: KycType::Undefined,
+            has_wallet: false,
+            last_used_at: None,
+            locked: true,
+        }
+    }
+}
+
+impl From<&UserEntity> for UserSummary {
+    fn from(entity: &UserEntity) -> Self {
+        Self {
+            username: entity.username.clone(),
+            created_at: entity.created_at,
+            kyc_type: entity.kyc_type.clone(),
+            has_wallet: entity.encrypted_password.is_some(),
+            last_used_at: entity.last_used_at,
+            locked: false,
+        }
+    }
+}
+
+/// Consecutive failed pin attempts of a user
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+pub struct PinAttempts {
+    /// Number of consecutive failed attempts since the last successful one
+    pub failed: u32,
+    /// Unix timestamp in seconds of the last failed attempt
+    pub last_failed_at: Option<i64>,
 }
 
 /// Struct to manage the state of the currently active (initialized) user
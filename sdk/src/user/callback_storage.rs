@@ -0,0 +1,162 @@
+//! An implementation of [`super::UserKvStorage`] on top of a string key-value storage of the host
+//! application, which the bindings connect to callbacks of the host language.
+
+use super::error::Result;
+use super::UserKvStorageError;
+use crate::types::users::UserEntity;
+use base64::prelude::*;
+
+const STORAGE_KEY_PREFIX: &str = "etopay.local.user";
+
+/// The operations of a string key-value storage of the host application, e.g. a keychain, an encrypted
+/// database or the browser storage. Errors are reported as a message and returned by the SDK as
+/// [`UserKvStorageError::Storage`].
+pub trait UserStorageCallbacks {
+    /// Get the value of a key, or `None` if the key does not exist.
+    fn get_item(&self, key: &str) -> std::result::Result<Option<String>, String>;
+
+    /// Associate a key with a value, overwriting any previous value.
+    fn set_item(&self, key: &str, value: &str) -> std::result::Result<(), String>;
+
+    /// Remove a key and the associated value. No error if the key does not exist.
+    fn remove_item(&self, key: &str) -> std::result::Result<(), String>;
+
+    /// List all keys of the storage, in any order. Keys not written by the SDK are ignored.
+    fn keys(&self) -> std::result::Result<Vec<String>, String>;
+}
+
+/// An implementation of [`super::UserKvStorage`] that stores the users as base64-encoded, versioned
+/// rmp_serde records in the storage of the host application, like the browser local storage. The keys
+/// are the usernames with the prefix `etopay.local.user.`.
+pub struct CallbackUserStorage<C> {
+    callbacks: C,
+}
+
+impl<C: UserStorageCallbacks> CallbackUserStorage<C> {
+    /// Create a storage using the callbacks of the host application.
+    pub fn new(callbacks: C) -> Self {
+        Self { callbacks }
+    }
+
+    fn storage_user_key(username: &str) -> String {
+        format!("{STORAGE_KEY_PREFIX}.{username}")
+    }
+
+    fn username_of_storage_key(key: &str) -> Option<&str> {
+        key.strip_prefix(STORAGE_KEY_PREFIX)?.strip_prefix('.')
+    }
+
+    fn get_item(&self, username: &str) -> Result<Option<String>> {
+        self.callbacks
+            .get_item(&Self::storage_user_key(username))
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not get storage key {username}: {e}")))
+    }
+}
+
+impl<C: UserStorageCallbacks> super::UserKvStorage for CallbackUserStorage<C> {
+    fn get(&self, username: &str) -> Result<UserEntity> {
+        let Some(value) = self.get_item(username)? else {
+            return Err(UserKvStorageError::UserNotFound {
+                username: username.to_string(),
+            });
+        };
+        let bytes = BASE64_STANDARD
+            .decode(value)
+            .map_err(|e| UserKvStorageError::Storage(format!("base64::DecodeError: {e:#?}")))?;
+        super::record::decode(username, &bytes, None)
+    }
+
+    fn delete(&mut self, username: &str) -> Result<()> {
+        self.callbacks
+            .remove_item(&Self::storage_user_key(username))
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not remove storage key {username}: {e}")))
+    }
+
+    fn exists(&self, username: &str) -> Result<bool> {
+        Ok(self.get_item(username)?.is_some())
+    }
+
+    fn set(&mut self, username: &str, value: &UserEntity) -> Result<()> {
+        let bytes = super::record::encode(username, value, None, None)?;
+        self.callbacks
+            .set_item(&Self::storage_user_key(username), &BASE64_STANDARD.encode(bytes))
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not set storage key {username}: {e}")))
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let keys = self
+            .callbacks
+            .keys()
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not list the storage keys: {e}")))?;
+        Ok(keys
+            .iter()
+            .filter_map(|key| Self::username_of_storage_key(key))
+            .map(String::from)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::{conformance, UserKvStorage};
+    use std::{collections::HashMap, sync::Mutex};
+
+    /// Callbacks of a host storage shared with other data
+    #[derive(Default)]
+    struct HostStorage(Mutex<HashMap<String, String>>);
+
+    impl UserStorageCallbacks for HostStorage {
+        fn get_item(&self, key: &str) -> std::result::Result<Option<String>, String> {
+            Ok(self.0.lock().unwrap().get(key).cloned())
+        }
+
+        fn set_item(&self, key: &str, value: &str) -> std::result::Result<(), String> {
+            self.0.lock().unwrap().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn remove_item(&self, key: &str) -> std::result::Result<(), String> {
+            self.0.lock().unwrap().remove(key);
+            Ok(())
+        }
+
+        fn keys(&self) -> std::result::Result<Vec<String>, String> {
+            Ok(self.0.lock().unwrap().keys().cloned().collect())
+        }
+    }
+
+    #[test]
+    fn test_callback_storage_passes_conformance_suite() {
+        conformance::run_all(|| {
+            let host = HostStorage::default();
+            host.set_item("other.app.key", "value").unwrap();
+            CallbackUserStorage::new(host)
+        });
+    }
+
+    #[test]
+    fn test_callback_errors_are_storage_errors() {
+        struct FailingStorage;
+        impl UserStorageCallbacks for FailingStorage {
+            fn get_item(&self, _key: &str) -> std::result::Result<Option<String>, String> {
+                Err(String::from("unavailable"))
+            }
+            fn set_item(&self, _key: &str, _value: &str) -> std::result::Result<(), String> {
+                Err(String::from("unavailable"))
+            }
+            fn remove_item(&self, _key: &str) -> std::result::Result<(), String> {
+                Err(String::from("unavailable"))
+            }
+            fn keys(&self) -> std::result::Result<Vec<String>, String> {
+                Err(String::from("unavailable"))
+            }
+        }
+
+        let mut storage = CallbackUserStorage::new(FailingStorage);
+        assert!(matches!(storage.get("alice"), Err(UserKvStorageError::Storage(_))));
+        assert!(matches!(storage.exists("alice"), Err(UserKvStorageError::Storage(_))));
+        assert!(matches!(storage.delete("alice"), Err(UserKvStorageError::Storage(_))));
+        assert!(matches!(storage.list(), Err(UserKvStorageError::Storage(_))));
+    }
+}
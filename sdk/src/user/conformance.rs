@@ -0,0 +1,274 @@
+//! Conformance checks for implementations of [`UserKvStorage`].
+//!
+//! A custom storage backend passed to [`Sdk::set_user_storage`](crate::core::Sdk::set_user_storage) should
+//! pass all of these checks. Call [`run_all`] from a test of the host application, or the single checks,
+//! e.g. from a parameterized test. The checks panic on the first violation, like an assertion.
+//!
+//! ```rust
+//! # use etopay_sdk::user::{conformance, memory_storage::MemoryUserStorage};
+//! conformance::run_all(MemoryUserStorage::new);
+//! ```
+
+#![allow(clippy::unwrap_used, clippy::expect_used)] // panicking on violations is the intended behavior
+
+use super::error::UserKvStorageError;
+use super::UserKvStorage;
+use crate::types::newtypes::EncryptionSalt;
+use crate::types::transactions::{
+    PendingTransaction, WalletTxHistory, WalletTxInfo, WatchedTransaction, WatchedTransactionStatus,
+};
+use crate::types::users::{KycType, PinAttempts, UserEntity};
+use crate::wallet::watch_only::WatchOnlySource;
+use std::collections::HashMap;
+
+/// Runs all checks, each on a new and empty storage created by `new_storage`.
+pub fn run_all<S: UserKvStorage>(mut new_storage: impl FnMut() -> S) {
+    let checks: [fn(&mut dyn UserKvStorage); 11] = [
+        check_set_get,
+        check_set_overwrites,
+        check_set_get_all_fields,
+        check_get_nonexistent,
+        check_exists,
+        check_not_exists,
+        check_delete_existing,
+        check_delete_nonexistent,
+        check_users_are_independent,
+        check_list_empty,
+        check_list,
+    ];
+    for check in checks {
+        let mut storage = new_storage();
+        check(&mut storage);
+    }
+}
+
+/// A stored user is loaded unchanged.
+pub fn check_set_get(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    let user = create_user_entity("username");
+
+    // Act
+    kv.set(&user.username, &user).unwrap();
+    let load = kv.get(&user.username).unwrap();
+
+    // Assert
+    assert_eq!(load, user);
+}
+
+/// Storing a user again overwrites the previous value.
+pub fn check_set_overwrites(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    let mut user = create_user_entity("username");
+
+    // Act
+    kv.set(&user.username, &user).unwrap();
+
+    user.is_kyc_verified = true;
+    kv.set(&user.username, &user).unwrap();
+
+    let load = kv.get(&user.username).unwrap();
+
+    // Assert
+    assert_eq!(load, user);
+}
+
+/// All fields of a user survive a round trip through the storage, including the optional ones.
+pub fn check_set_get_all_fields(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    let mut user = create_user_entity("username");
+    user.user_id = Some(String::from("user_id"));
+    user.local_share = Some(String::from("local_share"));
+    user.guardian_secret_key = Some(String::from("guardian_secret_key"));
+    user.guardian_recovery_key = Some(String::from("guardian_recovery_key"));
+    user.encrypted_passphrase = Some(String::from("encrypted_passphrase"));
+    user.offline_recovery_share = Some(String::from("offline_recovery_share"));
+    user.wallet_transactions = vec![wallet_tx_info("0x01")];
+    user.wallet_tx_history = HashMap::from([(
+        String::from("network_id"),
+        WalletTxHistory {
+            last_scanned_block: Some(42),
+            first_scanned_block: Some(7),
+            transactions: vec![wallet_tx_info("0x02")],
+        },
+    )]);
+    user.pin_attempts = PinAttempts {
+        failed: 2,
+        last_failed_at: Some(1_700_000_000),
+    };
+    user.evm_address_indices = vec![0, 1, 5];
+    user.pending_transactions = HashMap::from([(
+        String::from("network_id"),
+        vec![PendingTransaction {
+            transaction_hash: String::from("0x03"),
+            from: String::from("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: u128::MAX,
+            max_priority_fee_per_gas: 1,
+            raw_transaction: vec![0x02, 0xff],
+            created_at: 1_700_000_000,
+            replaces: Vec::new(),
+        }],
+    )]);
+    user.watched_transactions = vec![WatchedTransaction {
+        transaction_hash: String::from("0x03"),
+        network_id: String::from("network_id"),
+        status: WatchedTransactionStatus::Included,
+        confirmations: 3,
+    }];
+    user.watch_only = Some(WatchOnlySource::EvmAddresses(vec![String::from(
+        "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+    )]));
+    user.created_at = Some(1_600_000_000);
+    user.last_used_at = Some(1_700_000_000);
+
+    // Act
+    kv.set(&user.username, &user).unwrap();
+    let load = kv.get(&user.username).unwrap();
+
+    // Assert
+    assert_eq!(load, user);
+}
+
+/// Loading an unknown user fails with [`UserKvStorageError::UserNotFound`].
+pub fn check_get_nonexistent(kv: &mut dyn UserKvStorage) {
+    // Act
+    let load = kv.get("nonexistant_user");
+
+    // Assert
+    assert!(matches!(load.unwrap_err(), UserKvStorageError::UserNotFound { .. }));
+}
+
+/// A stored user exists.
+pub fn check_exists(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    let user = create_user_entity("username");
+    kv.set(&user.username, &user).unwrap();
+
+    // Act
+    let load = kv.exists(&user.username).unwrap();
+
+    // Assert
+    assert!(load);
+}
+
+/// An unknown user does not exist.
+pub fn check_not_exists(kv: &mut dyn UserKvStorage) {
+    // Act
+    let load = kv.exists("nonexistant_user").unwrap();
+
+    // Assert
+    assert!(!load);
+}
+
+/// A deleted user no longer exists and cannot be loaded.
+pub fn check_delete_existing(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    let user = create_user_entity("username");
+    kv.set(&user.username, &user).unwrap();
+
+    // Act
+    assert!(kv.exists(&user.username).unwrap());
+    kv.delete(&user.username).unwrap();
+
+    // Assert
+    assert!(!kv.exists(&user.username).unwrap());
+    assert!(kv.get(&user.username).is_err());
+}
+
+/// Deleting an unknown user succeeds.
+pub fn check_delete_nonexistent(kv: &mut dyn UserKvStorage) {
+    // Act
+    assert!(!kv.exists("nonexistant_user").unwrap());
+    kv.delete("nonexistant_user").unwrap();
+
+    // Assert
+    assert!(!kv.exists("nonexistant_user").unwrap());
+}
+
+/// Storing, overwriting and deleting a user does not affect other users.
+pub fn check_users_are_independent(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    let alice = create_user_entity("alice");
+    let mut bob = create_user_entity("bob");
+    kv.set(&alice.username, &alice).unwrap();
+    kv.set(&bob.username, &bob).unwrap();
+
+    // Act
+    bob.is_kyc_verified = true;
+    kv.set(&bob.username, &bob).unwrap();
+    let alice_after_update = kv.get(&alice.username).unwrap();
+    kv.delete(&alice.username).unwrap();
+
+    // Assert
+    assert_eq!(alice_after_update, alice);
+    assert!(!kv.exists(&alice.username).unwrap());
+    assert_eq!(kv.get(&bob.username).unwrap(), bob);
+}
+
+/// An empty storage lists no users.
+pub fn check_list_empty(kv: &mut dyn UserKvStorage) {
+    // Act
+    let usernames = kv.list().unwrap();
+
+    // Assert
+    assert!(usernames.is_empty());
+}
+
+/// All stored users are listed once, deleted users are not listed.
+pub fn check_list(kv: &mut dyn UserKvStorage) {
+    // Arrange
+    for username in ["alice", "bob", "carol"] {
+        kv.set(username, &create_user_entity(username)).unwrap();
+    }
+    kv.set("alice", &create_user_entity("alice")).unwrap();
+    kv.delete("bob").unwrap();
+
+    // Act
+    let mut usernames = kv.list().unwrap();
+
+    // Assert
+    usernames.sort();
+    assert_eq!(usernames, ["alice", "carol"]);
+}
+
+fn create_user_entity(username: &str) -> UserEntity {
+    UserEntity {
+        user_id: None,
+        username: username.to_string(),
+        encrypted_password: None,
+        salt: EncryptionSalt::generate(),
+        is_kyc_verified: false,
+        kyc_type: KycType::Undefined,
+        viviswap_state: None,
+        local_share: None,
+        wallet_transactions: Vec::new(),
+        wallet_tx_history: Default::default(),
+        pin_attempts: Default::default(),
+        evm_address_indices: Vec::new(),
+        pending_transactions: Default::default(),
+        watched_transactions: Vec::new(),
+        watch_only: None,
+        created_at: None,
+        last_used_at: None,
+        guardian_secret_key: None,
+        guardian_recovery_key: None,
+        encrypted_passphrase: None,
+        offline_recovery_share: None,
+    }
+}
+
+fn wallet_tx_info(transaction_id: &str) -> WalletTxInfo {
+    WalletTxInfo {
+        date: String::from("2024-01-01T00:00:00+00:00"),
+        block_id: Some(String::from("12")),
+        transaction_id: transaction_id.to_string(),
+        log_index: None,
+        incoming: true,
+        receiver: String::from("0xb0b0000000000000000000000000000000000000"),
+        amount: 1.5,
+        network: String::from("ETH"),
+        status: String::from("Confirmed"),
+        explorer_url: None,
+    }
+}
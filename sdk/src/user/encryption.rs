@@ -0,0 +1,457 @@
+//! Encryption at rest of the user records stored by the file and web storage.
+//!
+//! Each record is encrypted with AES-256-GCM, a fresh random nonce and the username as associated data, so
+//! a record cannot be moved to another user. The key is either provided by the host application through a
+//! [`UserStorageKeyProvider`] (e.g. from the platform keychain), or derived from the pin of the user.
+//!
+//! With the pin, the key is derived with a random salt, which is stored in clear with each record and replaced
+//! whenever the key changes. The key is only known after the pin was entered. Until then the encrypted record
+//! of the user cannot be read and fails with [`UserKvStorageError::Locked`]. Records of users that have not
+//! entered their pin yet, e.g. after [`Sdk::create_new_user`](crate::core::Sdk::create_new_user), are stored
+//! unencrypted. Failed attempts to unlock a record are counted in clear next to it, see
+//! [`super::UserKvStorage::set_unlock_attempts`], and limited like wrong pins of an unlocked user.
+
+use super::error::{Result, UserKvStorageError};
+use crate::types::newtypes::{EncryptionPin, EncryptionSalt, UserStorageKey};
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use rand::RngCore;
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+/// Length of the AES-256-GCM nonce.
+pub(crate) const NONCE_LEN: usize = 12;
+/// Length of the salt of the key derived from the pin.
+pub(crate) const SALT_LEN: usize = 12;
+
+/// Provides the keys to encrypt the user records, e.g. from the keychain of the platform.
+///
+/// The keys are identified by an id, which is stored in clear with each record. Keys that were
+/// rotated must therefore still be returned by [`UserStorageKeyProvider::key`] until all records are
+/// written again.
+pub trait UserStorageKeyProvider: Send + Sync {
+    /// Returns the id and the key used to encrypt new records of the user.
+    fn current_key(&self, username: &str) -> Result<(u32, UserStorageKey)>;
+
+    /// Returns the key with the id to decrypt a record of the user.
+    fn key(&self, username: &str, key_id: u32) -> Result<UserStorageKey>;
+
+    /// Replaces the current key of the user with a new one. Called when the user changes the pin,
+    /// before the record of the user is written again.
+    fn rotate_key(&self, username: &str) -> Result<()>;
+}
+
+/// The source of the key used to encrypt the user records.
+pub enum UserStorageEncryption {
+    /// Keys provided by the host application.
+    KeyProvider(Box<dyn UserStorageKeyProvider>),
+    /// A key derived from the pin of the user.
+    Pin,
+}
+
+impl core::fmt::Debug for UserStorageEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KeyProvider(_) => write!(f, "KeyProvider"),
+            Self::Pin => write!(f, "Pin"),
+        }
+    }
+}
+
+/// Reference to the key a record is encrypted with, stored in clear with the record.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum KeyRef {
+    /// The key derived from the pin of the user with the salt
+    Pin { salt: [u8; SALT_LEN] },
+    /// The key with the id of the host key provider
+    Provider(u32),
+}
+
+/// An encrypted user record.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EncryptedPayload {
+    pub key: KeyRef,
+    pub nonce: [u8; NONCE_LEN],
+    pub ciphertext: Vec<u8>,
+}
+
+/// A key derived from the pin and the salt it was derived with.
+#[derive(Clone)]
+struct PinKey {
+    salt: [u8; SALT_LEN],
+    key: UserStorageKey,
+}
+
+impl PinKey {
+    /// Derives the key of the pin with the salt.
+    fn derive(pin: &EncryptionPin, salt: [u8; SALT_LEN]) -> Result<Self> {
+        let key = pin
+            .derive_user_storage_key(&EncryptionSalt::from(salt))
+            .map_err(|e| UserKvStorageError::Storage(format!("could not derive the user storage key: {e}")))?;
+        Ok(Self { salt, key })
+    }
+
+    /// Derives the key of the pin with a new random salt.
+    fn generate(pin: &EncryptionPin) -> Result<Self> {
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill_bytes(&mut salt);
+        Self::derive(pin, salt)
+    }
+}
+
+/// Keys derived from the pin, per username.
+#[derive(Default)]
+struct PinKeys {
+    /// Keys of pins that are known to be correct
+    unlocked: HashMap<String, PinKey>,
+    /// Pins that were entered but are not verified yet
+    offered: HashMap<String, EncryptionPin>,
+    /// Keys of pins that were replaced by a new pin, to read records not written again yet
+    previous: HashMap<String, PinKey>,
+}
+
+/// Encrypts and decrypts the user records, shared by the [`crate::core::Sdk`] and the user storage.
+pub(crate) struct UserStorageCipher {
+    provider: Option<Box<dyn UserStorageKeyProvider>>,
+    pin_keys: Mutex<PinKeys>,
+}
+
+impl core::fmt::Debug for UserStorageCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UserStorageCipher(<REDACTED>)")
+    }
+}
+
+impl UserStorageCipher {
+    /// Create a new cipher using the keys of the encryption.
+    pub(crate) fn new(encryption: UserStorageEncryption) -> Self {
+        let provider = match encryption {
+            UserStorageEncryption::KeyProvider(provider) => Some(provider),
+            UserStorageEncryption::Pin => None,
+        };
+        Self {
+            provider,
+            pin_keys: Mutex::default(),
+        }
+    }
+
+    /// Returns `true` if the keys are derived from the pin.
+    pub(crate) fn uses_pin(&self) -> bool {
+        self.provider.is_none()
+    }
+
+    /// Encrypts a record of the user. Returns `None` if the key is derived from the pin and the pin was
+    /// not entered yet.
+    pub(crate) fn encrypt(&self, username: &str, plaintext: &[u8]) -> Result<Option<EncryptedPayload>> {
+        let (key_ref, key) = match &self.provider {
+            Some(provider) => {
+                let (key_id, key) = provider.current_key(username)?;
+                (KeyRef::Provider(key_id), key)
+            }
+            None => match self.pin_keys().unlocked.get(username) {
+                Some(pin_key) => (KeyRef::Pin { salt: pin_key.salt }, pin_key.key.clone()),
+                None => return Ok(None),
+            },
+        };
+
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_bytes()))
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: username.as_bytes(),
+                },
+            )
+            .map_err(|_| UserKvStorageError::Storage(format!("could not encrypt the record of {username}")))?;
+
+        Ok(Some(EncryptedPayload {
+            key: key_ref,
+            nonce,
+            ciphertext,
+        }))
+    }
+
+    /// Decrypts a record of the user. A pin that was entered but not verified yet is verified by a
+    /// successful decryption with the key derived from the pin and the salt of the record.
+    pub(crate) fn decrypt(&self, username: &str, payload: &EncryptedPayload) -> Result<Vec<u8>> {
+        match (&self.provider, payload.key) {
+            (Some(provider), KeyRef::Provider(key_id)) => {
+                let key = provider.key(username, key_id)?;
+                decrypt_with(&key, username, payload).ok_or_else(|| {
+                    UserKvStorageError::Storage(format!("could not decrypt the record of {username} with key {key_id}"))
+                })
+            }
+            (None, KeyRef::Pin { salt }) => {
+                let offered = {
+                    let mut pin_keys = self.pin_keys();
+                    if let Some(plaintext) = [pin_keys.unlocked.get(username), pin_keys.previous.get(username)]
+                        .into_iter()
+                        .flatten()
+                        .filter(|pin_key| pin_key.salt == salt)
+                        .find_map(|pin_key| decrypt_with(&pin_key.key, username, payload))
+                    {
+                        return Ok(plaintext);
+                    }
+                    pin_keys.offered.remove(username)
+                };
+
+                // derive the key without holding the lock, the key derivation is slow on purpose
+                let Some(pin) = offered else {
+                    return Err(UserKvStorageError::Locked {
+                        username: username.to_string(),
+                    });
+                };
+                let pin_key = PinKey::derive(&pin, salt)?;
+                match decrypt_with(&pin_key.key, username, payload) {
+                    Some(plaintext) => {
+                        self.pin_keys().unlocked.insert(username.to_string(), pin_key);
+                        Ok(plaintext)
+                    }
+                    None => Err(UserKvStorageError::Locked {
+                        username: username.to_string(),
+                    }),
+                }
+            }
+            _ => Err(UserKvStorageError::Locked {
+                username: username.to_string(),
+            }),
+        }
+    }
+
+    /// Offers the pin entered by the user to unlock the record. The pin is only used to encrypt records
+    /// after it was verified, see [`UserStorageCipher::confirm_pin`].
+    pub(crate) fn offer_pin(&self, username: &str, pin: &EncryptionPin) {
+        if self.uses_pin() {
+            self.pin_keys().offered.insert(username.to_string(), pin.clone());
+        }
+    }
+
+    /// Marks the offered pin as verified and derives the key for new records with a random salt. Returns
+    /// `true` if the records of the user were not unlocked before.
+    pub(crate) fn confirm_pin(&self, username: &str) -> Result<bool> {
+        let offered = {
+            let mut pin_keys = self.pin_keys();
+            let offered = pin_keys.offered.remove(username);
+            if pin_keys.unlocked.contains_key(username) {
+                return Ok(false);
+            }
+            offered
+        };
+        let Some(pin) = offered else {
+            return Ok(false);
+        };
+
+        let pin_key = PinKey::generate(&pin)?;
+        Ok(self.pin_keys().unlocked.insert(username.to_string(), pin_key).is_none())
+    }
+
+    /// Forgets the offered pin, e.g. because it was wrong.
+    pub(crate) fn discard_pin(&self, username: &str) {
+        self.pin_keys().offered.remove(username);
+    }
+
+    /// Forgets all keys derived from pins, so the records have to be unlocked again.
+    pub(crate) fn lock(&self) {
+        let mut pin_keys = self.pin_keys();
+        pin_keys.unlocked.clear();
+        pin_keys.offered.clear();
+        pin_keys.previous.clear();
+    }
+
+    /// Replaces the key used for new records of the user, derived from the new pin with a new random salt.
+    /// Records encrypted with the previous key stay readable until they are written again.
+    pub(crate) fn rotate_key(&self, username: &str, new_pin: &EncryptionPin) -> Result<()> {
+        match &self.provider {
+            Some(provider) => provider.rotate_key(username),
+            None => {
+                let pin_key = PinKey::generate(new_pin)?;
+                let mut pin_keys = self.pin_keys();
+                if let Some(previous) = pin_keys.unlocked.insert(username.to_string(), pin_key) {
+                    pin_keys.previous.insert(username.to_string(), previous);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn pin_keys(&self) -> std::sync::MutexGuard<'_, PinKeys> {
+        self.pin_keys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn decrypt_with(key: &UserStorageKey, username: &str, payload: &EncryptedPayload) -> Option<Vec<u8>> {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_bytes()))
+        .decrypt(
+            Nonce::from_slice(&payload.nonce),
+            Payload {
+                msg: &payload.ciphertext,
+                aad: username.as_bytes(),
+            },
+        )
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_utils::PIN;
+
+    struct RotatingKeyProvider {
+        keys: Mutex<Vec<UserStorageKey>>,
+    }
+
+    impl UserStorageKeyProvider for RotatingKeyProvider {
+        fn current_key(&self, _username: &str) -> Result<(u32, UserStorageKey)> {
+            let keys = self.keys.lock().unwrap();
+            Ok(((keys.len() - 1) as u32, keys[keys.len() - 1].clone()))
+        }
+
+        fn key(&self, _username: &str, key_id: u32) -> Result<UserStorageKey> {
+            self.keys
+                .lock()
+                .unwrap()
+                .get(key_id as usize)
+                .cloned()
+                .ok_or_else(|| UserKvStorageError::Storage(String::from("unknown key")))
+        }
+
+        fn rotate_key(&self, _username: &str) -> Result<()> {
+            self.keys.lock().unwrap().push(UserStorageKey::generate());
+            Ok(())
+        }
+    }
+
+    fn provider_cipher() -> UserStorageCipher {
+        UserStorageCipher::new(UserStorageEncryption::KeyProvider(Box::new(RotatingKeyProvider {
+            keys: Mutex::new(vec![UserStorageKey::generate()]),
+        })))
+    }
+
+    #[test]
+    fn test_provider_encrypt_decrypt_and_rotate() {
+        // Arrange
+        let cipher = provider_cipher();
+        let old = cipher.encrypt("alice", b"record").unwrap().unwrap();
+
+        // Act
+        cipher.rotate_key("alice", &PIN).unwrap();
+        let new = cipher.encrypt("alice", b"record").unwrap().unwrap();
+
+        // Assert
+        assert_eq!(old.key, KeyRef::Provider(0));
+        assert_eq!(new.key, KeyRef::Provider(1));
+        assert_eq!(cipher.decrypt("alice", &old).unwrap(), b"record");
+        assert_eq!(cipher.decrypt("alice", &new).unwrap(), b"record");
+        assert_ne!(old.ciphertext, new.ciphertext);
+    }
+
+    #[test]
+    fn test_record_of_another_user_is_rejected() {
+        // Arrange
+        let cipher = provider_cipher();
+        let payload = cipher.encrypt("alice", b"record").unwrap().unwrap();
+
+        // Act
+        let result = cipher.decrypt("bob", &payload);
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+
+    #[test]
+    fn test_pin_is_needed_to_encrypt_and_decrypt() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+
+        // Act & Assert: nothing is encrypted before the pin is verified
+        assert!(cipher.encrypt("alice", b"record").unwrap().is_none());
+        cipher.offer_pin("alice", &PIN);
+        assert!(cipher.encrypt("alice", b"record").unwrap().is_none());
+        assert!(cipher.confirm_pin("alice").unwrap());
+        let payload = cipher.encrypt("alice", b"record").unwrap().unwrap();
+
+        // Act & Assert: after locking, only the correct pin unlocks the record again
+        cipher.lock();
+        assert!(matches!(
+            cipher.decrypt("alice", &payload),
+            Err(UserKvStorageError::Locked { .. })
+        ));
+        cipher.offer_pin("alice", &EncryptionPin::try_from_string("54321").unwrap());
+        assert!(matches!(
+            cipher.decrypt("alice", &payload),
+            Err(UserKvStorageError::Locked { .. })
+        ));
+        cipher.offer_pin("alice", &PIN);
+        assert_eq!(cipher.decrypt("alice", &payload).unwrap(), b"record");
+        assert!(cipher.encrypt("alice", b"record").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_rotate_pin_key() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        let new_pin = EncryptionPin::try_from_string("54321").unwrap();
+        cipher.offer_pin("alice", &PIN);
+        cipher.confirm_pin("alice").unwrap();
+
+        let old_payload = cipher.encrypt("alice", b"old record").unwrap().unwrap();
+
+        // Act
+        cipher.rotate_key("alice", &new_pin).unwrap();
+        let payload = cipher.encrypt("alice", b"record").unwrap().unwrap();
+
+        // Assert
+        assert_eq!(cipher.decrypt("alice", &old_payload).unwrap(), b"old record");
+        cipher.lock();
+        cipher.offer_pin("alice", &PIN);
+        assert!(cipher.decrypt("alice", &payload).is_err());
+        cipher.offer_pin("alice", &new_pin);
+        assert_eq!(cipher.decrypt("alice", &payload).unwrap(), b"record");
+    }
+
+    #[test]
+    fn test_pin_key_is_derived_with_a_random_salt() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        for username in ["alice", "bob"] {
+            cipher.offer_pin(username, &PIN);
+            cipher.confirm_pin(username).unwrap();
+        }
+
+        // Act
+        let alice = cipher.encrypt("alice", b"record").unwrap().unwrap();
+        let bob = cipher.encrypt("bob", b"record").unwrap().unwrap();
+        cipher.rotate_key("alice", &PIN).unwrap();
+        let rotated = cipher.encrypt("alice", b"record").unwrap().unwrap();
+
+        // Assert: the same pin gives different salts per user and per key
+        let salt = |payload: &EncryptedPayload| match payload.key {
+            KeyRef::Pin { salt } => salt,
+            KeyRef::Provider(_) => panic!("expected a pin key"),
+        };
+        assert_ne!(salt(&alice), salt(&bob));
+        assert_ne!(salt(&alice), salt(&rotated));
+    }
+
+    #[test]
+    fn test_pin_record_with_another_salt_is_locked() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        cipher.offer_pin("alice", &PIN);
+        cipher.confirm_pin("alice").unwrap();
+        let mut payload = cipher.encrypt("alice", b"record").unwrap().unwrap();
+        payload.key = KeyRef::Pin { salt: [0; SALT_LEN] };
+
+        // Act
+        cipher.lock();
+        cipher.offer_pin("alice", &PIN);
+        let result = cipher.decrypt("alice", &payload);
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Locked { .. })));
+    }
+}
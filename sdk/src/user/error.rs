@@ -1,15 +1,47 @@
 /// A [`core::result::Result`] with [`UserKvStorageError`] as its error variant.
 pub type Result<T> = core::result::Result<T, UserKvStorageError>;
 
+/// Errors of the [`UserKvStorage`](super::UserKvStorage) and the user repository
 #[derive(thiserror::Error, Debug)]
 pub enum UserKvStorageError {
     /// The user already exists in the KV storage.
     #[error("User already exists: {username}")]
-    UserAlreadyExists { username: String },
+    UserAlreadyExists {
+        /// The username of the existing user
+        username: String,
+    },
 
     /// The user is not found in the KV storage,
     #[error("User not found: {username}")]
-    UserNotFound { username: String },
+    UserNotFound {
+        /// The username that was looked up
+        username: String,
+    },
+
+    /// The stored user was written by a newer SDK version with an unknown record format.
+    #[error("Unsupported user record version {version}, this SDK supports up to version {supported}")]
+    UnsupportedSchemaVersion {
+        /// The version of the stored record
+        version: u32,
+        /// The latest record version supported by this SDK
+        supported: u32,
+    },
+
+    /// The record of the user is encrypted and the key to decrypt it is not available.
+    #[error("The stored user {username} is locked, the key to decrypt it is not available")]
+    Locked {
+        /// The username of the locked user
+        username: String,
+    },
+
+    /// The exported user could not be decrypted, either the export password or the username is wrong or the
+    /// export was modified.
+    #[error("Wrong export password or username, or corrupted export")]
+    WrongExportPassword,
+
+    /// The storage quota of the browser is exceeded.
+    #[error("The storage quota is exceeded")]
+    QuotaExceeded,
 
     /// An internal storage error happened (backend specific)
     #[error("Internal storage error: {0}")]
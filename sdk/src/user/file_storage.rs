@@ -2,11 +2,13 @@
 //! This is mainly used on systems that have access to the file system as it provides a persistent
 //! storage mechanism.
 
+use super::encryption::UserStorageCipher;
 use super::error::{Result, UserKvStorageError};
-use crate::types::users::UserEntity;
+use crate::types::users::{PinAttempts, UserEntity};
 use jammdb::DB;
 use log::warn;
 use std::path::Path;
+use std::sync::Arc;
 
 #[doc = r"The default name of the local memory mapped DB"]
 const SHARED_DB_NAME: &str = "sdk-user.db";
@@ -16,15 +18,19 @@ const DB_BUCKET: &str = "users";
 /// Implementation of [`super::UserKvStorage`] using a [`jammdb`] file-based database.
 pub struct FileUserStorage {
     db: DB,
+    cipher: Option<Arc<UserStorageCipher>>,
 }
 
 impl FileUserStorage {
-    /// Initialize a new instance and create a db file
-    pub fn new(path_prefix: &Path) -> Result<Self> {
+    /// Initialize a new instance and create a db file. The records are encrypted with the cipher, if any.
+    pub fn new(path_prefix: &Path, cipher: Option<Arc<UserStorageCipher>>) -> Result<Self> {
         let path = path_prefix.join(SHARED_DB_NAME);
         warn!("Attempting to create user DB in path: {path:?}");
 
-        Ok(Self { db: DB::open(path)? })
+        Ok(Self {
+            db: DB::open(path)?,
+            cipher,
+        })
     }
 }
 
@@ -47,9 +53,7 @@ impl super::UserKvStorage for FileUserStorage {
                 username: username.to_owned(),
             })?;
 
-        let user: UserEntity = rmp_serde::from_slice(kv.value())?;
-
-        Ok(user)
+        super::record::decode(username, kv.value(), self.cipher.as_deref())
     }
 
     fn delete(&mut self, username: &str) -> Result<()> {
@@ -78,8 +82,9 @@ impl super::UserKvStorage for FileUserStorage {
         let tx = self.db.tx(true)?;
         let users_bucket = tx.get_or_create_bucket(DB_BUCKET)?;
 
-        // serialize struct to bytes and store in bucket
-        let user_bytes = rmp_serde::to_vec(&value)?;
+        // serialize struct to a versioned record and store in bucket
+        let previous = users_bucket.get_kv(username).map(|kv| kv.value().to_vec());
+        let user_bytes = super::record::encode(username, value, self.cipher.as_deref(), previous.as_deref())?;
         users_bucket.put(username, user_bytes)?;
 
         // commit the changes so they are saved to disk
@@ -87,4 +92,106 @@ impl super::UserKvStorage for FileUserStorage {
 
         Ok(())
     }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let tx = self.db.tx(true)?;
+        let users_bucket = tx.get_or_create_bucket(DB_BUCKET)?;
+
+        // the keys are the usernames, which were stored as strings
+        users_bucket
+            .kv_pairs()
+            .map(|kv| {
+                String::from_utf8(kv.key().to_vec())
+                    .map_err(|e| UserKvStorageError::Storage(format!("username is not valid UTF-8: {e}")))
+            })
+            .collect()
+    }
+
+    fn unlock_attempts(&self, username: &str) -> Result<PinAttempts> {
+        let tx = self.db.tx(true)?;
+        let users_bucket = tx.get_or_create_bucket(DB_BUCKET)?;
+        match users_bucket.get_kv(username) {
+            Some(kv) => super::record::unlock_attempts(kv.value()),
+            None => Ok(PinAttempts::default()),
+        }
+    }
+
+    fn set_unlock_attempts(&mut self, username: &str, attempts: PinAttempts) -> Result<()> {
+        let tx = self.db.tx(true)?;
+        let users_bucket = tx.get_or_create_bucket(DB_BUCKET)?;
+
+        let kv = users_bucket
+            .get_kv(username)
+            .ok_or_else(|| UserKvStorageError::UserNotFound {
+                username: username.to_owned(),
+            })?;
+        let user_bytes = super::record::with_unlock_attempts(kv.value(), attempts)?;
+        users_bucket.put(username, user_bytes)?;
+
+        // commit the changes so they are saved to disk
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::newtypes::EncryptionSalt;
+    use crate::types::users::KycType;
+    use crate::user::UserKvStorage;
+    use testing::CleanUp;
+
+    #[test]
+    fn test_get_upgrades_legacy_record_and_set_writes_current_version() {
+        // Arrange: store a user in the positional format written before the records were versioned
+        let cleanup = CleanUp::default();
+        let mut storage = FileUserStorage::new(Path::new(&cleanup.path_prefix), None).unwrap();
+        let user = UserEntity {
+            user_id: None,
+            username: String::from("alice"),
+            encrypted_password: None,
+            salt: EncryptionSalt::from([7; 12]),
+            is_kyc_verified: true,
+            kyc_type: KycType::Undefined,
+            viviswap_state: None,
+            local_share: Some(String::from("share")),
+            wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: vec![0, 2],
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
+        };
+        {
+            let tx = storage.db.tx(true).unwrap();
+            let users_bucket = tx.get_or_create_bucket(DB_BUCKET).unwrap();
+            let legacy = crate::user::record::encode_v0_positional(&user);
+            users_bucket.put("alice", legacy).unwrap();
+            tx.commit().unwrap();
+        }
+
+        // Act
+        let loaded = storage.get("alice").unwrap();
+        storage.set("alice", &loaded).unwrap();
+
+        // Assert
+        assert_eq!(loaded, user);
+        let tx = storage.db.tx(false).unwrap();
+        let stored = tx
+            .get_bucket(DB_BUCKET)
+            .unwrap()
+            .get_kv("alice")
+            .unwrap()
+            .value()
+            .to_vec();
+        assert_eq!(stored, crate::user::record::encode("alice", &user, None, None).unwrap());
+    }
 }
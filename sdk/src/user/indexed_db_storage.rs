@@ -0,0 +1,429 @@
+//! An implementation of [`super::UserKvStorage`] that stores the user records in the IndexedDB of the browser.
+//!
+//! IndexedDB is only accessible asynchronously, while [`super::UserKvStorage`] is synchronous. The SDK therefore
+//! re-reads all records with [`IndexedDbConnection::reload`] before each call of the bindings, reads are served
+//! from these records and writes update them and are queued to be written to the IndexedDB in order. After the
+//! call, the SDK waits with [`IndexedDbConnection::flush`] until the writes are stored, and reports a failed
+//! write, e.g. because the storage quota is exceeded. Re-reading the records before each call keeps the changes
+//! another tab or worker made in the meantime instead of overwriting them with an outdated record.
+//!
+//! In contrast to the browser local storage, IndexedDB is available in Web Workers and service workers and is
+//! not limited to a few megabytes. Users stored in the local storage by earlier SDK versions are moved to the
+//! IndexedDB when it is opened.
+
+use super::encryption::UserStorageCipher;
+use super::error::{Result, UserKvStorageError};
+use crate::types::users::{PinAttempts, UserEntity};
+use base64::prelude::*;
+use futures::channel::{mpsc, oneshot};
+use futures::StreamExt;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{js_sys, IdbDatabase, IdbFactory, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+/// The default name of the IndexedDB database
+pub const DEFAULT_DB_NAME: &str = "etopay";
+/// The name of the object store holding the user records
+const STORE_NAME: &str = "users";
+/// The version of the database layout, only increased when object stores change
+const DB_VERSION: u32 = 1;
+/// The prefix of the local storage keys used by the `BrowserLocalStorage`
+const LOCAL_STORAGE_KEY_PREFIX: &str = "etopay.local.user.";
+/// The name of the exception raised by the browser if the storage quota is exceeded
+const QUOTA_EXCEEDED_ERROR: &str = "QuotaExceededError";
+
+/// The records of all users, by username
+type Records = HashMap<String, Vec<u8>>;
+
+/// A request to the task accessing the IndexedDB, processed in order
+enum Request {
+    Put { username: String, bytes: Vec<u8> },
+    Delete { username: String },
+    Flush(oneshot::Sender<()>),
+    Load(oneshot::Sender<Result<Records>>),
+}
+
+/// The connection to the IndexedDB, shared by the storage and the SDK to re-read the records and to wait for
+/// the queued writes.
+#[derive(Clone)]
+pub(crate) struct IndexedDbConnection {
+    requests: mpsc::UnboundedSender<Request>,
+    records: Arc<Mutex<Records>>,
+    error: Arc<Mutex<Option<UserKvStorageError>>>,
+}
+
+impl IndexedDbConnection {
+    fn send(&self, request: Request) -> Result<()> {
+        self.requests
+            .unbounded_send(request)
+            .map_err(|_| UserKvStorageError::Storage(String::from("the IndexedDB connection stopped")))
+    }
+
+    fn records(&self) -> MutexGuard<'_, Records> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the error of a previous write once.
+    fn take_error(&self) -> Result<()> {
+        match self.error.lock().unwrap_or_else(PoisonError::into_inner).take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Waits until all queued writes are stored and returns the error of a failed write.
+    pub(crate) async fn flush(&self) -> Result<()> {
+        let (done, flushed) = oneshot::channel();
+        self.send(Request::Flush(done))?;
+        flushed
+            .await
+            .map_err(|_| UserKvStorageError::Storage(String::from("the IndexedDB connection stopped")))?;
+        self.take_error()
+    }
+
+    /// Waits until all queued writes are stored and reads all records again, so the changes of other tabs
+    /// and workers are seen. Returns the error of a failed write.
+    pub(crate) async fn reload(&self) -> Result<()> {
+        let (done, loaded) = oneshot::channel();
+        self.send(Request::Load(done))?;
+        let records = loaded
+            .await
+            .map_err(|_| UserKvStorageError::Storage(String::from("the IndexedDB connection stopped")))??;
+        *self.records() = records;
+        self.take_error()
+    }
+}
+
+/// Implementation of [`super::UserKvStorage`] using the IndexedDB of the browser.
+pub struct IndexedDbUserStorage {
+    connection: IndexedDbConnection,
+    cipher: Option<Arc<UserStorageCipher>>,
+}
+
+impl IndexedDbUserStorage {
+    /// Opens the IndexedDB database with the name, loads all users and moves the users of the browser local
+    /// storage into it. The records are encrypted with the cipher, if any.
+    pub(crate) async fn open(db_name: &str, cipher: Option<Arc<UserStorageCipher>>) -> Result<Self> {
+        let db = open_database(db_name).await?;
+        let mut records = load_records(&db).await?;
+
+        if let Err(e) = migrate_local_storage(&db, &mut records).await {
+            warn!("Could not move the users of the local storage to the IndexedDB: {e}");
+        }
+
+        let (requests, pending) = mpsc::unbounded();
+        let error = Arc::new(Mutex::new(None));
+        wasm_bindgen_futures::spawn_local(process_requests(db, pending, error.clone()));
+
+        Ok(Self {
+            connection: IndexedDbConnection {
+                requests,
+                records: Arc::new(Mutex::new(records)),
+                error,
+            },
+            cipher,
+        })
+    }
+
+    /// Opens the IndexedDB database with the name without encryption, e.g. for tests of the storage.
+    pub async fn open_unencrypted(db_name: &str) -> Result<Self> {
+        Self::open(db_name, None).await
+    }
+
+    /// Waits until all queued writes are stored in the IndexedDB. Returns the error of a failed write, e.g.
+    /// [`UserKvStorageError::QuotaExceeded`].
+    pub async fn flush(&self) -> Result<()> {
+        self.connection.flush().await
+    }
+
+    /// Waits until all queued writes are stored in the IndexedDB and reads all users again, e.g. to see the
+    /// changes of another tab.
+    pub async fn reload(&self) -> Result<()> {
+        self.connection.reload().await
+    }
+
+    /// Returns the connection, which stays usable after the storage is moved into the repository.
+    pub(crate) fn connection(&self) -> IndexedDbConnection {
+        self.connection.clone()
+    }
+
+    /// Stores the record in memory and queues it to be written.
+    fn put(&mut self, username: &str, bytes: Vec<u8>) -> Result<()> {
+        self.connection.records().insert(username.to_owned(), bytes.clone());
+        self.connection.send(Request::Put {
+            username: username.to_owned(),
+            bytes,
+        })
+    }
+}
+
+impl super::UserKvStorage for IndexedDbUserStorage {
+    fn get(&self, username: &str) -> Result<UserEntity> {
+        let records = self.connection.records();
+        let bytes = records.get(username).ok_or_else(|| UserKvStorageError::UserNotFound {
+            username: username.to_owned(),
+        })?;
+        super::record::decode(username, bytes, self.cipher.as_deref())
+    }
+
+    fn delete(&mut self, username: &str) -> Result<()> {
+        self.connection.take_error()?;
+        if self.connection.records().remove(username).is_some() {
+            self.connection.send(Request::Delete {
+                username: username.to_owned(),
+            })?;
+        }
+        Ok(())
+    }
+
+    fn exists(&self, username: &str) -> Result<bool> {
+        Ok(self.connection.records().contains_key(username))
+    }
+
+    fn set(&mut self, username: &str, value: &UserEntity) -> Result<()> {
+        self.connection.take_error()?;
+        let bytes = {
+            let records = self.connection.records();
+            let previous = records.get(username).map(Vec::as_slice);
+            super::record::encode(username, value, self.cipher.as_deref(), previous)?
+        };
+        self.put(username, bytes)
+    }
+
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(self.connection.records().keys().cloned().collect())
+    }
+
+    fn unlock_attempts(&self, username: &str) -> Result<PinAttempts> {
+        match self.connection.records().get(username) {
+            Some(bytes) => super::record::unlock_attempts(bytes),
+            None => Ok(PinAttempts::default()),
+        }
+    }
+
+    fn set_unlock_attempts(&mut self, username: &str, attempts: PinAttempts) -> Result<()> {
+        self.connection.take_error()?;
+        let bytes = {
+            let records = self.connection.records();
+            let bytes = records.get(username).ok_or_else(|| UserKvStorageError::UserNotFound {
+                username: username.to_owned(),
+            })?;
+            super::record::with_unlock_attempts(bytes, attempts)?
+        };
+        self.put(username, bytes)
+    }
+}
+
+/// Processes the requests one after another, so writes are applied in order and records are only read after
+/// the writes queued before.
+async fn process_requests(
+    db: IdbDatabase,
+    mut pending: mpsc::UnboundedReceiver<Request>,
+    error: Arc<Mutex<Option<UserKvStorageError>>>,
+) {
+    while let Some(request) = pending.next().await {
+        let result = match request {
+            Request::Put { username, bytes } => put_records(&db, &[(username, bytes)]).await,
+            Request::Delete { username } => delete_record(&db, &username).await,
+            Request::Flush(done) => {
+                let _ = done.send(());
+                continue;
+            }
+            Request::Load(done) => {
+                let _ = done.send(load_records(&db).await);
+                continue;
+            }
+        };
+        if let Err(e) = result {
+            warn!("Could not write a user to the IndexedDB: {e}");
+            *error.lock().unwrap_or_else(PoisonError::into_inner) = Some(e);
+        }
+    }
+}
+
+/// Returns the IndexedDB factory of the window or the worker.
+fn indexed_db() -> Result<IdbFactory> {
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("indexedDB"))
+        .ok()
+        .and_then(|factory| factory.dyn_into::<IdbFactory>().ok())
+        .ok_or_else(|| UserKvStorageError::Storage(String::from("IndexedDB is not available")))
+}
+
+async fn open_database(db_name: &str) -> Result<IdbDatabase> {
+    let request = indexed_db()?
+        .open_with_u32(db_name, DB_VERSION)
+        .map_err(|e| storage_error("could not open the IndexedDB", &e))?;
+
+    // create the object store when the database is created
+    let upgrade_request = request.clone();
+    let on_upgrade_needed = Closure::<dyn FnMut(JsValue)>::new(move |_event: JsValue| {
+        let db = upgrade_request
+            .result()
+            .ok()
+            .and_then(|db| db.dyn_into::<IdbDatabase>().ok());
+        if let Some(db) = db {
+            if let Err(e) = db.create_object_store(STORE_NAME) {
+                warn!("Could not create the IndexedDB object store: {e:?}");
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+
+    let result = await_request(&request).await;
+    request.set_onupgradeneeded(None);
+    result?
+        .dyn_into::<IdbDatabase>()
+        .map_err(|e| storage_error("the IndexedDB is not a database", &e))
+}
+
+async fn load_records(db: &IdbDatabase) -> Result<Records> {
+    let transaction = transaction(db, IdbTransactionMode::Readonly)?;
+    let store = transaction
+        .object_store(STORE_NAME)
+        .map_err(|e| storage_error("could not access the IndexedDB object store", &e))?;
+
+    // keys and values are both sorted by the key
+    let keys = store
+        .get_all_keys()
+        .map_err(|e| storage_error("could not load the IndexedDB keys", &e))?;
+    let keys = js_sys::Array::from(&await_request(&keys).await?);
+    let values = store
+        .get_all()
+        .map_err(|e| storage_error("could not load the IndexedDB values", &e))?;
+    let values = js_sys::Array::from(&await_request(&values).await?);
+
+    Ok(keys
+        .iter()
+        .zip(values.iter())
+        .filter_map(|(key, value)| Some((key.as_string()?, js_sys::Uint8Array::new(&value).to_vec())))
+        .collect())
+}
+
+async fn put_records(db: &IdbDatabase, records: &[(String, Vec<u8>)]) -> Result<()> {
+    let transaction = transaction(db, IdbTransactionMode::Readwrite)?;
+    let store = transaction
+        .object_store(STORE_NAME)
+        .map_err(|e| storage_error("could not access the IndexedDB object store", &e))?;
+    for (username, bytes) in records {
+        store
+            .put_with_key(
+                &js_sys::Uint8Array::from(bytes.as_slice()),
+                &JsValue::from_str(username),
+            )
+            .map_err(|e| storage_error("could not store the user", &e))?;
+    }
+    await_transaction(&transaction).await
+}
+
+async fn delete_record(db: &IdbDatabase, username: &str) -> Result<()> {
+    let transaction = transaction(db, IdbTransactionMode::Readwrite)?;
+    transaction
+        .object_store(STORE_NAME)
+        .and_then(|store| store.delete(&JsValue::from_str(username)))
+        .map_err(|e| storage_error("could not delete the user", &e))?;
+    await_transaction(&transaction).await
+}
+
+/// Moves the users stored by the `BrowserLocalStorage` into the IndexedDB. Users that already
+/// exist in the IndexedDB are kept and only removed from the local storage.
+async fn migrate_local_storage(db: &IdbDatabase, records: &mut Records) -> Result<()> {
+    let Some(storage) = local_storage() else {
+        return Ok(());
+    };
+
+    let length = storage
+        .length()
+        .map_err(|e| storage_error("could not read the local storage", &e))?;
+    let keys: Vec<String> = (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter(|key| key.starts_with(LOCAL_STORAGE_KEY_PREFIX))
+        .collect();
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    let mut migrated = Vec::new();
+    for key in &keys {
+        let username = &key[LOCAL_STORAGE_KEY_PREFIX.len()..];
+        if records.contains_key(username) {
+            continue;
+        }
+        let Ok(Some(value)) = storage.get_item(key) else {
+            continue;
+        };
+        migrated.push((username.to_owned(), BASE64_STANDARD.decode(value)?));
+    }
+
+    put_records(db, &migrated).await?;
+    info!("Moved {} users from the local storage to the IndexedDB", migrated.len());
+    records.extend(migrated);
+
+    for key in &keys {
+        if let Err(e) = storage.remove_item(key) {
+            warn!("Could not remove {key} from the local storage: {e:?}");
+        }
+    }
+    Ok(())
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("localStorage"))
+        .ok()
+        .and_then(|storage| storage.dyn_into::<web_sys::Storage>().ok())
+}
+
+fn transaction(db: &IdbDatabase, mode: IdbTransactionMode) -> Result<IdbTransaction> {
+    db.transaction_with_str_and_mode(STORE_NAME, mode)
+        .map_err(|e| storage_error("could not start an IndexedDB transaction", &e))
+}
+
+/// Waits for the request to succeed and returns its result.
+async fn await_request(request: &IdbRequest) -> Result<JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    let result = JsFuture::from(promise).await;
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+
+    if result.is_err() {
+        return Err(dom_error(request.error().ok().flatten()));
+    }
+    request
+        .result()
+        .map_err(|e| storage_error("the IndexedDB request has no result", &e))
+}
+
+/// Waits for the transaction to be committed. Quota errors are reported when the transaction is aborted.
+async fn await_transaction(transaction: &IdbTransaction) -> Result<()> {
+    let promise = js_sys::Promise::new(&mut |resolve, reject| {
+        transaction.set_oncomplete(Some(&resolve));
+        transaction.set_onerror(Some(&reject));
+        transaction.set_onabort(Some(&reject));
+    });
+    let result = JsFuture::from(promise).await;
+    transaction.set_oncomplete(None);
+    transaction.set_onerror(None);
+    transaction.set_onabort(None);
+
+    result.map(|_| ()).map_err(|_| dom_error(transaction.error()))
+}
+
+fn dom_error(error: Option<web_sys::DomException>) -> UserKvStorageError {
+    match error {
+        Some(e) if e.name() == QUOTA_EXCEEDED_ERROR => UserKvStorageError::QuotaExceeded,
+        Some(e) => UserKvStorageError::Storage(format!("IndexedDB error {}: {}", e.name(), e.message())),
+        None => UserKvStorageError::Storage(String::from("IndexedDB error")),
+    }
+}
+
+fn storage_error(context: &str, error: &JsValue) -> UserKvStorageError {
+    match error.dyn_ref::<web_sys::DomException>() {
+        Some(e) if e.name() == QUOTA_EXCEEDED_ERROR => UserKvStorageError::QuotaExceeded,
+        _ => UserKvStorageError::Storage(format!("{context}: {error:?}")),
+    }
+}
@@ -6,14 +6,15 @@ use super::error::{Result, UserKvStorageError};
 use crate::types::users::UserEntity;
 use std::collections::HashMap;
 
+/// Implementation of [`super::UserKvStorage`] using an in-memory database. Does not provide any type
+/// of persistence, but does not require access to the file-system.
+#[derive(Debug, Default)]
 pub struct MemoryUserStorage {
     db: HashMap<String, UserEntity>,
 }
 
-/// Implementation of [`super::UserKvStorage`] using an in-memory database. Does not provide any type
-/// of persistence, but does not require access to the file-system.
 impl MemoryUserStorage {
-    #[allow(dead_code)]
+    /// Create an empty storage
     pub fn new() -> Self {
         Self { db: HashMap::new() }
     }
@@ -46,6 +47,11 @@ impl super::UserKvStorage for MemoryUserStorage {
         self.db.insert(username.to_owned(), value.clone());
         Ok(())
     }
+
+    #[doc = " List all keys."]
+    fn list(&self) -> Result<Vec<String>> {
+        Ok(self.db.keys().cloned().collect())
+    }
 }
 
 #[cfg(test)]
@@ -53,7 +59,7 @@ mod tests {
     use super::*;
     use crate::{
         types::{newtypes::EncryptionSalt, users::KycType},
-        user::UserKvStorage,
+        user::{conformance, UserKvStorage},
     };
 
     fn create_user_entity() -> UserEntity {
@@ -67,6 +73,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         }
     }
     use testing::CleanUp;
@@ -76,7 +94,7 @@ mod tests {
     #[case::memory(Box::new(MemoryUserStorage::new()))]
     #[cfg_attr(
         feature = "jammdb_repo",
-        case::jammdb(Box::new(crate::user::file_storage::FileUserStorage::new(&std::path::Path::new(&CleanUp::default().path_prefix)).unwrap()))
+        case::jammdb(Box::new(crate::user::file_storage::FileUserStorage::new(&std::path::Path::new(&CleanUp::default().path_prefix), None).unwrap()))
     )]
     fn all_backends(#[case] mut kv: Box<dyn UserKvStorage>) {}
 
@@ -169,4 +187,27 @@ mod tests {
         kv.delete("nonexistant_user").unwrap();
         assert!(!kv.exists("nonexistant_user").unwrap());
     }
+
+    // conformance checks of custom storage backends
+    #[rstest_reuse::apply(all_backends)]
+    fn test_set_get_all_fields(mut kv: Box<dyn UserKvStorage>) {
+        conformance::check_set_get_all_fields(&mut *kv);
+    }
+    #[rstest_reuse::apply(all_backends)]
+    fn test_users_are_independent(mut kv: Box<dyn UserKvStorage>) {
+        conformance::check_users_are_independent(&mut *kv);
+    }
+    #[rstest_reuse::apply(all_backends)]
+    fn test_list_empty(mut kv: Box<dyn UserKvStorage>) {
+        conformance::check_list_empty(&mut *kv);
+    }
+    #[rstest_reuse::apply(all_backends)]
+    fn test_list(mut kv: Box<dyn UserKvStorage>) {
+        conformance::check_list(&mut *kv);
+    }
+
+    #[test]
+    fn test_memory_storage_passes_conformance_suite() {
+        conformance::run_all(MemoryUserStorage::new);
+    }
 }
@@ -5,22 +5,34 @@
 //! The `UserRepo` trait defines methods for creating, updating, deleting, and retrieving user entities,
 //! as well as methods for setting user state, password, KYC state, KYC type, and viviswap KYC state.
 //!
+//! Host applications can store the users in their own storage by implementing [`UserKvStorage`] and
+//! passing it to [`Sdk::set_user_storage`](crate::core::Sdk::set_user_storage). The [`conformance`]
+//! module contains the checks every implementation should pass. The bindings connect a storage of the host
+//! language through the callbacks of [`callback_storage`].
 
+pub mod conformance;
+pub mod encryption;
 pub mod error;
-pub mod repository;
+pub(crate) mod repository;
 
 use crate::{
     share::Share,
     types::{
         newtypes::EncryptedPassword,
-        transactions::WalletTxInfo,
-        users::{KycType, UserEntity},
+        transactions::{PendingTransaction, WalletTxHistory, WalletTxInfo, WatchedTransaction},
+        users::{KycType, PinAttempts, UserEntity, UserSummary},
         viviswap::{ViviswapVerificationStatus, ViviswapVerificationStep},
     },
 };
 use error::{Result, UserKvStorageError};
 
 /// Storage abstraction of [`UserEntity`] objects as a simple Key-Value storage
+///
+/// The SDK uses a jammdb file, the browser local storage or an in-memory storage by default, depending on
+/// the platform. Implement this trait to store the users elsewhere, e.g. in an encrypted database of the
+/// host application, and pass it to [`Sdk::set_user_storage`](crate::core::Sdk::set_user_storage). The
+/// methods are called synchronously from the async SDK functions, so they should not block for long.
+/// Backend specific errors are reported as [`UserKvStorageError::Storage`].
 #[cfg_attr(test, mockall::automock)]
 pub trait UserKvStorage {
     /// Get a value by key. Returns Error if key does not exist.
@@ -34,6 +46,22 @@ pub trait UserKvStorage {
 
     /// Associate a key with a value. This will overwrite any previous value.
     fn set(&mut self, username: &str, value: &UserEntity) -> Result<()>;
+
+    /// List all keys, i.e. the usernames of all stored users, in any order.
+    fn list(&self) -> Result<Vec<String>>;
+
+    /// Get the failed pin attempts stored in clear next to the encrypted record of a user, since a wrong pin
+    /// cannot be counted inside a record it does not decrypt. Storages that do not encrypt the records have no
+    /// such attempts.
+    fn unlock_attempts(&self, _username: &str) -> Result<PinAttempts> {
+        Ok(PinAttempts::default())
+    }
+
+    /// Set the failed pin attempts stored in clear next to the encrypted record of a user, without changing the
+    /// record. They are replaced by the pin attempts of the user when the record is written again.
+    fn set_unlock_attempts(&mut self, _username: &str, _attempts: PinAttempts) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Represents the storage and loading of different users in a repository. This could be
@@ -100,6 +128,18 @@ pub trait UserRepo {
     /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
     fn get(&self, username: &str) -> Result<UserEntity>;
 
+    /// List all users
+    ///
+    /// # Returns
+    ///
+    /// Returns the summaries of all users sorted by username. Users that are locked until the pin is entered
+    /// are listed with their username only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error retrieving the users from the database.
+    fn list(&self) -> Result<Vec<UserSummary>>;
+
     /// Set the wallet password for a user.
     ///
     /// # Arguments
@@ -188,6 +228,126 @@ pub trait UserRepo {
     /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
     fn set_wallet_transactions(&mut self, username: &str, transaction: Vec<WalletTxInfo>) -> Result<()>;
 
+    /// Set the scanned wallet transaction history of a network.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `network_id` - The id of the network the history belongs to, or the key of the history of a watch-only wallet.
+    /// * `history` - The transaction history including the last scanned block.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the history is set successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
+    fn set_wallet_tx_history(&mut self, username: &str, network_id: &str, history: WalletTxHistory) -> Result<()>;
+
+    /// Set the pending transactions of a user for a specific network.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `network_id` - The id of the network the transactions belong to.
+    /// * `transactions` - The signed transactions that are not yet known to be included.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the pending transactions are set successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
+    fn set_pending_transactions(
+        &mut self,
+        username: &str,
+        network_id: &str,
+        transactions: Vec<PendingTransaction>,
+    ) -> Result<()>;
+
+    /// Set the transactions followed by the transaction watcher of a user.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `transactions` - The watched transactions to store.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the watched transactions are set successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
+    fn set_watched_transactions(&mut self, username: &str, transactions: Vec<WatchedTransaction>) -> Result<()>;
+
+    /// Set the failed pin attempts of a user. Takes a shared reference, so wrong attempts are counted while
+    /// the password is decrypted with a shared reference to the repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `attempts` - The failed pin attempts to store.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the pin attempts are set successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
+    fn set_pin_attempts(&self, username: &str, attempts: PinAttempts) -> Result<()>;
+
+    /// Get the failed attempts to unlock the encrypted record of a user, see [`UserKvStorage::unlock_attempts`].
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    ///
+    /// Returns the failed attempts, which are empty if the record is not encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error reading the record from the database.
+    fn unlock_attempts(&self, username: &str) -> Result<PinAttempts>;
+
+    /// Set the failed attempts to unlock the encrypted record of a user, see
+    /// [`UserKvStorage::set_unlock_attempts`]. Takes a shared reference like [`UserRepo::set_pin_attempts`].
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `attempts` - The failed attempts to store.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the attempts are set successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error storing the attempts in the database.
+    fn set_unlock_attempts(&self, username: &str, attempts: PinAttempts) -> Result<()>;
+
+    /// Set the time a user was last used.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username of the user.
+    /// * `timestamp` - The Unix timestamp in seconds.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if the time is set successfully, otherwise returns an `Error`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Error::KVError` if there is an error retrieving the user from the database.
+    fn set_last_used_at(&mut self, username: &str, timestamp: i64) -> Result<()>;
+
     /// Set the local share for a user.
     ///
     /// # Arguments
@@ -208,25 +368,33 @@ pub trait UserRepo {
 
 /// An implementation of [`UserKvStorage`] using a jammdb file-based database.
 #[cfg(feature = "jammdb_repo")]
-pub mod file_storage;
+pub(crate) mod file_storage;
 
 /// An implementation of [`UserKvStorage`] that uses the browsers local storage.
 #[cfg(target_arch = "wasm32")]
-pub mod web_storage;
+pub(crate) mod web_storage;
+
+/// Versioned encoding of the user records stored by the file, web and callback storage and of exported users.
+pub(crate) mod record;
+
+/// An implementation of [`UserKvStorage`] that uses the IndexedDB of the browser.
+#[cfg(target_arch = "wasm32")]
+pub mod indexed_db_storage;
 
 /// An implementation of [`UserKvStorage`] that uses a non-persistent in-memory storage.
 pub mod memory_storage;
 
-// implementations shared by file and web storage
+/// An implementation of [`UserKvStorage`] that uses a string key-value storage of the host application.
+pub mod callback_storage;
+
+// implementations used by the record encoding
 
-#[cfg(any(feature = "jammdb_repo", target_arch = "wasm32"))]
 impl From<rmp_serde::decode::Error> for UserKvStorageError {
     fn from(value: rmp_serde::decode::Error) -> Self {
         UserKvStorageError::Storage(format!("rmp_serde::decode::Error: {:#?}", value))
     }
 }
 
-#[cfg(any(feature = "jammdb_repo", target_arch = "wasm32"))]
 impl From<rmp_serde::encode::Error> for UserKvStorageError {
     fn from(value: rmp_serde::encode::Error) -> Self {
         UserKvStorageError::Storage(format!("rmp_serde::encode::Error: {:#?}", value))
@@ -0,0 +1,1158 @@
+//! Versioned encoding of the [`UserEntity`] records stored by the file and web storage and of exported users.
+//!
+//! Records are stored as a MessagePack map `{ "version": u32, "user": UserEntity }`, where the user
+//! is encoded with field names. Adding a field with `#[serde(default)]` or reordering fields therefore
+//! does not need a new version. Changes that cannot be expressed like this (renaming or removing a field,
+//! changing its type) bump [`CURRENT_VERSION`] and register a migration in [`MIGRATIONS`].
+//!
+//! Records written before the versioning was introduced are positional MessagePack arrays of the
+//! [`UserEntity`] fields and are treated as version 0. On read, a record is upgraded step by step to the
+//! current version. It is written in the current format the next time the user is stored.
+//!
+//! With [encryption at rest](super::encryption), the encoded user is encrypted and stored next to the
+//! version and a reference to the key. The failed pin attempts of the user are stored in clear next to an
+//! encrypted record, so they can be counted and checked while the record is locked.
+//!
+//! Exported users are unencrypted records wrapped in an envelope encrypted with a password chosen for the
+//! export: `{ "format": "etopay-user-export", "version", "m_cost", "t_cost", "p_cost", "salt", "nonce",
+//! "ciphertext" }`. The key is derived from the password with Argon2id, and the format, the version and the
+//! username are authenticated with the record, so an export only imports as the user it was created for.
+
+use super::encryption::{EncryptedPayload, KeyRef, UserStorageCipher, NONCE_LEN, SALT_LEN};
+use super::error::{Result, UserKvStorageError};
+use crate::types::newtypes::{PlainPassword, LEGACY_PASSWORD_HEADER};
+use crate::types::users::{KycType, PinAttempts, UserEntity};
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use log::warn;
+use rand::RngCore;
+use rmpv::Value;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+/// A migration upgrades the user of a record from the version at its index in [`MIGRATIONS`] to the next one.
+type Migration = fn(Value) -> Result<Value>;
+
+/// The registry of all migrations, ordered by the version they upgrade from.
+const MIGRATIONS: &[Migration] = &[migrate_v0_positional_to_v1_named];
+
+/// The version of newly written records.
+pub(crate) const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// The fields of a [`UserEntity`] in the order of the positional encoding of version 0. Records of older
+/// SDK releases contain a prefix of this list, as new fields were only appended.
+const V0_FIELDS: &[&str] = &[
+    "user_id",
+    "username",
+    "encrypted_password",
+    "salt",
+    "is_kyc_verified",
+    "kyc_type",
+    "viviswap_state",
+    "local_share",
+    "wallet_transactions",
+    "wallet_tx_history",
+    "pin_attempts",
+    "evm_address_indices",
+    "pending_transactions",
+    "watched_transactions",
+    "watch_only",
+];
+
+/// The fields of a wallet transaction in the order of the positional encoding of the released versions, before
+/// the log index was added.
+const V0_WALLET_TX_FIELDS: &[&str] = &[
+    "date",
+    "block_id",
+    "transaction_id",
+    "incoming",
+    "receiver",
+    "amount",
+    "network",
+    "status",
+    "explorer_url",
+];
+
+const VERSION_KEY: &str = "version";
+const USER_KEY: &str = "user";
+const KEY_KEY: &str = "key";
+const KEY_ID_KEY: &str = "key_id";
+const SALT_KEY: &str = "salt";
+const NONCE_KEY: &str = "nonce";
+const CIPHERTEXT_KEY: &str = "ciphertext";
+const FAILED_ATTEMPTS_KEY: &str = "failed_attempts";
+const LAST_FAILED_AT_KEY: &str = "last_failed_at";
+const FORMAT_KEY: &str = "format";
+const M_COST_KEY: &str = "m_cost";
+const T_COST_KEY: &str = "t_cost";
+const P_COST_KEY: &str = "p_cost";
+
+/// Value of the `format` field identifying an exported user.
+const EXPORT_FORMAT: &str = "etopay-user-export";
+/// The current version of the export envelope.
+const EXPORT_VERSION: u32 = 1;
+/// Length of the random salt used to derive the key from the export password.
+const EXPORT_SALT_LEN: usize = 16;
+/// Upper limit of the Argon2id memory size in KiB accepted from an export (256 MiB).
+const MAX_M_COST: u32 = 256 * 1024;
+/// Upper limit of the Argon2id iterations accepted from an export.
+const MAX_T_COST: u32 = 16;
+/// Upper limit of the Argon2id parallelism accepted from an export.
+const MAX_P_COST: u32 = 8;
+
+#[derive(Serialize)]
+struct UserRecord<'a> {
+    version: u32,
+    user: &'a UserEntity,
+}
+
+/// The content of a record, either the encoded user or the encrypted encoded user.
+enum RecordContent {
+    User(Value),
+    Encrypted(EncryptedPayload),
+}
+
+/// Encodes a user as a record of the current version.
+///
+/// With a cipher, the user is encrypted and stored as `{ "version", "key", "key_id" | "salt", "nonce",
+/// "ciphertext", "failed_attempts", "last_failed_at" }`, with the pin attempts of the user. If the key is derived from the pin and not available yet, the record is stored unencrypted, unless the
+/// `previous` record is encrypted and would be replaced by an unencrypted one.
+pub(crate) fn encode(
+    username: &str,
+    user: &UserEntity,
+    cipher: Option<&UserStorageCipher>,
+    previous: Option<&[u8]>,
+) -> Result<Vec<u8>> {
+    if let Some(cipher) = cipher {
+        let plaintext = rmp_serde::to_vec_named(user)?;
+        if let Some(payload) = cipher.encrypt(username, &plaintext)? {
+            return encode_encrypted(CURRENT_VERSION, &payload, user.pin_attempts);
+        }
+        if previous.is_some_and(is_encrypted) {
+            return Err(UserKvStorageError::Locked {
+                username: username.to_string(),
+            });
+        }
+    }
+
+    let record = UserRecord {
+        version: CURRENT_VERSION,
+        user,
+    };
+    Ok(rmp_serde::to_vec_named(&record)?)
+}
+
+/// Decodes a record of any known version and upgrades it to the current [`UserEntity`]. Failed attempts to
+/// unlock the record replace the pin attempts of the user if there are more.
+pub(crate) fn decode(username: &str, bytes: &[u8], cipher: Option<&UserStorageCipher>) -> Result<UserEntity> {
+    let value = read_single_value(bytes)?;
+    let unlock_attempts = read_unlock_attempts(&value);
+    let (version, content) = split_record(value)?;
+    if version > CURRENT_VERSION {
+        return Err(UserKvStorageError::UnsupportedSchemaVersion {
+            version,
+            supported: CURRENT_VERSION,
+        });
+    }
+
+    let mut user = match content {
+        RecordContent::User(user) => user,
+        RecordContent::Encrypted(payload) => {
+            let Some(cipher) = cipher else {
+                return Err(UserKvStorageError::Locked {
+                    username: username.to_string(),
+                });
+            };
+            read_single_value(&cipher.decrypt(username, &payload)?)?
+        }
+    };
+
+    for migration in &MIGRATIONS[version as usize..] {
+        user = migration(user)?;
+    }
+    replace_unknown_kyc_type(&mut user);
+
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &user)?;
+    let mut user: UserEntity = rmp_serde::from_slice(&buf)?;
+    if unlock_attempts.failed > user.pin_attempts.failed {
+        user.pin_attempts = unlock_attempts;
+    }
+    Ok(user)
+}
+
+/// Returns the failed pin attempts stored in clear with the record, without decrypting it.
+pub(crate) fn unlock_attempts(bytes: &[u8]) -> Result<PinAttempts> {
+    Ok(read_unlock_attempts(&read_single_value(bytes)?))
+}
+
+/// Replaces the failed pin attempts stored in clear with the record, without decrypting it.
+pub(crate) fn with_unlock_attempts(bytes: &[u8], attempts: PinAttempts) -> Result<Vec<u8>> {
+    let Value::Map(mut entries) = read_single_value(bytes)? else {
+        return Err(UserKvStorageError::Storage(String::from(
+            "unlock attempts cannot be stored in a legacy user record",
+        )));
+    };
+    entries.retain(|(name, _)| !matches!(name.as_str(), Some(FAILED_ATTEMPTS_KEY | LAST_FAILED_AT_KEY)));
+    push_unlock_attempts(&mut entries, attempts);
+
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &Value::Map(entries))?;
+    Ok(buf)
+}
+
+/// Encodes a user as a record of the current version and encrypts it with the export password, to import it
+/// on another device.
+pub(crate) fn export(user: &UserEntity, password: &PlainPassword) -> Result<Vec<u8>> {
+    let record = Zeroizing::new(encode(&user.username, user, None, None)?);
+    seal_export(&user.username, &record, password)
+}
+
+/// Decrypts an exported user with the export password and decodes its record of any known version.
+///
+/// The export only decrypts for the username it was created for. The failed pin attempts of the exported user
+/// are not imported.
+pub(crate) fn import(username: &str, bytes: &[u8], password: &PlainPassword) -> Result<UserEntity> {
+    let record = open_export(username, bytes, password)?;
+    if is_encrypted(&record) {
+        return Err(UserKvStorageError::Storage(String::from(
+            "the exported user record is encrypted",
+        )));
+    }
+
+    let mut user = decode(username, &record, None)?;
+    if user.username != username {
+        return Err(UserKvStorageError::Storage(format!(
+            "the exported user record contains the user {}, expected {username}",
+            user.username
+        )));
+    }
+    user.pin_attempts = PinAttempts::default();
+    Ok(user)
+}
+
+/// Encrypts a record into the envelope of an exported user.
+fn seal_export(username: &str, record: &[u8], password: &PlainPassword) -> Result<Vec<u8>> {
+    let (m_cost, t_cost, p_cost) = (19 * 1024, 2, 1);
+    let mut salt = [0u8; EXPORT_SALT_LEN];
+    rand::rng().fill_bytes(&mut salt);
+    let key = derive_export_key(password, m_cost, t_cost, p_cost, &salt)?;
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce);
+    let ciphertext = Aes256Gcm::new_from_slice(key.as_ref())
+        .ok()
+        .and_then(|cipher| {
+            cipher
+                .encrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: record,
+                        aad: export_associated_data(EXPORT_VERSION, username).as_bytes(),
+                    },
+                )
+                .ok()
+        })
+        .ok_or_else(|| UserKvStorageError::Storage(String::from("could not encrypt the exported user")))?;
+
+    let entries = vec![
+        (Value::from(FORMAT_KEY), Value::from(EXPORT_FORMAT)),
+        (Value::from(VERSION_KEY), Value::from(EXPORT_VERSION)),
+        (Value::from(M_COST_KEY), Value::from(m_cost)),
+        (Value::from(T_COST_KEY), Value::from(t_cost)),
+        (Value::from(P_COST_KEY), Value::from(p_cost)),
+        (Value::from(SALT_KEY), Value::from(salt.as_slice())),
+        (Value::from(NONCE_KEY), Value::from(nonce.as_slice())),
+        (Value::from(CIPHERTEXT_KEY), Value::from(ciphertext.as_slice())),
+    ];
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &Value::Map(entries))?;
+    Ok(buf)
+}
+
+/// Decrypts the record of an exported user from its envelope.
+fn open_export(username: &str, bytes: &[u8], password: &PlainPassword) -> Result<Zeroizing<Vec<u8>>> {
+    let invalid = |reason: &str| UserKvStorageError::Storage(format!("invalid exported user: {reason}"));
+    let Value::Map(entries) = read_single_value(bytes)? else {
+        return Err(invalid("not an exported user"));
+    };
+
+    let (mut format, mut version, mut m_cost, mut t_cost, mut p_cost, mut salt, mut nonce, mut ciphertext) =
+        (None, None, None, None, None, None, None, None);
+    for (name, value) in entries {
+        match name.as_str() {
+            Some(FORMAT_KEY) => format = value.as_str().map(String::from),
+            Some(VERSION_KEY) => version = value.as_u64(),
+            Some(M_COST_KEY) => m_cost = value.as_u64().and_then(|cost| u32::try_from(cost).ok()),
+            Some(T_COST_KEY) => t_cost = value.as_u64().and_then(|cost| u32::try_from(cost).ok()),
+            Some(P_COST_KEY) => p_cost = value.as_u64().and_then(|cost| u32::try_from(cost).ok()),
+            Some(SALT_KEY) => salt = value.as_slice().map(<[u8]>::to_vec),
+            Some(NONCE_KEY) => {
+                nonce = value
+                    .as_slice()
+                    .and_then(|nonce| <[u8; NONCE_LEN]>::try_from(nonce).ok())
+            }
+            Some(CIPHERTEXT_KEY) => ciphertext = value.as_slice().map(<[u8]>::to_vec),
+            _ => {}
+        }
+    }
+
+    if format.as_deref() != Some(EXPORT_FORMAT) {
+        return Err(invalid("not an exported user"));
+    }
+    let version = version.ok_or_else(|| invalid("missing version"))?;
+    if version != u64::from(EXPORT_VERSION) {
+        return Err(invalid(&format!("unsupported version {version}")));
+    }
+    let (Some(m_cost), Some(t_cost), Some(p_cost)) = (m_cost, t_cost, p_cost) else {
+        return Err(invalid("missing key derivation parameters"));
+    };
+    let salt = salt.ok_or_else(|| invalid("missing salt"))?;
+    let nonce = nonce.ok_or_else(|| invalid("missing nonce"))?;
+    let ciphertext = ciphertext.ok_or_else(|| invalid("missing ciphertext"))?;
+
+    // the parameters are read from the unauthenticated envelope, so reject values that would make the key
+    // derivation exhaust the memory or take forever
+    if m_cost > MAX_M_COST || t_cost > MAX_T_COST || p_cost > MAX_P_COST {
+        return Err(invalid(&format!(
+            "key derivation parameters exceed the limits: m_cost={m_cost}, t_cost={t_cost}, p_cost={p_cost}"
+        )));
+    }
+    let key = derive_export_key(password, m_cost, t_cost, p_cost, &salt)?;
+
+    Aes256Gcm::new_from_slice(key.as_ref())
+        .ok()
+        .and_then(|cipher| {
+            cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: export_associated_data(EXPORT_VERSION, username).as_bytes(),
+                    },
+                )
+                .ok()
+        })
+        .map(Zeroizing::new)
+        .ok_or(UserKvStorageError::WrongExportPassword)
+}
+
+/// Derives the 256-bit key of an exported user from the export password with Argon2id.
+fn derive_export_key(
+    password: &PlainPassword,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    salt: &[u8],
+) -> Result<Zeroizing<[u8; 32]>> {
+    let params = argon2::Params::new(m_cost, t_cost, p_cost, Some(32))
+        .map_err(|e| UserKvStorageError::Storage(format!("invalid key derivation parameters: {e}")))?;
+    let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    argon2
+        .hash_password_into(password.as_str().as_bytes(), salt, key.as_mut())
+        .map_err(|e| UserKvStorageError::Storage(format!("could not derive the key of the exported user: {e}")))?;
+    Ok(key)
+}
+
+/// The associated data authenticated together with an exported user, binding it to the format version and the
+/// username.
+fn export_associated_data(version: u32, username: &str) -> String {
+    format!("{EXPORT_FORMAT}-v{version}-{username}")
+}
+
+/// Returns `true` if the record is encrypted.
+fn is_encrypted(bytes: &[u8]) -> bool {
+    read_single_value(bytes)
+        .and_then(split_record)
+        .is_ok_and(|(_, content)| matches!(content, RecordContent::Encrypted(_)))
+}
+
+fn encode_encrypted(version: u32, payload: &EncryptedPayload, attempts: PinAttempts) -> Result<Vec<u8>> {
+    let mut entries = vec![(Value::from(VERSION_KEY), Value::from(version))];
+    match payload.key {
+        KeyRef::Pin { salt } => {
+            entries.push((Value::from(KEY_KEY), Value::from("pin")));
+            entries.push((Value::from(SALT_KEY), Value::from(salt.as_slice())));
+        }
+        KeyRef::Provider(key_id) => {
+            entries.push((Value::from(KEY_KEY), Value::from("provider")));
+            entries.push((Value::from(KEY_ID_KEY), Value::from(key_id)));
+        }
+    }
+    entries.push((Value::from(NONCE_KEY), Value::from(payload.nonce.as_slice())));
+    entries.push((Value::from(CIPHERTEXT_KEY), Value::from(payload.ciphertext.as_slice())));
+    push_unlock_attempts(&mut entries, attempts);
+
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &Value::Map(entries))?;
+    Ok(buf)
+}
+
+/// Adds the failed pin attempts to the entries of an encrypted record, if there are any.
+fn push_unlock_attempts(entries: &mut Vec<(Value, Value)>, attempts: PinAttempts) {
+    if attempts.failed > 0 {
+        entries.push((Value::from(FAILED_ATTEMPTS_KEY), Value::from(attempts.failed)));
+    }
+    if let Some(last_failed_at) = attempts.last_failed_at {
+        entries.push((Value::from(LAST_FAILED_AT_KEY), Value::from(last_failed_at)));
+    }
+}
+
+/// Reads the failed pin attempts, which are only stored next to encrypted records.
+fn read_unlock_attempts(value: &Value) -> PinAttempts {
+    let mut attempts = PinAttempts::default();
+    if let Value::Map(entries) = value {
+        for (name, value) in entries {
+            match name.as_str() {
+                Some(FAILED_ATTEMPTS_KEY) => {
+                    attempts.failed = value
+                        .as_u64()
+                        .and_then(|failed| u32::try_from(failed).ok())
+                        .unwrap_or_default()
+                }
+                Some(LAST_FAILED_AT_KEY) => attempts.last_failed_at = value.as_i64(),
+                _ => {}
+            }
+        }
+    }
+    attempts
+}
+
+/// Reads exactly one MessagePack value.
+fn read_single_value(bytes: &[u8]) -> Result<Value> {
+    let mut reader = bytes;
+    let value = rmpv::decode::read_value(&mut reader)?;
+    if !reader.is_empty() {
+        return Err(UserKvStorageError::Storage(format!(
+            "user record has {} unexpected trailing bytes",
+            reader.len()
+        )));
+    }
+    Ok(value)
+}
+
+/// Splits a record into its version and content.
+fn split_record(value: Value) -> Result<(u32, RecordContent)> {
+    let entries = match value {
+        Value::Array(_) => return Ok((0, RecordContent::User(value))),
+        Value::Map(entries) => entries,
+        _ => {
+            return Err(UserKvStorageError::Storage(String::from(
+                "user record is neither a versioned record nor a legacy user",
+            )))
+        }
+    };
+
+    let (mut version, mut user, mut key, mut key_id, mut salt, mut nonce, mut ciphertext) =
+        (None, None, None, None, None, None, None);
+    for (name, value) in entries {
+        match name.as_str() {
+            Some(VERSION_KEY) => version = value.as_u64(),
+            Some(USER_KEY) => user = Some(value),
+            Some(KEY_KEY) => key = value.as_str().map(String::from),
+            Some(KEY_ID_KEY) => key_id = value.as_u64(),
+            Some(SALT_KEY) => salt = value.as_slice().and_then(|salt| <[u8; SALT_LEN]>::try_from(salt).ok()),
+            Some(NONCE_KEY) => {
+                nonce = value
+                    .as_slice()
+                    .and_then(|nonce| <[u8; NONCE_LEN]>::try_from(nonce).ok())
+            }
+            Some(CIPHERTEXT_KEY) => ciphertext = value.as_slice().map(<[u8]>::to_vec),
+            _ => {}
+        }
+    }
+
+    let invalid = |reason: &str| UserKvStorageError::Storage(format!("invalid user record: {reason}"));
+    let version = version.ok_or_else(|| invalid("missing version"))?;
+    let version = u32::try_from(version).map_err(|_| invalid("version out of range"))?;
+
+    let content = match (user, ciphertext) {
+        (Some(user), None) => RecordContent::User(user),
+        (None, Some(ciphertext)) => {
+            let key = match (key.as_deref(), key_id) {
+                (Some("pin"), None) => KeyRef::Pin {
+                    salt: salt.ok_or_else(|| invalid("missing salt"))?,
+                },
+                (Some("provider"), Some(key_id)) => {
+                    KeyRef::Provider(u32::try_from(key_id).map_err(|_| invalid("key id out of range"))?)
+                }
+                _ => return Err(invalid("unknown encryption key")),
+            };
+            RecordContent::Encrypted(EncryptedPayload {
+                key,
+                nonce: nonce.ok_or_else(|| invalid("missing nonce"))?,
+                ciphertext,
+            })
+        }
+        _ => return Err(invalid("missing the user or the ciphertext")),
+    };
+    Ok((version, content))
+}
+
+/// Version 0 to 1: the positional array of fields becomes a map keyed by the field names. The releases
+/// writing version 0 encrypted the password with the legacy scheme and stored it without a header, so it
+/// is prefixed with the header of the legacy format. Their wallet transactions are positional as well and
+/// lack the log index, which is inserted in the middle of the fields, so they are keyed by their names too.
+fn migrate_v0_positional_to_v1_named(user: Value) -> Result<Value> {
+    let Value::Array(fields) = user else {
+        return Err(UserKvStorageError::Storage(String::from(
+            "user record of version 0 is not an array",
+        )));
+    };
+    if fields.len() > V0_FIELDS.len() {
+        return Err(UserKvStorageError::Storage(format!(
+            "user record of version 0 has {} fields, expected at most {}",
+            fields.len(),
+            V0_FIELDS.len()
+        )));
+    }
+
+    let entries = V0_FIELDS
+        .iter()
+        .zip(fields)
+        .map(|(name, value)| match (*name, value) {
+            ("encrypted_password", Value::Array(bytes)) => {
+                let header = LEGACY_PASSWORD_HEADER.iter().map(|byte| Value::from(*byte));
+                (Value::from(*name), Value::Array(header.chain(bytes).collect()))
+            }
+            ("wallet_transactions", Value::Array(transactions)) => (
+                Value::from(*name),
+                Value::Array(transactions.into_iter().map(name_v0_wallet_transaction).collect()),
+            ),
+            (name, value) => (Value::from(name), value),
+        })
+        .collect();
+    Ok(Value::Map(entries))
+}
+
+/// Keys the fields of a positional wallet transaction of the released versions by their names. Transactions
+/// with another number of fields are kept as they are.
+fn name_v0_wallet_transaction(transaction: Value) -> Value {
+    match transaction {
+        Value::Array(fields) if fields.len() == V0_WALLET_TX_FIELDS.len() => Value::Map(
+            V0_WALLET_TX_FIELDS
+                .iter()
+                .zip(fields)
+                .map(|(name, value)| (Value::from(*name), value))
+                .collect(),
+        ),
+        transaction => transaction,
+    }
+}
+
+/// The available [`KycType`] variants depend on the enabled features. A record written by a build with a
+/// different set of features may contain a variant unknown to this build, which is reset to
+/// [`KycType::Undefined`] instead of making the whole user unreadable.
+fn replace_unknown_kyc_type(user: &mut Value) {
+    let Value::Map(entries) = user else { return };
+    let Some((_, kyc_type)) = entries.iter_mut().find(|(key, _)| key.as_str() == Some("kyc_type")) else {
+        return;
+    };
+
+    let mut buf = Vec::new();
+    let is_known =
+        rmpv::encode::write_value(&mut buf, kyc_type).is_ok() && rmp_serde::from_slice::<KycType>(&buf).is_ok();
+    if !is_known {
+        warn!("Stored KYC type {kyc_type} is not supported by this build, resetting it to undefined");
+        *kyc_type = Value::from("Undefined");
+    }
+}
+
+impl From<rmpv::decode::Error> for UserKvStorageError {
+    fn from(value: rmpv::decode::Error) -> Self {
+        UserKvStorageError::Storage(format!("rmpv::decode::Error: {:#?}", value))
+    }
+}
+
+impl From<rmpv::encode::Error> for UserKvStorageError {
+    fn from(value: rmpv::encode::Error) -> Self {
+        UserKvStorageError::Storage(format!("rmpv::encode::Error: {:#?}", value))
+    }
+}
+
+/// Encodes the fields of [`V0_FIELDS`] positionally, like the SDK builds writing version 0 with all appended
+/// fields. The fields added after the versioning are not encoded and keep their default values when decoded.
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+pub(crate) fn encode_v0_positional(user: &UserEntity) -> Vec<u8> {
+    let Value::Map(entries) = read_single_value(&rmp_serde::to_vec_named(user).unwrap()).unwrap() else {
+        panic!("user should be encoded as map")
+    };
+    let fields = V0_FIELDS
+        .iter()
+        .map(|name| {
+            let (_, value) = entries.iter().find(|(key, _)| key.as_str() == Some(name)).unwrap();
+            value.clone()
+        })
+        .collect();
+    let mut bytes = Vec::new();
+    rmpv::encode::write_value(&mut bytes, &Value::Array(fields)).unwrap();
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_utils::{self, LEGACY_ENCRYPTED_PASSWORD, PIN};
+    use crate::types::newtypes::EncryptionSalt;
+    use crate::types::transactions::WalletTxInfo;
+    use crate::types::viviswap::{
+        ViviswapAddressDetail, ViviswapPartiallyKycDetails, ViviswapState, ViviswapVerificationStatus,
+        ViviswapVerificationStep,
+    };
+    use crate::user::encryption::UserStorageEncryption;
+
+    const SALT: [u8; 12] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+
+    /// A user without password, viviswap state and transactions, as written by SDK releases before the
+    /// transaction history was stored.
+    const FIXTURE_V0_BASELINE: &[u8] = &[
+        0x99, // array of 9 fields
+        0xc0, // user_id: nil
+        0xa5, b'a', b'l', b'i', b'c', b'e', // username: "alice"
+        0xc0, // encrypted_password: nil
+        0x9c, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12,   // salt: 12 bytes
+        0xc2, // is_kyc_verified: false
+        0xa9, b'U', b'n', b'd', b'e', b'f', b'i', b'n', b'e', b'd', // kyc_type: "Undefined"
+        0xc0, // viviswap_state: nil
+        0xc0, // local_share: nil
+        0x90, // wallet_transactions: []
+    ];
+
+    /// A verified user with a local share and a wallet transaction, written by a release built with the
+    /// `postident` feature and before the transaction history was stored.
+    const FIXTURE_V0_POSTIDENT_WITH_TRANSACTION: &[u8] = &[
+        0x99, // array of 9 fields
+        0xa2, b'i', b'd', // user_id: "id"
+        0xa3, b'b', b'o', b'b', // username: "bob"
+        0xc0, // encrypted_password: nil
+        0x9c, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12,   // salt: 12 bytes
+        0xc3, // is_kyc_verified: true
+        0xa9, b'P', b'o', b's', b't', b'i', b'd', b'e', b'n', b't', // kyc_type: "Postident"
+        0xc0, // viviswap_state: nil
+        0xa5, b's', b'h', b'a', b'r', b'e', // local_share: "share"
+        0x91, // wallet_transactions: array of 1
+        0x99, // wallet transaction: array of 9 fields
+        0xaa, b'2', b'0', b'2', b'4', b'-', b'0', b'1', b'-', b'0', b'1', // date: "2024-01-01"
+        0xa2, b'1', b'2', // block_id: "12"
+        0xa4, b'0', b'x', b'0', b'1', // transaction_id: "0x01"
+        0xc3, // incoming: true
+        0xa4, b'0', b'x', b'b', b'0', // receiver: "0xb0"
+        0xcb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0, // amount: 1.5
+        0xa3, b'E', b'T', b'H', // network: "ETH"
+        0xa9, b'C', b'o', b'n', b'f', b'i', b'r', b'm', b'e', b'd', // status: "Confirmed"
+        0xc0, // explorer_url: nil
+    ];
+
+    /// A user with the password and the viviswap state filled in, written by a release built with the
+    /// `viviswap-kyc` feature. The password is encrypted with [`PIN`] and [`testing_utils::SALT`] in the legacy
+    /// scheme and stored without a header.
+    #[rustfmt::skip]
+    const FIXTURE_V0_VIVISWAP_WITH_PASSWORD: &[u8] = &[
+        0x99, // array of 9 fields
+        0xa2, b'i', b'd', // user_id: "id"
+        0xa5, b'c', b'a', b'r', b'o', b'l', // username: "carol"
+        0xdc, 0, 30, // encrypted_password: array of 30 bytes, the legacy ciphertext without header
+        0xcc, 129, 21, 50, 74, 5, 69, 0xcc, 194, 0xcc, 252, 21, 0xcc, 186, 0xcc, 201, 0xcc, 175, 4, 80, 0xcc, 139,
+        0xcc, 188, 109, 5, 7, 17, 51, 0xcc, 134, 0xcc, 232, 0xcc, 183, 0xcc, 130, 121, 61, 0xcc, 192, 12, 13,
+        // salt: 12 bytes
+        0x9c, 0xcc, 241, 0xcc, 167, 0xcc, 131, 0xcc, 245, 0xcc, 166, 0xcc, 203, 63, 0xcc, 247, 0xcc, 211, 0xcc, 157,
+        0xcc, 138, 34,
+        // is_kyc_verified: false
+        0xc2,
+        0xa8, b'V', b'i', b'v', b'i', b's', b'w', b'a', b'p', // kyc_type: "Viviswap"
+        0x96, // viviswap_state: array of 6 fields
+        // verification_status: "PartiallyVerified"
+        0xb1, b'P', b'a', b'r', b't', b'i', b'a', b'l', b'l', b'y', b'V', b'e', b'r', b'i', b'f', b'i', b'e', b'd',
+        0xca, 0x44, 0x7a, 0, 0, // monthly_limit_eur: 1000.0
+        0xa8, b'P', b'e', b'r', b's', b'o', b'n', b'a', b'l', // next_verification_step: "Personal"
+        0x98, // partial_kyc_details_input: array of 8 fields
+        0xc3, 0xc2, 0xc2, 0xc3, // is_individual, is_pep, is_us_citizen, is_regulatory_disclosure
+        0xa3, b'D', b'E', b'U', // country_of_residence: "DEU"
+        0xa3, b'D', b'E', b'U', // nationality: "DEU"
+        0xa9, b'C', b'a', b'r', b'o', b'l', b' ', b'D', b'o', b'e', // full_name: "Carol Doe"
+        0xaa, b'1', b'9', b'9', b'0', b'-', b'0', b'1', b'-', b'0', b'1', // date_of_birth: "1990-01-01"
+        0x93, // current_iban: array of 3 fields
+        0xa7, b'i', b'b', b'a', b'n', b'-', b'i', b'd', // id: "iban-id"
+        // address: "DE89370400440532013000"
+        0xb6, b'D', b'E', b'8', b'9', b'3', b'7', b'0', b'4', b'0', b'0', b'4', b'4', b'0', b'5', b'3', b'2', b'0',
+        b'1', b'3', b'0', b'0', b'0',
+        // is_verified: true
+        0xc3,
+        0xc0, // payment_methods: nil
+        0xc0, // local_share: nil
+        0x90, // wallet_transactions: []
+    ];
+
+    fn user(username: &str) -> UserEntity {
+        UserEntity {
+            user_id: None,
+            username: username.to_string(),
+            encrypted_password: None,
+            salt: EncryptionSalt::from(SALT),
+            is_kyc_verified: false,
+            kyc_type: KycType::Undefined,
+            viviswap_state: None,
+            local_share: None,
+            wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        // Arrange
+        let mut user = user("alice");
+        user.evm_address_indices = vec![0, 3];
+
+        // Act
+        let bytes = encode("alice", &user, None, None).unwrap();
+        let decoded = decode("alice", &bytes, None).unwrap();
+
+        // Assert
+        assert_eq!(decoded, user);
+    }
+
+    #[test]
+    fn test_encode_writes_current_version() {
+        // Act
+        let bytes = encode("alice", &user("alice"), None, None).unwrap();
+        let (version, content) = split_record(read_single_value(&bytes).unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(version, CURRENT_VERSION);
+        assert!(matches!(content, RecordContent::User(Value::Map(_))));
+    }
+
+    #[test]
+    fn test_decode_v0_baseline_fixture() {
+        // Act
+        let decoded = decode("alice", FIXTURE_V0_BASELINE, None).unwrap();
+
+        // Assert
+        assert_eq!(decoded, user("alice"));
+    }
+
+    #[test]
+    fn test_decode_v0_positional_record_with_appended_fields() {
+        // Arrange: the positional encoding written before the versioning, including all appended fields
+        let mut user = user("alice");
+        user.evm_address_indices = vec![0, 1, 5];
+        let bytes = encode_v0_positional(&user);
+
+        // Act
+        let decoded = decode("alice", &bytes, None).unwrap();
+
+        // Assert
+        assert_eq!(decoded, user);
+    }
+
+    #[test]
+    fn test_decode_v0_rejects_more_fields_than_known() {
+        // Arrange: the current user encoded positionally has more fields than any record of version 0
+        let bytes = rmp_serde::to_vec(&user("alice")).unwrap();
+
+        // Act
+        let result = decode("alice", &bytes, None);
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+
+    #[test]
+    fn test_decode_v0_fixture_with_password_and_viviswap_state() {
+        // Act
+        let decoded = decode("carol", FIXTURE_V0_VIVISWAP_WITH_PASSWORD, None).unwrap();
+
+        // Assert
+        assert_eq!(decoded.user_id.as_deref(), Some("id"));
+        assert_eq!(decoded.username, "carol");
+        assert_eq!(decoded.salt, EncryptionSalt::from(testing_utils::SALT));
+        assert!(!decoded.is_kyc_verified);
+        assert_eq!(
+            decoded.viviswap_state,
+            Some(ViviswapState {
+                verification_status: ViviswapVerificationStatus::PartiallyVerified,
+                monthly_limit_eur: 1000.0,
+                next_verification_step: ViviswapVerificationStep::Personal,
+                partial_kyc_details_input: ViviswapPartiallyKycDetails {
+                    is_individual: Some(true),
+                    is_pep: Some(false),
+                    is_us_citizen: Some(false),
+                    is_regulatory_disclosure: Some(true),
+                    country_of_residence: Some(String::from("DEU")),
+                    nationality: Some(String::from("DEU")),
+                    full_name: Some(String::from("Carol Doe")),
+                    date_of_birth: Some(String::from("1990-01-01")),
+                },
+                current_iban: Some(ViviswapAddressDetail {
+                    id: String::from("iban-id"),
+                    address: String::from("DE89370400440532013000"),
+                    is_verified: true,
+                }),
+                payment_methods: None,
+            })
+        );
+        #[cfg(feature = "viviswap-kyc")]
+        assert_eq!(decoded.kyc_type, KycType::Viviswap);
+        #[cfg(not(feature = "viviswap-kyc"))]
+        assert_eq!(decoded.kyc_type, KycType::Undefined);
+
+        let password = decoded.encrypted_password.unwrap();
+        assert!(password.is_legacy());
+        assert_eq!(password, *LEGACY_ENCRYPTED_PASSWORD);
+        assert_eq!(
+            password.decrypt(&PIN, &decoded.salt).unwrap().as_str(),
+            "StrongP@55w0rd"
+        );
+    }
+
+    #[test]
+    fn test_v0_fixture_is_written_in_the_current_format() {
+        // Arrange
+        let decoded = decode("carol", FIXTURE_V0_VIVISWAP_WITH_PASSWORD, None).unwrap();
+
+        // Act
+        let bytes = encode("carol", &decoded, None, None).unwrap();
+        let (version, _) = split_record(read_single_value(&bytes).unwrap()).unwrap();
+        let reloaded = decode("carol", &bytes, None).unwrap();
+
+        // Assert
+        assert_eq!(version, CURRENT_VERSION);
+        assert_eq!(reloaded, decoded);
+        assert!(reloaded.encrypted_password.unwrap().is_legacy());
+    }
+
+    #[test]
+    fn test_decode_v0_fixture_with_transaction() {
+        // Act
+        let decoded = decode("bob", FIXTURE_V0_POSTIDENT_WITH_TRANSACTION, None).unwrap();
+
+        // Assert
+        assert_eq!(decoded.user_id.as_deref(), Some("id"));
+        assert_eq!(decoded.username, "bob");
+        assert!(decoded.is_kyc_verified);
+        assert_eq!(decoded.local_share.as_deref(), Some("share"));
+        assert_eq!(
+            decoded.wallet_transactions,
+            vec![WalletTxInfo {
+                date: String::from("2024-01-01"),
+                block_id: Some(String::from("12")),
+                transaction_id: String::from("0x01"),
+                log_index: None,
+                incoming: true,
+                receiver: String::from("0xb0"),
+                amount: 1.5,
+                network: String::from("ETH"),
+                status: String::from("Confirmed"),
+                explorer_url: None,
+            }]
+        );
+        #[cfg(feature = "postident")]
+        assert_eq!(decoded.kyc_type, KycType::Postident);
+        #[cfg(not(feature = "postident"))]
+        assert_eq!(decoded.kyc_type, KycType::Undefined);
+    }
+
+    #[test]
+    fn test_decode_ignores_field_order() {
+        // Arrange: a record of the current version with the fields of the user in reverse order
+        let bytes = encode("alice", &user("alice"), None, None).unwrap();
+        let (version, content) = split_record(read_single_value(&bytes).unwrap()).unwrap();
+        let RecordContent::User(Value::Map(mut entries)) = content else {
+            panic!("user should be encoded as map")
+        };
+        entries.reverse();
+        let record = Value::Map(vec![
+            (Value::from(USER_KEY), Value::Map(entries)),
+            (Value::from(VERSION_KEY), Value::from(version)),
+        ]);
+        let mut reordered = Vec::new();
+        rmpv::encode::write_value(&mut reordered, &record).unwrap();
+
+        // Act
+        let decoded = decode("alice", &reordered, None).unwrap();
+
+        // Assert
+        assert_eq!(decoded, user("alice"));
+    }
+
+    #[test]
+    fn test_decode_rejects_newer_version() {
+        // Arrange
+        let record = Value::Map(vec![
+            (Value::from(VERSION_KEY), Value::from(CURRENT_VERSION + 1)),
+            (Value::from(USER_KEY), Value::Map(Vec::new())),
+        ]);
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &record).unwrap();
+
+        // Act
+        let result = decode("alice", &bytes, None);
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(UserKvStorageError::UnsupportedSchemaVersion { version, supported })
+                if version == CURRENT_VERSION + 1 && supported == CURRENT_VERSION
+        ));
+    }
+
+    #[test]
+    fn test_decode_rejects_trailing_bytes() {
+        // Arrange
+        let mut bytes = encode("alice", &user("alice"), None, None).unwrap();
+        bytes.push(0xc0);
+
+        // Act
+        let result = decode("alice", &bytes, None);
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+
+    #[test]
+    fn test_encrypted_record_roundtrip() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        cipher.offer_pin("alice", &PIN);
+        cipher.confirm_pin("alice").unwrap();
+        let user = user("alice");
+
+        // Act
+        let bytes = encode("alice", &user, Some(&cipher), None).unwrap();
+        let decoded = decode("alice", &bytes, Some(&cipher)).unwrap();
+
+        // Assert
+        assert!(is_encrypted(&bytes));
+        assert!(!bytes.windows(5).any(|window| window == b"alice"));
+        assert_eq!(decoded, user);
+    }
+
+    #[test]
+    fn test_locked_record_cannot_be_read_or_replaced() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        cipher.offer_pin("alice", &PIN);
+        cipher.confirm_pin("alice").unwrap();
+        let encrypted = encode("alice", &user("alice"), Some(&cipher), None).unwrap();
+        cipher.lock();
+
+        // Act
+        let read = decode("alice", &encrypted, Some(&cipher));
+        let write = encode("alice", &user("alice"), Some(&cipher), Some(&encrypted));
+        let read_without_cipher = decode("alice", &encrypted, None);
+
+        // Assert
+        assert!(matches!(read, Err(UserKvStorageError::Locked { .. })));
+        assert!(matches!(write, Err(UserKvStorageError::Locked { .. })));
+        assert!(matches!(read_without_cipher, Err(UserKvStorageError::Locked { .. })));
+    }
+
+    #[test]
+    fn test_pin_attempts_are_stored_in_clear_next_to_the_encrypted_record() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        cipher.offer_pin("alice", &PIN);
+        cipher.confirm_pin("alice").unwrap();
+        let encrypted = encode("alice", &user("alice"), Some(&cipher), None).unwrap();
+        let attempts = PinAttempts {
+            failed: 2,
+            last_failed_at: Some(1_700_000_000),
+        };
+
+        // Act
+        let locked = with_unlock_attempts(&encrypted, attempts).unwrap();
+        let decoded = decode("alice", &locked, Some(&cipher)).unwrap();
+        let reset = encode("alice", &user("alice"), Some(&cipher), Some(&locked)).unwrap();
+
+        // Assert
+        assert_eq!(unlock_attempts(&encrypted).unwrap(), PinAttempts::default());
+        assert_eq!(unlock_attempts(&locked).unwrap(), attempts);
+        assert_eq!(decoded.pin_attempts, attempts);
+        assert_eq!(
+            unlock_attempts(&encode("alice", &decoded, Some(&cipher), None).unwrap()).unwrap(),
+            attempts
+        );
+        assert_eq!(unlock_attempts(&reset).unwrap(), PinAttempts::default());
+    }
+
+    #[test]
+    fn test_unencrypted_record_is_written_until_the_pin_is_entered() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        let plain = encode("alice", &user("alice"), None, None).unwrap();
+
+        // Act
+        let bytes = encode("alice", &user("alice"), Some(&cipher), Some(&plain)).unwrap();
+
+        // Assert
+        assert!(!is_encrypted(&bytes));
+        assert_eq!(decode("alice", &bytes, Some(&cipher)).unwrap(), user("alice"));
+    }
+
+    fn export_password() -> PlainPassword {
+        PlainPassword::try_from_string("export password").unwrap()
+    }
+
+    #[test]
+    fn test_export_import_roundtrip() {
+        // Arrange
+        let mut user = user("alice");
+        user.created_at = Some(1_600_000_000);
+
+        // Act
+        let bytes = export(&user, &export_password()).unwrap();
+        let imported = import("alice", &bytes, &export_password()).unwrap();
+
+        // Assert
+        assert_eq!(imported, user);
+    }
+
+    #[test]
+    fn test_export_is_encrypted() {
+        // Arrange
+        let mut user = user("alice");
+        user.local_share = Some(String::from("local share of alice"));
+
+        // Act
+        let bytes = export(&user, &export_password()).unwrap();
+
+        // Assert
+        let share = b"local share of alice";
+        assert!(!bytes.windows(share.len()).any(|window| window == share));
+        assert!(decode("alice", &bytes, None).is_err());
+    }
+
+    #[test]
+    fn test_import_with_wrong_password_fails() {
+        // Arrange
+        let bytes = export(&user("alice"), &export_password()).unwrap();
+        let wrong_password = PlainPassword::try_from_string("wrong password").unwrap();
+
+        // Act
+        let result = import("alice", &bytes, &wrong_password);
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::WrongExportPassword)));
+    }
+
+    #[test]
+    fn test_import_as_another_user_fails() {
+        // Arrange
+        let bytes = export(&user("alice"), &export_password()).unwrap();
+
+        // Act
+        let result = import("mallory", &bytes, &export_password());
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::WrongExportPassword)));
+    }
+
+    #[test]
+    fn test_import_rejects_record_of_another_user() {
+        // Arrange: an export for alice containing the record of bob
+        let record = encode("bob", &user("bob"), None, None).unwrap();
+        let bytes = seal_export("alice", &record, &export_password()).unwrap();
+
+        // Act
+        let result = import("alice", &bytes, &export_password());
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+
+    #[test]
+    fn test_import_resets_pin_attempts() {
+        // Arrange
+        let mut user = user("alice");
+        user.pin_attempts = PinAttempts {
+            failed: 2,
+            last_failed_at: Some(1_700_000_000),
+        };
+        let bytes = export(&user, &export_password()).unwrap();
+
+        // Act
+        let imported = import("alice", &bytes, &export_password()).unwrap();
+
+        // Assert
+        assert_eq!(imported.pin_attempts, PinAttempts::default());
+    }
+
+    #[test]
+    fn test_import_rejects_unsealed_record() {
+        // Arrange: a plain record as exported by earlier versions
+        let record = encode("alice", &user("alice"), None, None).unwrap();
+
+        // Act
+        let result = import("alice", &record, &export_password());
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+
+    #[test]
+    fn test_import_rejects_excessive_key_derivation_parameters() {
+        // Arrange
+        let bytes = export(&user("alice"), &export_password()).unwrap();
+        let Value::Map(mut entries) = read_single_value(&bytes).unwrap() else {
+            panic!("export should be a map")
+        };
+        for (name, value) in &mut entries {
+            if name.as_str() == Some(M_COST_KEY) {
+                *value = Value::from(MAX_M_COST + 1);
+            }
+        }
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &Value::Map(entries)).unwrap();
+
+        // Act
+        let result = import("alice", &bytes, &export_password());
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+
+    #[test]
+    fn test_import_upgrades_v0_record() {
+        // Arrange
+        let bytes = seal_export("alice", FIXTURE_V0_BASELINE, &export_password()).unwrap();
+
+        // Act
+        let imported = import("alice", &bytes, &export_password()).unwrap();
+
+        // Assert
+        assert_eq!(imported, user("alice"));
+    }
+
+    #[test]
+    fn test_import_rejects_encrypted_record() {
+        // Arrange
+        let cipher = UserStorageCipher::new(UserStorageEncryption::Pin);
+        cipher.offer_pin("alice", &PIN);
+        cipher.confirm_pin("alice").unwrap();
+        let encrypted = encode("alice", &user("alice"), Some(&cipher), None).unwrap();
+        let bytes = seal_export("alice", &encrypted, &export_password()).unwrap();
+
+        // Act
+        let result = import("alice", &bytes, &export_password());
+
+        // Assert
+        assert!(matches!(result, Err(UserKvStorageError::Storage(_))));
+    }
+}
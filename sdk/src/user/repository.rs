@@ -6,80 +6,108 @@ use crate::{
     share::Share,
     types::{
         newtypes::EncryptedPassword,
-        transactions::WalletTxInfo,
-        users::{KycType, UserEntity},
+        transactions::{PendingTransaction, WalletTxHistory, WalletTxInfo, WatchedTransaction},
+        users::{KycType, PinAttempts, UserEntity, UserSummary},
         viviswap::{ViviswapPartiallyKycDetails, ViviswapState, ViviswapVerificationStatus, ViviswapVerificationStep},
     },
     user::error::UserKvStorageError,
 };
 use log::info;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 pub struct UserRepoImpl<I: super::UserKvStorage> {
-    inner: I,
+    /// The storage, behind a lock since [`UserRepo::set_pin_attempts`] writes through a shared reference
+    inner: Mutex<I>,
 }
 
 impl<I: super::UserKvStorage> UserRepoImpl<I> {
     pub fn new(inner: I) -> Self {
-        Self { inner }
+        Self {
+            inner: Mutex::new(inner),
+        }
+    }
+
+    fn inner(&self) -> MutexGuard<'_, I> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn inner_mut(&mut self) -> &mut I {
+        self.inner.get_mut().unwrap_or_else(PoisonError::into_inner)
     }
 }
 
 impl<I: super::UserKvStorage> UserRepo for UserRepoImpl<I> {
     fn create(&mut self, user: &UserEntity) -> Result<()> {
         info!("Creating entry in user DB");
-        if self.inner.exists(&user.username)? {
+        if self.inner_mut().exists(&user.username)? {
             return Err(UserKvStorageError::UserAlreadyExists {
                 username: user.username.clone(),
             })?;
         }
 
-        self.inner.set(&user.username, user)?;
+        self.inner_mut().set(&user.username, user)?;
         Ok(())
     }
 
     fn update(&mut self, user: &UserEntity) -> Result<()> {
         info!("Updating entry in user DB");
 
-        if !self.inner.exists(&user.username)? {
+        if !self.inner_mut().exists(&user.username)? {
             return Err(UserKvStorageError::UserNotFound {
                 username: user.username.clone(),
             })?;
         }
-        self.inner.set(&user.username, user)
+        self.inner_mut().set(&user.username, user)
     }
 
     fn delete(&mut self, username: &str) -> Result<()> {
         info!("Deleting entry in user DB");
-        self.inner.delete(username)
+        self.inner_mut().delete(username)
     }
 
     fn get(&self, username: &str) -> Result<UserEntity> {
         info!("Fetching entry in user DB");
-        self.inner.get(username)
+        self.inner().get(username)
+    }
+
+    fn list(&self) -> Result<Vec<UserSummary>> {
+        info!("Listing entries in user DB");
+        let inner = self.inner();
+        let mut usernames = inner.list()?;
+        usernames.sort();
+
+        usernames
+            .iter()
+            .map(|username| match inner.get(username) {
+                Ok(user) => Ok(UserSummary::from(&user)),
+                Err(UserKvStorageError::Locked { .. }) => Ok(UserSummary::locked(username)),
+                Err(e) => Err(e),
+            })
+            .collect()
     }
 
     fn set_wallet_password(&mut self, username: &str, password: EncryptedPassword) -> Result<()> {
         info!("Setting password in user DB");
 
-        let mut user = self.inner.get(username)?;
+        let mut user = self.inner_mut().get(username)?;
         user.encrypted_password = Some(password.to_owned());
-        self.inner.set(username, &user)
+        self.inner_mut().set(username, &user)
     }
 
     fn set_kyc_state(&mut self, username: &str, is_verified: bool) -> Result<()> {
         info!("Setting KYC state in user DB: {is_verified}");
 
-        let mut user = self.inner.get(username)?;
+        let mut user = self.inner_mut().get(username)?;
         user.is_kyc_verified = is_verified;
-        self.inner.set(username, &user)
+        self.inner_mut().set(username, &user)
     }
 
     fn set_kyc_type(&mut self, username: &str, kyc_type: KycType) -> Result<()> {
         info!("Setting KYC type in user DB: {kyc_type:#?}");
 
-        let mut user = self.inner.get(username)?;
+        let mut user = self.inner_mut().get(username)?;
         user.kyc_type = kyc_type;
-        self.inner.set(username, &user)
+        self.inner_mut().set(username, &user)
     }
 
     fn set_viviswap_kyc_state(
@@ -91,7 +119,7 @@ impl<I: super::UserKvStorage> UserRepo for UserRepoImpl<I> {
     ) -> Result<()> {
         info!("Setting viviswap KYC state in user DB: {verification_status:?}, {monthly_limit_eur}, {next_verification_step:?}");
 
-        let mut user = self.inner.get(username)?;
+        let mut user = self.inner_mut().get(username)?;
 
         match user.viviswap_state {
             None => {
@@ -115,22 +143,85 @@ impl<I: super::UserKvStorage> UserRepo for UserRepoImpl<I> {
                 });
             }
         };
-        self.inner.set(username, &user)
+        self.inner_mut().set(username, &user)
     }
 
     fn set_local_share(&mut self, username: &str, share: Option<&Share>) -> Result<()> {
         use secrecy::ExposeSecret;
         info!("Setting local share in user DB for: {username}");
-        let mut user = self.inner.get(username)?;
+        let mut user = self.inner_mut().get(username)?;
         user.local_share = share.map(|s| s.to_string().expose_secret().to_string());
-        self.inner.set(username, &user)
+        self.inner_mut().set(username, &user)
     }
 
     fn set_wallet_transactions(&mut self, username: &str, transaction: Vec<WalletTxInfo>) -> Result<()> {
         info!("Setting wallet transactions in user DB: {transaction:#?}");
-        let mut user = self.inner.get(username)?;
+        let mut user = self.inner_mut().get(username)?;
         user.wallet_transactions = transaction;
-        self.inner.set(username, &user)
+        self.inner_mut().set(username, &user)
+    }
+
+    fn set_wallet_tx_history(&mut self, username: &str, network_id: &str, history: WalletTxHistory) -> Result<()> {
+        info!(
+            "Setting wallet transaction history of network {network_id} in user DB, last scanned block: {:?}",
+            history.last_scanned_block
+        );
+        let mut user = self.inner_mut().get(username)?;
+        user.wallet_tx_history.insert(network_id.to_string(), history);
+        self.inner_mut().set(username, &user)
+    }
+
+    fn set_pending_transactions(
+        &mut self,
+        username: &str,
+        network_id: &str,
+        transactions: Vec<PendingTransaction>,
+    ) -> Result<()> {
+        info!(
+            "Setting {} pending transactions of network {network_id} in user DB",
+            transactions.len()
+        );
+        let mut user = self.inner_mut().get(username)?;
+        if transactions.is_empty() {
+            user.pending_transactions.remove(network_id);
+        } else {
+            user.pending_transactions.insert(network_id.to_string(), transactions);
+        }
+        self.inner_mut().set(username, &user)
+    }
+
+    fn set_watched_transactions(&mut self, username: &str, transactions: Vec<WatchedTransaction>) -> Result<()> {
+        info!("Setting {} watched transactions in user DB", transactions.len());
+        let mut user = self.inner_mut().get(username)?;
+        user.watched_transactions = transactions;
+        self.inner_mut().set(username, &user)
+    }
+
+    fn set_pin_attempts(&self, username: &str, attempts: PinAttempts) -> Result<()> {
+        info!("Setting failed pin attempts in user DB: {}", attempts.failed);
+        let mut inner = self.inner();
+        let mut user = inner.get(username)?;
+        user.pin_attempts = attempts;
+        inner.set(username, &user)
+    }
+
+    fn unlock_attempts(&self, username: &str) -> Result<PinAttempts> {
+        self.inner().unlock_attempts(username)
+    }
+
+    fn set_unlock_attempts(&self, username: &str, attempts: PinAttempts) -> Result<()> {
+        info!(
+            "Setting failed attempts to unlock the user record in user DB: {}",
+            attempts.failed
+        );
+        self.inner().set_unlock_attempts(username, attempts)
+    }
+
+    fn set_last_used_at(&mut self, username: &str, timestamp: i64) -> Result<()> {
+        info!("Setting last used time in user DB: {timestamp}");
+        let mut user = self.inner_mut().get(username)?;
+        user.last_used_at = Some(timestamp);
+        self.inner_mut().set(username, &user)
     }
 }
 
@@ -141,6 +232,7 @@ mod tests {
     use iota_sdk::wallet::account::types::InclusionState;
 
     use super::*;
+    use crate::types::transactions::WatchedTransactionStatus;
     use crate::{
         testing_utils::ENCRYPTED_PASSWORD,
         types::{
@@ -161,6 +253,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         }
     }
 
@@ -288,6 +392,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         };
         let result = user_repo.update(&updated_user);
 
@@ -319,6 +435,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         };
         let result = user_repo.update(&updated_user);
 
@@ -359,6 +487,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         };
         let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
         user_repo.create(&user).unwrap();
@@ -427,6 +567,7 @@ mod tests {
                 date: String::new(),
                 block_id: None,
                 transaction_id: String::from("transaction_id_1"),
+                log_index: None,
                 receiver: String::new(),
                 incoming: false,
                 amount: 0.5,
@@ -438,6 +579,7 @@ mod tests {
                 date: String::new(),
                 block_id: Some(String::from("block_2")),
                 transaction_id: String::from("transaction_id_2"),
+                log_index: None,
                 receiver: String::new(),
                 incoming: true,
                 amount: 4.0,
@@ -463,6 +605,207 @@ mod tests {
         assert_eq!(retrieved_user.wallet_transactions.get(1).unwrap(), txs.get(1).unwrap());
     }
 
+    #[test]
+    fn it_should_set_pin_attempts() {
+        // Arrange
+        let username = String::from("hauju");
+
+        let user = create_user_entity(&username, None);
+        let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
+        user_repo.create(&user).unwrap();
+
+        let attempts = PinAttempts {
+            failed: 4,
+            last_failed_at: Some(1_700_000_000),
+        };
+
+        // Act
+        user_repo.set_pin_attempts(&username, attempts).unwrap();
+
+        // Assert
+        let retrieved_user = user_repo.get(&username).unwrap();
+        assert_eq!(retrieved_user.pin_attempts, attempts);
+    }
+
+    #[test]
+    fn it_should_set_last_used_at() {
+        // Arrange
+        let username = String::from("hauju");
+
+        let user = create_user_entity(&username, None);
+        let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
+        user_repo.create(&user).unwrap();
+
+        // Act
+        user_repo.set_last_used_at(&username, 1_700_000_000).unwrap();
+
+        // Assert
+        let retrieved_user = user_repo.get(&username).unwrap();
+        assert_eq!(retrieved_user.last_used_at, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn it_should_list_users_sorted_by_username() {
+        // Arrange
+        let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
+        let mut bob = create_user_entity("bob", Some(ENCRYPTED_PASSWORD.clone()));
+        bob.created_at = Some(1_600_000_000);
+        bob.last_used_at = Some(1_700_000_000);
+        user_repo.create(&bob).unwrap();
+        user_repo.create(&create_user_entity("alice", None)).unwrap();
+
+        // Act
+        let users = user_repo.list().unwrap();
+
+        // Assert
+        assert_eq!(
+            users,
+            vec![
+                UserSummary {
+                    username: String::from("alice"),
+                    created_at: None,
+                    kyc_type: KycType::Undefined,
+                    has_wallet: false,
+                    last_used_at: None,
+                    locked: false,
+                },
+                UserSummary {
+                    username: String::from("bob"),
+                    created_at: Some(1_600_000_000),
+                    kyc_type: KycType::Undefined,
+                    has_wallet: true,
+                    last_used_at: Some(1_700_000_000),
+                    locked: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_list_locked_users_by_username() {
+        // Arrange
+        let mut storage = crate::user::MockUserKvStorage::new();
+        storage.expect_list().returning(|| Ok(vec![String::from("alice")]));
+        storage.expect_get().returning(|username| {
+            Err(UserKvStorageError::Locked {
+                username: username.to_string(),
+            })
+        });
+        let user_repo = UserRepoImpl::new(storage);
+
+        // Act
+        let users = user_repo.list().unwrap();
+
+        // Assert
+        assert_eq!(users, vec![UserSummary::locked("alice")]);
+    }
+
+    #[test]
+    fn it_should_set_pending_transactions_per_network() {
+        // Arrange
+        let username = String::from("hauju");
+
+        let user = create_user_entity(&username, None);
+        let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
+        user_repo.create(&user).unwrap();
+
+        let pending = vec![PendingTransaction {
+            transaction_hash: String::from("0x01"),
+            from: String::from("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"),
+            nonce: 7,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            raw_transaction: vec![0x02, 0xf8],
+            created_at: 1_700_000_000,
+            replaces: Vec::new(),
+        }];
+
+        // Act
+        user_repo
+            .set_pending_transactions(&username, "network_1", pending.clone())
+            .unwrap();
+        user_repo
+            .set_pending_transactions(&username, "network_2", pending.clone())
+            .unwrap();
+        user_repo
+            .set_pending_transactions(&username, "network_2", Vec::new())
+            .unwrap();
+
+        // Assert
+        let retrieved_user = user_repo.get(&username).unwrap();
+        assert_eq!(retrieved_user.pending_transactions.len(), 1);
+        assert_eq!(retrieved_user.pending_transactions.get("network_1"), Some(&pending));
+    }
+
+    #[test]
+    fn it_should_set_watched_transactions() {
+        // Arrange
+        let username = String::from("hauju");
+
+        let user = create_user_entity(&username, None);
+        let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
+        user_repo.create(&user).unwrap();
+
+        let watched = vec![WatchedTransaction {
+            transaction_hash: String::from("0x01"),
+            network_id: String::from("network_1"),
+            status: WatchedTransactionStatus::Included,
+            confirmations: 3,
+        }];
+
+        // Act
+        user_repo.set_watched_transactions(&username, watched.clone()).unwrap();
+
+        // Assert
+        let retrieved_user = user_repo.get(&username).unwrap();
+        assert_eq!(retrieved_user.watched_transactions, watched);
+    }
+
+    #[test]
+    fn it_should_update_wallet_tx_history_per_network() {
+        // Arrange
+        let username = String::from("hauju");
+
+        let user = create_user_entity(&username, None);
+        let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
+        user_repo.create(&user).unwrap();
+
+        let history = WalletTxHistory {
+            last_scanned_block: Some(42),
+            first_scanned_block: Some(7),
+            transactions: vec![WalletTxInfo {
+                date: String::new(),
+                block_id: Some(String::from("42")),
+                transaction_id: String::from("transaction_id_1"),
+                log_index: None,
+                receiver: String::new(),
+                incoming: true,
+                amount: 1.5,
+                network: String::from("ETH"),
+                status: format!("{:?}", InclusionState::Confirmed),
+                explorer_url: None,
+            }],
+        };
+
+        // Act
+        user_repo
+            .set_wallet_tx_history(&username, "network_1", history.clone())
+            .unwrap();
+        user_repo
+            .set_wallet_tx_history(&username, "network_2", WalletTxHistory::default())
+            .unwrap();
+
+        // Assert
+        let retrieved_user = user_repo.get(&username).unwrap();
+        assert_eq!(retrieved_user.wallet_tx_history.len(), 2);
+        assert_eq!(retrieved_user.wallet_tx_history.get("network_1"), Some(&history));
+        assert_eq!(
+            retrieved_user.wallet_tx_history.get("network_2"),
+            Some(&WalletTxHistory::default())
+        );
+    }
+
     #[test]
     fn it_should_error_on_duplicate_user_creation() {
         // Arrange
@@ -511,6 +854,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         };
         let mut user_repo = UserRepoImpl::new(MemoryUserStorage::new());
 
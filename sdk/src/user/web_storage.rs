@@ -1,18 +1,22 @@
+use super::encryption::UserStorageCipher;
 use super::error::Result;
 use super::UserKvStorageError;
-use crate::types::users::UserEntity;
+use crate::types::users::{PinAttempts, UserEntity};
 use base64::prelude::*;
+use std::sync::Arc;
 use web_sys::{js_sys, wasm_bindgen::JsValue};
 
 const STORAGE_KEY_PREFIX: &str = "etopay.local.user";
 
-/// An implementation of [`super::UserKvStorage`] that uses the browsers local storage, rmp_serde
-/// and base64-encoding to store user entities.
-pub struct BrowserLocalStorage {}
+/// An implementation of [`super::UserKvStorage`] that uses the browsers local storage, versioned
+/// rmp_serde records and base64-encoding to store user entities.
+pub struct BrowserLocalStorage {
+    cipher: Option<Arc<UserStorageCipher>>,
+}
 
 impl BrowserLocalStorage {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(cipher: Option<Arc<UserStorageCipher>>) -> Self {
+        Self { cipher }
     }
 
     fn get_storage(&self) -> Result<web_sys::Storage> {
@@ -50,6 +54,10 @@ impl BrowserLocalStorage {
     fn storage_user_key(&self, username: &str) -> String {
         format!("{STORAGE_KEY_PREFIX}.{username}")
     }
+
+    fn username_of_storage_key(key: &str) -> Option<&str> {
+        key.strip_prefix(STORAGE_KEY_PREFIX)?.strip_prefix('.')
+    }
 }
 
 impl From<base64::DecodeError> for UserKvStorageError {
@@ -66,8 +74,7 @@ impl super::UserKvStorage for BrowserLocalStorage {
             .map_err(|e| UserKvStorageError::Storage(format!("Could not get storage key {username}: {e:#?}")))?
         {
             let bytes = BASE64_STANDARD.decode(value)?;
-            let user: UserEntity = rmp_serde::from_slice(&bytes)?;
-            Ok(user)
+            super::record::decode(username, &bytes, self.cipher.as_deref())
         } else {
             Err(UserKvStorageError::UserNotFound {
                 username: username.to_string(),
@@ -91,14 +98,68 @@ impl super::UserKvStorage for BrowserLocalStorage {
 
     fn set(&mut self, username: &str, value: &UserEntity) -> Result<()> {
         let storage = self.get_storage()?;
+        let key = self.storage_user_key(username);
 
-        let bytes = rmp_serde::to_vec(value)?;
+        let previous = storage
+            .get_item(&key)
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not get storage key {username}: {e:#?}")))?
+            .map(|value| BASE64_STANDARD.decode(value))
+            .transpose()?;
+        let bytes = super::record::encode(username, value, self.cipher.as_deref(), previous.as_deref())?;
         let text = BASE64_STANDARD.encode(bytes);
 
         storage
-            .set_item(&self.storage_user_key(username), &text)
+            .set_item(&key, &text)
             .map_err(|e| UserKvStorageError::Storage(format!("Could not set storage key {username}: {e:#?}")))?;
 
         Ok(())
     }
+
+    fn list(&self) -> Result<Vec<String>> {
+        let storage = self.get_storage()?;
+        let length = storage
+            .length()
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not get the number of storage keys: {e:#?}")))?;
+
+        let mut usernames = Vec::new();
+        for index in 0..length {
+            let key = storage
+                .key(index)
+                .map_err(|e| UserKvStorageError::Storage(format!("Could not get storage key {index}: {e:#?}")))?;
+            if let Some(username) = key.as_deref().and_then(Self::username_of_storage_key) {
+                usernames.push(username.to_owned());
+            }
+        }
+        Ok(usernames)
+    }
+
+    fn unlock_attempts(&self, username: &str) -> Result<PinAttempts> {
+        match self
+            .get_storage()?
+            .get_item(&self.storage_user_key(username))
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not get storage key {username}: {e:#?}")))?
+        {
+            Some(value) => super::record::unlock_attempts(&BASE64_STANDARD.decode(value)?),
+            None => Ok(PinAttempts::default()),
+        }
+    }
+
+    fn set_unlock_attempts(&mut self, username: &str, attempts: PinAttempts) -> Result<()> {
+        let storage = self.get_storage()?;
+        let key = self.storage_user_key(username);
+
+        let Some(value) = storage
+            .get_item(&key)
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not get storage key {username}: {e:#?}")))?
+        else {
+            return Err(UserKvStorageError::UserNotFound {
+                username: username.to_string(),
+            });
+        };
+        let bytes = super::record::with_unlock_attempts(&BASE64_STANDARD.decode(value)?, attempts)?;
+
+        storage
+            .set_item(&key, &BASE64_STANDARD.encode(bytes))
+            .map_err(|e| UserKvStorageError::Storage(format!("Could not set storage key {username}: {e:#?}")))
+    }
 }
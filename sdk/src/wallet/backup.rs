@@ -0,0 +1,417 @@
+//! Versioned JSON backup format for the wallet.
+//!
+//! In contrast to the KDBX backup created by [`super::kdbx`], the JSON backup carries metadata of the
+//! wallet in addition to the mnemonic. The backup is a JSON envelope describing the key derivation and
+//! the encryption, so the parameters can be changed in later versions without breaking old backups:
+//!
+//! ```json
+//! {
+//!   "format": "etopay-wallet-backup",
+//!   "version": 1,
+//!   "kdf": { "algorithm": "argon2id", "m_cost": 19456, "t_cost": 2, "p_cost": 1, "salt": "<base64>" },
+//!   "cipher": { "algorithm": "aes-256-gcm", "nonce": "<base64>" },
+//!   "ciphertext": "<base64>"
+//! }
+//! ```
+//!
+//! The ciphertext contains the JSON encoded mnemonic, the BIP-39 passphrase if the wallet uses one, and
+//! [`WalletMetadata`].
+
+use crate::types::newtypes::MnemonicPassphrase;
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use iota_sdk::crypto::keys::bip39::Mnemonic;
+use log::info;
+use rand::RngCore;
+use secrecy::{ExposeSecret, SecretBox, SecretString};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Value of the `format` field identifying a JSON wallet backup.
+const FORMAT: &str = "etopay-wallet-backup";
+/// The current version of the JSON backup format.
+const VERSION: u32 = 1;
+/// The signature every KDBX file starts with.
+const KDBX_SIGNATURE: [u8; 4] = [0x03, 0xd9, 0xa2, 0x9a];
+/// Length of the random salt used for the key derivation.
+const SALT_LEN: usize = 16;
+/// Length of the AES-256-GCM nonce.
+const NONCE_LEN: usize = 12;
+/// Upper limit of the Argon2id memory size in KiB accepted from a backup (256 MiB).
+const MAX_M_COST: u32 = 256 * 1024;
+/// Upper limit of the Argon2id iterations accepted from a backup.
+const MAX_T_COST: u32 = 16;
+/// Upper limit of the Argon2id parallelism accepted from a backup.
+const MAX_P_COST: u32 = 8;
+
+/// The format of a wallet backup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFormat {
+    /// KeePass database with a single `mnemonic` entry
+    Kdbx,
+    /// Versioned JSON envelope which also contains the [`WalletMetadata`]
+    Json,
+}
+
+/// Metadata of the wallet stored in a JSON backup.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WalletMetadata {
+    /// The ids of the networks available when the backup was created. Only the ids are stored, the
+    /// networks themselves are resolved against the networks of the backend when restoring.
+    #[serde(default)]
+    pub network_ids: Vec<String>,
+    /// The id of the preferred network of the user
+    #[serde(default)]
+    pub preferred_network: Option<String>,
+    /// Address indices of the EVM wallets derived in addition to the default address with index 0
+    #[serde(default)]
+    pub evm_address_indices: Vec<u32>,
+    /// Labels of the additional shares, describing who holds them
+    #[serde(default)]
+    pub share_labels: Vec<String>,
+}
+
+/// A wallet restored from a backup.
+pub struct RestoredBackup {
+    /// The mnemonic of the wallet
+    pub mnemonic: SecretBox<Mnemonic>,
+    /// The BIP-39 passphrase of the wallet, if it uses one
+    pub passphrase: Option<MnemonicPassphrase>,
+    /// The metadata of the wallet, only available for [`BackupFormat::Json`]
+    pub metadata: Option<WalletMetadata>,
+}
+
+/// The unencrypted JSON envelope of a backup.
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    format: String,
+    version: u32,
+    kdf: Kdf,
+    cipher: Cipher,
+    ciphertext: String,
+}
+
+/// Key derivation function used to derive the encryption key from the backup password.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "algorithm")]
+enum Kdf {
+    #[serde(rename = "argon2id")]
+    Argon2id {
+        /// Memory size in KiB
+        m_cost: u32,
+        /// Number of iterations
+        t_cost: u32,
+        /// Degree of parallelism
+        p_cost: u32,
+        /// Base64 encoded salt
+        salt: String,
+    },
+}
+
+/// Cipher used to encrypt the payload.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "algorithm")]
+enum Cipher {
+    #[serde(rename = "aes-256-gcm")]
+    Aes256Gcm {
+        /// Base64 encoded nonce
+        nonce: String,
+    },
+}
+
+/// The encrypted content of a backup.
+#[derive(Serialize, Deserialize, zeroize::Zeroize, zeroize::ZeroizeOnDrop)]
+struct Content {
+    mnemonic: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    passphrase: Option<String>,
+    #[serde(default)]
+    #[zeroize(skip)]
+    metadata: WalletMetadata,
+}
+
+/// Detects the format of a backup from its first bytes.
+pub fn detect_format(backup: &[u8]) -> Result<BackupFormat, BackupError> {
+    if backup.starts_with(&KDBX_SIGNATURE) {
+        return Ok(BackupFormat::Kdbx);
+    }
+    match backup.iter().find(|b| !b.is_ascii_whitespace()) {
+        Some(b'{') => Ok(BackupFormat::Json),
+        _ => Err(BackupError::UnsupportedFormat("unknown backup format".to_string())),
+    }
+}
+
+/// Stores the mnemonic, the optional BIP-39 passphrase and metadata in a JSON backup encrypted with the password.
+pub fn store_backup(
+    mnemonic: &SecretBox<Mnemonic>,
+    passphrase: Option<&MnemonicPassphrase>,
+    metadata: &WalletMetadata,
+    password: &SecretString,
+) -> Result<Vec<u8>, BackupError> {
+    info!("Creating JSON backup from mnemonic");
+
+    let mut salt = [0u8; SALT_LEN];
+    rand::rng().fill_bytes(&mut salt);
+    let kdf = Kdf::Argon2id {
+        m_cost: 19 * 1024,
+        t_cost: 2,
+        p_cost: 1,
+        salt: STANDARD.encode(salt),
+    };
+    let key = kdf.derive_key(password)?;
+
+    let content = Content {
+        mnemonic: mnemonic.expose_secret().to_string(),
+        passphrase: passphrase.map(|p| p.as_str().to_string()),
+        metadata: metadata.clone(),
+    };
+    let plaintext = Zeroizing::new(serde_json::to_vec(&content)?);
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce);
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| BackupError::Encryption)?;
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: associated_data(VERSION).as_bytes(),
+            },
+        )
+        .map_err(|_| BackupError::Encryption)?;
+
+    let envelope = Envelope {
+        format: FORMAT.to_string(),
+        version: VERSION,
+        kdf,
+        cipher: Cipher::Aes256Gcm {
+            nonce: STANDARD.encode(nonce),
+        },
+        ciphertext: STANDARD.encode(ciphertext),
+    };
+    Ok(serde_json::to_vec_pretty(&envelope)?)
+}
+
+/// Loads the mnemonic, the BIP-39 passphrase and metadata from a JSON backup encrypted with the password.
+pub fn load_backup(backup: &[u8], password: &SecretString) -> Result<RestoredBackup, BackupError> {
+    info!("Loading JSON backup from bytes");
+
+    let envelope = serde_json::from_slice::<Envelope>(backup)?;
+    if envelope.format != FORMAT {
+        return Err(BackupError::UnsupportedFormat(envelope.format));
+    }
+    if envelope.version != VERSION {
+        return Err(BackupError::UnsupportedVersion(envelope.version));
+    }
+
+    let key = envelope.kdf.derive_key(password)?;
+    let Cipher::Aes256Gcm { nonce } = &envelope.cipher;
+    let nonce = STANDARD.decode(nonce)?;
+    if nonce.len() != NONCE_LEN {
+        return Err(BackupError::UnsupportedFormat("invalid nonce length".to_string()));
+    }
+    let ciphertext = STANDARD.decode(&envelope.ciphertext)?;
+
+    let cipher = Aes256Gcm::new_from_slice(key.as_ref()).map_err(|_| BackupError::Encryption)?;
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: associated_data(envelope.version).as_bytes(),
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| BackupError::WrongPassword)?;
+
+    let content = serde_json::from_slice::<Content>(&plaintext)?;
+    Ok(RestoredBackup {
+        mnemonic: SecretBox::new(Box::new(Mnemonic::from(content.mnemonic.clone()))),
+        passphrase: content
+            .passphrase
+            .clone()
+            .map(MnemonicPassphrase::try_from_string)
+            .transpose()
+            .map_err(|_| BackupError::UnsupportedFormat("empty passphrase".to_string()))?,
+        metadata: Some(content.metadata.clone()),
+    })
+}
+
+/// The associated data authenticated together with the ciphertext, binding it to the format version.
+fn associated_data(version: u32) -> String {
+    format!("{FORMAT}-v{version}")
+}
+
+impl Kdf {
+    /// Derive a 256-bit key from the password.
+    fn derive_key(&self, password: &SecretString) -> Result<Zeroizing<[u8; 32]>, BackupError> {
+        let Kdf::Argon2id {
+            m_cost,
+            t_cost,
+            p_cost,
+            salt,
+        } = self;
+        // the parameters are read from the unauthenticated envelope, so reject values that would make
+        // the key derivation exhaust the memory or take forever
+        if *m_cost > MAX_M_COST || *t_cost > MAX_T_COST || *p_cost > MAX_P_COST {
+            return Err(BackupError::UnsupportedFormat(format!(
+                "key derivation parameters exceed the limits: m_cost={m_cost}, t_cost={t_cost}, p_cost={p_cost}"
+            )));
+        }
+        let salt = STANDARD.decode(salt)?;
+        let params = argon2::Params::new(*m_cost, *t_cost, *p_cost, Some(32))
+            .map_err(|e| BackupError::UnsupportedFormat(format!("invalid key derivation parameters: {e}")))?;
+        let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        argon2
+            .hash_password_into(password.expose_secret().as_bytes(), &salt, key.as_mut())
+            .map_err(|_| BackupError::Encryption)?;
+        Ok(key)
+    }
+}
+
+/// Wrapper for errors of the JSON backup format
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    /// The backup is not valid JSON or does not have the expected structure
+    #[error("Invalid backup: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A binary field of the backup is not valid base64
+    #[error("Invalid base64 in backup: {0}")]
+    Base64(#[from] base64::DecodeError),
+    /// The backup is not in a format supported by this version of the SDK
+    #[error("Unsupported backup format: {0}")]
+    UnsupportedFormat(String),
+    /// The backup was created by a newer version of the SDK
+    #[error("Unsupported backup version: {0}")]
+    UnsupportedVersion(u32),
+    /// Deriving the key or encrypting the backup failed
+    #[error("Error while encrypting / decrypting the backup")]
+    Encryption,
+    /// The backup could not be decrypted, either the password is wrong or the backup was modified
+    #[error("Wrong password or corrupted backup")]
+    WrongPassword,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing_utils::example_network_id;
+    use crate::types::currencies::Currency;
+    use rstest::rstest;
+
+    fn example_metadata() -> WalletMetadata {
+        WalletMetadata {
+            network_ids: vec![example_network_id(Currency::Iota)],
+            preferred_network: Some("IOTA".to_string()),
+            evm_address_indices: vec![1, 2],
+            share_labels: vec!["trusted contact".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_store_and_load_backup() {
+        // Arrange
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let password = SecretString::new("password".into());
+
+        // Act
+        let backup = store_backup(&mnemonic, None, &example_metadata(), &password).unwrap();
+        let restored = load_backup(&backup, &password).unwrap();
+
+        // Assert
+        assert_eq!(detect_format(&backup).unwrap(), BackupFormat::Json);
+        assert_eq!(restored.mnemonic.expose_secret().to_string(), "secret mnemonic");
+        assert_eq!(restored.passphrase, None);
+        assert_eq!(restored.metadata, Some(example_metadata()));
+        assert!(!String::from_utf8(backup).unwrap().contains("secret mnemonic"));
+    }
+
+    #[test]
+    fn test_store_and_load_backup_with_passphrase() {
+        // Arrange
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let passphrase = MnemonicPassphrase::try_from_string("secret passphrase").unwrap();
+        let password = SecretString::new("password".into());
+
+        // Act
+        let backup = store_backup(&mnemonic, Some(&passphrase), &example_metadata(), &password).unwrap();
+        let restored = load_backup(&backup, &password).unwrap();
+
+        // Assert
+        assert_eq!(restored.mnemonic.expose_secret().to_string(), "secret mnemonic");
+        assert_eq!(restored.passphrase, Some(passphrase));
+        assert!(!String::from_utf8(backup).unwrap().contains("secret passphrase"));
+    }
+
+    #[test]
+    fn test_load_backup_with_wrong_password() {
+        // Arrange
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let backup = store_backup(
+            &mnemonic,
+            None,
+            &example_metadata(),
+            &SecretString::new("password".into()),
+        )
+        .unwrap();
+
+        // Act
+        let result = load_backup(&backup, &SecretString::new("wrong password".into()));
+
+        // Assert
+        assert!(matches!(result, Err(BackupError::WrongPassword)));
+    }
+
+    #[test]
+    fn test_load_backup_with_newer_version() {
+        // Arrange
+        let password = SecretString::new("password".into());
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let backup = store_backup(&mnemonic, None, &example_metadata(), &password).unwrap();
+        let mut envelope = serde_json::from_slice::<serde_json::Value>(&backup).unwrap();
+        envelope["version"] = serde_json::json!(2);
+
+        // Act
+        let result = load_backup(&serde_json::to_vec(&envelope).unwrap(), &password);
+
+        // Assert
+        assert!(matches!(result, Err(BackupError::UnsupportedVersion(2))));
+    }
+
+    #[rstest]
+    #[case::m_cost("m_cost", MAX_M_COST + 1)]
+    #[case::t_cost("t_cost", MAX_T_COST + 1)]
+    #[case::p_cost("p_cost", MAX_P_COST + 1)]
+    fn test_load_backup_with_excessive_kdf_parameters(#[case] parameter: &str, #[case] value: u32) {
+        // Arrange
+        let password = SecretString::new("password".into());
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let backup = store_backup(&mnemonic, None, &example_metadata(), &password).unwrap();
+        let mut envelope = serde_json::from_slice::<serde_json::Value>(&backup).unwrap();
+        envelope["kdf"][parameter] = serde_json::json!(value);
+
+        // Act
+        let result = load_backup(&serde_json::to_vec(&envelope).unwrap(), &password);
+
+        // Assert
+        assert!(matches!(result, Err(BackupError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        // Arrange
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let kdbx = crate::kdbx::store_mnemonic(&mnemonic, None, &SecretString::new("password".into())).unwrap();
+
+        // Act & Assert
+        assert_eq!(detect_format(&kdbx).unwrap(), BackupFormat::Kdbx);
+        assert_eq!(detect_format(b"  {}").unwrap(), BackupFormat::Json);
+        detect_format(b"not a backup").unwrap_err();
+    }
+}
@@ -1,4 +1,4 @@
-use super::{kdbx::KdbxStorageError, share::ShareError};
+use super::{backup::BackupError, kdbx::KdbxStorageError, mnemonic::MnemonicError, share::ShareError};
 use crate::{backend::error::ApiError, types::error::TypeError, user::error::UserKvStorageError};
 use iota_sdk::types::block;
 use serde::Serialize;
@@ -13,8 +13,12 @@ pub enum ErrorKind {
     MissingPassword,
     /// You need to set / upload the recovery share before you can initialize the wallet.
     SetRecoveryShare,
+    /// You need to set the additional shares (e.g. from trusted contacts) before you can initialize the wallet.
+    SetAdditionalShares,
     /// You need to use the mnemonic or create a wallet before you can use the wallet.
     UseMnemonic,
+    /// You need to set the BIP-39 passphrase of the wallet on this device before you can initialize the wallet.
+    MissingPassphrase,
 }
 
 /// Wrapper for wallet errors
@@ -48,6 +52,41 @@ pub enum WalletError {
     #[error("Wallet address is empty")]
     EmptyWalletAddress,
 
+    /// Error raises if no guardian recovery was started (or requested by the user to approve)
+    #[error("No guardian recovery was started")]
+    GuardianRecoveryNotStarted,
+
+    /// Error raises if the fingerprint of the key of a guardian recovery does not match the fingerprint confirmed
+    /// by the guardian, e.g. because the key was replaced on its way
+    #[error("The fingerprint of the guardian recovery key does not match")]
+    GuardianRecoveryFingerprintMismatch,
+
+    /// Error raises if the user is asked to act as guardian before handing out a guardian public key
+    #[error("No guardian key was created")]
+    GuardianKeyNotSet,
+
+    /// Error raises if the mnemonic used for recovery is not the one of the existing wallet, or the shares of the
+    /// wallet were created by an older version without a fingerprint to check the mnemonic against
+    #[error("Mnemonic does not match the wallet")]
+    MnemonicDoesNotMatchWallet,
+
+    /// Error raises if the user already has a wallet, but neither the local share nor the backup share could be
+    /// loaded to check the mnemonic used for recovery against, e.g. without an access token
+    #[error("The mnemonic cannot be checked against the existing wallet")]
+    MnemonicNotVerifiable,
+
+    /// Error raises if a watch-only wallet is asked to sign a transaction
+    #[error("Wallet is watch-only and cannot sign transactions")]
+    WatchOnly,
+
+    /// Error raises if the watch-only wallet is used before its addresses or extended public key were set
+    #[error("No watch-only addresses or extended public key set")]
+    MissingWatchOnlySource,
+
+    /// Error raises if the address does not belong to the wallet
+    #[error("Address {0} does not belong to the wallet")]
+    UnknownAddress(String),
+
     /// Error raises if something failed to parse
     #[error("ParseError: {0}")]
     Parse(String),
@@ -84,6 +123,10 @@ pub enum WalletError {
     #[error("KdbxStorage error: {0}")]
     KdbxStorage(#[from] KdbxStorageError),
 
+    /// Errors related to the JSON backup format
+    #[error("Backup error: {0}")]
+    Backup(#[from] BackupError),
+
     /// Error occurs in sdk types
     #[error("Type errors: {0}")]
     Type(#[from] TypeError),
@@ -96,6 +139,10 @@ pub enum WalletError {
     #[error("Share error: {0}")]
     Share(#[from] ShareError),
 
+    /// The mnemonic is invalid, e.g. because of an unknown word or a wrong checksum
+    #[error("Invalid mnemonic: {0}")]
+    Mnemonic(#[from] MnemonicError),
+
     /// Error occurred while handling bip39 compliant mnemonics
     #[error("Bip39 error: {0:?}")]
     Bip39(iota_sdk::crypto::keys::bip39::Error),
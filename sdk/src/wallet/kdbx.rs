@@ -1,3 +1,4 @@
+use crate::types::newtypes::MnemonicPassphrase;
 use iota_sdk::crypto::keys::bip39::Mnemonic;
 use kdbx_rs::database::Entry;
 use kdbx_rs::errors::FailedUnlock;
@@ -5,8 +6,11 @@ use kdbx_rs::{CompositeKey, Database, Kdbx};
 use log::info;
 use secrecy::{ExposeSecret, SecretBox, SecretString};
 
-/// load mnemonic from kdbx file
-pub fn load_mnemonic(backup: &[u8], password: &SecretString) -> Result<SecretBox<Mnemonic>, KdbxStorageError> {
+/// load mnemonic and the BIP-39 passphrase, if stored, from kdbx file
+pub fn load_mnemonic(
+    backup: &[u8],
+    password: &SecretString,
+) -> Result<(SecretBox<Mnemonic>, Option<MnemonicPassphrase>), KdbxStorageError> {
     info!("Loading kdbx file from bytes");
     let kdbx = kdbx_rs::from_reader(backup)?;
     let key = CompositeKey::from_password(password.expose_secret());
@@ -20,11 +24,21 @@ pub fn load_mnemonic(backup: &[u8], password: &SecretString) -> Result<SecretBox
         return Err(KdbxStorageError::NotFound("Mnemonic not found".to_string()));
     };
     let mnemonic = Mnemonic::from(mnemonic);
-    Ok(SecretBox::new(Box::new(mnemonic)))
+
+    let passphrase = unlocked
+        .find_entry(|entry| entry.title() == Some("passphrase"))
+        .and_then(|entry| entry.password())
+        .and_then(|passphrase| MnemonicPassphrase::try_from_string(passphrase).ok());
+
+    Ok((SecretBox::new(Box::new(mnemonic)), passphrase))
 }
 
-/// store mnemonic in kdbx file
-pub fn store_mnemonic(mnemonic: &SecretBox<Mnemonic>, password: &SecretString) -> Result<Vec<u8>, KdbxStorageError> {
+/// store mnemonic and the optional BIP-39 passphrase in kdbx file
+pub fn store_mnemonic(
+    mnemonic: &SecretBox<Mnemonic>,
+    passphrase: Option<&MnemonicPassphrase>,
+    password: &SecretString,
+) -> Result<Vec<u8>, KdbxStorageError> {
     info!("Creating kdbx file from mnemonic");
 
     let mut database = Database::default();
@@ -35,6 +49,13 @@ pub fn store_mnemonic(mnemonic: &SecretBox<Mnemonic>, password: &SecretString) -
     entry.set_password(mnemonic.expose_secret().to_string());
     database.add_entry(entry);
 
+    if let Some(passphrase) = passphrase {
+        let mut entry = Entry::default();
+        entry.set_title("passphrase");
+        entry.set_password(passphrase.as_str().to_string());
+        database.add_entry(entry);
+    }
+
     let mut kdbx = Kdbx::from_database(database);
     kdbx.set_key(CompositeKey::from_password(password.expose_secret()))?;
 
@@ -86,10 +107,27 @@ mod tests {
         let password = SecretString::new("password".into());
 
         // Act
-        let kdbx = store_mnemonic(&mnemonic, &password).unwrap();
-        let mnemonic = load_mnemonic(&kdbx, &password).unwrap();
+        let kdbx = store_mnemonic(&mnemonic, None, &password).unwrap();
+        let (mnemonic, passphrase) = load_mnemonic(&kdbx, &password).unwrap();
+
+        // Assert
+        assert_eq!(mnemonic.expose_secret().to_string(), "secret mnemonic");
+        assert_eq!(passphrase, None);
+    }
+
+    #[test]
+    fn test_store_and_load_mnemonic_with_passphrase() {
+        // Arrange
+        let mnemonic = SecretBox::new(Box::new("secret mnemonic".into()));
+        let passphrase = MnemonicPassphrase::try_from_string("secret passphrase").unwrap();
+        let password = SecretString::new("password".into());
+
+        // Act
+        let kdbx = store_mnemonic(&mnemonic, Some(&passphrase), &password).unwrap();
+        let (mnemonic, loaded_passphrase) = load_mnemonic(&kdbx, &password).unwrap();
 
         // Assert
         assert_eq!(mnemonic.expose_secret().to_string(), "secret mnemonic");
+        assert_eq!(loaded_passphrase, Some(passphrase));
     }
 }
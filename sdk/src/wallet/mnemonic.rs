@@ -0,0 +1,588 @@
+//! Generation and validation of BIP-39 mnemonics.
+//!
+//! Mnemonics can be generated with 12, 15, 18, 21 or 24 words in any of the BIP-39 wordlist languages.
+//! When decoding a mnemonic the language is detected from its words, and a [`MnemonicError`] describes
+//! exactly what is wrong with an invalid mnemonic so that the user interface can highlight it.
+//!
+//! As required by BIP-39, mnemonics are NFKD normalized before their words are looked up in the wordlists, so
+//! words with precomposed accents and the ideographic space used by Japanese wallets are accepted. Generated and
+//! normalized mnemonics always consist of NFKD normalized words separated by a single ASCII space.
+
+use alloy::signers::local::coins_bip39::{
+    ChineseSimplified, ChineseTraditional, Czech, English, French, Italian, Japanese, Korean, Portuguese, Spanish,
+    Wordlist,
+};
+use iota_sdk::crypto::{
+    hashes::{sha::Sha256, Digest},
+    keys::bip39::Mnemonic,
+};
+use rand::RngCore;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroizing;
+
+/// Number of bits encoded by each word of a mnemonic.
+const BITS_PER_WORD: usize = 11;
+
+/// Error produced when generating, parsing or validating a mnemonic.
+#[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
+pub enum MnemonicError {
+    /// The mnemonic does not consist of 12, 15, 18, 21 or 24 words.
+    #[error("Invalid number of words: {0}, expected 12, 15, 18, 21 or 24")]
+    InvalidWordCount(usize),
+
+    /// The word at the zero-based `index` is not part of the wordlist of the mnemonic.
+    #[error("Unknown word at index {index}")]
+    UnknownWord {
+        /// The zero-based index of the word in the mnemonic
+        index: usize,
+    },
+
+    /// All words are known, but the checksum encoded in the last word does not match.
+    #[error("Invalid mnemonic checksum")]
+    InvalidChecksum,
+
+    /// The name or code of the wordlist language is not supported.
+    #[error("Unknown mnemonic language: {0}")]
+    UnknownLanguage(String),
+}
+
+/// The number of words of a mnemonic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MnemonicWordCount {
+    /// 12 words, encoding 128 bits of entropy
+    Words12,
+    /// 15 words, encoding 160 bits of entropy
+    Words15,
+    /// 18 words, encoding 192 bits of entropy
+    Words18,
+    /// 21 words, encoding 224 bits of entropy
+    Words21,
+    /// 24 words, encoding 256 bits of entropy
+    #[default]
+    Words24,
+}
+
+impl MnemonicWordCount {
+    /// The number of words.
+    pub fn words(self) -> usize {
+        match self {
+            Self::Words12 => 12,
+            Self::Words15 => 15,
+            Self::Words18 => 18,
+            Self::Words21 => 21,
+            Self::Words24 => 24,
+        }
+    }
+
+    /// The number of entropy bytes encoded by the words.
+    fn entropy_len(self) -> usize {
+        // each 3 words encode 32 bits of entropy and 1 bit of checksum
+        self.words() / 3 * 4
+    }
+}
+
+impl TryFrom<usize> for MnemonicWordCount {
+    type Error = MnemonicError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        match value {
+            12 => Ok(Self::Words12),
+            15 => Ok(Self::Words15),
+            18 => Ok(Self::Words18),
+            21 => Ok(Self::Words21),
+            24 => Ok(Self::Words24),
+            other => Err(MnemonicError::InvalidWordCount(other)),
+        }
+    }
+}
+
+/// The language of the BIP-39 wordlist of a mnemonic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MnemonicLanguage {
+    /// English
+    #[default]
+    English,
+    /// Chinese (simplified)
+    ChineseSimplified,
+    /// Chinese (traditional)
+    ChineseTraditional,
+    /// Czech
+    Czech,
+    /// French
+    French,
+    /// Italian
+    Italian,
+    /// Japanese
+    Japanese,
+    /// Korean
+    Korean,
+    /// Portuguese
+    Portuguese,
+    /// Spanish
+    Spanish,
+}
+
+impl MnemonicLanguage {
+    /// All supported languages, in the order they are tried when detecting the language of a mnemonic.
+    pub const ALL: [Self; 10] = [
+        Self::English,
+        Self::ChineseSimplified,
+        Self::ChineseTraditional,
+        Self::Czech,
+        Self::French,
+        Self::Italian,
+        Self::Japanese,
+        Self::Korean,
+        Self::Portuguese,
+        Self::Spanish,
+    ];
+
+    /// The word at `index` of the wordlist.
+    fn word(self, index: usize) -> Option<&'static str> {
+        match self {
+            Self::English => English::get(index).ok(),
+            Self::ChineseSimplified => ChineseSimplified::get(index).ok(),
+            Self::ChineseTraditional => ChineseTraditional::get(index).ok(),
+            Self::Czech => Czech::get(index).ok(),
+            Self::French => French::get(index).ok(),
+            Self::Italian => Italian::get(index).ok(),
+            Self::Japanese => Japanese::get(index).ok(),
+            Self::Korean => Korean::get(index).ok(),
+            Self::Portuguese => Portuguese::get(index).ok(),
+            Self::Spanish => Spanish::get(index).ok(),
+        }
+    }
+
+    /// The index of the NFKD normalized `word` in the wordlist.
+    fn index_of(self, word: &str) -> Option<usize> {
+        match self {
+            Self::English => word_index::<English>(word),
+            Self::ChineseSimplified => word_index::<ChineseSimplified>(word),
+            Self::ChineseTraditional => word_index::<ChineseTraditional>(word),
+            Self::Czech => word_index::<Czech>(word),
+            Self::French => word_index::<French>(word),
+            Self::Italian => word_index::<Italian>(word),
+            Self::Japanese => word_index::<Japanese>(word),
+            Self::Korean => word_index::<Korean>(word),
+            Self::Portuguese => word_index::<Portuguese>(word),
+            Self::Spanish => word_index::<Spanish>(word),
+        }
+    }
+
+    /// Stable code of the language, used when storing the language in a share payload.
+    pub(crate) fn code(self) -> u8 {
+        match self {
+            Self::English => 0,
+            Self::ChineseSimplified => 1,
+            Self::ChineseTraditional => 2,
+            Self::Czech => 3,
+            Self::French => 4,
+            Self::Italian => 5,
+            Self::Japanese => 6,
+            Self::Korean => 7,
+            Self::Portuguese => 8,
+            Self::Spanish => 9,
+        }
+    }
+
+    /// The language for a code returned by [`MnemonicLanguage::code`].
+    pub(crate) fn from_code(code: u8) -> Result<Self, MnemonicError> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+            .ok_or_else(|| MnemonicError::UnknownLanguage(code.to_string()))
+    }
+}
+
+impl std::fmt::Display for MnemonicLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::English => "english",
+            Self::ChineseSimplified => "chinese_simplified",
+            Self::ChineseTraditional => "chinese_traditional",
+            Self::Czech => "czech",
+            Self::French => "french",
+            Self::Italian => "italian",
+            Self::Japanese => "japanese",
+            Self::Korean => "korean",
+            Self::Portuguese => "portuguese",
+            Self::Spanish => "spanish",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for MnemonicLanguage {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| language.to_string() == name)
+            .ok_or_else(|| MnemonicError::UnknownLanguage(s.to_string()))
+    }
+}
+
+/// Looks up the index of the NFKD normalized `word` in the wordlist `W`. Words of the wordlist which are not
+/// stored in NFKD form are compared by their normalized form.
+fn word_index<W: Wordlist>(word: &str) -> Option<usize> {
+    W::get_index(word)
+        .ok()
+        .or_else(|| W::get_all().iter().position(|w| w.nfkd().eq(word.chars())))
+}
+
+/// Options for generating a new mnemonic. The default is a mnemonic with 24 English words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MnemonicOptions {
+    /// The number of words of the mnemonic
+    pub word_count: MnemonicWordCount,
+    /// The language of the wordlist used for the words
+    pub language: MnemonicLanguage,
+}
+
+impl MnemonicOptions {
+    /// Creates the options from an optional number of words and an optional language name (e.g. `"english"` or
+    /// `"chinese_simplified"`), used by the bindings. Missing values use the defaults.
+    pub fn try_from_parts(word_count: Option<u8>, language: Option<&str>) -> Result<Self, MnemonicError> {
+        Ok(Self {
+            word_count: word_count
+                .map(|count| MnemonicWordCount::try_from(usize::from(count)))
+                .transpose()?
+                .unwrap_or_default(),
+            language: language
+                .map(MnemonicLanguage::from_str)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Generates a new random mnemonic with the given options.
+pub fn generate_mnemonic(options: &MnemonicOptions) -> Mnemonic {
+    let mut entropy = Zeroizing::new(vec![0; options.word_count.entropy_len()]);
+    rand::rng().fill_bytes(&mut entropy);
+    encode_entropy(&entropy, options.language)
+}
+
+/// Validates a mnemonic and returns the detected language of its wordlist.
+pub fn validate_mnemonic(mnemonic: &str) -> Result<MnemonicLanguage, MnemonicError> {
+    decode_entropy(mnemonic).map(|(language, _)| language)
+}
+
+/// Validates a mnemonic and returns the detected language of its wordlist together with the mnemonic in the form
+/// the BIP-39 seed is derived from: the NFKD normalized words separated by a single ASCII space.
+pub fn normalize_mnemonic(mnemonic: &str) -> Result<(MnemonicLanguage, Mnemonic), MnemonicError> {
+    let (language, entropy) = decode_entropy(mnemonic)?;
+    Ok((language, encode_entropy(&entropy, language)))
+}
+
+/// Encodes the entropy bytes as a mnemonic using the wordlist of `language`. The entropy has to be 16, 20,
+/// 24, 28 or 32 bytes long.
+pub(crate) fn encode_entropy(entropy: &[u8], language: MnemonicLanguage) -> Mnemonic {
+    let checksum_bits = entropy.len() * 8 / 32;
+    let word_count = (entropy.len() * 8 + checksum_bits) / BITS_PER_WORD;
+    let checksum = Sha256::digest(entropy);
+
+    let mut bits = entropy
+        .iter()
+        .chain(checksum.first())
+        .flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1 == 1));
+    let words = (0..word_count)
+        .filter_map(|_| {
+            let index = (0..BITS_PER_WORD).fold(0, |index, _| (index << 1) | usize::from(bits.next().unwrap_or(false)));
+            language.word(index)
+        })
+        .collect::<Vec<_>>();
+
+    Mnemonic::from(words.join(" ").nfkd().collect::<String>())
+}
+
+/// Decodes a mnemonic into the language of its wordlist and the entropy bytes it encodes. The mnemonic is NFKD
+/// normalized first, which also turns ideographic spaces into ASCII spaces.
+///
+/// The language is detected from the words: the first language (in the order of [`MnemonicLanguage::ALL`])
+/// that knows all words and matches the checksum is used. If no language knows all words, the first unknown
+/// word of the language knowing the most words is reported.
+pub(crate) fn decode_entropy(mnemonic: &str) -> Result<(MnemonicLanguage, Zeroizing<Vec<u8>>), MnemonicError> {
+    let normalized = Zeroizing::new(mnemonic.nfkd().collect::<String>());
+    let words = normalized.split_whitespace().collect::<Vec<_>>();
+    let word_count = MnemonicWordCount::try_from(words.len())?;
+
+    let mut best_unknown_word = None;
+    let mut best_known_words = 0;
+    let mut checksum_mismatch = false;
+    for language in MnemonicLanguage::ALL {
+        let indices = words.iter().map(|word| language.index_of(word)).collect::<Vec<_>>();
+
+        let known_words = indices.iter().filter(|index| index.is_some()).count();
+        if let Some(unknown) = indices.iter().position(Option::is_none) {
+            if known_words > best_known_words {
+                best_known_words = known_words;
+                best_unknown_word = Some(unknown);
+            }
+            continue;
+        }
+
+        match entropy_from_indices(indices.into_iter().flatten(), word_count) {
+            Some(entropy) => return Ok((language, entropy)),
+            None => checksum_mismatch = true,
+        }
+    }
+
+    if checksum_mismatch {
+        Err(MnemonicError::InvalidChecksum)
+    } else {
+        Err(MnemonicError::UnknownWord {
+            index: best_unknown_word.unwrap_or(0),
+        })
+    }
+}
+
+/// Collects the bits of the word indices into the entropy bytes. Returns [`None`] if the checksum does not match.
+fn entropy_from_indices(
+    indices: impl Iterator<Item = usize>,
+    word_count: MnemonicWordCount,
+) -> Option<Zeroizing<Vec<u8>>> {
+    let mut bits = indices.flat_map(|index| (0..BITS_PER_WORD).rev().map(move |shift| (index >> shift) & 1 == 1));
+    let mut next_byte =
+        |bit_count: usize| (0..bit_count).fold(0u8, |byte, _| (byte << 1) | u8::from(bits.next().unwrap_or(false)));
+
+    let entropy_len = word_count.entropy_len();
+    let entropy = Zeroizing::new((0..entropy_len).map(|_| next_byte(8)).collect::<Vec<_>>());
+
+    let checksum_bits = entropy_len * 8 / 32;
+    let checksum = next_byte(checksum_bits);
+    let expected = Sha256::digest(entropy.as_slice()).first().copied().unwrap_or(0) >> (8 - checksum_bits);
+
+    (checksum == expected).then_some(entropy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+    use rstest::rstest;
+
+    // official BIP-39 test vector for the zero entropy
+    const ZERO_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // official Japanese BIP-39 test vector for the zero entropy, with ideographic spaces and precomposed kana
+    const JAPANESE_ZERO_MNEMONIC: &str = "あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あおぞら";
+
+    #[rstest]
+    #[case(MnemonicWordCount::Words12)]
+    #[case(MnemonicWordCount::Words15)]
+    #[case(MnemonicWordCount::Words18)]
+    #[case(MnemonicWordCount::Words21)]
+    #[case(MnemonicWordCount::Words24)]
+    fn test_generate_and_decode(#[case] word_count: MnemonicWordCount) {
+        for language in MnemonicLanguage::ALL {
+            // Arrange
+            let options = MnemonicOptions { word_count, language };
+
+            // Act
+            let mnemonic = generate_mnemonic(&options);
+            let (decoded_language, entropy) = decode_entropy(mnemonic.as_ref()).unwrap();
+
+            // Assert
+            assert_eq!(mnemonic.as_ref().split(' ').count(), word_count.words());
+            assert_eq!(entropy.len(), word_count.entropy_len());
+            assert_eq!(encode_entropy(&entropy, decoded_language).as_ref(), mnemonic.as_ref());
+        }
+    }
+
+    // the English test vectors of the BIP-39 reference implementation
+    #[rstest]
+    #[case("00000000000000000000000000000000", ZERO_MNEMONIC)]
+    #[case(
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "legal winner thank year wave sausage worth useful legal winner thank yellow"
+    )]
+    #[case(
+        "80808080808080808080808080808080",
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage above"
+    )]
+    #[case(
+        "ffffffffffffffffffffffffffffffff",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"
+    )]
+    #[case(
+        "000000000000000000000000000000000000000000000000",
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent"
+    )]
+    #[case(
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal will"
+    )]
+    #[case(
+        "808080808080808080808080808080808080808080808080",
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter always"
+    )]
+    #[case(
+        "ffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo when"
+    )]
+    #[case(
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art"
+    )]
+    #[case(
+        "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+        "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth title"
+    )]
+    #[case(
+        "8080808080808080808080808080808080808080808080808080808080808080",
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless"
+    )]
+    #[case(
+        "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote"
+    )]
+    #[case(
+        "9e885d952ad362caeb4efe34a8e91bd2",
+        "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic"
+    )]
+    #[case(
+        "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+        "gravity machine north sort system female filter attitude volume fold club stay feature office ecology stable narrow fog"
+    )]
+    #[case(
+        "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+        "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length"
+    )]
+    #[case(
+        "c0ba5a8e914111210f2bd131f3d5e08d",
+        "scheme spot photo card baby mountain device kick cradle pact join borrow"
+    )]
+    #[case(
+        "f30f8c1da665478f49b001d94c5fc452",
+        "vessel ladder alter error federal sibling chat ability sun glass valve picture"
+    )]
+    #[case(
+        "0460ef47585604c5660618db2e6a7e7f",
+        "afford alter spike radar gate glance object seek swamp infant panel yellow"
+    )]
+    #[case(
+        "72f60ebac5dd8add8d2a25a797102c3ce21bc029c200076f",
+        "indicate race push merry suffer human cruise dwarf pole review arch keep canvas theme poem divorce alter left"
+    )]
+    fn test_bip39_test_vectors(#[case] entropy: &str, #[case] mnemonic: &str) {
+        // Arrange
+        let entropy = hex::decode(entropy).unwrap();
+
+        // Act
+        let encoded = encode_entropy(&entropy, MnemonicLanguage::English);
+        let (language, decoded) = decode_entropy(mnemonic).unwrap();
+        let iota_entropy = iota_sdk::crypto::keys::bip39::wordlist::decode(
+            &Mnemonic::from(mnemonic.to_string()),
+            &iota_sdk::crypto::keys::bip39::wordlist::ENGLISH,
+        )
+        .unwrap();
+
+        // Assert
+        assert_eq!(encoded.as_ref(), mnemonic);
+        assert_eq!(language, MnemonicLanguage::English);
+        assert_eq!(decoded.as_slice(), entropy.as_slice());
+        assert_eq!(AsRef::<[u8]>::as_ref(&iota_entropy), entropy.as_slice());
+    }
+
+    #[test]
+    fn test_japanese_test_vector_is_nfkd_normalized() {
+        // Act
+        let (language, entropy) = decode_entropy(JAPANESE_ZERO_MNEMONIC).unwrap();
+        let (normalized_language, normalized) = normalize_mnemonic(JAPANESE_ZERO_MNEMONIC).unwrap();
+
+        // Assert
+        assert_eq!(language, MnemonicLanguage::Japanese);
+        assert_eq!(entropy.as_slice(), &[0; 16]);
+        assert_eq!(normalized_language, MnemonicLanguage::Japanese);
+        assert_eq!(
+            normalized.as_ref(),
+            JAPANESE_ZERO_MNEMONIC.nfkd().collect::<String>().as_str()
+        );
+        assert!(!normalized.as_ref().contains('\u{3000}'));
+        assert_eq!(
+            alloy::signers::local::coins_bip39::Mnemonic::<Japanese>::new_from_phrase(normalized.as_ref())
+                .unwrap()
+                .to_phrase(),
+            normalized.as_ref()
+        );
+    }
+
+    #[test]
+    fn test_precomposed_and_decomposed_accents_are_equal() {
+        // Arrange
+        let words = ["ábaco"; 11].join(" ") + " abierto";
+        let precomposed = words.nfc().collect::<String>();
+        let decomposed = words.nfd().collect::<String>();
+
+        // Act
+        let (language, entropy) = decode_entropy(&precomposed).unwrap();
+        let (decomposed_language, decomposed_entropy) = decode_entropy(&decomposed).unwrap();
+
+        // Assert
+        assert_eq!(language, MnemonicLanguage::Spanish);
+        assert_eq!(decomposed_language, MnemonicLanguage::Spanish);
+        assert_eq!(entropy.as_slice(), &[0; 16]);
+        assert_eq!(decomposed_entropy.as_slice(), &[0; 16]);
+    }
+
+    #[test]
+    fn test_generated_mnemonic_matches_iota_sdk() {
+        let mnemonic = generate_mnemonic(&MnemonicOptions::default());
+        let (_, entropy) = decode_entropy(mnemonic.as_ref()).unwrap();
+
+        let iota_entropy = iota_sdk::crypto::keys::bip39::wordlist::decode(
+            &mnemonic,
+            &iota_sdk::crypto::keys::bip39::wordlist::ENGLISH,
+        )
+        .unwrap();
+        assert_eq!(entropy.as_slice(), AsRef::<[u8]>::as_ref(&iota_entropy));
+    }
+
+    #[rstest]
+    #[case("abandon abandon abandon", MnemonicError::InvalidWordCount(3))]
+    #[case(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+        MnemonicError::InvalidChecksum
+    )]
+    #[case(
+        "abandon abandon abandon abandon abandon notaword abandon abandon abandon abandon abandon about",
+        MnemonicError::UnknownWord { index: 5 }
+    )]
+    #[case(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abouts",
+        MnemonicError::UnknownWord { index: 11 }
+    )]
+    fn test_validate_mnemonic_errors(#[case] mnemonic: &str, #[case] expected: MnemonicError) {
+        assert_eq!(validate_mnemonic(mnemonic), Err(expected));
+    }
+
+    #[rstest]
+    #[case(None, None, Ok(MnemonicOptions::default()))]
+    #[case(Some(12), Some("Japanese"), Ok(MnemonicOptions { word_count: MnemonicWordCount::Words12, language: MnemonicLanguage::Japanese }))]
+    #[case(Some(13), None, Err(MnemonicError::InvalidWordCount(13)))]
+    #[case(None, Some("klingon"), Err(MnemonicError::UnknownLanguage("klingon".to_string())))]
+    fn test_options_try_from_parts(
+        #[case] word_count: Option<u8>,
+        #[case] language: Option<&str>,
+        #[case] expected: Result<MnemonicOptions, MnemonicError>,
+    ) {
+        assert_eq!(MnemonicOptions::try_from_parts(word_count, language), expected);
+    }
+
+    #[test]
+    fn test_language_from_str() {
+        for language in MnemonicLanguage::ALL {
+            assert_eq!(language.to_string().parse::<MnemonicLanguage>(), Ok(language));
+            assert_eq!(MnemonicLanguage::from_code(language.code()), Ok(language));
+        }
+        assert_eq!(
+            "klingon".parse::<MnemonicLanguage>(),
+            Err(MnemonicError::UnknownLanguage("klingon".to_string()))
+        );
+    }
+}
@@ -16,8 +16,17 @@ pub(crate) mod wallet_user_eth;
 /// Module containing code related to the SSS secret sharing scheme
 pub mod share;
 
+/// Module containing code related to generating and validating BIP-39 mnemonics
+pub mod mnemonic;
+
+/// Module containing code related to watch-only wallets
+pub mod watch_only;
+
 /// Module containing code related to the KDBX file format
 pub mod kdbx;
 
+/// Module containing code related to the versioned JSON backup format
+pub mod backup;
+
 /// Errors related to sdk wallet
 pub mod error;
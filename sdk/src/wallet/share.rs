@@ -1,9 +1,13 @@
+use super::mnemonic::{decode_entropy, encode_entropy, MnemonicLanguage};
+use crate::types::newtypes::{EncryptionPin, EncryptionSalt, MnemonicPassphrase};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use iota_sdk::crypto::{
     hashes::{blake2b::Blake2b256, Digest},
-    keys::bip39::Mnemonic,
+    keys::{bip39::Mnemonic, x25519},
 };
+use rand::RngCore;
 use secrecy::{ExposeSecret, SecretBox, SecretSlice, SecretString};
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 /// A share that can be used with other [`Share`] to construct the secret.
@@ -22,8 +26,34 @@ pub struct Share {
     /// The actual share data bytes, representing the `payload_type` content split into shares using
     /// `encoding` and encrypted using `encryption`.
     data: ShareData,
+
+    /// The number of shares needed to reconstruct the secret.
+    threshold: u8,
+
+    /// The total number of shares the secret was split into.
+    count: u8,
+
+    /// Optional label describing who holds the share, e.g. a trusted contact.
+    label: Option<String>,
+
+    /// Commitment to the secret, used to check that shares belong together without reconstructing the
+    /// secret. Missing for shares created by older versions. Encrypted shares authenticate it as associated
+    /// data, so it cannot be replaced without the key.
+    fingerprint: Option<[u8; FINGERPRINT_LEN]>,
+
+    /// The labels of all additional shares of the scheme, so the scheme can be recreated from any share.
+    additional_labels: Vec<String>,
 }
 
+/// Threshold of the shares created before the share scheme was configurable.
+const DEFAULT_THRESHOLD: u8 = 2;
+
+/// The recovery, local and backup share, which are always created.
+const DEFAULT_COUNT: u8 = 3;
+
+/// Number of bytes of the hash of the secret stored as fingerprint in the shares.
+const FINGERPRINT_LEN: usize = 8;
+
 /// A type that has the immutable data from the share, and will zeroize it on Drop
 #[derive(zeroize::ZeroizeOnDrop, Clone)]
 #[cfg_attr(test, derive(PartialEq))] // for testing purposes we also derive PartialEq
@@ -49,24 +79,121 @@ impl FromStr for Share {
         let data = parts.next().ok_or(ShareError::NotEnoughParts)?;
         let data = ShareData(STANDARD.decode(data)?.into());
 
+        // shares without the scheme part were created with the fixed 2-of-3 scheme
+        let (threshold, count) = match parts.next() {
+            Some(scheme) => {
+                let (threshold, count) = scheme
+                    .split_once("of")
+                    .ok_or_else(|| ShareError::InvalidShareFormat(format!("Unrecognized share scheme: `{scheme}`")))?;
+                (threshold.parse()?, count.parse()?)
+            }
+            None => (DEFAULT_THRESHOLD, DEFAULT_COUNT),
+        };
+        let label = parts
+            .next()
+            .filter(|label| !label.is_empty())
+            .map(|label| {
+                String::from_utf8(STANDARD.decode(label)?)
+                    .map_err(|_| ShareError::InvalidShareFormat("Share label is not valid utf-8".to_string()))
+            })
+            .transpose()?;
+        let fingerprint = parts
+            .next()
+            .filter(|fingerprint| !fingerprint.is_empty())
+            .map(|fingerprint| {
+                <[u8; FINGERPRINT_LEN]>::try_from(STANDARD.decode(fingerprint)?).map_err(|_| {
+                    ShareError::InvalidShareFormat(format!("Share fingerprint must have {FINGERPRINT_LEN} bytes"))
+                })
+            })
+            .transpose()?;
+
+        // shares without the labels part have no labeled additional shares
+        let additional = usize::from(count.saturating_sub(DEFAULT_COUNT));
+        let additional_labels = match parts.next().filter(|labels| !labels.is_empty()) {
+            Some(labels) => {
+                let labels = labels
+                    .split('.')
+                    .map(|label| {
+                        String::from_utf8(STANDARD.decode(label)?)
+                            .map_err(|_| ShareError::InvalidShareFormat("Share label is not valid utf-8".to_string()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if labels.len() != additional {
+                    return Err(ShareError::InvalidShareFormat(format!(
+                        "Share scheme has {additional} additional shares, but {} labels",
+                        labels.len()
+                    )));
+                }
+                labels
+            }
+            None => vec![String::new(); additional],
+        };
+
         Ok(Share {
             payload_type: version,
             encoding,
             encryption,
             data,
+            threshold,
+            count,
+            label,
+            fingerprint,
+            additional_labels,
         })
     }
 }
 
 impl Share {
     /// Format this [`Share`] to a string value, returned as a [`Secret`].
+    ///
+    /// The scheme, the base64 encoded label, fingerprint and the `.` separated base64 encoded labels of the
+    /// additional shares are appended to the original `ME-RS-N-<data>` format, e.g.
+    /// `ME-RS-N-<data>-3of5-<label>-<fingerprint>-<label>.<label>`. Older versions ignore these parts.
+    /// Trailing parts that are not set are omitted, parts that are not set before a set part are empty.
     pub fn to_string(&self) -> SecretString {
         let base64_data = STANDARD.encode(&self.data.0);
-        format!(
+        let mut value = format!(
             "{}-{}-{}-{}",
             self.payload_type, self.encoding, self.encryption, base64_data
-        )
-        .into()
+        );
+
+        // the trailing parts together with whether they are set
+        let mut parts = vec![
+            (
+                format!("{}of{}", self.threshold, self.count),
+                self.threshold != DEFAULT_THRESHOLD || self.count != DEFAULT_COUNT,
+            ),
+            (
+                self.label
+                    .as_ref()
+                    .map(|label| STANDARD.encode(label))
+                    .unwrap_or_default(),
+                self.label.is_some(),
+            ),
+            (
+                self.fingerprint
+                    .as_ref()
+                    .map(|fingerprint| STANDARD.encode(fingerprint))
+                    .unwrap_or_default(),
+                self.fingerprint.is_some(),
+            ),
+            (
+                self.additional_labels
+                    .iter()
+                    .map(|label| STANDARD.encode(label))
+                    .collect::<Vec<_>>()
+                    .join("."),
+                self.additional_labels.iter().any(|label| !label.is_empty()),
+            ),
+        ];
+        while parts.last().is_some_and(|(_, set)| !set) {
+            parts.pop();
+        }
+        for (part, _) in parts {
+            value.push('-');
+            value.push_str(&part);
+        }
+        value.into()
     }
 
     /// Checks if the share is encrypted.
@@ -74,6 +201,29 @@ impl Share {
         self.encryption != Encryption::None
     }
 
+    /// The number of shares needed to reconstruct the secret.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// The total number of shares the secret was split into.
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// The label describing who holds the share, if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// The [`ShareScheme`] this share was created with, including the labels of the additional shares.
+    pub fn scheme(&self) -> ShareScheme {
+        ShareScheme {
+            threshold: self.threshold,
+            additional_labels: self.additional_labels.clone(),
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn mock_share() -> Self {
         Share {
@@ -81,6 +231,11 @@ impl Share {
             encoding: Encoding::RustySecrets,
             encryption: Encryption::None,
             data: ShareData("test".to_string().into_bytes().into()),
+            threshold: DEFAULT_THRESHOLD,
+            count: DEFAULT_COUNT,
+            label: None,
+            fingerprint: None,
+            additional_labels: Vec::new(),
         }
     }
 }
@@ -90,12 +245,20 @@ impl Share {
 enum PayloadType {
     /// Payload contains the raw entropy bytes stored in the mnemonic.
     MnemonicEntropy,
+    /// Payload contains the code of the wordlist language as one byte and the raw entropy bytes stored in the
+    /// mnemonic. Used for wallets which use a BIP-39 passphrase, which itself is never part of the shares.
+    MnemonicEntropyWithPassphrase,
+    /// Payload contains the code of the wordlist language as one byte and the raw entropy bytes stored in the
+    /// mnemonic. Used for mnemonics which do not use the English wordlist.
+    MnemonicEntropyWithLanguage,
 }
 
 impl std::fmt::Display for PayloadType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::MnemonicEntropy => write!(f, "ME"),
+            Self::MnemonicEntropyWithPassphrase => write!(f, "MP"),
+            Self::MnemonicEntropyWithLanguage => write!(f, "ML"),
         }
     }
 }
@@ -106,6 +269,8 @@ impl FromStr for PayloadType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "ME" => Ok(Self::MnemonicEntropy),
+            "MP" => Ok(Self::MnemonicEntropyWithPassphrase),
+            "ML" => Ok(Self::MnemonicEntropyWithLanguage),
             other => Err(ShareError::InvalidShareFormat(format!(
                 "Unrecognized Payload type: `{}`",
                 other
@@ -147,6 +312,10 @@ impl FromStr for Encoding {
 enum Encryption {
     None,
     AesGcm,
+    /// Encrypted for the holder of an x25519 secret key, e.g. a guardian
+    X25519,
+    /// Encrypted with a key derived from the pin of the user, used for the shares stored on the device
+    Pin,
 }
 
 impl std::fmt::Display for Encryption {
@@ -154,6 +323,8 @@ impl std::fmt::Display for Encryption {
         match self {
             Self::None => write!(f, "N"),
             Self::AesGcm => write!(f, "AesGcm"),
+            Self::X25519 => write!(f, "X25519"),
+            Self::Pin => write!(f, "Pin"),
         }
     }
 }
@@ -165,6 +336,8 @@ impl FromStr for Encryption {
         match s {
             "N" => Ok(Self::None),
             "AesGcm" => Ok(Self::AesGcm),
+            "X25519" => Ok(Self::X25519),
+            "Pin" => Ok(Self::Pin),
             other => Err(ShareError::InvalidShareFormat(format!(
                 "Unrecognized Encryption: `{}`",
                 other
@@ -192,6 +365,9 @@ pub enum ShareError {
     #[error("Provided shares are incompatible: {0}")]
     IncompatibleShares(String),
 
+    #[error("Invalid share scheme: {0}")]
+    InvalidScheme(String),
+
     #[error("Error while decrypting / encrypting: {0}")]
     EncryptionError(&'static str),
 
@@ -205,6 +381,61 @@ pub enum ShareError {
     RustySecretsError(#[from] rusty_secrets::errors::Error),
 }
 
+/// Describes how many shares are created from a secret and how many of them are needed to reconstruct it.
+///
+/// The recovery, local and backup share are always created. Additional shares, e.g. for trusted contacts
+/// or a hardware backup, are created for each label in `additional_labels`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareScheme {
+    /// The number of shares needed to reconstruct the secret.
+    pub threshold: u8,
+
+    /// The labels of the additional shares. An empty label creates an unlabeled share.
+    pub additional_labels: Vec<String>,
+}
+
+impl Default for ShareScheme {
+    /// The 2-of-3 scheme with the recovery, local and backup share only.
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_THRESHOLD,
+            additional_labels: Vec::new(),
+        }
+    }
+}
+
+impl ShareScheme {
+    /// The total number of shares created with this scheme.
+    pub fn count(&self) -> usize {
+        usize::from(DEFAULT_COUNT) + self.additional_labels.len()
+    }
+
+    /// Checks that the threshold is at least two and not larger than the number of shares.
+    pub fn validate(&self) -> Result<(), ShareError> {
+        if self.threshold < DEFAULT_THRESHOLD {
+            return Err(ShareError::InvalidScheme(format!(
+                "threshold must be at least {DEFAULT_THRESHOLD}, but is {}",
+                self.threshold
+            )));
+        }
+        if self.count() > usize::from(u8::MAX) {
+            return Err(ShareError::InvalidScheme(format!(
+                "at most {} shares are supported, but {} are configured",
+                u8::MAX,
+                self.count()
+            )));
+        }
+        if usize::from(self.threshold) > self.count() {
+            return Err(ShareError::InvalidScheme(format!(
+                "threshold {} is larger than the number of shares {}",
+                self.threshold,
+                self.count()
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 /// Contains all the shares generated by splitting a secret
 pub struct GeneratedShares {
@@ -214,39 +445,86 @@ pub struct GeneratedShares {
     pub local: Share,
     /// backup share that is shared with etopay backend, encrypted
     pub backup: Share,
+    /// additional shares in the order of [`ShareScheme::additional_labels`], to be handed out by the user
+    pub additional: Vec<Share>,
 }
 
 /// Creates shares from a [`Mnemonic`] that can be resolved into a [`Mnemonic`] again when reconstructed.
+///
+/// The BIP-39 passphrase of a wallet is never stored in the shares, only whether the wallet `uses_passphrase`,
+/// so it can be asked for when reconstructing. The language of the wordlist is detected and stored in the
+/// shares if it is not English.
 pub fn create_shares_from_mnemonic(
     mnemonic: impl Into<Mnemonic>,
+    uses_passphrase: bool,
     password: &SecretSlice<u8>,
+    scheme: &ShareScheme,
 ) -> super::error::Result<GeneratedShares> {
-    let mnemonic: Mnemonic = mnemonic.into();
+    let (payload_type, secret) = mnemonic_payload(&mnemonic.into(), uses_passphrase)?;
+    create_shares_from_secret(payload_type, &secret, password, scheme).map_err(Into::into)
+}
+
+/// Checks with the fingerprint of `share` whether it was created from `mnemonic`, without needing any other
+/// share. Returns [`None`] if the share has no fingerprint, e.g. because an older version created it.
+pub(crate) fn is_secret_of_share(share: &Share, mnemonic: impl Into<Mnemonic>) -> super::error::Result<Option<bool>> {
+    let Some(expected) = share.fingerprint else {
+        return Ok(None);
+    };
+    let uses_passphrase = share.payload_type == PayloadType::MnemonicEntropyWithPassphrase;
+    let (_, secret) = mnemonic_payload(&mnemonic.into(), uses_passphrase)?;
+    Ok(Some(fingerprint(&secret) == expected))
+}
 
+/// Encodes the mnemonic as the payload stored in the shares.
+fn mnemonic_payload(
+    mnemonic: &Mnemonic,
+    uses_passphrase: bool,
+) -> super::error::Result<(PayloadType, SecretSlice<u8>)> {
     // convert the mnemonic string into the raw entropy it encodes
-    let entropy =
-        iota_sdk::crypto::keys::bip39::wordlist::decode(&mnemonic, &iota_sdk::crypto::keys::bip39::wordlist::ENGLISH)?;
+    let (language, entropy) = decode_entropy(mnemonic.as_ref())?;
 
     let entropy_bytes: &[u8] = entropy.as_ref();
-    create_shares_from_secret(PayloadType::MnemonicEntropy, &entropy_bytes.to_vec().into(), password)
-        .map_err(Into::into)
+    let (payload_type, secret) = match (language, uses_passphrase) {
+        (MnemonicLanguage::English, false) => (PayloadType::MnemonicEntropy, entropy_bytes.to_vec()),
+        (language, uses_passphrase) => {
+            let mut secret = Vec::with_capacity(1 + entropy_bytes.len());
+            secret.push(language.code());
+            secret.extend_from_slice(entropy_bytes);
+            let payload_type = if uses_passphrase {
+                PayloadType::MnemonicEntropyWithPassphrase
+            } else {
+                PayloadType::MnemonicEntropyWithLanguage
+            };
+            (payload_type, secret)
+        }
+    };
+
+    Ok((payload_type, secret.into()))
 }
 
-/// Reconstruct a [`Mnemonic`] from the shares. Can be used to initialize a wallet using the
+/// Reconstruct a [`Mnemonic`] from the shares, and whether the wallet uses a BIP-39 passphrase which is kept
+/// outside of the shares. Can be used to initialize a wallet using the
 /// [`iota_sdk::client::secret::mnemonic::MnemonicSecretManager::try_from_mnemonic`] function.
 pub fn reconstruct_mnemonic(
     shares: &[&Share],
     password: Option<&SecretSlice<u8>>,
-) -> super::error::Result<SecretBox<Mnemonic>> {
+) -> super::error::Result<(SecretBox<Mnemonic>, bool)> {
     let (payload_type, secret) = reconstruct_secret(shares, password)?;
-    match payload_type {
-        PayloadType::MnemonicEntropy => Ok(SecretBox::new(Box::new(
-            iota_sdk::crypto::keys::bip39::wordlist::encode(
-                secret.expose_secret(),
-                &iota_sdk::crypto::keys::bip39::wordlist::ENGLISH,
-            )?,
-        ))),
-    }
+    let (language, entropy) = match payload_type {
+        PayloadType::MnemonicEntropy => (MnemonicLanguage::English, secret.expose_secret()),
+        PayloadType::MnemonicEntropyWithPassphrase | PayloadType::MnemonicEntropyWithLanguage => {
+            let Some((&code, entropy)) = secret.expose_secret().split_first() else {
+                return Err(ShareError::InvalidShareFormat("Empty payload".to_string()).into());
+            };
+            (MnemonicLanguage::from_code(code)?, entropy)
+        }
+    };
+
+    let mnemonic = encode_entropy(entropy, language);
+    Ok((
+        SecretBox::new(Box::new(mnemonic)),
+        payload_type == PayloadType::MnemonicEntropyWithPassphrase,
+    ))
 }
 
 /// Creates shares from any secret represented as a vector of bytes.
@@ -254,10 +532,18 @@ fn create_shares_from_secret(
     payload_type: PayloadType,
     secret: &SecretSlice<u8>,
     password: &SecretSlice<u8>,
+    scheme: &ShareScheme,
 ) -> Result<GeneratedShares, ShareError> {
+    scheme.validate()?;
+    let threshold = scheme.threshold;
+    // cannot fail since the scheme is validated
+    let count = u8::try_from(scheme.count()).unwrap_or(u8::MAX);
+
+    let fingerprint = Some(fingerprint(secret));
+
     let out = rusty_secrets::dss::ss1::split_secret(
-        2,
-        3,
+        threshold,
+        count,
         secret.expose_secret(),
         // we specify reproducibility since we want to be able to regenerate the local share from
         // the others, and we need the signatures to match
@@ -270,6 +556,11 @@ fn create_shares_from_secret(
         encoding: Encoding::RustySecrets,
         encryption: Encryption::None,
         data: ShareData(s.into_string().into_bytes().into()),
+        threshold,
+        count,
+        label: None,
+        fingerprint,
+        additional_labels: scheme.additional_labels.clone(),
     });
 
     let recovery = share_data_iter.next().ok_or(ShareError::NotEnoughParts)?;
@@ -278,12 +569,21 @@ fn create_shares_from_secret(
 
     // encrypt the backup / recovery share(s) with the password
     backup.encryption = Encryption::AesGcm;
-    backup.data = encrypt_with_password(&backup.data, password)?;
+    backup.data = encrypt_with_password(&backup.data, password, backup.associated_data())?;
+
+    let additional = share_data_iter
+        .zip(&scheme.additional_labels)
+        .map(|(share, label)| Share {
+            label: (!label.is_empty()).then(|| label.clone()),
+            ..share
+        })
+        .collect();
 
     Ok(GeneratedShares {
         recovery,
         local,
         backup,
+        additional,
     })
 }
 
@@ -295,10 +595,24 @@ fn reconstruct_secret(
     let Some(share) = shares.first() else {
         return Err(ShareError::NotEnoughShares {
             provided: shares.len(),
-            required: 2,
+            required: usize::from(DEFAULT_THRESHOLD),
         });
     };
 
+    // make sure all shares were created with the same scheme
+    let (threshold, count) = (share.threshold, share.count);
+    if !shares.iter().all(|s| s.threshold == threshold && s.count == count) {
+        return Err(ShareError::IncompatibleShares(format!(
+            "All shares must use the same scheme, first share uses `{threshold}of{count}`"
+        )));
+    }
+    if shares.len() < usize::from(threshold) {
+        return Err(ShareError::NotEnoughShares {
+            provided: shares.len(),
+            required: usize::from(threshold),
+        });
+    }
+
     // make sure all shares have the same payload type
     let payload_type = share.payload_type;
     if !shares.iter().all(|s| s.payload_type == payload_type) {
@@ -324,8 +638,14 @@ fn reconstruct_secret(
                 let Some(password) = password else {
                     return Err(ShareError::PasswordNotProvided);
                 };
-                decrypt_with_password(&s.data, password)
+                decrypt_with_password(&s.data, password, s.associated_data())
             }
+            Encryption::X25519 => Err(ShareError::EncryptionError(
+                "Share encrypted for a public key must be decrypted first",
+            )),
+            Encryption::Pin => Err(ShareError::EncryptionError(
+                "Share encrypted with the pin must be decrypted first",
+            )),
         })
         .collect::<Result<Vec<ShareData>, ShareError>>()?;
 
@@ -339,15 +659,431 @@ fn reconstruct_secret(
 
             let (secret, _access_structure, _metadata) =
                 rusty_secrets::dss::ss1::recover_secret(&rusty_secrets_shares)?;
+            let secret: SecretSlice<u8> = SecretBox::new(secret.into());
+
+            // shares of older versions have no fingerprint to check
+            let expected = fingerprint(&secret);
+            if shares.iter().any(|s| s.fingerprint.is_some_and(|f| f != expected)) {
+                return Err(ShareError::IncompatibleShares(
+                    "The fingerprint of the shares does not match the reconstructed secret".to_string(),
+                ));
+            }
+
+            Ok((payload_type, secret))
+        }
+    }
+}
+
+/// Health of a single share, as reported by [`check_share_health`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ShareHealth {
+    /// The share is valid and belongs to the same secret as the other shares.
+    Healthy,
+    /// The share is not available.
+    Missing,
+    /// The share cannot be used as it is stored anymore, because it is encrypted with another password than
+    /// the current one or stored in plain text by an older version.
+    Stale,
+    /// The share is valid, but was created by an older version without fingerprint, so it is unknown whether
+    /// it belongs to the same secret as the other shares.
+    Unknown,
+    /// The share belongs to another secret or share scheme than the other shares.
+    Inconsistent,
+    /// The share data is not valid.
+    Corrupt,
+}
+
+/// Health of the shares of the wallet of a user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShareHealthReport {
+    /// The local share stored in the user repository.
+    pub local: ShareHealth,
+    /// The encrypted backup share stored in the backend.
+    pub backup: ShareHealth,
+    /// The recovery share stored in the backend.
+    pub recovery: ShareHealth,
+    /// The recovery share provided by the user.
+    pub provided_recovery: ShareHealth,
+    /// The additional shares provided by the user or created with the wallet.
+    pub additional: Vec<ShareHealth>,
+}
+
+impl ShareHealthReport {
+    /// Checks if the shares should be split and uploaded again. This is the case if any of the local, backup
+    /// and recovery share is not healthy, or any share provided by the user is not usable.
+    pub fn needs_repair(&self) -> bool {
+        [self.local, self.backup, self.recovery]
+            .iter()
+            .any(|health| *health != ShareHealth::Healthy)
+            || std::iter::once(&self.provided_recovery)
+                .chain(&self.additional)
+                .any(|health| !matches!(health, ShareHealth::Healthy | ShareHealth::Missing))
+    }
+}
+
+/// Check the health of the shares without reconstructing the secret.
+///
+/// Encrypted shares are decrypted with the password, and all shares are checked to be valid. The shares are
+/// expected to commit to the same secret and scheme, and the commitment of the most shares (or the first of
+/// them in case of a tie) is taken as reference. Shares with another commitment are reported as
+/// [`ShareHealth::Inconsistent`].
+pub(crate) fn check_share_health(shares: &[Option<&Share>], password: Option<&SecretSlice<u8>>) -> Vec<ShareHealth> {
+    let mut health = shares
+        .iter()
+        .map(|share| share.map_or(ShareHealth::Missing, |share| verify_share(share, password)))
+        .collect::<Vec<_>>();
+
+    // the secret and scheme each share commits to
+    let commitments = shares
+        .iter()
+        .map(|share| share.map(|s| (s.fingerprint, s.threshold, s.count)))
+        .collect::<Vec<_>>();
+    let candidates = commitments
+        .iter()
+        .zip(&health)
+        .filter(|(_, health)| **health == ShareHealth::Healthy)
+        .filter_map(|(commitment, _)| *commitment)
+        .collect::<Vec<_>>();
+    let reference = candidates
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, candidate)| {
+            let occurrences = candidates.iter().filter(|c| c == candidate).count();
+            (occurrences, std::cmp::Reverse(*index))
+        })
+        .map(|(_, candidate)| *candidate);
 
-            Ok((payload_type, SecretBox::new(secret.into())))
+    let Some((reference_fingerprint, reference_threshold, reference_count)) = reference else {
+        return health;
+    };
+    for (health, commitment) in health.iter_mut().zip(commitments) {
+        let Some((fingerprint, threshold, count)) = commitment else {
+            continue;
+        };
+        let same_scheme = threshold == reference_threshold && count == reference_count;
+        let same_secret = fingerprint.is_none() || fingerprint == reference_fingerprint;
+        if matches!(
+            *health,
+            ShareHealth::Healthy | ShareHealth::Stale | ShareHealth::Unknown
+        ) && !(same_scheme && same_secret)
+        {
+            *health = ShareHealth::Inconsistent;
         }
     }
+    health
+}
+
+impl Share {
+    /// Checks if the share is not encrypted or can be decrypted with the password.
+    pub(crate) fn can_decrypt(&self, password: Option<&SecretSlice<u8>>) -> bool {
+        match self.encryption {
+            Encryption::None => true,
+            Encryption::AesGcm => password
+                .is_some_and(|password| decrypt_with_password(&self.data, password, self.associated_data()).is_ok()),
+            Encryption::X25519 | Encryption::Pin => false,
+        }
+    }
+
+    /// The data authenticated together with the encrypted share data, so the fingerprint of an encrypted
+    /// share cannot be replaced. Empty for shares without fingerprint, which keeps them compatible with
+    /// shares encrypted by older versions.
+    fn associated_data(&self) -> &[u8] {
+        self.fingerprint
+            .as_ref()
+            .map_or(&[], |fingerprint| fingerprint.as_slice())
+    }
+}
+
+/// Encrypts the local share with a key derived from the pin and salt of the user, before it is
+/// stored on the device.
+pub(crate) fn encrypt_local_share(
+    share: &Share,
+    pin: &EncryptionPin,
+    salt: &EncryptionSalt,
+) -> Result<Share, ShareError> {
+    if share.is_encrypted() {
+        return Err(ShareError::EncryptionError("Local share is already encrypted"));
+    }
+    let key = pin
+        .derive_local_share_key(salt)
+        .map_err(|_| ShareError::EncryptionError("Error deriving local share key from pin"))?;
+
+    Ok(Share {
+        encryption: Encryption::Pin,
+        data: encrypt_with_password(&share.data, &key, share.associated_data())?,
+        ..share.clone()
+    })
+}
+
+/// Decrypts a local share encrypted with [`encrypt_local_share`].
+pub(crate) fn decrypt_local_share(
+    share: &Share,
+    pin: &EncryptionPin,
+    salt: &EncryptionSalt,
+) -> Result<Share, ShareError> {
+    if share.encryption != Encryption::Pin {
+        return Err(ShareError::EncryptionError("Local share is not encrypted with the pin"));
+    }
+    let key = pin
+        .derive_local_share_key(salt)
+        .map_err(|_| ShareError::EncryptionError("Error deriving local share key from pin"))?;
+
+    Ok(Share {
+        encryption: Encryption::None,
+        data: decrypt_with_password(&share.data, &key, share.associated_data())?,
+        ..share.clone()
+    })
+}
+
+/// Re-encrypts a stored local share with a new pin and salt, e.g. when the pin is changed. Local shares
+/// stored in plain text by older versions are encrypted for the first time.
+pub(crate) fn reencrypt_local_share(
+    stored: &str,
+    (old_pin, old_salt): (&EncryptionPin, &EncryptionSalt),
+    (new_pin, new_salt): (&EncryptionPin, &EncryptionSalt),
+) -> Result<String, ShareError> {
+    let share = stored.parse::<Share>()?;
+    let share = if share.is_encrypted() {
+        decrypt_local_share(&share, old_pin, old_salt)?
+    } else {
+        share
+    };
+    let share = encrypt_local_share(&share, new_pin, new_salt)?;
+    Ok(share.to_string().expose_secret().to_string())
+}
+
+/// A guardian of the user, who holds a share to help with the recovery of the wallet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guardian {
+    /// The username of the guardian.
+    pub username: String,
+
+    /// The base64 encoded x25519 public key of the guardian, see [`generate_secret_key`].
+    pub public_key: String,
+}
+
+/// Parses a base64 encoded x25519 public key.
+fn parse_public_key(public_key: &str) -> Result<x25519::PublicKey, ShareError> {
+    let bytes = STANDARD.decode(public_key)?;
+    x25519::PublicKey::try_from_slice(&bytes)
+        .map_err(|e| ShareError::InvalidShareFormat(format!("invalid public key: {e}")))
+}
+
+/// Parses the bytes of an x25519 secret key.
+fn parse_secret_key(secret_key: &SecretSlice<u8>) -> Result<x25519::SecretKey, ShareError> {
+    x25519::SecretKey::try_from_slice(secret_key.expose_secret())
+        .map_err(|_| ShareError::EncryptionError("Invalid secret key"))
 }
 
-fn encrypt_with_password(data: &ShareData, key: &SecretSlice<u8>) -> Result<ShareData, ShareError> {
+/// Returns the base64 encoded x25519 public key of the secret key.
+pub(crate) fn public_key(secret_key: &SecretSlice<u8>) -> Result<String, ShareError> {
+    Ok(STANDARD.encode(parse_secret_key(secret_key)?.public_key().to_bytes()))
+}
+
+/// Returns a short fingerprint of the base64 encoded x25519 public key, e.g. `1a2b-3c4d-5e6f-7a8b`, which
+/// can be read out and compared to make sure a public key was not replaced on its way.
+pub(crate) fn public_key_fingerprint(public_key: &str) -> Result<String, ShareError> {
+    let public_key = parse_public_key(public_key)?;
+    let hash = Blake2b256::new()
+        .chain_update(b"etopay public key fingerprint")
+        .chain_update(public_key.to_bytes())
+        .finalize();
+    Ok(hash[..8]
+        .chunks(2)
+        .map(|chunk| chunk.iter().map(|byte| format!("{byte:02x}")).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-"))
+}
+
+/// Generates a random x25519 secret key, e.g. the key of a guardian or the key to receive the shares
+/// approved by the guardians.
+pub(crate) fn generate_secret_key() -> SecretSlice<u8> {
+    let mut key = vec![0u8; x25519::SECRET_KEY_LENGTH];
+    rand::rng().fill_bytes(&mut key);
+    SecretBox::new(key.into())
+}
+
+/// Encrypts an x25519 secret key with a key derived from the pin and salt of the user, before it is stored
+/// on the device. Returns the base64 encoded nonce and ciphertext.
+pub(crate) fn encrypt_secret_key(
+    secret_key: &SecretSlice<u8>,
+    pin: &EncryptionPin,
+    salt: &EncryptionSalt,
+) -> Result<String, ShareError> {
+    let key = pin
+        .derive_local_share_key(salt)
+        .map_err(|_| ShareError::EncryptionError("Error deriving secret key encryption key from pin"))?;
+    let data = ShareData(secret_key.expose_secret().into());
+    Ok(STANDARD.encode(encrypt_with_password(&data, &key, b"etopay secret key")?.0))
+}
+
+/// Decrypts a secret key encrypted with [`encrypt_secret_key`].
+pub(crate) fn decrypt_secret_key(
+    stored: &str,
+    pin: &EncryptionPin,
+    salt: &EncryptionSalt,
+) -> Result<SecretSlice<u8>, ShareError> {
+    let key = pin
+        .derive_local_share_key(salt)
+        .map_err(|_| ShareError::EncryptionError("Error deriving secret key encryption key from pin"))?;
+    let data = decrypt_with_password(&ShareData(STANDARD.decode(stored)?.into()), &key, b"etopay secret key")?;
+    Ok(SecretBox::new(data.0.clone()))
+}
+
+/// Re-encrypts a stored secret key with a new pin and salt, like [`reencrypt_local_share`].
+pub(crate) fn reencrypt_secret_key(
+    stored: &str,
+    (old_pin, old_salt): (&EncryptionPin, &EncryptionSalt),
+    (new_pin, new_salt): (&EncryptionPin, &EncryptionSalt),
+) -> Result<String, ShareError> {
+    encrypt_secret_key(&decrypt_secret_key(stored, old_pin, old_salt)?, new_pin, new_salt)
+}
+
+/// Encrypts the BIP-39 passphrase of the wallet with a key derived from the pin and salt of the user. The
+/// passphrase is not part of the shares, so it is only stored on the device. Returns the base64 encoded
+/// nonce and ciphertext.
+pub(crate) fn encrypt_passphrase(
+    passphrase: &MnemonicPassphrase,
+    pin: &EncryptionPin,
+    salt: &EncryptionSalt,
+) -> Result<String, ShareError> {
+    let key = pin
+        .derive_local_share_key(salt)
+        .map_err(|_| ShareError::EncryptionError("Error deriving passphrase encryption key from pin"))?;
+    let data = ShareData(passphrase.as_str().as_bytes().into());
+    Ok(STANDARD.encode(encrypt_with_password(&data, &key, b"etopay passphrase")?.0))
+}
+
+/// Decrypts a passphrase encrypted with [`encrypt_passphrase`].
+pub(crate) fn decrypt_passphrase(
+    stored: &str,
+    pin: &EncryptionPin,
+    salt: &EncryptionSalt,
+) -> Result<MnemonicPassphrase, ShareError> {
+    let key = pin
+        .derive_local_share_key(salt)
+        .map_err(|_| ShareError::EncryptionError("Error deriving passphrase encryption key from pin"))?;
+    let data = decrypt_with_password(&ShareData(STANDARD.decode(stored)?.into()), &key, b"etopay passphrase")?;
+    std::str::from_utf8(&data.0)
+        .ok()
+        .and_then(|passphrase| MnemonicPassphrase::try_from_string(passphrase).ok())
+        .ok_or(ShareError::EncryptionError("Invalid stored passphrase"))
+}
+
+/// Re-encrypts a stored passphrase with a new pin and salt, like [`reencrypt_local_share`].
+pub(crate) fn reencrypt_passphrase(
+    stored: &str,
+    (old_pin, old_salt): (&EncryptionPin, &EncryptionSalt),
+    (new_pin, new_salt): (&EncryptionPin, &EncryptionSalt),
+) -> Result<String, ShareError> {
+    encrypt_passphrase(&decrypt_passphrase(stored, old_pin, old_salt)?, new_pin, new_salt)
+}
+
+/// Encrypts a share for the holder of the secret key belonging to the base64 encoded `public_key`.
+///
+/// A new ephemeral key pair is used for every share. The key agreed with Diffie-Hellman is used to
+/// encrypt the data like a password, and the ephemeral public key is stored in front of it.
+pub(crate) fn encrypt_for_public_key(share: &Share, public_key: &str) -> Result<Share, ShareError> {
+    if share.is_encrypted() {
+        return Err(ShareError::EncryptionError("Share is already encrypted"));
+    }
+    let public_key = parse_public_key(public_key)?;
+    let ephemeral = parse_secret_key(&generate_secret_key())?;
+    let ephemeral_public = ephemeral.public_key();
+    let shared = ephemeral.diffie_hellman(&public_key);
+    let key = agreed_key(shared.as_bytes(), &ephemeral_public, &public_key);
+
+    let mut data = ephemeral_public.to_bytes().to_vec();
+    data.extend_from_slice(&encrypt_with_password(&share.data, &key, share.associated_data())?.0);
+
+    Ok(Share {
+        encryption: Encryption::X25519,
+        data: ShareData(data.into()),
+        ..share.clone()
+    })
+}
+
+/// Decrypts a share encrypted with [`encrypt_for_public_key`] using the secret key.
+pub(crate) fn decrypt_with_secret_key(share: &Share, secret_key: &SecretSlice<u8>) -> Result<Share, ShareError> {
+    if share.encryption != Encryption::X25519 {
+        return Err(ShareError::EncryptionError("Share is not encrypted for a public key"));
+    }
+    if share.data.0.len() <= x25519::PUBLIC_KEY_LENGTH {
+        return Err(ShareError::InvalidShareFormat(
+            "not enough data for the ephemeral public key".to_string(),
+        ));
+    }
+    let secret_key = parse_secret_key(secret_key)?;
+    let (ephemeral_public, data) = share.data.0.split_at(x25519::PUBLIC_KEY_LENGTH);
+    let ephemeral_public = x25519::PublicKey::try_from_slice(ephemeral_public)
+        .map_err(|e| ShareError::InvalidShareFormat(format!("invalid ephemeral public key: {e}")))?;
+    let shared = secret_key.diffie_hellman(&ephemeral_public);
+    let key = agreed_key(shared.as_bytes(), &ephemeral_public, &secret_key.public_key());
+
+    Ok(Share {
+        encryption: Encryption::None,
+        data: decrypt_with_password(&ShareData(data.into()), &key, share.associated_data())?,
+        ..share.clone()
+    })
+}
+
+/// Hashes the Diffie-Hellman shared secret together with both public keys into the encryption key.
+fn agreed_key(shared: &[u8], ephemeral_public: &x25519::PublicKey, public_key: &x25519::PublicKey) -> SecretSlice<u8> {
+    let key = Blake2b256::new()
+        .chain_update(b"etopay guardian share")
+        .chain_update(shared)
+        .chain_update(ephemeral_public.to_bytes())
+        .chain_update(public_key.to_bytes())
+        .finalize();
+    SecretBox::new(key.as_slice().into())
+}
+
+/// Check that a single share is valid and has a fingerprint.
+fn verify_share(share: &Share, password: Option<&SecretSlice<u8>>) -> ShareHealth {
+    let data = match share.encryption {
+        Encryption::None => share.data.clone(),
+        Encryption::AesGcm => {
+            match password.map(|password| decrypt_with_password(&share.data, password, share.associated_data())) {
+                Some(Ok(data)) => data,
+                // encrypted with another password than the current one
+                Some(Err(_)) | None => return ShareHealth::Stale,
+            }
+        }
+        // encrypted for a guardian or with the pin, so only the fingerprint can be checked
+        Encryption::X25519 | Encryption::Pin if share.fingerprint.is_some() => return ShareHealth::Healthy,
+        Encryption::X25519 | Encryption::Pin => return ShareHealth::Unknown,
+    };
+
+    let valid = match share.encoding {
+        Encoding::RustySecrets => {
+            rusty_secrets::dss::ss1::Share::from_string(&String::from_utf8_lossy(&data.0)).is_ok()
+        }
+    };
+
+    if !valid {
+        ShareHealth::Corrupt
+    } else if share.fingerprint.is_none() {
+        ShareHealth::Unknown
+    } else {
+        ShareHealth::Healthy
+    }
+}
+
+/// The fingerprint of a secret stored in its shares.
+fn fingerprint(secret: &SecretSlice<u8>) -> [u8; FINGERPRINT_LEN] {
+    let hash = Blake2b256::new()
+        .chain_update(b"etopay share fingerprint")
+        .chain_update(secret.expose_secret())
+        .finalize();
+    let mut fingerprint = [0; FINGERPRINT_LEN];
+    fingerprint.copy_from_slice(&hash[..FINGERPRINT_LEN]);
+    fingerprint
+}
+
+/// Encrypts the data with a key derived from `key`, authenticating `aad` as associated data.
+fn encrypt_with_password(data: &ShareData, key: &SecretSlice<u8>, aad: &[u8]) -> Result<ShareData, ShareError> {
     use aes_gcm::{
-        aead::{Aead, AeadCore, KeyInit, OsRng},
+        aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
         Aes256Gcm, Key,
     };
 
@@ -367,7 +1103,7 @@ fn encrypt_with_password(data: &ShareData, key: &SecretSlice<u8>) -> Result<Shar
 
     // encrypt the data and prepend the nonce value (to use while decrypting)
     let encrypted = cipher
-        .encrypt(&nonce, data.0.as_ref())
+        .encrypt(&nonce, Payload { msg: &data.0, aad })
         .map_err(|_| ShareError::EncryptionError("Error encrypting share using password"))?;
 
     let mut data = nonce.to_vec();
@@ -375,9 +1111,11 @@ fn encrypt_with_password(data: &ShareData, key: &SecretSlice<u8>) -> Result<Shar
     Ok(ShareData(data.into()))
 }
 
-fn decrypt_with_password(data: &ShareData, key: &SecretSlice<u8>) -> Result<ShareData, ShareError> {
+/// Decrypts data encrypted with [`encrypt_with_password`], which fails if `aad` is not the associated data
+/// used for the encryption.
+fn decrypt_with_password(data: &ShareData, key: &SecretSlice<u8>, aad: &[u8]) -> Result<ShareData, ShareError> {
     use aes_gcm::{
-        aead::{Aead, KeyInit},
+        aead::{Aead, KeyInit, Payload},
         Aes256Gcm, Key, Nonce,
     };
 
@@ -407,7 +1145,7 @@ fn decrypt_with_password(data: &ShareData, key: &SecretSlice<u8>) -> Result<Shar
 
     Ok(ShareData(
         cipher
-            .decrypt(nonce, data)
+            .decrypt(nonce, Payload { msg: data, aad })
             .map_err(|_| ShareError::EncryptionError("Error decrypting share using password"))?
             .into(),
     ))
@@ -416,6 +1154,10 @@ fn decrypt_with_password(data: &ShareData, key: &SecretSlice<u8>) -> Result<Shar
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{
+        mnemonic::{generate_mnemonic, MnemonicError, MnemonicOptions, MnemonicWordCount},
+        WalletError,
+    };
     use secrecy::SecretBox;
 
     #[test]
@@ -425,6 +1167,11 @@ mod test {
             encoding: Encoding::RustySecrets,
             encryption: super::Encryption::None,
             data: ShareData("data".to_string().into_bytes().into()),
+            threshold: DEFAULT_THRESHOLD,
+            count: DEFAULT_COUNT,
+            label: None,
+            fingerprint: None,
+            additional_labels: Vec::new(),
         };
         println!("{:?}, {}, {:?}", s.to_string(), s.to_string().expose_secret(), s);
 
@@ -437,7 +1184,13 @@ mod test {
         let secret = SecretBox::new("secret".to_string().into_bytes().into());
         let password = SecretBox::new("password".to_string().into_bytes().into());
 
-        let shares = create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &password).unwrap();
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
 
         assert_eq!(
             reconstruct_secret(&[&shares.backup, &shares.local], Some(&password))
@@ -479,15 +1232,26 @@ mod test {
         let secret = SecretBox::new("my hex string".to_string().into_bytes().into());
         let password = SecretBox::new("password".to_string().into_bytes().into());
 
-        let shares = create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &password).unwrap();
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
 
         // reconstruct using backup and recovery
         let (_, reconstructed_secret) =
             reconstruct_secret(&[&shares.backup, &shares.recovery], Some(&password)).unwrap();
 
         // now create shares again and make sure we
-        let new_shares =
-            create_shares_from_secret(PayloadType::MnemonicEntropy, &reconstructed_secret, &password).unwrap();
+        let new_shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &reconstructed_secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
 
         // reconstruct using a mix of old and "new" shares
         let (_, final_secret) = reconstruct_secret(&[&shares.backup, &new_shares.local], Some(&password)).unwrap();
@@ -503,11 +1267,12 @@ mod test {
         let mnemonic = iota_sdk::client::Client::generate_mnemonic().unwrap();
 
         // Perform and check
-        let shares = create_shares_from_mnemonic(mnemonic.clone(), &password).unwrap();
+        let shares = create_shares_from_mnemonic(mnemonic.clone(), false, &password, &ShareScheme::default()).unwrap();
 
         assert_eq!(
             reconstruct_mnemonic(&[&shares.backup, &shares.local], Some(&password))
                 .unwrap()
+                .0
                 .expose_secret()
                 .as_bytes(),
             mnemonic.as_bytes()
@@ -515,6 +1280,7 @@ mod test {
         assert_eq!(
             reconstruct_mnemonic(&[&shares.backup, &shares.recovery], Some(&password))
                 .unwrap()
+                .0
                 .expose_secret()
                 .as_bytes(),
             mnemonic.as_bytes()
@@ -522,6 +1288,7 @@ mod test {
         assert_eq!(
             reconstruct_mnemonic(&[&shares.recovery, &shares.local], None)
                 .unwrap()
+                .0
                 .expose_secret()
                 .as_bytes(),
             mnemonic.as_bytes()
@@ -529,6 +1296,7 @@ mod test {
         assert_eq!(
             reconstruct_mnemonic(&[&shares.recovery, &shares.local, &shares.backup], Some(&password))
                 .unwrap()
+                .0
                 .expose_secret()
                 .as_bytes(),
             mnemonic.as_bytes()
@@ -539,6 +1307,92 @@ mod test {
         assert!(reconstruct_mnemonic(&[&shares.backup], Some(&password)).is_err());
     }
 
+    #[test]
+    fn test_is_secret_of_share() {
+        // Arrange
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let mnemonic = iota_sdk::client::Client::generate_mnemonic().unwrap();
+        let other = iota_sdk::client::Client::generate_mnemonic().unwrap();
+        let shares = create_shares_from_mnemonic(mnemonic.clone(), false, &password, &ShareScheme::default()).unwrap();
+        let with_passphrase =
+            create_shares_from_mnemonic(mnemonic.clone(), true, &password, &ShareScheme::default()).unwrap();
+        let mut old_share = shares.local.clone();
+        old_share.fingerprint = None;
+
+        // Act & Assert
+        assert_eq!(is_secret_of_share(&shares.local, mnemonic.clone()).unwrap(), Some(true));
+        assert_eq!(is_secret_of_share(&shares.local, other).unwrap(), Some(false));
+        assert_eq!(
+            is_secret_of_share(&with_passphrase.local, mnemonic.clone()).unwrap(),
+            Some(true)
+        );
+        assert_eq!(is_secret_of_share(&old_share, mnemonic).unwrap(), None);
+    }
+
+    #[rstest::rstest]
+    #[case::english(MnemonicLanguage::English, false, "ME-")]
+    #[case::english_with_passphrase(MnemonicLanguage::English, true, "MP-")]
+    #[case::spanish(MnemonicLanguage::Spanish, false, "ML-")]
+    #[case::spanish_with_passphrase(MnemonicLanguage::Spanish, true, "MP-")]
+    fn test_split_recover_mnemonic_with_language_and_passphrase(
+        #[case] language: MnemonicLanguage,
+        #[case] uses_passphrase: bool,
+        #[case] prefix: &str,
+    ) {
+        // Arrange
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let options = MnemonicOptions {
+            word_count: MnemonicWordCount::Words12,
+            language,
+        };
+        let mnemonic = generate_mnemonic(&options);
+
+        // Act
+        let shares =
+            create_shares_from_mnemonic(mnemonic.clone(), uses_passphrase, &password, &ShareScheme::default()).unwrap();
+        let (reconstructed, reconstructed_uses_passphrase) =
+            reconstruct_mnemonic(&[&shares.recovery, &shares.local], None).unwrap();
+
+        // Assert
+        assert!(shares.local.to_string().expose_secret().starts_with(prefix));
+        assert_eq!(reconstructed.expose_secret().as_bytes(), mnemonic.as_bytes());
+        assert_eq!(reconstructed_uses_passphrase, uses_passphrase);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_passphrase() {
+        // Arrange
+        let pin = EncryptionPin::try_from_string("1234").unwrap();
+        let salt = EncryptionSalt::generate();
+        let new_salt = EncryptionSalt::generate();
+        let passphrase = MnemonicPassphrase::try_from_string("correct horse battery staple").unwrap();
+
+        // Act
+        let stored = encrypt_passphrase(&passphrase, &pin, &salt).unwrap();
+        let reencrypted = reencrypt_passphrase(&stored, (&pin, &salt), (&pin, &new_salt)).unwrap();
+
+        // Assert
+        assert!(!stored.contains("correct horse"));
+        assert_eq!(decrypt_passphrase(&stored, &pin, &salt).unwrap(), passphrase);
+        assert_eq!(decrypt_passphrase(&reencrypted, &pin, &new_salt).unwrap(), passphrase);
+        decrypt_passphrase(&stored, &pin, &new_salt).unwrap_err();
+        decrypt_secret_key(&stored, &pin, &salt).unwrap_err();
+    }
+
+    #[test]
+    fn test_create_shares_from_invalid_mnemonic() {
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let mnemonic =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+
+        let result = create_shares_from_mnemonic(mnemonic.to_string(), false, &password, &ShareScheme::default());
+
+        assert!(matches!(
+            result,
+            Err(WalletError::Mnemonic(MnemonicError::InvalidChecksum))
+        ));
+    }
+
     #[test]
     fn test_split_recover_mnemonic_example() {
         let password: SecretSlice<u8> = "mnemonic share password".to_string().into_bytes().into();
@@ -568,6 +1422,7 @@ mod test {
         assert_eq!(
             reconstruct_mnemonic(&shares, Some(&password))
                 .unwrap()
+                .0
                 .expose_secret()
                 .to_string(),
             mnemonic_str,
@@ -582,6 +1437,7 @@ mod test {
         assert_eq!(
             reconstruct_mnemonic(&shares, Some(&password))
                 .unwrap()
+                .0
                 .expose_secret()
                 .to_string(),
             mnemonic_str
@@ -594,18 +1450,441 @@ mod test {
         let shares: Vec<Share> = shares.iter().map(|&s| s.parse::<Share>().unwrap()).collect();
         let shares: Vec<&Share> = shares.iter().collect();
         assert_eq!(
-            reconstruct_mnemonic(&shares, None).unwrap().expose_secret().to_string(),
+            reconstruct_mnemonic(&shares, None)
+                .unwrap()
+                .0
+                .expose_secret()
+                .to_string(),
             mnemonic_str
         );
     }
 
+    #[test]
+    fn test_split_recover_three_of_five() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let scheme = ShareScheme {
+            threshold: 3,
+            additional_labels: vec!["trusted contact".to_string(), "hardware-backup".to_string()],
+        };
+
+        // Act
+        let shares = create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &password, &scheme).unwrap();
+
+        // Assert
+        assert_eq!(shares.additional.len(), 2);
+        assert_eq!(shares.additional[0].label(), Some("trusted contact"));
+        assert_eq!(shares.additional[1].label(), Some("hardware-backup"));
+        assert_eq!(shares.local.label(), None);
+        assert!(shares.additional.iter().all(|s| s.threshold() == 3 && s.count() == 5));
+
+        // the labels and the scheme survive the string format
+        let parsed = shares.additional[1]
+            .to_string()
+            .expose_secret()
+            .parse::<Share>()
+            .unwrap();
+        assert_eq!(parsed, shares.additional[1]);
+        let parsed_local = shares.local.to_string().expose_secret().parse::<Share>().unwrap();
+        assert_eq!(parsed_local.scheme(), scheme);
+
+        assert_eq!(
+            reconstruct_secret(&[&shares.local, &shares.additional[0], &shares.additional[1]], None)
+                .unwrap()
+                .1
+                .expose_secret(),
+            secret.expose_secret()
+        );
+        assert_eq!(
+            reconstruct_secret(&[&shares.recovery, &shares.backup, &parsed], Some(&password))
+                .unwrap()
+                .1
+                .expose_secret(),
+            secret.expose_secret()
+        );
+        assert!(matches!(
+            reconstruct_secret(&[&shares.local, &shares.recovery], None),
+            Err(ShareError::NotEnoughShares {
+                provided: 2,
+                required: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_reconstruct_rejects_mixed_schemes() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let scheme = ShareScheme {
+            threshold: 2,
+            additional_labels: vec![String::new()],
+        };
+        let default_shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
+        let shares = create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &password, &scheme).unwrap();
+
+        // Act
+        let result = reconstruct_secret(&[&default_shares.local, &shares.recovery], None);
+
+        // Assert
+        assert!(matches!(result, Err(ShareError::IncompatibleShares(_))));
+        assert_eq!(shares.additional[0].label(), None);
+    }
+
+    #[test]
+    fn test_default_scheme_keeps_share_format() {
+        // Arrange
+        let share = "ME-RS-N-ZGF0YQ==";
+
+        // Act
+        let parsed = share.parse::<Share>().unwrap();
+
+        // Assert
+        assert_eq!(parsed.threshold(), 2);
+        assert_eq!(parsed.count(), 3);
+        assert_eq!(parsed.label(), None);
+        assert_eq!(parsed.scheme(), ShareScheme::default());
+        assert_eq!(parsed.to_string().expose_secret(), share);
+    }
+
+    #[test]
+    fn test_check_share_health() {
+        // Arrange
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let wrong_password = SecretBox::new("wrong password".to_string().into_bytes().into());
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let other_secret = SecretBox::new("other secret".to_string().into_bytes().into());
+        let scheme = ShareScheme::default();
+        let shares = create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &password, &scheme).unwrap();
+        let other_shares =
+            create_shares_from_secret(PayloadType::MnemonicEntropy, &other_secret, &password, &scheme).unwrap();
+        let old_password_shares =
+            create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &wrong_password, &scheme).unwrap();
+        let legacy_share = Share {
+            fingerprint: None,
+            ..shares.recovery.clone()
+        };
+        let corrupt_share = Share {
+            data: ShareData("not a share".to_string().into_bytes().into()),
+            ..shares.recovery.clone()
+        };
+
+        // Act
+        let health = check_share_health(
+            &[
+                Some(&shares.local),
+                Some(&shares.backup),
+                Some(&other_shares.recovery),
+                None,
+                Some(&old_password_shares.backup),
+                Some(&legacy_share),
+                Some(&corrupt_share),
+            ],
+            Some(&password),
+        );
+
+        // Assert
+        assert_eq!(
+            health,
+            [
+                ShareHealth::Healthy,
+                ShareHealth::Healthy,
+                ShareHealth::Inconsistent,
+                ShareHealth::Missing,
+                ShareHealth::Stale,
+                ShareHealth::Unknown,
+                ShareHealth::Corrupt,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_share_health_detects_other_scheme() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
+        let scheme = ShareScheme {
+            threshold: 2,
+            additional_labels: vec![String::new()],
+        };
+        let other_shares =
+            create_shares_from_secret(PayloadType::MnemonicEntropy, &secret, &password, &scheme).unwrap();
+
+        // Act
+        let health = check_share_health(
+            &[Some(&shares.local), Some(&other_shares.recovery), Some(&shares.backup)],
+            Some(&password),
+        );
+
+        // Assert
+        assert_eq!(
+            health,
+            [ShareHealth::Healthy, ShareHealth::Inconsistent, ShareHealth::Healthy]
+        );
+    }
+
+    #[test]
+    fn test_encrypted_share_authenticates_fingerprint() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
+        let tampered = Share {
+            fingerprint: Some([0; FINGERPRINT_LEN]),
+            ..shares.backup.clone()
+        };
+        let stripped = Share {
+            fingerprint: None,
+            ..shares.backup.clone()
+        };
+
+        // Act & Assert
+        assert!(shares.backup.can_decrypt(Some(&password)));
+        assert!(!tampered.can_decrypt(Some(&password)));
+        assert!(!stripped.can_decrypt(Some(&password)));
+        assert_eq!(
+            check_share_health(&[Some(&tampered), Some(&stripped)], Some(&password)),
+            [ShareHealth::Stale, ShareHealth::Stale]
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_rejects_wrong_fingerprint() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
+        let tampered = Share {
+            fingerprint: Some([0; FINGERPRINT_LEN]),
+            ..shares.recovery.clone()
+        };
+
+        // Act
+        let result = reconstruct_secret(&[&shares.local, &tampered], None);
+
+        // Assert
+        assert!(matches!(result, Err(ShareError::IncompatibleShares(_))));
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_local_share() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
+        let pin = EncryptionPin::try_from_string("12345").unwrap();
+        let wrong_pin = EncryptionPin::try_from_string("54321").unwrap();
+        let salt = EncryptionSalt::generate();
+
+        // Act
+        let encrypted = encrypt_local_share(&shares.local, &pin, &salt).unwrap();
+        let stored = encrypted.to_string().expose_secret().parse::<Share>().unwrap();
+
+        // Assert
+        assert!(stored.is_encrypted());
+        assert_eq!(stored.encryption, Encryption::Pin);
+        assert!(encrypted.to_string().expose_secret().starts_with("ME-RS-Pin-"));
+        assert_ne!(stored.data, shares.local.data);
+        assert_eq!(decrypt_local_share(&stored, &pin, &salt).unwrap(), shares.local);
+        decrypt_local_share(&stored, &wrong_pin, &salt).unwrap_err();
+        decrypt_local_share(&stored, &pin, &EncryptionSalt::generate()).unwrap_err();
+        encrypt_local_share(&stored, &pin, &salt).unwrap_err();
+        decrypt_local_share(&shares.backup, &pin, &salt).unwrap_err();
+    }
+
+    #[test]
+    fn test_reencrypt_local_share() {
+        // Arrange
+        let share = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &SecretBox::new("secret".to_string().into_bytes().into()),
+            &SecretBox::new("password".to_string().into_bytes().into()),
+            &ShareScheme::default(),
+        )
+        .unwrap()
+        .local;
+        let old_pin = EncryptionPin::try_from_string("12345").unwrap();
+        let new_pin = EncryptionPin::try_from_string("54321").unwrap();
+        let old_salt = EncryptionSalt::generate();
+        let new_salt = EncryptionSalt::generate();
+        let plain = share.to_string().expose_secret().to_string();
+        let encrypted = encrypt_local_share(&share, &old_pin, &old_salt)
+            .unwrap()
+            .to_string()
+            .expose_secret()
+            .to_string();
+
+        for stored in [plain, encrypted] {
+            // Act
+            let reencrypted = reencrypt_local_share(&stored, (&old_pin, &old_salt), (&new_pin, &new_salt)).unwrap();
+
+            // Assert
+            let reencrypted = reencrypted.parse::<Share>().unwrap();
+            assert_eq!(decrypt_local_share(&reencrypted, &new_pin, &new_salt).unwrap(), share);
+            decrypt_local_share(&reencrypted, &old_pin, &old_salt).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_guardian_share_roundtrip() {
+        // Arrange
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &SecretBox::new("secret".to_string().into_bytes().into()),
+            &SecretBox::new("password".to_string().into_bytes().into()),
+            &ShareScheme {
+                threshold: 2,
+                additional_labels: vec!["guardian".to_string()],
+            },
+        )
+        .unwrap();
+        let guardian_key = generate_secret_key();
+        let other_key = generate_secret_key();
+
+        // Act
+        let encrypted = encrypt_for_public_key(&shares.additional[0], &public_key(&guardian_key).unwrap()).unwrap();
+        let stored = encrypted.to_string().expose_secret().parse::<Share>().unwrap();
+
+        // Assert
+        assert!(encrypted.to_string().expose_secret().starts_with("ME-RS-X25519-"));
+        assert_eq!(stored.label(), Some("guardian"));
+        assert_eq!(
+            decrypt_with_secret_key(&stored, &guardian_key).unwrap(),
+            shares.additional[0]
+        );
+        decrypt_with_secret_key(&stored, &other_key).unwrap_err();
+        reconstruct_secret(&[&shares.local, &stored], None).unwrap_err();
+
+        let decrypted = decrypt_with_secret_key(&stored, &guardian_key).unwrap();
+        let (_, secret) = reconstruct_secret(&[&shares.local, &decrypted], None).unwrap();
+        assert_eq!(secret.expose_secret(), b"secret");
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_secret_key() {
+        // Arrange
+        let secret_key = generate_secret_key();
+        let pin = EncryptionPin::try_from_string("12345").unwrap();
+        let new_pin = EncryptionPin::try_from_string("54321").unwrap();
+        let salt = EncryptionSalt::generate();
+        let new_salt = EncryptionSalt::generate();
+
+        // Act
+        let stored = encrypt_secret_key(&secret_key, &pin, &salt).unwrap();
+        let reencrypted = reencrypt_secret_key(&stored, (&pin, &salt), (&new_pin, &new_salt)).unwrap();
+
+        // Assert
+        assert_eq!(
+            decrypt_secret_key(&stored, &pin, &salt).unwrap().expose_secret(),
+            secret_key.expose_secret()
+        );
+        assert_eq!(
+            decrypt_secret_key(&reencrypted, &new_pin, &new_salt)
+                .unwrap()
+                .expose_secret(),
+            secret_key.expose_secret()
+        );
+        decrypt_secret_key(&stored, &new_pin, &salt).unwrap_err();
+        decrypt_secret_key(&reencrypted, &pin, &salt).unwrap_err();
+    }
+
+    #[test]
+    fn test_public_key_fingerprint() {
+        // Arrange
+        let key = public_key(&generate_secret_key()).unwrap();
+        let other_key = public_key(&generate_secret_key()).unwrap();
+
+        // Act
+        let fingerprint = public_key_fingerprint(&key).unwrap();
+
+        // Assert
+        assert_eq!(fingerprint.len(), 19);
+        assert_eq!(fingerprint.split('-').count(), 4);
+        assert_eq!(fingerprint, public_key_fingerprint(&key).unwrap());
+        assert_ne!(fingerprint, public_key_fingerprint(&other_key).unwrap());
+        public_key_fingerprint("not a key").unwrap_err();
+    }
+
+    #[test]
+    fn test_fingerprint_survives_string_format() {
+        // Arrange
+        let secret = SecretBox::new("secret".to_string().into_bytes().into());
+        let password = SecretBox::new("password".to_string().into_bytes().into());
+        let shares = create_shares_from_secret(
+            PayloadType::MnemonicEntropy,
+            &secret,
+            &password,
+            &ShareScheme::default(),
+        )
+        .unwrap();
+
+        // Act
+        let share_string = shares.local.to_string();
+        let parsed = share_string.expose_secret().parse::<Share>().unwrap();
+
+        // Assert
+        assert!(share_string.expose_secret().contains("-2of3--"));
+        assert_eq!(parsed, shares.local);
+        assert!(parsed.fingerprint.is_some());
+    }
+
+    #[rstest::rstest]
+    #[case::threshold_one(1, 0)]
+    #[case::threshold_too_large(4, 0)]
+    #[case::too_many_shares(2, 253)]
+    fn test_invalid_share_scheme(#[case] threshold: u8, #[case] additional: usize) {
+        // Arrange
+        let scheme = ShareScheme {
+            threshold,
+            additional_labels: vec![String::new(); additional],
+        };
+
+        // Act
+        let result = scheme.validate();
+
+        // Assert
+        assert!(matches!(result, Err(ShareError::InvalidScheme(_))));
+    }
+
     #[test]
     fn test_aes_gcm_encrypt_decrypt() {
         let key: SecretSlice<u8> = "key".to_string().into_bytes().into();
         let data = ShareData("my secret data".as_bytes().to_vec().into());
 
         assert_eq!(
-            decrypt_with_password(&encrypt_with_password(&data, &key).unwrap(), &key).unwrap(),
+            decrypt_with_password(&encrypt_with_password(&data, &key, &[]).unwrap(), &key, &[]).unwrap(),
             data,
         );
     }
@@ -617,7 +1896,7 @@ mod test {
 
         let data = ShareData("my secret data".as_bytes().to_vec().into());
 
-        assert!(decrypt_with_password(&encrypt_with_password(&data, &key).unwrap(), &wrong_key).is_err());
+        assert!(decrypt_with_password(&encrypt_with_password(&data, &key, &[]).unwrap(), &wrong_key, &[]).is_err());
     }
 
     #[test]
@@ -635,7 +1914,8 @@ mod test {
                     32, 112, 222, 26, 190, 160, 235, 203, 235, 74, 13, 213, 181, 30, 151, 28, 60, 146, 145, 37, 128,
                     57, 80, 202, 77, 21, 179, 21, 100, 60, 85, 127, 68, 223,
                 ])),
-                &key
+                &key,
+                &[]
             )
             .unwrap(),
             data
@@ -647,7 +1927,8 @@ mod test {
                     76, 61, 16, 170, 160, 112, 228, 107, 253, 241, 246, 102, 145, 90, 79, 73, 157, 173, 81, 106, 1,
                     200, 23, 180, 127, 225, 147, 226, 233, 110, 94, 50, 150, 110
                 ])),
-                &key
+                &key,
+                &[]
             )
             .unwrap(),
             data
@@ -659,7 +1940,8 @@ mod test {
                     138, 139, 139, 160, 101, 108, 251, 7, 211, 55, 8, 160, 244, 248, 42, 23, 172, 229, 68, 143, 129,
                     245, 6, 117, 192, 226, 109, 184, 0, 84, 68, 165, 143, 201
                 ])),
-                &key
+                &key,
+                &[]
             )
             .unwrap(),
             data
@@ -3,13 +3,22 @@
 //! The WalletImpl struct represents an instantiated wallet and holds the necessary state and configuration.
 //!
 
-use super::share::Share;
+use super::backup::{detect_format, load_backup, store_backup, BackupFormat, RestoredBackup, WalletMetadata};
+use super::mnemonic::{generate_mnemonic, validate_mnemonic, MnemonicOptions};
+use super::share::{
+    check_share_health, decrypt_local_share, decrypt_passphrase, decrypt_secret_key, decrypt_with_secret_key,
+    encrypt_for_public_key, encrypt_local_share, encrypt_passphrase, encrypt_secret_key, generate_secret_key,
+    public_key, public_key_fingerprint, reencrypt_local_share, reencrypt_passphrase, reencrypt_secret_key, Guardian,
+    Share, ShareError, ShareHealth, ShareHealthReport, ShareScheme,
+};
 use super::wallet_user::{WalletImplStardust, WalletUser};
-use super::wallet_user_eth::{WalletImplEth, WalletImplEthErc20};
+use super::wallet_user_eth::{TokenMetadataCache, WalletImplEth, WalletImplEthErc20};
+use crate::backend::error::ApiError;
 use crate::core::{Config, UserRepoT};
 use crate::types::currencies::Currency;
 use crate::types::networks::{Network, NetworkType};
-use crate::types::newtypes::{AccessToken, EncryptionPin, EncryptionSalt, PlainPassword};
+use crate::types::newtypes::{AccessToken, EncryptionPin, EncryptionSalt, MnemonicPassphrase, PlainPassword};
+use crate::types::users::UserEntity;
 use crate::wallet::error::{ErrorKind, Result, WalletError};
 use async_trait::async_trait;
 use iota_sdk::crypto::keys::bip39::Mnemonic;
@@ -61,16 +70,25 @@ pub trait WalletManager: std::fmt::Debug {
     /// Set the recovery share
     fn set_recovery_share(&mut self, share: Option<Share>);
 
-    /// Generate a new mnemonic and create shares. Returns the new mnemonic.
+    /// Get the additional shares of schemes with more than three shares
+    fn get_additional_shares(&self) -> Vec<Share>;
+
+    /// Set the additional shares of schemes with more than three shares
+    fn set_additional_shares(&mut self, shares: Vec<Share>);
+
+    /// Generate a new mnemonic with the given word count and language and create shares, the optional
+    /// BIP-39 passphrase is stored in the shares. Returns the new mnemonic.
     async fn create_wallet_from_new_mnemonic(
         &mut self,
         config: &Config,
         access_token: &Option<AccessToken>,
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
+        passphrase: &Option<MnemonicPassphrase>,
+        options: &MnemonicOptions,
     ) -> Result<String>;
 
-    /// Create shares from a mnemonic
+    /// Create shares from a mnemonic, the optional BIP-39 passphrase is stored in the shares
     async fn create_wallet_from_existing_mnemonic(
         &mut self,
         config: &Config,
@@ -78,9 +96,11 @@ pub trait WalletManager: std::fmt::Debug {
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
         mnemonic: &str,
+        passphrase: &Option<MnemonicPassphrase>,
     ) -> Result<()>;
 
-    /// Create shares from a kdbx backup byte stream
+    /// Create shares from a kdbx or JSON backup byte stream, the format is detected automatically.
+    /// Returns the metadata stored in a JSON backup.
     async fn create_wallet_from_backup(
         &mut self,
         config: &Config,
@@ -89,7 +109,7 @@ pub trait WalletManager: std::fmt::Debug {
         pin: &EncryptionPin,
         backup: &[u8],
         backup_password: &PlainPassword,
-    ) -> Result<()>;
+    ) -> Result<Option<WalletMetadata>>;
 
     /// Create kdbx backup bytes from shares
     async fn create_wallet_backup(
@@ -101,6 +121,17 @@ pub trait WalletManager: std::fmt::Debug {
         backup_password: &PlainPassword,
     ) -> Result<Vec<u8>>;
 
+    /// Create JSON backup bytes from shares, including the metadata of the wallet
+    async fn create_json_wallet_backup(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        backup_password: &PlainPassword,
+        metadata: WalletMetadata,
+    ) -> Result<Vec<u8>>;
+
     /// deletes the user's wallet
     async fn delete_wallet(
         &mut self,
@@ -109,7 +140,7 @@ pub trait WalletManager: std::fmt::Debug {
         repo: &mut UserRepoT,
     ) -> Result<()>;
 
-    /// Checks if the mnemonic resembled by the shares is the same as the provided mnemonic.
+    /// Checks if the mnemonic and BIP-39 passphrase resembled by the shares are the same as the provided ones.
     async fn check_mnemonic(
         &mut self,
         config: &Config,
@@ -117,6 +148,7 @@ pub trait WalletManager: std::fmt::Debug {
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
         mnemonic: &str,
+        passphrase: &Option<MnemonicPassphrase>,
     ) -> Result<bool>;
 
     /// Changes the password of the existing wallet by re-encrypting the backup share
@@ -129,6 +161,101 @@ pub trait WalletManager: std::fmt::Debug {
         new_password: &PlainPassword,
     ) -> Result<()>;
 
+    /// Checks the health of the local, backup, recovery and additional shares without reconstructing the
+    /// mnemonic
+    async fn check_shares(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<ShareHealthReport>;
+
+    /// Reconstructs the mnemonic from the usable shares, then creates, stores and uploads new shares
+    async fn repair_shares(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<()>;
+
+    /// Stores the recovery share encrypted with the pin on the device, so that the wallet can be unlocked with
+    /// the shares on the device only, e.g. on an offline device signing transactions. `false` removes it again.
+    async fn set_offline_unlock(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        enabled: bool,
+    ) -> Result<()>;
+
+    /// Returns the public key of the user as a guardian. Other users need it to nominate the user as their
+    /// guardian. A random key pair is created on first use, and its secret key is stored encrypted with the pin.
+    async fn get_guardian_public_key(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<String>;
+
+    /// Creates new shares with one additional share for each guardian, encrypted with the public key of
+    /// the guardian and uploaded to the backend. `threshold` shares are needed to reconstruct the mnemonic.
+    async fn set_guardians(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        guardians: Vec<Guardian>,
+        threshold: u8,
+    ) -> Result<()>;
+
+    /// Starts the recovery of the wallet with the help of the guardians by creating a new key pair and
+    /// asking the guardians to approve the recovery. The secret key is stored encrypted with the pin. Returns
+    /// the fingerprint of the public key, which the guardians confirm before approving.
+    async fn start_guardian_recovery(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<String>;
+
+    /// Returns the fingerprint of the public key of the recovery started by the user with the given username,
+    /// to compare it with the fingerprint shown to that user before approving.
+    async fn get_guardian_recovery_fingerprint(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        username: &str,
+    ) -> Result<String>;
+
+    /// Approves the recovery of the user with the given username, for whom this user is a guardian, by
+    /// encrypting the share of the guardian for the key of the recovery. The fingerprint of the key has to
+    /// match the confirmed `fingerprint`, so a key replaced on its way is never approved.
+    async fn approve_guardian_recovery(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        username: &str,
+        fingerprint: &str,
+    ) -> Result<()>;
+
+    /// Reconstructs the mnemonic from the shares approved by the guardians, and the backup share if
+    /// needed, then creates, stores and uploads new shares for the same guardians.
+    async fn complete_guardian_recovery(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<()>;
+
     /// Tries to instantiate a [`WalletUser`] object from shares and/or returns a mutable reference bound to
     /// the lifetime of this object. The same instance may be reused across several calls to
     /// `try_get`, hence the lifetime is bound to the lifetime of `self`.
@@ -140,6 +267,10 @@ pub trait WalletManager: std::fmt::Debug {
         network: Network,
         pin: &EncryptionPin,
     ) -> Result<WalletBorrow<'a>>;
+
+    /// Instantiates a watch-only [`WalletUser`] object from the [`WatchOnlySource`](super::watch_only::WatchOnlySource) stored for the user. It needs
+    /// neither the pin nor the shares, but cannot sign transactions. Only EVM and ERC-20 networks are supported.
+    fn try_get_watch_only<'a>(&'a mut self, repo: &mut UserRepoT, network: Network) -> Result<WalletBorrow<'a>>;
 }
 
 /// Implementation of [`WalletManager`] that uses the SSS schema to store and retrieve the mnemonic
@@ -151,6 +282,12 @@ pub struct WalletManagerImpl {
 
     /// The recovery share that the user should download
     pub recovery_share: Option<Share>,
+
+    /// The additional shares that the user should hand out, or that were provided to initialize the wallet
+    pub additional_shares: Vec<Share>,
+
+    /// The token metadata cache of the SDK instance, shared by the ERC-20 wallets
+    token_metadata_cache: TokenMetadataCache,
 }
 
 #[derive(Debug, PartialEq)]
@@ -172,23 +309,101 @@ enum RecoveryUsed {
     Remote,
 }
 
+/// Reads the local share of the user and decrypts it with the pin.
+///
+/// Local shares stored in plain text by older versions of the SDK are encrypted with the pin and
+/// stored again.
+fn read_local_share(repo: &mut UserRepoT, user: &UserEntity, pin: &EncryptionPin) -> Result<Option<Share>> {
+    let Some(share) = &user.local_share else {
+        return Ok(None);
+    };
+    let share = share.parse::<Share>()?;
+    if share.is_encrypted() {
+        return Ok(Some(decrypt_local_share(&share, pin, &user.salt)?));
+    }
+
+    log::info!("Local share is stored in plain text, encrypting it with the pin");
+    if let Err(e) = store_local_share(repo, user, pin, &share) {
+        log::warn!("Error encrypting local share: {e:#}");
+    }
+    Ok(Some(share))
+}
+
+/// Encrypts the local share with the pin and stores it in the user repository.
+fn store_local_share(repo: &mut UserRepoT, user: &UserEntity, pin: &EncryptionPin, share: &Share) -> Result<()> {
+    let share = encrypt_local_share(share, pin, &user.salt)?;
+    repo.set_local_share(&user.username, Some(&share))?;
+    Ok(())
+}
+
+/// Decrypts the BIP-39 passphrase stored on the device if the shares are marked to use one, since the
+/// passphrase itself is never part of the shares.
+fn read_passphrase(
+    user: &UserEntity,
+    pin: &EncryptionPin,
+    uses_passphrase: bool,
+) -> Result<Option<MnemonicPassphrase>> {
+    if !uses_passphrase {
+        return Ok(None);
+    }
+    let Some(stored) = &user.encrypted_passphrase else {
+        return Err(WalletError::WalletNotInitialized(ErrorKind::MissingPassphrase));
+    };
+    Ok(Some(decrypt_passphrase(stored, pin, &user.salt)?))
+}
+
+/// Reads the recovery share stored on the device for the offline unlock and decrypts it with the pin.
+fn read_offline_recovery_share(user: &UserEntity, pin: &EncryptionPin) -> Result<Option<Share>> {
+    user.offline_recovery_share
+        .as_deref()
+        .map(|share| Ok(decrypt_local_share(&share.parse()?, pin, &user.salt)?))
+        .transpose()
+}
+
+/// Encrypts the recovery share with the pin like the local share, to be stored for the offline unlock.
+fn encrypt_offline_recovery_share(user: &UserEntity, pin: &EncryptionPin, share: &Share) -> Result<String> {
+    let share = encrypt_local_share(share, pin, &user.salt)?;
+    Ok(share.to_string().expose_secret().to_string())
+}
+
+/// Adds the shares that are not in `shares` yet, so a share that is provided twice is not counted twice.
+fn extend_distinct(shares: &mut Vec<Share>, more: impl IntoIterator<Item = Share>) {
+    for share in more {
+        let value = share.to_string();
+        if !shares
+            .iter()
+            .any(|s| s.to_string().expose_secret() == value.expose_secret())
+        {
+            shares.push(share);
+        }
+    }
+}
+
 impl WalletManagerImpl {
     /// Create a new [`WalletManagerImpl`] from a username.
     pub fn new(username: impl Into<String>) -> Self {
         Self {
             username: username.into(),
             recovery_share: None,
+            additional_shares: Vec::new(),
+            token_metadata_cache: TokenMetadataCache::default(),
         }
     }
 
-    // fn for getting the mnemonic
+    /// Shares the given token metadata cache with the ERC-20 wallets created by this manager.
+    pub(crate) fn with_token_metadata_cache(mut self, cache: TokenMetadataCache) -> Self {
+        self.token_metadata_cache = cache;
+        self
+    }
+
+    // fn for getting the mnemonic and the BIP-39 passphrase stored in the shares
     async fn try_resemble_shares(
         &mut self,
         config: &Config,
         access_token: &Option<AccessToken>,
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
-    ) -> Result<(Mnemonic, Status)> {
+    ) -> Result<(Mnemonic, Option<MnemonicPassphrase>, Status)> {
         info!("Initializing wallet for user from shares");
 
         let username = &self.username;
@@ -196,15 +411,6 @@ impl WalletManagerImpl {
 
         let user = repo.get(username)?;
 
-        // make sure the provided pin is valid (even though we currenctly do not need it unless
-        // decrypting some of the shares, but in the future we might want to encrypt the local
-        // share too, for example)
-        // let _ = user
-        //     .encrypted_password
-        //     .as_ref()
-        //     .ok_or(crate::Error::WalletNotInitialized(crate::WalletNotInitializedKind::MissingPassword))?
-        //     .decrypt(pin, &user.salt)?;
-
         // check the availability of each share (in priority order of ease-of-use and availability)
 
         let mut available_shares: Vec<Share> = Vec::new();
@@ -216,8 +422,9 @@ impl WalletManagerImpl {
         let mut recovery_used = None;
         let mut backup_used = false;
 
-        if let Some(share) = user.local_share.map(|s| s.parse::<Share>()) {
-            available_shares.push(share?);
+        // the local share is encrypted with the pin, so this also makes sure the pin is valid
+        if let Some(share) = read_local_share(repo, &user, pin)? {
+            available_shares.push(share);
             log::info!("Local storage share available");
             local_used = true;
         }
@@ -226,6 +433,10 @@ impl WalletManagerImpl {
             available_shares.push(share);
             log::info!("Local recovery share available");
             recovery_used = Some(RecoveryUsed::Local);
+        } else if let Some(share) = read_offline_recovery_share(&user, pin)? {
+            available_shares.push(share);
+            log::info!("Recovery share stored on the device for the offline unlock available");
+            recovery_used = Some(RecoveryUsed::Local);
         } else {
             log::info!("Local recovery share not available, checking if it can be downloaded");
             recovery_share_available_with_user_action = true;
@@ -247,9 +458,14 @@ impl WalletManagerImpl {
             }
         }
 
-        // if we have less than two, we should try to get the backup share
+        if !self.additional_shares.is_empty() {
+            log::info!("{} additional shares available", self.additional_shares.len());
+            extend_distinct(&mut available_shares, self.additional_shares.iter().cloned());
+        }
+
+        // if we have less than the threshold, we should try to get the backup share
         // this is the last resort since it requires the password
-        if available_shares.len() < 2 {
+        if available_shares.len() < required_shares(&available_shares) {
             if let Some(access_token) = &access_token {
                 // try to get it from the backend
                 match crate::backend::shares::download_backup_share(config, access_token, username).await {
@@ -271,21 +487,24 @@ impl WalletManagerImpl {
         let available_shares = available_shares;
         let recovery_share_available_with_upload = recovery_share_available_with_user_action;
         let password_required = password_required;
+        let required = required_shares(&available_shares);
 
         log::info!(
-            "Done collecting shares. Got {} shares, recovery_share_available_with_user_action = {}, password_required = {}",
+            "Done collecting shares. Got {} of {} required shares, recovery_share_available_with_user_action = {}, password_required = {}",
             available_shares.len(),
+            required,
             recovery_share_available_with_upload,
             password_required
         );
 
-        if available_shares.len() >= 2 {
+        if available_shares.len() >= required {
             // enough shares are available!
 
             // if the password is required, we need to try to get it or return an error
             let password = if password_required {
                 let password = user
                     .encrypted_password
+                    .as_ref()
                     .ok_or(WalletError::WalletNotInitialized(ErrorKind::MissingPassword))?
                     .decrypt(pin, &user.salt)?;
 
@@ -297,23 +516,28 @@ impl WalletManagerImpl {
             let shares_ref = available_shares.iter().collect::<Vec<&Share>>();
 
             // now we can finally try to recreate the mnemonic from the shares
-            let mnemonic = crate::share::reconstruct_mnemonic(
+            let (mnemonic, uses_passphrase) = crate::share::reconstruct_mnemonic(
                 &shares_ref,
                 password.as_ref().map(PlainPassword::into_secret).as_ref(),
             )?;
+            let passphrase = read_passphrase(&user, pin, uses_passphrase)?;
 
             if !local_used {
                 log::info!("Local share not set, recreating shares and storing local share again");
 
                 // create the shares again, and just use a random password since we are not interested
-                // in the backup share anyways (which is the only reason this needs a password)
+                // in the backup share anyways (which is the only reason this needs a password). The
+                // same scheme is needed to get the same local share.
+                let scheme = available_shares.first().map(Share::scheme).unwrap_or_default();
                 let shares = crate::share::create_shares_from_mnemonic(
                     secrecy::ExposeSecret::expose_secret(&mnemonic).clone(),
+                    uses_passphrase,
                     &SecretBox::new(String::from("dummy password").as_bytes().into()),
+                    &scheme,
                 )?;
 
                 // ignore the error since we were still able to create a valid wallet
-                if let Err(e) = repo.set_local_share(username, Some(&shares.local)) {
+                if let Err(e) = store_local_share(repo, &user, pin, &shares.local) {
                     log::warn!("Error storing local share again: {e:#}");
                 } else {
                     log::info!("Done storing local share again");
@@ -322,22 +546,119 @@ impl WalletManagerImpl {
 
             Ok((
                 mnemonic.expose_secret().clone(),
+                passphrase,
                 Status {
                     local: local_used,
                     recovery: recovery_used,
                     backup: backup_used,
                 },
             ))
-        } else if available_shares.len() == 1 && recovery_share_available_with_upload {
+        } else if !available_shares.is_empty()
+            && available_shares.len() + 1 >= required
+            && recovery_share_available_with_upload
+        {
             Err(WalletError::WalletNotInitialized(ErrorKind::SetRecoveryShare))
+        } else if available_shares
+            .first()
+            .is_some_and(|share| !share.scheme().additional_labels.is_empty())
+        {
+            // the additional shares are only kept by the user, so the available shares need them to reach the
+            // threshold of this scheme
+            Err(WalletError::WalletNotInitialized(ErrorKind::SetAdditionalShares))
         } else {
             // there is no way to recover the shares
             Err(WalletError::WalletNotInitialized(ErrorKind::UseMnemonic))
         }
     }
 
+    /// Collects the local, backup, recovery and additional shares and checks their health. The shares are
+    /// returned in the order of the fields of [`ShareHealthReport`] together with the decrypted password.
+    async fn collect_share_health(
+        &self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<(Vec<Option<Share>>, Vec<ShareHealth>, Option<PlainPassword>)> {
+        info!("Checking the health of the shares");
+
+        // the shares stored in the backend are needed to know if they are missing
+        let Some(access_token) = access_token else {
+            return Err(WalletError::MissingAccessToken);
+        };
+
+        let user = repo.get(&self.username)?;
+        let password = user
+            .encrypted_password
+            .map(|password| password.decrypt(pin, &user.salt))
+            .transpose()?;
+
+        // shares that are available but cannot be parsed
+        let mut corrupt = Vec::new();
+
+        // local shares stored in plain text by older versions still need to be encrypted with the pin
+        let mut plain_text_local = false;
+        let local = match user.local_share.as_deref().map(|s| {
+            s.parse::<Share>().and_then(|share| {
+                if share.is_encrypted() {
+                    decrypt_local_share(&share, pin, &user.salt)
+                } else {
+                    plain_text_local = true;
+                    Ok(share)
+                }
+            })
+        }) {
+            Some(Ok(share)) => Some(share),
+            Some(Err(e)) => {
+                warn!("Local share is corrupt: {e}");
+                corrupt.push(0);
+                None
+            }
+            None => None,
+        };
+        let backup = match crate::backend::shares::download_backup_share(config, access_token, &self.username).await {
+            Ok(share) => share,
+            Err(ApiError::Parse(e)) => {
+                warn!("Backup share is corrupt: {e}");
+                corrupt.push(1);
+                None
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let recovery = match crate::backend::shares::download_recovery_share(config, access_token, &self.username).await
+        {
+            Ok(share) => share,
+            Err(ApiError::Parse(e)) => {
+                warn!("Recovery share is corrupt: {e}");
+                corrupt.push(2);
+                None
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut shares = vec![local, backup, recovery, self.recovery_share.clone()];
+
+        // the additional shares provided by the user
+        shares.extend(self.additional_shares.iter().cloned().map(Some));
+
+        let share_refs = shares.iter().map(Option::as_ref).collect::<Vec<_>>();
+        let mut health = check_share_health(&share_refs, password.as_ref().map(PlainPassword::into_secret).as_ref());
+        for index in corrupt {
+            if let Some(health) = health.get_mut(index) {
+                *health = ShareHealth::Corrupt;
+            }
+        }
+        if plain_text_local && matches!(health.first(), Some(ShareHealth::Healthy | ShareHealth::Unknown)) {
+            health[0] = ShareHealth::Stale;
+        }
+
+        Ok((shares, health, password))
+    }
+
     /// Creates shares from the provided mnemonic and stores the local share locally, uploads the other
     /// shares to the backend and returns the recovery share for the user to download and save.
+    ///
+    /// If the user has guardians, they get a share of the new mnemonic as well, so the guardians stay valid.
     async fn create_and_upload_shares(
         &mut self,
         config: &Config,
@@ -345,20 +666,130 @@ impl WalletManagerImpl {
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
         mnemonic: impl Into<Mnemonic>,
+        passphrase: Option<&MnemonicPassphrase>,
+    ) -> Result<()> {
+        let guardians = match access_token {
+            Some(access_token) => {
+                crate::backend::shares::download_guardian_shares(config, access_token, &self.username).await?
+            }
+            None => Vec::new(),
+        };
+
+        match guardians.first() {
+            Some((_, share)) => {
+                let threshold = share.threshold();
+                let guardians = guardians.into_iter().map(|(guardian, _)| guardian).collect();
+                self.create_and_upload_guardian_shares(
+                    config,
+                    access_token,
+                    repo,
+                    pin,
+                    mnemonic,
+                    passphrase,
+                    guardians,
+                    threshold,
+                )
+                .await
+            }
+            None => {
+                let additional_shares = self
+                    .split_and_upload_shares(config, access_token, repo, pin, mnemonic, passphrase, &config.shares)
+                    .await?;
+                self.additional_shares = additional_shares;
+                Ok(())
+            }
+        }
+    }
+
+    /// Creates shares with one additional share for each guardian and uploads the additional shares
+    /// encrypted with the public key of their guardian.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_and_upload_guardian_shares(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        mnemonic: impl Into<Mnemonic>,
+        passphrase: Option<&MnemonicPassphrase>,
+        guardians: Vec<Guardian>,
+        threshold: u8,
     ) -> Result<()> {
+        let Some(token) = access_token else {
+            return Err(WalletError::MissingAccessToken);
+        };
+        if guardians.is_empty() {
+            return Err(ShareError::InvalidScheme("at least one guardian is needed".to_string()).into());
+        }
+        if threshold > 3 {
+            // otherwise the guardians would be needed every time the wallet is used
+            return Err(ShareError::InvalidScheme(format!(
+                "threshold {threshold} is larger than the 3 shares available without guardians"
+            ))
+            .into());
+        }
+
+        let scheme = ShareScheme {
+            threshold,
+            additional_labels: guardians.iter().map(|g| g.username.clone()).collect(),
+        };
+        let additional_shares = self
+            .split_and_upload_shares(config, access_token, repo, pin, mnemonic, passphrase, &scheme)
+            .await?;
+
+        log::info!("Uploading shares for {} guardians", guardians.len());
+        let guardian_shares = guardians
+            .into_iter()
+            .zip(additional_shares)
+            .map(|(guardian, share)| {
+                let share = encrypt_for_public_key(&share, &guardian.public_key)?;
+                Ok((guardian, share))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        crate::backend::shares::upload_guardian_shares(config, token, &guardian_shares, &self.username).await?;
+
+        // the guardians hold the additional shares, so there is nothing to hand out
+        self.additional_shares.clear();
+        Ok(())
+    }
+
+    /// Creates shares with the given scheme, stores the local share, uploads the backup and recovery share
+    /// and returns the additional shares.
+    #[allow(clippy::too_many_arguments)]
+    async fn split_and_upload_shares(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        mnemonic: impl Into<Mnemonic>,
+        passphrase: Option<&MnemonicPassphrase>,
+        scheme: &ShareScheme,
+    ) -> Result<Vec<Share>> {
         log::info!("Creating and uploading shares");
 
         // get the password from the repo
-        let user = repo.get(&self.username)?;
-        let Some(encrypted_password) = user.encrypted_password else {
+        let mut user = repo.get(&self.username)?;
+        let Some(encrypted_password) = &user.encrypted_password else {
             return Err(WalletError::WalletNotInitialized(ErrorKind::MissingPassword));
         };
 
         let password = encrypted_password.decrypt(pin, &user.salt)?;
-        let shares = crate::share::create_shares_from_mnemonic(mnemonic, &password.into_secret())?;
+        let shares =
+            crate::share::create_shares_from_mnemonic(mnemonic, passphrase.is_some(), &password.into_secret(), scheme)?;
+
+        // the passphrase is not part of the shares, so it is only kept on the device
+        user.encrypted_passphrase = passphrase
+            .map(|passphrase| encrypt_passphrase(passphrase, pin, &user.salt))
+            .transpose()?;
+        // keep the offline unlock working with the new recovery share
+        if user.offline_recovery_share.is_some() {
+            user.offline_recovery_share = Some(encrypt_offline_recovery_share(&user, pin, &shares.recovery)?);
+        }
+        repo.update(&user)?;
 
         log::info!("Shares created, storing local share");
-        repo.set_local_share(&user.username, Some(&shares.local))?;
+        store_local_share(repo, &user, pin, &shares.local)?;
         self.recovery_share = Some(shares.recovery.clone());
 
         if let Some(access_token) = access_token {
@@ -370,10 +801,15 @@ impl WalletManagerImpl {
         } else {
             log::info!("No access token, skipping uploading backup and recovery shares");
         }
-        Ok(())
+        Ok(shares.additional)
     }
 }
 
+/// The number of shares needed to reconstruct the mnemonic from the shares, as stored in the shares.
+fn required_shares(shares: &[Share]) -> usize {
+    shares.iter().map(|s| usize::from(s.threshold())).max().unwrap_or(2)
+}
+
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl WalletManager for WalletManagerImpl {
@@ -383,6 +819,12 @@ impl WalletManager for WalletManagerImpl {
     fn set_recovery_share(&mut self, share: Option<Share>) {
         self.recovery_share = share;
     }
+    fn get_additional_shares(&self) -> Vec<Share> {
+        self.additional_shares.clone()
+    }
+    fn set_additional_shares(&mut self, shares: Vec<Share>) {
+        self.additional_shares = shares;
+    }
     /// Generate a new mnemonic and create shares. Returns the new mnemonic.
     async fn create_wallet_from_new_mnemonic(
         &mut self,
@@ -390,9 +832,11 @@ impl WalletManager for WalletManagerImpl {
         access_token: &Option<AccessToken>,
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
+        passphrase: &Option<MnemonicPassphrase>,
+        options: &MnemonicOptions,
     ) -> Result<String> {
-        let mnemonic = iota_sdk::client::Client::generate_mnemonic()?;
-        self.create_and_upload_shares(config, access_token, repo, pin, mnemonic.as_ref())
+        let mnemonic = generate_mnemonic(options);
+        self.create_and_upload_shares(config, access_token, repo, pin, mnemonic.as_ref(), passphrase.as_ref())
             .await?;
 
         Ok(mnemonic.to_string())
@@ -406,12 +850,15 @@ impl WalletManager for WalletManagerImpl {
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
         mnemonic: &str,
+        passphrase: &Option<MnemonicPassphrase>,
     ) -> Result<()> {
-        self.create_and_upload_shares(config, access_token, repo, pin, mnemonic)
+        // report an invalid mnemonic before contacting the backend
+        validate_mnemonic(mnemonic)?;
+        self.create_and_upload_shares(config, access_token, repo, pin, mnemonic, passphrase.as_ref())
             .await
     }
 
-    /// Create shares from a kdbx backup byte stream
+    /// Create shares from a kdbx or JSON backup byte stream
     async fn create_wallet_from_backup(
         &mut self,
         config: &Config,
@@ -420,11 +867,30 @@ impl WalletManager for WalletManagerImpl {
         pin: &EncryptionPin,
         backup: &[u8],
         backup_password: &PlainPassword,
-    ) -> Result<()> {
+    ) -> Result<Option<WalletMetadata>> {
         use secrecy::ExposeSecret;
-        let mnemonic = crate::kdbx::load_mnemonic(backup, &backup_password.into_secret_string())?;
-        self.create_and_upload_shares(config, access_token, repo, pin, mnemonic.expose_secret().clone())
-            .await
+        let password = backup_password.into_secret_string();
+        let restored = match detect_format(backup)? {
+            BackupFormat::Kdbx => {
+                let (mnemonic, passphrase) = crate::kdbx::load_mnemonic(backup, &password)?;
+                RestoredBackup {
+                    mnemonic,
+                    passphrase,
+                    metadata: None,
+                }
+            }
+            BackupFormat::Json => load_backup(backup, &password)?,
+        };
+        self.create_and_upload_shares(
+            config,
+            access_token,
+            repo,
+            pin,
+            restored.mnemonic.expose_secret().clone(),
+            restored.passphrase.as_ref(),
+        )
+        .await?;
+        Ok(restored.metadata)
     }
 
     /// Create kdbx backup bytes from shares
@@ -436,10 +902,37 @@ impl WalletManager for WalletManagerImpl {
         pin: &EncryptionPin,
         backup_password: &PlainPassword,
     ) -> Result<Vec<u8>> {
-        let (mnemonic, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
+        let (mnemonic, passphrase, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
 
         Ok(crate::kdbx::store_mnemonic(
             &SecretBox::new(Box::new(mnemonic)),
+            passphrase.as_ref(),
+            &backup_password.into_secret_string(),
+        )?)
+    }
+
+    /// Create JSON backup bytes from shares
+    async fn create_json_wallet_backup(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        backup_password: &PlainPassword,
+        mut metadata: WalletMetadata,
+    ) -> Result<Vec<u8>> {
+        let (mnemonic, passphrase, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
+
+        // the labels are part of every share, also of the encrypted local share
+        metadata.share_labels = match &repo.get(&self.username)?.local_share {
+            Some(share) => share.parse::<Share>()?.scheme().additional_labels,
+            None => Vec::new(),
+        };
+
+        Ok(store_backup(
+            &SecretBox::new(Box::new(mnemonic)),
+            passphrase.as_ref(),
+            &metadata,
             &backup_password.into_secret_string(),
         )?)
     }
@@ -459,9 +952,13 @@ impl WalletManager for WalletManagerImpl {
             }
         }
 
-        // clear the local and recovery share
+        // clear the local and recovery shares
         repo.set_local_share(&self.username, None)?;
+        let mut user = repo.get(&self.username)?;
+        user.offline_recovery_share = None;
+        repo.update(&user)?;
         self.recovery_share = None;
+        self.additional_shares.clear();
 
         // call backend if access_token exists
         if let Some(access_token) = access_token {
@@ -478,13 +975,15 @@ impl WalletManager for WalletManagerImpl {
         repo: &mut UserRepoT,
         pin: &EncryptionPin,
         mnemonic: &str,
+        passphrase: &Option<MnemonicPassphrase>,
     ) -> Result<bool> {
         // first use the existing pin and stored (encrypted) password to resemble the shares into
         // the mnemonic
-        let (existing_mnemonic, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
+        let (existing_mnemonic, existing_passphrase, _status) =
+            self.try_resemble_shares(config, access_token, repo, pin).await?;
 
-        // perform a str-str comparison
-        Ok(*mnemonic == **existing_mnemonic)
+        // perform a str-str comparison, the passphrase has to match as well
+        Ok(*mnemonic == **existing_mnemonic && *passphrase == existing_passphrase)
     }
 
     async fn change_wallet_password(
@@ -508,67 +1007,435 @@ impl WalletManager for WalletManagerImpl {
             Err(e) => return Err(e),
         }
 
-        // now update the password in the repo (perhaps a bit hacky... xD)
-        let mut user = repo.get(&self.username)?;
+        // now update the password in the repo, the local shares and secret keys are encrypted with a key
+        // derived from the salt and need to be re-encrypted in the same step
+        let old_user = repo.get(&self.username)?;
         let salt = EncryptionSalt::generate();
-        let encrypted_password = new_password.encrypt(pin, &salt)?;
+        let mut user = old_user.clone();
+        user.encrypted_password = Some(new_password.encrypt(pin, &salt)?);
+        user.local_share = old_user
+            .local_share
+            .as_deref()
+            .map(|share| reencrypt_local_share(share, (pin, &old_user.salt), (pin, &salt)))
+            .transpose()?;
+        user.guardian_secret_key = old_user
+            .guardian_secret_key
+            .as_deref()
+            .map(|key| reencrypt_secret_key(key, (pin, &old_user.salt), (pin, &salt)))
+            .transpose()?;
+        user.guardian_recovery_key = old_user
+            .guardian_recovery_key
+            .as_deref()
+            .map(|key| reencrypt_secret_key(key, (pin, &old_user.salt), (pin, &salt)))
+            .transpose()?;
+        user.encrypted_passphrase = old_user
+            .encrypted_passphrase
+            .as_deref()
+            .map(|passphrase| reencrypt_passphrase(passphrase, (pin, &old_user.salt), (pin, &salt)))
+            .transpose()?;
+        user.offline_recovery_share = old_user
+            .offline_recovery_share
+            .as_deref()
+            .map(|share| reencrypt_local_share(share, (pin, &old_user.salt), (pin, &salt)))
+            .transpose()?;
         user.salt = salt;
-        user.encrypted_password = Some(encrypted_password);
         repo.update(&user)?;
 
         // and if we need to reconstruct the shares, do it!
-        if let Ok((mnemonic, _status)) = result {
-            self.create_and_upload_shares(config, access_token, repo, pin, mnemonic.clone())
-                .await?;
+        if let Ok((mnemonic, passphrase, _status)) = result {
+            if let Err(e) = self
+                .create_and_upload_shares(config, access_token, repo, pin, mnemonic.clone(), passphrase.as_ref())
+                .await
+            {
+                // restore the old password and local shares, so the wallet can still be unlocked with the
+                // shares stored in the backend
+                warn!("Error creating the shares for the new password, restoring the old password: {e:#}");
+                repo.update(&old_user)?;
+                return Err(e);
+            }
         }
 
         Ok(())
     }
 
-    async fn try_get<'a>(
-        &'a mut self,
-        config: &mut Config,
+    async fn check_shares(
+        &mut self,
+        config: &Config,
         access_token: &Option<AccessToken>,
         repo: &mut UserRepoT,
-        network: Network,
         pin: &EncryptionPin,
-    ) -> Result<WalletBorrow<'a>> {
-        let (mnemonic, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
+    ) -> Result<ShareHealthReport> {
+        let (_shares, health, _password) = self.collect_share_health(config, access_token, repo, pin).await?;
+
+        let mut health = health.into_iter();
+        let mut next = || health.next().unwrap_or(ShareHealth::Missing);
+        Ok(ShareHealthReport {
+            local: next(),
+            backup: next(),
+            recovery: next(),
+            provided_recovery: next(),
+            additional: health.collect(),
+        })
+    }
 
-        // we have the mnemonic and can now instantiate the WalletImpl
+    async fn repair_shares(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<()> {
+        let (shares, health, password) = self.collect_share_health(config, access_token, repo, pin).await?;
+
+        // only use the shares that still belong to the secret and can be decrypted
+        let password = password.as_ref().map(PlainPassword::into_secret);
+        let usable = shares
+            .iter()
+            .zip(&health)
+            .filter_map(|(share, health)| match (share, health) {
+                (Some(share), ShareHealth::Healthy | ShareHealth::Unknown) => Some(share),
+                (Some(share), ShareHealth::Stale) if share.can_decrypt(password.as_ref()) => Some(share),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        info!("Repairing shares from {} usable shares", usable.len());
+
+        let (mnemonic, uses_passphrase) =
+            crate::share::reconstruct_mnemonic(&usable, password.as_ref()).map_err(|e| {
+                warn!("Could not reconstruct the mnemonic from the usable shares: {e}");
+                WalletError::WalletNotInitialized(ErrorKind::UseMnemonic)
+            })?;
+        let passphrase = read_passphrase(&repo.get(&self.username)?, pin, uses_passphrase)?;
+
+        self.create_and_upload_shares(
+            config,
+            access_token,
+            repo,
+            pin,
+            mnemonic.expose_secret().clone(),
+            passphrase.as_ref(),
+        )
+        .await
+    }
 
-        let path = config
-            .path_prefix
-            .join("wallets")
-            .join(&self.username)
-            .join(network.clone().id);
+    async fn set_offline_unlock(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        enabled: bool,
+    ) -> Result<()> {
+        if !enabled {
+            info!("Removing the recovery share stored for the offline unlock");
+            let mut user = repo.get(&self.username)?;
+            user.offline_recovery_share = None;
+            repo.update(&user)?;
+            return Ok(());
+        }
 
-        let bo = match network.network_type {
-            NetworkType::Evm { node_urls, chain_id } => {
-                let wallet = WalletImplEth::new(mnemonic, node_urls, chain_id)?;
-                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
-            }
-            NetworkType::EvmErc20 {
-                node_urls,
-                chain_id,
-                contract_address,
-            } => {
-                let wallet = WalletImplEthErc20::new(mnemonic, node_urls, chain_id, contract_address)?;
-                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
-            }
-            NetworkType::Stardust { node_urls } => {
-                let currency = Currency::try_from(network.currency)?;
-                let wallet = WalletImplStardust::new(mnemonic, &path, currency, node_urls).await?;
-                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
-            }
-        };
+        info!("Storing the recovery share for the offline unlock");
+        let (mnemonic, passphrase, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
 
-        Ok(WalletBorrow {
-            inner: bo,
-            _lifetime: PhantomData,
-        })
-    }
-}
+        // the shares are reproducible, so creating them again with the scheme of the local share results in
+        // the recovery share of the wallet. The password is only needed for the backup share, which is not used.
+        let user = repo.get(&self.username)?;
+        let scheme = read_local_share(repo, &user, pin)?
+            .map(|share| share.scheme())
+            .unwrap_or_default();
+        let shares = crate::share::create_shares_from_mnemonic(
+            mnemonic,
+            passphrase.is_some(),
+            &SecretBox::new(String::from("dummy password").as_bytes().into()),
+            &scheme,
+        )?;
+
+        let mut user = repo.get(&self.username)?;
+        user.offline_recovery_share = Some(encrypt_offline_recovery_share(&user, pin, &shares.recovery)?);
+        repo.update(&user)?;
+        Ok(())
+    }
+
+    async fn get_guardian_public_key(
+        &mut self,
+        _config: &Config,
+        _access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<String> {
+        let mut user = repo.get(&self.username)?;
+        if let Some(stored) = &user.guardian_secret_key {
+            return Ok(public_key(&decrypt_secret_key(stored, pin, &user.salt)?)?);
+        }
+
+        info!("Creating the guardian key pair");
+        let secret_key = generate_secret_key();
+        user.guardian_secret_key = Some(encrypt_secret_key(&secret_key, pin, &user.salt)?);
+        repo.update(&user)?;
+        Ok(public_key(&secret_key)?)
+    }
+
+    async fn set_guardians(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        guardians: Vec<Guardian>,
+        threshold: u8,
+    ) -> Result<()> {
+        if access_token.is_none() {
+            return Err(WalletError::MissingAccessToken);
+        }
+        let (mnemonic, passphrase, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
+        self.create_and_upload_guardian_shares(
+            config,
+            access_token,
+            repo,
+            pin,
+            mnemonic,
+            passphrase.as_ref(),
+            guardians,
+            threshold,
+        )
+        .await
+    }
+
+    async fn start_guardian_recovery(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<String> {
+        let Some(access_token) = access_token else {
+            return Err(WalletError::MissingAccessToken);
+        };
+        let secret_key = generate_secret_key();
+        let recovery_public_key = public_key(&secret_key)?;
+
+        // store the key before the guardians can encrypt their shares for it
+        let mut user = repo.get(&self.username)?;
+        user.guardian_recovery_key = Some(encrypt_secret_key(&secret_key, pin, &user.salt)?);
+        repo.update(&user)?;
+
+        crate::backend::shares::start_guardian_recovery(config, access_token, &recovery_public_key, &self.username)
+            .await?;
+        Ok(public_key_fingerprint(&recovery_public_key)?)
+    }
+
+    async fn get_guardian_recovery_fingerprint(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        username: &str,
+    ) -> Result<String> {
+        let Some(token) = access_token else {
+            return Err(WalletError::MissingAccessToken);
+        };
+        let Some((_share, recovery_public_key)) =
+            crate::backend::shares::download_guardian_recovery(config, token, username).await?
+        else {
+            return Err(WalletError::GuardianRecoveryNotStarted);
+        };
+        Ok(public_key_fingerprint(&recovery_public_key)?)
+    }
+
+    async fn approve_guardian_recovery(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+        username: &str,
+        fingerprint: &str,
+    ) -> Result<()> {
+        let Some(token) = access_token else {
+            return Err(WalletError::MissingAccessToken);
+        };
+        let Some((share, recovery_public_key)) =
+            crate::backend::shares::download_guardian_recovery(config, token, username).await?
+        else {
+            return Err(WalletError::GuardianRecoveryNotStarted);
+        };
+
+        // only the user who started the recovery knows the fingerprint of its key
+        if public_key_fingerprint(&recovery_public_key)? != fingerprint.trim().to_lowercase() {
+            warn!("The fingerprint of the recovery key of {username} does not match the confirmed fingerprint");
+            return Err(WalletError::GuardianRecoveryFingerprintMismatch);
+        }
+
+        // the share was encrypted for the guardian key of this user
+        let user = repo.get(&self.username)?;
+        let Some(stored) = &user.guardian_secret_key else {
+            return Err(WalletError::GuardianKeyNotSet);
+        };
+        let share = decrypt_with_secret_key(&share, &decrypt_secret_key(stored, pin, &user.salt)?)?;
+        let share = encrypt_for_public_key(&share, &recovery_public_key)?;
+
+        crate::backend::shares::upload_guardian_approval(config, token, &share, username).await?;
+        info!("Approved the guardian recovery of {username}");
+        Ok(())
+    }
+
+    async fn complete_guardian_recovery(
+        &mut self,
+        config: &Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        pin: &EncryptionPin,
+    ) -> Result<()> {
+        let Some(token) = access_token else {
+            return Err(WalletError::MissingAccessToken);
+        };
+        let user = repo.get(&self.username)?;
+        let Some(stored) = &user.guardian_recovery_key else {
+            return Err(WalletError::GuardianRecoveryNotStarted);
+        };
+        let recovery_key = decrypt_secret_key(stored, pin, &user.salt)?;
+
+        let mut shares = crate::backend::shares::download_guardian_approvals(config, token, &self.username)
+            .await?
+            .iter()
+            .map(|share| decrypt_with_secret_key(share, &recovery_key))
+            .collect::<core::result::Result<Vec<_>, _>>()?;
+        info!("{} guardians approved the recovery", shares.len());
+
+        // if not enough guardians approved, the backup share can be used with the password
+        let mut password = None;
+        if shares.len() < required_shares(&shares) {
+            if let Some(share) = crate::backend::shares::download_backup_share(config, token, &self.username).await? {
+                let encrypted_password = user
+                    .encrypted_password
+                    .as_ref()
+                    .ok_or(WalletError::WalletNotInitialized(ErrorKind::MissingPassword))?;
+                password = Some(encrypted_password.decrypt(pin, &user.salt)?.into_secret());
+                shares.push(share);
+            }
+        }
+
+        let shares_ref = shares.iter().collect::<Vec<_>>();
+        let (mnemonic, uses_passphrase) = crate::share::reconstruct_mnemonic(&shares_ref, password.as_ref())?;
+        let passphrase = read_passphrase(&user, pin, uses_passphrase)?;
+        info!("Wallet recovered with the help of the guardians");
+
+        // the old local and recovery shares are lost, so replace all shares
+        self.create_and_upload_shares(
+            config,
+            access_token,
+            repo,
+            pin,
+            mnemonic.expose_secret().clone(),
+            passphrase.as_ref(),
+        )
+        .await?;
+
+        let mut user = repo.get(&self.username)?;
+        user.guardian_recovery_key = None;
+        repo.update(&user)?;
+        Ok(())
+    }
+
+    async fn try_get<'a>(
+        &'a mut self,
+        config: &mut Config,
+        access_token: &Option<AccessToken>,
+        repo: &mut UserRepoT,
+        network: Network,
+        pin: &EncryptionPin,
+    ) -> Result<WalletBorrow<'a>> {
+        let (mnemonic, passphrase, _status) = self.try_resemble_shares(config, access_token, repo, pin).await?;
+
+        // we have the mnemonic and can now instantiate the WalletImpl
+
+        let path = config
+            .path_prefix
+            .join("wallets")
+            .join(&self.username)
+            .join(network.clone().id);
+
+        // all EVM networks share the derivation path, so the same address indices are used for each of them
+        let address_indices = repo.get(&self.username)?.evm_address_indices;
+
+        let bo = match network.network_type {
+            NetworkType::Evm { node_urls, chain_id } => {
+                let wallet = WalletImplEth::new(mnemonic, passphrase.as_ref(), node_urls, chain_id, &address_indices)?;
+                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
+            }
+            NetworkType::EvmErc20 {
+                node_urls,
+                chain_id,
+                contract_address,
+                token_symbol,
+                token_decimals,
+            } => {
+                let wallet = WalletImplEthErc20::new(
+                    mnemonic,
+                    passphrase.as_ref(),
+                    node_urls,
+                    chain_id,
+                    contract_address,
+                    token_symbol,
+                    token_decimals,
+                    &address_indices,
+                )?
+                .with_token_metadata_cache(self.token_metadata_cache.clone());
+                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
+            }
+            NetworkType::Stardust { node_urls } => {
+                let currency = Currency::try_from(network.currency)?;
+                let wallet = WalletImplStardust::new(mnemonic, passphrase.as_ref(), &path, currency, node_urls).await?;
+                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
+            }
+        };
+
+        Ok(WalletBorrow {
+            inner: bo,
+            _lifetime: PhantomData,
+        })
+    }
+
+    fn try_get_watch_only<'a>(&'a mut self, repo: &mut UserRepoT, network: Network) -> Result<WalletBorrow<'a>> {
+        let user = repo.get(&self.username)?;
+        let Some(source) = user.watch_only else {
+            return Err(WalletError::MissingWatchOnlySource);
+        };
+
+        let bo = match network.network_type {
+            NetworkType::Evm { node_urls, chain_id } => {
+                let addresses = source.evm_addresses(&user.evm_address_indices)?;
+                let wallet = WalletImplEth::new_watch_only(addresses, node_urls, chain_id)?;
+                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
+            }
+            NetworkType::EvmErc20 {
+                node_urls,
+                chain_id,
+                contract_address,
+                token_symbol,
+                token_decimals,
+            } => {
+                let addresses = source.evm_addresses(&user.evm_address_indices)?;
+                let wallet = WalletImplEthErc20::new_watch_only(
+                    addresses,
+                    node_urls,
+                    chain_id,
+                    contract_address,
+                    token_symbol,
+                    token_decimals,
+                )?
+                .with_token_metadata_cache(self.token_metadata_cache.clone());
+                Box::new(wallet) as Box<dyn WalletUser + Sync + Send>
+            }
+            // the stardust wallet needs a secret manager to sync its accounts, so it cannot be watch-only
+            NetworkType::Stardust { .. } => return Err(WalletError::WalletFeatureNotImplemented),
+        };
+
+        Ok(WalletBorrow {
+            inner: bo,
+            _lifetime: PhantomData,
+        })
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -576,15 +1443,17 @@ mod tests {
     use crate::{
         core::{Config, UserRepoT},
         kdbx::KdbxStorageError,
-        testing_utils::{example_network, BACKUP_PASSWORD},
+        testing_utils::{example_network, example_network_id, BACKUP_PASSWORD},
         types::{
             newtypes::{AccessToken, EncryptionPin, EncryptionSalt, PlainPassword},
             users::KycType,
         },
         user::{memory_storage::MemoryUserStorage, repository::UserRepoImpl, MockUserRepo},
+        wallet::watch_only::WatchOnlySource,
     };
     use kdbx_rs::errors::UnlockError;
     use rstest::rstest;
+    use secrecy::SecretSlice;
     use std::sync::LazyLock;
 
     const MNEMONIC:&str = "endorse answer radar about source reunion marriage tag sausage weekend frost daring base attack because joke dream slender leisure group reason prepare broken river";
@@ -614,6 +1483,18 @@ mod tests {
             viviswap_state: None,
             local_share: None,
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         })
         .unwrap();
 
@@ -633,7 +1514,7 @@ mod tests {
 
         // Act
         let result = manager
-            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, mnemonic)
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, mnemonic, &None)
             .await;
 
         // Assert
@@ -657,7 +1538,7 @@ mod tests {
 
         // Create wallet
         manager
-            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin)
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &MnemonicOptions::default())
             .await
             .expect("failed to create new wallet");
 
@@ -674,7 +1555,7 @@ mod tests {
 
         // Assert
         match should_succeed {
-            Ok(_) => restore_result.unwrap(),
+            Ok(_) => assert_eq!(restore_result.unwrap(), None),
             Err(ref expected_err) => {
                 assert_eq!(restore_result.err().unwrap().to_string(), expected_err.to_string());
             }
@@ -682,33 +1563,844 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_change_password() {
-        //Arrange
-        //Arrange
-        let (mut config, _cleanup) = Config::new_test_with_cleanup();
+    async fn test_json_backup_and_restore() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
         let mut manager = WalletManagerImpl::new(USERNAME);
         let (pin, mut repo) = get_user_repo();
+        let metadata = WalletMetadata {
+            network_ids: vec![example_network_id(Currency::Iota)],
+            preferred_network: Some(String::from("IOTA")),
+            evm_address_indices: vec![1],
+            share_labels: vec![String::from("ignored label")],
+        };
 
-        // create a wallet
         manager
-            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin)
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
             .await
-            .expect("should succeed to create new wallet");
+            .expect("failed to create wallet");
 
-        let new_password = PlainPassword::try_from_string("new_password").unwrap();
+        // Act
+        let backup = manager
+            .create_json_wallet_backup(&config, &None, &mut repo, &pin, &BACKUP_PASSWORD, metadata.clone())
+            .await
+            .expect("failed to create backup");
         manager
-            .change_wallet_password(&config, &None, &mut repo, &pin, &new_password)
+            .delete_wallet(&config, &None, &mut repo)
             .await
-            .expect("should succeed to change wallet password");
+            .expect("failed to delete wallet");
+        let restored = manager
+            .create_wallet_from_backup(&config, &None, &mut repo, &pin, &backup, &BACKUP_PASSWORD)
+            .await
+            .expect("failed to restore backup");
+
+        // Assert
+        assert_eq!(
+            restored,
+            Some(WalletMetadata {
+                share_labels: config.shares.additional_labels.clone(),
+                ..metadata
+            })
+        );
+        let is_same = manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .unwrap();
+        assert!(is_same);
+    }
+
+    #[rstest]
+    #[case::kdbx(false)]
+    #[case::json(true)]
+    #[tokio::test]
+    async fn test_passphrase_is_kept_out_of_shares_and_in_backup(#[case] json: bool) {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        let passphrase = Some(MnemonicPassphrase::try_from_string("25th word").unwrap());
+
+        manager
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &passphrase)
+            .await
+            .expect("failed to create wallet");
+
+        // Act
+        let backup = if json {
+            manager
+                .create_json_wallet_backup(
+                    &config,
+                    &None,
+                    &mut repo,
+                    &pin,
+                    &BACKUP_PASSWORD,
+                    WalletMetadata::default(),
+                )
+                .await
+        } else {
+            manager
+                .create_wallet_backup(&config, &None, &mut repo, &pin, &BACKUP_PASSWORD)
+                .await
+        }
+        .expect("failed to create backup");
+        manager
+            .delete_wallet(&config, &None, &mut repo)
+            .await
+            .expect("failed to delete wallet");
+        manager
+            .create_wallet_from_backup(&config, &None, &mut repo, &pin, &backup, &BACKUP_PASSWORD)
+            .await
+            .expect("failed to restore backup");
+
+        // Assert
+        let with_passphrase = manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &passphrase)
+            .await
+            .unwrap();
+        let without_passphrase = manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .unwrap();
+        assert!(with_passphrase);
+        assert!(!without_passphrase);
+
+        let user = repo.get(USERNAME).unwrap();
+        assert!(user.encrypted_passphrase.is_some());
+        assert!(user.local_share.unwrap().starts_with("MP-"));
+    }
+
+    #[tokio::test]
+    async fn test_passphrase_missing_on_device() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        let passphrase = Some(MnemonicPassphrase::try_from_string("25th word").unwrap());
+
+        manager
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &passphrase)
+            .await
+            .expect("failed to create wallet");
+
+        let mut user = repo.get(USERNAME).unwrap();
+        user.encrypted_passphrase = None;
+        repo.update(&user).unwrap();
+
+        // Act
+        let result = manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &passphrase)
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(WalletError::WalletNotInitialized(ErrorKind::MissingPassphrase))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_offline_unlock_without_backend_shares() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        manager
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .expect("failed to create wallet");
+
+        // Act
+        manager
+            .set_offline_unlock(&config, &None, &mut repo, &pin, true)
+            .await
+            .expect("failed to enable the offline unlock");
+
+        // Assert
+        // a new manager neither has the recovery share in memory nor an access token to download shares
+        let mut offline_manager = WalletManagerImpl::new(USERNAME);
+        let matches = offline_manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .expect("failed to unlock the wallet offline");
+        assert!(matches);
+
+        manager
+            .set_offline_unlock(&config, &None, &mut repo, &pin, false)
+            .await
+            .expect("failed to disable the offline unlock");
+        assert_eq!(repo.get(USERNAME).unwrap().offline_recovery_share, None);
+
+        let mut offline_manager = WalletManagerImpl::new(USERNAME);
+        let result = offline_manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(WalletError::WalletNotInitialized(ErrorKind::SetRecoveryShare))
+        ));
+    }
 
+    #[tokio::test]
+    async fn test_create_wallet_from_new_mnemonic_with_options() {
+        // Arrange
+        let (mut config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        let options = MnemonicOptions {
+            word_count: crate::mnemonic::MnemonicWordCount::Words15,
+            language: crate::mnemonic::MnemonicLanguage::French,
+        };
+
+        // Act
+        let mnemonic = manager
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &options)
+            .await
+            .expect("should succeed to create new wallet");
+        let (resembled, _passphrase, _status) = manager
+            .try_resemble_shares(&config, &None, &mut repo, &pin)
+            .await
+            .expect("should resemble the shares");
         let wallet = manager
             .try_get(&mut config, &None, &mut repo, example_network(Currency::Iota), &pin)
             .await
-            .expect("should succeed to get wallet after password change");
+            .expect("should succeed to get wallet");
 
+        // Assert
+        assert_eq!(mnemonic.split(' ').count(), 15);
+        assert_eq!(
+            validate_mnemonic(&mnemonic),
+            Ok(crate::mnemonic::MnemonicLanguage::French)
+        );
+        assert_eq!(&**resembled, mnemonic.as_str());
         wallet.get_address().await.expect("wallet should return an address");
     }
 
+    #[tokio::test]
+    async fn test_create_wallet_from_invalid_mnemonic() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        // Act
+        let result = manager
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC_INCORRECT, &None)
+            .await;
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(WalletError::Mnemonic(crate::mnemonic::MnemonicError::InvalidWordCount(
+                23
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        //Arrange
+        //Arrange
+        let (mut config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        // create a wallet
+        manager
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &MnemonicOptions::default())
+            .await
+            .expect("should succeed to create new wallet");
+
+        let new_password = PlainPassword::try_from_string("new_password").unwrap();
+        manager
+            .change_wallet_password(&config, &None, &mut repo, &pin, &new_password)
+            .await
+            .expect("should succeed to change wallet password");
+
+        let wallet = manager
+            .try_get(&mut config, &None, &mut repo, example_network(Currency::Iota), &pin)
+            .await
+            .expect("should succeed to get wallet after password change");
+
+        wallet.get_address().await.expect("wallet should return an address");
+    }
+
+    #[tokio::test]
+    async fn test_change_password_restores_old_password_if_upload_fails() {
+        // Arrange
+        let mut srv = mockito::Server::new_async().await;
+        let url = format!("{}/api", srv.url());
+        srv.mock("GET", "/api/user/shares/guardians").with_status(404).create();
+        let upload_mock = srv.mock("PUT", "/api/user/shares/backup").with_status(500).create();
+
+        let (mut config, _cleanup) = Config::new_test_with_cleanup_url(&url);
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        manager
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &MnemonicOptions::default())
+            .await
+            .expect("should succeed to create new wallet");
+        let old_user = repo.get(USERNAME).unwrap();
+
+        // Act
+        let new_password = PlainPassword::try_from_string("new_password").unwrap();
+        let result = manager
+            .change_wallet_password(&config, &access_token, &mut repo, &pin, &new_password)
+            .await;
+
+        // Assert
+        assert!(result.is_err());
+        let user = repo.get(USERNAME).unwrap();
+        assert_eq!(user.local_share, old_user.local_share);
+        assert_eq!(user.encrypted_password, old_user.encrypted_password);
+        manager
+            .try_get(&mut config, &None, &mut repo, example_network(Currency::Iota), &pin)
+            .await
+            .expect("should still unlock the wallet with the old password");
+        upload_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_change_password_reencrypts_offline_recovery_share() {
+        // Arrange: a user without a wallet, so only the password stored on the device is changed
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        let mut user = repo.get(USERNAME).unwrap();
+        user.offline_recovery_share = Some(encrypt_offline_recovery_share(&user, &pin, &Share::mock_share()).unwrap());
+        repo.update(&user).unwrap();
+
+        // Act
+        let new_password = PlainPassword::try_from_string("new_password").unwrap();
+        manager
+            .change_wallet_password(&config, &None, &mut repo, &pin, &new_password)
+            .await
+            .expect("should succeed to change wallet password");
+
+        // Assert
+        let user = repo.get(USERNAME).unwrap();
+        assert_eq!(
+            read_offline_recovery_share(&user, &pin).unwrap(),
+            Some(Share::mock_share())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resemble_shares_with_additional_shares() {
+        // Arrange
+        let (mut config, _cleanup) = Config::new_test_with_cleanup();
+        config.shares = crate::share::ShareScheme {
+            threshold: 4,
+            additional_labels: vec!["trusted contact".to_string(), "hardware backup".to_string()],
+        };
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        let mnemonic = manager
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &MnemonicOptions::default())
+            .await
+            .expect("should succeed to create new wallet");
+        let additional_shares = manager.get_additional_shares();
+        assert_eq!(additional_shares.len(), 2);
+        assert_eq!(additional_shares[0].label(), Some("trusted contact"));
+
+        // Act
+        manager.set_additional_shares(Vec::new());
+        let without_additional = manager.try_resemble_shares(&config, &None, &mut repo, &pin).await;
+
+        manager.set_additional_shares(additional_shares);
+        let with_additional = manager.try_resemble_shares(&config, &None, &mut repo, &pin).await;
+
+        // Assert
+        assert!(matches!(
+            without_additional,
+            Err(WalletError::WalletNotInitialized(ErrorKind::SetAdditionalShares))
+        ));
+        let (resembled, _passphrase, _status) = with_additional.expect("should resemble the shares");
+        assert_eq!(&**resembled, mnemonic.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_local_and_recovery_share_stay_below_threshold_of_additional_shares() {
+        // Arrange
+        let (mut config, _cleanup) = Config::new_test_with_cleanup();
+        config.shares = crate::share::ShareScheme {
+            threshold: 3,
+            additional_labels: vec!["trusted contact".to_string(), "hardware backup".to_string()],
+        };
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        manager
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &MnemonicOptions::default())
+            .await
+            .expect("should succeed to create new wallet");
+        manager.set_additional_shares(Vec::new());
+
+        // Act
+        let user = repo.get(USERNAME).unwrap();
+        let local = read_local_share(&mut repo, &user, &pin).unwrap().unwrap();
+        let recovery = manager.recovery_share.clone().unwrap();
+        let result = manager.try_resemble_shares(&config, &None, &mut repo, &pin).await;
+
+        // Assert: the shares handed out to the contacts are not kept on the device
+        let shares = [local, recovery];
+        assert!(shares.len() < required_shares(&shares));
+        assert!(matches!(
+            result,
+            Err(WalletError::WalletNotInitialized(ErrorKind::SetAdditionalShares))
+        ));
+    }
+
+    /// Creates a repository with a user that has the [`SHARE_LOCAL`] share and the [`SHARE_PASSWORD`].
+    fn get_user_repo_with_shares() -> (EncryptionPin, UserRepoT) {
+        let (pin, mut repo) = get_user_repo();
+        let mut user = repo.get(USERNAME).unwrap();
+        let password = PlainPassword::try_from_string(SHARE_PASSWORD).unwrap();
+        user.encrypted_password = Some(password.encrypt(&pin, &user.salt).unwrap());
+        user.local_share = Some(SHARE_LOCAL.to_string());
+        repo.update(&user).unwrap();
+        (pin, repo)
+    }
+
+    #[tokio::test]
+    async fn test_check_shares_reports_stale_unknown_and_missing_shares() {
+        // Arrange
+        let mut srv = mockito::Server::new_async().await;
+        let url = format!("{}/api", srv.url());
+        let backup_mock = srv
+            .mock("GET", "/api/user/shares/backup")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!("{{ \"share\":\"{SHARE_BACKUP}\" }}"))
+            .create();
+        let recovery_mock = srv.mock("GET", "/api/user/shares/recovery").with_status(404).create();
+
+        let (config, _cleanup) = Config::new_test_with_cleanup_url(&url);
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo_with_shares();
+
+        // Act
+        let report = manager
+            .check_shares(&config, &access_token, &mut repo, &pin)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            report,
+            ShareHealthReport {
+                local: ShareHealth::Stale,
+                backup: ShareHealth::Unknown,
+                recovery: ShareHealth::Missing,
+                provided_recovery: ShareHealth::Missing,
+                additional: Vec::new(),
+            }
+        );
+        assert!(report.needs_repair());
+        backup_mock.assert();
+        recovery_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_check_shares_requires_access_token() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo_with_shares();
+
+        // Act
+        let result = manager.check_shares(&config, &None, &mut repo, &pin).await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::MissingAccessToken)));
+    }
+
+    #[tokio::test]
+    async fn test_repair_shares_replaces_stale_shares() {
+        // Arrange
+        let mut srv = mockito::Server::new_async().await;
+        let url = format!("{}/api", srv.url());
+        let download_mock = srv
+            .mock("GET", "/api/user/shares/backup")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(format!("{{ \"share\":\"{SHARE_BACKUP}\" }}"))
+            .create();
+        srv.mock("GET", "/api/user/shares/recovery").with_status(404).create();
+        srv.mock("GET", "/api/user/shares/guardians").with_status(404).create();
+        let upload_backup_mock = srv.mock("PUT", "/api/user/shares/backup").with_status(200).create();
+        let upload_recovery_mock = srv.mock("PUT", "/api/user/shares/recovery").with_status(200).create();
+
+        let (config, _cleanup) = Config::new_test_with_cleanup_url(&url);
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo_with_shares();
+
+        // Act
+        manager
+            .repair_shares(&config, &access_token, &mut repo, &pin)
+            .await
+            .unwrap();
+
+        // Assert
+        let local_share = repo.get(USERNAME).unwrap().local_share.unwrap();
+        assert_ne!(local_share, SHARE_LOCAL);
+        assert!(
+            local_share.starts_with("ME-RS-Pin-"),
+            "new local share should be encrypted"
+        );
+        assert!(
+            local_share.contains("-2of3--"),
+            "new local share should have a fingerprint"
+        );
+        assert!(manager.recovery_share.is_some());
+        download_mock.assert();
+        upload_backup_mock.assert();
+        upload_recovery_mock.assert();
+    }
+
+    fn example_guardians() -> Vec<(Guardian, SecretSlice<u8>)> {
+        ["alice", "bob"]
+            .into_iter()
+            .map(|username| {
+                let secret_key = generate_secret_key();
+                let guardian = Guardian {
+                    username: username.to_string(),
+                    public_key: public_key(&secret_key).unwrap(),
+                };
+                (guardian, secret_key)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_set_guardians_uploads_encrypted_shares() {
+        // Arrange
+        let mut srv = mockito::Server::new_async().await;
+        let url = format!("{}/api", srv.url());
+        let upload_backup_mock = srv.mock("PUT", "/api/user/shares/backup").with_status(200).create();
+        let upload_recovery_mock = srv.mock("PUT", "/api/user/shares/recovery").with_status(200).create();
+        let upload_guardians_mock = srv
+            .mock("PUT", "/api/user/shares/guardians")
+            .match_body(mockito::Matcher::Regex("ME-RS-X25519-".to_string()))
+            .with_status(200)
+            .create();
+
+        let (config, _cleanup) = Config::new_test_with_cleanup_url(&url);
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        manager
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .unwrap();
+        let guardians = example_guardians().into_iter().map(|(g, _)| g).collect();
+
+        // Act
+        manager
+            .set_guardians(&config, &access_token, &mut repo, &pin, guardians, 2)
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(manager.additional_shares.is_empty());
+        assert_eq!(manager.recovery_share.as_ref().map(Share::threshold), Some(2));
+        let (mnemonic, _passphrase, _status) = manager
+            .try_resemble_shares(&config, &None, &mut repo, &pin)
+            .await
+            .unwrap();
+        assert_eq!(&**mnemonic, MNEMONIC);
+        upload_backup_mock.assert();
+        upload_recovery_mock.assert();
+        upload_guardians_mock.assert();
+    }
+
+    #[rstest]
+    #[case::no_guardians(0, 2)]
+    #[case::threshold_too_large(2, 4)]
+    #[tokio::test]
+    async fn test_set_guardians_rejects_invalid_scheme(#[case] guardians: usize, #[case] threshold: u8) {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+        manager
+            .create_wallet_from_existing_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .unwrap();
+        let guardians = example_guardians()
+            .into_iter()
+            .take(guardians)
+            .map(|(g, _)| g)
+            .collect();
+
+        // Act
+        let result = manager
+            .set_guardians(&config, &access_token, &mut repo, &pin, guardians, threshold)
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::Share(ShareError::InvalidScheme(_)))));
+    }
+
+    #[tokio::test]
+    async fn test_get_guardian_public_key_creates_key_once() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        // Act
+        let public_key = manager
+            .get_guardian_public_key(&config, &None, &mut repo, &pin)
+            .await
+            .unwrap();
+        let same_public_key = manager
+            .get_guardian_public_key(&config, &None, &mut repo, &pin)
+            .await
+            .unwrap();
+
+        // Assert
+        assert_eq!(public_key, same_public_key);
+        let user = repo.get(USERNAME).unwrap();
+        let stored = user.guardian_secret_key.unwrap();
+        let secret_key = decrypt_secret_key(&stored, &pin, &user.salt).unwrap();
+        assert_eq!(crate::share::public_key(&secret_key).unwrap(), public_key);
+    }
+
+    #[rstest]
+    #[case::wrong_fingerprint("0000-0000-0000-0000", WalletError::GuardianRecoveryFingerprintMismatch)]
+    #[case::no_guardian_key("", WalletError::GuardianKeyNotSet)]
+    #[tokio::test]
+    async fn test_approve_guardian_recovery_checks_fingerprint(
+        #[case] fingerprint: &str,
+        #[case] expected: WalletError,
+    ) {
+        // Arrange
+        let mut srv = mockito::Server::new_async().await;
+        let url = format!("{}/api", srv.url());
+        let (config, _cleanup) = Config::new_test_with_cleanup_url(&url);
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        let (guardian, _secret_key) = example_guardians().remove(0);
+        let share = crate::share::create_shares_from_mnemonic(
+            MNEMONIC,
+            false,
+            &PASSWORD.into_secret(),
+            &ShareScheme {
+                threshold: 2,
+                additional_labels: vec![guardian.username.clone()],
+            },
+        )
+        .unwrap()
+        .additional
+        .remove(0);
+        let recovery_public_key = public_key(&generate_secret_key()).unwrap();
+        let fingerprint = match fingerprint {
+            "" => public_key_fingerprint(&recovery_public_key).unwrap(),
+            fingerprint => fingerprint.to_string(),
+        };
+        let recovery = api_types::api::user::GetGuardianRecoveryResponse {
+            share: encrypt_for_public_key(&share, &guardian.public_key)
+                .unwrap()
+                .to_string()
+                .expose_secret()
+                .to_string(),
+            public_key: recovery_public_key,
+        };
+        let recovery_mock = srv
+            .mock("GET", "/api/user/guardians/friend/recovery")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&recovery).unwrap())
+            .create();
+        let approval_mock = srv
+            .mock("PUT", "/api/user/guardians/friend/recovery")
+            .expect(0)
+            .create();
+
+        // Act
+        let result = manager
+            .approve_guardian_recovery(&config, &access_token, &mut repo, &pin, "friend", &fingerprint)
+            .await;
+
+        // Assert
+        assert_eq!(result.unwrap_err().to_string(), expected.to_string());
+        recovery_mock.assert();
+        approval_mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_complete_guardian_recovery_requires_started_recovery() {
+        // Arrange
+        let (config, _cleanup) = Config::new_test_with_cleanup();
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        // Act
+        let result = manager
+            .complete_guardian_recovery(&config, &access_token, &mut repo, &pin)
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::GuardianRecoveryNotStarted)));
+    }
+
+    #[tokio::test]
+    async fn test_try_get_watch_only_uses_stored_addresses() {
+        // Arrange
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (_pin, mut repo) = get_user_repo();
+        let mut user = repo.get(USERNAME).unwrap();
+        user.watch_only = Some(WatchOnlySource::EvmAddresses(vec![
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_string(),
+        ]));
+        repo.update(&user).unwrap();
+
+        // Act
+        let wallet = manager
+            .try_get_watch_only(&mut repo, example_network(Currency::Eth))
+            .unwrap();
+
+        // Assert
+        assert_eq!(
+            wallet.get_address().await.unwrap(),
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8"
+        );
+    }
+
+    #[rstest]
+    #[case(None, Currency::Eth)]
+    #[case(Some(WatchOnlySource::EvmAddresses(vec!["0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_string()])), Currency::Iota)]
+    fn test_try_get_watch_only_fails(#[case] source: Option<WatchOnlySource>, #[case] currency: Currency) {
+        // Arrange
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (_pin, mut repo) = get_user_repo();
+        let mut user = repo.get(USERNAME).unwrap();
+        user.watch_only = source.clone();
+        repo.update(&user).unwrap();
+
+        // Act
+        let result = manager.try_get_watch_only(&mut repo, example_network(currency));
+
+        // Assert
+        match source {
+            None => assert!(matches!(result, Err(WalletError::MissingWatchOnlySource))),
+            Some(_) => assert!(matches!(result, Err(WalletError::WalletFeatureNotImplemented))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_guardian_recovery() {
+        // Arrange
+        let mut srv = mockito::Server::new_async().await;
+        let url = format!("{}/api", srv.url());
+        let (config, _cleanup) = Config::new_test_with_cleanup_url(&url);
+        let access_token = Some(AccessToken::try_from_string("a fake token").unwrap());
+        let mut manager = WalletManagerImpl::new(USERNAME);
+        let (pin, mut repo) = get_user_repo();
+
+        // the shares of the guardians, created before the device and the recovery share were lost
+        let guardians = example_guardians();
+        let shares = crate::share::create_shares_from_mnemonic(
+            MNEMONIC,
+            false,
+            &PASSWORD.into_secret(),
+            &ShareScheme {
+                threshold: 2,
+                additional_labels: guardians.iter().map(|(g, _)| g.username.clone()).collect(),
+            },
+        )
+        .unwrap();
+
+        let start_mock = srv
+            .mock("POST", "/api/user/shares/guardians/recovery")
+            .with_status(201)
+            .create();
+        let fingerprint = manager
+            .start_guardian_recovery(&config, &access_token, &mut repo, &pin)
+            .await
+            .unwrap();
+        let user = repo.get(USERNAME).unwrap();
+        let recovery_key = decrypt_secret_key(user.guardian_recovery_key.as_ref().unwrap(), &pin, &user.salt).unwrap();
+        let recovery_public_key = public_key(&recovery_key).unwrap();
+        assert_eq!(fingerprint, public_key_fingerprint(&recovery_public_key).unwrap());
+
+        // both guardians approve by encrypting their share for the recovery
+        let approvals = guardians
+            .iter()
+            .zip(&shares.additional)
+            .map(|((guardian, secret_key), share)| {
+                let share = encrypt_for_public_key(share, &guardian.public_key).unwrap();
+                let share = decrypt_with_secret_key(&share, secret_key).unwrap();
+                encrypt_for_public_key(&share, &recovery_public_key)
+                    .unwrap()
+                    .to_string()
+                    .expose_secret()
+                    .to_string()
+            })
+            .collect();
+        let approvals_mock = srv
+            .mock("GET", "/api/user/shares/guardians/recovery")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                serde_json::to_string(&api_types::api::user::GetGuardianApprovalsResponse { shares: approvals })
+                    .unwrap(),
+            )
+            .create();
+        let guardian_shares = api_types::api::user::GetGuardianSharesResponse {
+            shares: guardians
+                .iter()
+                .zip(&shares.additional)
+                .map(|((guardian, _), share)| api_types::api::user::GuardianShare {
+                    guardian: guardian.username.clone(),
+                    public_key: guardian.public_key.clone(),
+                    share: encrypt_for_public_key(share, &guardian.public_key)
+                        .unwrap()
+                        .to_string()
+                        .expose_secret()
+                        .to_string(),
+                })
+                .collect(),
+        };
+        let guardians_mock = srv
+            .mock("GET", "/api/user/shares/guardians")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&guardian_shares).unwrap())
+            .create();
+        let upload_backup_mock = srv.mock("PUT", "/api/user/shares/backup").with_status(200).create();
+        let upload_recovery_mock = srv.mock("PUT", "/api/user/shares/recovery").with_status(200).create();
+        let upload_guardians_mock = srv.mock("PUT", "/api/user/shares/guardians").with_status(200).create();
+
+        // Act
+        manager
+            .complete_guardian_recovery(&config, &access_token, &mut repo, &pin)
+            .await
+            .unwrap();
+
+        // Assert
+        assert!(repo.get(USERNAME).unwrap().guardian_recovery_key.is_none());
+        let is_same = manager
+            .check_mnemonic(&config, &None, &mut repo, &pin, MNEMONIC, &None)
+            .await
+            .unwrap();
+        assert!(is_same);
+        start_mock.assert();
+        approvals_mock.assert();
+        guardians_mock.assert();
+        upload_backup_mock.assert();
+        upload_recovery_mock.assert();
+        upload_guardians_mock.assert();
+    }
+
     #[tokio::test]
     async fn delete_wallet_removes_files() {
         //Arrange
@@ -718,7 +2410,7 @@ mod tests {
 
         // create a wallet
         manager
-            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin)
+            .create_wallet_from_new_mnemonic(&config, &None, &mut repo, &pin, &None, &MnemonicOptions::default())
             .await
             .expect("should succeed to create new wallet");
 
@@ -910,6 +2602,18 @@ mod tests {
             viviswap_state: None,
             local_share: local_share.map(|s| s.to_string()),
             wallet_transactions: Vec::new(),
+            wallet_tx_history: Default::default(),
+            pin_attempts: Default::default(),
+            evm_address_indices: Vec::new(),
+            pending_transactions: Default::default(),
+            watched_transactions: Vec::new(),
+            watch_only: None,
+            created_at: None,
+            last_used_at: None,
+            guardian_secret_key: None,
+            guardian_recovery_key: None,
+            encrypted_passphrase: None,
+            offline_recovery_share: None,
         };
 
         repo.expect_get().returning(move |_| Ok(user.clone()));
@@ -917,8 +2621,9 @@ mod tests {
         manager.recovery_share = local_recovery_share.map(|s| s.parse::<Share>().unwrap());
 
         // If the expected result is OK and there was no local share from the beginning, we expect
-        // the local share to be set to a valid share.
-        if expected_result.is_ok() && local_share.is_none() {
+        // the local share to be set to a valid share. A plain text local share is encrypted with the
+        // pin and stored again.
+        if (expected_result.is_ok() && local_share.is_none()) || local_share.is_some() {
             repo.expect_set_local_share().returning(|_, _| Ok(()));
         }
         let mut repo = Box::new(repo) as UserRepoT;
@@ -927,7 +2632,7 @@ mod tests {
         let result = manager
             .try_resemble_shares(&config, &access_token, &mut repo, &pin)
             .await
-            .map(|(_mnemonic, status)| status);
+            .map(|(_mnemonic, _passphrase, status)| status);
 
         // Assert
         match (&result, expected_result) {
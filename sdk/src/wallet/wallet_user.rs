@@ -1,11 +1,17 @@
 use super::error::{Result, WalletError};
+use super::mnemonic::{normalize_mnemonic, MnemonicLanguage};
 use crate::types::currencies::{CryptoAmount, Currency};
-use crate::types::transactions::{GasCostEstimation, WalletTxInfo, WalletTxInfoList};
+use crate::types::newtypes::MnemonicPassphrase;
+use crate::types::transactions::{
+    GasCostEstimation, PendingTransaction, TransactionReplacement, UnsignedTransaction, UnsignedTransactionDetails,
+    WalletTxInfo, WalletTxInfoList,
+};
 use async_trait::async_trait;
 use iota_sdk::client::secret::SecretManager;
-use iota_sdk::crypto::keys::bip39::Mnemonic;
+use iota_sdk::crypto::keys::bip39::{mnemonic_to_seed, Mnemonic, Passphrase};
 use iota_sdk::types::block::payload::transaction::TransactionId;
 use iota_sdk::types::block::payload::TaggedDataPayload;
+use iota_sdk::wallet::account::types::InclusionState;
 use iota_sdk::wallet::account::{Account, SyncOptions, TransactionOptions};
 use iota_sdk::wallet::ClientOptions;
 use log::{error, info};
@@ -13,6 +19,7 @@ use rust_decimal_macros::dec;
 use std::fmt::Debug;
 use std::path::Path;
 use std::str::FromStr;
+use zeroize::Zeroizing;
 
 /// The number of addresses to automatically generate when setting up the wallet
 const USER_ADDRESS_LIMIT: u32 = 20;
@@ -30,6 +37,33 @@ pub struct TransactionIntent {
 
     /// Optional data to attach to the transaction.
     pub data: Option<Vec<u8>>,
+
+    /// The address of the wallet to send from, or `None` to send from the default address.
+    pub address_from: Option<String>,
+}
+
+/// The result of scanning the network for transactions of the wallet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletTxScan {
+    /// The transactions of the wallet found in the scanned blocks.
+    pub transactions: Vec<WalletTxInfo>,
+
+    /// The last block that was scanned, if any. Pass this to the next scan to continue where this one stopped.
+    pub last_scanned_block: Option<u64>,
+
+    /// The oldest block that was scanned, if any. Pass this to the next scan to backfill the blocks before it.
+    pub first_scanned_block: Option<u64>,
+}
+
+/// How far a transaction is included in the network.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransactionConfirmation {
+    /// The inclusion state of the transaction.
+    pub inclusion_state: InclusionState,
+
+    /// The number of blocks including and following the block of the transaction, or `None` if the network has
+    /// immediate finality and an included transaction is final.
+    pub confirmations: Option<u64>,
 }
 
 #[cfg_attr(test, mockall::automock)]
@@ -48,6 +82,18 @@ pub trait WalletUser: Debug {
     /// This function can return an error if it fails to synchronize the wallet, generate addresses, or encounter any other issues.
     async fn get_address(&self) -> Result<String>;
 
+    /// Gets all addresses of the wallet that are in use
+    ///
+    /// # Returns
+    ///
+    /// Returns the addresses ordered by their derivation index, starting with the default address returned by
+    /// [`WalletUser::get_address`].
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if it fails to synchronize the wallet or generate addresses.
+    async fn get_addresses(&self) -> Result<Vec<String>>;
+
     /// Gets the balance of a user.
     ///
     /// # Returns
@@ -59,6 +105,18 @@ pub trait WalletUser: Debug {
     /// This function can return an error if it fails to synchronize the wallet or encounters any other issues.
     async fn get_balance(&self) -> Result<CryptoAmount>;
 
+    /// Gets the balance of a single address of the wallet.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - One of the addresses returned by [`WalletUser::get_addresses`].
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the address does not belong to the wallet or the balance cannot be
+    /// retrieved.
+    async fn get_address_balance(&self, address: &str) -> Result<CryptoAmount>;
+
     /// Send amount to receiver
     ///
     /// # Arguments
@@ -92,6 +150,26 @@ pub trait WalletUser: Debug {
     /// This function can return an error if it cannot retrieve the list of wallet transactions.
     async fn get_wallet_tx_list(&self, start: usize, limit: usize) -> Result<WalletTxInfoList>;
 
+    /// Scans the network for incoming and outgoing transactions of the wallet
+    ///
+    /// # Arguments
+    ///
+    /// * `last_scanned_block` - The last block that was already scanned, or `None` to start with the most recent blocks.
+    /// * `first_scanned_block` - The oldest block that was already scanned, or `None` to not backfill older blocks.
+    ///
+    /// # Returns
+    ///
+    /// The transactions found in the newly scanned blocks together with the range of blocks scanned so far.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support scanning or the node cannot be reached.
+    async fn scan_wallet_tx(
+        &self,
+        last_scanned_block: Option<u64>,
+        first_scanned_block: Option<u64>,
+    ) -> Result<WalletTxScan>;
+
     /// Get detailed report of a particular transaction in the history
     ///
     /// # Arguments
@@ -107,6 +185,21 @@ pub trait WalletUser: Debug {
     /// This function can return an error if it cannot retrieve the wallet transaction.
     async fn get_wallet_tx(&self, tx_id: &str) -> Result<WalletTxInfo>;
 
+    /// Get how far a transaction is included in the network
+    ///
+    /// # Arguments
+    ///
+    /// * `tx_id` - The id of the transaction sent by this wallet.
+    ///
+    /// # Returns
+    ///
+    /// The inclusion state and number of confirmations of the transaction.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the transaction is unknown or the node cannot be reached.
+    async fn get_transaction_confirmation(&self, tx_id: &str) -> Result<TransactionConfirmation>;
+
     /// Estimate gas cost for eip 1559 transaction
     ///
     /// # Arguments
@@ -117,6 +210,142 @@ pub trait WalletUser: Debug {
     ///
     /// This function can return an error if it cannot parse input transaction or retrieve information from the node.
     async fn estimate_gas_cost(&self, intent: &TransactionIntent) -> Result<GasCostEstimation>;
+
+    /// Signs a transaction locally without broadcasting it
+    ///
+    /// # Arguments
+    ///
+    /// * `intent` - The transaction to sign.
+    /// * `min_nonce` - The lowest nonce to use, e.g. to skip nonces of transactions that are still pending. The
+    ///   pending nonce of the network is used if it is higher.
+    ///
+    /// # Returns
+    ///
+    /// The signed transaction together with its nonce and fees.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support local signing, or the nonce or fees cannot
+    /// be retrieved from the node.
+    async fn sign_transaction(&self, intent: &TransactionIntent, min_nonce: Option<u64>) -> Result<PendingTransaction>;
+
+    /// Signs a transaction replacing a pending transaction, using the same nonce and higher fees
+    ///
+    /// # Arguments
+    ///
+    /// * `pending` - The pending transaction to replace.
+    /// * `replacement` - Whether to speed up or cancel the pending transaction.
+    ///
+    /// # Returns
+    ///
+    /// The signed replacement transaction.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support local signing, the pending transaction
+    /// cannot be decoded or was not sent from this wallet.
+    async fn sign_replacement(
+        &self,
+        pending: &PendingTransaction,
+        replacement: TransactionReplacement,
+    ) -> Result<PendingTransaction>;
+
+    /// Broadcasts a signed transaction to the network
+    ///
+    /// # Arguments
+    ///
+    /// * `pending` - The signed transaction to broadcast.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support local signing or the node rejects the
+    /// transaction.
+    async fn broadcast_transaction(&self, pending: &PendingTransaction) -> Result<()>;
+
+    /// Gets the nonce of the next transaction of an address, counting only transactions included in a block
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - One of the addresses returned by [`WalletUser::get_addresses`].
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support nonces or the node cannot be reached.
+    async fn get_confirmed_nonce(&self, address: &str) -> Result<u64>;
+
+    /// Prepares a transaction without signing it, so it can be signed on an offline device
+    ///
+    /// The nonce, fees and gas limit are retrieved from the network, but no key is needed, so this also works
+    /// for watch-only wallets.
+    ///
+    /// # Arguments
+    ///
+    /// * `intent` - The transaction to prepare.
+    /// * `min_nonce` - The lowest nonce to use, e.g. to skip nonces of transactions that are still pending. The
+    ///   pending nonce of the network is used if it is higher.
+    ///
+    /// # Returns
+    ///
+    /// The unsigned transaction together with its sender.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support offline signing, or the nonce or fees
+    /// cannot be retrieved from the node.
+    async fn prepare_unsigned_transaction(
+        &self,
+        intent: &TransactionIntent,
+        min_nonce: Option<u64>,
+    ) -> Result<UnsignedTransaction>;
+
+    /// Decodes a transaction prepared by [`WalletUser::prepare_unsigned_transaction`] to review it before signing
+    ///
+    /// # Arguments
+    ///
+    /// * `unsigned` - The unsigned transaction.
+    ///
+    /// # Returns
+    ///
+    /// The receiver, value, data and fees of the transaction. For token transfers, the receiver and the value are
+    /// the ones of the transferred tokens.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support offline signing, the transaction cannot
+    /// be decoded, is for another chain or its sender does not belong to the wallet.
+    async fn review_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<UnsignedTransactionDetails>;
+
+    /// Signs a transaction prepared by [`WalletUser::prepare_unsigned_transaction`] without contacting the network
+    ///
+    /// # Arguments
+    ///
+    /// * `unsigned` - The unsigned transaction.
+    ///
+    /// # Returns
+    ///
+    /// The signed transaction, ready to be broadcast by [`WalletUser::broadcast_signed_transaction`].
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support offline signing, the transaction cannot
+    /// be decoded, is for another chain or its sender does not belong to the wallet.
+    async fn sign_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<PendingTransaction>;
+
+    /// Broadcasts a transaction that was signed on an offline device
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_transaction` - The EIP-2718 encoded signed transaction.
+    ///
+    /// # Returns
+    ///
+    /// The broadcast transaction, so that it can be tracked until it is included.
+    ///
+    /// # Errors
+    ///
+    /// This function can return an error if the network does not support offline signing, the transaction cannot
+    /// be decoded, was not signed by the wallet or the node rejects it.
+    async fn broadcast_signed_transaction(&self, raw_transaction: &[u8]) -> Result<PendingTransaction>;
 }
 
 /// [`WalletUser`] implementation for IOTA and SMR using the stardust protocol
@@ -127,8 +356,14 @@ pub struct WalletImplStardust {
 }
 
 impl WalletImplStardust {
-    /// Creates a new [`WalletImpl`] from the specified [`Config`] and [`Mnemonic`].
-    pub async fn new(mnemonic: Mnemonic, path: &Path, currency: Currency, node_url: Vec<String>) -> Result<Self> {
+    /// Creates a new [`WalletImpl`] from the specified [`Config`], [`Mnemonic`] and optional BIP-39 passphrase.
+    pub async fn new(
+        mnemonic: Mnemonic,
+        passphrase: Option<&MnemonicPassphrase>,
+        path: &Path,
+        currency: Currency,
+        node_url: Vec<String>,
+    ) -> Result<Self> {
         // we now have the mnemonic and can initialize a wallet
         let node_urls: Vec<&str> = node_url.iter().map(String::as_str).collect();
 
@@ -147,7 +382,26 @@ impl WalletImplStardust {
         }
 
         let account_manager = {
-            let secret_manager = SecretManager::try_from_mnemonic(mnemonic)?;
+            // the seed is derived from the NFKD normalized mnemonic, as required by BIP-39
+            let (language, mnemonic) = normalize_mnemonic(mnemonic.as_ref())?;
+            let secret_manager = match (language, passphrase) {
+                (MnemonicLanguage::English, None) => SecretManager::try_from_mnemonic(mnemonic)?,
+                (_, passphrase) => {
+                    // the mnemonic secret manager only supports English mnemonics without passphrase, so we
+                    // derive the seed ourselves
+                    let passphrase = passphrase.map(|p| p.as_str().to_string()).unwrap_or_default();
+                    let seed = mnemonic_to_seed(&mnemonic, &Passphrase::from(passphrase));
+                    // encode the seed directly into a zeroizing buffer, so no copy of it is left in memory
+                    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+                    let mut hex_seed = Zeroizing::new(String::with_capacity(2 + 2 * seed.as_ref().len()));
+                    hex_seed.push_str("0x");
+                    for byte in seed.as_ref() {
+                        hex_seed.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+                        hex_seed.push(char::from(HEX_DIGITS[usize::from(byte & 0x0f)]));
+                    }
+                    SecretManager::try_from_hex_seed(hex_seed)?
+                }
+            };
             iota_sdk::wallet::Wallet::builder()
                 .with_client_options(client_options)
                 .with_coin_type(coin_type)
@@ -210,6 +464,15 @@ impl WalletUser for WalletImplStardust {
         }
     }
 
+    // Only the first generated address is used as receiving address for now
+    async fn get_addresses(&self) -> Result<Vec<String>> {
+        Ok(vec![self.get_address().await?])
+    }
+
+    async fn get_address_balance(&self, _address: &str) -> Result<CryptoAmount> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
     async fn get_balance(&self) -> Result<CryptoAmount> {
         self.sync_wallet().await?;
         let account = self.account_manager.get_account(APP_NAME).await?;
@@ -226,8 +489,13 @@ impl WalletUser for WalletImplStardust {
             address_to,
             amount,
             data,
+            address_from,
         } = intent;
 
+        if address_from.is_some() {
+            return Err(WalletError::WalletFeatureNotImplemented);
+        }
+
         let min_amount = *amount + MIN_DUST_OUTPUT;
 
         // Check if we have enough balance, otherwise return with err
@@ -285,6 +553,15 @@ impl WalletUser for WalletImplStardust {
         })
     }
 
+    // The history is synchronized by the account itself, there is nothing to scan
+    async fn scan_wallet_tx(
+        &self,
+        _last_scanned_block: Option<u64>,
+        _first_scanned_block: Option<u64>,
+    ) -> Result<WalletTxScan> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
     /// Get detailed report of a particular transaction in the history
     async fn get_wallet_tx(&self, tx_id: &str) -> Result<WalletTxInfo> {
         let account = self.account_manager.get_account(APP_NAME).await?;
@@ -301,6 +578,24 @@ impl WalletUser for WalletImplStardust {
         }
     }
 
+    // Stardust milestones are final, an included transaction cannot be reverted
+    async fn get_transaction_confirmation(&self, tx_id: &str) -> Result<TransactionConfirmation> {
+        self.sync_wallet().await?;
+        let account = self.account_manager.get_account(APP_NAME).await?;
+        let transaction_id: TransactionId = tx_id
+            .parse()
+            .map_err(|e: <TransactionId as FromStr>::Err| WalletError::InvalidTransaction(e.to_string()))?;
+
+        let Some(transaction) = account.get_transaction(&transaction_id).await else {
+            return Err(WalletError::TransactionNotFound);
+        };
+
+        Ok(TransactionConfirmation {
+            inclusion_state: transaction.inclusion_state,
+            confirmations: None,
+        })
+    }
+
     async fn estimate_gas_cost(&self, _intent: &TransactionIntent) -> Result<GasCostEstimation> {
         // Stardust is fee-less
         Ok(GasCostEstimation {
@@ -309,6 +604,51 @@ impl WalletUser for WalletImplStardust {
             max_priority_fee_per_gas: 0,
         })
     }
+
+    // Stardust transactions are built and signed by the account and have no nonce
+    async fn sign_transaction(
+        &self,
+        _intent: &TransactionIntent,
+        _min_nonce: Option<u64>,
+    ) -> Result<PendingTransaction> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn sign_replacement(
+        &self,
+        _pending: &PendingTransaction,
+        _replacement: TransactionReplacement,
+    ) -> Result<PendingTransaction> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn broadcast_transaction(&self, _pending: &PendingTransaction) -> Result<()> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn get_confirmed_nonce(&self, _address: &str) -> Result<u64> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn prepare_unsigned_transaction(
+        &self,
+        _intent: &TransactionIntent,
+        _min_nonce: Option<u64>,
+    ) -> Result<UnsignedTransaction> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn review_unsigned_transaction(&self, _unsigned: &UnsignedTransaction) -> Result<UnsignedTransactionDetails> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn sign_unsigned_transaction(&self, _unsigned: &UnsignedTransaction) -> Result<PendingTransaction> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
+
+    async fn broadcast_signed_transaction(&self, _raw_transaction: &[u8]) -> Result<PendingTransaction> {
+        Err(WalletError::WalletFeatureNotImplemented)
+    }
 }
 
 #[cfg(test)]
@@ -330,9 +670,15 @@ mod tests {
     async fn get_wallet_user(mnemonic: impl Into<Mnemonic>, currency: Currency) -> (WalletImplStardust, CleanUp) {
         let (_, cleanup) = Config::new_test_with_cleanup();
         let node_url = vec![String::from("https://api.testnet.iotaledger.net")];
-        let wallet = WalletImplStardust::new(mnemonic.into(), Path::new(&cleanup.path_prefix), currency, node_url)
-            .await
-            .expect("should initialize wallet");
+        let wallet = WalletImplStardust::new(
+            mnemonic.into(),
+            None,
+            Path::new(&cleanup.path_prefix),
+            currency,
+            node_url,
+        )
+        .await
+        .expect("should initialize wallet");
         (wallet, cleanup)
     }
 
@@ -386,6 +732,7 @@ mod tests {
             address_to: address,
             amount,
             data: Some(index.into_bytes()),
+            address_from: None,
         };
 
         let transaction = wallet_user.send_amount(&intent).await.unwrap();
@@ -420,6 +767,7 @@ mod tests {
             address_to: address,
             amount,
             data: Some(index.into_bytes()),
+            address_from: None,
         };
 
         let transaction = wallet_user.send_amount(&intent).await.unwrap();
@@ -462,6 +810,7 @@ mod tests {
             address_to: address,
             amount,
             data: message,
+            address_from: None,
         };
 
         // Act
@@ -486,6 +835,7 @@ mod tests {
             address_to: address,
             amount,
             data: None,
+            address_from: None,
         };
 
         // Act
@@ -509,6 +859,7 @@ mod tests {
             address_to: address,
             amount,
             data: None,
+            address_from: None,
         };
 
         // Act
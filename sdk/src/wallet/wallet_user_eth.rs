@@ -1,39 +1,73 @@
 use super::error::Result;
-use super::wallet_user::{TransactionIntent, WalletUser};
+use super::wallet_user::{TransactionConfirmation, TransactionIntent, WalletTxScan, WalletUser};
 use crate::types::currencies::CryptoAmount;
-use crate::types::transactions::{GasCostEstimation, WalletTxInfo, WalletTxInfoList};
+use crate::types::newtypes::MnemonicPassphrase;
+use crate::types::transactions::{
+    GasCostEstimation, PendingTransaction, TransactionReplacement, UnsignedTransaction, UnsignedTransactionDetails,
+    WalletTxHistory, WalletTxInfo, WalletTxInfoList,
+};
 use crate::wallet::error::WalletError;
+use crate::wallet::mnemonic::{normalize_mnemonic, MnemonicLanguage};
+use alloy::eips::eip1559::Eip1559Estimation;
+use alloy::eips::eip2718::{Decodable2718, Encodable2718};
 use alloy::eips::BlockNumberOrTag;
-use alloy::network::{Ethereum, EthereumWallet, TransactionBuilder};
-use alloy::rpc::types::TransactionRequest;
-use alloy::signers::local::coins_bip39::English;
-use alloy::signers::local::MnemonicBuilder;
-use alloy::sol_types::SolCall;
+use alloy::network::{Ethereum, EthereumWallet, NetworkWallet, TransactionBuilder, TransactionResponse};
+use alloy::rpc::client::BatchRequest;
+use alloy::rpc::types::{Block, Filter, TransactionRequest};
+use alloy::signers::local::coins_bip39::{
+    ChineseSimplified, ChineseTraditional, Czech, English, French, Italian, Japanese, Korean, Portuguese, Spanish,
+    Wordlist,
+};
+use alloy::signers::local::{LocalSignerError, MnemonicBuilder, PrivateKeySigner};
+use alloy::sol_types::{SolCall, SolEvent};
 use alloy::{
     primitives::Address,
     primitives::U256,
     providers::{Provider, ProviderBuilder},
 };
-use alloy_consensus::Transaction;
+use alloy_consensus::{SignableTransaction, Transaction, TxEip1559, TxEnvelope, TxType, TypedTransaction};
 use alloy_primitives::TxHash;
 use alloy_provider::fillers::{
     BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller, WalletFiller,
 };
 use alloy_provider::{Identity, RootProvider, WalletProvider};
+use alloy_rlp::Decodable;
 use async_trait::async_trait;
 use iota_sdk::crypto::keys::bip39::Mnemonic;
 use iota_sdk::wallet::account::types::InclusionState;
 use log::info;
 use reqwest::Url;
-use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
-use std::ops::{Div, Mul};
+use std::ops::RangeInclusive;
 use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+/// The number of decimals of the native currency, 1 ETH = 10^18 wei
+const ETH_DECIMALS: u8 = 18;
 
 const WEI_TO_ETH_DIVISOR: CryptoAmount = unsafe { CryptoAmount::new_unchecked(dec!(1_000_000_000_000_000_000)) }; // SAFETY: the value is non-negative
 
+/// The maximum number of blocks walked in a single history scan. This bounds the amount of data
+/// fetched per call, the remaining blocks are scanned by subsequent calls.
+const MAX_BLOCKS_PER_SCAN: u64 = 500;
+
+/// The number of blocks requested in a single JSON-RPC batch while walking blocks.
+const BLOCKS_PER_BATCH: usize = 50;
+
+/// The maximum block range queried with `eth_getLogs` in a single history scan. Log queries are much
+/// cheaper than walking blocks, but most nodes limit the range of a single query.
+const MAX_LOG_BLOCKS_PER_SCAN: u64 = 5_000;
+
+/// The percentage by which the fees of a replacement transaction are increased. Most nodes only accept
+/// a transaction with the same nonce if both fees are increased by at least 10%.
+const REPLACEMENT_FEE_BUMP_PERCENT: u128 = 15;
+
+/// The gas used by a plain transfer without data, which is what a cancel transaction is.
+const CANCEL_GAS_LIMIT: u64 = 21_000;
+
 // Type alias for the crazy long type used as Provider with the default fillers (Gas, Nonce,
 // ChainId) and Wallet
 type ProviderType = FillProvider<
@@ -50,57 +84,170 @@ pub struct WalletImplEth {
     /// ChainId for the transactions.
     chain_id: u64,
 
-    /// Rpc client, contains the Signer based on the mnemonic.
+    /// The addresses of the wallet keyed by their address index. Index 0 is the default address.
+    addresses: BTreeMap<u32, Address>,
+
+    /// Rpc client, contains a Signer for each address based on the mnemonic.
     provider: ProviderType,
+
+    /// Whether the wallet only knows the addresses and cannot sign transactions.
+    watch_only: bool,
+}
+
+/// Derives the signer at `index` from the mnemonic `phrase` using the wordlist `W` of the mnemonic.
+fn mnemonic_signer<W: Wordlist>(
+    phrase: &str,
+    passphrase: Option<&MnemonicPassphrase>,
+    index: u32,
+) -> std::result::Result<PrivateKeySigner, LocalSignerError> {
+    let mut builder = MnemonicBuilder::<W>::default().phrase(phrase.to_string());
+    if let Some(passphrase) = passphrase {
+        builder = builder.password(passphrase.as_str());
+    }
+    builder.index(index).and_then(|builder| builder.build())
 }
 
 impl WalletImplEth {
-    /// Creates a new [`WalletImplEth`] from the specified [`Mnemonic`].
-    pub fn new(mnemonic: Mnemonic, node_urls: Vec<String>, chain_id: u64) -> Result<Self> {
-        // Ase mnemonic to create a Signer
+    /// Creates a new [`WalletImplEth`] from the specified [`Mnemonic`] and optional BIP-39 passphrase. Next to the
+    /// default address with index 0, the addresses with the given `address_indices` are derived.
+    pub fn new(
+        mnemonic: Mnemonic,
+        passphrase: Option<&MnemonicPassphrase>,
+        node_urls: Vec<String>,
+        chain_id: u64,
+        address_indices: &[u32],
+    ) -> Result<Self> {
+        // Use mnemonic to create a Signer for each address
         // Child key at derivation path: m/44'/60'/0'/0/{index}.
-        let wallet = MnemonicBuilder::<English>::default()
-            .phrase(mnemonic.as_ref().to_string())
-            .index(0)?
-            // // Use this if your mnemonic is encrypted.
-            // .password(password)
-            .build()?;
-
-        // construct the ProviderBuilder
-        let url =
-            Url::parse(&node_urls[0]).map_err(|e| WalletError::Parse(format!("could not parse the url: {e:?}")))?;
-
-        // build a Provider that has the default fillers for GasEstimation, Nonce providing and chain_id fetcher
-        let http_provider = ProviderBuilder::<_, _, Ethereum>::new()
-            .wallet(wallet.clone())
-            .on_http(url);
+        // The seed is derived from the NFKD normalized mnemonic, as required by BIP-39.
+        let (language, mnemonic) = normalize_mnemonic(mnemonic.as_ref())?;
+        let phrase = mnemonic.as_ref();
+        let derive_signer = |index: u32| match language {
+            MnemonicLanguage::English => mnemonic_signer::<English>(phrase, passphrase, index),
+            MnemonicLanguage::ChineseSimplified => mnemonic_signer::<ChineseSimplified>(phrase, passphrase, index),
+            MnemonicLanguage::ChineseTraditional => mnemonic_signer::<ChineseTraditional>(phrase, passphrase, index),
+            MnemonicLanguage::Czech => mnemonic_signer::<Czech>(phrase, passphrase, index),
+            MnemonicLanguage::French => mnemonic_signer::<French>(phrase, passphrase, index),
+            MnemonicLanguage::Italian => mnemonic_signer::<Italian>(phrase, passphrase, index),
+            MnemonicLanguage::Japanese => mnemonic_signer::<Japanese>(phrase, passphrase, index),
+            MnemonicLanguage::Korean => mnemonic_signer::<Korean>(phrase, passphrase, index),
+            MnemonicLanguage::Portuguese => mnemonic_signer::<Portuguese>(phrase, passphrase, index),
+            MnemonicLanguage::Spanish => mnemonic_signer::<Spanish>(phrase, passphrase, index),
+        };
+
+        let default_signer = derive_signer(0)?;
+        let mut addresses = BTreeMap::from([(0, default_signer.address())]);
+        let mut wallet = EthereumWallet::new(default_signer);
+        for &index in address_indices {
+            if addresses.contains_key(&index) {
+                continue;
+            }
+            let signer = derive_signer(index)?;
+            addresses.insert(index, signer.address());
+            wallet.register_signer(signer);
+        }
 
         info!("Wallet creation successful");
 
         Ok(WalletImplEth {
             chain_id,
-            provider: http_provider,
+            addresses,
+            provider: Self::build_provider(wallet, &node_urls)?,
+            watch_only: false,
         })
     }
 
-    fn convert_wei_to_eth(value_wei: CryptoAmount) -> CryptoAmount {
-        value_wei.div(WEI_TO_ETH_DIVISOR)
+    /// Creates a new watch-only [`WalletImplEth`] from the addresses of a wallet keyed by their address index. It
+    /// can query balances and transactions of the addresses, but rejects signing with [`WalletError::WatchOnly`].
+    pub fn new_watch_only(addresses: BTreeMap<u32, Address>, node_urls: Vec<String>, chain_id: u64) -> Result<Self> {
+        if addresses.is_empty() {
+            return Err(WalletError::EmptyWalletAddress);
+        }
+
+        // The provider requires a wallet, so a random signer is registered. It is never used since signing is
+        // rejected before the provider is asked to sign.
+        let wallet = EthereumWallet::new(PrivateKeySigner::random());
+
+        info!("Watch-only wallet creation successful");
+
+        Ok(WalletImplEth {
+            chain_id,
+            addresses,
+            provider: Self::build_provider(wallet, &node_urls)?,
+            watch_only: true,
+        })
     }
 
-    fn convert_eth_to_wei(value_eth: CryptoAmount) -> CryptoAmount {
-        value_eth.mul(WEI_TO_ETH_DIVISOR)
+    /// Builds a Provider that has the default fillers for GasEstimation, Nonce providing and chain_id fetcher.
+    fn build_provider(wallet: EthereumWallet, node_urls: &[String]) -> Result<ProviderType> {
+        let node_url = node_urls
+            .first()
+            .ok_or_else(|| WalletError::Parse("no node url configured".to_string()))?;
+        let url = Url::parse(node_url).map_err(|e| WalletError::Parse(format!("could not parse the url: {e:?}")))?;
+
+        Ok(ProviderBuilder::<_, _, Ethereum>::new().wallet(wallet).on_http(url))
     }
 
-    fn convert_alloy_256_to_crypto_amount(v: alloy_primitives::Uint<256, 4>) -> Result<CryptoAmount> {
-        let value_i128 = v.to::<i128>();
-        let value_decimal = Decimal::from_i128(value_i128);
+    /// Returns the default address of the wallet with index 0.
+    fn default_address(&self) -> Result<Address> {
+        self.addresses
+            .values()
+            .next()
+            .copied()
+            .ok_or(WalletError::EmptyWalletAddress)
+    }
 
-        let Some(result_decimal) = value_decimal else {
-            return Err(WalletError::ConversionError(format!(
-                "could not convert alloy 256 to decimal: {v:?}"
-            )));
+    /// Rejects signing for watch-only wallets.
+    fn ensure_can_sign(&self) -> Result<()> {
+        if self.watch_only {
+            return Err(WalletError::WatchOnly);
+        }
+        Ok(())
+    }
+
+    /// Parses the address and checks that it belongs to the wallet.
+    fn own_address(&self, address: &str) -> Result<Address> {
+        let address = Address::from_str(address)?;
+        if self.addresses.values().any(|a| *a == address) {
+            Ok(address)
+        } else {
+            Err(WalletError::UnknownAddress(address.to_string()))
+        }
+    }
+
+    /// Whether the address belongs to the wallet.
+    fn is_own_address(&self, address: &Address) -> bool {
+        self.addresses.values().any(|a| a == address)
+    }
+
+    fn convert_wei_to_eth(value_wei: CryptoAmount) -> CryptoAmount {
+        value_wei / WEI_TO_ETH_DIVISOR
+    }
+
+    /// Converts a raw integer amount (e.g. wei) into a [`CryptoAmount`] with the given number of decimals.
+    /// Fractional digits exceeding the precision of [`Decimal`] are rounded, the integer part is never truncated.
+    fn convert_alloy_256_to_crypto_amount(v: alloy_primitives::Uint<256, 4>, decimals: u8) -> Result<CryptoAmount> {
+        let digits = v.to_string();
+        let decimals = usize::from(decimals);
+
+        // place the decimal point in the string representation to avoid overflowing any intermediate integer type
+        let value = match digits.len().checked_sub(decimals) {
+            Some(0) | None => format!("0.{digits:0>decimals$}"),
+            Some(n) => {
+                let (integer, fraction) = digits.split_at(n);
+                let fraction = fraction.trim_end_matches('0');
+                if fraction.is_empty() {
+                    integer.to_string()
+                } else {
+                    format!("{integer}.{fraction}")
+                }
+            }
         };
 
+        let result_decimal = Decimal::from_str(&value).map_err(|e| {
+            WalletError::ConversionError(format!("could not convert alloy 256 {v:?} to decimal: {e:?}"))
+        })?;
+
         CryptoAmount::try_from(result_decimal).map_err(|e| {
             WalletError::ConversionError(format!(
                 "could not convert decimal {result_decimal:?} to crypto amount: {e:?}"
@@ -108,12 +255,23 @@ impl WalletImplEth {
         })
     }
 
-    fn convert_crypto_amount_to_u256(v: CryptoAmount) -> Result<alloy_primitives::U256> {
-        let value_decimal = v.inner();
-        let value_i128: i128 = value_decimal.try_into()?;
-        let value = U256::from(value_i128);
+    /// Converts a [`CryptoAmount`] into the raw integer amount (e.g. wei) with the given number of decimals.
+    fn convert_crypto_amount_to_u256(v: CryptoAmount, decimals: u8) -> Result<alloy_primitives::U256> {
+        let value_decimal = v.inner().normalize();
+
+        let Some(exponent) = u32::from(decimals).checked_sub(value_decimal.scale()) else {
+            return Err(WalletError::InvalidTransactionAmount(format!(
+                "{value_decimal} has more than {decimals} decimals"
+            )));
+        };
+
+        let mantissa = u128::try_from(value_decimal.mantissa())
+            .map_err(|e| WalletError::ConversionError(format!("could not convert {value_decimal} to u256: {e:?}")))?;
 
-        Ok(value)
+        U256::from(10)
+            .checked_pow(U256::from(exponent))
+            .and_then(|factor| U256::from(mantissa).checked_mul(factor))
+            .ok_or_else(|| WalletError::ConversionError(format!("{value_decimal} does not fit into u256")))
     }
 
     /// Helper function that prepares the [`TransactionRequest`] so that we can also use the same logic for gas estimation.
@@ -122,11 +280,11 @@ impl WalletImplEth {
             address_to,
             amount,
             data,
+            address_from,
         } = intent;
 
         let addr_to = Address::from_str(address_to)?;
-        let amount_wei = Self::convert_eth_to_wei(*amount);
-        let amount_wei_u256 = Self::convert_crypto_amount_to_u256(amount_wei)?;
+        let amount_wei_u256 = Self::convert_crypto_amount_to_u256(*amount, ETH_DECIMALS)?;
 
         let mut tx = TransactionRequest::default()
             .with_to(addr_to)
@@ -138,11 +296,18 @@ impl WalletImplEth {
             tx.set_input(data.to_owned());
         }
 
+        // without a sender, the provider signs with the default address
+        if let Some(address_from) = address_from {
+            tx.set_from(self.own_address(address_from)?);
+        }
+
         Ok(tx)
     }
 
     /// Submit the [`TransactionRequest`] and wait for it to be included in a block.
     async fn submit_transaction_request(&self, tx_request: TransactionRequest) -> Result<String> {
+        self.ensure_can_sign()?;
+
         // Send the transaction, the nonce is automatically managed by the provider.
         let pending_tx = self.provider.send_transaction(tx_request).await?;
 
@@ -157,6 +322,195 @@ impl WalletImplEth {
         Ok(receipt.transaction_hash.to_string())
     }
 
+    /// Fill in the sender, nonce, fees and gas limit of the [`TransactionRequest`] and sign it locally without
+    /// broadcasting it. The pending nonce of the sender is used, but at least `min_nonce`.
+    async fn sign_transaction_request(
+        &self,
+        tx_request: TransactionRequest,
+        min_nonce: Option<u64>,
+    ) -> Result<PendingTransaction> {
+        self.ensure_can_sign()?;
+        let tx_request = self.fill_transaction_request(tx_request, min_nonce).await?;
+        self.sign_filled_transaction_request(tx_request).await
+    }
+
+    /// Fill in the sender, nonce, fees and gas limit of the [`TransactionRequest`] and export it as unsigned
+    /// EIP-1559 transaction. This only needs the addresses, so it also works for watch-only wallets.
+    async fn prepare_unsigned_transaction_request(
+        &self,
+        tx_request: TransactionRequest,
+        min_nonce: Option<u64>,
+    ) -> Result<UnsignedTransaction> {
+        let tx_request = self.fill_transaction_request(tx_request, min_nonce).await?;
+        let from = match tx_request.from {
+            Some(from) => from,
+            None => self.default_address()?,
+        };
+
+        let Ok(TypedTransaction::Eip1559(tx)) = tx_request.build_typed_tx() else {
+            return Err(WalletError::InvalidTransaction(String::from(
+                "could not build an EIP-1559 transaction",
+            )));
+        };
+
+        info!("Prepared unsigned transaction from {from} with nonce {}", tx.nonce);
+
+        Ok(UnsignedTransaction {
+            from: from.to_string(),
+            raw_transaction: tx.encoded_for_signing(),
+        })
+    }
+
+    /// Set the sender, chain id, nonce, fees and gas limit of the [`TransactionRequest`] from the network.
+    async fn fill_transaction_request(
+        &self,
+        mut tx_request: TransactionRequest,
+        min_nonce: Option<u64>,
+    ) -> Result<TransactionRequest> {
+        let from = match tx_request.from {
+            Some(from) => from,
+            None => self.default_address()?,
+        };
+        tx_request.set_from(from);
+        tx_request.set_chain_id(self.chain_id);
+
+        let pending_nonce = self.provider.get_transaction_count(from).pending().await?;
+        tx_request.set_nonce(pending_nonce.max(min_nonce.unwrap_or_default()));
+
+        let fees = self.provider.estimate_eip1559_fees().await?;
+        tx_request.set_max_fee_per_gas(fees.max_fee_per_gas);
+        tx_request.set_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+        let gas_limit = self.provider.estimate_gas(tx_request.clone()).await?;
+        tx_request.set_gas_limit(gas_limit);
+
+        Ok(tx_request)
+    }
+
+    /// Sign a [`TransactionRequest`] that has all fields set with the signer of its sender.
+    async fn sign_filled_transaction_request(&self, tx_request: TransactionRequest) -> Result<PendingTransaction> {
+        self.ensure_can_sign()?;
+
+        let from = match tx_request.from {
+            Some(from) => from,
+            None => self.default_address()?,
+        };
+
+        let envelope = tx_request
+            .build(self.provider.wallet())
+            .await
+            .map_err(|e| WalletError::InvalidTransaction(format!("could not sign transaction: {e}")))?;
+
+        info!(
+            "Signed transaction {} with nonce {}",
+            envelope.tx_hash(),
+            envelope.nonce()
+        );
+
+        Ok(Self::to_pending_transaction(&envelope, from))
+    }
+
+    /// Decode an unsigned EIP-1559 transaction exported by [`WalletUser::prepare_unsigned_transaction`].
+    fn decode_unsigned_transaction(raw_transaction: &[u8]) -> Result<TxEip1559> {
+        let Some((&ty, mut fields)) = raw_transaction.split_first() else {
+            return Err(WalletError::InvalidTransaction(String::from(
+                "unsigned transaction is empty",
+            )));
+        };
+        if ty != u8::from(TxType::Eip1559) {
+            return Err(WalletError::InvalidTransaction(format!(
+                "unsigned transaction has type {ty}, expected an EIP-1559 transaction"
+            )));
+        }
+
+        let tx = TxEip1559::decode(&mut fields)
+            .map_err(|e| WalletError::InvalidTransaction(format!("could not decode unsigned transaction: {e}")))?;
+        if !fields.is_empty() {
+            return Err(WalletError::InvalidTransaction(String::from(
+                "unsigned transaction has trailing bytes",
+            )));
+        }
+
+        Ok(tx)
+    }
+
+    /// Decode an unsigned transaction of the wallet and check that it is for the chain of the wallet.
+    fn decode_own_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<(Address, TxEip1559)> {
+        let from = self.own_address(&unsigned.from)?;
+        let tx = Self::decode_unsigned_transaction(&unsigned.raw_transaction)?;
+        if tx.chain_id != self.chain_id {
+            return Err(WalletError::InvalidTransaction(format!(
+                "transaction is for chain id {}, but the wallet is on chain id {}",
+                tx.chain_id, self.chain_id
+            )));
+        }
+        Ok((from, tx))
+    }
+
+    /// The receiver of a decoded transaction, contract creations are never prepared by the wallet.
+    fn unsigned_transaction_receiver(tx: &TxEip1559) -> Result<Address> {
+        tx.to
+            .to()
+            .copied()
+            .ok_or_else(|| WalletError::InvalidTransaction(String::from("unsigned transaction creates a contract")))
+    }
+
+    /// Collect the details of a decoded transaction to review before signing it, with the receiver and the
+    /// value of the transfer.
+    fn unsigned_transaction_details(
+        from: Address,
+        tx: &TxEip1559,
+        to: Address,
+        value: CryptoAmount,
+    ) -> Result<UnsignedTransactionDetails> {
+        let max_fee = U256::from(tx.gas_limit).saturating_mul(U256::from(tx.max_fee_per_gas));
+        Ok(UnsignedTransactionDetails {
+            from: from.to_string(),
+            to: to.to_string(),
+            value: value.inner(),
+            data: tx.input.to_vec(),
+            chain_id: tx.chain_id,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            max_fee_per_gas: tx.max_fee_per_gas,
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas,
+            max_fee: Self::convert_alloy_256_to_crypto_amount(max_fee, ETH_DECIMALS)?.inner(),
+        })
+    }
+
+    /// Convert a signed transaction into the [`PendingTransaction`] that is tracked until it is included.
+    fn to_pending_transaction(envelope: &TxEnvelope, from: Address) -> PendingTransaction {
+        PendingTransaction {
+            transaction_hash: envelope.tx_hash().to_string(),
+            from: from.to_string(),
+            nonce: envelope.nonce(),
+            gas_limit: envelope.gas_limit(),
+            max_fee_per_gas: envelope.max_fee_per_gas(),
+            max_priority_fee_per_gas: envelope.max_priority_fee_per_gas().unwrap_or_default(),
+            raw_transaction: envelope.encoded_2718(),
+            created_at: chrono::Utc::now().timestamp(),
+            replaces: Vec::new(),
+        }
+    }
+
+    /// Increase the fees of a transaction so that it can replace the transaction with the same nonce. The new fees
+    /// are at least the current estimation of the network, so a stuck transaction is not replaced by another
+    /// underpriced one.
+    fn bump_fees(max_fee_per_gas: u128, max_priority_fee_per_gas: u128, current: Eip1559Estimation) -> (u128, u128) {
+        let bump = |fee: u128| {
+            fee.saturating_mul(100 + REPLACEMENT_FEE_BUMP_PERCENT)
+                .div_ceil(100)
+                .max(fee.saturating_add(1))
+        };
+
+        let max_priority_fee_per_gas = bump(max_priority_fee_per_gas).max(current.max_priority_fee_per_gas);
+        let max_fee_per_gas = bump(max_fee_per_gas)
+            .max(current.max_fee_per_gas)
+            .max(max_priority_fee_per_gas);
+
+        (max_fee_per_gas, max_priority_fee_per_gas)
+    }
+
     async fn estimate_transaction_request_gas(&self, tx_request: TransactionRequest) -> Result<GasCostEstimation> {
         // Returns the estimated gas cost for the underlying transaction to be executed
         let gas_limit = self.provider.estimate_gas(tx_request).await?;
@@ -173,6 +527,163 @@ impl WalletImplEth {
             gas_limit,
         })
     }
+
+    /// Get the [`InclusionState`] of a transaction based on its receipt.
+    async fn get_inclusion_state(&self, transaction_hash: TxHash) -> Result<InclusionState> {
+        let receipt = self.provider.get_transaction_receipt(transaction_hash).await?;
+        Ok(match receipt.map(|r| r.inner.is_success()) {
+            Some(true) => InclusionState::Confirmed,
+            Some(false) => InclusionState::Conflicting,
+            None => InclusionState::Pending,
+        })
+    }
+
+    /// Maps a native transfer to a [`WalletTxInfo`].
+    fn to_wallet_tx_info(
+        tx: &alloy::rpc::types::Transaction,
+        receiver: Address,
+        incoming: bool,
+        timestamp: Option<u64>,
+        status: InclusionState,
+    ) -> Result<WalletTxInfo> {
+        let value_wei_crypto_amount = Self::convert_alloy_256_to_crypto_amount(tx.value(), 0)?;
+        let value_eth_crypto_amount = Self::convert_wei_to_eth(value_wei_crypto_amount);
+
+        let value_eth_f64: f64 = value_eth_crypto_amount.inner().try_into()?; // TODO: WalletTxInfo f64 -> Decimal ? maybe
+
+        Ok(WalletTxInfo {
+            date: timestamp.map(|n| n.to_string()).unwrap_or_else(String::new),
+            block_id: tx.block_number.map(|n| n.to_string()),
+            transaction_id: tx.tx_hash().to_string(),
+            log_index: None,
+            receiver: receiver.to_string(),
+            incoming,
+            amount: value_eth_f64,
+            network: "ETH".to_string(),
+            status: format!("{:?}", status),
+            explorer_url: None,
+        })
+    }
+
+    /// Fetches the blocks with their full transactions. The blocks are requested in JSON-RPC batches of
+    /// [`BLOCKS_PER_BATCH`] blocks, blocks unknown to the node are returned as `None`.
+    async fn get_blocks(&self, blocks: RangeInclusive<u64>) -> Result<Vec<Option<Block>>> {
+        let numbers = blocks.collect::<Vec<_>>();
+        let mut result = Vec::with_capacity(numbers.len());
+
+        for chunk in numbers.chunks(BLOCKS_PER_BATCH) {
+            let mut batch = BatchRequest::new(self.provider.client());
+            let mut waiters = Vec::with_capacity(chunk.len());
+            for &number in chunk {
+                waiters.push(
+                    batch.add_call::<_, Option<Block>>(
+                        "eth_getBlockByNumber",
+                        &(BlockNumberOrTag::Number(number), true),
+                    )?,
+                );
+            }
+            batch.send().await?;
+
+            for waiter in waiters {
+                result.push(waiter.await?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Collects the native transfers of the block sent from or to the wallet addresses.
+    async fn collect_transfers(&self, block: &Block, transactions: &mut Vec<WalletTxInfo>) -> Result<()> {
+        for tx in block.transactions.txns() {
+            // only native transfers are of interest here, contract creations and calls without value are skipped
+            let Some(receiver) = tx.to() else {
+                continue;
+            };
+            if tx.value().is_zero() {
+                continue;
+            }
+
+            let incoming = self.is_own_address(&receiver);
+            if !incoming && !self.is_own_address(&tx.from()) {
+                continue;
+            }
+
+            let status = self.get_inclusion_state(tx.tx_hash()).await?;
+            transactions.push(Self::to_wallet_tx_info(
+                tx,
+                receiver,
+                incoming,
+                Some(block.header.timestamp),
+                status,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Walks the blocks following `last_scanned_block` and collects all native transfers sent from or to the
+    /// wallet addresses. At most [`MAX_BLOCKS_PER_SCAN`] blocks are walked per scan, blocks not needed to
+    /// catch up with the chain are used to backfill the history before `first_scanned_block`.
+    async fn scan_blocks(
+        &self,
+        last_scanned_block: Option<u64>,
+        first_scanned_block: Option<u64>,
+    ) -> Result<WalletTxScan> {
+        let latest_block = self.provider.get_block_number().await?;
+
+        let forward = match last_scanned_block {
+            Some(block) => block.saturating_add(1)..=latest_block.min(block.saturating_add(MAX_BLOCKS_PER_SCAN)),
+            None => latest_block.saturating_sub(MAX_BLOCKS_PER_SCAN - 1)..=latest_block,
+        };
+
+        let mut scan = WalletTxScan {
+            transactions: Vec::new(),
+            last_scanned_block,
+            // a new history is started with the most recent blocks, the backfill cursor is set below
+            first_scanned_block: last_scanned_block.and(first_scanned_block),
+        };
+
+        let mut remaining = MAX_BLOCKS_PER_SCAN;
+        if !forward.is_empty() {
+            remaining -= forward.end() - forward.start() + 1;
+
+            for (number, block) in forward.clone().zip(self.get_blocks(forward).await?) {
+                let Some(block) = block else {
+                    // the node does not know this block yet, continue from here with the next scan
+                    log::debug!("Block {number} not available, stopping the scan");
+                    break;
+                };
+                self.collect_transfers(&block, &mut scan.transactions).await?;
+
+                scan.last_scanned_block = Some(number);
+                if last_scanned_block.is_none() && scan.first_scanned_block.is_none() {
+                    scan.first_scanned_block = Some(number);
+                }
+            }
+        }
+
+        // an existing history is backfilled from the newest to the oldest block, moving the cursor block by block
+        if let Some(first_block) = last_scanned_block.and(first_scanned_block).filter(|b| *b > 0) {
+            let backfill = first_block.saturating_sub(remaining)..=first_block - 1;
+            for (number, block) in backfill.clone().zip(self.get_blocks(backfill).await?).rev() {
+                let Some(block) = block else {
+                    // pruned nodes do not keep old blocks, there is nothing more to backfill from this node
+                    log::debug!("Block {number} not available, stopping the backfill");
+                    break;
+                };
+                self.collect_transfers(&block, &mut scan.transactions).await?;
+                scan.first_scanned_block = Some(number);
+            }
+        }
+
+        info!(
+            "Scanned blocks {:?} to {:?} and found {} transactions",
+            scan.first_scanned_block,
+            scan.last_scanned_block,
+            scan.transactions.len()
+        );
+
+        Ok(scan)
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -180,32 +691,64 @@ impl WalletImplEth {
 #[cfg_attr(test, mockall::automock)]
 impl WalletUser for WalletImplEth {
     async fn get_address(&self) -> Result<String> {
-        Ok(self.provider.default_signer_address().to_string())
+        Ok(self.default_address()?.to_string())
+    }
+
+    async fn get_addresses(&self) -> Result<Vec<String>> {
+        Ok(self.addresses.values().map(Address::to_string).collect())
     }
 
     async fn get_balance(&self) -> Result<CryptoAmount> {
         let mut total = U256::ZERO;
-        for addr in self.provider.signer_addresses() {
+        for &addr in self.addresses.values() {
             let balance = self.provider.get_balance(addr).await?;
             log::info!("Balance for address {} = {}", addr, balance);
             total += balance;
         }
 
-        let balance_wei_crypto_amount = Self::convert_alloy_256_to_crypto_amount(total)?;
+        let balance_wei_crypto_amount = Self::convert_alloy_256_to_crypto_amount(total, 0)?;
         let balance_eth_crypto_amount = Self::convert_wei_to_eth(balance_wei_crypto_amount);
         Ok(balance_eth_crypto_amount)
     }
 
+    async fn get_address_balance(&self, address: &str) -> Result<CryptoAmount> {
+        let address = self.own_address(address)?;
+        let balance = self.provider.get_balance(address).await?;
+        let balance_wei_crypto_amount = Self::convert_alloy_256_to_crypto_amount(balance, 0)?;
+        Ok(Self::convert_wei_to_eth(balance_wei_crypto_amount))
+    }
+
     async fn send_amount(&self, intent: &TransactionIntent) -> Result<String> {
         let tx_request = self.prepare_transaction(intent)?;
         self.submit_transaction_request(tx_request).await
     }
 
-    // The network does not provide information about historical transactions
-    // (they can be retrieved manually, but this is a time-consuming process),
-    // so the handling of this method is implemented at the SDK level.
-    async fn get_wallet_tx_list(&self, _start: usize, _limit: usize) -> Result<WalletTxInfoList> {
-        Err(WalletError::WalletFeatureNotImplemented)
+    // The network does not provide an index of historical transactions, so only the most recent
+    // blocks are scanned here. The SDK persists the scan progress and builds up the full history
+    // over time using `scan_wallet_tx`.
+    async fn get_wallet_tx_list(&self, start: usize, limit: usize) -> Result<WalletTxInfoList> {
+        let scan = self.scan_blocks(None, None).await?;
+
+        let mut history = WalletTxHistory::default();
+        history.merge(scan.transactions);
+
+        // Extract transactions for the current page based on the start index and page size
+        let transactions = history
+            .transactions
+            .into_iter()
+            .skip(start * limit)
+            .take(limit)
+            .collect();
+
+        Ok(WalletTxInfoList { transactions })
+    }
+
+    async fn scan_wallet_tx(
+        &self,
+        last_scanned_block: Option<u64>,
+        first_scanned_block: Option<u64>,
+    ) -> Result<WalletTxScan> {
+        self.scan_blocks(last_scanned_block, first_scanned_block).await
     }
 
     async fn get_wallet_tx(&self, transaction_id: &str) -> Result<WalletTxInfo> {
@@ -226,38 +769,46 @@ impl WalletUser for WalletImplEth {
             None
         };
 
-        let my_address = self.get_address().await?;
-
         let Some(receiver_address) = tx.to() else {
             return Err(WalletError::InvalidTransaction(
                 "Transaction has no to address".to_string(),
             ));
         };
 
-        let is_transaction_incoming = receiver_address.to_string() == my_address;
+        let is_transaction_incoming = self.is_own_address(&receiver_address);
 
-        let receipt = self.provider.get_transaction_receipt(transaction_hash).await?;
-        let status = match receipt.map(|r| r.inner.is_success()) {
-            Some(true) => InclusionState::Confirmed,
-            Some(false) => InclusionState::Conflicting,
-            None => InclusionState::Pending,
+        let status = self.get_inclusion_state(transaction_hash).await?;
+
+        let mut info = Self::to_wallet_tx_info(&tx, receiver_address, is_transaction_incoming, date, status)?;
+        info.transaction_id = transaction_id.to_string();
+        Ok(info)
+    }
+
+    async fn get_transaction_confirmation(&self, tx_id: &str) -> Result<TransactionConfirmation> {
+        let transaction_hash = TxHash::from_str(tx_id)?;
+        let Some(receipt) = self.provider.get_transaction_receipt(transaction_hash).await? else {
+            return Ok(TransactionConfirmation {
+                inclusion_state: InclusionState::Pending,
+                confirmations: Some(0),
+            });
         };
 
-        let balance_wei_crypto_amount = Self::convert_alloy_256_to_crypto_amount(tx.value())?;
-        let value_eth_crypto_amount = Self::convert_wei_to_eth(balance_wei_crypto_amount);
+        // a reverted transaction is included as well, but it will never succeed
+        let inclusion_state = if receipt.inner.is_success() {
+            InclusionState::Confirmed
+        } else {
+            InclusionState::Conflicting
+        };
 
-        let value_eth_f64: f64 = value_eth_crypto_amount.inner().try_into()?; // TODO: WalletTxInfo f64 -> Decimal ? maybe
+        let latest_block = self.provider.get_block_number().await?;
+        let confirmations = receipt
+            .block_number
+            .map(|block| latest_block.saturating_sub(block) + 1)
+            .unwrap_or_default();
 
-        Ok(WalletTxInfo {
-            date: date.map(|n| n.to_string()).unwrap_or_else(String::new),
-            block_id: tx.block_number.map(|n| n.to_string()),
-            transaction_id: transaction_id.to_string(),
-            receiver: receiver_address.to_string(),
-            incoming: is_transaction_incoming,
-            amount: value_eth_f64,
-            network: "ETH".to_string(),
-            status: format!("{:?}", status),
-            explorer_url: None,
+        Ok(TransactionConfirmation {
+            inclusion_state,
+            confirmations: Some(confirmations),
         })
     }
 
@@ -265,6 +816,124 @@ impl WalletUser for WalletImplEth {
         let tx_request = self.prepare_transaction(intent)?;
         self.estimate_transaction_request_gas(tx_request).await
     }
+
+    async fn sign_transaction(&self, intent: &TransactionIntent, min_nonce: Option<u64>) -> Result<PendingTransaction> {
+        let tx_request = self.prepare_transaction(intent)?;
+        self.sign_transaction_request(tx_request, min_nonce).await
+    }
+
+    async fn sign_replacement(
+        &self,
+        pending: &PendingTransaction,
+        replacement: TransactionReplacement,
+    ) -> Result<PendingTransaction> {
+        let from = self.own_address(&pending.from)?;
+        let original = TxEnvelope::decode_2718(&mut pending.raw_transaction.as_slice())
+            .map_err(|e| WalletError::InvalidTransaction(format!("could not decode pending transaction: {e}")))?;
+
+        let mut tx_request = TransactionRequest::default()
+            .with_from(from)
+            .with_chain_id(self.chain_id)
+            .with_nonce(original.nonce());
+
+        match replacement {
+            TransactionReplacement::SpeedUp => {
+                tx_request.set_kind(original.kind());
+                tx_request.set_value(original.value());
+                tx_request.set_input(original.input().clone());
+                tx_request.set_gas_limit(original.gas_limit());
+            }
+            TransactionReplacement::Cancel => {
+                tx_request.set_to(from);
+                tx_request.set_value(U256::ZERO);
+                tx_request.set_gas_limit(CANCEL_GAS_LIMIT);
+            }
+        }
+
+        let current = self.provider.estimate_eip1559_fees().await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) = Self::bump_fees(
+            original.max_fee_per_gas(),
+            original.max_priority_fee_per_gas().unwrap_or_default(),
+            current,
+        );
+        tx_request.set_max_fee_per_gas(max_fee_per_gas);
+        tx_request.set_max_priority_fee_per_gas(max_priority_fee_per_gas);
+
+        self.sign_filled_transaction_request(tx_request).await
+    }
+
+    async fn broadcast_transaction(&self, pending: &PendingTransaction) -> Result<()> {
+        let pending_tx = self.provider.send_raw_transaction(&pending.raw_transaction).await?;
+        info!("Broadcast transaction {}", pending_tx.tx_hash());
+        Ok(())
+    }
+
+    async fn get_confirmed_nonce(&self, address: &str) -> Result<u64> {
+        let address = self.own_address(address)?;
+        Ok(self.provider.get_transaction_count(address).latest().await?)
+    }
+
+    async fn prepare_unsigned_transaction(
+        &self,
+        intent: &TransactionIntent,
+        min_nonce: Option<u64>,
+    ) -> Result<UnsignedTransaction> {
+        let tx_request = self.prepare_transaction(intent)?;
+        self.prepare_unsigned_transaction_request(tx_request, min_nonce).await
+    }
+
+    async fn review_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<UnsignedTransactionDetails> {
+        let (from, tx) = self.decode_own_unsigned_transaction(unsigned)?;
+        let to = Self::unsigned_transaction_receiver(&tx)?;
+        let value = Self::convert_alloy_256_to_crypto_amount(tx.value, ETH_DECIMALS)?;
+        Self::unsigned_transaction_details(from, &tx, to, value)
+    }
+
+    async fn sign_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<PendingTransaction> {
+        self.ensure_can_sign()?;
+
+        let (from, tx) = self.decode_own_unsigned_transaction(unsigned)?;
+
+        // signing only needs the key of the sender, so this works without a connection to the node
+        let envelope = NetworkWallet::<Ethereum>::sign_transaction_from(
+            self.provider.wallet(),
+            from,
+            TypedTransaction::Eip1559(tx),
+        )
+        .await
+        .map_err(|e| WalletError::InvalidTransaction(format!("could not sign transaction: {e}")))?;
+
+        info!(
+            "Signed transaction {} with nonce {} offline",
+            envelope.tx_hash(),
+            envelope.nonce()
+        );
+
+        Ok(Self::to_pending_transaction(&envelope, from))
+    }
+
+    async fn broadcast_signed_transaction(&self, raw_transaction: &[u8]) -> Result<PendingTransaction> {
+        let envelope = TxEnvelope::decode_2718(&mut &raw_transaction[..])
+            .map_err(|e| WalletError::InvalidTransaction(format!("could not decode signed transaction: {e}")))?;
+        if envelope.chain_id() != Some(self.chain_id) {
+            return Err(WalletError::InvalidTransaction(format!(
+                "transaction is for chain id {:?}, but the wallet is on chain id {}",
+                envelope.chain_id(),
+                self.chain_id
+            )));
+        }
+
+        let from = envelope
+            .recover_signer()
+            .map_err(|e| WalletError::InvalidTransaction(format!("could not recover the signer: {e}")))?;
+        if !self.is_own_address(&from) {
+            return Err(WalletError::UnknownAddress(from.to_string()));
+        }
+
+        let pending = Self::to_pending_transaction(&envelope, from);
+        self.broadcast_transaction(&pending).await?;
+        Ok(pending)
+    }
 }
 
 alloy::sol!(
@@ -273,31 +942,138 @@ alloy::sol!(
     "src/abi/erc20.json"
 );
 
+/// Symbol and decimals of an ERC20 token.
+#[derive(Debug, Clone, PartialEq)]
+struct TokenMetadata {
+    symbol: String,
+    decimals: u8,
+}
+
+/// Token metadata queried from the contracts, keyed by chain id and contract address. The metadata
+/// of a deployed token never changes, so the SDK instance keeps one cache that is shared by its
+/// short-lived wallet instances.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TokenMetadataCache(Arc<RwLock<HashMap<(u64, Address), TokenMetadata>>>);
+
+impl TokenMetadataCache {
+    fn get(&self, key: &(u64, Address)) -> Option<TokenMetadata> {
+        self.0.read().ok().and_then(|cache| cache.get(key).cloned())
+    }
+
+    fn insert(&self, key: (u64, Address), metadata: TokenMetadata) {
+        if let Ok(mut cache) = self.0.write() {
+            cache.insert(key, metadata);
+        }
+    }
+}
+
 /// [`WalletUser`] implementation for ETH-ERC20
 #[derive(Debug)]
 pub struct WalletImplEthErc20 {
     inner: WalletImplEth,
     contract_address: Address,
+
+    /// Symbol of the token from the network configuration, queried from the contract if not set.
+    token_symbol: Option<String>,
+
+    /// Decimals of the token from the network configuration, queried from the contract if not set.
+    token_decimals: Option<u8>,
+
+    /// Caches the token metadata queried from the contract.
+    token_metadata_cache: TokenMetadataCache,
 }
 impl WalletImplEthErc20 {
     /// Creates a new [`WalletImplEth`] from the specified [`Mnemonic`].
-    pub fn new(mnemonic: Mnemonic, node_urls: Vec<String>, chain_id: u64, contract_address: String) -> Result<Self> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        mnemonic: Mnemonic,
+        passphrase: Option<&MnemonicPassphrase>,
+        node_urls: Vec<String>,
+        chain_id: u64,
+        contract_address: String,
+        token_symbol: Option<String>,
+        token_decimals: Option<u8>,
+        address_indices: &[u32],
+    ) -> Result<Self> {
+        Ok(Self {
+            inner: WalletImplEth::new(mnemonic, passphrase, node_urls, chain_id, address_indices)?,
+            contract_address: contract_address.parse()?,
+            token_symbol,
+            token_decimals,
+            token_metadata_cache: TokenMetadataCache::default(),
+        })
+    }
+
+    /// Creates a new watch-only [`WalletImplEthErc20`] from the addresses of a wallet keyed by their address index.
+    pub fn new_watch_only(
+        addresses: BTreeMap<u32, Address>,
+        node_urls: Vec<String>,
+        chain_id: u64,
+        contract_address: String,
+        token_symbol: Option<String>,
+        token_decimals: Option<u8>,
+    ) -> Result<Self> {
         Ok(Self {
-            inner: WalletImplEth::new(mnemonic, node_urls, chain_id)?,
+            inner: WalletImplEth::new_watch_only(addresses, node_urls, chain_id)?,
             contract_address: contract_address.parse()?,
+            token_symbol,
+            token_decimals,
+            token_metadata_cache: TokenMetadataCache::default(),
         })
     }
 
+    /// Uses the given cache for the token metadata queried from the contract instead of a cache of its own.
+    pub(crate) fn with_token_metadata_cache(mut self, cache: TokenMetadataCache) -> Self {
+        self.token_metadata_cache = cache;
+        self
+    }
+
     fn get_contract(&self) -> Erc20Contract::Erc20ContractInstance<(), &ProviderType> {
         Erc20Contract::new(self.contract_address, &self.inner.provider)
     }
 
+    /// Get the symbol and decimals of the token. Values from the network configuration take
+    /// precedence, missing values are queried from the contract once and cached.
+    async fn get_token_metadata(&self) -> Result<TokenMetadata> {
+        if let (Some(symbol), Some(decimals)) = (&self.token_symbol, self.token_decimals) {
+            return Ok(TokenMetadata {
+                symbol: symbol.clone(),
+                decimals,
+            });
+        }
+
+        let key = (self.inner.chain_id, self.contract_address);
+        let queried = match self.token_metadata_cache.get(&key) {
+            Some(metadata) => metadata,
+            None => {
+                let contract = self.get_contract();
+                let metadata = TokenMetadata {
+                    symbol: contract.symbol().call().await?._0,
+                    decimals: contract.decimals().call().await?._0,
+                };
+                info!(
+                    "Queried token metadata for contract {}: {metadata:?}",
+                    self.contract_address
+                );
+
+                self.token_metadata_cache.insert(key, metadata.clone());
+                metadata
+            }
+        };
+
+        Ok(TokenMetadata {
+            symbol: self.token_symbol.clone().unwrap_or(queried.symbol),
+            decimals: self.token_decimals.unwrap_or(queried.decimals),
+        })
+    }
+
     /// Helper function that prepares the [`TransactionRequest`] so that we can also use the same logic for gas estimation.
-    fn prepare_transaction(&self, intent: &TransactionIntent) -> Result<TransactionRequest> {
+    async fn prepare_transaction(&self, intent: &TransactionIntent) -> Result<TransactionRequest> {
         let TransactionIntent {
             address_to,
             amount,
             data,
+            address_from,
         } = intent;
 
         if data.as_ref().is_some_and(|d| !d.is_empty()) {
@@ -306,29 +1082,161 @@ impl WalletImplEthErc20 {
         }
 
         let addr_to = Address::from_str(address_to)?;
-        let amount_wei = WalletImplEth::convert_eth_to_wei(*amount);
-        let amount_wei_u256 = WalletImplEth::convert_crypto_amount_to_u256(amount_wei)?;
+        let decimals = self.get_token_metadata().await?.decimals;
+        let amount_u256 = WalletImplEth::convert_crypto_amount_to_u256(*amount, decimals)?;
 
         let contract = self.get_contract();
 
         // create a TransactionReqeust encoding the contract call
-        Ok(contract.transfer(addr_to, amount_wei_u256).into_transaction_request())
-    }
-}
+        let mut tx = contract.transfer(addr_to, amount_u256).into_transaction_request();
 
-#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
-#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-#[cfg_attr(test, mockall::automock)]
-impl WalletUser for WalletImplEthErc20 {
-    async fn get_address(&self) -> Result<String> {
-        self.inner.get_address().await
+        // without a sender, the provider signs with the default address
+        if let Some(address_from) = address_from {
+            tx.set_from(self.inner.own_address(address_from)?);
+        }
+
+        Ok(tx)
     }
 
-    async fn get_balance(&self) -> Result<CryptoAmount> {
-        let contract = self.get_contract();
+    /// Queries the `Transfer` events of the token contract in the blocks following `last_scanned_block` where a
+    /// wallet address is either the sender or the receiver. At most [`MAX_LOG_BLOCKS_PER_SCAN`] blocks are
+    /// queried per scan, blocks not needed to catch up with the chain are used to backfill the history before
+    /// `first_scanned_block`.
+    async fn scan_transfer_logs(
+        &self,
+        last_scanned_block: Option<u64>,
+        first_scanned_block: Option<u64>,
+    ) -> Result<WalletTxScan> {
+        let latest_block = self.inner.provider.get_block_number().await?;
+
+        let forward = match last_scanned_block {
+            Some(block) => block.saturating_add(1)..=latest_block.min(block.saturating_add(MAX_LOG_BLOCKS_PER_SCAN)),
+            None => latest_block.saturating_sub(MAX_LOG_BLOCKS_PER_SCAN - 1)..=latest_block,
+        };
+
+        let mut scan = WalletTxScan {
+            transactions: Vec::new(),
+            last_scanned_block: last_scanned_block.or(Some(latest_block)),
+            first_scanned_block: last_scanned_block.and(first_scanned_block),
+        };
+
+        let mut remaining = MAX_LOG_BLOCKS_PER_SCAN;
+        if !forward.is_empty() {
+            remaining -= forward.end() - forward.start() + 1;
+            scan.transactions = self.get_transfers(&forward).await?;
+            scan.last_scanned_block = Some(*forward.end());
+            if last_scanned_block.is_none() {
+                scan.first_scanned_block = Some(*forward.start());
+            }
+        }
+
+        // an existing history is backfilled with the blocks not needed to catch up with the chain
+        if let Some(first_block) = last_scanned_block.and(first_scanned_block).filter(|b| *b > 0) {
+            let backfill = first_block.saturating_sub(remaining)..=first_block - 1;
+            if !backfill.is_empty() {
+                scan.transactions.extend(self.get_transfers(&backfill).await?);
+                scan.first_scanned_block = Some(*backfill.start());
+            }
+        }
+
+        info!(
+            "Scanned token transfers in blocks {:?} to {:?} and found {} transactions",
+            scan.first_scanned_block,
+            scan.last_scanned_block,
+            scan.transactions.len()
+        );
+
+        Ok(scan)
+    }
+
+    /// Queries the `Transfer` events of the token contract in the given blocks where a wallet address is
+    /// either the sender or the receiver.
+    async fn get_transfers(&self, blocks: &RangeInclusive<u64>) -> Result<Vec<WalletTxInfo>> {
+        let provider = &self.inner.provider;
+        let (first_block, last_block) = (*blocks.start(), *blocks.end());
+
+        let my_addresses = self.inner.addresses.values().map(|a| a.into_word()).collect::<Vec<_>>();
+        let filter = Filter::new()
+            .address(self.contract_address)
+            .event_signature(Erc20Contract::Transfer::SIGNATURE_HASH)
+            .from_block(first_block)
+            .to_block(last_block);
+
+        // topics cannot be combined with OR across positions, so query sent and received transfers separately
+        let mut logs = provider.get_logs(&filter.clone().topic1(my_addresses.clone())).await?;
+        logs.extend(provider.get_logs(&filter.topic2(my_addresses)).await?);
+
+        let metadata = self.get_token_metadata().await?;
+
+        let mut timestamps = HashMap::new();
+        let mut seen_logs = HashSet::new();
+        let mut transactions = Vec::new();
+        for log in logs {
+            if log.removed {
+                continue;
+            }
+            let (Some(transaction_hash), Some(block_number)) = (log.transaction_hash, log.block_number) else {
+                continue;
+            };
+            // transfers to the own address are returned by both queries
+            if !seen_logs.insert((transaction_hash, log.log_index)) {
+                continue;
+            }
+
+            let timestamp = match (log.block_timestamp, timestamps.get(&block_number).copied()) {
+                (Some(timestamp), _) => Some(timestamp),
+                (None, Some(timestamp)) => timestamp,
+                (None, None) => {
+                    let timestamp = provider
+                        .get_block_by_number(BlockNumberOrTag::Number(block_number))
+                        .await?
+                        .map(|b| b.header.timestamp);
+                    timestamps.insert(block_number, timestamp);
+                    timestamp
+                }
+            };
+
+            let transfer = log.log_decode::<Erc20Contract::Transfer>()?.inner.data;
+            let amount: f64 = WalletImplEth::convert_alloy_256_to_crypto_amount(transfer.value, metadata.decimals)?
+                .inner()
+                .try_into()?; // TODO: WalletTxInfo f64 -> Decimal ? maybe
+
+            transactions.push(WalletTxInfo {
+                date: timestamp.map(|n| n.to_string()).unwrap_or_else(String::new),
+                block_id: Some(block_number.to_string()),
+                transaction_id: transaction_hash.to_string(),
+                log_index: log.log_index,
+                receiver: transfer.to.to_string(),
+                incoming: self.inner.is_own_address(&transfer.to),
+                amount,
+                network: metadata.symbol.clone(),
+                // logs are only emitted by successfully executed transactions
+                status: format!("{:?}", InclusionState::Confirmed),
+                explorer_url: None,
+            });
+        }
+
+        Ok(transactions)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(test, mockall::automock)]
+impl WalletUser for WalletImplEthErc20 {
+    async fn get_address(&self) -> Result<String> {
+        self.inner.get_address().await
+    }
+
+    async fn get_addresses(&self) -> Result<Vec<String>> {
+        self.inner.get_addresses().await
+    }
+
+    async fn get_balance(&self) -> Result<CryptoAmount> {
+        let contract = self.get_contract();
 
         let mut total = U256::ZERO;
-        for addr in self.inner.provider.signer_addresses() {
+        for &addr in self.inner.addresses.values() {
             // call the smart contract here
             let result = contract.balanceOf(addr).call().await?;
             let balance = result.balance;
@@ -337,22 +1245,48 @@ impl WalletUser for WalletImplEthErc20 {
             total += balance;
         }
 
-        // TODO: respect the decimals here
-        let balance_wei_crypto_amount = WalletImplEth::convert_alloy_256_to_crypto_amount(total)?;
-        let balance_eth_crypto_amount = WalletImplEth::convert_wei_to_eth(balance_wei_crypto_amount);
-        Ok(balance_eth_crypto_amount)
+        let decimals = self.get_token_metadata().await?.decimals;
+        WalletImplEth::convert_alloy_256_to_crypto_amount(total, decimals)
+    }
+
+    async fn get_address_balance(&self, address: &str) -> Result<CryptoAmount> {
+        let address = self.inner.own_address(address)?;
+        let balance = self.get_contract().balanceOf(address).call().await?.balance;
+
+        let decimals = self.get_token_metadata().await?.decimals;
+        WalletImplEth::convert_alloy_256_to_crypto_amount(balance, decimals)
     }
 
     async fn send_amount(&self, intent: &TransactionIntent) -> Result<String> {
-        let tx_request = self.prepare_transaction(intent)?;
+        let tx_request = self.prepare_transaction(intent).await?;
         self.inner.submit_transaction_request(tx_request).await
     }
 
-    // The network does not provide information about historical transactions
-    // (they can be retrieved manually, but this is a time-consuming process),
-    // so the handling of this method is implemented at the SDK level.
-    async fn get_wallet_tx_list(&self, _start: usize, _limit: usize) -> Result<WalletTxInfoList> {
-        Err(WalletError::WalletFeatureNotImplemented)
+    // Only the `Transfer` events of the most recent blocks are queried here. The SDK persists the
+    // scan progress and builds up the full history over time using `scan_wallet_tx`.
+    async fn get_wallet_tx_list(&self, start: usize, limit: usize) -> Result<WalletTxInfoList> {
+        let scan = self.scan_transfer_logs(None, None).await?;
+
+        let mut history = WalletTxHistory::default();
+        history.merge(scan.transactions);
+
+        // Extract transactions for the current page based on the start index and page size
+        let transactions = history
+            .transactions
+            .into_iter()
+            .skip(start * limit)
+            .take(limit)
+            .collect();
+
+        Ok(WalletTxInfoList { transactions })
+    }
+
+    async fn scan_wallet_tx(
+        &self,
+        last_scanned_block: Option<u64>,
+        first_scanned_block: Option<u64>,
+    ) -> Result<WalletTxScan> {
+        self.scan_transfer_logs(last_scanned_block, first_scanned_block).await
     }
 
     async fn get_wallet_tx(&self, transaction_id: &str) -> Result<WalletTxInfo> {
@@ -369,29 +1303,95 @@ impl WalletUser for WalletImplEthErc20 {
 
         let args = Erc20Contract::transferCall::abi_decode(tx.inner.input(), true)?;
 
-        let amount_wei_crypto_amount = WalletImplEth::convert_alloy_256_to_crypto_amount(args._value)?;
-        let value_eth_crypto_amount = WalletImplEth::convert_wei_to_eth(amount_wei_crypto_amount);
-        info.amount = value_eth_crypto_amount.inner().try_into()?; // TODO: WalletTxInfo f64 -> Decimal ? maybe
+        let metadata = self.get_token_metadata().await?;
+        let amount = WalletImplEth::convert_alloy_256_to_crypto_amount(args._value, metadata.decimals)?;
+        info.amount = amount.inner().try_into()?; // TODO: WalletTxInfo f64 -> Decimal ? maybe
 
         info.receiver = args._to.to_string();
+        info.network = metadata.symbol;
 
         Ok(info)
     }
 
+    async fn get_transaction_confirmation(&self, tx_id: &str) -> Result<TransactionConfirmation> {
+        self.inner.get_transaction_confirmation(tx_id).await
+    }
+
     async fn estimate_gas_cost(&self, intent: &TransactionIntent) -> Result<GasCostEstimation> {
-        let tx_request = self.prepare_transaction(intent)?;
+        let tx_request = self.prepare_transaction(intent).await?;
         self.inner.estimate_transaction_request_gas(tx_request).await
     }
+
+    async fn sign_transaction(&self, intent: &TransactionIntent, min_nonce: Option<u64>) -> Result<PendingTransaction> {
+        let tx_request = self.prepare_transaction(intent).await?;
+        self.inner.sign_transaction_request(tx_request, min_nonce).await
+    }
+
+    async fn sign_replacement(
+        &self,
+        pending: &PendingTransaction,
+        replacement: TransactionReplacement,
+    ) -> Result<PendingTransaction> {
+        self.inner.sign_replacement(pending, replacement).await
+    }
+
+    async fn broadcast_transaction(&self, pending: &PendingTransaction) -> Result<()> {
+        self.inner.broadcast_transaction(pending).await
+    }
+
+    async fn get_confirmed_nonce(&self, address: &str) -> Result<u64> {
+        self.inner.get_confirmed_nonce(address).await
+    }
+
+    async fn prepare_unsigned_transaction(
+        &self,
+        intent: &TransactionIntent,
+        min_nonce: Option<u64>,
+    ) -> Result<UnsignedTransaction> {
+        let tx_request = self.prepare_transaction(intent).await?;
+        self.inner
+            .prepare_unsigned_transaction_request(tx_request, min_nonce)
+            .await
+    }
+
+    async fn review_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<UnsignedTransactionDetails> {
+        let (from, tx) = self.inner.decode_own_unsigned_transaction(unsigned)?;
+        if WalletImplEth::unsigned_transaction_receiver(&tx)? != self.contract_address {
+            return Err(WalletError::InvalidTransaction(String::from(
+                "unsigned transaction does not call the token contract",
+            )));
+        }
+
+        // show the receiver and the amount of the tokens instead of the contract call
+        let args = Erc20Contract::transferCall::abi_decode(&tx.input, true)?;
+        let decimals = self.get_token_metadata().await?.decimals;
+        let value = WalletImplEth::convert_alloy_256_to_crypto_amount(args._value, decimals)?;
+        WalletImplEth::unsigned_transaction_details(from, &tx, args._to, value)
+    }
+
+    async fn sign_unsigned_transaction(&self, unsigned: &UnsignedTransaction) -> Result<PendingTransaction> {
+        self.inner.sign_unsigned_transaction(unsigned).await
+    }
+
+    async fn broadcast_signed_transaction(&self, raw_transaction: &[u8]) -> Result<PendingTransaction> {
+        self.inner.broadcast_signed_transaction(raw_transaction).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::Config;
-    use iota_sdk::crypto::keys::bip39::Mnemonic;
+    use coins_bip32::{
+        ecdsa::SigningKey,
+        xkeys::{Parent, XPriv},
+    };
+    use iota_sdk::crypto::keys::bip39::{mnemonic_to_seed, Mnemonic, Passphrase};
     use rust_decimal::prelude::FromPrimitive;
+    use rust_decimal_macros::dec;
     use serde_json::json;
     use testing::CleanUp;
+    use unicode_normalization::UnicodeNormalization;
 
     // Account #0: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266 (10000 ETH)
     // Private Key: 0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80
@@ -403,7 +1403,8 @@ mod tests {
         let node_url = vec![String::from("https://sepolia.mode.network")];
         let chain_id = 31337;
 
-        let wallet = WalletImplEth::new(mnemonic.into(), node_url, chain_id).expect("should initialize wallet");
+        let wallet =
+            WalletImplEth::new(mnemonic.into(), None, node_url, chain_id, &[]).expect("should initialize wallet");
         (wallet, cleanup)
     }
 
@@ -413,7 +1414,8 @@ mod tests {
         node_url: String,
         chain_id: u64,
     ) -> WalletImplEth {
-        WalletImplEth::new(mnemonic.into(), vec![node_url], chain_id).expect("could not initialize WalletImplEth")
+        WalletImplEth::new(mnemonic.into(), None, vec![node_url], chain_id, &[])
+            .expect("could not initialize WalletImplEth")
     }
 
     #[tokio::test]
@@ -430,6 +1432,123 @@ mod tests {
         assert_eq!(parsed, expected);
     }
 
+    #[tokio::test]
+    async fn test_get_addresses_derives_address_indices() {
+        // Arrange
+        let node_url = vec![String::from("https://sepolia.mode.network")];
+        let wallet_user = WalletImplEth::new(HARDHAT_MNEMONIC.into(), None, node_url, 31337, &[2, 1, 2]).unwrap();
+
+        // Act
+        let addresses = wallet_user.get_addresses().await.unwrap();
+
+        // Assert
+        assert_eq!(
+            addresses,
+            vec![
+                "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+                "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+                "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            ]
+        );
+        assert_eq!(wallet_user.get_address().await.unwrap(), addresses[0]);
+    }
+
+    #[tokio::test]
+    async fn test_passphrase_changes_derived_addresses() {
+        // Arrange
+        let node_url = vec![String::from("https://sepolia.mode.network")];
+        let passphrase = MnemonicPassphrase::try_from_string("25th word").unwrap();
+
+        // Act
+        let wallet_user = WalletImplEth::new(HARDHAT_MNEMONIC.into(), Some(&passphrase), node_url, 31337, &[]).unwrap();
+        let address = wallet_user.get_address().await.unwrap();
+
+        // Assert
+        assert_ne!(address, "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    }
+
+    #[tokio::test]
+    async fn test_new_wallet_from_non_english_mnemonic() {
+        // Arrange
+        let node_url = vec![String::from("https://sepolia.mode.network")];
+        // official Japanese BIP-39 test vector for the zero entropy, with ideographic spaces and precomposed kana
+        let mnemonic = "あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あいこくしん\u{3000}あおぞら";
+        let passphrase = MnemonicPassphrase::try_from_string("25th word").unwrap();
+
+        // the default address derived from the BIP-39 seed of the NFKD normalized mnemonic
+        let seed = mnemonic_to_seed(
+            &Mnemonic::from(mnemonic.nfkd().collect::<String>()),
+            &Passphrase::from(passphrase.as_str().to_string()),
+        );
+        let key = XPriv::root_from_seed(seed.as_ref(), None)
+            .unwrap()
+            .derive_path("m/44'/60'/0'/0/0")
+            .unwrap();
+        let signing_key: &SigningKey = key.as_ref();
+        let expected = alloy::signers::utils::public_key_to_address(signing_key.verifying_key()).to_string();
+
+        // Act
+        let wallet_user = WalletImplEth::new(
+            mnemonic.to_string().into(),
+            Some(&passphrase),
+            node_url.clone(),
+            31337,
+            &[],
+        )
+        .unwrap();
+        let generated = crate::mnemonic::generate_mnemonic(&crate::mnemonic::MnemonicOptions {
+            word_count: crate::mnemonic::MnemonicWordCount::Words12,
+            language: MnemonicLanguage::Japanese,
+        });
+        let generated_wallet_user = WalletImplEth::new(generated, None, node_url, 31337, &[]).unwrap();
+
+        // Assert
+        assert_eq!(wallet_user.get_address().await.unwrap(), expected);
+        assert_ne!(
+            generated_wallet_user.get_address().await.unwrap(),
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+        );
+    }
+
+    #[test]
+    fn test_new_wallet_from_invalid_mnemonic() {
+        let node_url = vec![String::from("https://sepolia.mode.network")];
+        let mnemonic =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+
+        let result = WalletImplEth::new(mnemonic.to_string().into(), None, node_url, 31337, &[]);
+
+        assert!(matches!(
+            result,
+            Err(WalletError::Mnemonic(crate::mnemonic::MnemonicError::InvalidChecksum))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_prepare_transaction_with_sender_address() {
+        // Arrange
+        let node_url = vec![String::from("https://sepolia.mode.network")];
+        let wallet_user = WalletImplEth::new(HARDHAT_MNEMONIC.into(), None, node_url, 31337, &[1]).unwrap();
+        let mut intent = TransactionIntent {
+            address_to: String::from("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC"),
+            amount: CryptoAmount::from(1),
+            data: None,
+            address_from: Some(String::from("0x70997970C51812dc3A010C7d01b50e0d17dc79C8")),
+        };
+
+        // Act
+        let tx = wallet_user.prepare_transaction(&intent).unwrap();
+        intent.address_from = Some(String::from("0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC"));
+        let unknown_sender = wallet_user.prepare_transaction(&intent);
+
+        // Assert
+        assert_eq!(
+            tx.from,
+            Some(alloy_primitives::address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8"))
+        );
+        assert!(matches!(unknown_sender, Err(WalletError::UnknownAddress(_))));
+    }
+
     #[tokio::test]
     async fn test_convert_wei_to_decimal() {
         //Arrange
@@ -464,12 +1583,35 @@ mod tests {
             alloy_primitives::Uint::from_str("10000000000000000000000").unwrap();
 
         // Act
-        let value_crypto_amount = WalletImplEth::convert_alloy_256_to_crypto_amount(value_alloy_256).unwrap();
+        let value_crypto_amount = WalletImplEth::convert_alloy_256_to_crypto_amount(value_alloy_256, 0).unwrap();
 
         // Assert
         assert_eq!(value_crypto_amount, expected_value_crypto_amount)
     }
 
+    #[rstest::rstest]
+    #[case::usdc("1500000", 6, dec!(1.5))]
+    #[case::below_one("42", 6, dec!(0.000042))]
+    #[case::one_wei("1", 18, dec!(0.000000000000000001))]
+    #[case::zero("0", 18, dec!(0))]
+    // larger than i128::MAX, previously truncated
+    #[case::exceeds_i128("1000000000000000000000000000000000000000000", 18, dec!(1000000000000000000000000))]
+    #[tokio::test]
+    async fn test_convert_alloy_256_to_crypto_amount_with_decimals(
+        #[case] value: &str,
+        #[case] decimals: u8,
+        #[case] expected: Decimal,
+    ) {
+        // Arrange
+        let value_alloy_256: alloy_primitives::Uint<256, 4> = alloy_primitives::Uint::from_str(value).unwrap();
+
+        // Act
+        let value_crypto_amount = WalletImplEth::convert_alloy_256_to_crypto_amount(value_alloy_256, decimals).unwrap();
+
+        // Assert
+        assert_eq!(value_crypto_amount.inner(), expected)
+    }
+
     #[tokio::test]
     async fn test_convert_crypto_amount_to_alloy_256() {
         //Arrange
@@ -477,12 +1619,36 @@ mod tests {
         let value_crypto_amount = CryptoAmount::from(1);
 
         // Act
-        let value_u256 = WalletImplEth::convert_crypto_amount_to_u256(value_crypto_amount).unwrap();
+        let value_u256 = WalletImplEth::convert_crypto_amount_to_u256(value_crypto_amount, 0).unwrap();
 
         // Assert
         assert_eq!(value_u256, expected_value_u256)
     }
 
+    #[rstest::rstest]
+    #[case::usdc(dec!(1.5), 6, Some("1500000"))]
+    #[case::eth(dec!(10000), 18, Some("10000000000000000000000"))]
+    #[case::trailing_zeros(dec!(2.500000), 2, Some("250"))]
+    #[case::too_many_decimals(dec!(0.0000001), 6, None)]
+    #[tokio::test]
+    async fn test_convert_crypto_amount_to_alloy_256_with_decimals(
+        #[case] value: Decimal,
+        #[case] decimals: u8,
+        #[case] expected: Option<&str>,
+    ) {
+        // Arrange
+        let value_crypto_amount = CryptoAmount::try_from(value).unwrap();
+
+        // Act
+        let result = WalletImplEth::convert_crypto_amount_to_u256(value_crypto_amount, decimals);
+
+        // Assert
+        match expected {
+            Some(expected) => assert_eq!(result.unwrap(), U256::from_str(expected).unwrap()),
+            None => assert!(matches!(result, Err(WalletError::InvalidTransactionAmount(_)))),
+        }
+    }
+
     #[tokio::test]
     async fn test_get_balance() {
         //Arrange
@@ -529,6 +1695,148 @@ mod tests {
         assert_eq!(balance, CryptoAmount::from(10000))
     }
 
+    #[tokio::test]
+    async fn test_watch_only_wallet_gets_address_and_balance() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let address = alloy_primitives::address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+        let wallet_user = WalletImplEth::new_watch_only(BTreeMap::from([(0, address)]), vec![server.url()], 31337)
+            .expect("could not initialize watch-only WalletImplEth");
+
+        let mocked_rpc_get_balance = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getBalance",
+                "params": [address.to_string().to_lowercase(), "latest"],
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc": "2.0", "id": 1, "result": "0xDE0B6B3A7640000"}"#)
+            .create();
+
+        // Act
+        let wallet_address = wallet_user.get_address().await.unwrap();
+        let balance = wallet_user.get_balance().await.unwrap();
+
+        // Assert
+        mocked_rpc_get_balance.assert();
+        assert_eq!(wallet_address, address.to_string());
+        assert_eq!(balance, CryptoAmount::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_watch_only_wallet_rejects_signing() {
+        // Arrange
+        let server = mockito::Server::new_async().await;
+        let address = alloy_primitives::address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+        let wallet_user = WalletImplEth::new_watch_only(BTreeMap::from([(0, address)]), vec![server.url()], 31337)
+            .expect("could not initialize watch-only WalletImplEth");
+        let intent = TransactionIntent {
+            address_to: "0xb0b0000000000000000000000000000000000000".to_string(),
+            amount: CryptoAmount::from(1),
+            data: None,
+            address_from: None,
+        };
+
+        // Act
+        let send_result = wallet_user.send_amount(&intent).await;
+        let sign_result = wallet_user.sign_transaction(&intent, None).await;
+
+        // Assert
+        assert!(matches!(send_result, Err(WalletError::WatchOnly)));
+        assert!(matches!(sign_result, Err(WalletError::WatchOnly)));
+    }
+
+    #[test]
+    fn test_watch_only_wallet_requires_an_address() {
+        let result = WalletImplEth::new_watch_only(BTreeMap::new(), vec![String::from("http://localhost")], 31337);
+
+        assert!(matches!(result, Err(WalletError::EmptyWalletAddress)));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance_erc20_queries_and_caches_token_metadata() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let node_url = Url::parse(&url).unwrap();
+        let chain_id = 31337;
+        let contract_address = "0xe7f1725e7734ce288f8367e1bb143e90bb3f0512";
+
+        let new_wallet_user = || {
+            WalletImplEthErc20::new(
+                HARDHAT_MNEMONIC.into(),
+                None,
+                vec![node_url.to_string()],
+                chain_id,
+                contract_address.to_string(),
+                None,
+                None,
+                &[],
+            )
+            .unwrap()
+        };
+        let cache = TokenMetadataCache::default();
+
+        let mut eth_call_mock = |selector: &str, result: &str| {
+            server
+                .mock("POST", "/")
+                .match_header("content-type", "application/json")
+                .match_body(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::Regex(r#""method":"eth_call""#.to_string()),
+                    mockito::Matcher::Regex(selector.to_string()),
+                ]))
+                .with_status(200)
+                .with_body(format!(r#"{{"jsonrpc":"2.0","id":0,"result":"{result}"}}"#))
+        };
+
+        // balanceOf(address) returns 2_500_000 = 2.5 tokens with 6 decimals
+        let mocked_rpc_balance_of = eth_call_mock(
+            "0x70a08231",
+            "0x00000000000000000000000000000000000000000000000000000000002625a0",
+        )
+        .expect(3)
+        .create();
+        // decimals() returns 6
+        let mocked_rpc_decimals = eth_call_mock(
+            "0x313ce567",
+            "0x0000000000000000000000000000000000000000000000000000000000000006",
+        )
+        .expect(2)
+        .create();
+        // symbol() returns "USDC"
+        let mocked_rpc_symbol = eth_call_mock(
+            "0x95d89b41",
+            "0x000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000045553444300000000000000000000000000000000000000000000000000000000",
+        )
+        .expect(2)
+        .create();
+
+        // Act
+        let balance = new_wallet_user()
+            .with_token_metadata_cache(cache.clone())
+            .get_balance()
+            .await
+            .unwrap();
+        // a new wallet instance with the same cache uses the cached token metadata
+        let balance_cached = new_wallet_user()
+            .with_token_metadata_cache(cache)
+            .get_balance()
+            .await
+            .unwrap();
+        // the cache is not shared with wallets of other SDK instances
+        let balance_other_cache = new_wallet_user().get_balance().await.unwrap();
+
+        // Assert
+        mocked_rpc_balance_of.assert();
+        mocked_rpc_decimals.assert();
+        mocked_rpc_symbol.assert();
+        assert_eq!(balance.inner(), dec!(2.5));
+        assert_eq!(balance_cached.inner(), dec!(2.5));
+        assert_eq!(balance_other_cache.inner(), dec!(2.5));
+    }
+
     #[tokio::test]
     async fn test_send_amount_eth() {
         //Arrange
@@ -549,6 +1857,7 @@ mod tests {
             address_to: to.clone(),
             amount: amount_to_send,
             data: Some(metadata),
+            address_from: None,
         };
 
         let mocked_rpc_estimate_gas = server
@@ -883,7 +2192,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn should_estimate_gas_cost() {
+    async fn test_scan_wallet_tx_finds_incoming_transfer() {
         // Arrange
         let mut server = mockito::Server::new_async().await;
         let url = server.url();
@@ -892,70 +2201,985 @@ mod tests {
 
         let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, node_url.to_string(), chain_id).await;
 
-        let to = String::from("0xb0b0000000000000000000000000000000000000");
-        let transaction_data = "data";
-
-        let intent = TransactionIntent {
-            address_to: to.clone(),
-            amount: CryptoAmount::from(1),
-            data: Some(transaction_data.to_string().into_bytes()),
-        };
-
-        let expected_estimation = GasCostEstimation {
-            gas_limit: 24009,
-            max_fee_per_gas: 2000000001,
-            max_priority_fee_per_gas: 1,
-        };
+        let incoming_transaction_hash = "0xcd718a69d478340dc28fdf6bf8056374a52dc95841b44083163ced8dfe29310c";
+        let unrelated_transaction_hash = "0x4bb1a4a2ba1ec9d1d6c2ac0fbd2e0b3b5e3c1d20eb0b6bd5a5e1a2c9e5d5c1f0";
 
-        let from = wallet_user.get_address().await.unwrap().to_lowercase();
-        let mocked_rpc_estimate_gas = server
+        let mocked_rpc_block_number = server
             .mock("POST", "/")
             .match_header("content-type", "application/json")
             .match_body(mockito::Matcher::PartialJson(json!({
                 "jsonrpc": "2.0",
-                "id": 0,
-                "method": "eth_estimateGas",
-                "params": [{"from": from,"to": to,"value": "0xde0b6b3a7640000", "input": "0x64617461"},"pending"],
+                "method": "eth_blockNumber",
             })))
             .with_status(200)
-            .with_body(
-                r#"{
-                    "jsonrpc": "2.0",
-                    "result": 24009
-                }"#,
-            )
+            .with_body(r#"{"jsonrpc":"2.0","id":0,"result":"0x10"}"#)
             .create();
 
-        let mocked_rpc_eth_fee_history = server
+        let transaction_json = |hash: &str, from: &str, to: &str| {
+            json!({
+                "accessList": [],
+                "blockHash": "0xf7756d836b6716aaeffc2139c032752ba5acf02fe94acb65743f0d177554b2e2",
+                "blockNumber": "0x10",
+                "chainId": "0x7a69",
+                "from": from,
+                "gas": "0x5208",
+                "gasPrice": "0xb9029a7ea",
+                "hash": hash,
+                "input": "0x",
+                "maxFeePerGas": "0xf22a22912",
+                "maxPriorityFeePerGas": "0x5f5e100",
+                "nonce": "0x4",
+                "r": "0xef566fc229bb0a10eee5f99c9cabe47f0f20ebaa6d16e4f7b90ee144086b21e9",
+                "s": "0x109de5d9baca8daeee1ce1b7d1a304e223d07b1420b37704e675ccffd364a4dc",
+                "to": to,
+                "transactionIndex": "0x0",
+                "type": "0x2",
+                "v": "0x0",
+                "value": "0xde0b6b3a7640000"
+            })
+        };
+
+        let block_json = json!({
+            "difficulty": "0x0",
+            "extraData": "0x",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0xa410",
+            "hash": "0xf7756d836b6716aaeffc2139c032752ba5acf02fe94acb65743f0d177554b2e2",
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x33bc13fdf135073277971b4d9f4f72082e907996",
+            "mixHash": "0x8c2dc0f970fa3aa6beb64c9f06a202a4314acfa4effaa4c75fd5bc9f9c77a519",
+            "nonce": "0x0000000000000000",
+            "number": "0x10",
+            "parentHash": "0xbc33aa8829350cc2e3ba7cf64d4beb2f1b554d570efc8bccb7b05ef50d76a47a",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size": "0x223",
+            "stateRoot": "0x8af5429b649f9fc633ce3c95219026fd08a249867e28c7eab22994eaa6125bb9",
+            "timestamp": "0x55bf47e3",
+            "totalDifficulty": "0x0",
+            "transactions": [
+                transaction_json(
+                    incoming_transaction_hash,
+                    "0x901c7c311d39e0b26257219765e71e8db3107a81",
+                    "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+                ),
+                transaction_json(
+                    unrelated_transaction_hash,
+                    "0x901c7c311d39e0b26257219765e71e8db3107a81",
+                    "0xef1c6e67703c7bd7107eed8303fbe6ec2554bf6b"
+                ),
+            ],
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "uncles": []
+        });
+
+        let mocked_rpc_get_block_by_number = mock_get_blocks(&mut server, HashMap::from([(16, block_json)]), 1);
+
+        let mocked_rpc_get_transaction_receipt_response_json = json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "result": {
+                "blockHash": "0xf7756d836b6716aaeffc2139c032752ba5acf02fe94acb65743f0d177554b2e2",
+                "blockNumber": "0x10",
+                "contractAddress": null,
+                "cumulativeGasUsed": "0x5208",
+                "effectiveGasPrice": "0xb9029a7ea",
+                "from": "0x901c7c311d39e0b26257219765e71e8db3107a81",
+                "gasUsed": "0x5208",
+                "logs": [],
+                "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                "status": "0x1",
+                "to": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "transactionHash": incoming_transaction_hash,
+                "transactionIndex": "0x0",
+                "type": "0x2"
+            }
+        });
+
+        let mocked_rpc_get_transaction_receipt = server
             .mock("POST", "/")
             .match_header("content-type", "application/json")
             .match_body(mockito::Matcher::PartialJson(json!({
                 "jsonrpc": "2.0",
-                "method": "eth_feeHistory",
+                "method": "eth_getTransactionReceipt",
+                "params": [ incoming_transaction_hash ],
             })))
             .with_status(200)
-            .with_body(
-                r#"{
-                "jsonrpc": "2.0",
-                "result": {
-                    "baseFeePerGas": [1000000000, 875000000],
-                    "gasUsedRatio": [0.0],
-                    "oldestBlock": 0,
-                    "reward": [[0]]
-                }
-            }
-            "#,
-            )
+            .with_body(serde_json::to_vec(&mocked_rpc_get_transaction_receipt_response_json).unwrap())
+            .expect(1)
             .create();
 
         // Act
-        let result = wallet_user.estimate_gas_cost(&intent).await;
+        let scan = wallet_user.scan_wallet_tx(Some(15), None).await;
 
         // Assert
-        mocked_rpc_estimate_gas.assert();
-        mocked_rpc_eth_fee_history.assert();
-        let response = result.unwrap();
-
+        mocked_rpc_block_number.assert();
+        mocked_rpc_get_block_by_number.assert();
+        mocked_rpc_get_transaction_receipt.assert();
+
+        let scan = scan.unwrap();
+        assert_eq!(scan.last_scanned_block, Some(16));
+        assert_eq!(scan.first_scanned_block, None);
+        assert_eq!(scan.transactions.len(), 1);
+
+        let tx = &scan.transactions[0];
+        assert_eq!(tx.transaction_id, incoming_transaction_hash);
+        assert_eq!(tx.block_id.as_deref(), Some("16"));
+        assert!(tx.incoming);
+        assert_eq!(tx.amount, 1.0);
+        assert_eq!(tx.status, format!("{:?}", InclusionState::Confirmed));
+    }
+
+    #[tokio::test]
+    async fn test_scan_wallet_tx_does_nothing_when_up_to_date() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let node_url = Url::parse(&url).unwrap();
+        let chain_id = 31337;
+
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, node_url.to_string(), chain_id).await;
+
+        let mocked_rpc_block_number = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_blockNumber",
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":0,"result":"0x10"}"#)
+            .create();
+
+        let mocked_rpc_get_block_by_number = mock_get_blocks(&mut server, HashMap::new(), 0);
+
+        // Act
+        let scan = wallet_user.scan_wallet_tx(Some(16), None).await;
+
+        // Assert
+        mocked_rpc_block_number.assert();
+        mocked_rpc_get_block_by_number.assert();
+        assert_eq!(
+            scan.unwrap(),
+            WalletTxScan {
+                transactions: Vec::new(),
+                last_scanned_block: Some(16),
+                first_scanned_block: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_wallet_tx_backfills_older_blocks() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, server.url(), 31337).await;
+
+        let mocked_rpc_block_number = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_blockNumber",
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":0,"result":"0x10"}"#)
+            .create();
+
+        // the history is up to date, so the scan walks the blocks 0 to 3 before the first scanned block 4
+        let blocks = (0..4).map(|n| (n, empty_block_json(n))).collect::<HashMap<_, _>>();
+        let mocked_rpc_get_block_by_number = mock_get_blocks(&mut server, blocks, 1);
+
+        // Act
+        let scan = wallet_user.scan_wallet_tx(Some(16), Some(4)).await;
+
+        // Assert
+        mocked_rpc_block_number.assert();
+        mocked_rpc_get_block_by_number.assert();
+        assert_eq!(
+            scan.unwrap(),
+            WalletTxScan {
+                transactions: Vec::new(),
+                last_scanned_block: Some(16),
+                first_scanned_block: Some(0),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_wallet_tx_starts_new_history_with_latest_blocks() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, server.url(), 31337).await;
+
+        let mocked_rpc_block_number = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_blockNumber",
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":0,"result":"0x3e8"}"#)
+            .create();
+
+        // 500 blocks are walked in batches of 50 blocks
+        let blocks = (501..=1000)
+            .map(|n| (n, empty_block_json(n)))
+            .collect::<HashMap<_, _>>();
+        let mocked_rpc_get_block_by_number = mock_get_blocks(&mut server, blocks, 10);
+
+        // Act
+        let scan = wallet_user.scan_wallet_tx(None, None).await;
+
+        // Assert
+        mocked_rpc_block_number.assert();
+        mocked_rpc_get_block_by_number.assert();
+        assert_eq!(
+            scan.unwrap(),
+            WalletTxScan {
+                transactions: Vec::new(),
+                last_scanned_block: Some(1000),
+                first_scanned_block: Some(501),
+            }
+        );
+    }
+
+    /// Mocks the JSON-RPC batches of `eth_getBlockByNumber` requests used to walk blocks. Each request is
+    /// answered with the block of `blocks` with the requested number, or `null` if there is none.
+    fn mock_get_blocks(
+        server: &mut mockito::Server,
+        blocks: HashMap<u64, serde_json::Value>,
+        expected_batches: usize,
+    ) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(
+                json!([{ "method": "eth_getBlockByNumber" }]),
+            ))
+            .with_status(200)
+            .with_body_from_request(move |request| {
+                let requests: Vec<serde_json::Value> = serde_json::from_slice(request.body().unwrap()).unwrap();
+                let responses = requests
+                    .iter()
+                    .map(|r| {
+                        let number = r["params"][0].as_str().unwrap().trim_start_matches("0x");
+                        let number = u64::from_str_radix(number, 16).unwrap();
+                        json!({
+                            "jsonrpc": "2.0",
+                            "id": r["id"],
+                            "result": blocks.get(&number).cloned().unwrap_or_default(),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                serde_json::to_vec(&responses).unwrap()
+            })
+            .expect(expected_batches)
+            .create()
+    }
+
+    /// A block without transactions.
+    fn empty_block_json(number: u64) -> serde_json::Value {
+        json!({
+            "difficulty": "0x0",
+            "extraData": "0x",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "hash": format!("0x{number:064x}"),
+            "logsBloom": format!("0x{}", "0".repeat(512)),
+            "miner": "0x33bc13fdf135073277971b4d9f4f72082e907996",
+            "mixHash": "0x8c2dc0f970fa3aa6beb64c9f06a202a4314acfa4effaa4c75fd5bc9f9c77a519",
+            "nonce": "0x0000000000000000",
+            "number": format!("{number:#x}"),
+            "parentHash": "0xbc33aa8829350cc2e3ba7cf64d4beb2f1b554d570efc8bccb7b05ef50d76a47a",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size": "0x223",
+            "stateRoot": "0x8af5429b649f9fc633ce3c95219026fd08a249867e28c7eab22994eaa6125bb9",
+            "timestamp": "0x55bf47e3",
+            "totalDifficulty": "0x0",
+            "transactions": [],
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "uncles": []
+        })
+    }
+
+    #[tokio::test]
+    async fn test_scan_wallet_tx_erc20_transfer_logs() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let node_url = Url::parse(&url).unwrap();
+        let chain_id = 31337;
+
+        let contract_address = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+        let wallet_user = WalletImplEthErc20::new(
+            HARDHAT_MNEMONIC.into(),
+            None,
+            vec![node_url.to_string()],
+            chain_id,
+            contract_address.to_string(),
+            Some(String::from("USDC")),
+            None,
+            &[],
+        )
+        .unwrap();
+
+        let transaction_hash = "0xcd718a69d478340dc28fdf6bf8056374a52dc95841b44083163ced8dfe29310c";
+
+        let mocked_rpc_block_number = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_blockNumber",
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":0,"result":"0x20"}"#)
+            .create();
+
+        // incoming transfer of 1.5 tokens (6 decimals) from 0x901c... to the hardhat account #0
+        let mocked_rpc_get_logs_response_json = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": [{
+                "address": contract_address,
+                "topics": [
+                    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    "0x000000000000000000000000901c7c311d39e0b26257219765e71e8db3107a81",
+                    "0x000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+                ],
+                "data": "0x000000000000000000000000000000000000000000000000000000000016e360",
+                "blockNumber": "0x1f",
+                "blockHash": "0xf7756d836b6716aaeffc2139c032752ba5acf02fe94acb65743f0d177554b2e2",
+                "blockTimestamp": "0x55bf47e3",
+                "transactionHash": transaction_hash,
+                "transactionIndex": "0x0",
+                "logIndex": "0x0",
+                "removed": false
+            }]
+        });
+
+        // the same log is returned for the sent and received query and must only be reported once
+        let mocked_rpc_get_logs = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getLogs",
+                "params": [{ "address": contract_address, "fromBlock": "0x1b", "toBlock": "0x20" }],
+            })))
+            .with_status(200)
+            .with_body(serde_json::to_vec(&mocked_rpc_get_logs_response_json).unwrap())
+            .expect(2)
+            .create();
+
+        let mocked_rpc_decimals = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_call",
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"jsonrpc":"2.0","id":3,"result":"0x0000000000000000000000000000000000000000000000000000000000000006"}"#,
+            )
+            .create();
+
+        // Act
+        let scan = wallet_user.scan_wallet_tx(Some(26), None).await;
+
+        // Assert
+        mocked_rpc_block_number.assert();
+        mocked_rpc_get_logs.assert();
+        mocked_rpc_decimals.assert();
+
+        let scan = scan.unwrap();
+        assert_eq!(scan.last_scanned_block, Some(32));
+        assert_eq!(scan.first_scanned_block, None);
+        assert_eq!(
+            scan.transactions,
+            vec![WalletTxInfo {
+                date: "1438599139".to_string(),
+                block_id: Some("31".to_string()),
+                transaction_id: transaction_hash.to_string(),
+                log_index: Some(0),
+                incoming: true,
+                receiver: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string(),
+                amount: 1.5,
+                network: "USDC".to_string(),
+                status: format!("{:?}", InclusionState::Confirmed),
+                explorer_url: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_estimate_gas_cost() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let url = server.url();
+        let node_url = Url::parse(&url).unwrap();
+        let chain_id = 31337;
+
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, node_url.to_string(), chain_id).await;
+
+        let to = String::from("0xb0b0000000000000000000000000000000000000");
+        let transaction_data = "data";
+
+        let intent = TransactionIntent {
+            address_to: to.clone(),
+            amount: CryptoAmount::from(1),
+            data: Some(transaction_data.to_string().into_bytes()),
+            address_from: None,
+        };
+
+        let expected_estimation = GasCostEstimation {
+            gas_limit: 24009,
+            max_fee_per_gas: 2000000001,
+            max_priority_fee_per_gas: 1,
+        };
+
+        let from = wallet_user.get_address().await.unwrap().to_lowercase();
+        let mocked_rpc_estimate_gas = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "id": 0,
+                "method": "eth_estimateGas",
+                "params": [{"from": from,"to": to,"value": "0xde0b6b3a7640000", "input": "0x64617461"},"pending"],
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "jsonrpc": "2.0",
+                    "result": 24009
+                }"#,
+            )
+            .create();
+
+        let mocked_rpc_eth_fee_history = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_feeHistory",
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{
+                "jsonrpc": "2.0",
+                "result": {
+                    "baseFeePerGas": [1000000000, 875000000],
+                    "gasUsedRatio": [0.0],
+                    "oldestBlock": 0,
+                    "reward": [[0]]
+                }
+            }
+            "#,
+            )
+            .create();
+
+        // Act
+        let result = wallet_user.estimate_gas_cost(&intent).await;
+
+        // Assert
+        mocked_rpc_estimate_gas.assert();
+        mocked_rpc_eth_fee_history.assert();
+        let response = result.unwrap();
+
         assert_eq!(expected_estimation, response)
     }
+
+    #[rstest::rstest]
+    #[case::pending(None, "0x10", InclusionState::Pending, 0)]
+    #[case::included(Some(("0x1", "0xd")), "0x10", InclusionState::Confirmed, 4)]
+    #[case::reverted(Some(("0x0", "0x10")), "0x10", InclusionState::Conflicting, 1)]
+    #[tokio::test]
+    async fn test_get_transaction_confirmation(
+        #[case] receipt: Option<(&str, &str)>,
+        #[case] latest_block: &str,
+        #[case] expected_state: InclusionState,
+        #[case] expected_confirmations: u64,
+    ) {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, server.url(), 31337).await;
+        let transaction_hash = "0x969dc1d6a97464e62fb1dab451b03d24111c278bf6f4d2e2b3910205a8682ed2";
+
+        let receipt = receipt.map(|(status, block_number)| {
+            json!({
+                "transactionHash": transaction_hash,
+                "blockHash": "0xe6262c1924326d12b88aaa35a95a0c7cdd11f2d20ebae84618484120bd037c34",
+                "blockNumber": block_number,
+                "contractAddress": null,
+                "cumulativeGasUsed": "0x5208",
+                "effectiveGasPrice": "0x3b9aca00",
+                "from": "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266",
+                "gasUsed": "0x5208",
+                "logs": [],
+                "logsBloom": format!("0x{}", "0".repeat(512)),
+                "status": status,
+                "to": "0xb0b0000000000000000000000000000000000000",
+                "transactionIndex": "0x0",
+                "type": "0x2"
+            })
+        });
+        let is_included = receipt.is_some();
+        let mocked_rpc_get_receipt = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "method": "eth_getTransactionReceipt",
+                "params": [transaction_hash],
+            })))
+            .with_status(200)
+            .with_body(json!({"jsonrpc": "2.0", "id": 0, "result": receipt}).to_string())
+            .create();
+        let mocked_rpc_block_number = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJson(json!({"method": "eth_blockNumber"})))
+            .with_status(200)
+            .with_body(json!({"jsonrpc": "2.0", "id": 1, "result": latest_block}).to_string())
+            .expect(usize::from(is_included))
+            .create();
+
+        // Act
+        let confirmation = wallet_user
+            .get_transaction_confirmation(transaction_hash)
+            .await
+            .unwrap();
+
+        // Assert
+        mocked_rpc_get_receipt.assert();
+        mocked_rpc_block_number.assert();
+        assert_eq!(confirmation.inclusion_state, expected_state);
+        assert_eq!(confirmation.confirmations, Some(expected_confirmations));
+    }
+
+    /// Mocks the `eth_feeHistory` call used for the EIP-1559 fee estimation, resulting in a max fee per gas of
+    /// 2000000001 wei and a max priority fee per gas of 1 wei.
+    fn mock_fee_history(server: &mut mockito::Server) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_feeHistory",
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{
+                "jsonrpc": "2.0",
+                "result": {
+                    "baseFeePerGas": [1000000000, 875000000],
+                    "gasUsedRatio": [0.0],
+                    "oldestBlock": 0,
+                    "reward": [[0]]
+                }
+            }
+            "#,
+            )
+            .create()
+    }
+
+    #[tokio::test]
+    async fn test_sign_transaction_uses_min_nonce() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, server.url(), 31337).await;
+        let from = wallet_user.get_address().await.unwrap();
+        let to = String::from("0xb0b0000000000000000000000000000000000000");
+
+        let intent = TransactionIntent {
+            address_to: to.clone(),
+            amount: CryptoAmount::from(1),
+            data: None,
+            address_from: None,
+        };
+
+        let mocked_rpc_get_transaction_count = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getTransactionCount",
+                "params": [from.to_lowercase(), "pending"],
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc": "2.0", "result": "0x5"}"#)
+            .create();
+        let mocked_rpc_eth_fee_history = mock_fee_history(&mut server);
+        let mocked_rpc_estimate_gas = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_estimateGas",
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc": "2.0", "result": 21000}"#)
+            .create();
+
+        // Act
+        let signed = wallet_user.sign_transaction(&intent, Some(7)).await.unwrap();
+
+        // Assert
+        mocked_rpc_get_transaction_count.assert();
+        mocked_rpc_eth_fee_history.assert();
+        mocked_rpc_estimate_gas.assert();
+
+        assert_eq!(signed.from, from);
+        assert_eq!(signed.nonce, 7);
+        assert_eq!(signed.gas_limit, 21000);
+        assert_eq!(signed.max_fee_per_gas, 2000000001);
+        assert_eq!(signed.max_priority_fee_per_gas, 1);
+
+        let envelope = TxEnvelope::decode_2718(&mut signed.raw_transaction.as_slice()).unwrap();
+        assert_eq!(envelope.tx_hash().to_string(), signed.transaction_hash);
+        assert_eq!(envelope.to(), Some(Address::from_str(&to).unwrap()));
+        assert_eq!(envelope.chain_id(), Some(31337));
+    }
+
+    #[tokio::test]
+    async fn test_sign_replacement_cancel_sends_nothing_to_self_with_same_nonce() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let wallet_user = get_wallet_user_with_mocked_provider(HARDHAT_MNEMONIC, server.url(), 31337).await;
+        let from = Address::from_str(&wallet_user.get_address().await.unwrap()).unwrap();
+
+        let original = TransactionRequest::default()
+            .with_from(from)
+            .with_to(Address::from_str("0xb0b0000000000000000000000000000000000000").unwrap())
+            .with_value(U256::from(1_000_000))
+            .with_chain_id(31337)
+            .with_nonce(3)
+            .with_gas_limit(50_000)
+            .with_max_fee_per_gas(2_000_000_001)
+            .with_max_priority_fee_per_gas(1);
+        let pending = wallet_user.sign_filled_transaction_request(original).await.unwrap();
+
+        let mocked_rpc_eth_fee_history = mock_fee_history(&mut server);
+
+        // Act
+        let replacement = wallet_user
+            .sign_replacement(&pending, TransactionReplacement::Cancel)
+            .await
+            .unwrap();
+
+        // Assert
+        mocked_rpc_eth_fee_history.assert();
+
+        assert_ne!(replacement.transaction_hash, pending.transaction_hash);
+        assert_eq!(replacement.nonce, 3);
+        assert_eq!(replacement.gas_limit, CANCEL_GAS_LIMIT);
+        assert_eq!(replacement.max_fee_per_gas, 2_300_000_002);
+        assert_eq!(replacement.max_priority_fee_per_gas, 2);
+
+        let envelope = TxEnvelope::decode_2718(&mut replacement.raw_transaction.as_slice()).unwrap();
+        assert_eq!(envelope.to(), Some(from));
+        assert_eq!(envelope.value(), U256::ZERO);
+        assert!(envelope.input().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sign_replacement_rejects_foreign_sender() {
+        // Arrange
+        let (wallet_user, _cleanup) = get_wallet_user(HARDHAT_MNEMONIC).await;
+        let pending = PendingTransaction {
+            transaction_hash: String::from("0x01"),
+            from: String::from("0xb0b0000000000000000000000000000000000000"),
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1,
+            max_priority_fee_per_gas: 1,
+            raw_transaction: Vec::new(),
+            created_at: 0,
+            replaces: Vec::new(),
+        };
+
+        // Act
+        let result = wallet_user
+            .sign_replacement(&pending, TransactionReplacement::SpeedUp)
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::UnknownAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn test_offline_signing_round_trip() {
+        // Arrange
+        let mut server = mockito::Server::new_async().await;
+        let (offline_wallet, _cleanup) = get_wallet_user(HARDHAT_MNEMONIC).await;
+        let from = Address::from_str(&offline_wallet.get_address().await.unwrap()).unwrap();
+        let online_wallet = WalletImplEth::new_watch_only(BTreeMap::from([(0, from)]), vec![server.url()], 31337)
+            .expect("could not initialize watch-only WalletImplEth");
+        let to = Address::from_str("0xb0b0000000000000000000000000000000000000").unwrap();
+
+        let intent = TransactionIntent {
+            address_to: to.to_string(),
+            amount: CryptoAmount::from(1),
+            data: None,
+            address_from: None,
+        };
+
+        let mocked_rpc_get_transaction_count = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_getTransactionCount",
+                "params": [from.to_string().to_lowercase(), "pending"],
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc": "2.0", "result": "0x5"}"#)
+            .create();
+        let mocked_rpc_eth_fee_history = mock_fee_history(&mut server);
+        let mocked_rpc_estimate_gas = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_estimateGas",
+            })))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc": "2.0", "result": 21000}"#)
+            .create();
+
+        // Act
+        let unsigned = online_wallet.prepare_unsigned_transaction(&intent, None).await.unwrap();
+        let signed = offline_wallet.sign_unsigned_transaction(&unsigned).await.unwrap();
+
+        let mocked_rpc_send_raw_transaction = server
+            .mock("POST", "/")
+            .match_header("content-type", "application/json")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "jsonrpc": "2.0",
+                "method": "eth_sendRawTransaction",
+            })))
+            .with_status(200)
+            .with_body(format!(
+                r#"{{"jsonrpc": "2.0", "id": 0, "result": "{}"}}"#,
+                signed.transaction_hash
+            ))
+            .create();
+        let broadcast = online_wallet
+            .broadcast_signed_transaction(&signed.raw_transaction)
+            .await
+            .unwrap();
+
+        // Assert
+        mocked_rpc_get_transaction_count.assert();
+        mocked_rpc_eth_fee_history.assert();
+        mocked_rpc_estimate_gas.assert();
+        mocked_rpc_send_raw_transaction.assert();
+
+        assert_eq!(unsigned.from, from.to_string());
+        assert_eq!(unsigned.raw_transaction[0], u8::from(TxType::Eip1559));
+        assert_eq!(signed.from, from.to_string());
+        assert_eq!(signed.nonce, 5);
+        assert_eq!(signed.gas_limit, 21000);
+        assert_eq!(broadcast.transaction_hash, signed.transaction_hash);
+        assert_eq!(broadcast.from, signed.from);
+
+        let envelope = TxEnvelope::decode_2718(&mut signed.raw_transaction.as_slice()).unwrap();
+        assert_eq!(envelope.to(), Some(to));
+        assert_eq!(envelope.chain_id(), Some(31337));
+    }
+
+    #[tokio::test]
+    async fn test_review_unsigned_transaction() {
+        // Arrange
+        let (wallet_user, _cleanup) = get_wallet_user(HARDHAT_MNEMONIC).await;
+        let from = wallet_user.get_address().await.unwrap();
+        let to = Address::from_str("0xb0b0000000000000000000000000000000000000").unwrap();
+        let tx = TxEip1559 {
+            chain_id: 31337,
+            nonce: 5,
+            gas_limit: 21_000,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: to.into(),
+            value: U256::from(1_500_000_000_000_000_000u128),
+            input: b"tag".to_vec().into(),
+            ..Default::default()
+        };
+        let unsigned = UnsignedTransaction {
+            from: from.clone(),
+            raw_transaction: tx.encoded_for_signing(),
+        };
+
+        // Act
+        let details = wallet_user.review_unsigned_transaction(&unsigned).await.unwrap();
+
+        // Assert
+        assert_eq!(
+            details,
+            UnsignedTransactionDetails {
+                from,
+                to: to.to_string(),
+                value: dec!(1.5),
+                data: b"tag".to_vec(),
+                chain_id: 31337,
+                nonce: 5,
+                gas_limit: 21_000,
+                max_fee_per_gas: 2_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                max_fee: dec!(0.000042),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_unsigned_token_transfer() {
+        // Arrange
+        let contract_address = Address::from_str("0x5fbdb2315678afecb367f032d93f642f64180aa3").unwrap();
+        let wallet_user = WalletImplEthErc20::new(
+            HARDHAT_MNEMONIC.into(),
+            None,
+            vec![String::from("http://localhost:8545")],
+            31337,
+            contract_address.to_string(),
+            Some(String::from("USDC")),
+            Some(6),
+            &[],
+        )
+        .unwrap();
+        let from = wallet_user.get_address().await.unwrap();
+        let receiver = Address::from_str("0xb0b0000000000000000000000000000000000000").unwrap();
+        let call = Erc20Contract::transferCall {
+            _to: receiver,
+            _value: U256::from(2_500_000),
+        };
+        let tx = TxEip1559 {
+            chain_id: 31337,
+            nonce: 0,
+            gas_limit: 50_000,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: contract_address.into(),
+            input: call.abi_encode().into(),
+            ..Default::default()
+        };
+        let unsigned = UnsignedTransaction {
+            from,
+            raw_transaction: tx.encoded_for_signing(),
+        };
+
+        // Act
+        let details = wallet_user.review_unsigned_transaction(&unsigned).await.unwrap();
+
+        // Assert
+        assert_eq!(details.to, receiver.to_string());
+        assert_eq!(details.value, dec!(2.5));
+        assert_eq!(details.data, call.abi_encode());
+        assert_eq!(details.max_fee, dec!(0.00005));
+    }
+
+    #[tokio::test]
+    async fn test_review_unsigned_transaction_rejects_foreign_sender() {
+        // Arrange
+        let (wallet_user, _cleanup) = get_wallet_user(HARDHAT_MNEMONIC).await;
+        let tx = TxEip1559 {
+            chain_id: 31337,
+            to: Address::ZERO.into(),
+            ..Default::default()
+        };
+        let unsigned = UnsignedTransaction {
+            from: String::from("0xb0b0000000000000000000000000000000000000"),
+            raw_transaction: tx.encoded_for_signing(),
+        };
+
+        // Act
+        let result = wallet_user.review_unsigned_transaction(&unsigned).await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::UnknownAddress(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sign_unsigned_transaction_rejects_other_chain() {
+        // Arrange
+        let (wallet_user, _cleanup) = get_wallet_user(HARDHAT_MNEMONIC).await;
+        let from = wallet_user.get_address().await.unwrap();
+        let tx = TxEip1559 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 21_000,
+            max_fee_per_gas: 1,
+            max_priority_fee_per_gas: 1,
+            to: Address::ZERO.into(),
+            ..Default::default()
+        };
+        let unsigned = UnsignedTransaction {
+            from,
+            raw_transaction: tx.encoded_for_signing(),
+        };
+
+        // Act
+        let result = wallet_user.sign_unsigned_transaction(&unsigned).await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::InvalidTransaction(_))));
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_signed_transaction_rejects_foreign_signer() {
+        // Arrange
+        let (signer_wallet, _cleanup) = get_wallet_user(HARDHAT_MNEMONIC).await;
+        let pending = signer_wallet
+            .sign_filled_transaction_request(
+                TransactionRequest::default()
+                    .with_to(Address::ZERO)
+                    .with_chain_id(31337)
+                    .with_nonce(0)
+                    .with_gas_limit(21_000)
+                    .with_max_fee_per_gas(1)
+                    .with_max_priority_fee_per_gas(1),
+            )
+            .await
+            .unwrap();
+        let other = alloy_primitives::address!("0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+        let online_wallet = WalletImplEth::new_watch_only(
+            BTreeMap::from([(0, other)]),
+            vec![String::from("http://localhost")],
+            31337,
+        )
+        .expect("could not initialize watch-only WalletImplEth");
+
+        // Act
+        let result = online_wallet
+            .broadcast_signed_transaction(&pending.raw_transaction)
+            .await;
+
+        // Assert
+        assert!(matches!(result, Err(WalletError::UnknownAddress(_))));
+    }
+
+    #[rstest::rstest]
+    #[case::bumps_both_fees(100, 10, (1, 1), (115, 12))]
+    #[case::uses_current_estimation_if_higher(100, 10, (300, 50), (300, 50))]
+    #[case::increases_tiny_fees(1, 0, (0, 0), (2, 1))]
+    #[case::max_fee_covers_tip(10, 10, (0, 40), (40, 40))]
+    fn test_bump_fees(
+        #[case] max_fee_per_gas: u128,
+        #[case] max_priority_fee_per_gas: u128,
+        #[case] current: (u128, u128),
+        #[case] expected: (u128, u128),
+    ) {
+        // Arrange
+        let current = Eip1559Estimation {
+            max_fee_per_gas: current.0,
+            max_priority_fee_per_gas: current.1,
+        };
+
+        // Act
+        let result = WalletImplEth::bump_fees(max_fee_per_gas, max_priority_fee_per_gas, current);
+
+        // Assert
+        assert_eq!(result, expected);
+    }
 }
@@ -0,0 +1,110 @@
+//! Watch-only wallets
+//!
+//! A watch-only wallet only knows the public addresses of a wallet, so it can show the balance and the
+//! transaction history without the pin, the shares or the mnemonic. It is created from a
+//! [`WatchOnlySource`] stored for the user and rejects every attempt to sign a transaction.
+//!
+//! Watch-only wallets are supported for EVM and ERC-20 networks.
+
+use super::error::{Result, WalletError};
+use alloy::primitives::Address;
+use coins_bip32::{
+    ecdsa::VerifyingKey,
+    enc::{MainnetEncoder, XKeyEncoder},
+    xkeys::{Parent, XPub},
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The public information a watch-only wallet is created from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WatchOnlySource {
+    /// A list of EVM addresses. The first address is used as the default address.
+    EvmAddresses(Vec<String>),
+    /// The BIP-32 extended public key (`xpub`) of the EVM account at the derivation path `m/44'/60'/0'`. The
+    /// default address with index 0 and the addresses derived with `Sdk::derive_new_address` are derived from it.
+    EvmAccountXpub(String),
+}
+
+impl WatchOnlySource {
+    /// Resolves the EVM addresses of the source keyed by their address index. For an extended public key the
+    /// addresses are derived at `m/44'/60'/0'/0/{index}` for index 0 and the given `address_indices`.
+    pub(crate) fn evm_addresses(&self, address_indices: &[u32]) -> Result<BTreeMap<u32, Address>> {
+        let addresses = match self {
+            Self::EvmAddresses(addresses) => addresses
+                .iter()
+                .zip(0..)
+                .map(|(address, index)| Ok((index, Address::from_str(address)?)))
+                .collect::<Result<BTreeMap<_, _>>>()?,
+            Self::EvmAccountXpub(xpub) => {
+                let account = MainnetEncoder::xpub_from_base58(xpub)
+                    .map_err(|e| WalletError::Parse(format!("invalid extended public key: {e}")))?;
+                // the external chain of the account, non-hardened so it can be derived from the public key
+                let chain = derive_child(&account, 0)?;
+
+                std::iter::once(0)
+                    .chain(address_indices.iter().copied())
+                    .map(|index| {
+                        let xpub = derive_child(&chain, index)?;
+                        let key: &VerifyingKey = xpub.as_ref();
+                        Ok((index, alloy::signers::utils::public_key_to_address(key)))
+                    })
+                    .collect::<Result<BTreeMap<_, _>>>()?
+            }
+        };
+
+        if addresses.is_empty() {
+            return Err(WalletError::EmptyWalletAddress);
+        }
+        Ok(addresses)
+    }
+}
+
+fn derive_child(xpub: &XPub, index: u32) -> Result<XPub> {
+    xpub.derive_child(index)
+        .map_err(|e| WalletError::Parse(format!("could not derive public key with index {index}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // extended public key of the account m/44'/60'/0' of the hardhat mnemonic
+    // `test test test test test test test test test test test junk`
+    const HARDHAT_XPUB: &str = "xpub6Ce9NcJvTk36xtLSrJLZqE7wtgA5deCeYs7rSQtreh4cj6ByPtrg9sD7V2FNFLPnf8heNP3FGkeV9qwfzvZNSd54JoNXVsXFYSYwHsnJxqP";
+
+    #[test]
+    fn test_evm_addresses_from_list() {
+        let source = WatchOnlySource::EvmAddresses(vec![
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string(),
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_string(),
+        ]);
+
+        let addresses = source.evm_addresses(&[5]).unwrap();
+
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[&1].to_string(), "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+    }
+
+    #[test]
+    fn test_evm_addresses_from_xpub() {
+        let source = WatchOnlySource::EvmAccountXpub(HARDHAT_XPUB.to_string());
+
+        let addresses = source.evm_addresses(&[1]).unwrap();
+
+        assert_eq!(addresses[&0].to_string(), "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+        assert_eq!(addresses[&1].to_string(), "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+    }
+
+    #[test]
+    fn test_evm_addresses_rejects_invalid_source() {
+        let empty = WatchOnlySource::EvmAddresses(Vec::new()).evm_addresses(&[]);
+        let invalid_address = WatchOnlySource::EvmAddresses(vec!["0x1234".to_string()]).evm_addresses(&[]);
+        let invalid_xpub = WatchOnlySource::EvmAccountXpub("xpub123".to_string()).evm_addresses(&[]);
+
+        assert!(matches!(empty, Err(WalletError::EmptyWalletAddress)));
+        assert!(matches!(invalid_address, Err(WalletError::FromHexError(_))));
+        assert!(matches!(invalid_xpub, Err(WalletError::Parse(_))));
+    }
+}
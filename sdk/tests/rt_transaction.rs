@@ -99,6 +99,10 @@ async fn it_should_create_purchase_request_and_confirm_it() {
         path_prefix: Path::new(&existing_cleanup.path_prefix).into(),
         auth_provider: "standalone".to_string(),
         log_level: log::LevelFilter::Debug,
+        max_pin_attempts: 10,
+        evm_confirmations: 12,
+        http: Default::default(),
+        shares: Default::default(),
     };
 
     let mut sdk = Sdk::new(config).expect("should not fail to initialize sdk"); // set the backend url if the environment variable is set
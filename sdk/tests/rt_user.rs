@@ -1,3 +1,4 @@
+use etopay_sdk::mnemonic::MnemonicOptions;
 mod utils;
 use crate::utils::init_sdk;
 
@@ -41,7 +42,9 @@ async fn it_should_delete_user_and_wallet() {
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     sdk.delete_user(Some(&user.pin)).await.unwrap();
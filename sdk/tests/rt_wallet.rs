@@ -1,5 +1,6 @@
 mod utils;
 use etopay_sdk::{
+    mnemonic::MnemonicOptions,
     types::{
         currencies::CryptoAmount,
         newtypes::{AccessToken, EncryptionPin, PlainPassword},
@@ -24,7 +25,9 @@ async fn it_should_change_password_with_wallet() {
     assert!(!sdk.is_wallet_password_set().await.unwrap());
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     let new_password = PlainPassword::try_from_string("new_password!").unwrap();
     sdk.set_wallet_password(&user.pin, &new_password).await.unwrap();
@@ -59,7 +62,9 @@ async fn it_should_create_a_new_wallet_with_access_token() {
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
     // Act
-    let result = sdk.create_wallet_from_new_mnemonic(&user.pin).await;
+    let result = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await;
 
     // Assert
     let mnemonic = result.unwrap();
@@ -80,7 +85,9 @@ async fn it_should_create_a_new_wallet_without_access_token() {
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
     // Act
-    let result = sdk.create_wallet_from_new_mnemonic(&user.pin).await;
+    let result = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await;
 
     // Assert
     let mnemonic = result.unwrap();
@@ -96,7 +103,10 @@ async fn it_should_verify_mnemonic() {
     sdk.create_new_user(&user.username).await.unwrap();
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    let mnemonic = sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    let mnemonic = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     let result = sdk.verify_mnemonic(&user.pin, &mnemonic).await;
@@ -137,7 +147,9 @@ async fn it_should_generate_new_receiver_address() {
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     let result = sdk.generate_new_address(&user.pin).await;
@@ -157,7 +169,10 @@ async fn it_should_get_balance() {
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
-    let _ = sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    let _ = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
     // Act
     let result = sdk.get_balance(&user.pin).await;
     // Assert
@@ -176,7 +191,10 @@ async fn it_should_fail_get_balance_wrong_pin() {
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
 
-    let _ = sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    let _ = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     let wrong_pin = EncryptionPin::try_from_string("54321").unwrap();
@@ -199,7 +217,10 @@ async fn it_should_create_wallet_backup() {
     sdk.create_new_user(&user.username).await.unwrap();
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    let _ = sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    let _ = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     let backup_password = PlainPassword::try_from_string("backup_password").unwrap();
@@ -219,7 +240,10 @@ async fn it_should_reset_wallet_pin() {
     sdk.create_new_user(&user.username).await.unwrap();
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    let _ = sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    let _ = sdk
+        .create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     let new_pin = EncryptionPin::try_from_string("54321").unwrap();
@@ -296,7 +320,9 @@ async fn it_should_delete_wallet() {
     sdk.create_new_user(&user.username).await.unwrap();
     sdk.init_user(&user.username).await.unwrap();
     sdk.set_wallet_password(&user.pin, &user.password).await.unwrap();
-    sdk.create_wallet_from_new_mnemonic(&user.pin).await.unwrap();
+    sdk.create_wallet_from_new_mnemonic(&user.pin, &MnemonicOptions::default())
+        .await
+        .unwrap();
 
     // Act
     let result = sdk.delete_wallet(&user.pin).await;
@@ -282,6 +282,8 @@ pub enum ArgumentType {
     String,
     VecString,
     VecU8,
+    /// A Java object, passed to rust as global reference to call its methods later
+    Object,
     Option(Box<Self>),
 }
 
@@ -297,6 +299,7 @@ impl ArgumentType {
             ArgumentType::String => syn::parse_quote! { JString<'local> },
             ArgumentType::VecString => syn::parse_quote! { JObjectArray<'local>},
             ArgumentType::VecU8 => syn::parse_quote! { JByteArray<'local>},
+            ArgumentType::Object => syn::parse_quote! { JObject<'local>},
             ArgumentType::Option(inner) => inner.to_jni_type(), //since all objects can be null in Java, everything basically has the same signature.
         }
     }
@@ -312,6 +315,7 @@ impl ArgumentType {
             Self::String => "String",
             Self::VecString => "String[]",
             Self::VecU8 => "byte[]",
+            Self::Object => "Object",
             ArgumentType::Option(inner) => inner.to_java_type(),
         }
     }
@@ -351,6 +355,9 @@ impl ArgumentType {
             Self::VecU8 => quote! {
                 let #identity: Vec<u8> = env.convert_byte_array(&#identity).expect("Could not get Java byte array").into();
             },
+            Self::Object => quote! {
+                let #identity: jni::objects::GlobalRef = env.new_global_ref(&#identity).expect("Could not create global reference");
+            },
             Self::Option(inner) => {
                 let inner_prelude = inner.fn_prelude(identity);
 
@@ -400,6 +407,7 @@ fn parse_type(ty: &syn::Type) -> syn::Result<ArgumentType> {
                     "f64" => Ok(ArgumentType::F64),
                     "bool" => Ok(ArgumentType::Bool),
                     "String" => Ok(ArgumentType::String),
+                    "GlobalRef" => Ok(ArgumentType::Object),
                     _ => Err(syn::Error::new(
                         ident.span(),
                         format!("unsupported argument type: {}", ident),
@@ -423,7 +431,7 @@ fn parse_type(ty: &syn::Type) -> syn::Result<ArgumentType> {
 
                     // only types that are java objects can be null (i.e. not primitives)
                     match inner {
-                        ArgumentType::VecU8 | ArgumentType::String | ArgumentType::VecString => {
+                        ArgumentType::VecU8 | ArgumentType::String | ArgumentType::VecString | ArgumentType::Object => {
                             Ok(ArgumentType::Option(Box::new(inner)))
                         }
                         _ => Err(syn::Error::new(
@@ -517,7 +525,7 @@ pub fn get_public_name(attr: &syn::Attribute) -> syn::Result<Option<String>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::ReturnType;
+    use crate::{ArgumentType, ReturnType};
     use proc_macro2::TokenStream;
     use quote::quote;
 
@@ -569,4 +577,22 @@ mod tests {
             assert!(parsed.is_err());
         }
     }
+
+    #[test]
+    fn parse_object_argument() {
+        let cases: Vec<(syn::FnArg, ArgumentType)> = vec![
+            (syn::parse_quote!(value: GlobalRef), ArgumentType::Object),
+            (
+                syn::parse_quote!(value: Option<GlobalRef>),
+                ArgumentType::Option(Box::new(ArgumentType::Object)),
+            ),
+        ];
+
+        for case in cases {
+            let (name, parsed) = ArgumentType::parse(&case.0).unwrap();
+            assert_eq!(name, "value");
+            assert_eq!(parsed, case.1);
+            assert_eq!(parsed.to_java_type(), "Object");
+        }
+    }
 }
@@ -112,6 +112,23 @@ mod ffi {
         format!("{value:?}")
     }
 
+    // Object
+    pub fn objectArgument(value: GlobalRef) -> Result<String, String> {
+        // the env of the function cannot be borrowed mutably, so get a new one for this thread
+        let vm = env.get_java_vm().map_err(|e| e.to_string())?;
+        let mut env = vm.get_env().map_err(|e| e.to_string())?;
+        let string = env
+            .call_method(&value, "toString", "()Ljava/lang/String;", &[])
+            .and_then(|s| s.l())
+            .map_err(|e| e.to_string())?;
+        env.get_string(&JString::from(string))
+            .map(String::from)
+            .map_err(|e| e.to_string())
+    }
+    pub fn optionObject(value: Option<GlobalRef>) -> bool {
+        value.is_some()
+    }
+
     pub fn optionStringReturn(is_none: bool) -> Option<String> {
         if is_none {
             None
@@ -262,4 +262,17 @@ public class Tests {
 		assertEquals("string", t.optionStringReturn(false));
 		assertEquals(null, t.optionStringReturn(true));
 	}
+
+	@Test
+	public void objectArgument() throws Exception {
+		MyTestClass t = new MyTestClass();
+		assertEquals("1234", t.objectArgument(Integer.valueOf(1234)));
+	}
+
+	@Test
+	public void optionObject() throws Exception {
+		MyTestClass t = new MyTestClass();
+		assertEquals(true, t.optionObject(Integer.valueOf(1234)));
+		assertEquals(false, t.optionObject(null));
+	}
 }